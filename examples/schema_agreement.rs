@@ -27,8 +27,11 @@ async fn main() -> Result<()> {
 
     match session.await_schema_agreement().await {
         Ok(_schema_version) => println!("Schema is in agreement in time"),
-        Err(SchemaAgreementError::Timeout(_)) => {
-            println!("Schema is NOT in agreement in time")
+        Err(SchemaAgreementError::TimeoutWithLaggingNodes { lagging_nodes, .. }) => {
+            println!(
+                "Schema is NOT in agreement in time, {} node(s) lagging",
+                lagging_nodes.len()
+            )
         }
         Err(err) => bail!(err),
     };
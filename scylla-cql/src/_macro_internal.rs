@@ -7,6 +7,7 @@ pub use crate::deserialize::row::{
     BuiltinDeserializationError as BuiltinRowDeserializationError,
     BuiltinDeserializationErrorKind as BuiltinRowDeserializationErrorKind,
     BuiltinTypeCheckErrorKind as DeserBuiltinRowTypeCheckErrorKind, ColumnIterator, DeserializeRow,
+    DeserializeRowWithMode, RawColumn,
 };
 pub use crate::deserialize::value::{
     deser_error_replace_rust_name as value_deser_error_replace_rust_name,
@@ -115,6 +116,123 @@ impl<T: SerializeRowInOrder + ?Sized> SerializeRowInOrder for &T {
     }
 }
 
+/// Represents a struct that can be deserialized from the columns of a row matched by name,
+/// possibly as a part of a larger struct that flattens it
+///
+/// For now this trait is an implementation detail of `#[derive(DeserializeRow)]` when
+/// deserializing by name
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be flattened here",
+    label = "`{Self}` is not a struct that derives `DeserializeRow` with `match_by_name` flavor",
+    note = "There are two common reasons for that:
+- `{Self}` does not use `#[derive(DeserializeRow)]`
+- `{Self}` uses `#[scylla(flavor = \"enforce_order\")]`"
+)]
+pub trait DeserializeRowByName<'frame, 'metadata>: Sized {
+    /// A type that keeps track of the columns of this struct, first during type check
+    /// and then during deserialization
+    type Partial;
+
+    /// Returns a partial view of this struct, with no columns visited yet
+    fn partial() -> Self::Partial;
+
+    /// Type checks the column at the given index
+    ///
+    /// Returns whether the column corresponds to a field of this struct (or to a subfield,
+    /// in case of a flattened field).
+    fn type_check_column(
+        partial: &mut Self::Partial,
+        column_index: usize,
+        specs: &[ColumnSpec],
+    ) -> Result<bool, TypeCheckError>;
+
+    /// Appends the names of the required columns that have not been type checked
+    fn missing_columns(partial: &Self::Partial, missing: &mut Vec<&'static str>);
+
+    /// Deserializes a single column
+    ///
+    /// Returns whether the column corresponds to a field of this struct (or to a subfield,
+    /// in case of a flattened field).
+    fn deserialize_column(
+        partial: &mut Self::Partial,
+        column: &RawColumn<'frame, 'metadata>,
+    ) -> Result<bool, DeserializationError>;
+
+    /// Creates the struct out of the deserialized columns
+    ///
+    /// Type check must have been successful for the deserialized columns.
+    fn finish(partial: Self::Partial) -> Self;
+}
+
+pub mod de {
+    pub mod row {
+        use super::super::DeserializeRowByName;
+        use crate::deserialize::row::{
+            deser_error_replace_rust_name, mk_typck_err, BuiltinTypeCheckErrorKind, ColumnIterator,
+        };
+        use crate::deserialize::{DeserializationError, TypeCheckError};
+        use crate::frame::response::result::ColumnSpec;
+
+        /// Type checks all the columns of a row against a struct matching them by name
+        ///
+        /// Implementation detail of `#[derive(DeserializeRow)]` with flattened fields
+        pub fn type_check_by_name<'frame, 'metadata, T: DeserializeRowByName<'frame, 'metadata>>(
+            specs: &[ColumnSpec],
+        ) -> Result<(), TypeCheckError> {
+            let column_types_iter = || specs.iter().map(|spec| spec.typ().clone().into_owned());
+
+            let mut partial = T::partial();
+            for (column_index, spec) in specs.iter().enumerate() {
+                if !T::type_check_column(&mut partial, column_index, specs)? {
+                    return Err(mk_typck_err::<T>(
+                        column_types_iter(),
+                        BuiltinTypeCheckErrorKind::ColumnWithUnknownName {
+                            column_index,
+                            column_name: spec.name().to_owned(),
+                        },
+                    ));
+                }
+            }
+
+            let mut missing = Vec::new();
+            T::missing_columns(&partial, &mut missing);
+            if !missing.is_empty() {
+                return Err(mk_typck_err::<T>(
+                    column_types_iter(),
+                    BuiltinTypeCheckErrorKind::ValuesMissingForColumns {
+                        column_names: missing,
+                    },
+                ));
+            }
+
+            Ok(())
+        }
+
+        /// Deserializes all the columns of a row into a struct matching them by name
+        ///
+        /// Implementation detail of `#[derive(DeserializeRow)]` with flattened fields
+        pub fn deserialize_by_name<
+            'frame,
+            'metadata,
+            T: DeserializeRowByName<'frame, 'metadata>,
+        >(
+            row: ColumnIterator<'frame, 'metadata>,
+        ) -> Result<T, DeserializationError> {
+            let mut partial = T::partial();
+            for column in row {
+                let column = column.map_err(deser_error_replace_rust_name::<T>)?;
+                let used = T::deserialize_column(&mut partial, &column)?;
+                assert!(
+                    used,
+                    "Typecheck should have prevented this scenario! Unknown column name: {}",
+                    column.spec.name()
+                );
+            }
+            Ok(T::finish(partial))
+        }
+    }
+}
+
 pub mod ser {
     pub mod row {
         use super::super::{PartialSerializeRowByName, SerializeRowByName, SerializeRowInOrder};
@@ -129,10 +247,19 @@ pub mod ser {
                 writers::WrittenCellProof,
                 RowWriter, SerializationError,
             },
+            value::MaybeUnset,
         };
 
         pub use crate::serialize::row::mk_typck_err;
 
+        /// Makes a `None` value serialize as unset instead of NULL.
+        ///
+        /// Used by fields annotated with `#[scylla(none_as_unset)]`.
+        #[inline]
+        pub fn none_as_unset<T>(value: &Option<T>) -> MaybeUnset<&T> {
+            MaybeUnset::from_option(value.as_ref())
+        }
+
         /// Serializes a single value coming from type T into the writer
         ///
         /// `T` is not used for any sanity nor logical checks; it is only used when creating an
@@ -0,0 +1,454 @@
+//! Deserializing rows of arbitrary types into JSON objects.
+//!
+//! [JsonRow] can be used to deserialize rows of any result set, e.g. to serve them
+//! from a JSON API. Each row becomes a JSON object mapping column names to values.
+//! Values are converted as follows:
+//! - `boolean` and numbers become JSON booleans and numbers, except for `decimal`
+//!   and `varint`, which become strings to avoid losing precision, and for
+//!   non-finite floating point numbers, which become strings `"NaN"`, `"Infinity"`
+//!   and `"-Infinity"`,
+//! - `ascii`, `text`, `inet`, `uuid` and `timeuuid` become strings,
+//! - `blob` becomes a hex string prefixed with `0x` (see [BlobFormat]),
+//! - `timestamp` becomes an RFC 3339 string (see [TimestampFormat]),
+//! - `date` becomes a `YYYY-MM-DD` string, `time` a `HH:MM:SS.nnnnnnnnn` string,
+//!   and `duration` a string such as `1mo2d3ns`,
+//! - lists, sets, vectors and tuples become arrays,
+//! - maps and user defined types become objects; map keys which are not strings
+//!   are converted to their JSON representation, as in `SELECT JSON`,
+//! - nulls and empty values become `null`.
+//!
+//! The formatting of some values can be adjusted with [JsonFormat].
+
+use chrono_04::SecondsFormat;
+use serde_json_1::{Map, Number, Value};
+
+use super::row::{
+    deser_error_replace_rust_name, mk_deser_err, BuiltinDeserializationErrorKind, ColumnIterator,
+    DeserializeRow,
+};
+use super::value::DeserializeValue;
+use super::{DeserializationError, TypeCheckError};
+use crate::frame::response::result::ColumnSpec;
+use crate::value::{CqlTime, CqlValue};
+
+/// A row deserialized into a JSON object, mapping column names to values
+/// formatted with the default [JsonFormat].
+///
+/// See the [module documentation](self) for details of the conversion.
+///
+/// # Example
+/// ```rust
+/// # use scylla_cql::deserialize::json::JsonRow;
+/// # use scylla_cql::frame::response::result::{ColumnSpec, ColumnType, NativeType, TableSpec};
+/// # use scylla_cql::deserialize::{FrameSlice, row::{ColumnIterator, DeserializeRow}};
+/// # let specs = [ColumnSpec::borrowed("a", ColumnType::Native(NativeType::Int), TableSpec::borrowed("ks", "tab"))];
+/// # let bytes = bytes::Bytes::from_static(&[0, 0, 0, 4, 0, 0, 0, 42]);
+/// # let row = ColumnIterator::new(&specs, FrameSlice::new(&bytes));
+/// let JsonRow(object) = JsonRow::deserialize(row)?;
+/// assert_eq!(serde_json_1::Value::Object(object).to_string(), r#"{"a":42}"#);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct JsonRow(pub Map<String, Value>);
+
+impl<'frame, 'metadata> DeserializeRow<'frame, 'metadata> for JsonRow {
+    #[inline]
+    fn type_check(_specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        // All types can be converted to JSON.
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        JsonFormat::default()
+            .deserialize_row(row)
+            .map(JsonRow)
+            .map_err(deser_error_replace_rust_name::<Self>)
+    }
+}
+
+/// Format of `timestamp` values in JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimestampFormat {
+    /// An RFC 3339 string in UTC with millisecond precision, e.g. `2024-05-01T12:30:00.000Z`.
+    #[default]
+    Rfc3339,
+    /// A number of milliseconds since the unix epoch.
+    MillisSinceEpoch,
+}
+
+/// Format of `blob` values in JSON.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BlobFormat {
+    /// A string of hex digits prefixed with `0x`, e.g. `0xcafe`, as in `SELECT JSON`.
+    #[default]
+    Hex,
+    /// An array of byte values, e.g. `[202, 254]`.
+    Array,
+}
+
+/// Options of converting CQL values to JSON.
+///
+/// Use [JsonFormat::deserialize_row] to deserialize rows with non-default options:
+/// ```rust
+/// # use scylla_cql::deserialize::json::{JsonFormat, TimestampFormat};
+/// # use scylla_cql::deserialize::row::ColumnIterator;
+/// # fn check_only_compiles(row: ColumnIterator<'_, '_>) -> Result<(), Box<dyn std::error::Error>> {
+/// let format = JsonFormat::new().with_timestamp_format(TimestampFormat::MillisSinceEpoch);
+/// let object = format.deserialize_row(row)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct JsonFormat {
+    timestamp_format: TimestampFormat,
+    blob_format: BlobFormat,
+}
+
+impl JsonFormat {
+    /// Creates the default format.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the format of `timestamp` values. Default: [TimestampFormat::Rfc3339].
+    pub fn with_timestamp_format(mut self, timestamp_format: TimestampFormat) -> Self {
+        self.timestamp_format = timestamp_format;
+        self
+    }
+
+    /// Sets the format of `blob` values. Default: [BlobFormat::Hex].
+    pub fn with_blob_format(mut self, blob_format: BlobFormat) -> Self {
+        self.blob_format = blob_format;
+        self
+    }
+
+    /// Deserializes a row into a JSON object, mapping column names to values.
+    pub fn deserialize_row(
+        &self,
+        mut row: ColumnIterator<'_, '_>,
+    ) -> Result<Map<String, Value>, DeserializationError> {
+        let mut object = Map::new();
+        while let Some(column) = row.next().transpose()? {
+            let value = <Option<CqlValue>>::deserialize(column.spec.typ(), column.slice).map_err(
+                |err| {
+                    mk_deser_err::<JsonRow>(
+                        BuiltinDeserializationErrorKind::ColumnDeserializationFailed {
+                            column_index: column.index,
+                            column_name: column.spec.name().to_owned(),
+                            err,
+                        },
+                    )
+                },
+            )?;
+            object.insert(
+                column.spec.name().to_owned(),
+                value.map_or(Value::Null, |value| self.value_to_json(&value)),
+            );
+        }
+        Ok(object)
+    }
+
+    /// Converts a CQL value to JSON.
+    pub fn value_to_json(&self, value: &CqlValue) -> Value {
+        match value {
+            CqlValue::Ascii(s) | CqlValue::Text(s) => Value::String(s.clone()),
+            CqlValue::Boolean(b) => Value::Bool(*b),
+            CqlValue::Blob(bytes) => match self.blob_format {
+                BlobFormat::Hex => Value::String(hex_string(bytes)),
+                BlobFormat::Array => Value::Array(bytes.iter().map(|b| (*b).into()).collect()),
+            },
+            CqlValue::Counter(c) => c.0.into(),
+            CqlValue::Decimal(d) => {
+                let (bytes, scale) = d.as_signed_be_bytes_slice_and_exponent();
+                Value::String(decimal_string(bytes, scale))
+            }
+            CqlValue::Varint(v) => Value::String(decimal_string(v.as_signed_bytes_be_slice(), 0)),
+            CqlValue::Date(d) => match d.try_to_chrono_04_naive_date() {
+                Ok(date) => Value::String(date.to_string()),
+                // Days since the unix epoch, as chrono cannot represent the date.
+                Err(_) => (i64::from(d.0) - (1 << 31)).into(),
+            },
+            CqlValue::Double(d) => float_to_json(*d),
+            CqlValue::Float(f) => float_to_json(f64::from(*f)),
+            CqlValue::Duration(d) => {
+                Value::String(format!("{}mo{}d{}ns", d.months, d.days, d.nanoseconds))
+            }
+            CqlValue::Empty => Value::Null,
+            CqlValue::Int(i) => (*i).into(),
+            CqlValue::BigInt(i) => (*i).into(),
+            CqlValue::SmallInt(i) => (*i).into(),
+            CqlValue::TinyInt(i) => (*i).into(),
+            CqlValue::Timestamp(ts) => {
+                match (self.timestamp_format, ts.try_to_chrono_04_datetime_utc()) {
+                    (TimestampFormat::Rfc3339, Ok(datetime)) => {
+                        Value::String(datetime.to_rfc3339_opts(SecondsFormat::Millis, true))
+                    }
+                    _ => ts.0.into(),
+                }
+            }
+            CqlValue::Time(CqlTime(t)) => Value::String(format!(
+                "{:02}:{:02}:{:02}.{:09}",
+                t / 3_600_000_000_000,
+                t / 60_000_000_000 % 60,
+                t / 1_000_000_000 % 60,
+                t % 1_000_000_000,
+            )),
+            CqlValue::Inet(addr) => Value::String(addr.to_string()),
+            CqlValue::Uuid(uuid) => Value::String(uuid.to_string()),
+            CqlValue::Timeuuid(uuid) => Value::String(uuid.to_string()),
+            CqlValue::List(elems) | CqlValue::Set(elems) | CqlValue::Vector(elems) => {
+                Value::Array(elems.iter().map(|elem| self.value_to_json(elem)).collect())
+            }
+            CqlValue::Tuple(elems) => Value::Array(
+                elems
+                    .iter()
+                    .map(|elem| self.optional_value_to_json(elem.as_ref()))
+                    .collect(),
+            ),
+            CqlValue::Map(entries) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        let key = match self.value_to_json(key) {
+                            Value::String(key) => key,
+                            key => key.to_string(),
+                        };
+                        (key, self.value_to_json(value))
+                    })
+                    .collect(),
+            ),
+            CqlValue::UserDefinedType { fields, .. } => Value::Object(
+                fields
+                    .iter()
+                    .map(|(name, value)| {
+                        (name.clone(), self.optional_value_to_json(value.as_ref()))
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    fn optional_value_to_json(&self, value: Option<&CqlValue>) -> Value {
+        value.map_or(Value::Null, |value| self.value_to_json(value))
+    }
+}
+
+fn float_to_json(value: f64) -> Value {
+    match Number::from_f64(value) {
+        Some(number) => Value::Number(number),
+        None if value.is_nan() => Value::String("NaN".to_owned()),
+        None if value > 0.0 => Value::String("Infinity".to_owned()),
+        None => Value::String("-Infinity".to_owned()),
+    }
+}
+
+fn hex_string(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(2 + 2 * bytes.len());
+    out.push_str("0x");
+    for byte in bytes {
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+// Formats `unscaled * 10^(-scale)`, where `unscaled` is given as a big-endian
+// two's complement integer, in the decimal notation.
+fn decimal_string(unscaled: &[u8], scale: i32) -> String {
+    let negative = unscaled.first().is_some_and(|b| b & 0x80 != 0);
+    // Magnitude as big-endian base-256 digits.
+    let mut magnitude = unscaled.to_vec();
+    if negative {
+        // Two's complement negation: invert the bits and add one.
+        for byte in magnitude.iter_mut() {
+            *byte = !*byte;
+        }
+        for byte in magnitude.iter_mut().rev() {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            if !overflow {
+                break;
+            }
+        }
+    }
+
+    // Repeatedly divide the magnitude by 10, collecting the remainders.
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|b| *b != 0) {
+        let mut remainder = 0_u32;
+        for byte in magnitude.iter_mut() {
+            let current = (remainder << 8) | u32::from(*byte);
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    digits.reverse();
+    let mut digits = String::from_utf8(digits).expect("digits are ASCII");
+
+    if scale > 0 {
+        let scale = scale as usize;
+        if digits.len() <= scale {
+            digits.insert_str(0, &"0".repeat(scale - digits.len() + 1));
+        }
+        digits.insert(digits.len() - scale, '.');
+    } else if scale < 0 && digits != "0" {
+        digits.push_str(&"0".repeat(scale.unsigned_abs() as usize));
+    }
+    if negative {
+        digits.insert(0, '-');
+    }
+    digits
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use serde_json_1::json;
+
+    use super::{decimal_string, BlobFormat, JsonFormat, JsonRow, TimestampFormat};
+    use crate::deserialize::row::{ColumnIterator, DeserializeRow};
+    use crate::deserialize::FrameSlice;
+    use crate::frame::response::result::{
+        CollectionType, ColumnSpec, ColumnType, NativeType, TableSpec, UserDefinedType,
+    };
+    use crate::serialize::value::SerializeValue;
+    use crate::serialize::writers::CellWriter;
+    use crate::value::{CqlDate, CqlDecimal, CqlTimestamp, CqlValue, CqlVarint};
+
+    #[test]
+    fn decimal_formatting() {
+        assert_eq!(decimal_string(&[], 0), "0");
+        assert_eq!(decimal_string(&[0x00], 3), "0.000");
+        assert_eq!(decimal_string(&[0x04, 0xd2], 2), "12.34");
+        assert_eq!(decimal_string(&[0xfb, 0x2e], 2), "-12.34");
+        assert_eq!(decimal_string(&[0x05], 3), "0.005");
+        assert_eq!(decimal_string(&[0xff], 0), "-1");
+        assert_eq!(decimal_string(&[0x80], 0), "-128");
+        assert_eq!(decimal_string(&[0x07], -2), "700");
+        assert_eq!(
+            decimal_string(&[0x01, 0, 0, 0, 0, 0, 0, 0, 0], 0),
+            "18446744073709551616"
+        );
+    }
+
+    #[test]
+    fn value_conversion() {
+        let format = JsonFormat::new();
+        let value = CqlValue::Map(vec![
+            (
+                CqlValue::Int(1),
+                CqlValue::Tuple(vec![
+                    Some(CqlValue::Blob(vec![0xca, 0xfe])),
+                    None,
+                    Some(CqlValue::Double(f64::NAN)),
+                ]),
+            ),
+            (
+                CqlValue::Int(2),
+                CqlValue::Tuple(vec![
+                    Some(CqlValue::Decimal(
+                        CqlDecimal::from_signed_be_bytes_and_exponent(vec![0x04, 0xd2], 2),
+                    )),
+                    Some(CqlValue::Varint(CqlVarint::from_signed_bytes_be(vec![
+                        0xff,
+                    ]))),
+                    Some(CqlValue::Date(CqlDate((1 << 31) + 1))),
+                ]),
+            ),
+        ]);
+        assert_eq!(
+            format.value_to_json(&value),
+            json!({"1": ["0xcafe", null, "NaN"], "2": ["12.34", "-1", "1970-01-02"]})
+        );
+
+        let udt = CqlValue::UserDefinedType {
+            keyspace: "ks".to_owned(),
+            name: "udt".to_owned(),
+            fields: vec![
+                (
+                    "at".to_owned(),
+                    Some(CqlValue::Timestamp(CqlTimestamp(1_500_000_000_123))),
+                ),
+                (
+                    "addr".to_owned(),
+                    Some(CqlValue::Inet(IpAddr::V4(Ipv4Addr::LOCALHOST))),
+                ),
+                ("missing".to_owned(), None),
+            ],
+        };
+        assert_eq!(
+            format.value_to_json(&udt),
+            json!({"at": "2017-07-14T02:40:00.123Z", "addr": "127.0.0.1", "missing": null})
+        );
+
+        let format = format
+            .with_timestamp_format(TimestampFormat::MillisSinceEpoch)
+            .with_blob_format(BlobFormat::Array);
+        assert_eq!(
+            format.value_to_json(&CqlValue::Timestamp(CqlTimestamp(1_500_000_000_123))),
+            json!(1_500_000_000_123_i64)
+        );
+        assert_eq!(
+            format.value_to_json(&CqlValue::Blob(vec![0xca, 0xfe])),
+            json!([202, 254])
+        );
+    }
+
+    fn serialize(value: &impl SerializeValue, typ: &ColumnType, out: &mut Vec<u8>) {
+        let _proof = value.serialize(typ, CellWriter::new(out)).unwrap();
+    }
+
+    #[test]
+    fn row_deserialization() {
+        let table = TableSpec::borrowed("ks", "tab");
+        let list_type = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Text))),
+        };
+        let udt_type = ColumnType::UserDefinedType {
+            frozen: false,
+            definition: Arc::new(UserDefinedType {
+                name: "udt".into(),
+                keyspace: "ks".into(),
+                field_types: vec![("a".into(), ColumnType::Native(NativeType::BigInt))],
+            }),
+        };
+        let specs = [
+            ColumnSpec::borrowed("id", ColumnType::Native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("tags", list_type.clone(), table.clone()),
+            ColumnSpec::borrowed("details", udt_type.clone(), table.clone()),
+            ColumnSpec::borrowed("nothing", ColumnType::Native(NativeType::Text), table),
+        ];
+
+        let mut bytes = Vec::new();
+        serialize(&7_i32, &specs[0].typ().clone(), &mut bytes);
+        serialize(&vec!["x", "y"], &list_type, &mut bytes);
+        serialize(
+            &CqlValue::UserDefinedType {
+                keyspace: "ks".to_owned(),
+                name: "udt".to_owned(),
+                fields: vec![("a".to_owned(), Some(CqlValue::BigInt(-5)))],
+            },
+            &udt_type,
+            &mut bytes,
+        );
+        serialize(&None::<String>, &specs[3].typ().clone(), &mut bytes);
+
+        let bytes = Bytes::from(bytes);
+        let row = ColumnIterator::new(&specs, FrameSlice::new(&bytes));
+        let JsonRow(object) = JsonRow::deserialize(row).unwrap();
+        assert_eq!(
+            serde_json_1::Value::Object(object),
+            json!({"id": 7, "tags": ["x", "y"], "details": {"a": -5}, "nothing": null})
+        );
+    }
+}
@@ -1,6 +1,8 @@
 #![doc = include_str!("README.md")]
 
 pub mod frame_slice;
+#[cfg(feature = "serde_json-1")]
+pub mod json;
 pub mod result;
 pub mod row;
 pub mod value;
@@ -13,6 +13,7 @@ use crate::frame::response::result::{ColumnSpec, ColumnType};
 use crate::value::{CqlValue, Row};
 
 /// Represents a raw, unparsed column value.
+#[derive(Clone, Debug)]
 #[non_exhaustive]
 pub struct RawColumn<'frame, 'metadata> {
     /// Index of the column in the row.
@@ -133,6 +134,154 @@ impl<'frame, 'metadata> DeserializeRow<'frame, 'metadata> for ColumnIterator<'fr
     }
 }
 
+impl<'frame, 'metadata> RawColumn<'frame, 'metadata> {
+    /// Returns the CQL type of the column.
+    #[inline]
+    pub fn typ(&self) -> &'metadata ColumnType<'metadata> {
+        self.spec.typ()
+    }
+
+    /// Returns the serialized value of the column, or `None` if the column is null.
+    #[inline]
+    pub fn raw_bytes(&self) -> Option<&'frame [u8]> {
+        self.slice.map(|slice| slice.as_slice())
+    }
+
+    /// Type checks and deserializes the value of the column.
+    pub fn deserialize<T>(&self) -> Result<T, ColumnAccessError>
+    where
+        T: DeserializeValue<'frame, 'metadata>,
+    {
+        T::type_check(self.typ())?;
+        Ok(T::deserialize(self.typ(), self.slice)?)
+    }
+}
+
+/// A row whose columns are kept in the serialized form, to be deserialized on demand.
+///
+/// Useful for wide rows of which only a few columns are needed, or whose columns
+/// are to be passed through untouched (see [RawColumn::raw_bytes]). Columns are
+/// only split upon deserialization of the row; their values are not parsed until
+/// [RawRow::get] or [RawColumn::deserialize] is called.
+///
+/// # Example
+/// ```rust
+/// # use scylla_cql::deserialize::row::{ColumnAccessError, RawRow};
+/// fn process(row: &RawRow<'_, '_>) -> Result<(), ColumnAccessError> {
+///     let id: i32 = row.get_by_name("id")?;
+///     let payload = row.column_by_name("payload").and_then(|c| c.raw_bytes());
+///     println!("{id}: {payload:?}");
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct RawRow<'frame, 'metadata> {
+    columns: Vec<RawColumn<'frame, 'metadata>>,
+}
+
+impl<'frame, 'metadata> RawRow<'frame, 'metadata> {
+    /// Returns all columns of the row, in the order of the result metadata.
+    #[inline]
+    pub fn columns(&self) -> &[RawColumn<'frame, 'metadata>] {
+        &self.columns
+    }
+
+    /// Returns the number of columns in the row.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `true` if the row has no columns.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the column at the given index.
+    #[inline]
+    pub fn column(&self, index: usize) -> Option<&RawColumn<'frame, 'metadata>> {
+        self.columns.get(index)
+    }
+
+    /// Returns the first column of the given name.
+    pub fn column_by_name(&self, name: &str) -> Option<&RawColumn<'frame, 'metadata>> {
+        self.columns
+            .iter()
+            .find(|column| column.spec.name() == name)
+    }
+
+    /// Type checks and deserializes the value of the column at the given index.
+    pub fn get<T>(&self, index: usize) -> Result<T, ColumnAccessError>
+    where
+        T: DeserializeValue<'frame, 'metadata>,
+    {
+        self.column(index)
+            .ok_or(ColumnAccessError::IndexOutOfRange {
+                index,
+                column_count: self.len(),
+            })?
+            .deserialize()
+    }
+
+    /// Type checks and deserializes the value of the first column of the given name.
+    pub fn get_by_name<T>(&self, name: &str) -> Result<T, ColumnAccessError>
+    where
+        T: DeserializeValue<'frame, 'metadata>,
+    {
+        self.column_by_name(name)
+            .ok_or_else(|| ColumnAccessError::NoColumnWithName {
+                name: name.to_owned(),
+            })?
+            .deserialize()
+    }
+}
+
+impl<'frame, 'metadata> DeserializeRow<'frame, 'metadata> for RawRow<'frame, 'metadata> {
+    #[inline]
+    fn type_check(_specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        // Columns are type checked when they are accessed.
+        Ok(())
+    }
+
+    #[inline]
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        let columns = row
+            .collect::<Result<_, _>>()
+            .map_err(deser_error_replace_rust_name::<Self>)?;
+        Ok(Self { columns })
+    }
+}
+
+/// An error returned when accessing a column of a [RawRow].
+#[derive(Debug, Error, Clone)]
+#[non_exhaustive]
+pub enum ColumnAccessError {
+    /// The row has fewer columns than the requested index.
+    #[error("Column index {index} out of range for a row of {column_count} columns")]
+    IndexOutOfRange {
+        /// The requested index.
+        index: usize,
+        /// The number of columns in the row.
+        column_count: usize,
+    },
+
+    /// The row has no column of the requested name.
+    #[error("No column named {name} in the row")]
+    NoColumnWithName {
+        /// The requested name.
+        name: String,
+    },
+
+    /// Type check failed
+    #[error("Type check failed: {0}")]
+    TypeCheckFailed(#[from] TypeCheckError),
+
+    /// Deserialization failed
+    #[error("Deserialization failed: {0}")]
+    DeserializationFailed(#[from] DeserializationError),
+}
+
 make_error_replace_rust_name!(
     pub(self),
     _typck_error_replace_rust_name,
@@ -259,6 +408,97 @@ impl_tuple_multiple!(
     t0, t1, t2, t3, t4, t5, t6, t7, t8, t9, t10, t11, t12, t13, t14, t15
 );
 
+// column matching mode chosen at call time
+
+/// A row type whose matching of columns to fields can be chosen at call time,
+/// by wrapping it in [Strict] or [Lenient].
+///
+/// Regardless of the flavor chosen at derive time, a struct deriving `DeserializeRow`
+/// implements this trait, so that the same struct can serve both narrow queries
+/// (selecting exactly the struct's columns) and wide ones (e.g. `SELECT *`).
+///
+/// Errors returned by the methods are attributed to the wrapped type itself.
+pub trait DeserializeRowWithMode<'frame, 'metadata>: DeserializeRow<'frame, 'metadata> {
+    /// Checks that the columns match the type's fields exactly: in order, by name and by count.
+    fn type_check_strict(specs: &[ColumnSpec]) -> Result<(), TypeCheckError>;
+
+    /// Deserializes a row that passed [DeserializeRowWithMode::type_check_strict].
+    fn deserialize_strict(
+        row: ColumnIterator<'frame, 'metadata>,
+    ) -> Result<Self, DeserializationError>;
+
+    /// Checks that the columns contain all of the type's fields, matching them by name.
+    /// Columns not corresponding to any field are allowed and are ignored.
+    fn type_check_lenient(specs: &[ColumnSpec]) -> Result<(), TypeCheckError>;
+
+    /// Deserializes a row that passed [DeserializeRowWithMode::type_check_lenient].
+    fn deserialize_lenient(
+        row: ColumnIterator<'frame, 'metadata>,
+    ) -> Result<Self, DeserializationError>;
+}
+
+/// Deserializes the wrapped row type, requiring the columns to match its fields exactly:
+/// in order, by name and by count.
+///
+/// The wrapper can be used e.g. as `QueryRowsResult::rows::<Strict<MyRow>>()`,
+/// and removed with [Strict::into_inner].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Strict<T>(pub T);
+
+impl<T> Strict<T> {
+    /// Returns the deserialized row.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'frame, 'metadata, T> DeserializeRow<'frame, 'metadata> for Strict<T>
+where
+    T: DeserializeRowWithMode<'frame, 'metadata>,
+{
+    #[inline]
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        T::type_check_strict(specs)
+    }
+
+    #[inline]
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        T::deserialize_strict(row).map(Self)
+    }
+}
+
+/// Deserializes the wrapped row type, matching columns to its fields by name
+/// and ignoring columns which do not correspond to any field.
+///
+/// The wrapper can be used e.g. as `QueryRowsResult::rows::<Lenient<MyRow>>()`,
+/// and removed with [Lenient::into_inner].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Lenient<T>(pub T);
+
+impl<T> Lenient<T> {
+    /// Returns the deserialized row.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<'frame, 'metadata, T> DeserializeRow<'frame, 'metadata> for Lenient<T>
+where
+    T: DeserializeRowWithMode<'frame, 'metadata>,
+{
+    #[inline]
+    fn type_check(specs: &[ColumnSpec]) -> Result<(), TypeCheckError> {
+        T::type_check_lenient(specs)
+    }
+
+    #[inline]
+    fn deserialize(row: ColumnIterator<'frame, 'metadata>) -> Result<Self, DeserializationError> {
+        T::deserialize_lenient(row).map(Self)
+    }
+}
+
 // Error facilities
 
 /// Failed to type check incoming result column types again given Rust type,
@@ -517,6 +757,82 @@ fn _test_struct_deserialization_name_check_skip_requires_enforce_order() {}
 /// ```
 fn _test_struct_deserialization_skip_name_check_conflicts_with_rename() {}
 
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql, flavor = "enforce_order", skip_name_checks, rename_all = "camelCase")]
+/// struct TestRow {
+///     a: i32,
+/// }
+/// ```
+fn _test_struct_deserialization_skip_name_check_conflicts_with_rename_all() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql, flavor = "enforce_order")]
+/// struct TestRow {
+///     a: i32,
+///     #[scylla(default)]
+///     b: i32,
+/// }
+/// ```
+fn _test_struct_deserialization_default_requires_match_by_name() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql)]
+/// struct Inner {
+///     b: i32,
+/// }
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql, flavor = "enforce_order")]
+/// struct TestRow {
+///     a: i32,
+///     #[scylla(flatten)]
+///     inner: Inner,
+/// }
+/// ```
+fn _test_struct_deserialization_flatten_requires_match_by_name() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql, flavor = "enforce_order")]
+/// struct Inner {
+///     b: i32,
+/// }
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql)]
+/// struct TestRow {
+///     a: i32,
+///     #[scylla(flatten)]
+///     inner: Inner,
+/// }
+/// ```
+fn _test_struct_deserialization_flattened_struct_requires_match_by_name() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql)]
+/// struct Inner {
+///     b: i32,
+/// }
+///
+/// #[derive(scylla_macros::DeserializeRow)]
+/// #[scylla(crate = scylla_cql)]
+/// struct TestRow {
+///     a: i32,
+///     #[scylla(flatten, rename = "c")]
+///     inner: Inner,
+/// }
+/// ```
+fn _test_struct_deserialization_flatten_conflicts_with_rename() {}
+
 /// ```compile_fail
 ///
 /// #[derive(scylla_macros::DeserializeRow)]
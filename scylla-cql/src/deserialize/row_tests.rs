@@ -8,7 +8,9 @@ use crate::frame::response::result::{ColumnSpec, ColumnType, NativeType, TableSp
 
 use super::super::tests::{serialize_cells, spec};
 use super::{BuiltinDeserializationError, ColumnIterator, CqlValue, DeserializeRow, Row};
-use super::{BuiltinTypeCheckError, BuiltinTypeCheckErrorKind};
+use super::{BuiltinTypeCheckError, BuiltinTypeCheckErrorKind, Lenient, Strict};
+use super::{ColumnAccessError, RawRow};
+use crate::value::Counter;
 
 #[test]
 fn test_tuple_deserialization() {
@@ -81,6 +83,49 @@ fn test_deserialization_as_column_iterator() {
     assert!(iter.next().is_none());
 }
 
+#[test]
+fn test_deserialization_as_raw_row() {
+    let col_specs = [
+        spec("i1", ColumnType::Native(NativeType::Int)),
+        spec("i2", ColumnType::Native(NativeType::Text)),
+        spec("i3", ColumnType::Native(NativeType::Counter)),
+    ];
+    let serialized_values = serialize_cells([val_int(123), val_str("ScyllaDB"), None]);
+    let row = deserialize::<RawRow>(&col_specs, &serialized_values).unwrap();
+
+    assert_eq!(row.len(), 3);
+    assert_eq!(row.get::<i32>(0).unwrap(), 123);
+    assert_eq!(row.get_by_name::<&str>("i2").unwrap(), "ScyllaDB");
+    assert_eq!(row.get_by_name::<Option<Counter>>("i3").unwrap(), None);
+
+    let col2 = row.column_by_name("i2").unwrap();
+    assert_eq!(col2.index, 1);
+    assert_eq!(col2.typ(), &ColumnType::Native(NativeType::Text));
+    assert_eq!(col2.raw_bytes(), Some("ScyllaDB".as_bytes()));
+    assert_eq!(row.column(2).unwrap().raw_bytes(), None);
+
+    assert_matches!(
+        row.get::<i32>(3),
+        Err(ColumnAccessError::IndexOutOfRange {
+            index: 3,
+            column_count: 3
+        })
+    );
+    assert_matches!(
+        row.get_by_name::<i32>("i4"),
+        Err(ColumnAccessError::NoColumnWithName { name }) if name == "i4"
+    );
+    assert_matches!(
+        row.get::<String>(0),
+        Err(ColumnAccessError::TypeCheckFailed(_))
+    );
+    assert_matches!(
+        // A null value can only be deserialized as an `Option`.
+        row.get::<Counter>(2),
+        Err(ColumnAccessError::DeserializationFailed(_))
+    );
+}
+
 // Do not remove. It's not used in tests but we keep it here to check that
 // we properly ignore warnings about unused variables, unnecessary `mut`s
 // etc. that usually pop up when generating code for empty structs.
@@ -193,6 +238,88 @@ fn test_struct_deserialization_strict_ordering() {
     MyRow::type_check(specs).unwrap_err();
 }
 
+#[test]
+fn test_struct_deserialization_mode_chosen_at_call_time() {
+    #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = "crate")]
+    struct MyRow<'a> {
+        a: &'a str,
+        b: Option<i32>,
+        #[scylla(skip)]
+        c: String,
+    }
+
+    #[derive(DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = "crate", flavor = "enforce_order")]
+    struct MyOrderedRow<'a> {
+        a: &'a str,
+        b: Option<i32>,
+    }
+
+    let expected = MyRow {
+        a: "abc",
+        b: Some(123),
+        c: String::new(),
+    };
+
+    // Columns in order, with an extra one, as with `SELECT *`.
+    let wide_specs = &[
+        spec("a", ColumnType::Native(NativeType::Text)),
+        spec("x", ColumnType::Native(NativeType::Int)),
+        spec("b", ColumnType::Native(NativeType::Int)),
+    ];
+    let wide_byts = serialize_cells([val_str("abc"), val_int(7), val_int(123)]);
+    MyRow::type_check(wide_specs).unwrap_err();
+    Strict::<MyRow>::type_check(wide_specs).unwrap_err();
+    let row = deserialize::<Lenient<MyRow<'_>>>(wide_specs, &wide_byts).unwrap();
+    assert_eq!(row.into_inner(), expected);
+    let row = deserialize::<Lenient<MyOrderedRow<'_>>>(wide_specs, &wide_byts).unwrap();
+    assert_eq!(
+        row.0,
+        MyOrderedRow {
+            a: "abc",
+            b: Some(123)
+        }
+    );
+
+    // Columns out of order are only accepted leniently.
+    let reordered_specs = &[
+        spec("b", ColumnType::Native(NativeType::Int)),
+        spec("a", ColumnType::Native(NativeType::Text)),
+    ];
+    let reordered_byts = serialize_cells([val_int(123), val_str("abc")]);
+    let err = Strict::<MyRow>::type_check(reordered_specs).unwrap_err();
+    assert_matches!(
+        get_typck_err_inner(err.0.as_ref()).kind,
+        BuiltinTypeCheckErrorKind::ColumnNameMismatch { .. }
+    );
+    let row = deserialize::<Lenient<MyRow<'_>>>(reordered_specs, &reordered_byts).unwrap();
+    assert_eq!(row.0, expected);
+
+    // Exactly matching columns are accepted in both modes.
+    let narrow_specs = &[
+        spec("a", ColumnType::Native(NativeType::Text)),
+        spec("b", ColumnType::Native(NativeType::Int)),
+    ];
+    let narrow_byts = serialize_cells([val_str("abc"), val_int(123)]);
+    let row = deserialize::<Strict<MyRow<'_>>>(narrow_specs, &narrow_byts).unwrap();
+    assert_eq!(row.0, expected);
+    let row = deserialize::<Lenient<MyRow<'_>>>(narrow_specs, &narrow_byts).unwrap();
+    assert_eq!(row.0, expected);
+
+    // Missing columns are rejected in both modes.
+    let missing_specs = &[
+        spec("a", ColumnType::Native(NativeType::Text)),
+        spec("x", ColumnType::Native(NativeType::Int)),
+    ];
+    Strict::<MyRow>::type_check(missing_specs).unwrap_err();
+    let err = Lenient::<MyRow>::type_check(missing_specs).unwrap_err();
+    assert_matches!(
+        get_typck_err_inner(err.0.as_ref()).kind,
+        BuiltinTypeCheckErrorKind::ValuesMissingForColumns { .. }
+    );
+}
+
 #[test]
 fn test_struct_deserialization_no_name_check() {
     #[derive(DeserializeRow, PartialEq, Eq, Debug)]
@@ -268,6 +395,252 @@ fn test_struct_deserialization_cross_rename_fields() {
     }
 }
 
+#[test]
+fn test_struct_deserialization_rename_all_fields() {
+    #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate, rename_all = "SCREAMING_SNAKE_CASE")]
+    struct TestRow {
+        user_id: i32,
+        #[scylla(rename = "name")]
+        user_name: String,
+    }
+
+    let row_bytes = serialize_cells(["Ala".as_bytes(), &42_i32.to_be_bytes()].map(Some));
+    let specs = [
+        spec("name", ColumnType::Native(NativeType::Text)),
+        spec("USER_ID", ColumnType::Native(NativeType::Int)),
+    ];
+
+    let row = deserialize::<TestRow>(&specs, &row_bytes).unwrap();
+    assert_eq!(
+        row,
+        TestRow {
+            user_id: 42,
+            user_name: "Ala".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_struct_deserialization_field_defaults() {
+    fn default_port() -> i32 {
+        9042
+    }
+
+    #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate)]
+    struct TestRow {
+        host: String,
+        #[scylla(default = "default_port")]
+        port: i32,
+        #[scylla(default)]
+        weight: Option<i32>,
+    }
+
+    // All columns are present
+    {
+        let row_bytes = serialize_cells(
+            [
+                &19042_i32.to_be_bytes()[..],
+                b"localhost",
+                &7_i32.to_be_bytes(),
+            ]
+            .map(Some),
+        );
+        let specs = [
+            spec("port", ColumnType::Native(NativeType::Int)),
+            spec("host", ColumnType::Native(NativeType::Text)),
+            spec("weight", ColumnType::Native(NativeType::Int)),
+        ];
+
+        let row = deserialize::<TestRow>(&specs, &row_bytes).unwrap();
+        assert_eq!(
+            row,
+            TestRow {
+                host: "localhost".to_owned(),
+                port: 19042,
+                weight: Some(7),
+            }
+        );
+    }
+
+    // Columns of fields with `default` are missing
+    {
+        let row_bytes = serialize_cells([Some(b"localhost")]);
+        let specs = [spec("host", ColumnType::Native(NativeType::Text))];
+
+        let row = deserialize::<TestRow>(&specs, &row_bytes).unwrap();
+        assert_eq!(
+            row,
+            TestRow {
+                host: "localhost".to_owned(),
+                port: 9042,
+                weight: None,
+            }
+        );
+
+        // `Strict` still requires all the columns.
+        let specs = [spec("host", ColumnType::Native(NativeType::Text))];
+        Strict::<TestRow>::type_check(&specs).unwrap_err();
+    }
+
+    // Columns of fields without `default` are still required
+    {
+        let specs = [spec("port", ColumnType::Native(NativeType::Int))];
+        TestRow::type_check(&specs).unwrap_err();
+    }
+}
+
+#[test]
+fn test_struct_deserialization_flattened_fields() {
+    #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate)]
+    struct Audit<'a> {
+        created_by: &'a str,
+        #[scylla(default)]
+        version: Option<i32>,
+    }
+
+    #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate)]
+    struct Named<'a> {
+        name: &'a str,
+        #[scylla(flatten)]
+        audit: Audit<'a>,
+    }
+
+    #[derive(scylla_macros::DeserializeRow, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate)]
+    struct TestRow<'a> {
+        id: i32,
+        #[scylla(flatten)]
+        named: Named<'a>,
+        #[scylla(skip)]
+        note: String,
+    }
+
+    // Columns are matched against the fields of nested structs, in any order
+    {
+        let row_bytes = serialize_cells(
+            [
+                &b"alice"[..],
+                &3_i32.to_be_bytes(),
+                b"users",
+                &42_i32.to_be_bytes(),
+            ]
+            .map(Some),
+        );
+        let specs = [
+            spec("created_by", ColumnType::Native(NativeType::Text)),
+            spec("version", ColumnType::Native(NativeType::Int)),
+            spec("name", ColumnType::Native(NativeType::Text)),
+            spec("id", ColumnType::Native(NativeType::Int)),
+        ];
+
+        let row = deserialize::<TestRow>(&specs, &row_bytes).unwrap();
+        assert_eq!(
+            row,
+            TestRow {
+                id: 42,
+                named: Named {
+                    name: "users",
+                    audit: Audit {
+                        created_by: "alice",
+                        version: Some(3),
+                    },
+                },
+                note: String::new(),
+            }
+        );
+    }
+
+    // Nested fields with `default` may be missing
+    {
+        let row_bytes = serialize_cells([
+            Some(&42_i32.to_be_bytes()[..]),
+            Some(b"users"),
+            Some(b"bob"),
+        ]);
+        let specs = [
+            spec("id", ColumnType::Native(NativeType::Int)),
+            spec("name", ColumnType::Native(NativeType::Text)),
+            spec("created_by", ColumnType::Native(NativeType::Text)),
+        ];
+
+        let row = deserialize::<TestRow>(&specs, &row_bytes).unwrap();
+        assert_eq!(
+            row.named.audit,
+            Audit {
+                created_by: "bob",
+                version: None,
+            }
+        );
+    }
+
+    // Missing nested columns are reported
+    {
+        let specs = [
+            spec("id", ColumnType::Native(NativeType::Int)),
+            spec("version", ColumnType::Native(NativeType::Int)),
+        ];
+        let err = TestRow::type_check(&specs).unwrap_err();
+        let err = get_typck_err_inner(err.0.as_ref());
+        let BuiltinTypeCheckErrorKind::ValuesMissingForColumns { column_names } = &err.kind else {
+            panic!("unexpected error kind: {:?}", err.kind)
+        };
+        assert_eq!(column_names, &["name", "created_by"]);
+    }
+
+    // Unknown and duplicated columns are rejected
+    {
+        let specs = [
+            spec("id", ColumnType::Native(NativeType::Int)),
+            spec("name", ColumnType::Native(NativeType::Text)),
+            spec("created_by", ColumnType::Native(NativeType::Text)),
+            spec("owner", ColumnType::Native(NativeType::Text)),
+        ];
+        let err = TestRow::type_check(&specs).unwrap_err();
+        let err = get_typck_err_inner(err.0.as_ref());
+        assert_matches!(
+            &err.kind,
+            BuiltinTypeCheckErrorKind::ColumnWithUnknownName { column_index: 3, column_name }
+                if column_name == "owner"
+        );
+
+        let specs = [
+            spec("id", ColumnType::Native(NativeType::Int)),
+            spec("name", ColumnType::Native(NativeType::Text)),
+            spec("created_by", ColumnType::Native(NativeType::Text)),
+            spec("created_by", ColumnType::Native(NativeType::Text)),
+        ];
+        let err = TestRow::type_check(&specs).unwrap_err();
+        let err = get_typck_err_inner(err.0.as_ref());
+        assert_matches!(
+            err.kind,
+            BuiltinTypeCheckErrorKind::DuplicatedColumn {
+                column_index: 3,
+                column_name: "created_by"
+            }
+        );
+    }
+
+    // Nested columns are type checked
+    {
+        let specs = [
+            spec("id", ColumnType::Native(NativeType::Int)),
+            spec("name", ColumnType::Native(NativeType::Text)),
+            spec("created_by", ColumnType::Native(NativeType::Int)),
+        ];
+        let err = TestRow::type_check(&specs).unwrap_err();
+        let err = get_typck_err_inner(err.0.as_ref());
+        assert_matches!(
+            &err.kind,
+            BuiltinTypeCheckErrorKind::ColumnTypeCheckFailed { column_index: 2, column_name, .. }
+                if column_name == "created_by"
+        );
+    }
+}
+
 fn val_int(i: i32) -> Option<Vec<u8>> {
     Some(i.to_be_bytes().to_vec())
 }
@@ -23,8 +23,8 @@ use crate::frame::response::result::{ColumnType, NativeType};
 use crate::frame::types;
 use crate::value::CqlVarintBorrowed;
 use crate::value::{
-    deser_cql_value, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime,
-    CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
+    deser_cql_value, BytesStr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration,
+    CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint, CqlVector,
 };
 
 /// A type that can be deserialized from a column value inside a row that was
@@ -241,6 +241,54 @@ impl_emptiable_strict_type!(
     'b
 );
 
+/// Sign-extends a varint to `N` bytes of two's complement big-endian representation.
+/// Returns `None` if the value does not fit.
+fn varint_to_fixed_be_bytes<const N: usize>(val: &[u8]) -> Option<[u8; N]> {
+    let fill = match val.first() {
+        Some(b) if b & 0x80 != 0 => 0xff,
+        _ => 0x00,
+    };
+    let mut arr = [fill; N];
+    if val.len() > N {
+        // Only redundant sign bytes may be dropped.
+        let (extra, rest) = val.split_at(val.len() - N);
+        if extra.iter().any(|b| *b != fill) || (rest[0] ^ fill) & 0x80 != 0 {
+            return None;
+        }
+        arr.copy_from_slice(rest);
+    } else {
+        arr[N - val.len()..].copy_from_slice(val);
+    }
+    Some(arr)
+}
+
+impl_emptiable_strict_type!(
+    i128,
+    Varint,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        let arr = varint_to_fixed_be_bytes::<16>(val).ok_or_else(|| {
+            mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::ValueOverflow)
+        })?;
+        Ok(i128::from_be_bytes(arr))
+    }
+);
+
+impl_emptiable_strict_type!(
+    u128,
+    Varint,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_slice::<Self>(typ, v)?;
+        // The additional byte holds the sign, which must be positive.
+        let arr = varint_to_fixed_be_bytes::<17>(val)
+            .and_then(|arr| (arr[0] == 0x00).then(|| arr[1..].try_into().unwrap()))
+            .ok_or_else(|| {
+                mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::ValueOverflow)
+            })?;
+        Ok(u128::from_be_bytes(arr))
+    }
+);
+
 #[cfg(feature = "num-bigint-03")]
 impl_emptiable_strict_type!(
     num_bigint_03::BigInt,
@@ -388,7 +436,16 @@ impl_string_type!(
     }
 );
 
-// TODO: Consider support for deserialization of string::String<Bytes>
+impl_string_type!(
+    BytesStr,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let val = ensure_not_null_owned::<Self>(typ, v)?;
+        check_ascii::<BytesStr>(typ, &val)?;
+        BytesStr::from_utf8(val).map_err(|err| {
+            mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::InvalidUtf8(err))
+        })
+    }
+);
 
 // counter
 
@@ -585,6 +642,17 @@ impl_emptiable_strict_type!(
     }
 );
 
+impl_emptiable_strict_type!(
+    std::time::SystemTime,
+    Timestamp,
+    |typ: &'metadata ColumnType<'metadata>, v: Option<FrameSlice<'frame>>| {
+        let millis = get_millis_from_timestamp_column::<Self>(typ, v)?;
+        CqlTimestamp(millis)
+            .try_into()
+            .map_err(|_| mk_deser_err::<Self>(typ, BuiltinDeserializationErrorKind::ValueOverflow))
+    }
+);
+
 #[cfg(feature = "chrono-04")]
 impl_emptiable_strict_type!(
     chrono_04::DateTime<chrono_04::Utc>,
@@ -698,6 +766,10 @@ make_error_replace_rust_name!(
 // lists and sets
 
 /// An iterator over either a CQL set or list.
+///
+/// Elements are deserialized lazily, as the iterator advances, directly from
+/// the frame. This avoids materializing the whole collection at once, which
+/// is useful for very large collections.
 #[derive(Debug, Clone)]
 pub struct ListlikeIterator<'frame, 'metadata, T> {
     coll_typ: &'metadata ColumnType<'metadata>,
@@ -829,6 +901,11 @@ where
     }
 }
 
+impl<'frame, 'metadata, T> ExactSizeIterator for ListlikeIterator<'frame, 'metadata, T> where
+    T: DeserializeValue<'frame, 'metadata>
+{
+}
+
 impl<'frame, 'metadata, T> DeserializeValue<'frame, 'metadata> for Vec<T>
 where
     T: DeserializeValue<'frame, 'metadata>,
@@ -1109,7 +1186,32 @@ where
     }
 }
 
+impl<'frame, 'metadata, T> DeserializeValue<'frame, 'metadata> for CqlVector<T>
+where
+    T: DeserializeValue<'frame, 'metadata>,
+{
+    fn type_check(typ: &ColumnType) -> Result<(), TypeCheckError> {
+        // Unlike Vec, CqlVector only deserializes from the vector type.
+        VectorIterator::<'frame, 'metadata, T>::type_check(typ)
+            .map_err(typck_error_replace_rust_name::<Self>)
+    }
+
+    fn deserialize(
+        typ: &'metadata ColumnType<'metadata>,
+        v: Option<FrameSlice<'frame>>,
+    ) -> Result<Self, DeserializationError> {
+        VectorIterator::<'frame, 'metadata, T>::deserialize(typ, v)
+            .and_then(|it| it.collect::<Result<_, DeserializationError>>())
+            .map(CqlVector)
+            .map_err(deser_error_replace_rust_name::<Self>)
+    }
+}
+
 /// An iterator over a CQL map.
+///
+/// Entries are deserialized lazily, as the iterator advances, directly from
+/// the frame. This avoids materializing the whole map at once, which
+/// is useful for very large maps.
 #[derive(Debug, Clone)]
 pub struct MapIterator<'frame, 'metadata, K, V> {
     coll_typ: &'metadata ColumnType<'metadata>,
@@ -1259,10 +1361,19 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.raw_iter.size_hint()
+        // Each entry consists of two raw items: a key and a value.
+        let remaining = self.raw_iter.remaining / 2;
+        (remaining, Some(remaining))
     }
 }
 
+impl<'frame, 'metadata, K, V> ExactSizeIterator for MapIterator<'frame, 'metadata, K, V>
+where
+    K: DeserializeValue<'frame, 'metadata>,
+    V: DeserializeValue<'frame, 'metadata>,
+{
+}
+
 impl<'frame, 'metadata, K, V> DeserializeValue<'frame, 'metadata> for BTreeMap<K, V>
 where
     K: DeserializeValue<'frame, 'metadata> + Ord,
@@ -1688,8 +1799,15 @@ impl<'frame> Iterator for FixedLengthBytesSequenceIterator<'frame> {
         self.remaining = self.remaining.checked_sub(1)?;
         Some(self.slice.read_cql_bytes())
     }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
 }
 
+impl ExactSizeIterator for FixedLengthBytesSequenceIterator<'_> {}
+
 /// Iterates over a sequence of `[bytes]` items from a frame subslice.
 ///
 /// The `[bytes]` items are parsed until the end of subslice is reached.
@@ -2363,6 +2481,38 @@ fn _test_udt_bad_attributes_skip_name_check_requires_enforce_order() {}
 /// ```
 fn _test_udt_bad_attributes_skip_name_check_conflicts_with_rename() {}
 
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeValue)]
+/// #[scylla(crate = scylla_cql, flavor = "enforce_order", skip_name_checks, rename_all = "camelCase")]
+/// struct TestUdt {
+///     a: i32,
+/// }
+/// ```
+fn _test_udt_bad_attributes_skip_name_check_conflicts_with_rename_all() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeValue)]
+/// #[scylla(crate = scylla_cql, rename_all = "Title Case")]
+/// struct TestUdt {
+///     a: i32,
+/// }
+/// ```
+fn _test_udt_bad_attributes_unknown_rename_all_rule() {}
+
+/// ```compile_fail
+///
+/// #[derive(scylla_macros::DeserializeValue)]
+/// #[scylla(crate = scylla_cql, rename_all = "camelCase")]
+/// struct TestUdt {
+///     #[scylla(rename = "fieldA")]
+///     a: i32,
+///     field_a: String,
+/// }
+/// ```
+fn _test_udt_bad_attributes_rename_all_collision_with_rename() {}
+
 /// ```compile_fail
 ///
 /// #[derive(scylla_macros::DeserializeValue)]
@@ -20,8 +20,8 @@ use crate::serialize::value::SerializeValue;
 use crate::serialize::CellWriter;
 use crate::utils::parse::ParseErrorCause;
 use crate::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed,
+    BytesStr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, CqlVector,
 };
 
 use super::{
@@ -29,7 +29,7 @@ use super::{
     BuiltinTypeCheckError, BuiltinTypeCheckErrorKind, DeserializeValue, ListlikeIterator,
     MapDeserializationErrorKind, MapIterator, MapTypeCheckErrorKind, MaybeEmpty,
     SetOrListDeserializationErrorKind, SetOrListTypeCheckErrorKind, UdtDeserializationErrorKind,
-    UdtTypeCheckErrorKind,
+    UdtTypeCheckErrorKind, VectorTypeCheckErrorKind,
 };
 
 #[test]
@@ -483,6 +483,25 @@ fn test_deserialize_vector() {
         &mut Bytes::new(),
     );
 
+    // CqlVector
+
+    assert_ser_de_identity(
+        &ColumnType::Vector {
+            typ: Box::new(ColumnType::Native(NativeType::Float)),
+            dimensions: 3,
+        },
+        &CqlVector(vec![0.1_f32, 0.2, 0.3]),
+        &mut Bytes::new(),
+    );
+    assert_ser_de_identity(
+        &ColumnType::Vector {
+            typ: Box::new(ColumnType::Native(NativeType::Text)),
+            dimensions: 2,
+        },
+        &CqlVector(vec!["ala".to_string(), "kot".to_string()]),
+        &mut Bytes::new(),
+    );
+
     // deser_cql_value
 
     let buf: Vec<u8> = vec![0, 0, 0, 1, 0, 0, 0, 2];
@@ -514,9 +533,11 @@ fn test_deserialize_ascii() {
     {
         let decoded_str = deserialize::<&str>(typ, &ascii).unwrap();
         let decoded_string = deserialize::<String>(typ, &ascii).unwrap();
+        let decoded_bytes_str = deserialize::<BytesStr>(typ, &ascii).unwrap();
 
         assert_eq!(decoded_str, ASCII_TEXT);
         assert_eq!(decoded_string, ASCII_TEXT);
+        assert_eq!(decoded_bytes_str, ASCII_TEXT);
 
         // ser/de identity
 
@@ -539,6 +560,7 @@ fn test_deserialize_text() {
     // Should fail because it's not an ASCII string
     deserialize::<&str>(&ColumnType::Native(NativeType::Ascii), &unicode).unwrap_err();
     deserialize::<String>(&ColumnType::Native(NativeType::Ascii), &unicode).unwrap_err();
+    deserialize::<BytesStr>(&ColumnType::Native(NativeType::Ascii), &unicode).unwrap_err();
 
     let decoded_text_str =
         deserialize::<&str>(&ColumnType::Native(NativeType::Text), &unicode).unwrap();
@@ -547,6 +569,13 @@ fn test_deserialize_text() {
     assert_eq!(decoded_text_str, UNICODE_TEXT);
     assert_eq!(decoded_text_string, UNICODE_TEXT);
 
+    // BytesStr shares the buffer of the frame instead of copying the text.
+    let decoded_text_bytes_str =
+        deserialize::<BytesStr>(&ColumnType::Native(NativeType::Text), &unicode).unwrap();
+    assert_eq!(decoded_text_bytes_str, UNICODE_TEXT);
+    let frame_range = unicode.as_ptr_range();
+    assert!(frame_range.contains(&decoded_text_bytes_str.as_ptr()));
+
     // ser/de identity
 
     assert_ser_de_identity(
@@ -559,6 +588,11 @@ fn test_deserialize_text() {
         &UNICODE_TEXT.to_owned(),
         &mut Bytes::new(),
     );
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Text),
+        &BytesStr::from_static(UNICODE_TEXT),
+        &mut Bytes::new(),
+    );
 }
 
 #[test]
@@ -661,6 +695,22 @@ fn test_varlen_numbers() {
         &mut Bytes::new(),
     );
 
+    for value in [0, 1, -1, 127, 128, -128, -129, i128::MIN, i128::MAX] {
+        assert_ser_de_identity(
+            &ColumnType::Native(NativeType::Varint),
+            &value,
+            &mut Bytes::new(),
+        );
+    }
+
+    for value in [0, 1, 127, 128, u128::MAX] {
+        assert_ser_de_identity(
+            &ColumnType::Native(NativeType::Varint),
+            &value,
+            &mut Bytes::new(),
+        );
+    }
+
     #[cfg(feature = "num-bigint-03")]
     assert_ser_de_identity(
         &ColumnType::Native(NativeType::Varint),
@@ -699,6 +749,49 @@ fn test_varlen_numbers() {
     );
 }
 
+#[test]
+fn test_varint_to_128_bit_integers() {
+    let typ = ColumnType::Native(NativeType::Varint);
+
+    // Non-normalized values with redundant sign bytes
+    let bytes = make_bytes(&[0x00, 0x00, 0x80]);
+    assert_eq!(deserialize::<i128>(&typ, &bytes).unwrap(), 128);
+    assert_eq!(deserialize::<u128>(&typ, &bytes).unwrap(), 128);
+    let bytes = make_bytes(&[0xff; 20]);
+    assert_eq!(deserialize::<i128>(&typ, &bytes).unwrap(), -1);
+
+    // Empty value is zero
+    let bytes = make_bytes(&[]);
+    assert_eq!(deserialize::<i128>(&typ, &bytes).unwrap(), 0);
+    assert_eq!(deserialize::<u128>(&typ, &bytes).unwrap(), 0);
+
+    // u128::MAX needs a leading zero byte
+    let mut u128_max = vec![0x00];
+    u128_max.extend([0xff; 16]);
+    let bytes = make_bytes(&u128_max);
+    assert_eq!(deserialize::<u128>(&typ, &bytes).unwrap(), u128::MAX);
+
+    let overflowing: [(&[u8], &str); 4] = [
+        (&u128_max, "i128"),
+        (&[0x01; 17], "u128"),
+        (&[0xff], "u128"),
+        (
+            &[0x00, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            "i128",
+        ),
+    ];
+    for (value, rust_type) in overflowing {
+        let bytes = make_bytes(value);
+        let err = match rust_type {
+            "i128" => deserialize::<i128>(&typ, &bytes).unwrap_err(),
+            _ => deserialize::<u128>(&typ, &bytes).unwrap_err(),
+        };
+        let err = get_deser_err(&err);
+        assert_eq!(err.rust_name, rust_type);
+        assert_matches!(err.kind, BuiltinDeserializationErrorKind::ValueOverflow);
+    }
+}
+
 #[test]
 fn test_date_time_types() {
     // duration
@@ -761,6 +854,18 @@ fn test_date_time_types() {
         &mut Bytes::new(),
     );
 
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Timestamp),
+        &(std::time::UNIX_EPOCH + std::time::Duration::from_millis(0xdead_cafe_deaf)),
+        &mut Bytes::new(),
+    );
+
+    assert_ser_de_identity(
+        &ColumnType::Native(NativeType::Timestamp),
+        &(std::time::UNIX_EPOCH - std::time::Duration::from_millis(0xdead_cafe_deaf)),
+        &mut Bytes::new(),
+    );
+
     #[cfg(feature = "chrono-04")]
     assert_ser_de_identity(
         &ColumnType::Native(NativeType::Timestamp),
@@ -935,7 +1040,9 @@ fn test_list_and_set() {
 
     // iterator
     let mut iter = deserialize::<ListlikeIterator<&str>>(&list_typ, &collection).unwrap();
+    assert_eq!(iter.len(), 3);
     assert_eq!(iter.next().transpose().unwrap(), Some("quick"));
+    assert_eq!(iter.len(), 2);
     assert_eq!(iter.next().transpose().unwrap(), Some("brown"));
     assert_eq!(iter.next().transpose().unwrap(), Some("fox"));
     assert_eq!(iter.next().transpose().unwrap(), None);
@@ -1037,7 +1144,9 @@ fn test_map() {
 
     // iterator
     let mut iter = deserialize::<MapIterator<i32, &str>>(&typ, &collection).unwrap();
+    assert_eq!(iter.len(), 3);
     assert_eq!(iter.next().transpose().unwrap(), Some((1, "quick")));
+    assert_eq!(iter.len(), 2);
     assert_eq!(iter.next().transpose().unwrap(), Some((2, "brown")));
     assert_eq!(iter.next().transpose().unwrap(), Some((3, "fox")));
     assert_eq!(iter.next().transpose().unwrap(), None);
@@ -1645,6 +1754,133 @@ fn test_udt_cross_rename_fields() {
     }
 }
 
+#[test]
+fn test_udt_rename_all_fields() {
+    #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate, rename_all = "camelCase")]
+    struct TestUdt {
+        first_name: String,
+        #[scylla(rename = "Age")]
+        age: i32,
+    }
+
+    let udt_bytes = UdtSerializer::new()
+        .field(&42_i32.to_be_bytes())
+        .field("Ala".as_bytes())
+        .finalize();
+    let typ = udt_def_with_fields([
+        ("Age", ColumnType::Native(NativeType::Int)),
+        ("firstName", ColumnType::Native(NativeType::Text)),
+    ]);
+
+    let udt = deserialize::<TestUdt>(&typ, &udt_bytes).unwrap();
+    assert_eq!(
+        udt,
+        TestUdt {
+            first_name: "Ala".to_owned(),
+            age: 42,
+        }
+    );
+
+    // The Rust field name is no longer accepted.
+    let typ = udt_def_with_fields([
+        ("Age", ColumnType::Native(NativeType::Int)),
+        ("first_name", ColumnType::Native(NativeType::Text)),
+    ]);
+    deserialize::<TestUdt>(&typ, &udt_bytes).unwrap_err();
+}
+
+#[test]
+fn test_udt_field_defaults() {
+    fn default_port() -> i32 {
+        9042
+    }
+
+    #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate)]
+    struct Udt {
+        host: String,
+        #[scylla(default = "default_port")]
+        port: i32,
+        #[scylla(default)]
+        tags: Vec<String>,
+    }
+
+    #[derive(scylla_macros::DeserializeValue, PartialEq, Eq, Debug)]
+    #[scylla(crate = crate, flavor = "enforce_order")]
+    struct OrderedUdt {
+        host: String,
+        #[scylla(default = "default_port")]
+        port: i32,
+        #[scylla(default)]
+        tags: Vec<String>,
+    }
+
+    // All fields are present
+    {
+        let udt_bytes = UdtSerializer::new()
+            .field(b"localhost")
+            .field(&19042_i32.to_be_bytes())
+            .field(&0_i32.to_be_bytes())
+            .finalize();
+        let typ = udt_def_with_fields([
+            ("host", ColumnType::Native(NativeType::Text)),
+            ("port", ColumnType::Native(NativeType::Int)),
+            (
+                "tags",
+                ColumnType::Collection {
+                    frozen: false,
+                    typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Text))),
+                },
+            ),
+        ]);
+
+        let udt = deserialize::<Udt>(&typ, &udt_bytes).unwrap();
+        assert_eq!(
+            udt,
+            Udt {
+                host: "localhost".to_owned(),
+                port: 19042,
+                tags: Vec::new(),
+            }
+        );
+        let udt = deserialize::<OrderedUdt>(&typ, &udt_bytes).unwrap();
+        assert_eq!(udt.port, 19042);
+    }
+
+    // The UDT is older than the struct - fields added later are missing
+    {
+        let udt_bytes = UdtSerializer::new().field(b"localhost").finalize();
+        let typ = udt_def_with_fields([("host", ColumnType::Native(NativeType::Text))]);
+
+        let udt = deserialize::<Udt>(&typ, &udt_bytes).unwrap();
+        assert_eq!(
+            udt,
+            Udt {
+                host: "localhost".to_owned(),
+                port: 9042,
+                tags: Vec::new(),
+            }
+        );
+        let udt = deserialize::<OrderedUdt>(&typ, &udt_bytes).unwrap();
+        assert_eq!(
+            udt,
+            OrderedUdt {
+                host: "localhost".to_owned(),
+                port: 9042,
+                tags: Vec::new(),
+            }
+        );
+    }
+
+    // Fields without `default` are still required
+    {
+        let typ = udt_def_with_fields([("port", ColumnType::Native(NativeType::Int))]);
+        Udt::type_check(&typ).unwrap_err();
+        OrderedUdt::type_check(&typ).unwrap_err();
+    }
+}
+
 #[test]
 fn test_custom_type_parser() {
     #[derive(Default, Debug, PartialEq, Eq)]
@@ -2136,6 +2372,39 @@ fn test_set_or_list_elem_deser_errors() {
     }
 }
 
+#[test]
+fn test_vector_errors() {
+    // Not a vector
+    assert_type_check_error!(
+        &Bytes::new(),
+        CqlVector<f32>,
+        ColumnType::Native(NativeType::Float),
+        BuiltinTypeCheckErrorKind::VectorError(VectorTypeCheckErrorKind::NotVector)
+    );
+
+    // Unlike Vec, CqlVector does not accept lists.
+    assert_type_check_error!(
+        &Bytes::new(),
+        CqlVector<f32>,
+        ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Float))),
+        },
+        BuiltinTypeCheckErrorKind::VectorError(VectorTypeCheckErrorKind::NotVector)
+    );
+
+    // Element type mismatch
+    assert_type_check_error!(
+        &Bytes::new(),
+        CqlVector<f32>,
+        ColumnType::Vector {
+            typ: Box::new(ColumnType::Native(NativeType::Double)),
+            dimensions: 2,
+        },
+        BuiltinTypeCheckErrorKind::VectorError(VectorTypeCheckErrorKind::ElementTypeCheckFailed(_))
+    );
+}
+
 #[test]
 fn test_map_errors() {
     // Not a map
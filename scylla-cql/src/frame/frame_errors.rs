@@ -55,6 +55,10 @@ pub enum FrameBodyExtensionsParseError {
     /// Failed to decompress frame body (lz4).
     #[error("Error decompressing lz4 data {0}")]
     Lz4DecompressError(Arc<dyn Error + Sync + Send>),
+
+    /// Failed to decompress frame body (zstd).
+    #[error("Zstd decompression error: {0}")]
+    ZstdDecompressError(Arc<dyn Error + Sync + Send>),
 }
 
 /// An error that occurred during frame header deserialization.
@@ -77,7 +81,7 @@ pub enum FrameHeaderParseError {
     FrameFromServer,
 
     /// Received a frame with unsupported version.
-    #[error("Received a frame from version {0}, but only 4 is supported")]
+    #[error("Received a frame from unsupported protocol version {0}")]
     VersionNotSupported(u8),
 
     /// Received unknown response opcode.
@@ -91,6 +95,41 @@ pub enum FrameHeaderParseError {
     /// Connection was closed before whole frame was read.
     #[error("Connection was closed before body was read: missing {0} out of {1}")]
     ConnectionClosed(usize, usize),
+
+    /// Failed to read a protocol v5 segment carrying the frame.
+    #[error("Failed to read a segment: {0}")]
+    SegmentParseError(#[from] SegmentParseError),
+}
+
+/// An error that occurred while reading a protocol v5 segment.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SegmentParseError {
+    /// Failed to read the segment from the socket.
+    #[error("Failed to read the segment: {0}")]
+    IoError(std::io::Error),
+
+    /// The CRC24 of the segment header does not match.
+    #[error("Segment header CRC mismatch: received {received:#08x}, computed {computed:#08x}")]
+    HeaderCrcMismatch {
+        /// CRC received from the server.
+        received: u32,
+        /// CRC computed from the received header.
+        computed: u32,
+    },
+
+    /// The CRC32 of the segment payload does not match.
+    #[error("Segment payload CRC mismatch: received {received:#010x}, computed {computed:#010x}")]
+    PayloadCrcMismatch {
+        /// CRC received from the server.
+        received: u32,
+        /// CRC computed from the received payload.
+        computed: u32,
+    },
+
+    /// Failed to decompress the segment payload (lz4).
+    #[error("Error decompressing lz4 segment payload: {0}")]
+    Lz4DecompressError(Arc<dyn Error + Sync + Send>),
 }
 
 /// An error that occurred during CQL request serialization.
@@ -128,6 +167,14 @@ pub enum CqlRequestSerializationError {
     /// Request body compression failed.
     #[error("Snap compression error: {0}")]
     SnapCompressError(Arc<dyn Error + Sync + Send>),
+
+    /// Request body compression failed (zstd).
+    #[error("Zstd compression error: {0}")]
+    ZstdCompressError(Arc<dyn Error + Sync + Send>),
+
+    /// The custom payload does not fit in the frame, e.g. it has too many entries.
+    #[error("Failed to serialize custom payload: {0}")]
+    CustomPayloadSerialization(std::num::TryFromIntError),
 }
 
 /// An error type returned when deserialization of CQL
@@ -305,6 +352,8 @@ pub enum ClusterChangeEventParseError {
 pub enum PreparedParseError {
     #[error("Malformed prepared statement's id length: {0}")]
     IdLengthParseError(LowLevelDeserializationError),
+    #[error("Malformed prepared statement's result metadata id: {0}")]
+    ResultMetadataIdParseError(LowLevelDeserializationError),
     #[error("Invalid result metadata: {0}")]
     ResultMetadataParseError(ResultMetadataParseError),
     #[error("Invalid prepared metadata: {0}")]
@@ -336,6 +385,10 @@ pub enum RawRowsAndPagingStateResponseParseError {
     /// Failed to parse paging state response.
     #[error("Malformed paging state: {0}")]
     PagingStateParseError(LowLevelDeserializationError),
+
+    /// Failed to parse the new result metadata id (protocol v5).
+    #[error("Malformed new result metadata id: {0}")]
+    NewMetadataIdParseError(LowLevelDeserializationError),
 }
 
 /// An error type returned when deserialization
@@ -12,6 +12,7 @@ pub mod frame_errors;
 pub mod protocol_features;
 pub mod request;
 pub mod response;
+pub mod segment;
 pub mod server_event_type;
 pub mod types;
 
@@ -28,7 +29,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 use std::{collections::HashMap, convert::TryFrom};
 
-use request::SerializableRequest;
+use request::{RequestOpcode, SerializableRequest};
 use response::ResponseOpcode;
 
 const HEADER_SIZE: usize = 9;
@@ -69,6 +70,12 @@ pub enum Compression {
     Lz4,
     /// Snappy compression algorithm.
     Snappy,
+    /// Zstandard compression algorithm.
+    ///
+    /// Trades more CPU time for better compression ratios than LZ4 and Snappy.
+    /// Available with the `zstd` feature.
+    #[cfg(feature = "zstd")]
+    Zstd,
 }
 
 impl Compression {
@@ -77,6 +84,8 @@ impl Compression {
         match self {
             Compression::Lz4 => "lz4",
             Compression::Snappy => "snappy",
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => "zstd",
         }
     }
 }
@@ -95,6 +104,8 @@ impl FromStr for Compression {
         match s {
             "lz4" => Ok(Self::Lz4),
             "snappy" => Ok(Self::Snappy),
+            #[cfg(feature = "zstd")]
+            "zstd" => Ok(Self::Zstd),
             other => Err(Self::Err {
                 name: other.to_owned(),
             }),
@@ -108,6 +119,59 @@ impl Display for Compression {
     }
 }
 
+/// Version of the CQL native protocol.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[non_exhaustive]
+pub enum ProtocolVersion {
+    /// Version 4 of the protocol, supported by all ScyllaDB versions and Cassandra 2.2+.
+    V4,
+
+    /// Version 5 of the protocol, supported by Cassandra 4.0+, but not by ScyllaDB.
+    ///
+    /// Among others, it wraps frames into checksummed [segments](segment), supports
+    /// setting the keyspace per request and tracks the version of prepared statements' result metadata.
+    V5,
+}
+
+impl ProtocolVersion {
+    /// All protocol versions supported by the driver, in ascending order.
+    pub const ALL: &'static [ProtocolVersion] = &[ProtocolVersion::V4, ProtocolVersion::V5];
+
+    /// The newest protocol version supported by the driver.
+    pub const LATEST: ProtocolVersion = ProtocolVersion::V5;
+
+    /// Returns the version number, as sent in the frame header.
+    pub fn as_u8(self) -> u8 {
+        match self {
+            ProtocolVersion::V4 => 4,
+            ProtocolVersion::V5 => 5,
+        }
+    }
+}
+
+/// Protocol version not supported by the driver.
+#[derive(Error, Debug, Clone)]
+#[error("Unsupported protocol version: {0}")]
+pub struct UnsupportedProtocolVersion(pub u8);
+
+impl TryFrom<u8> for ProtocolVersion {
+    type Error = UnsupportedProtocolVersion;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            4 => Ok(ProtocolVersion::V4),
+            5 => Ok(ProtocolVersion::V5),
+            other => Err(UnsupportedProtocolVersion(other)),
+        }
+    }
+}
+
+impl Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "v{}", self.as_u8())
+    }
+}
+
 /// A serialized CQL request frame, nearly ready to be sent over the wire.
 ///
 /// The only difference from a real frame is that it does not contain the stream number yet.
@@ -127,26 +191,181 @@ impl SerializedRequest {
         req: &R,
         compression: Option<Compression>,
         tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_with_compression_threshold(req, compression, 0, tracing)
+    }
+
+    /// Creates a new serialized request frame from a request object,
+    /// compressing the request body only if its size is at least `compression_threshold` bytes.
+    ///
+    /// Compressing tiny requests wastes CPU and adds latency, while hardly reducing their size.
+    /// The compression flag is set per frame, so uncompressed frames can be freely mixed
+    /// with compressed ones on a connection with negotiated compression.
+    ///
+    /// # Parameters
+    /// - `req`: The request object to serialize. Must implement `SerializableRequest`.
+    /// - `compression`: An optional compression algorithm to use for the request body.
+    /// - `compression_threshold`: The minimal size (in bytes) of the uncompressed body to compress it.
+    /// - `tracing`: A boolean indicating whether to request tracing information in the response.
+    pub fn make_with_compression_threshold<R: SerializableRequest>(
+        req: &R,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_for_version(
+            req,
+            ProtocolVersion::V4,
+            compression,
+            compression_threshold,
+            tracing,
+        )
+    }
+
+    /// Creates a new serialized request frame of the given protocol version from a request object.
+    ///
+    /// In protocol v5 and newer, frames are never compressed on their own - compression
+    /// is applied to whole [segments](segment) instead - so `compression`
+    /// and `compression_threshold` are ignored.
+    ///
+    /// # Parameters
+    /// - `req`: The request object to serialize. Must implement `SerializableRequest`.
+    /// - `version`: The protocol version negotiated on the connection.
+    /// - `compression`: An optional compression algorithm to use for the request body.
+    /// - `compression_threshold`: The minimal size (in bytes) of the uncompressed body to compress it.
+    /// - `tracing`: A boolean indicating whether to request tracing information in the response.
+    pub fn make_for_version<R: SerializableRequest>(
+        req: &R,
+        version: ProtocolVersion,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_with_body(
+            R::OPCODE,
+            version,
+            |buf| req.serialize_for_version(version, buf),
+            None,
+            compression,
+            compression_threshold,
+            tracing,
+        )
+    }
+
+    /// Creates a new serialized request frame of the given protocol version from a request object,
+    /// prepending the given custom payload to the request body.
+    ///
+    /// Custom payloads are only interpreted by the server for QUERY, PREPARE, EXECUTE
+    /// and BATCH requests. An empty payload is not sent at all.
+    ///
+    /// # Parameters
+    /// - `req`: The request object to serialize. Must implement `SerializableRequest`.
+    /// - `version`: The protocol version negotiated on the connection.
+    /// - `custom_payload`: The custom payload to send along with the request.
+    /// - `compression`: An optional compression algorithm to use for the request body.
+    /// - `compression_threshold`: The minimal size (in bytes) of the uncompressed body to compress it.
+    /// - `tracing`: A boolean indicating whether to request tracing information in the response.
+    pub fn make_with_custom_payload<R: SerializableRequest>(
+        req: &R,
+        version: ProtocolVersion,
+        custom_payload: &HashMap<String, Bytes>,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_with_body(
+            R::OPCODE,
+            version,
+            |buf| req.serialize_for_version(version, buf),
+            Some(custom_payload).filter(|payload| !payload.is_empty()),
+            compression,
+            compression_threshold,
+            tracing,
+        )
+    }
+
+    /// Creates a new serialized request frame from an already serialized request body.
+    ///
+    /// This allows sending requests which the driver has no typed support for.
+    /// The body is sent as is (apart from the compression), so it is up to the caller
+    /// to make it valid for the given opcode.
+    ///
+    /// # Parameters
+    /// - `opcode`: The opcode of the request.
+    /// - `version`: The protocol version negotiated on the connection.
+    /// - `body`: The serialized request body.
+    /// - `compression`: An optional compression algorithm to use for the request body.
+    /// - `compression_threshold`: The minimal size (in bytes) of the uncompressed body to compress it.
+    /// - `tracing`: A boolean indicating whether to request tracing information in the response.
+    pub fn make_raw(
+        opcode: RequestOpcode,
+        version: ProtocolVersion,
+        body: &[u8],
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+    ) -> Result<SerializedRequest, CqlRequestSerializationError> {
+        Self::make_with_body(
+            opcode,
+            version,
+            |buf| {
+                buf.extend_from_slice(body);
+                Ok(())
+            },
+            None,
+            compression,
+            compression_threshold,
+            tracing,
+        )
+    }
+
+    fn make_with_body(
+        opcode: RequestOpcode,
+        version: ProtocolVersion,
+        serialize_body: impl FnOnce(&mut Vec<u8>) -> Result<(), CqlRequestSerializationError>,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
     ) -> Result<SerializedRequest, CqlRequestSerializationError> {
         let mut flags = 0;
         let mut data = vec![0; HEADER_SIZE];
 
+        // The custom payload precedes the request body and is compressed together with it.
+        let serialize_body = |buf: &mut Vec<u8>| {
+            if let Some(custom_payload) = custom_payload {
+                types::write_bytes_map(custom_payload, buf)
+                    .map_err(CqlRequestSerializationError::CustomPayloadSerialization)?;
+            }
+            serialize_body(buf)
+        };
+        if custom_payload.is_some() {
+            flags |= flag::CUSTOM_PAYLOAD;
+        }
+
+        // Since protocol v5, compression is done on the segment level.
+        let compression = compression.filter(|_| version < ProtocolVersion::V5);
         if let Some(compression) = compression {
-            flags |= flag::COMPRESSION;
-            let body = req.to_bytes()?;
-            compress_append(&body, compression, &mut data)?;
+            let mut body = Vec::new();
+            serialize_body(&mut body)?;
+            if body.len() >= compression_threshold {
+                flags |= flag::COMPRESSION;
+                compress_append(&body, compression, &mut data)?;
+            } else {
+                data.extend_from_slice(&body);
+            }
         } else {
-            req.serialize(&mut data)?;
+            serialize_body(&mut data)?;
         }
 
         if tracing {
             flags |= flag::TRACING;
         }
 
-        data[0] = 4; // We only support version 4 for now
+        data[0] = version.as_u8();
         data[1] = flags;
         // Leave space for the stream number
-        data[4] = R::OPCODE as u8;
+        data[4] = opcode as u8;
 
         let req_size = (data.len() - HEADER_SIZE) as u32;
         data[5..9].copy_from_slice(&req_size.to_be_bytes());
@@ -165,12 +384,30 @@ impl SerializedRequest {
     pub fn get_data(&self) -> &[u8] {
         &self.data[..]
     }
+
+    /// Returns whether this is an AUTH_RESPONSE request frame.
+    ///
+    /// Such frames may carry secrets, e.g. passwords.
+    pub fn is_auth_response(&self) -> bool {
+        self.data[4] == RequestOpcode::AuthResponse as u8
+    }
+}
+
+/// With the `secrecy-08` feature, AUTH_RESPONSE frames are zeroized when dropped,
+/// so that the credentials they carry do not linger in memory.
+#[cfg(feature = "secrecy-08")]
+impl Drop for SerializedRequest {
+    fn drop(&mut self) {
+        if self.is_auth_response() {
+            secrecy_08::Zeroize::zeroize(&mut self.data);
+        }
+    }
 }
 
 /// Parts of the frame header which are not determined by the request/response type.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct FrameParams {
-    /// The version of the frame protocol. Currently, only version 4 is supported.
+    /// The version of the frame protocol.
     /// The most significant bit (0x80) is treated specially:
     /// it indicates whether the frame is from the client or server.
     pub version: u8,
@@ -195,6 +432,9 @@ impl Default for FrameParams {
 
 /// Reads a response frame from the provided reader (usually, a socket).
 /// Then parses and validates the frame header and extracts the body.
+///
+/// The frame is expected to be sent directly, not wrapped in a segment.
+/// For reading frames wrapped in protocol v5 segments, see [segment::SegmentedFrameReader].
 pub async fn read_response_frame(
     reader: &mut (impl AsyncRead + Unpin),
 ) -> Result<(FrameParams, ResponseOpcode, Bytes), FrameHeaderParseError> {
@@ -204,16 +444,38 @@ pub async fn read_response_frame(
         .await
         .map_err(FrameHeaderParseError::HeaderIoError)?;
 
+    let (frame_params, opcode, length) = parse_response_frame_header(&raw_header)?;
+
+    let mut raw_body = Vec::with_capacity(length).limit(length);
+    while raw_body.has_remaining_mut() {
+        let n = reader.read_buf(&mut raw_body).await.map_err(|err| {
+            FrameHeaderParseError::BodyChunkIoError(raw_body.remaining_mut(), err)
+        })?;
+        if n == 0 {
+            // EOF, too early
+            return Err(FrameHeaderParseError::ConnectionClosed(
+                raw_body.remaining_mut(),
+                length,
+            ));
+        }
+    }
+
+    Ok((frame_params, opcode, raw_body.into_inner().into()))
+}
+
+// Parses and validates the header of a response frame.
+// Returns the header's contents along with the length of the frame body.
+fn parse_response_frame_header(
+    raw_header: &[u8; HEADER_SIZE],
+) -> Result<(FrameParams, ResponseOpcode, usize), FrameHeaderParseError> {
     let mut buf = &raw_header[..];
 
-    // TODO: Validate version
     let version = buf.get_u8();
     if version & 0x80 != 0x80 {
         return Err(FrameHeaderParseError::FrameFromClient);
     }
-    if version & 0x7F != 0x04 {
-        return Err(FrameHeaderParseError::VersionNotSupported(version & 0x7f));
-    }
+    ProtocolVersion::try_from(version & 0x7F)
+        .map_err(|_| FrameHeaderParseError::VersionNotSupported(version & 0x7F))?;
 
     let flags = buf.get_u8();
     let stream = buf.get_i16();
@@ -229,21 +491,7 @@ pub async fn read_response_frame(
     // TODO: Guard from frames that are too large
     let length = buf.get_u32() as usize;
 
-    let mut raw_body = Vec::with_capacity(length).limit(length);
-    while raw_body.has_remaining_mut() {
-        let n = reader.read_buf(&mut raw_body).await.map_err(|err| {
-            FrameHeaderParseError::BodyChunkIoError(raw_body.remaining_mut(), err)
-        })?;
-        if n == 0 {
-            // EOF, too early
-            return Err(FrameHeaderParseError::ConnectionClosed(
-                raw_body.remaining_mut(),
-                length,
-            ));
-        }
-    }
-
-    Ok((frame_params, opcode, raw_body.into_inner().into()))
+    Ok((frame_params, opcode, length))
 }
 
 /// Represents the already parsed response body extensions,
@@ -324,6 +572,34 @@ pub fn parse_response_body_extensions(
     })
 }
 
+// Zstd level used for compressing request bodies. It is the library's default,
+// which offers a good balance between compression ratio and CPU usage.
+#[cfg(feature = "zstd")]
+const ZSTD_COMPRESSION_LEVEL: i32 = zstd::DEFAULT_COMPRESSION_LEVEL;
+
+// Upper bound on the size of a zstd-decompressed body. Unlike LZ4 frames, zstd ones
+// don't need to declare their decompressed size, so a small malicious body could
+// otherwise expand without limit. The protocol limits frames to 256 MB.
+#[cfg(feature = "zstd")]
+const ZSTD_MAX_DECOMPRESSED_SIZE: usize = 256 * 1024 * 1024;
+
+#[cfg(feature = "zstd")]
+fn zstd_decompress(comp_body: &[u8], limit: usize) -> Result<Vec<u8>, std::io::Error> {
+    use std::io::Read;
+
+    let mut uncomp_body = Vec::new();
+    zstd::stream::read::Decoder::with_buffer(comp_body)?
+        .take(limit as u64 + 1)
+        .read_to_end(&mut uncomp_body)?;
+    if uncomp_body.len() > limit {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("decompressed body exceeds {} bytes", limit),
+        ));
+    }
+    Ok(uncomp_body)
+}
+
 /// Compresses the request body using the specified compression algorithm,
 /// appending the compressed data to the provided output buffer.
 pub fn compress_append(
@@ -349,6 +625,13 @@ pub fn compress_append(
             out.truncate(old_size + compressed_size);
             Ok(())
         }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            let compressed = zstd::bulk::compress(uncomp_body, ZSTD_COMPRESSION_LEVEL)
+                .map_err(|err| CqlRequestSerializationError::ZstdCompressError(Arc::new(err)))?;
+            out.extend_from_slice(&compressed);
+            Ok(())
+        }
     }
 }
 
@@ -368,6 +651,9 @@ pub fn decompress(
         Compression::Snappy => snap::raw::Decoder::new()
             .decompress_vec(comp_body)
             .map_err(|err| FrameBodyExtensionsParseError::SnapDecompressError(Arc::new(err))),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => zstd_decompress(comp_body, ZSTD_MAX_DECOMPRESSED_SIZE)
+            .map_err(|err| FrameBodyExtensionsParseError::ZstdDecompressError(Arc::new(err))),
     }
 }
 
@@ -382,6 +668,7 @@ pub struct TryFromPrimitiveError<T: Copy + std::fmt::Debug> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use std::borrow::Cow;
 
     #[test]
     fn test_lz4_compress() {
@@ -406,4 +693,130 @@ mod test {
         assert_eq!(32, comp_body.len());
         assert_eq!(uncomp_body.as_bytes(), result);
     }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_compress_decompress() {
+        let mut comp_body = Vec::from(&b"prefix"[..]);
+        let uncomp_body = "Hello, World!".repeat(100);
+        let compression = Compression::Zstd;
+        compress_append(uncomp_body.as_bytes(), compression, &mut comp_body).unwrap();
+        assert_eq!(&comp_body[..6], b"prefix");
+        assert!(comp_body.len() < uncomp_body.len());
+        let result = decompress(&comp_body[6..], compression).unwrap();
+        assert_eq!(uncomp_body.as_bytes(), result);
+        assert_eq!("zstd".parse::<Compression>().unwrap(), compression);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_decompress_limit() {
+        let mut comp_body = Vec::new();
+        let uncomp_body = vec![0_u8; 1024 * 1024];
+        compress_append(&uncomp_body, Compression::Zstd, &mut comp_body).unwrap();
+        assert!(comp_body.len() < 1024);
+
+        assert_eq!(
+            zstd_decompress(&comp_body, uncomp_body.len()).unwrap(),
+            uncomp_body
+        );
+        assert!(zstd_decompress(&comp_body, uncomp_body.len() - 1).is_err());
+    }
+
+    #[test]
+    fn test_compression_threshold() {
+        let startup = request::Startup {
+            options: [(Cow::Borrowed("CQL_VERSION"), Cow::Borrowed("4.0.0"))]
+                .into_iter()
+                .collect(),
+        };
+        let body = startup.to_bytes().unwrap();
+
+        // Bodies at least as big as the threshold are compressed.
+        let req = SerializedRequest::make_with_compression_threshold(
+            &startup,
+            Some(Compression::Lz4),
+            body.len(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(req.get_data()[1] & flag::COMPRESSION, flag::COMPRESSION);
+
+        // Smaller ones are left uncompressed.
+        let req = SerializedRequest::make_with_compression_threshold(
+            &startup,
+            Some(Compression::Lz4),
+            body.len() + 1,
+            false,
+        )
+        .unwrap();
+        assert_eq!(req.get_data()[1] & flag::COMPRESSION, 0);
+        assert_eq!(&req.get_data()[HEADER_SIZE..], &body[..]);
+        assert_eq!(req.get_data()[5..9], (body.len() as u32).to_be_bytes()[..]);
+    }
+
+    #[test]
+    fn test_make_raw_matches_typed_request() {
+        let startup = request::Startup {
+            options: [(Cow::Borrowed("CQL_VERSION"), Cow::Borrowed("4.0.0"))]
+                .into_iter()
+                .collect(),
+        };
+        let body = startup.to_bytes().unwrap();
+
+        for compression in [None, Some(Compression::Lz4)] {
+            let typed = SerializedRequest::make(&startup, compression, true).unwrap();
+            let raw = SerializedRequest::make_raw(
+                RequestOpcode::Startup,
+                ProtocolVersion::V4,
+                &body,
+                compression,
+                0,
+                true,
+            )
+            .unwrap();
+            assert_eq!(typed.get_data(), raw.get_data());
+        }
+    }
+
+    #[test]
+    fn test_custom_payload_precedes_body() {
+        let startup = request::Startup {
+            options: [(Cow::Borrowed("CQL_VERSION"), Cow::Borrowed("4.0.0"))]
+                .into_iter()
+                .collect(),
+        };
+        let body = startup.to_bytes().unwrap();
+        let payload = HashMap::from([("key".to_owned(), Bytes::from_static(b"value"))]);
+
+        let req = SerializedRequest::make_with_custom_payload(
+            &startup,
+            ProtocolVersion::V4,
+            &payload,
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            req.get_data()[1] & flag::CUSTOM_PAYLOAD,
+            flag::CUSTOM_PAYLOAD
+        );
+        let mut frame_body = &req.get_data()[HEADER_SIZE..];
+        assert_eq!(types::read_bytes_map(&mut frame_body).unwrap(), payload);
+        assert_eq!(frame_body, &body[..]);
+
+        // An empty payload is omitted.
+        let req = SerializedRequest::make_with_custom_payload(
+            &startup,
+            ProtocolVersion::V4,
+            &HashMap::new(),
+            None,
+            0,
+            false,
+        )
+        .unwrap();
+        let plain = SerializedRequest::make(&startup, None, false).unwrap();
+        assert_eq!(req.get_data(), plain.get_data());
+    }
 }
@@ -8,6 +8,7 @@ use crate::frame::{
     frame_errors::CqlRequestSerializationError,
     request::{RequestOpcode, SerializableRequest},
     types::{self, SerialConsistency},
+    ProtocolVersion,
 };
 use crate::serialize::{
     raw_batch::{RawBatchValues, RawBatchValuesIterator},
@@ -112,7 +113,11 @@ where
     Statement: Clone,
     Values: RawBatchValues,
 {
-    fn do_serialize(&self, buf: &mut Vec<u8>) -> Result<(), BatchSerializationError> {
+    fn do_serialize(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), BatchSerializationError> {
         // Serializing type of batch
         buf.put_u8(self.batch_type as u8);
 
@@ -198,7 +203,12 @@ where
             flags |= FLAG_WITH_DEFAULT_TIMESTAMP;
         }
 
-        buf.put_u8(flags);
+        // Protocol v5 extends the flags to 4 bytes.
+        if version >= ProtocolVersion::V5 {
+            buf.put_u32(flags as u32);
+        } else {
+            buf.put_u8(flags);
+        }
 
         if let Some(serial_consistency) = self.serial_consistency {
             types::write_serial_consistency(serial_consistency, buf);
@@ -220,7 +230,15 @@ where
     const OPCODE: RequestOpcode = RequestOpcode::Batch;
 
     fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
-        self.do_serialize(buf)?;
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.do_serialize(version, buf)?;
         Ok(())
     }
 }
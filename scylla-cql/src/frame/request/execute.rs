@@ -9,6 +9,7 @@ use thiserror::Error;
 use crate::{
     frame::request::{query, RequestOpcode, SerializableRequest},
     frame::types,
+    frame::ProtocolVersion,
 };
 
 use super::{
@@ -31,13 +32,90 @@ impl SerializableRequest for Execute<'_> {
     const OPCODE: RequestOpcode = RequestOpcode::Execute;
 
     fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.do_serialize(version, &[], buf)?;
+        Ok(())
+    }
+}
+
+/// An `EXECUTE` request carrying the ID of the result metadata of the prepared statement,
+/// as returned by the server when preparing the statement (see [ResultMetadata::id]).
+///
+/// The server compares it with the ID of the current result metadata, and if they
+/// differ (e.g. because the table was altered), it sends the new metadata along with the rows.
+/// The ID is sent only in protocol v5 and newer; a plain [Execute] sends an empty one.
+///
+/// [ResultMetadata::id]: crate::frame::response::result::ResultMetadata::id
+pub struct ExecuteWithResultMetadataId<'a, 'e> {
+    execute: &'a Execute<'e>,
+    result_metadata_id: &'a [u8],
+}
+
+impl<'a, 'e> ExecuteWithResultMetadataId<'a, 'e> {
+    /// Attaches the result metadata ID to the request.
+    pub fn new(execute: &'a Execute<'e>, result_metadata_id: &'a [u8]) -> Self {
+        Self {
+            execute,
+            result_metadata_id,
+        }
+    }
+
+    /// Returns the wrapped request.
+    pub fn execute(&self) -> &'a Execute<'e> {
+        self.execute
+    }
+
+    /// Returns the ID of the result metadata of the prepared statement.
+    pub fn result_metadata_id(&self) -> &'a [u8] {
+        self.result_metadata_id
+    }
+}
+
+impl SerializableRequest for ExecuteWithResultMetadataId<'_, '_> {
+    const OPCODE: RequestOpcode = RequestOpcode::Execute;
+
+    fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.execute
+            .do_serialize(version, self.result_metadata_id, buf)?;
+        Ok(())
+    }
+}
+
+impl Execute<'_> {
+    fn do_serialize(
+        &self,
+        version: ProtocolVersion,
+        result_metadata_id: &[u8],
+        buf: &mut Vec<u8>,
+    ) -> Result<(), ExecuteSerializationError> {
         // Serializing statement id
         types::write_short_bytes(&self.id[..], buf)
             .map_err(ExecuteSerializationError::StatementIdSerialization)?;
 
+        // Serializing result metadata id
+        if version >= ProtocolVersion::V5 {
+            types::write_short_bytes(result_metadata_id, buf)
+                .map_err(ExecuteSerializationError::BadResultMetadataId)?;
+        }
+
         // Serializing params
         self.parameters
-            .serialize(buf)
+            .serialize_for_version(version, buf)
             .map_err(ExecuteSerializationError::QueryParametersSerialization)?;
         Ok(())
     }
@@ -63,4 +141,8 @@ pub enum ExecuteSerializationError {
     /// Failed to serialize prepared statement id.
     #[error("Malformed statement id: {0}")]
     StatementIdSerialization(TryFromIntError),
+
+    /// Failed to serialize prepared statement's result metadata id.
+    #[error("Malformed result metadata id: {0}")]
+    BadResultMetadataId(TryFromIntError),
 }
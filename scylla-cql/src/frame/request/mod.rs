@@ -28,7 +28,7 @@ use self::batch::BatchStatement;
 
 use super::frame_errors::{CqlRequestSerializationError, LowLevelDeserializationError};
 use super::types::SerialConsistency;
-use super::TryFromPrimitiveError;
+use super::{ProtocolVersion, TryFromPrimitiveError};
 
 /// Possible requests sent by the client.
 // Why is it distinct from [RequestOpcode]?
@@ -154,6 +154,20 @@ pub trait SerializableRequest {
     /// Serializes the request into the provided buffer.
     fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError>;
 
+    /// Serializes the request into the provided buffer, using the body format
+    /// of the given protocol version.
+    ///
+    /// Only requests whose body format differs between protocol versions
+    /// need to override this. By default, it falls back to [`Self::serialize`].
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        let _ = version;
+        self.serialize(buf)
+    }
+
     /// Serializes the request into a heap-allocated `Bytes` object.
     fn to_bytes(&self) -> Result<Bytes, CqlRequestSerializationError> {
         let mut v = Vec::new();
@@ -162,6 +176,33 @@ pub trait SerializableRequest {
     }
 }
 
+/// A request executed in the given keyspace, overriding the keyspace set
+/// on the connection with `USE`.
+///
+/// Supported for [Query] and [Prepare] requests. Requires protocol v5 - serializing
+/// the request for an older protocol version results in an error.
+pub struct WithKeyspace<'a, R> {
+    request: &'a R,
+    keyspace: &'a str,
+}
+
+impl<'a, R> WithKeyspace<'a, R> {
+    /// Wraps the request so that it is executed in the given keyspace.
+    pub fn new(request: &'a R, keyspace: &'a str) -> Self {
+        Self { request, keyspace }
+    }
+
+    /// Returns the wrapped request.
+    pub fn request(&self) -> &'a R {
+        self.request
+    }
+
+    /// Returns the keyspace in which the request is executed.
+    pub fn keyspace(&self) -> &'a str {
+        self.keyspace
+    }
+}
+
 /// Requests that can be deserialized from a CQL frame.
 ///
 /// Not intended for driver's direct usage (as driver has no interest in deserialising CQL requests),
@@ -256,12 +297,14 @@ mod tests {
         frame::{
             request::{
                 batch::{Batch, BatchStatement, BatchType},
-                execute::Execute,
+                execute::{Execute, ExecuteWithResultMetadataId},
+                prepare::Prepare,
                 query::{Query, QueryParameters},
-                DeserializableRequest, SerializableRequest,
+                DeserializableRequest, SerializableRequest, WithKeyspace,
             },
             response::result::{ColumnType, NativeType},
             types::{self, SerialConsistency},
+            ProtocolVersion,
         },
         Consistency,
     };
@@ -439,4 +482,89 @@ mod tests {
             let _parse_error = Batch::deserialize(&mut &buf[..]).unwrap_err();
         }
     }
+
+    #[test]
+    fn v5_serialization_with_keyspace() {
+        let query = Query {
+            contents: Cow::Borrowed("SELECT * FROM t"),
+            parameters: QueryParameters::default(),
+        };
+        let query = WithKeyspace::new(&query, "ks");
+        let prepare = Prepare {
+            query: "SELECT * FROM t",
+        };
+        let prepare = WithKeyspace::new(&prepare, "ks");
+
+        // Keyspace can't be sent in protocol v4.
+        query
+            .serialize_for_version(ProtocolVersion::V4, &mut Vec::new())
+            .unwrap_err();
+        prepare
+            .serialize_for_version(ProtocolVersion::V4, &mut Vec::new())
+            .unwrap_err();
+
+        // In v5, flags are 4 bytes long and the keyspace follows the other parameters.
+        let mut buf = Vec::new();
+        query
+            .serialize_for_version(ProtocolVersion::V5, &mut buf)
+            .unwrap();
+        let mut buf_ptr = buf.as_slice();
+        assert_eq!(
+            types::read_long_string(&mut buf_ptr).unwrap(),
+            "SELECT * FROM t"
+        );
+        assert_eq!(
+            types::read_consistency(&mut buf_ptr).unwrap(),
+            Consistency::default()
+        );
+        assert_eq!(types::read_int(&mut buf_ptr).unwrap(), 0x80);
+        assert_eq!(types::read_string(&mut buf_ptr).unwrap(), "ks");
+        assert!(buf_ptr.is_empty());
+
+        let mut buf = Vec::new();
+        prepare
+            .serialize_for_version(ProtocolVersion::V5, &mut buf)
+            .unwrap();
+        let mut buf_ptr = buf.as_slice();
+        assert_eq!(
+            types::read_long_string(&mut buf_ptr).unwrap(),
+            "SELECT * FROM t"
+        );
+        assert_eq!(types::read_int(&mut buf_ptr).unwrap(), 0x01);
+        assert_eq!(types::read_string(&mut buf_ptr).unwrap(), "ks");
+        assert!(buf_ptr.is_empty());
+
+        // Without a keyspace, v5 PREPARE only carries empty flags.
+        let mut buf = Vec::new();
+        prepare
+            .request()
+            .serialize_for_version(ProtocolVersion::V5, &mut buf)
+            .unwrap();
+        let mut buf_ptr = buf.as_slice();
+        types::read_long_string(&mut buf_ptr).unwrap();
+        assert_eq!(types::read_int(&mut buf_ptr).unwrap(), 0);
+        assert!(buf_ptr.is_empty());
+
+        // Execute carries the result metadata id in v5 only.
+        let execute = Execute {
+            id: Bytes::from_static(&[1, 2, 3]),
+            parameters: QueryParameters::default(),
+        };
+        let execute_with_id = ExecuteWithResultMetadataId::new(&execute, &[4, 5]);
+        let mut buf = Vec::new();
+        execute_with_id
+            .serialize_for_version(ProtocolVersion::V5, &mut buf)
+            .unwrap();
+        let mut buf_ptr = buf.as_slice();
+        assert_eq!(types::read_short_bytes(&mut buf_ptr).unwrap(), &[1, 2, 3]);
+        assert_eq!(types::read_short_bytes(&mut buf_ptr).unwrap(), &[4, 5]);
+
+        let mut buf = Vec::new();
+        execute_with_id
+            .serialize_for_version(ProtocolVersion::V4, &mut buf)
+            .unwrap();
+        let mut plain_buf = Vec::new();
+        execute.serialize(&mut plain_buf).unwrap();
+        assert_eq!(buf, plain_buf);
+    }
 }
@@ -7,10 +7,14 @@ use thiserror::Error;
 use crate::frame::frame_errors::CqlRequestSerializationError;
 
 use crate::{
-    frame::request::{RequestOpcode, SerializableRequest},
+    frame::request::{RequestOpcode, SerializableRequest, WithKeyspace},
     frame::types,
+    frame::ProtocolVersion,
 };
 
+// Prepare flags (protocol v5 only)
+const FLAG_WITH_KEYSPACE: u32 = 0x01;
+
 /// CQL protocol-level representation of an `PREPARE` request,
 /// used to prepare a single statement for further execution.
 pub struct Prepare<'a> {
@@ -22,8 +26,62 @@ impl SerializableRequest for Prepare<'_> {
     const OPCODE: RequestOpcode = RequestOpcode::Prepare;
 
     fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.do_serialize(version, None, buf)?;
+        Ok(())
+    }
+}
+
+impl SerializableRequest for WithKeyspace<'_, Prepare<'_>> {
+    const OPCODE: RequestOpcode = RequestOpcode::Prepare;
+
+    fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.request()
+            .do_serialize(version, Some(self.keyspace()), buf)?;
+        Ok(())
+    }
+}
+
+impl Prepare<'_> {
+    fn do_serialize(
+        &self,
+        version: ProtocolVersion,
+        keyspace: Option<&str>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), PrepareSerializationError> {
         types::write_long_string(self.query, buf)
             .map_err(PrepareSerializationError::StatementStringSerialization)?;
+
+        if version >= ProtocolVersion::V5 {
+            let flags = if keyspace.is_some() {
+                FLAG_WITH_KEYSPACE
+            } else {
+                0
+            };
+            types::write_int(flags as i32, buf);
+            if let Some(keyspace) = keyspace {
+                types::write_string(keyspace, buf)
+                    .map_err(PrepareSerializationError::KeyspaceSerialization)?;
+            }
+        } else if keyspace.is_some() {
+            return Err(PrepareSerializationError::KeyspaceNotSupported(version));
+        }
+
         Ok(())
     }
 }
@@ -35,4 +93,12 @@ pub enum PrepareSerializationError {
     /// Failed to serialize the CQL statement string.
     #[error("Failed to serialize statement contents: {0}")]
     StatementStringSerialization(TryFromIntError),
+
+    /// Failed to serialize the keyspace name.
+    #[error("Failed to serialize keyspace name: {0}")]
+    KeyspaceSerialization(TryFromIntError),
+
+    /// A per-request keyspace was set, but the protocol version does not support it.
+    #[error("Setting a per-request keyspace requires protocol v5 or newer, but {0} is used")]
+    KeyspaceNotSupported(ProtocolVersion),
 }
@@ -2,13 +2,15 @@
 
 use std::{borrow::Cow, num::TryFromIntError, ops::ControlFlow, sync::Arc};
 
-use crate::frame::{frame_errors::CqlRequestSerializationError, types::SerialConsistency};
+use crate::frame::{
+    frame_errors::CqlRequestSerializationError, types::SerialConsistency, ProtocolVersion,
+};
 use crate::serialize::row::SerializedValues;
 use bytes::{Buf, BufMut};
 use thiserror::Error;
 
 use crate::{
-    frame::request::{RequestOpcode, SerializableRequest},
+    frame::request::{RequestOpcode, SerializableRequest, WithKeyspace},
     frame::types,
 };
 
@@ -22,6 +24,8 @@ const FLAG_WITH_PAGING_STATE: u8 = 0x08;
 const FLAG_WITH_SERIAL_CONSISTENCY: u8 = 0x10;
 const FLAG_WITH_DEFAULT_TIMESTAMP: u8 = 0x20;
 const FLAG_WITH_NAMES_FOR_VALUES: u8 = 0x40;
+// Protocol v5 only; v5 extends the flags to 4 bytes.
+const FLAG_WITH_KEYSPACE: u32 = 0x80;
 const ALL_FLAGS: u8 = FLAG_VALUES
     | FLAG_SKIP_METADATA
     | FLAG_PAGE_SIZE
@@ -45,10 +49,48 @@ impl SerializableRequest for Query<'_> {
     const OPCODE: RequestOpcode = RequestOpcode::Query;
 
     fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.do_serialize(version, None, buf)?;
+        Ok(())
+    }
+}
+
+impl SerializableRequest for WithKeyspace<'_, Query<'_>> {
+    const OPCODE: RequestOpcode = RequestOpcode::Query;
+
+    fn serialize(&self, buf: &mut Vec<u8>) -> Result<(), CqlRequestSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), CqlRequestSerializationError> {
+        self.request()
+            .do_serialize(version, Some(self.keyspace()), buf)?;
+        Ok(())
+    }
+}
+
+impl Query<'_> {
+    fn do_serialize(
+        &self,
+        version: ProtocolVersion,
+        keyspace: Option<&str>,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), QuerySerializationError> {
         types::write_long_string(&self.contents, buf)
             .map_err(QuerySerializationError::StatementStringSerialization)?;
         self.parameters
-            .serialize(buf)
+            .do_serialize(version, keyspace, buf)
             .map_err(QuerySerializationError::QueryParametersSerialization)?;
         Ok(())
     }
@@ -119,11 +161,37 @@ impl QueryParameters<'_> {
         &self,
         buf: &mut impl BufMut,
     ) -> Result<(), QueryParametersSerializationError> {
+        self.serialize_for_version(ProtocolVersion::V4, buf)
+    }
+
+    /// Serializes the parameters into the provided buffer,
+    /// using the format of the given protocol version.
+    pub fn serialize_for_version(
+        &self,
+        version: ProtocolVersion,
+        buf: &mut impl BufMut,
+    ) -> Result<(), QueryParametersSerializationError> {
+        self.do_serialize(version, None, buf)
+    }
+
+    // The keyspace is set only by a QUERY request wrapped in [WithKeyspace].
+    fn do_serialize(
+        &self,
+        version: ProtocolVersion,
+        keyspace: Option<&str>,
+        buf: &mut impl BufMut,
+    ) -> Result<(), QueryParametersSerializationError> {
+        if keyspace.is_some() && version < ProtocolVersion::V5 {
+            return Err(QueryParametersSerializationError::KeyspaceNotSupported(
+                version,
+            ));
+        }
+
         types::write_consistency(self.consistency, buf);
 
         let paging_state_bytes = self.paging_state.as_bytes_slice();
 
-        let mut flags = 0;
+        let mut flags: u8 = 0;
         if !self.values.is_empty() {
             flags |= FLAG_VALUES;
         }
@@ -148,7 +216,15 @@ impl QueryParameters<'_> {
             flags |= FLAG_WITH_DEFAULT_TIMESTAMP;
         }
 
-        buf.put_u8(flags);
+        if version >= ProtocolVersion::V5 {
+            let mut flags = flags as u32;
+            if keyspace.is_some() {
+                flags |= FLAG_WITH_KEYSPACE;
+            }
+            buf.put_u32(flags);
+        } else {
+            buf.put_u8(flags);
+        }
 
         if !self.values.is_empty() {
             self.values.write_to_request(buf);
@@ -170,6 +246,11 @@ impl QueryParameters<'_> {
             types::write_long(timestamp, buf);
         }
 
+        if let Some(keyspace) = keyspace {
+            types::write_string(keyspace, buf)
+                .map_err(QueryParametersSerializationError::BadKeyspaceName)?;
+        }
+
         Ok(())
     }
 }
@@ -345,4 +426,12 @@ pub enum QueryParametersSerializationError {
     /// Failed to serialize paging state.
     #[error("Malformed paging state: {0}")]
     BadPagingState(#[from] TryFromIntError),
+
+    /// Failed to serialize the keyspace name.
+    #[error("Malformed keyspace name: {0}")]
+    BadKeyspaceName(TryFromIntError),
+
+    /// A per-request keyspace was set, but the protocol version does not support it.
+    #[error("Setting a per-request keyspace requires protocol v5 or newer, but {0} is used")]
+    KeyspaceNotSupported(ProtocolVersion),
 }
@@ -3,6 +3,7 @@
 use crate::frame::frame_errors::{CqlErrorParseError, LowLevelDeserializationError};
 use crate::frame::protocol_features::ProtocolFeatures;
 use crate::frame::types;
+use crate::frame::ProtocolVersion;
 use crate::Consistency;
 use byteorder::ReadBytesExt;
 use bytes::Bytes;
@@ -31,11 +32,46 @@ fn make_error_field_err(
     }
 }
 
+// Reads the number of replicas which failed to execute the request.
+// Since protocol v5, the servers sends a map from replica addresses to failure codes instead.
+fn read_num_failures(
+    buf: &mut &[u8],
+    version: ProtocolVersion,
+    db_error: &'static str,
+) -> Result<i32, CqlErrorParseError> {
+    if version < ProtocolVersion::V5 {
+        return types::read_int(buf)
+            .map_err(|err| make_error_field_err(db_error, "NUM_FAILURES", err));
+    }
+
+    let num_failures =
+        types::read_int(buf).map_err(|err| make_error_field_err(db_error, "REASON_MAP", err))?;
+    for _ in 0..num_failures {
+        let addr_len = buf
+            .read_u8()
+            .map_err(|err| make_error_field_err(db_error, "REASON_MAP", err))?;
+        types::read_raw_bytes(addr_len as usize, buf)
+            .map_err(|err| make_error_field_err(db_error, "REASON_MAP", err))?;
+        types::read_short(buf).map_err(|err| make_error_field_err(db_error, "REASON_MAP", err))?;
+    }
+    Ok(num_failures)
+}
+
 impl Error {
     /// Deserializes the error response from the provided buffer.
     pub fn deserialize(
         features: &ProtocolFeatures,
         buf: &mut &[u8],
+    ) -> Result<Self, CqlErrorParseError> {
+        Self::deserialize_for_version(features, ProtocolVersion::V4, buf)
+    }
+
+    /// Deserializes the error response from the provided buffer,
+    /// using the format of the given protocol version.
+    pub fn deserialize_for_version(
+        features: &ProtocolFeatures,
+        version: ProtocolVersion,
+        buf: &mut &[u8],
     ) -> Result<Self, CqlErrorParseError> {
         let code = types::read_int(buf)
             .map_err(|err| CqlErrorParseError::ErrorCodeParseError(err.into()))?;
@@ -58,18 +94,34 @@ impl Error {
             0x1001 => DbError::Overloaded,
             0x1002 => DbError::IsBootstrapping,
             0x1003 => DbError::TruncateError,
-            0x1100 => DbError::WriteTimeout {
-                consistency: types::read_consistency(buf)
-                    .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "CONSISTENCY", err))?,
-                received: types::read_int(buf)
-                    .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "RECEIVED", err))?,
-                required: types::read_int(buf)
-                    .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "REQUIRED", err))?,
-                write_type: WriteType::from(
-                    types::read_string(buf)
-                        .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "WRITE_TYPE", err))?,
-                ),
-            },
+            0x1100 => {
+                let error = DbError::WriteTimeout {
+                    consistency: types::read_consistency(buf)
+                        .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "CONSISTENCY", err))?,
+                    received: types::read_int(buf)
+                        .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "RECEIVED", err))?,
+                    required: types::read_int(buf)
+                        .map_err(|err| make_error_field_err("WRITE_TIMEOUT", "REQUIRED", err))?,
+                    write_type: WriteType::from(
+                        types::read_string(buf).map_err(|err| {
+                            make_error_field_err("WRITE_TIMEOUT", "WRITE_TYPE", err)
+                        })?,
+                    ),
+                };
+                // Since protocol v5, the number of contentions is sent for CAS writes.
+                if let DbError::WriteTimeout {
+                    write_type: WriteType::Cas,
+                    ..
+                } = error
+                {
+                    if version >= ProtocolVersion::V5 {
+                        types::read_short(buf).map_err(|err| {
+                            make_error_field_err("WRITE_TIMEOUT", "CONTENTIONS", err)
+                        })?;
+                    }
+                }
+                error
+            }
             0x1200 => DbError::ReadTimeout {
                 consistency: types::read_consistency(buf)
                     .map_err(|err| make_error_field_err("READ_TIMEOUT", "CONSISTENCY", err))?,
@@ -89,8 +141,7 @@ impl Error {
                     .map_err(|err| make_error_field_err("READ_FAILURE", "RECEIVED", err))?,
                 required: types::read_int(buf)
                     .map_err(|err| make_error_field_err("READ_FAILURE", "REQUIRED", err))?,
-                numfailures: types::read_int(buf)
-                    .map_err(|err| make_error_field_err("READ_FAILURE", "NUM_FAILURES", err))?,
+                numfailures: read_num_failures(buf, version, "READ_FAILURE")?,
                 data_present: buf
                     .read_u8()
                     .map_err(|err| make_error_field_err("READ_FAILURE", "DATA_PRESENT", err))?
@@ -113,8 +164,7 @@ impl Error {
                     .map_err(|err| make_error_field_err("WRITE_FAILURE", "RECEIVED", err))?,
                 required: types::read_int(buf)
                     .map_err(|err| make_error_field_err("WRITE_FAILURE", "REQUIRED", err))?,
-                numfailures: types::read_int(buf)
-                    .map_err(|err| make_error_field_err("WRITE_FAILURE", "NUM_FAILURES", err))?,
+                numfailures: read_num_failures(buf, version, "WRITE_FAILURE")?,
                 write_type: WriteType::from(
                     types::read_string(buf)
                         .map_err(|err| make_error_field_err("WRITE_FAILURE", "WRITE_TYPE", err))?,
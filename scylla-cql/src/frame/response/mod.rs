@@ -14,7 +14,7 @@ pub use supported::Supported;
 
 use crate::frame::protocol_features::ProtocolFeatures;
 use crate::frame::response::result::ResultMetadata;
-use crate::frame::TryFromPrimitiveError;
+use crate::frame::{ProtocolVersion, TryFromPrimitiveError};
 
 use super::frame_errors::CqlResponseParseError;
 
@@ -184,18 +184,39 @@ impl Response {
         opcode: ResponseOpcode,
         buf_bytes: bytes::Bytes,
         cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+    ) -> Result<Response, CqlResponseParseError> {
+        Self::deserialize_for_version(
+            features,
+            ProtocolVersion::V4,
+            opcode,
+            buf_bytes,
+            cached_metadata,
+        )
+    }
+
+    /// Deserialize a response from the given bytes, using the format of the given protocol version.
+    pub fn deserialize_for_version(
+        features: &ProtocolFeatures,
+        version: ProtocolVersion,
+        opcode: ResponseOpcode,
+        buf_bytes: bytes::Bytes,
+        cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
     ) -> Result<Response, CqlResponseParseError> {
         let buf = &mut &*buf_bytes;
         let response = match opcode {
-            ResponseOpcode::Error => Response::Error(Error::deserialize(features, buf)?),
+            ResponseOpcode::Error => {
+                Response::Error(Error::deserialize_for_version(features, version, buf)?)
+            }
             ResponseOpcode::Ready => Response::Ready,
             ResponseOpcode::Authenticate => {
                 Response::Authenticate(authenticate::Authenticate::deserialize(buf)?)
             }
             ResponseOpcode::Supported => Response::Supported(Supported::deserialize(buf)?),
-            ResponseOpcode::Result => {
-                Response::Result(result::deserialize(buf_bytes, cached_metadata)?)
-            }
+            ResponseOpcode::Result => Response::Result(result::deserialize_for_version(
+                buf_bytes,
+                version,
+                cached_metadata,
+            )?),
             ResponseOpcode::Event => Response::Event(event::Event::deserialize(buf)?),
             ResponseOpcode::AuthChallenge => {
                 Response::AuthChallenge(authenticate::AuthChallenge::deserialize(buf)?)
@@ -14,9 +14,10 @@ use crate::frame::frame_errors::{
 use crate::frame::request::query::PagingStateResponse;
 use crate::frame::response::event::SchemaChangeEvent;
 use crate::frame::types;
+use crate::frame::ProtocolVersion;
 use bytes::{Buf, Bytes};
 use std::borrow::Cow;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 use std::sync::Arc;
 use std::{result::Result as StdResult, str};
 
@@ -266,6 +267,77 @@ impl ColumnType<'_> {
     }
 }
 
+/// Formats the type in the CQL syntax, e.g. `map<text, frozen<list<int>>>`.
+///
+/// User-defined types are formatted with their keyspace, e.g. `ks.my_udt`.
+impl Display for ColumnType<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let frozen = match self {
+            ColumnType::Collection { frozen, .. } | ColumnType::UserDefinedType { frozen, .. } => {
+                *frozen
+            }
+            _ => false,
+        };
+        if frozen {
+            f.write_str("frozen<")?;
+        }
+        match self {
+            ColumnType::Native(native) => write!(f, "{}", native)?,
+            ColumnType::Collection { typ, .. } => match typ {
+                CollectionType::List(elem) => write!(f, "list<{}>", elem)?,
+                CollectionType::Set(elem) => write!(f, "set<{}>", elem)?,
+                CollectionType::Map(key, value) => write!(f, "map<{}, {}>", key, value)?,
+            },
+            ColumnType::Vector { typ, dimensions } => write!(f, "vector<{}, {}>", typ, dimensions)?,
+            ColumnType::UserDefinedType { definition, .. } => {
+                write!(f, "{}.{}", definition.keyspace, definition.name)?
+            }
+            ColumnType::Tuple(elems) => {
+                f.write_str("tuple<")?;
+                for (i, elem) in elems.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", elem)?;
+                }
+                f.write_str(">")?;
+            }
+        }
+        if frozen {
+            f.write_str(">")?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the type with its CQL name, e.g. `bigint`.
+impl Display for NativeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            NativeType::Ascii => "ascii",
+            NativeType::Boolean => "boolean",
+            NativeType::Blob => "blob",
+            NativeType::Counter => "counter",
+            NativeType::Date => "date",
+            NativeType::Decimal => "decimal",
+            NativeType::Double => "double",
+            NativeType::Duration => "duration",
+            NativeType::Float => "float",
+            NativeType::Int => "int",
+            NativeType::BigInt => "bigint",
+            NativeType::Text => "text",
+            NativeType::Timestamp => "timestamp",
+            NativeType::Inet => "inet",
+            NativeType::SmallInt => "smallint",
+            NativeType::TinyInt => "tinyint",
+            NativeType::Time => "time",
+            NativeType::Timeuuid => "timeuuid",
+            NativeType::Uuid => "uuid",
+            NativeType::Varint => "varint",
+        })
+    }
+}
+
 impl CollectionType<'_> {
     /// Converts a [CollectionType] to an owned version, where all
     /// references are replaced with owned values.
@@ -284,6 +356,13 @@ impl CollectionType<'_> {
     }
 }
 
+/// Formats the table as `keyspace.table`.
+impl Display for TableSpec<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.ks_name, self.table_name)
+    }
+}
+
 impl<'a> TableSpec<'a> {
     /// Creates a new borrowed [TableSpec] with the given keyspace and table names.
     pub const fn borrowed(ks: &'a str, table: &'a str) -> Self {
@@ -413,6 +492,7 @@ impl<'frame> ColumnSpec<'frame> {
 pub struct ResultMetadata<'a> {
     col_count: usize,
     col_specs: Vec<ColumnSpec<'a>>,
+    id: Option<Bytes>,
 }
 
 impl<'a> ResultMetadata<'a> {
@@ -428,6 +508,16 @@ impl<'a> ResultMetadata<'a> {
         &self.col_specs
     }
 
+    /// Retrieves the ID of the result metadata of a prepared statement,
+    /// sent by the server since protocol v5.
+    ///
+    /// It must be passed back when executing the statement, see
+    /// [ExecuteWithResultMetadataId](crate::frame::request::execute::ExecuteWithResultMetadataId).
+    #[inline]
+    pub fn id(&self) -> Option<&Bytes> {
+        self.id.as_ref()
+    }
+
     /// Creates a new mock empty [ResultMetadata] with 0 columns.
     /// Used only for testing purposes.
     // Preferred to implementing Default, because users shouldn't be encouraged to create
@@ -437,6 +527,7 @@ impl<'a> ResultMetadata<'a> {
         Self {
             col_count: 0,
             col_specs: Vec::new(),
+            id: None,
         }
     }
 }
@@ -989,6 +1080,7 @@ fn deser_result_metadata(
     let metadata = ResultMetadata {
         col_count,
         col_specs,
+        id: None,
     };
     Ok((metadata, paging_state))
 }
@@ -1005,6 +1097,8 @@ impl RawMetadataAndRawRows {
         let global_tables_spec = flags & 0x0001 != 0;
         let has_more_pages = flags & 0x0002 != 0;
         let no_metadata = flags & 0x0004 != 0;
+        // Protocol v5 only.
+        let metadata_changed = flags & 0x0008 != 0;
 
         let col_count = types::read_int_length(frame.as_slice_mut())
             .map_err(RawRowsAndPagingStateResponseParseError::ColumnCountParseError)?;
@@ -1016,6 +1110,13 @@ impl RawMetadataAndRawRows {
             })
             .transpose()?;
 
+        if metadata_changed {
+            // The new metadata itself follows and is always preferred over the cached one,
+            // so the new metadata id is not needed.
+            types::read_short_bytes(frame.as_slice_mut())
+                .map_err(RawRowsAndPagingStateResponseParseError::NewMetadataIdParseError)?;
+        }
+
         let paging_state = PagingStateResponse::new_from_raw_bytes(raw_paging_state);
 
         let raw_rows = Self {
@@ -1057,6 +1158,7 @@ impl RawMetadataAndRawRows {
                 ResultMetadata {
                     col_count,
                     col_specs,
+                    id: None,
                 }
             };
             Ok(server_metadata)
@@ -1177,16 +1279,24 @@ fn deser_set_keyspace(buf: &mut &[u8]) -> StdResult<SetKeyspace, SetKeyspacePars
     Ok(SetKeyspace { keyspace_name })
 }
 
-fn deser_prepared(buf: &mut &[u8]) -> StdResult<Prepared, PreparedParseError> {
+fn deser_prepared(
+    buf: &mut &[u8],
+    version: ProtocolVersion,
+) -> StdResult<Prepared, PreparedParseError> {
     let id_len = types::read_short(buf)
         .map_err(|err| PreparedParseError::IdLengthParseError(err.into()))?
         as usize;
     let id: Bytes = buf[0..id_len].to_owned().into();
     buf.advance(id_len);
+    let result_metadata_id = (version >= ProtocolVersion::V5)
+        .then(|| types::read_short_bytes(buf).map(Bytes::copy_from_slice))
+        .transpose()
+        .map_err(PreparedParseError::ResultMetadataIdParseError)?;
     let prepared_metadata =
         deser_prepared_metadata(buf).map_err(PreparedParseError::PreparedMetadataParseError)?;
-    let (result_metadata, paging_state_response) =
+    let (mut result_metadata, paging_state_response) =
         deser_result_metadata(buf).map_err(PreparedParseError::ResultMetadataParseError)?;
+    result_metadata.id = result_metadata_id;
     if let PagingStateResponse::HasMorePages { state } = paging_state_response {
         return Err(PreparedParseError::NonZeroPagingState(
             state
@@ -1215,6 +1325,18 @@ fn deser_schema_change(buf: &mut &[u8]) -> StdResult<SchemaChange, SchemaChangeE
 pub fn deserialize(
     buf_bytes: Bytes,
     cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+) -> StdResult<Result, CqlResultParseError> {
+    deserialize_for_version(buf_bytes, ProtocolVersion::V4, cached_metadata)
+}
+
+/// Deserializes a CQL `RESULT` response from the provided buffer,
+/// using the format of the given protocol version.
+///
+/// Reuses cached metadata if provided, otherwise deserializes it.
+pub fn deserialize_for_version(
+    buf_bytes: Bytes,
+    version: ProtocolVersion,
+    cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
 ) -> StdResult<Result, CqlResultParseError> {
     let buf = &mut &*buf_bytes;
     use self::Result::*;
@@ -1225,7 +1347,7 @@ pub fn deserialize(
             0x0001 => Void,
             0x0002 => Rows(deser_rows(buf_bytes.slice_ref(buf), cached_metadata)?),
             0x0003 => SetKeyspace(deser_set_keyspace(buf)?),
-            0x0004 => Prepared(deser_prepared(buf)?),
+            0x0004 => Prepared(deser_prepared(buf, version)?),
             0x0005 => SchemaChange(deser_schema_change(buf)?),
             id => return Err(CqlResultParseError::UnknownResultId(id)),
         },
@@ -1369,6 +1491,7 @@ mod test_utils {
             Self {
                 col_count,
                 col_specs,
+                id: None,
             }
         }
 
@@ -1465,3 +1588,65 @@ mod test_utils {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deser_prepared_v5_reads_result_metadata_id() {
+        let mut buf = Vec::new();
+        types::write_short_bytes(&[1, 2, 3], &mut buf).unwrap();
+        types::write_short_bytes(&[4, 5], &mut buf).unwrap();
+        // Prepared metadata: flags, column count, partition key count.
+        types::write_int(0, &mut buf);
+        types::write_int(0, &mut buf);
+        types::write_int(0, &mut buf);
+        // Result metadata: flags, column count.
+        types::write_int(0, &mut buf);
+        types::write_int(0, &mut buf);
+
+        let prepared = deser_prepared(&mut &buf[..], ProtocolVersion::V5).unwrap();
+        assert_eq!(prepared.id, &[1, 2, 3][..]);
+        assert_eq!(
+            prepared.result_metadata.id().map(|id| &id[..]),
+            Some(&[4, 5][..])
+        );
+    }
+
+    #[test]
+    fn column_type_display() {
+        let udt = ColumnType::UserDefinedType {
+            frozen: true,
+            definition: Arc::new(UserDefinedType {
+                name: "address".into(),
+                keyspace: "ks".into(),
+                field_types: vec![("street".into(), ColumnType::Native(NativeType::Text))],
+            }),
+        };
+        let typ = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::Map(
+                Box::new(ColumnType::Native(NativeType::Timeuuid)),
+                Box::new(ColumnType::Collection {
+                    frozen: true,
+                    typ: CollectionType::List(Box::new(ColumnType::Tuple(vec![
+                        ColumnType::Native(NativeType::BigInt),
+                        udt,
+                    ]))),
+                }),
+            ),
+        };
+        assert_eq!(
+            typ.to_string(),
+            "map<timeuuid, frozen<list<tuple<bigint, frozen<ks.address>>>>>"
+        );
+
+        let vector = ColumnType::Vector {
+            typ: Box::new(ColumnType::Native(NativeType::Float)),
+            dimensions: 3,
+        };
+        assert_eq!(vector.to_string(), "vector<float, 3>");
+        assert_eq!(TableSpec::borrowed("ks", "tab").to_string(), "ks.tab");
+    }
+}
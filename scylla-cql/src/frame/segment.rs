@@ -0,0 +1,430 @@
+//! Framing of frames into segments, introduced in protocol v5.
+//!
+//! Once the server responds to STARTUP on a protocol v5 connection (with either READY
+//! or AUTHENTICATE), frames are no longer sent over the wire directly.
+//! Instead, they are wrapped into segments, whose header and payload are protected
+//! by checksums (CRC24 and CRC32, respectively). If compression was negotiated,
+//! segment payloads are compressed with LZ4 - frames themselves are then never compressed.
+//!
+//! A segment is either self-contained, i.e. it contains one or more complete frames,
+//! or it carries a part of a single frame which is too large to fit in one segment.
+//!
+//! See [the protocol specification](https://github.com/apache/cassandra/blob/trunk/doc/native_protocol_v5.spec#L260)
+//! for details.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::frame_errors::{FrameHeaderParseError, SegmentParseError};
+use super::response::ResponseOpcode;
+use super::{parse_response_frame_header, FrameParams, HEADER_SIZE};
+
+use std::sync::Arc;
+
+/// The maximal length of a segment payload (before compression).
+pub const MAX_SEGMENT_PAYLOAD_LEN: usize = (1 << 17) - 1;
+
+const UNCOMPRESSED_HEADER_LEN: usize = 3;
+const COMPRESSED_HEADER_LEN: usize = 5;
+const HEADER_CRC_LEN: usize = 3;
+const PAYLOAD_CRC_LEN: usize = 4;
+
+const CRC24_INIT: u32 = 0x875060;
+const CRC24_POLY: u32 = 0x1974F0B;
+
+// Bytes which the payload CRC32 is initialized with, so that a payload of zeros
+// does not result in a zero checksum.
+const CRC32_INITIAL_BYTES: [u8; 4] = [0xFA, 0x2D, 0x55, 0xCA];
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB88320
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+// CRC24 of the first `len` bytes of `bytes`, processed from the least significant one.
+fn crc24(mut bytes: u64, len: usize) -> u32 {
+    let mut crc = CRC24_INIT;
+    for _ in 0..len {
+        crc ^= ((bytes & 0xFF) as u32) << 16;
+        bytes >>= 8;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc
+}
+
+fn crc32(payload: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in CRC32_INITIAL_BYTES.iter().chain(payload) {
+        crc = CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+fn put_uint_le(value: u64, len: usize, out: &mut Vec<u8>) {
+    out.extend_from_slice(&value.to_le_bytes()[..len]);
+}
+
+fn get_uint_le(buf: &[u8]) -> u64 {
+    let mut bytes = [0u8; 8];
+    bytes[..buf.len()].copy_from_slice(buf);
+    u64::from_le_bytes(bytes)
+}
+
+/// Writes a single segment carrying `payload` into `out`.
+///
+/// If `lz4` is set (i.e. LZ4 compression was negotiated), the segment is written
+/// in the compressed format. The payload itself is compressed only if it is at least
+/// `compression_threshold` bytes long and compression actually makes it smaller.
+///
+/// # Panics
+/// Panics if the payload is longer than [MAX_SEGMENT_PAYLOAD_LEN].
+pub fn write_segment(
+    payload: &[u8],
+    self_contained: bool,
+    lz4: bool,
+    compression_threshold: usize,
+    out: &mut Vec<u8>,
+) {
+    assert!(
+        payload.len() <= MAX_SEGMENT_PAYLOAD_LEN,
+        "Segment payload too large: {} bytes",
+        payload.len()
+    );
+
+    if lz4 {
+        let compressed = (payload.len() >= compression_threshold)
+            .then(|| lz4_flex::compress(payload))
+            .filter(|compressed| compressed.len() < payload.len());
+        // Uncompressed length of 0 denotes a payload which is not compressed.
+        let (wire_payload, uncompressed_len) = match &compressed {
+            Some(compressed) => (&compressed[..], payload.len()),
+            None => (payload, 0),
+        };
+
+        let header = wire_payload.len() as u64
+            | (uncompressed_len as u64) << 17
+            | (self_contained as u64) << 34;
+        put_uint_le(header, COMPRESSED_HEADER_LEN, out);
+        put_uint_le(
+            crc24(header, COMPRESSED_HEADER_LEN) as u64,
+            HEADER_CRC_LEN,
+            out,
+        );
+        out.extend_from_slice(wire_payload);
+        out.put_u32_le(crc32(wire_payload));
+    } else {
+        let header = payload.len() as u64 | (self_contained as u64) << 17;
+        put_uint_le(header, UNCOMPRESSED_HEADER_LEN, out);
+        put_uint_le(
+            crc24(header, UNCOMPRESSED_HEADER_LEN) as u64,
+            HEADER_CRC_LEN,
+            out,
+        );
+        out.extend_from_slice(payload);
+        out.put_u32_le(crc32(payload));
+    }
+}
+
+/// Wraps outgoing frames into segments.
+///
+/// Frames are accumulated and coalesced into self-contained segments.
+/// Frames too large to fit in one segment are split into multiple segments.
+pub struct SegmentEncoder {
+    lz4: bool,
+    compression_threshold: usize,
+    pending: Vec<u8>,
+}
+
+impl SegmentEncoder {
+    /// Creates a new encoder.
+    ///
+    /// # Parameters
+    /// - `lz4`: Whether LZ4 compression was negotiated on the connection.
+    /// - `compression_threshold`: The minimal size (in bytes) of a segment payload to compress it.
+    pub fn new(lz4: bool, compression_threshold: usize) -> Self {
+        Self {
+            lz4,
+            compression_threshold,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Adds a frame to be sent. Segments which are complete are written to `out`.
+    pub fn push_frame(&mut self, frame: &[u8], out: &mut Vec<u8>) {
+        if self.pending.len() + frame.len() > MAX_SEGMENT_PAYLOAD_LEN {
+            self.flush(out);
+        }
+
+        if frame.len() > MAX_SEGMENT_PAYLOAD_LEN {
+            for part in frame.chunks(MAX_SEGMENT_PAYLOAD_LEN) {
+                write_segment(part, false, self.lz4, self.compression_threshold, out);
+            }
+        } else {
+            self.pending.extend_from_slice(frame);
+        }
+    }
+
+    /// Writes all accumulated frames to `out`, as a self-contained segment.
+    pub fn flush(&mut self, out: &mut Vec<u8>) {
+        if !self.pending.is_empty() {
+            write_segment(
+                &self.pending,
+                true,
+                self.lz4,
+                self.compression_threshold,
+                out,
+            );
+            self.pending.clear();
+        }
+    }
+}
+
+/// Zeroizes the frames which have not been flushed yet, along with the spare capacity
+/// of the buffer, e.g. after frames carrying secrets were pushed.
+#[cfg(feature = "secrecy-08")]
+impl secrecy_08::Zeroize for SegmentEncoder {
+    fn zeroize(&mut self) {
+        self.pending.zeroize();
+    }
+}
+
+/// A segment read from the wire.
+#[derive(Debug)]
+pub struct Segment {
+    /// The payload of the segment, already decompressed.
+    pub payload: Bytes,
+
+    /// Whether the segment contains only complete frames.
+    pub self_contained: bool,
+}
+
+/// Reads a single segment from the provided reader (usually, a socket),
+/// verifies its checksums and decompresses its payload if needed.
+pub async fn read_segment(
+    reader: &mut (impl AsyncRead + Unpin),
+    lz4: bool,
+) -> Result<Segment, SegmentParseError> {
+    let header_len = if lz4 {
+        COMPRESSED_HEADER_LEN
+    } else {
+        UNCOMPRESSED_HEADER_LEN
+    };
+    let mut raw_header = [0u8; COMPRESSED_HEADER_LEN + HEADER_CRC_LEN];
+    let raw_header = &mut raw_header[..header_len + HEADER_CRC_LEN];
+    reader
+        .read_exact(raw_header)
+        .await
+        .map_err(SegmentParseError::IoError)?;
+
+    let header = get_uint_le(&raw_header[..header_len]);
+    let received_header_crc = get_uint_le(&raw_header[header_len..]) as u32;
+    let computed_header_crc = crc24(header, header_len);
+    if received_header_crc != computed_header_crc {
+        return Err(SegmentParseError::HeaderCrcMismatch {
+            received: received_header_crc,
+            computed: computed_header_crc,
+        });
+    }
+
+    let payload_len = (header & MAX_SEGMENT_PAYLOAD_LEN as u64) as usize;
+    let (uncompressed_len, self_contained) = if lz4 {
+        (
+            ((header >> 17) & MAX_SEGMENT_PAYLOAD_LEN as u64) as usize,
+            header & (1 << 34) != 0,
+        )
+    } else {
+        (0, header & (1 << 17) != 0)
+    };
+
+    let mut payload = vec![0u8; payload_len + PAYLOAD_CRC_LEN];
+    reader
+        .read_exact(&mut payload)
+        .await
+        .map_err(SegmentParseError::IoError)?;
+    let received_payload_crc = (&payload[payload_len..]).get_u32_le();
+    payload.truncate(payload_len);
+    let computed_payload_crc = crc32(&payload);
+    if received_payload_crc != computed_payload_crc {
+        return Err(SegmentParseError::PayloadCrcMismatch {
+            received: received_payload_crc,
+            computed: computed_payload_crc,
+        });
+    }
+
+    if uncompressed_len != 0 {
+        payload = lz4_flex::decompress(&payload, uncompressed_len)
+            .map_err(|err| SegmentParseError::Lz4DecompressError(Arc::new(err)))?;
+    }
+
+    Ok(Segment {
+        payload: payload.into(),
+        self_contained,
+    })
+}
+
+/// Reads response frames wrapped in segments.
+///
+/// Keeps the payloads of already read segments which have not been consumed yet,
+/// so that frames coalesced in one segment or split among several ones
+/// are read correctly.
+pub struct SegmentedFrameReader {
+    lz4: bool,
+    buffer: BytesMut,
+}
+
+impl SegmentedFrameReader {
+    /// Creates a new reader.
+    ///
+    /// `lz4` denotes whether LZ4 compression was negotiated on the connection.
+    pub fn new(lz4: bool) -> Self {
+        Self {
+            lz4,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    /// Reads a response frame from the provided reader (usually, a socket),
+    /// reading as many segments as needed.
+    /// Then parses and validates the frame header and extracts the body.
+    pub async fn read_response_frame(
+        &mut self,
+        reader: &mut (impl AsyncRead + Unpin),
+    ) -> Result<(FrameParams, ResponseOpcode, Bytes), FrameHeaderParseError> {
+        loop {
+            if let Some(raw_header) = self.buffer.get(..HEADER_SIZE) {
+                let (frame_params, opcode, length) =
+                    parse_response_frame_header(raw_header.try_into().unwrap())?;
+                if self.buffer.len() >= HEADER_SIZE + length {
+                    self.buffer.advance(HEADER_SIZE);
+                    let body = self.buffer.split_to(length).freeze();
+                    return Ok((frame_params, opcode, body));
+                }
+            }
+
+            let segment = read_segment(reader, self.lz4).await?;
+            self.buffer.extend_from_slice(&segment.payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response_frame(stream: i16, body: &[u8]) -> Vec<u8> {
+        let mut frame = vec![0x85, 0x00];
+        frame.extend_from_slice(&stream.to_be_bytes());
+        frame.push(ResponseOpcode::Ready as u8);
+        frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        frame.extend_from_slice(body);
+        frame
+    }
+
+    #[test]
+    fn test_crc32_matches_standard_crc32() {
+        // Standard CRC32 of "123456789" (without the initial bytes) is 0xCBF43926.
+        let mut crc = !0u32;
+        for byte in b"123456789" {
+            crc = CRC32_TABLE[((crc ^ *byte as u32) & 0xFF) as usize] ^ (crc >> 8);
+        }
+        assert_eq!(!crc, 0xCBF43926);
+    }
+
+    #[test]
+    fn test_uncompressed_segment_layout() {
+        let mut out = Vec::new();
+        write_segment(b"abc", true, false, 0, &mut out);
+
+        assert_eq!(
+            out.len(),
+            UNCOMPRESSED_HEADER_LEN + HEADER_CRC_LEN + 3 + PAYLOAD_CRC_LEN
+        );
+        // Payload length 3 with the self-contained flag (bit 17).
+        assert_eq!(&out[..3], &[0x03, 0x00, 0x02]);
+        assert_eq!(&out[6..9], b"abc");
+    }
+
+    #[tokio::test]
+    async fn test_segment_roundtrip() {
+        let payload: Vec<u8> = (0..1000).map(|i| (i % 7) as u8).collect();
+        for lz4 in [false, true] {
+            for threshold in [0, usize::MAX] {
+                let mut out = Vec::new();
+                write_segment(&payload, true, lz4, threshold, &mut out);
+                let segment = read_segment(&mut &out[..], lz4).await.unwrap();
+                assert_eq!(&segment.payload[..], &payload[..]);
+                assert!(segment.self_contained);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_corrupted_segment_is_rejected() {
+        let mut out = Vec::new();
+        write_segment(b"payload", true, false, 0, &mut out);
+
+        let mut corrupted_header = out.clone();
+        corrupted_header[0] ^= 0x01;
+        assert!(matches!(
+            read_segment(&mut &corrupted_header[..], false).await,
+            Err(SegmentParseError::HeaderCrcMismatch { .. })
+        ));
+
+        let mut corrupted_payload = out.clone();
+        corrupted_payload[7] ^= 0x01;
+        assert!(matches!(
+            read_segment(&mut &corrupted_payload[..], false).await,
+            Err(SegmentParseError::PayloadCrcMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_frames_coalesced_and_split() {
+        let small_frames: Vec<Vec<u8>> =
+            (0..3).map(|i| response_frame(i, &[i as u8; 10])).collect();
+        let large_frame = response_frame(3, &vec![0xAB; 3 * MAX_SEGMENT_PAYLOAD_LEN]);
+
+        for lz4 in [false, true] {
+            let mut encoder = SegmentEncoder::new(lz4, 0);
+            let mut out = Vec::new();
+            for frame in small_frames.iter().chain(std::iter::once(&large_frame)) {
+                encoder.push_frame(frame, &mut out);
+            }
+            encoder.flush(&mut out);
+
+            let mut reader = SegmentedFrameReader::new(lz4);
+            let mut wire = &out[..];
+            for (stream, frame) in small_frames
+                .iter()
+                .chain(std::iter::once(&large_frame))
+                .enumerate()
+            {
+                let (params, opcode, body) = reader.read_response_frame(&mut wire).await.unwrap();
+                assert_eq!(params.stream, stream as i16);
+                assert_eq!(opcode, ResponseOpcode::Ready);
+                assert_eq!(&body[..], &frame[HEADER_SIZE..]);
+            }
+            assert!(wire.is_empty());
+        }
+    }
+}
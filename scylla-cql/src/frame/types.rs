@@ -144,6 +144,57 @@ impl TryFrom<Consistency> for SerialConsistency {
     }
 }
 
+/// Unknown consistency.
+#[derive(Error, Debug, Clone)]
+#[error("Unknown consistency: {name}")]
+pub struct ConsistencyFromStrError {
+    name: String,
+}
+
+// Makes the name case-insensitive and lets words be separated with `_`, `-` or nothing,
+// so that e.g. `LOCAL_QUORUM`, `local-quorum` and `LocalQuorum` are all accepted.
+fn normalize_consistency_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-'))
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+impl str::FromStr for Consistency {
+    type Err = ConsistencyFromStrError;
+
+    /// Parses a consistency name, e.g. `LOCAL_QUORUM` or `local_quorum`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_consistency_name(s).as_str() {
+            "any" => Ok(Consistency::Any),
+            "one" => Ok(Consistency::One),
+            "two" => Ok(Consistency::Two),
+            "three" => Ok(Consistency::Three),
+            "quorum" => Ok(Consistency::Quorum),
+            "all" => Ok(Consistency::All),
+            "localquorum" => Ok(Consistency::LocalQuorum),
+            "eachquorum" => Ok(Consistency::EachQuorum),
+            "localone" => Ok(Consistency::LocalOne),
+            "serial" => Ok(Consistency::Serial),
+            "localserial" => Ok(Consistency::LocalSerial),
+            _ => Err(ConsistencyFromStrError { name: s.to_owned() }),
+        }
+    }
+}
+
+impl str::FromStr for SerialConsistency {
+    type Err = ConsistencyFromStrError;
+
+    /// Parses a serial consistency name, e.g. `LOCAL_SERIAL` or `local_serial`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match normalize_consistency_name(s).as_str() {
+            "serial" => Ok(SerialConsistency::Serial),
+            "localserial" => Ok(SerialConsistency::LocalSerial),
+            _ => Err(ConsistencyFromStrError { name: s.to_owned() }),
+        }
+    }
+}
+
 impl std::fmt::Display for Consistency {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{self:?}")
@@ -214,6 +265,33 @@ pub(crate) fn write_int_length(
     Ok(())
 }
 
+#[test]
+fn consistency_from_str() {
+    assert_eq!(
+        "LOCAL_QUORUM".parse::<Consistency>().unwrap(),
+        Consistency::LocalQuorum
+    );
+    assert_eq!(
+        "local-one".parse::<Consistency>().unwrap(),
+        Consistency::LocalOne
+    );
+    assert_eq!(
+        "Quorum".parse::<Consistency>().unwrap(),
+        Consistency::Quorum
+    );
+    assert_eq!(
+        "EachQuorum".parse::<Consistency>().unwrap(),
+        Consistency::EachQuorum
+    );
+    assert!("quorums".parse::<Consistency>().is_err());
+
+    assert_eq!(
+        "local_serial".parse::<SerialConsistency>().unwrap(),
+        SerialConsistency::LocalSerial
+    );
+    assert!("quorum".parse::<SerialConsistency>().is_err());
+}
+
 #[test]
 fn type_int() {
     let vals = [i32::MIN, -1, 0, 1, i32::MAX];
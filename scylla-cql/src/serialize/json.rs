@@ -0,0 +1,737 @@
+//! Serializing JSON values as CQL values, guided by the types of the bind markers.
+//!
+//! This allows binding documents of unknown shape, e.g. received by an ingestion service,
+//! to prepared statements: [serde_json_1::Value] implements [SerializeValue], and
+//! [serde_json_1::Map] implements [SerializeRow](super::row::SerializeRow), binding
+//! object entries to bind markers of the same names.
+//!
+//! JSON values are converted to the CQL type of the bind marker as follows:
+//! - `boolean` accepts booleans,
+//! - integer types accept integral numbers and strings containing them,
+//! - `float` and `double` accept numbers, as well as strings `"NaN"`, `"Infinity"`
+//!   and `"-Infinity"`,
+//! - `decimal` and `varint` accept numbers and strings in the decimal notation,
+//!   e.g. `"-12.5e3"`; strings do not lose precision; exponents are limited
+//!   to ±10000,
+//! - `ascii` and `text` accept strings,
+//! - `blob` accepts `0x`-prefixed hex strings and arrays of bytes,
+//! - `timestamp` accepts RFC 3339 strings and numbers of milliseconds since the unix epoch,
+//! - `date` accepts `YYYY-MM-DD` strings and numbers of days since the unix epoch,
+//! - `time` accepts `HH:MM:SS[.fffffffff]` strings and numbers of nanoseconds since midnight,
+//! - `duration` accepts strings such as `1h30m` or `1mo2d3ns`,
+//! - `inet`, `uuid` and `timeuuid` accept strings,
+//! - lists, sets, vectors and tuples accept arrays,
+//! - maps accept objects; keys are converted to the key type in the same way as values,
+//!   after being parsed as JSON (e.g. key `"1"` of a `map<int, text>` becomes `1`),
+//! - user defined types accept objects with fields as keys; missing fields are set to null,
+//! - `null` is accepted by all types.
+//!
+//! This matches the representation produced by
+//! [JsonRow](crate::deserialize::json::JsonRow) with the default format.
+
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use serde_json_1::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use super::value::{fix_rust_name_in_err, SerializeValue};
+use super::writers::WrittenCellProof;
+use super::{CellWriter, SerializationError};
+use crate::frame::response::result::{CollectionType, ColumnType, NativeType};
+use crate::value::{
+    Counter, CqlDate, CqlDecimal, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue,
+    CqlVarint,
+};
+
+/// Failed to convert a JSON value to the CQL type of a bind marker.
+#[derive(Debug, Clone, Error)]
+#[error("Failed to convert JSON value {value} to CQL type {typ}: {reason}")]
+#[non_exhaustive]
+pub struct JsonConversionError {
+    /// The JSON value (or its part) that could not be converted.
+    pub value: Value,
+    /// The CQL type that the value was being converted to.
+    pub typ: ColumnType<'static>,
+    /// Why the value could not be converted.
+    pub reason: String,
+}
+
+impl SerializeValue for Value {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        match json_to_cql_value(self, typ).map_err(SerializationError::new)? {
+            None => Ok(writer.set_null()),
+            Some(value) => value
+                .serialize(typ, writer)
+                .map_err(fix_rust_name_in_err::<Self>),
+        }
+    }
+}
+
+/// Converts a JSON value to a CQL value of the given type.
+///
+/// Returns `None` for JSON nulls. See the [module documentation](self) for the accepted values.
+pub fn json_to_cql_value(
+    value: &Value,
+    typ: &ColumnType,
+) -> Result<Option<CqlValue>, JsonConversionError> {
+    let err = |reason: &str| JsonConversionError {
+        value: value.clone(),
+        typ: typ.clone().into_owned(),
+        reason: reason.to_owned(),
+    };
+
+    let cql_value = match (typ, value) {
+        (_, Value::Null) => return Ok(None),
+        (ColumnType::Native(native), _) => native_to_cql_value(value, native).map_err(err)?,
+        (
+            ColumnType::Collection {
+                typ: CollectionType::List(elem_type),
+                ..
+            },
+            Value::Array(elems),
+        ) => CqlValue::List(elems_to_cql_values(elems, elem_type)?),
+        (
+            ColumnType::Collection {
+                typ: CollectionType::Set(elem_type),
+                ..
+            },
+            Value::Array(elems),
+        ) => CqlValue::Set(elems_to_cql_values(elems, elem_type)?),
+        (ColumnType::Vector { typ: elem_type, .. }, Value::Array(elems)) => {
+            CqlValue::Vector(elems_to_cql_values(elems, elem_type)?)
+        }
+        (
+            ColumnType::Collection {
+                typ: CollectionType::Map(key_type, value_type),
+                ..
+            },
+            Value::Object(entries),
+        ) => CqlValue::Map(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = map_key_to_json(key, key_type);
+                    Ok((
+                        non_null_to_cql_value(&key, key_type)?,
+                        non_null_to_cql_value(value, value_type)?,
+                    ))
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (ColumnType::Tuple(elem_types), Value::Array(elems)) => {
+            if elems.len() > elem_types.len() {
+                return Err(err("too many tuple elements"));
+            }
+            CqlValue::Tuple(
+                elems
+                    .iter()
+                    .zip(elem_types)
+                    .map(|(elem, elem_type)| json_to_cql_value(elem, elem_type))
+                    .collect::<Result<_, _>>()?,
+            )
+        }
+        (ColumnType::UserDefinedType { definition, .. }, Value::Object(fields)) => {
+            if let Some(unknown) = fields
+                .keys()
+                .find(|name| !definition.field_types.iter().any(|(f, _)| f == *name))
+            {
+                return Err(err(&format!("no field named {unknown} in the UDT")));
+            }
+            CqlValue::UserDefinedType {
+                keyspace: definition.keyspace.clone().into_owned(),
+                name: definition.name.clone().into_owned(),
+                fields: definition
+                    .field_types
+                    .iter()
+                    .map(|(name, field_type)| {
+                        let field = fields.get(name.as_ref()).unwrap_or(&Value::Null);
+                        Ok((
+                            name.clone().into_owned(),
+                            json_to_cql_value(field, field_type)?,
+                        ))
+                    })
+                    .collect::<Result<_, _>>()?,
+            }
+        }
+        _ => return Err(err("unsupported JSON value for this type")),
+    };
+    Ok(Some(cql_value))
+}
+
+fn non_null_to_cql_value(value: &Value, typ: &ColumnType) -> Result<CqlValue, JsonConversionError> {
+    json_to_cql_value(value, typ)?.ok_or_else(|| JsonConversionError {
+        value: Value::Null,
+        typ: typ.clone().into_owned(),
+        reason: "collection elements cannot be null".to_owned(),
+    })
+}
+
+fn elems_to_cql_values(
+    elems: &[Value],
+    elem_type: &ColumnType,
+) -> Result<Vec<CqlValue>, JsonConversionError> {
+    elems
+        .iter()
+        .map(|elem| non_null_to_cql_value(elem, elem_type))
+        .collect()
+}
+
+// JSON object keys are always strings. Keys of types other than text are parsed as JSON,
+// so that e.g. `{"1": ...}` can be bound to a `map<int, ...>`.
+fn map_key_to_json(key: &str, key_type: &ColumnType) -> Value {
+    match key_type {
+        ColumnType::Native(NativeType::Ascii | NativeType::Text) => Value::String(key.to_owned()),
+        _ => serde_json_1::from_str(key).unwrap_or_else(|_| Value::String(key.to_owned())),
+    }
+}
+
+fn native_to_cql_value(value: &Value, typ: &NativeType) -> Result<CqlValue, &'static str> {
+    let cql_value = match (typ, value) {
+        (NativeType::Ascii, Value::String(s)) => CqlValue::Ascii(s.clone()),
+        (NativeType::Text, Value::String(s)) => CqlValue::Text(s.clone()),
+        (NativeType::Boolean, Value::Bool(b)) => CqlValue::Boolean(*b),
+        (NativeType::TinyInt, _) => CqlValue::TinyInt(integer(value)?),
+        (NativeType::SmallInt, _) => CqlValue::SmallInt(integer(value)?),
+        (NativeType::Int, _) => CqlValue::Int(integer(value)?),
+        (NativeType::BigInt, _) => CqlValue::BigInt(integer(value)?),
+        (NativeType::Counter, _) => CqlValue::Counter(Counter(integer(value)?)),
+        (NativeType::Float, _) => CqlValue::Float(float(value)? as f32),
+        (NativeType::Double, _) => CqlValue::Double(float(value)?),
+        (NativeType::Decimal, Value::String(s)) => CqlValue::Decimal(parse_decimal(s)?),
+        (NativeType::Decimal, Value::Number(n)) => {
+            CqlValue::Decimal(parse_decimal(&n.to_string())?)
+        }
+        (NativeType::Varint, Value::String(s)) => CqlValue::Varint(parse_varint(s)?),
+        (NativeType::Varint, Value::Number(n)) => CqlValue::Varint(parse_varint(&n.to_string())?),
+        (NativeType::Blob, Value::String(s)) => CqlValue::Blob(parse_hex(s)?),
+        (NativeType::Blob, Value::Array(bytes)) => CqlValue::Blob(
+            bytes
+                .iter()
+                .map(|byte| {
+                    byte.as_u64()
+                        .and_then(|byte| u8::try_from(byte).ok())
+                        .ok_or("blob arrays must consist of numbers from 0 to 255")
+                })
+                .collect::<Result<_, _>>()?,
+        ),
+        (NativeType::Timestamp, Value::String(s)) => {
+            let datetime = chrono_04::DateTime::parse_from_rfc3339(s)
+                .map_err(|_| "invalid RFC 3339 timestamp")?;
+            CqlValue::Timestamp(CqlTimestamp(datetime.timestamp_millis()))
+        }
+        (NativeType::Timestamp, Value::Number(n)) => {
+            CqlValue::Timestamp(CqlTimestamp(n.as_i64().ok_or("expected milliseconds")?))
+        }
+        (NativeType::Date, Value::String(s)) => {
+            let date = chrono_04::NaiveDate::from_str(s).map_err(|_| "invalid date")?;
+            let unix_epoch = chrono_04::NaiveDate::from_yo_opt(1970, 1).unwrap();
+            let days = date.signed_duration_since(unix_epoch).num_days();
+            CqlValue::Date(CqlDate(((1 << 31) + days) as u32))
+        }
+        (NativeType::Date, Value::Number(n)) => {
+            let days = n.as_i64().ok_or("expected days")?;
+            let days = u32::try_from((1 << 31) + days).map_err(|_| "date out of range")?;
+            CqlValue::Date(CqlDate(days))
+        }
+        (NativeType::Time, Value::String(s)) => {
+            use chrono_04::Timelike;
+            let time = chrono_04::NaiveTime::from_str(s).map_err(|_| "invalid time")?;
+            let nanos = i64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+                + i64::from(time.nanosecond());
+            CqlValue::Time(CqlTime(nanos))
+        }
+        (NativeType::Time, Value::Number(n)) => {
+            CqlValue::Time(CqlTime(n.as_i64().ok_or("expected nanoseconds")?))
+        }
+        (NativeType::Duration, Value::String(s)) => CqlValue::Duration(parse_duration(s)?),
+        (NativeType::Inet, Value::String(s)) => {
+            CqlValue::Inet(IpAddr::from_str(s).map_err(|_| "invalid IP address")?)
+        }
+        (NativeType::Uuid, Value::String(s)) => {
+            CqlValue::Uuid(Uuid::parse_str(s).map_err(|_| "invalid UUID")?)
+        }
+        (NativeType::Timeuuid, Value::String(s)) => {
+            CqlValue::Timeuuid(CqlTimeuuid::from_str(s).map_err(|_| "invalid UUID")?)
+        }
+        _ => return Err("unsupported JSON value for this type"),
+    };
+    Ok(cql_value)
+}
+
+fn integer<T: TryFrom<i64>>(value: &Value) -> Result<T, &'static str> {
+    let integer = match value {
+        Value::Number(n) => n.as_i64().ok_or("expected an integer")?,
+        Value::String(s) => s.parse().map_err(|_| "expected an integer")?,
+        _ => return Err("expected an integer"),
+    };
+    T::try_from(integer).map_err(|_| "integer out of range")
+}
+
+fn float(value: &Value) -> Result<f64, &'static str> {
+    match value {
+        Value::Number(n) => n.as_f64().ok_or("expected a number"),
+        Value::String(s) => match s.as_str() {
+            "NaN" => Ok(f64::NAN),
+            "Infinity" => Ok(f64::INFINITY),
+            "-Infinity" => Ok(f64::NEG_INFINITY),
+            _ => s.parse().map_err(|_| "expected a number"),
+        },
+        _ => Err("expected a number"),
+    }
+}
+
+fn parse_hex(s: &str) -> Result<Vec<u8>, &'static str> {
+    const ERR: &str = "blobs must be 0x-prefixed hex strings";
+    let digits = s.strip_prefix("0x").ok_or(ERR)?.as_bytes();
+    if digits.len() % 2 != 0 {
+        return Err(ERR);
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            std::str::from_utf8(pair)
+                .ok()
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or(ERR)
+        })
+        .collect()
+}
+
+// The largest absolute value of an exponent accepted in the decimal notation.
+// Without a bound, a short string such as `1e100000000000` would expand into
+// an enormous number of digits when written out as a `varint`.
+const MAX_EXPONENT: i64 = 10_000;
+
+fn parse_exponent(s: &str) -> Result<i64, &'static str> {
+    let exponent = s.parse::<i64>().map_err(|_| "invalid exponent")?;
+    if !(-MAX_EXPONENT..=MAX_EXPONENT).contains(&exponent) {
+        return Err("exponent out of range");
+    }
+    Ok(exponent)
+}
+
+// Parses a number in the decimal notation into its unscaled value,
+// as a big-endian two's complement integer, and its scale.
+fn parse_decimal_parts(s: &str) -> Result<(Vec<u8>, i64), &'static str> {
+    const ERR: &str = "invalid decimal number";
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (mantissa, exponent) = match s.find(['e', 'E']) {
+        Some(pos) => (&s[..pos], parse_exponent(&s[pos + 1..])?),
+        None => (s, 0),
+    };
+    let (integral, fractional) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integral.is_empty() && fractional.is_empty() {
+        return Err(ERR);
+    }
+
+    // Magnitude as big-endian base-256 digits.
+    let mut magnitude: Vec<u8> = Vec::new();
+    for digit in integral.bytes().chain(fractional.bytes()) {
+        if !digit.is_ascii_digit() {
+            return Err(ERR);
+        }
+        let mut carry = u32::from(digit - b'0');
+        for byte in magnitude.iter_mut().rev() {
+            let current = u32::from(*byte) * 10 + carry;
+            *byte = current as u8;
+            carry = current >> 8;
+        }
+        if carry > 0 {
+            magnitude.insert(0, carry as u8);
+        }
+    }
+    // Make room for the sign bit.
+    magnitude.insert(0, 0);
+    if negative {
+        // Two's complement negation: invert the bits and add one.
+        for byte in magnitude.iter_mut() {
+            *byte = !*byte;
+        }
+        for byte in magnitude.iter_mut().rev() {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            if !overflow {
+                break;
+            }
+        }
+    }
+    // Strip redundant sign bytes.
+    while magnitude.len() > 1
+        && ((magnitude[0] == 0x00 && magnitude[1] & 0x80 == 0)
+            || (magnitude[0] == 0xff && magnitude[1] & 0x80 != 0))
+    {
+        magnitude.remove(0);
+    }
+
+    let scale = i64::try_from(fractional.len())
+        .ok()
+        .and_then(|len| len.checked_sub(exponent))
+        .ok_or("decimal scale out of range")?;
+    Ok((magnitude, scale))
+}
+
+fn parse_decimal(s: &str) -> Result<CqlDecimal, &'static str> {
+    let (unscaled, scale) = parse_decimal_parts(s)?;
+    let scale = i32::try_from(scale).map_err(|_| "decimal scale out of range")?;
+    Ok(CqlDecimal::from_signed_be_bytes_and_exponent(
+        unscaled, scale,
+    ))
+}
+
+fn parse_varint(s: &str) -> Result<CqlVarint, &'static str> {
+    // Write the number without the fractional part and the exponent,
+    // e.g. `1.5e3` as `1500`, and parse it again.
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(pos) => (&unsigned[..pos], parse_exponent(&unsigned[pos + 1..])?),
+        None => (unsigned, 0),
+    };
+    let (integral, fractional) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let mut digits = format!("{integral}{fractional}");
+    let scale = i64::try_from(fractional.len())
+        .ok()
+        .and_then(|len| len.checked_sub(exponent))
+        .ok_or("integer out of range")?;
+    if scale > 0 {
+        let scale = scale as usize;
+        if scale > digits.len() || digits[digits.len() - scale..].bytes().any(|d| d != b'0') {
+            return Err("expected an integer");
+        }
+        digits.truncate(digits.len() - scale);
+    } else {
+        digits.extend(std::iter::repeat('0').take(scale.unsigned_abs() as usize));
+    }
+    if negative {
+        digits.insert(0, '-');
+    }
+    let (unscaled, _) = parse_decimal_parts(&digits)?;
+    Ok(CqlVarint::from_signed_bytes_be(unscaled))
+}
+
+// Parses durations in the format used by CQL literals, e.g. `1h30m` or `-2d12h`,
+// including the `XmoYdZns` format produced by `JsonRow`.
+fn parse_duration(s: &str) -> Result<CqlDuration, &'static str> {
+    const ERR: &str = "invalid duration";
+    let (negative, mut rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    if rest.is_empty() {
+        return Err(ERR);
+    }
+
+    let (mut months, mut days, mut nanoseconds) = (0_i64, 0_i64, 0_i64);
+    while !rest.is_empty() {
+        let digits_end = rest.find(|c: char| !c.is_ascii_digit()).ok_or(ERR)?;
+        let amount: i64 = rest[..digits_end].parse().map_err(|_| ERR)?;
+        rest = &rest[digits_end..];
+        let unit_end = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (unit, tail) = rest.split_at(unit_end);
+        rest = tail;
+
+        let (field, multiplier) = match unit.to_ascii_lowercase().as_str() {
+            "y" => (&mut months, 12),
+            "mo" => (&mut months, 1),
+            "w" => (&mut days, 7),
+            "d" => (&mut days, 1),
+            "h" => (&mut nanoseconds, 3_600_000_000_000),
+            "m" => (&mut nanoseconds, 60_000_000_000),
+            "s" => (&mut nanoseconds, 1_000_000_000),
+            "ms" => (&mut nanoseconds, 1_000_000),
+            "us" | "µs" => (&mut nanoseconds, 1_000),
+            "ns" => (&mut nanoseconds, 1),
+            _ => return Err(ERR),
+        };
+        *field = amount
+            .checked_mul(multiplier)
+            .and_then(|amount| field.checked_add(amount))
+            .ok_or("duration out of range")?;
+    }
+
+    let sign = if negative { -1 } else { 1 };
+    Ok(CqlDuration {
+        months: i32::try_from(sign * months).map_err(|_| "duration out of range")?,
+        days: i32::try_from(sign * days).map_err(|_| "duration out of range")?,
+        nanoseconds: sign * nanoseconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json_1::{json, Value};
+
+    use super::{json_to_cql_value, parse_decimal, parse_duration, parse_varint};
+    use crate::frame::response::result::{
+        CollectionType, ColumnSpec, ColumnType, NativeType, TableSpec, UserDefinedType,
+    };
+    use crate::serialize::row::{RowSerializationContext, SerializeRow};
+    use crate::serialize::value::SerializeValue;
+    use crate::serialize::writers::{CellWriter, RowWriter};
+    use crate::value::{
+        Counter, CqlDate, CqlDecimal, CqlDuration, CqlTime, CqlTimestamp, CqlValue, CqlVarint,
+    };
+
+    fn native(typ: NativeType) -> ColumnType<'static> {
+        ColumnType::Native(typ)
+    }
+
+    fn convert(value: Value, typ: &ColumnType) -> CqlValue {
+        json_to_cql_value(&value, typ).unwrap().unwrap()
+    }
+
+    #[test]
+    fn number_parsing() {
+        assert_eq!(
+            parse_decimal("12.34").unwrap(),
+            CqlDecimal::from_signed_be_bytes_and_exponent(vec![0x04, 0xd2], 2)
+        );
+        assert_eq!(
+            parse_decimal("-12.34").unwrap(),
+            CqlDecimal::from_signed_be_bytes_and_exponent(vec![0xfb, 0x2e], 2)
+        );
+        assert_eq!(
+            parse_decimal("1.5e3").unwrap(),
+            CqlDecimal::from_signed_be_bytes_and_exponent(vec![0x0f], -2)
+        );
+        assert_eq!(
+            parse_varint("-128").unwrap(),
+            CqlVarint::from_signed_bytes_be(vec![0x80])
+        );
+        assert_eq!(
+            parse_varint("128").unwrap(),
+            CqlVarint::from_signed_bytes_be(vec![0x00, 0x80])
+        );
+        assert_eq!(
+            parse_varint("1.5e1").unwrap(),
+            CqlVarint::from_signed_bytes_be(vec![0x0f])
+        );
+        assert_eq!(
+            parse_varint("18446744073709551616").unwrap(),
+            CqlVarint::from_signed_bytes_be(vec![0x01, 0, 0, 0, 0, 0, 0, 0, 0])
+        );
+        parse_varint("1.5").unwrap_err();
+        parse_decimal("1.2.3").unwrap_err();
+        parse_decimal("-").unwrap_err();
+    }
+
+    #[test]
+    fn huge_exponents_are_rejected() {
+        // 10^10000 takes 33220 bits, plus the sign bit.
+        assert_eq!(
+            parse_varint("1e10000")
+                .unwrap()
+                .as_signed_bytes_be_slice()
+                .len(),
+            4153
+        );
+        parse_varint("1e10001").unwrap_err();
+        parse_varint("1e100000000000").unwrap_err();
+        parse_varint("1e-9223372036854775808").unwrap_err();
+        parse_varint("1.5e9223372036854775807").unwrap_err();
+        parse_decimal("1e100000000000").unwrap_err();
+        parse_decimal("1e-9223372036854775808").unwrap_err();
+        parse_decimal("1.5e9223372036854775807").unwrap_err();
+        assert_eq!(
+            parse_decimal("1e-10000").unwrap(),
+            CqlDecimal::from_signed_be_bytes_and_exponent(vec![0x01], 10_000)
+        );
+    }
+
+    #[test]
+    fn duration_parsing() {
+        assert_eq!(
+            parse_duration("1y2mo3w4d5h6m7s8ms9us10ns").unwrap(),
+            CqlDuration {
+                months: 14,
+                days: 25,
+                nanoseconds: 18_367_008_009_010,
+            }
+        );
+        assert_eq!(
+            parse_duration("-1mo2d3ns").unwrap(),
+            CqlDuration {
+                months: -1,
+                days: -2,
+                nanoseconds: -3,
+            }
+        );
+        parse_duration("").unwrap_err();
+        parse_duration("1").unwrap_err();
+        parse_duration("1x").unwrap_err();
+    }
+
+    #[test]
+    fn native_conversion() {
+        assert_eq!(
+            convert(json!(7), &native(NativeType::TinyInt)),
+            CqlValue::TinyInt(7)
+        );
+        assert_eq!(
+            convert(json!("9007199254740993"), &native(NativeType::BigInt)),
+            CqlValue::BigInt(9007199254740993)
+        );
+        assert_eq!(
+            convert(json!(3), &native(NativeType::Counter)),
+            CqlValue::Counter(Counter(3))
+        );
+        assert_eq!(
+            convert(json!("-Infinity"), &native(NativeType::Double)),
+            CqlValue::Double(f64::NEG_INFINITY)
+        );
+        assert_eq!(
+            convert(json!("0xcafe"), &native(NativeType::Blob)),
+            CqlValue::Blob(vec![0xca, 0xfe])
+        );
+        assert_eq!(
+            convert(json!([202, 254]), &native(NativeType::Blob)),
+            CqlValue::Blob(vec![0xca, 0xfe])
+        );
+        assert_eq!(
+            convert(
+                json!("2017-07-14T04:40:00.123+02:00"),
+                &native(NativeType::Timestamp)
+            ),
+            CqlValue::Timestamp(CqlTimestamp(1_500_000_000_123))
+        );
+        assert_eq!(
+            convert(json!("1970-01-02"), &native(NativeType::Date)),
+            CqlValue::Date(CqlDate((1 << 31) + 1))
+        );
+        assert_eq!(
+            convert(json!("01:00:00.5"), &native(NativeType::Time)),
+            CqlValue::Time(CqlTime(3_600_500_000_000))
+        );
+        assert_eq!(
+            convert(json!("127.0.0.1"), &native(NativeType::Inet)),
+            CqlValue::Inet("127.0.0.1".parse().unwrap())
+        );
+        assert_eq!(
+            json_to_cql_value(&json!(null), &native(NativeType::Int)).unwrap(),
+            None
+        );
+
+        let err = json_to_cql_value(&json!(1.5), &native(NativeType::Int)).unwrap_err();
+        assert_eq!(err.reason, "expected an integer");
+        json_to_cql_value(&json!(300), &native(NativeType::TinyInt)).unwrap_err();
+        json_to_cql_value(&json!("abc"), &native(NativeType::Uuid)).unwrap_err();
+        json_to_cql_value(&json!(true), &native(NativeType::Text)).unwrap_err();
+    }
+
+    #[test]
+    fn complex_conversion() {
+        let map_type = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::Map(
+                Box::new(native(NativeType::Int)),
+                Box::new(ColumnType::Tuple(vec![
+                    native(NativeType::Text),
+                    native(NativeType::Uuid),
+                ])),
+            ),
+        };
+        assert_eq!(
+            convert(json!({"1": ["a", null]}), &map_type),
+            CqlValue::Map(vec![(
+                CqlValue::Int(1),
+                CqlValue::Tuple(vec![Some(CqlValue::Text("a".to_owned())), None])
+            )])
+        );
+
+        let udt_type = ColumnType::UserDefinedType {
+            frozen: false,
+            definition: Arc::new(UserDefinedType {
+                name: "udt".into(),
+                keyspace: "ks".into(),
+                field_types: vec![
+                    ("a".into(), native(NativeType::Int)),
+                    (
+                        "b".into(),
+                        ColumnType::Collection {
+                            frozen: false,
+                            typ: CollectionType::Set(Box::new(native(NativeType::Text))),
+                        },
+                    ),
+                ],
+            }),
+        };
+        assert_eq!(
+            convert(json!({"b": ["x"]}), &udt_type),
+            CqlValue::UserDefinedType {
+                keyspace: "ks".to_owned(),
+                name: "udt".to_owned(),
+                fields: vec![
+                    ("a".to_owned(), None),
+                    (
+                        "b".to_owned(),
+                        Some(CqlValue::Set(vec![CqlValue::Text("x".to_owned())]))
+                    ),
+                ],
+            }
+        );
+        let err = json_to_cql_value(&json!({"c": 1}), &udt_type).unwrap_err();
+        assert_eq!(err.reason, "no field named c in the UDT");
+        let err = json_to_cql_value(&json!({"b": [null]}), &udt_type).unwrap_err();
+        assert_eq!(err.reason, "collection elements cannot be null");
+    }
+
+    #[test]
+    fn json_value_serialization() {
+        let typ = ColumnType::Collection {
+            frozen: false,
+            typ: CollectionType::List(Box::new(native(NativeType::BigInt))),
+        };
+        let mut json_bytes = Vec::new();
+        json!([1, "2"])
+            .serialize(&typ, CellWriter::new(&mut json_bytes))
+            .unwrap();
+        let mut expected = Vec::new();
+        SerializeValue::serialize(&vec![1_i64, 2], &typ, CellWriter::new(&mut expected)).unwrap();
+        assert_eq!(json_bytes, expected);
+
+        let mut bytes = Vec::new();
+        json!("x")
+            .serialize(&native(NativeType::Int), CellWriter::new(&mut bytes))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn json_object_row_serialization() {
+        let table = TableSpec::borrowed("ks", "tab");
+        let columns = [
+            ColumnSpec::borrowed("id", native(NativeType::Int), table.clone()),
+            ColumnSpec::borrowed("name", native(NativeType::Text), table),
+        ];
+        let ctx = RowSerializationContext::from_specs(&columns);
+
+        let Value::Object(row) = json!({"name": "Ann", "id": 1}) else {
+            unreachable!()
+        };
+        let mut data = Vec::new();
+        row.serialize(&ctx, &mut RowWriter::new(&mut data)).unwrap();
+        let mut expected = Vec::new();
+        SerializeRow::serialize(&(1_i32, "Ann"), &ctx, &mut RowWriter::new(&mut expected)).unwrap();
+        assert_eq!(data, expected);
+
+        let Value::Object(row) = json!({"id": 1}) else {
+            unreachable!()
+        };
+        row.serialize(&ctx, &mut RowWriter::new(&mut Vec::new()))
+            .unwrap_err();
+    }
+}
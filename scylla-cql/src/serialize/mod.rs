@@ -7,6 +7,8 @@ use std::{error::Error, sync::Arc};
 use thiserror::Error;
 
 pub mod batch;
+#[cfg(feature = "serde_json-1")]
+pub mod json;
 pub mod raw_batch;
 pub mod row;
 pub mod value;
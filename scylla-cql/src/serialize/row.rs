@@ -233,6 +233,169 @@ impl<T: SerializeValue, S: BuildHasher> SerializeRow for HashMap<&str, T, S> {
     impl_serialize_row_for_map!();
 }
 
+#[cfg(feature = "serde_json-1")]
+impl SerializeRow for serde_json_1::Map<String, serde_json_1::Value> {
+    impl_serialize_row_for_map!();
+}
+
+/// Decides what [`NamedValues`] bind to a bind marker they hold no value for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum MissingValueBehavior {
+    /// Fail the serialization with [`BuiltinTypeCheckErrorKind::ValueMissingForColumn`],
+    /// like maps do.
+    #[default]
+    Error,
+
+    /// Leave the bind marker unset, so that the column is not modified.
+    Unset,
+
+    /// Bind NULL to the bind marker.
+    Null,
+}
+
+/// A row of values of possibly different types, bound to bind markers by name.
+///
+/// Unlike maps, which fail the serialization if they lack a value for some
+/// bind marker, `NamedValues` can be configured to leave such markers unset
+/// or to bind NULL to them - see [`NamedValues::with_missing_value_behavior`].
+/// Values which do not match any bind marker are always reported as an error.
+///
+/// # Example
+/// ```
+/// # use scylla_cql::serialize::row::{MissingValueBehavior, NamedValues};
+/// let values = NamedValues::new()
+///     .with_missing_value_behavior(MissingValueBehavior::Unset)
+///     .bind("id", 42_i32)
+///     .bind("name", "Alice".to_owned());
+/// ```
+#[derive(Default)]
+pub struct NamedValues {
+    values: HashMap<String, Box<dyn SerializeValue + Send + Sync>>,
+    missing_value_behavior: MissingValueBehavior,
+}
+
+impl NamedValues {
+    /// Creates an empty set of values, which fails the serialization
+    /// if there is no value for some bind marker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what to bind to the bind markers without a value.
+    pub fn with_missing_value_behavior(mut self, behavior: MissingValueBehavior) -> Self {
+        self.missing_value_behavior = behavior;
+        self
+    }
+
+    /// Binds the value to the bind markers with given name, replacing the previous value.
+    pub fn bind(
+        mut self,
+        name: impl Into<String>,
+        value: impl SerializeValue + Send + Sync + 'static,
+    ) -> Self {
+        self.insert(name, value);
+        self
+    }
+
+    /// Binds the value to the bind markers with given name, replacing the previous value.
+    pub fn insert(
+        &mut self,
+        name: impl Into<String>,
+        value: impl SerializeValue + Send + Sync + 'static,
+    ) {
+        self.values.insert(name.into(), Box::new(value));
+    }
+
+    /// Removes the value bound to given name. Returns whether there was such value.
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.values.remove(name).is_some()
+    }
+
+    /// Returns whether there is a value bound to given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.values.contains_key(name)
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for NamedValues
+where
+    K: Into<String>,
+    V: SerializeValue + Send + Sync + 'static,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut values = Self::new();
+        for (name, value) in iter {
+            values.insert(name, value);
+        }
+        values
+    }
+}
+
+impl std::fmt::Debug for NamedValues {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut names: Vec<&str> = self.values.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        f.debug_struct("NamedValues")
+            .field("names", &names)
+            .field("missing_value_behavior", &self.missing_value_behavior)
+            .finish()
+    }
+}
+
+impl SerializeRow for NamedValues {
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        // Column names aren't guaranteed to be unique, see `impl_serialize_row_for_map`.
+        let mut unused_columns: HashSet<&str> = self.values.keys().map(String::as_str).collect();
+
+        for col in ctx.columns.iter() {
+            match self.values.get(col.name()) {
+                Some(v) => {
+                    crate::_macro_internal::ser::row::serialize_column::<Self>(v, col, writer)?;
+                    let _ = unused_columns.remove(col.name());
+                }
+                None => match self.missing_value_behavior {
+                    MissingValueBehavior::Error => {
+                        return Err(mk_typck_err::<Self>(
+                            BuiltinTypeCheckErrorKind::ValueMissingForColumn {
+                                name: col.name().to_owned(),
+                            },
+                        ))
+                    }
+                    MissingValueBehavior::Unset => {
+                        writer.make_cell_writer().set_unset();
+                    }
+                    MissingValueBehavior::Null => {
+                        writer.make_cell_writer().set_null();
+                    }
+                },
+            }
+        }
+
+        if let Some(name) = unused_columns.iter().min() {
+            // Report the lexicographically first value for deterministic error messages
+            return Err(mk_typck_err::<Self>(
+                BuiltinTypeCheckErrorKind::NoColumnWithName {
+                    name: name.to_string(),
+                },
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        // Without any values, bind markers may still be filled according to
+        // `missing_value_behavior`, which requires the statement to be prepared.
+        self.values.is_empty() && self.missing_value_behavior == MissingValueBehavior::Error
+    }
+}
+
 impl<T: SerializeRow + ?Sized> SerializeRow for &T {
     fn serialize(
         &self,
@@ -645,6 +808,16 @@ mod doctests {
     /// ```
     fn _test_struct_deserialization_skip_name_check_conflicts_with_rename() {}
 
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::SerializeRow)]
+    /// #[scylla(crate = scylla_cql, flavor = "enforce_order", skip_name_checks, rename_all = "camelCase")]
+    /// struct TestRow {
+    ///     a: i32,
+    /// }
+    /// ```
+    fn _test_struct_deserialization_skip_name_check_conflicts_with_rename_all() {}
+
     /// ```compile_fail
     ///
     /// #[derive(scylla_macros::SerializeRow)]
@@ -7,8 +7,8 @@ use crate::frame::response::result::{
 use crate::frame::types::RawValue;
 use crate::serialize::row::{
     BuiltinSerializationError, BuiltinSerializationErrorKind, BuiltinTypeCheckError,
-    BuiltinTypeCheckErrorKind, RowSerializationContext, SerializeRow, SerializeValue,
-    SerializedValues,
+    BuiltinTypeCheckErrorKind, MissingValueBehavior, NamedValues, RowSerializationContext,
+    SerializeRow, SerializeValue, SerializedValues,
 };
 use crate::serialize::value::tests::get_ser_err as get_value_ser_err;
 use crate::serialize::value::{
@@ -220,6 +220,64 @@ fn test_map_errors() {
     assert_eq!(name, "b");
 }
 
+#[test]
+fn test_named_values() {
+    let spec = [
+        col("a", ColumnType::Native(NativeType::Int)),
+        col("b", ColumnType::Native(NativeType::Text)),
+        col("a", ColumnType::Native(NativeType::Int)),
+    ];
+
+    // Values are matched by name, regardless of their types.
+    let values = NamedValues::new()
+        .bind("b", "x".to_owned())
+        .bind("a", 123_i32);
+    let expected = do_serialize((123_i32, "x", 123_i32), &spec);
+    assert_eq!(do_serialize(values, &spec), expected);
+
+    // Missing values are reported, or filled according to the configuration.
+    let values: NamedValues = [("a", 123_i32)].into_iter().collect();
+    let err = do_serialize_err(values, &spec);
+    let BuiltinTypeCheckErrorKind::ValueMissingForColumn { name } = &get_typeck_err(&err).kind
+    else {
+        panic!("unexpected error kind: {}", get_typeck_err(&err).kind)
+    };
+    assert_eq!(name, "b");
+
+    let values = NamedValues::new()
+        .with_missing_value_behavior(MissingValueBehavior::Unset)
+        .bind("a", 123_i32);
+    let expected = do_serialize((123_i32, MaybeUnset::<&str>::Unset, 123_i32), &spec);
+    assert_eq!(do_serialize(values, &spec), expected);
+
+    let values = NamedValues::new()
+        .with_missing_value_behavior(MissingValueBehavior::Null)
+        .bind("a", 123_i32);
+    let expected = do_serialize((123_i32, None::<&str>, 123_i32), &spec);
+    assert_eq!(do_serialize(values, &spec), expected);
+
+    // Values without a bind marker are always reported.
+    let values = NamedValues::new()
+        .with_missing_value_behavior(MissingValueBehavior::Null)
+        .bind("a", 123_i32)
+        .bind("c", 456_i32);
+    let err = do_serialize_err(values, &spec);
+    let BuiltinTypeCheckErrorKind::NoColumnWithName { name } = &get_typeck_err(&err).kind else {
+        panic!("unexpected error kind: {}", get_typeck_err(&err).kind)
+    };
+    assert_eq!(name, "c");
+
+    // Serialization of one of the values fails
+    let values = NamedValues::new().bind("a", 123_i32).bind("b", 456_i32);
+    let err = do_serialize_err(values, &spec);
+    let BuiltinSerializationErrorKind::ColumnSerializationFailed { name, err: _ } =
+        &get_ser_err(&err).kind
+    else {
+        panic!("Expected BuiltinSerializationErrorKind::ColumnSerializationFailed")
+    };
+    assert_eq!(name, "b");
+}
+
 // Do not remove. It's not used in tests but we keep it here to check that
 // we properly ignore warnings about unused variables, unnecessary `mut`s
 // etc. that usually pop up when generating code for empty structs.
@@ -620,6 +678,33 @@ fn test_row_serialization_with_column_rename_and_enforce_order() {
     assert_eq!(reference, row);
 }
 
+#[derive(SerializeRow, Debug)]
+#[scylla(crate = crate, flavor = "enforce_order", rename_all = "kebab-case")]
+struct TestRowWithRenameAll {
+    first_name: String,
+    #[scylla(rename = "x")]
+    age: i32,
+}
+
+#[test]
+fn test_row_serialization_with_rename_all() {
+    let spec = [
+        col("first-name", ColumnType::Native(NativeType::Text)),
+        col("x", ColumnType::Native(NativeType::Int)),
+    ];
+
+    let reference = do_serialize(("Ala ma kota", 42i32), &spec);
+    let row = do_serialize(
+        TestRowWithRenameAll {
+            first_name: "Ala ma kota".to_owned(),
+            age: 42,
+        },
+        &spec,
+    );
+
+    assert_eq!(reference, row);
+}
+
 #[derive(SerializeRow, Debug)]
 #[scylla(crate = crate, flavor = "enforce_order", skip_name_checks)]
 struct TestRowWithSkippedNameChecks {
@@ -709,6 +794,73 @@ fn test_row_serialization_with_skipped_field() {
     assert_eq!(reference, row);
 }
 
+#[derive(SerializeRow)]
+#[scylla(crate = crate)]
+struct TestRowWithNoneAsUnset {
+    a: i32,
+    #[scylla(none_as_unset)]
+    b: Option<i32>,
+    c: Option<i32>,
+}
+
+#[derive(SerializeRow)]
+#[scylla(crate = crate, flavor = "enforce_order")]
+struct TestRowWithNoneAsUnsetEnforceOrder {
+    a: i32,
+    #[scylla(none_as_unset)]
+    b: Option<i32>,
+    c: Option<i32>,
+}
+
+#[test]
+fn test_row_serialization_with_none_as_unset() {
+    let spec = [
+        col("a", ColumnType::Native(NativeType::Int)),
+        col("b", ColumnType::Native(NativeType::Int)),
+        col("c", ColumnType::Native(NativeType::Int)),
+    ];
+
+    let reference = do_serialize((1_i32, MaybeUnset::<i32>::Unset, None::<i32>), &spec);
+    let row = do_serialize(
+        TestRowWithNoneAsUnset {
+            a: 1,
+            b: None,
+            c: None,
+        },
+        &spec,
+    );
+    assert_eq!(reference, row);
+    let row = do_serialize(
+        TestRowWithNoneAsUnsetEnforceOrder {
+            a: 1,
+            b: None,
+            c: None,
+        },
+        &spec,
+    );
+    assert_eq!(reference, row);
+
+    let reference = do_serialize((1_i32, 2_i32, 3_i32), &spec);
+    let row = do_serialize(
+        TestRowWithNoneAsUnset {
+            a: 1,
+            b: Some(2),
+            c: Some(3),
+        },
+        &spec,
+    );
+    assert_eq!(reference, row);
+    let row = do_serialize(
+        TestRowWithNoneAsUnsetEnforceOrder {
+            a: 1,
+            b: Some(2),
+            c: Some(3),
+        },
+        &spec,
+    );
+    assert_eq!(reference, row);
+}
+
 #[test]
 fn test_row_serialization_with_boxed_tuple() {
     let spec = [
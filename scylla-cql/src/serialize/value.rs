@@ -17,13 +17,11 @@ use uuid::Uuid;
 use crate::frame::response::result::{CollectionType, ColumnType, NativeType};
 use crate::frame::types::{unsigned_vint_encode, vint_encode};
 use crate::value::{
-    Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, MaybeUnset, Unset,
+    BytesStr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
+    CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, CqlVector, MaybeUnset, Unset,
+    ValueOverflow,
 };
 
-#[cfg(feature = "chrono-04")]
-use crate::value::ValueOverflow;
-
 use super::writers::WrittenCellProof;
 use super::{CellValueBuilder, CellWriter, SerializationError};
 
@@ -171,6 +169,15 @@ impl SerializeValue for CqlTime {
         writer.set_value(me.0.to_be_bytes().as_slice()).unwrap()
     });
 }
+impl SerializeValue for std::time::SystemTime {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Timestamp);
+        let cql_timestamp = CqlTimestamp::try_from(*me).map_err(|_: ValueOverflow| {
+            mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::ValueOverflow)
+        })?;
+        <CqlTimestamp as SerializeValue>::serialize(&cql_timestamp, typ, writer)?
+    });
+}
 #[cfg(feature = "chrono-04")]
 impl SerializeValue for chrono_04::NaiveDate {
     impl_serialize_via_writer!(|me, typ, writer| {
@@ -179,10 +186,11 @@ impl SerializeValue for chrono_04::NaiveDate {
     });
 }
 #[cfg(feature = "chrono-04")]
-impl SerializeValue for chrono_04::DateTime<chrono_04::Utc> {
+impl<Tz: chrono_04::TimeZone> SerializeValue for chrono_04::DateTime<Tz> {
     impl_serialize_via_writer!(|me, typ, writer| {
         exact_type_check!(typ, Timestamp);
-        <CqlTimestamp as SerializeValue>::serialize(&(*me).into(), typ, writer)?
+        let cql_timestamp = CqlTimestamp(me.timestamp_millis());
+        <CqlTimestamp as SerializeValue>::serialize(&cql_timestamp, typ, writer)?
     });
 }
 #[cfg(feature = "chrono-04")]
@@ -273,6 +281,37 @@ impl SerializeValue for CqlVarintBorrowed<'_> {
             .map_err(|_| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::SizeOverflow))?
     });
 }
+/// Strips the redundant leading sign bytes from a two's complement
+/// big-endian representation, leaving at least one byte.
+fn trim_varint_be_bytes(mut bytes: &[u8]) -> &[u8] {
+    while let [first, second, ..] = bytes {
+        let redundant =
+            (*first == 0x00 && second & 0x80 == 0) || (*first == 0xff && second & 0x80 != 0);
+        if !redundant {
+            break;
+        }
+        bytes = &bytes[1..];
+    }
+    bytes
+}
+impl SerializeValue for i128 {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Varint);
+        writer
+            .set_value(trim_varint_be_bytes(&me.to_be_bytes()))
+            .unwrap()
+    });
+}
+impl SerializeValue for u128 {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Varint);
+        // Prepend a zero byte, so that values with the most significant bit set
+        // are not interpreted as negative.
+        let mut bytes = [0; 17];
+        bytes[1..].copy_from_slice(&me.to_be_bytes());
+        writer.set_value(trim_varint_be_bytes(&bytes)).unwrap()
+    });
+}
 #[cfg(feature = "num-bigint-03")]
 impl SerializeValue for num_bigint_03::BigInt {
     impl_serialize_via_writer!(|me, typ, writer| {
@@ -352,6 +391,14 @@ impl SerializeValue for String {
             .map_err(|_| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::SizeOverflow))?
     });
 }
+impl SerializeValue for BytesStr {
+    impl_serialize_via_writer!(|me, typ, writer| {
+        exact_type_check!(typ, Ascii, Text);
+        writer
+            .set_value(me.as_bytes())
+            .map_err(|_| mk_ser_err::<Self>(typ, BuiltinSerializationErrorKind::SizeOverflow))?
+    });
+}
 impl<T: SerializeValue> SerializeValue for Option<T> {
     fn serialize<'b>(
         &self,
@@ -565,6 +612,33 @@ impl<'a, T: SerializeValue + 'a> SerializeValue for &'a [T] {
         }
     }
 }
+impl<T: SerializeValue> SerializeValue for CqlVector<T> {
+    fn serialize<'b>(
+        &self,
+        typ: &ColumnType,
+        writer: CellWriter<'b>,
+    ) -> Result<WrittenCellProof<'b>, SerializationError> {
+        match typ {
+            ColumnType::Vector {
+                typ: element_type,
+                dimensions,
+            } => serialize_vector(
+                std::any::type_name::<Self>(),
+                self.0.len(),
+                self.0.iter(),
+                element_type,
+                *dimensions,
+                typ,
+                writer,
+            ),
+
+            _ => Err(mk_typck_err::<Self>(
+                typ,
+                VectorTypeCheckErrorKind::NotVector,
+            )),
+        }
+    }
+}
 impl SerializeValue for CqlValue {
     fn serialize<'b>(
         &self,
@@ -653,7 +727,7 @@ fn serialize_cql_value<'b>(
     }
 }
 
-fn fix_rust_name_in_err<RustT>(mut err: SerializationError) -> SerializationError {
+pub(crate) fn fix_rust_name_in_err<RustT>(mut err: SerializationError) -> SerializationError {
     // The purpose of this function is to change the `rust_name` field
     // in the error to the given one. Most of the time, the `err` given to the
     // function here will be the sole owner of the data, so theoretically
@@ -1160,6 +1234,9 @@ pub enum BuiltinTypeCheckErrorKind {
 
     /// A type check failure specific to a CQL UDT.
     UdtError(UdtTypeCheckErrorKind),
+
+    /// A type check failure specific to a CQL vector.
+    VectorError(VectorTypeCheckErrorKind),
 }
 
 impl From<SetOrListTypeCheckErrorKind> for BuiltinTypeCheckErrorKind {
@@ -1186,6 +1263,12 @@ impl From<UdtTypeCheckErrorKind> for BuiltinTypeCheckErrorKind {
     }
 }
 
+impl From<VectorTypeCheckErrorKind> for BuiltinTypeCheckErrorKind {
+    fn from(value: VectorTypeCheckErrorKind) -> Self {
+        BuiltinTypeCheckErrorKind::VectorError(value)
+    }
+}
+
 impl Display for BuiltinTypeCheckErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1199,6 +1282,7 @@ impl Display for BuiltinTypeCheckErrorKind {
             BuiltinTypeCheckErrorKind::MapError(err) => err.fmt(f),
             BuiltinTypeCheckErrorKind::TupleError(err) => err.fmt(f),
             BuiltinTypeCheckErrorKind::UdtError(err) => err.fmt(f),
+            BuiltinTypeCheckErrorKind::VectorError(err) => err.fmt(f),
         }
     }
 }
@@ -1368,6 +1452,17 @@ impl Display for SetOrListSerializationErrorKind {
     }
 }
 
+/// Describes why type checking of a vector type failed.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum VectorTypeCheckErrorKind {
+    /// The CQL type is not a vector.
+    #[error(
+        "the CQL type the Rust type was attempted to be type checked against was not a vector"
+    )]
+    NotVector,
+}
+
 /// Describes why serialization of a vector type failed.
 #[derive(Error, Debug, Clone)]
 #[non_exhaustive]
@@ -1551,6 +1646,16 @@ mod doctests {
     /// ```
     fn _test_udt_bad_attributes_skip_name_check_conflicts_with_rename() {}
 
+    /// ```compile_fail
+    ///
+    /// #[derive(scylla_macros::SerializeValue)]
+    /// #[scylla(crate = scylla_cql, flavor = "enforce_order", skip_name_checks, rename_all = "camelCase")]
+    /// struct TestUdt {
+    ///     a: i32,
+    /// }
+    /// ```
+    fn _test_udt_bad_attributes_skip_name_check_conflicts_with_rename_all() {}
+
     /// ```compile_fail
     ///
     /// #[derive(scylla_macros::SerializeValue)]
@@ -4,12 +4,13 @@ use crate::serialize::value::{
     BuiltinTypeCheckErrorKind, MapSerializationErrorKind, MapTypeCheckErrorKind, SerializeValue,
     SetOrListSerializationErrorKind, SetOrListTypeCheckErrorKind, TupleSerializationErrorKind,
     TupleTypeCheckErrorKind, UdtSerializationErrorKind, UdtTypeCheckErrorKind,
+    VectorSerializationErrorKind, VectorTypeCheckErrorKind,
 };
 use crate::serialize::writers::WrittenCellProof;
 use crate::serialize::{CellWriter, SerializationError};
 use crate::value::{
     Counter, CqlDate, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
-    MaybeUnset, Unset,
+    CqlVector, MaybeUnset, Unset,
 };
 use crate::SerializeValue;
 
@@ -295,6 +296,64 @@ fn test_set_or_list_errors() {
         .expect("CustomSerializationError");
 }
 
+#[test]
+fn test_vector_errors() {
+    let typ = ColumnType::Vector {
+        typ: Box::new(ColumnType::Native(NativeType::Float)),
+        dimensions: 2,
+    };
+
+    // Not a vector. Unlike Vec, CqlVector does not serialize as a list.
+    let v = CqlVector(vec![1.0_f32, 2.0]);
+    let list_typ = ColumnType::Collection {
+        frozen: false,
+        typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Float))),
+    };
+    let err = do_serialize_err(v, &list_typ);
+    let err = get_typeck_err(&err);
+    assert_eq!(err.rust_name, std::any::type_name::<CqlVector<f32>>());
+    assert_eq!(err.got, list_typ);
+    assert_matches!(
+        err.kind,
+        BuiltinTypeCheckErrorKind::VectorError(VectorTypeCheckErrorKind::NotVector)
+    );
+
+    // Wrong number of dimensions
+    let v = CqlVector(vec![1.0_f32, 2.0, 3.0]);
+    let err = do_serialize_err(v, &typ);
+    let err = get_ser_err(&err);
+    assert_eq!(err.got, typ);
+    assert_matches!(
+        err.kind,
+        BuiltinSerializationErrorKind::VectorError(
+            VectorSerializationErrorKind::InvalidNumberOfElements(3, 2)
+        )
+    );
+
+    // Error during serialization of an element
+    let v = CqlVector(vec![1_i32, 2]);
+    let err = do_serialize_err(v, &typ);
+    let err = get_ser_err(&err);
+    let BuiltinSerializationErrorKind::VectorError(
+        VectorSerializationErrorKind::ElementSerializationFailed(err),
+    ) = &err.kind
+    else {
+        panic!("unexpected error kind: {}", err.kind)
+    };
+    let err = get_typeck_err(err);
+    assert_matches!(
+        err.kind,
+        BuiltinTypeCheckErrorKind::MismatchedType {
+            expected: &[ColumnType::Native(NativeType::Int)],
+        }
+    );
+
+    // Correct serialization
+    let v = CqlVector(vec![1.0_f32, 2.0]);
+    let data = do_serialize(v, &typ);
+    assert_eq!(data, [0, 0, 0, 8, 0x3f, 0x80, 0, 0, 0x40, 0, 0, 0]);
+}
+
 #[test]
 fn test_map_errors() {
     // Not a map
@@ -1342,6 +1401,49 @@ fn test_udt_serialization_with_field_rename_and_enforce_order() {
     assert_eq!(reference, udt);
 }
 
+#[derive(SerializeValue, Debug)]
+#[scylla(crate = crate, rename_all = "PascalCase")]
+struct TestUdtWithRenameAll {
+    first_name: String,
+    #[scylla(rename = "x")]
+    age: i32,
+}
+
+#[test]
+fn test_udt_serialization_with_rename_all() {
+    let typ = ColumnType::UserDefinedType {
+        frozen: false,
+        definition: Arc::new(UserDefinedType {
+            name: "typ".into(),
+            keyspace: "ks".into(),
+            field_types: vec![
+                ("x".into(), ColumnType::Native(NativeType::Int)),
+                ("FirstName".into(), ColumnType::Native(NativeType::Text)),
+            ],
+        }),
+    };
+
+    let mut reference = Vec::new();
+    // Total length of the struct is 23
+    reference.extend_from_slice(&23i32.to_be_bytes());
+    // Field 'x'
+    reference.extend_from_slice(&4i32.to_be_bytes());
+    reference.extend_from_slice(&42i32.to_be_bytes());
+    // Field 'FirstName'
+    reference.extend_from_slice(&("Ala ma kota".len() as i32).to_be_bytes());
+    reference.extend_from_slice("Ala ma kota".as_bytes());
+
+    let udt = do_serialize(
+        TestUdtWithRenameAll {
+            first_name: "Ala ma kota".to_owned(),
+            age: 42,
+        },
+        &typ,
+    );
+
+    assert_eq!(reference, udt);
+}
+
 #[expect(unused)]
 #[derive(SerializeValue, Debug)]
 #[scylla(crate = crate, flavor = "enforce_order", skip_name_checks)]
@@ -1708,6 +1810,53 @@ fn cql_varint_serialization() {
     }
 }
 
+#[test]
+fn i128_u128_varint_serialization() {
+    let typ = ColumnType::Native(NativeType::Varint);
+    let with_len = |b: &[u8]| {
+        (b.len() as i32)
+            .to_be_bytes()
+            .iter()
+            .chain(b)
+            .cloned()
+            .collect::<Vec<_>>()
+    };
+
+    let signed_cases: &[(i128, &[u8])] = &[
+        (0, &[0x00]),
+        (1, &[0x01]),
+        (127, &[0x7F]),
+        (128, &[0x00, 0x80]),
+        (129, &[0x00, 0x81]),
+        (-1, &[0xFF]),
+        (-128, &[0x80]),
+        (-129, &[0xFF, 0x7F]),
+        (
+            i128::MIN,
+            &[0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+        ),
+    ];
+    for (i, b) in signed_cases {
+        assert_eq!(do_serialize(*i, &typ), with_len(b));
+    }
+
+    let unsigned_cases: &[(u128, &[u8])] = &[
+        (0, &[0x00]),
+        (127, &[0x7F]),
+        (128, &[0x00, 0x80]),
+        (
+            u128::MAX,
+            &[
+                0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                0xFF, 0xFF, 0xFF,
+            ],
+        ),
+    ];
+    for (u, b) in unsigned_cases {
+        assert_eq!(do_serialize(*u, &typ), with_len(b));
+    }
+}
+
 #[cfg(any(
     feature = "num-bigint-03",
     feature = "num-bigint-04",
@@ -2121,6 +2270,69 @@ fn date_time_04_serialization() {
     }
 }
 
+#[cfg(feature = "chrono-04")]
+#[test]
+fn date_time_04_with_offset_serialization() {
+    use chrono_04::{DateTime, FixedOffset, Utc};
+
+    let utc = DateTime::<Utc>::from_timestamp_millis(1687219200123).unwrap();
+    let expected = do_serialize(utc, &ColumnType::Native(NativeType::Timestamp));
+
+    // The offset is dropped, only the instant is written
+    for offset in [
+        FixedOffset::east_opt(0).unwrap(),
+        FixedOffset::east_opt(2 * 3600).unwrap(),
+        FixedOffset::west_opt(9 * 3600 + 30 * 60).unwrap(),
+    ] {
+        let bytes = do_serialize(
+            utc.with_timezone(&offset),
+            &ColumnType::Native(NativeType::Timestamp),
+        );
+        assert_eq!(bytes, expected);
+    }
+}
+
+#[test]
+fn system_time_serialization() {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    let test_cases = [
+        // UNIX epoch baseline
+        (UNIX_EPOCH, 0i64),
+        // One second since UNIX epoch
+        (UNIX_EPOCH + Duration::from_secs(1), 1000),
+        // 1 nanosecond since UNIX epoch, lost during serialization
+        (UNIX_EPOCH + Duration::from_nanos(1), 0),
+        // 1 nanosecond before UNIX epoch, rounded down to the previous millisecond
+        (UNIX_EPOCH - Duration::from_nanos(1), -1),
+        // 2 days before UNIX epoch
+        (
+            UNIX_EPOCH - Duration::from_secs(2 * 24 * 60 * 60),
+            -2 * 24 * 60 * 60 * 1000,
+        ),
+    ];
+    for (test_time, expected) in test_cases {
+        let bytes = do_serialize(test_time, &ColumnType::Native(NativeType::Timestamp));
+
+        let mut expected_bytes: Vec<u8> = vec![0, 0, 0, 8];
+        expected_bytes.extend_from_slice(&expected.to_be_bytes());
+
+        assert_eq!(bytes, expected_bytes);
+    }
+
+    // Times too far from the epoch to be represented in milliseconds must return error
+    // (if the platform can represent them at all)
+    let far_future = UNIX_EPOCH.checked_add(Duration::from_secs(1 << 60));
+    let far_past = UNIX_EPOCH.checked_sub(Duration::from_secs(1 << 60));
+    for test_time in [far_future, far_past].into_iter().flatten() {
+        let err = do_serialize_err(test_time, &ColumnType::Native(NativeType::Timestamp));
+        assert_matches!(
+            get_ser_err(&err).kind,
+            BuiltinSerializationErrorKind::ValueOverflow
+        );
+    }
+}
+
 #[cfg(feature = "time-03")]
 #[test]
 fn offset_date_time_03_serialization() {
@@ -3,6 +3,9 @@
 
 use std::net::IpAddr;
 use std::result::Result as StdResult;
+use std::sync::Arc;
+
+use bytes::Bytes;
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -15,8 +18,10 @@ use crate::deserialize::value::{
 };
 use crate::deserialize::DeserializationError;
 use crate::deserialize::FrameSlice;
-use crate::frame::response::result::{CollectionType, ColumnType};
+use crate::frame::response::result::{CollectionType, ColumnType, UserDefinedType};
 use crate::frame::types;
+use crate::serialize::value::SerializeValue;
+use crate::serialize::{CellWriter, SerializationError};
 use crate::utils::safe_format::IteratorSafeFormatExt;
 
 /// Error type indicating that the value is too large to fit in the destination type.
@@ -156,6 +161,76 @@ impl CqlTimeuuid {
 }
 
 impl CqlTimeuuid {
+    /// The difference between the UUID epoch (1582-10-15) and the Unix epoch,
+    /// in 100-nanosecond intervals.
+    const UUID_EPOCH_OFFSET: i64 = 0x01B21DD213814000;
+
+    /// The number of 100-nanosecond intervals in a millisecond.
+    const INTERVALS_PER_MILLI: i64 = 10_000;
+
+    /// The smallest clock sequence and node, according to the signed comparison
+    /// of least significant bits.
+    const MIN_CLOCK_SEQ_AND_NODE: u64 = 0x8080808080808080;
+
+    /// The greatest clock sequence and node, according to the signed comparison
+    /// of least significant bits.
+    const MAX_CLOCK_SEQ_AND_NODE: u64 = 0x7f7f7f7f7f7f7f7f;
+
+    /// Returns the timestamp of the `CqlTimeuuid`, with millisecond precision.
+    ///
+    /// This is the same as the `toTimestamp` CQL function returns.
+    /// The version of the UUID is not verified.
+    pub fn timestamp(&self) -> CqlTimestamp {
+        let intervals = self.msb() as i64 - Self::UUID_EPOCH_OFFSET;
+        CqlTimestamp(intervals.div_euclid(Self::INTERVALS_PER_MILLI))
+    }
+
+    /// Returns the smallest `CqlTimeuuid` with the given timestamp.
+    ///
+    /// This is the same value as the `minTimeuuid` CQL function returns, so
+    /// `t >= minTimeuuid(?)` restrictions can be evaluated client-side with
+    /// `t >= CqlTimeuuid::min_for_timestamp(timestamp)`.
+    ///
+    /// Like in ScyllaDB, timestamps outside of the range representable by
+    /// a timeuuid (years 1582 to 5236) wrap around.
+    pub fn min_for_timestamp(timestamp: CqlTimestamp) -> Self {
+        let intervals = timestamp.0.wrapping_mul(Self::INTERVALS_PER_MILLI);
+        Self::from_time_and_lsb(intervals, Self::MIN_CLOCK_SEQ_AND_NODE)
+    }
+
+    /// Returns the greatest `CqlTimeuuid` with the given timestamp.
+    ///
+    /// This is the same value as the `maxTimeuuid` CQL function returns, so
+    /// `t <= maxTimeuuid(?)` restrictions can be evaluated client-side with
+    /// `t <= CqlTimeuuid::max_for_timestamp(timestamp)`.
+    ///
+    /// Like in ScyllaDB, timestamps outside of the range representable by
+    /// a timeuuid (years 1582 to 5236) wrap around.
+    pub fn max_for_timestamp(timestamp: CqlTimestamp) -> Self {
+        // The last 100-nanosecond interval of the millisecond
+        let intervals = timestamp
+            .0
+            .wrapping_add(1)
+            .wrapping_mul(Self::INTERVALS_PER_MILLI)
+            .wrapping_sub(1);
+        Self::from_time_and_lsb(intervals, Self::MAX_CLOCK_SEQ_AND_NODE)
+    }
+
+    /// Builds a version 1 UUID from the number of 100-nanosecond intervals
+    /// since the Unix epoch and 8 least significant bytes.
+    fn from_time_and_lsb(intervals_since_unix_epoch: i64, lsb: u64) -> Self {
+        let time = intervals_since_unix_epoch.wrapping_add(Self::UUID_EPOCH_OFFSET) as u64;
+        let time_low = time as u32;
+        let time_mid = (time >> 32) as u16;
+        let time_hi_and_version = ((time >> 48) as u16 & 0x0fff) | 0x1000;
+        Self(Uuid::from_fields(
+            time_low,
+            time_mid,
+            time_hi_and_version,
+            &lsb.to_be_bytes(),
+        ))
+    }
+
     /// Read 8 most significant bytes of timeuuid from serialized bytes
     fn msb(&self) -> u64 {
         // Scylla and Cassandra use a standard UUID memory layout for MSB:
@@ -660,7 +735,9 @@ pub struct CqlTimestamp(pub i64);
 pub struct CqlTime(pub i64);
 
 impl CqlDate {
-    fn try_to_chrono_04_naive_date(&self) -> Result<chrono_04::NaiveDate, ValueOverflow> {
+    pub(crate) fn try_to_chrono_04_naive_date(
+        &self,
+    ) -> Result<chrono_04::NaiveDate, ValueOverflow> {
         let days_since_unix_epoch = self.0 as i64 - (1 << 31);
 
         // date_days is u32 then converted to i64 then we subtract 2^31;
@@ -698,7 +775,7 @@ impl TryInto<chrono_04::NaiveDate> for CqlDate {
 }
 
 impl CqlTimestamp {
-    fn try_to_chrono_04_datetime_utc(
+    pub(crate) fn try_to_chrono_04_datetime_utc(
         &self,
     ) -> Result<chrono_04::DateTime<chrono_04::Utc>, ValueOverflow> {
         use chrono_04::TimeZone;
@@ -707,11 +784,62 @@ impl CqlTimestamp {
             _ => Err(ValueOverflow),
         }
     }
+
+    /// Converts the timestamp into a `chrono::DateTime` in the given time zone.
+    ///
+    /// Timestamps carry no time zone information, so the caller decides which
+    /// zone the value should be presented in. Fails with [`ValueOverflow`] if the
+    /// timestamp does not fit in `chrono::DateTime`.
+    #[cfg(feature = "chrono-04")]
+    pub fn try_to_chrono_04_datetime_in<Tz: chrono_04::TimeZone>(
+        &self,
+        tz: &Tz,
+    ) -> Result<chrono_04::DateTime<Tz>, ValueOverflow> {
+        self.try_to_chrono_04_datetime_utc()
+            .map(|datetime| datetime.with_timezone(tz))
+    }
+}
+
+impl TryFrom<std::time::SystemTime> for CqlTimestamp {
+    type Error = ValueOverflow;
+
+    fn try_from(value: std::time::SystemTime) -> Result<Self, Self::Error> {
+        const NANOS_PER_MILLI: u128 = 1_000_000;
+
+        // Sub-millisecond precision is truncated towards negative infinity,
+        // the same way as for `chrono` and `time` types.
+        let millis = match value.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after_epoch) => {
+                i128::try_from(after_epoch.as_millis()).map_err(|_| ValueOverflow)?
+            }
+            Err(err) => {
+                let before_epoch =
+                    (err.duration().as_nanos() + NANOS_PER_MILLI - 1) / NANOS_PER_MILLI;
+                -i128::try_from(before_epoch).map_err(|_| ValueOverflow)?
+            }
+        };
+
+        i64::try_from(millis).map(Self).map_err(|_| ValueOverflow)
+    }
+}
+
+impl TryInto<std::time::SystemTime> for CqlTimestamp {
+    type Error = ValueOverflow;
+
+    fn try_into(self) -> Result<std::time::SystemTime, Self::Error> {
+        let offset = std::time::Duration::from_millis(self.0.unsigned_abs());
+        if self.0 >= 0 {
+            std::time::UNIX_EPOCH.checked_add(offset)
+        } else {
+            std::time::UNIX_EPOCH.checked_sub(offset)
+        }
+        .ok_or(ValueOverflow)
+    }
 }
 
 #[cfg(feature = "chrono-04")]
-impl From<chrono_04::DateTime<chrono_04::Utc>> for CqlTimestamp {
-    fn from(value: chrono_04::DateTime<chrono_04::Utc>) -> Self {
+impl<Tz: chrono_04::TimeZone> From<chrono_04::DateTime<Tz>> for CqlTimestamp {
+    fn from(value: chrono_04::DateTime<Tz>) -> Self {
         Self(value.timestamp_millis())
     }
 }
@@ -864,8 +992,22 @@ impl TryInto<time_03::Time> for CqlTime {
 }
 
 /// Represents a CQL Duration value
+///
+/// A CQL duration consists of three independent components: months, days and nanoseconds.
+/// They are kept separate, because the length of a month or a day (e.g. due to DST changes)
+/// depends on the point in time the duration is added to. The database requires all
+/// the components to have the same sign.
+///
+/// # Conversions
+/// Conversions from [`std::time::Duration`], `chrono::Duration` (feature `chrono-04`)
+/// and `time::Duration` (feature `time-03`) put the whole duration into the `nanoseconds`
+/// component, leaving `months` and `days` zero.
+///
+/// Conversions in the other direction are lossy: a day is assumed to be 24 hours long,
+/// and a month is assumed to be the average length of a month in the Gregorian calendar,
+/// i.e. 30.436875 days ([`CqlDuration::AVERAGE_MONTH`]).
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
-#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq)]
 pub struct CqlDuration {
     /// Number of months.
     pub months: i32,
@@ -875,6 +1017,393 @@ pub struct CqlDuration {
     pub nanoseconds: i64,
 }
 
+impl CqlDuration {
+    /// A duration of zero length.
+    pub const ZERO: Self = Self {
+        months: 0,
+        days: 0,
+        nanoseconds: 0,
+    };
+
+    /// The average length of a month in the Gregorian calendar (2 629 746 seconds),
+    /// used when converting a duration with months to a fixed-length duration.
+    pub const AVERAGE_MONTH: std::time::Duration = std::time::Duration::from_secs(2_629_746);
+
+    const NANOS_PER_DAY: i128 = 86_400 * 1_000_000_000;
+
+    /// Creates a new duration from its components.
+    pub const fn new(months: i32, days: i32, nanoseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            nanoseconds,
+        }
+    }
+
+    /// Adds two durations component-wise.
+    /// Returns `None` if any of the components overflows.
+    ///
+    /// Note that adding durations of different signs may result in a duration
+    /// with components of different signs, which is rejected by the database.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_add(rhs.months)?,
+            days: self.days.checked_add(rhs.days)?,
+            nanoseconds: self.nanoseconds.checked_add(rhs.nanoseconds)?,
+        })
+    }
+
+    /// Subtracts two durations component-wise.
+    /// Returns `None` if any of the components overflows.
+    ///
+    /// See [`CqlDuration::checked_add`] for a remark about signs of the components.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_sub(rhs.months)?,
+            days: self.days.checked_sub(rhs.days)?,
+            nanoseconds: self.nanoseconds.checked_sub(rhs.nanoseconds)?,
+        })
+    }
+
+    /// Negates all the components of the duration.
+    /// Returns `None` if any of the components overflows.
+    pub fn checked_neg(self) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_neg()?,
+            days: self.days.checked_neg()?,
+            nanoseconds: self.nanoseconds.checked_neg()?,
+        })
+    }
+
+    /// Multiplies all the components of the duration by `rhs`.
+    /// Returns `None` if any of the components overflows.
+    pub fn checked_mul(self, rhs: i32) -> Option<Self> {
+        Some(Self {
+            months: self.months.checked_mul(rhs)?,
+            days: self.days.checked_mul(rhs)?,
+            nanoseconds: self.nanoseconds.checked_mul(rhs as i64)?,
+        })
+    }
+
+    /// Returns `true` if all the components are zero.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// Returns `true` if any of the components is negative.
+    pub fn is_negative(&self) -> bool {
+        self.months < 0 || self.days < 0 || self.nanoseconds < 0
+    }
+
+    /// The total length of the duration in nanoseconds, with a day assumed
+    /// to be 24 hours long and a month to be [`CqlDuration::AVERAGE_MONTH`] long.
+    ///
+    /// The result always fits in `i128`.
+    pub fn approximate_total_nanoseconds(&self) -> i128 {
+        self.months as i128 * Self::AVERAGE_MONTH.as_nanos() as i128
+            + self.days as i128 * Self::NANOS_PER_DAY
+            + self.nanoseconds as i128
+    }
+}
+
+impl std::ops::Add for CqlDuration {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if any of the components overflows.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl std::ops::Sub for CqlDuration {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if any of the components overflows.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl std::ops::Neg for CqlDuration {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if any of the components overflows.
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("overflow when negating duration")
+    }
+}
+
+impl std::ops::Mul<i32> for CqlDuration {
+    type Output = Self;
+
+    /// # Panics
+    /// Panics if any of the components overflows.
+    fn mul(self, rhs: i32) -> Self::Output {
+        self.checked_mul(rhs)
+            .expect("overflow when multiplying duration by scalar")
+    }
+}
+
+impl std::ops::AddAssign for CqlDuration {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for CqlDuration {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl TryFrom<std::time::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    fn try_from(value: std::time::Duration) -> Result<Self, Self::Error> {
+        let nanoseconds = value.as_nanos().try_into().map_err(|_| ValueOverflow)?;
+        Ok(Self::new(0, 0, nanoseconds))
+    }
+}
+
+impl TryInto<std::time::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    /// Fails if the duration is negative or too large.
+    /// See [`CqlDuration`] for details on how months and days are converted.
+    fn try_into(self) -> Result<std::time::Duration, Self::Error> {
+        let nanos: u128 = self
+            .approximate_total_nanoseconds()
+            .try_into()
+            .map_err(|_| ValueOverflow)?;
+        let secs = (nanos / 1_000_000_000)
+            .try_into()
+            .map_err(|_| ValueOverflow)?;
+        Ok(std::time::Duration::new(
+            secs,
+            (nanos % 1_000_000_000) as u32,
+        ))
+    }
+}
+
+#[cfg(feature = "chrono-04")]
+impl TryFrom<chrono_04::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    fn try_from(value: chrono_04::Duration) -> Result<Self, Self::Error> {
+        let nanoseconds = value.num_nanoseconds().ok_or(ValueOverflow)?;
+        Ok(Self::new(0, 0, nanoseconds))
+    }
+}
+
+#[cfg(feature = "chrono-04")]
+impl From<CqlDuration> for chrono_04::Duration {
+    /// See [`CqlDuration`] for details on how months and days are converted.
+    fn from(value: CqlDuration) -> Self {
+        let nanos = value.approximate_total_nanoseconds();
+        // The maximal total length of a CQL duration (around 185 million years)
+        // is well within the range of chrono's duration, so none of this can fail.
+        let secs = (nanos / 1_000_000_000) as i64;
+        let subsec_nanos = (nanos % 1_000_000_000) as i64;
+        chrono_04::Duration::try_seconds(secs).unwrap()
+            + chrono_04::Duration::nanoseconds(subsec_nanos)
+    }
+}
+
+#[cfg(feature = "time-03")]
+impl TryFrom<time_03::Duration> for CqlDuration {
+    type Error = ValueOverflow;
+
+    fn try_from(value: time_03::Duration) -> Result<Self, Self::Error> {
+        let nanoseconds = value
+            .whole_nanoseconds()
+            .try_into()
+            .map_err(|_| ValueOverflow)?;
+        Ok(Self::new(0, 0, nanoseconds))
+    }
+}
+
+#[cfg(feature = "time-03")]
+impl From<CqlDuration> for time_03::Duration {
+    /// See [`CqlDuration`] for details on how months and days are converted.
+    fn from(value: CqlDuration) -> Self {
+        let nanos = value.approximate_total_nanoseconds();
+        // Seconds always fit in i64, see the chrono conversion.
+        time_03::Duration::new(
+            (nanos / 1_000_000_000) as i64,
+            (nanos % 1_000_000_000) as i32,
+        )
+    }
+}
+
+/// Represents a CQL `vector<T, n>` value.
+///
+/// [`Vec<T>`] and slices can also be used with CQL vectors, but they
+/// are accepted for CQL lists and sets too. `CqlVector` type checks only
+/// against the vector type, so a mistake in the schema or the statement
+/// is reported as a type check error instead of going unnoticed.
+///
+/// The number of elements must match the number of dimensions of the vector
+/// type, which is checked during serialization.
+///
+/// # Example
+/// ```rust
+/// # use scylla_cql::value::CqlVector;
+/// let embedding: CqlVector<f32> = vec![0.1, 0.2, 0.3].into();
+/// assert_eq!(embedding.dimensions(), 3);
+/// assert_eq!(Vec::from(embedding), vec![0.1, 0.2, 0.3]);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CqlVector<T>(pub Vec<T>);
+
+impl<T> CqlVector<T> {
+    /// Returns the number of elements, which is the number of dimensions of the vector.
+    pub fn dimensions(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the elements of the vector.
+    pub fn as_slice(&self) -> &[T] {
+        &self.0
+    }
+
+    /// Converts the vector into its elements.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<T>> for CqlVector<T> {
+    fn from(value: Vec<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<CqlVector<T>> for Vec<T> {
+    fn from(value: CqlVector<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> FromIterator<T> for CqlVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> AsRef<[T]> for CqlVector<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+/// An immutable UTF-8 string backed by [Bytes].
+///
+/// Values of `text` and `ascii` columns deserialized as `BytesStr` are not copied
+/// out of the response frame; they share its buffer instead. Unlike `&str`, they
+/// do not borrow from the frame, so they can be kept after the result is dropped,
+/// e.g. when streaming rows. Note that the whole frame buffer stays allocated
+/// as long as any `BytesStr` referring to it is alive.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BytesStr(Bytes);
+
+impl BytesStr {
+    /// Creates a string from bytes containing valid UTF-8.
+    pub fn from_utf8(bytes: Bytes) -> Result<Self, std::str::Utf8Error> {
+        std::str::from_utf8(&bytes)?;
+        Ok(Self(bytes))
+    }
+
+    /// Creates a string from a static string slice, without copying it.
+    pub const fn from_static(s: &'static str) -> Self {
+        Self(Bytes::from_static(s.as_bytes()))
+    }
+
+    /// Returns the string as a string slice.
+    pub fn as_str(&self) -> &str {
+        // SAFETY:
+        // `BytesStr` can only be constructed from valid UTF-8, and `Bytes` are immutable.
+        unsafe { std::str::from_utf8_unchecked(&self.0) }
+    }
+
+    /// Returns the underlying bytes.
+    pub fn as_bytes(&self) -> &Bytes {
+        &self.0
+    }
+
+    /// Converts the string into the underlying bytes.
+    pub fn into_bytes(self) -> Bytes {
+        self.0
+    }
+}
+
+impl std::ops::Deref for BytesStr {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for BytesStr {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::borrow::Borrow<str> for BytesStr {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<String> for BytesStr {
+    fn from(value: String) -> Self {
+        Self(Bytes::from(value))
+    }
+}
+
+impl From<&'static str> for BytesStr {
+    fn from(value: &'static str) -> Self {
+        Self::from_static(value)
+    }
+}
+
+impl From<BytesStr> for Bytes {
+    fn from(value: BytesStr) -> Self {
+        value.0
+    }
+}
+
+impl PartialEq<str> for BytesStr {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for BytesStr {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl std::fmt::Debug for BytesStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl std::fmt::Display for BytesStr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
 /// Represents all possible CQL values that can be returned by the database.
 ///
 /// This type can represent a CQL value of any type. Therefore, it should be used in places
@@ -1181,6 +1710,14 @@ impl CqlValue {
         }
     }
 
+    /// Casts the value to a vec of CQL values if it is of `vector` type.
+    pub fn as_vector(&self) -> Option<&Vec<CqlValue>> {
+        match self {
+            Self::Vector(s) => Some(s),
+            _ => None,
+        }
+    }
+
     /// Casts the value to a user-defined type (UDT) if it is of that type.
     /// The UDT is represented as a vector of pairs,
     /// where each pair consists of a field name and an optional (=nullable) value.
@@ -1236,6 +1773,130 @@ impl CqlValue {
     // TODO
 }
 
+/// Builds [CqlValue::UserDefinedType] values at runtime, validating them
+/// against the definition of the UDT, e.g. one from the schema metadata
+/// fetched by the driver.
+///
+/// Fields which are not set are null. Fields are validated as they are set,
+/// so that errors point to the offending field.
+///
+/// # Example
+/// ```rust
+/// # use std::sync::Arc;
+/// # use scylla_cql::frame::response::result::{ColumnType, NativeType, UserDefinedType};
+/// # use scylla_cql::value::{CqlValue, UdtValueBuilder};
+/// let definition = Arc::new(UserDefinedType {
+///     name: "address".into(),
+///     keyspace: "ks".into(),
+///     field_types: vec![
+///         ("street".into(), ColumnType::Native(NativeType::Text)),
+///         ("number".into(), ColumnType::Native(NativeType::Int)),
+///     ],
+/// });
+///
+/// let address: CqlValue = UdtValueBuilder::new(definition.clone())
+///     .field("street", CqlValue::Text("Baker Street".to_owned()))?
+///     .field("number", CqlValue::Int(221))?
+///     .build();
+///
+/// // Values not matching the field type are rejected.
+/// assert!(UdtValueBuilder::new(definition)
+///     .field("number", CqlValue::Text("221b".to_owned()))
+///     .is_err());
+/// # Ok::<(), scylla_cql::value::UdtValueError>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct UdtValueBuilder {
+    definition: Arc<UserDefinedType<'static>>,
+    fields: Vec<Option<CqlValue>>,
+}
+
+impl UdtValueBuilder {
+    /// Creates a builder of a value of the given UDT, with all fields set to null.
+    pub fn new(definition: Arc<UserDefinedType<'static>>) -> Self {
+        let fields = vec![None; definition.field_types.len()];
+        Self { definition, fields }
+    }
+
+    /// Sets the field of the given name.
+    ///
+    /// Fails if there is no such field in the UDT, or if the value cannot be
+    /// serialized as the type of the field.
+    pub fn field(mut self, name: &str, value: CqlValue) -> Result<Self, UdtValueError> {
+        self.set_field(name, Some(value))?;
+        Ok(self)
+    }
+
+    /// Sets the field of the given name to the given value, or to null.
+    ///
+    /// Fails under the same conditions as [UdtValueBuilder::field].
+    pub fn set_field(
+        &mut self,
+        name: &str,
+        value: Option<CqlValue>,
+    ) -> Result<&mut Self, UdtValueError> {
+        let index = self
+            .definition
+            .field_types
+            .iter()
+            .position(|(field_name, _)| field_name == name)
+            .ok_or_else(|| UdtValueError::NoSuchField {
+                field_name: name.to_owned(),
+            })?;
+        if let Some(value) = &value {
+            let typ = &self.definition.field_types[index].1;
+            let mut buf = Vec::new();
+            value
+                .serialize(typ, CellWriter::new(&mut buf))
+                .map_err(|err| UdtValueError::FieldTypeMismatch {
+                    field_name: name.to_owned(),
+                    err,
+                })?;
+        }
+        self.fields[index] = value;
+        Ok(self)
+    }
+
+    /// Builds the value, with fields in the order of the definition.
+    pub fn build(self) -> CqlValue {
+        let UserDefinedType {
+            name,
+            keyspace,
+            field_types,
+        } = &*self.definition;
+        CqlValue::UserDefinedType {
+            keyspace: keyspace.clone().into_owned(),
+            name: name.clone().into_owned(),
+            fields: field_types
+                .iter()
+                .zip(self.fields)
+                .map(|((field_name, _), value)| (field_name.clone().into_owned(), value))
+                .collect(),
+        }
+    }
+}
+
+/// An error returned by [UdtValueBuilder] when a field cannot be set.
+#[derive(Debug, Error, Clone)]
+#[non_exhaustive]
+pub enum UdtValueError {
+    /// The UDT has no field of the given name.
+    #[error("The UDT has no field named {field_name}")]
+    NoSuchField {
+        /// Name of the field.
+        field_name: String,
+    },
+
+    /// The value does not match the type of the field.
+    #[error("Value of field {field_name} does not match its type: {err}")]
+    FieldTypeMismatch {
+        /// Name of the field.
+        field_name: String,
+        /// The error returned when serializing the value as the type of the field.
+        err: SerializationError,
+    },
+}
+
 /// Displays a CqlValue. The syntax should resemble the CQL literal syntax
 /// (but no guarantee is given that it's always the same).
 impl std::fmt::Display for CqlValue {
@@ -1573,6 +2234,90 @@ mod tests {
         assert_eq!(0x0000000000000000, uuid.lsb());
     }
 
+    #[test]
+    fn timeuuid_timestamp() {
+        // 2023-06-19 23:49:56.990 UTC
+        let uuid = CqlTimeuuid::from_str("fed35080-0efb-11ee-a1ca-00006490e9a4").unwrap();
+        assert_eq!(uuid.timestamp(), CqlTimestamp(1687218596990));
+
+        // The UUID epoch, 1582-10-15
+        let uuid = CqlTimeuuid::from_str("00000000-0000-1000-8080-808080808080").unwrap();
+        assert_eq!(uuid.timestamp(), CqlTimestamp(-12219292800000));
+
+        // Rounded towards the past
+        let uuid = CqlTimeuuid::from_str("00000001-0000-1000-8080-808080808080").unwrap();
+        assert_eq!(uuid.timestamp(), CqlTimestamp(-12219292800000));
+    }
+
+    #[test]
+    fn timeuuid_min_max_for_timestamp() {
+        let timestamp = CqlTimestamp(1687218596990);
+        let min = CqlTimeuuid::min_for_timestamp(timestamp);
+        let max = CqlTimeuuid::max_for_timestamp(timestamp);
+        assert_eq!(
+            min.to_string(),
+            "fed339e0-0efb-11ee-8080-808080808080".to_owned()
+        );
+        assert_eq!(
+            max.to_string(),
+            "fed360ef-0efb-11ee-7f7f-7f7f7f7f7f7f".to_owned()
+        );
+        assert_eq!(min.timestamp(), timestamp);
+        assert_eq!(max.timestamp(), timestamp);
+
+        // All timeuuids with the timestamp are in range, regardless of
+        // the clock sequence and node
+        for uuid in [
+            "fed339e0-0efb-11ee-8080-808080808080",
+            "fed339e0-0efb-11ee-0000-000000000000",
+            "fed35080-0efb-11ee-ffff-ffffffffffff",
+            "fed35081-0efb-11ee-a1ca-00006490e9a4",
+            "fed360ef-0efb-11ee-7f7f-7f7f7f7f7f7f",
+        ] {
+            let uuid = CqlTimeuuid::from_str(uuid).unwrap();
+            assert!(min <= uuid && uuid <= max, "{uuid} out of range");
+        }
+
+        // Timeuuids of adjacent milliseconds are not
+        let previous = CqlTimeuuid::max_for_timestamp(CqlTimestamp(timestamp.0 - 1));
+        let next = CqlTimeuuid::min_for_timestamp(CqlTimestamp(timestamp.0 + 1));
+        assert!(previous < min);
+        assert!(max < next);
+        assert_eq!(previous.timestamp(), CqlTimestamp(timestamp.0 - 1));
+        assert_eq!(next.timestamp(), CqlTimestamp(timestamp.0 + 1));
+    }
+
+    #[cfg(feature = "chrono-04")]
+    #[test]
+    fn timestamp_to_chrono_04_datetime_in_zone() {
+        use chrono_04::{FixedOffset, NaiveDate, TimeZone as _};
+
+        // 2023-06-20 00:00:00.123 UTC
+        let timestamp = CqlTimestamp(1687219200123);
+        let tz = FixedOffset::west_opt(5 * 3600).unwrap();
+
+        let datetime = timestamp.try_to_chrono_04_datetime_in(&tz).unwrap();
+        assert_eq!(datetime.offset(), &tz);
+        assert_eq!(
+            datetime.naive_local(),
+            NaiveDate::from_ymd_opt(2023, 6, 19)
+                .unwrap()
+                .and_hms_milli_opt(19, 0, 0, 123)
+                .unwrap()
+        );
+        assert_eq!(CqlTimestamp::from(datetime), timestamp);
+        assert_eq!(
+            datetime,
+            tz.timestamp_millis_opt(timestamp.0).single().unwrap()
+        );
+
+        // Out of chrono's range
+        assert_eq!(
+            CqlTimestamp(i64::MAX).try_to_chrono_04_datetime_in(&tz),
+            Err(ValueOverflow)
+        );
+    }
+
     #[test]
     fn test_cql_value_displayer() {
         assert_eq!(format!("{}", CqlValue::Boolean(true)), "true");
@@ -1674,4 +2419,143 @@ mod tests {
             "{foo:123,bar:321}"
         );
     }
+
+    #[test]
+    fn cql_duration_arithmetic() {
+        let a = CqlDuration::new(1, 2, 3);
+        let b = CqlDuration::new(10, 20, 30);
+
+        assert_eq!(a + b, CqlDuration::new(11, 22, 33));
+        assert_eq!(b - a, CqlDuration::new(9, 18, 27));
+        assert_eq!(-a, CqlDuration::new(-1, -2, -3));
+        assert_eq!(a * 3, CqlDuration::new(3, 6, 9));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+
+        assert!(CqlDuration::ZERO.is_zero());
+        assert!((a - b).is_negative());
+
+        let max = CqlDuration::new(i32::MAX, 0, 0);
+        assert_eq!(max.checked_add(a), None);
+        assert_eq!(CqlDuration::new(i32::MIN, 0, 0).checked_neg(), None);
+        assert_eq!(max.checked_mul(2), None);
+    }
+
+    #[test]
+    fn cql_duration_std_conversions() {
+        let std_duration = std::time::Duration::new(90_061, 5);
+        let duration = CqlDuration::try_from(std_duration).unwrap();
+        assert_eq!(duration, CqlDuration::new(0, 0, 90_061_000_000_005));
+        let converted: std::time::Duration = duration.try_into().unwrap();
+        assert_eq!(converted, std_duration);
+
+        // Days are 24 hours, months are 1/12 of an average Gregorian year.
+        let converted: std::time::Duration = CqlDuration::new(12, 1, 1).try_into().unwrap();
+        assert_eq!(
+            converted,
+            std::time::Duration::new(365 * 86_400 + 20_952 + 86_400, 1)
+        );
+
+        // Negative durations can't be represented.
+        TryInto::<std::time::Duration>::try_into(CqlDuration::new(0, 0, -1)).unwrap_err();
+
+        // Too large to fit in nanoseconds.
+        CqlDuration::try_from(std::time::Duration::from_secs(u64::MAX)).unwrap_err();
+    }
+
+    #[cfg(feature = "chrono-04")]
+    #[test]
+    fn cql_duration_chrono_04_conversions() {
+        let chrono_duration = chrono_04::Duration::try_seconds(-90_061).unwrap();
+        let duration = CqlDuration::try_from(chrono_duration).unwrap();
+        assert_eq!(duration, CqlDuration::new(0, 0, -90_061_000_000_000));
+        assert_eq!(chrono_04::Duration::from(duration), chrono_duration);
+
+        assert_eq!(
+            chrono_04::Duration::from(CqlDuration::new(-1, -1, -1)),
+            -(chrono_04::Duration::try_seconds(2_629_746 + 86_400).unwrap()
+                + chrono_04::Duration::nanoseconds(1))
+        );
+
+        // The extremes must not panic.
+        let _ = chrono_04::Duration::from(CqlDuration::new(i32::MAX, i32::MAX, i64::MAX));
+        let _ = chrono_04::Duration::from(CqlDuration::new(i32::MIN, i32::MIN, i64::MIN));
+
+        CqlDuration::try_from(chrono_04::Duration::MAX).unwrap_err();
+    }
+
+    #[cfg(feature = "time-03")]
+    #[test]
+    fn cql_duration_time_03_conversions() {
+        let time_duration = time_03::Duration::new(-90_061, -5);
+        let duration = CqlDuration::try_from(time_duration).unwrap();
+        assert_eq!(duration, CqlDuration::new(0, 0, -90_061_000_000_005));
+        assert_eq!(time_03::Duration::from(duration), time_duration);
+
+        assert_eq!(
+            time_03::Duration::from(CqlDuration::new(1, 1, 1)),
+            time_03::Duration::new(2_629_746 + 86_400, 1)
+        );
+
+        let _ = time_03::Duration::from(CqlDuration::new(i32::MAX, i32::MAX, i64::MAX));
+        let _ = time_03::Duration::from(CqlDuration::new(i32::MIN, i32::MIN, i64::MIN));
+
+        CqlDuration::try_from(time_03::Duration::MAX).unwrap_err();
+    }
+
+    #[test]
+    fn udt_value_builder() {
+        use crate::frame::response::result::NativeType;
+
+        let definition = Arc::new(UserDefinedType {
+            name: "udt".into(),
+            keyspace: "ks".into(),
+            field_types: vec![
+                ("a".into(), ColumnType::Native(NativeType::Int)),
+                (
+                    "b".into(),
+                    ColumnType::Collection {
+                        frozen: false,
+                        typ: CollectionType::List(Box::new(ColumnType::Native(NativeType::Text))),
+                    },
+                ),
+                ("c".into(), ColumnType::Native(NativeType::Text)),
+            ],
+        });
+
+        let mut builder = UdtValueBuilder::new(definition.clone())
+            .field("b", CqlValue::List(vec![CqlValue::Text("x".to_owned())]))
+            .unwrap()
+            .field("a", CqlValue::Int(1))
+            .unwrap();
+        builder.set_field("a", None).unwrap();
+        assert_eq!(
+            builder.build(),
+            CqlValue::UserDefinedType {
+                keyspace: "ks".to_owned(),
+                name: "udt".to_owned(),
+                fields: vec![
+                    ("a".to_owned(), None),
+                    (
+                        "b".to_owned(),
+                        Some(CqlValue::List(vec![CqlValue::Text("x".to_owned())]))
+                    ),
+                    ("c".to_owned(), None),
+                ],
+            }
+        );
+
+        let builder = UdtValueBuilder::new(definition);
+        assert_matches::assert_matches!(
+            builder.clone().field("d", CqlValue::Int(1)),
+            Err(UdtValueError::NoSuchField { field_name }) if field_name == "d"
+        );
+        assert_matches::assert_matches!(
+            builder.field("b", CqlValue::List(vec![CqlValue::Int(1)])),
+            Err(UdtValueError::FieldTypeMismatch { field_name, .. }) if field_name == "b"
+        );
+    }
 }
@@ -1,10 +1,38 @@
-use darling::{FromAttributes, FromField};
+use darling::{FromAttributes, FromField, FromMeta};
 use proc_macro2::Span;
 use syn::parse_quote;
 
 pub(crate) mod row;
 pub(crate) mod value;
 
+/// The value of the `default` field attribute, which tells how to initialize
+/// the field if it is absent from the deserialized data.
+enum DefaultValue {
+    /// `#[scylla(default)]` - use `Default::default()`.
+    Trait,
+    /// `#[scylla(default = "path")]` - call the given function.
+    Path(syn::Path),
+}
+
+impl FromMeta for DefaultValue {
+    fn from_word() -> darling::Result<Self> {
+        Ok(Self::Trait)
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        Ok(Self::Path(syn::parse_str(value)?))
+    }
+}
+
+/// An expression initializing a field with the given `default` attribute,
+/// or with `Default::default()` if there is none.
+fn default_value_expr(default: Option<&DefaultValue>) -> syn::Expr {
+    match default {
+        Some(DefaultValue::Path(path)) => parse_quote!(#path()),
+        Some(DefaultValue::Trait) | None => parse_quote!(::std::default::Default::default()),
+    }
+}
+
 /// Common attributes that all deserialize impls should understand.
 trait DeserializeCommonStructAttrs {
     /// The path to either `scylla` or `scylla_cql` crate.
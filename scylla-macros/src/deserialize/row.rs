@@ -5,9 +5,11 @@ use proc_macro2::Span;
 use syn::ext::IdentExt;
 use syn::parse_quote;
 
-use crate::Flavor;
+use crate::{Flavor, RenameRule};
 
-use super::{DeserializeCommonFieldAttrs, DeserializeCommonStructAttrs};
+use super::{
+    default_value_expr, DefaultValue, DeserializeCommonFieldAttrs, DeserializeCommonStructAttrs,
+};
 
 #[derive(FromAttributes)]
 #[darling(attributes(scylla))]
@@ -24,6 +26,11 @@ struct StructAttrs {
     // This annotation only works if `enforce_order` is specified.
     #[darling(default)]
     skip_name_checks: bool,
+
+    // If set, then the names of the columns are derived from the Rust field
+    // names according to the given naming convention, e.g. `camelCase`.
+    // The `rename` field attribute takes precedence over it.
+    rename_all: Option<RenameRule>,
 }
 
 impl DeserializeCommonStructAttrs for StructAttrs {
@@ -36,23 +43,41 @@ impl DeserializeCommonStructAttrs for StructAttrs {
 #[darling(attributes(scylla))]
 struct Field {
     // If true, then the field is not parsed at all, but it is initialized
-    // with Default::default() instead. All other attributes are ignored.
+    // with its default value instead. All other attributes are ignored.
     #[darling(default)]
     skip: bool,
 
+    // If set, then - if there is no column for this field - it will be
+    // initialized with Default::default() or with the result of the given
+    // function. Only supported by the `match_by_name` flavor.
+    #[darling(default)]
+    default: Option<DefaultValue>,
+
     // If set, then deserialization will look for the column with given name
     // and deserialize it to this Rust field, instead of just using the Rust
     // field name.
     #[darling(default)]
     rename: Option<String>,
 
+    // If true, then the field is not matched against a single column, but
+    // it is a struct deriving `DeserializeRow` (with the `match_by_name`
+    // flavor) whose fields are matched against the columns of this row.
+    #[darling(default)]
+    flatten: bool,
+
+    // Copied from the struct attributes after parsing, so that the field
+    // is able to compute its name on its own.
+    #[darling(skip)]
+    rename_all: Option<RenameRule>,
+
     ident: Option<syn::Ident>,
     ty: syn::Type,
 }
 
 impl DeserializeCommonFieldAttrs for Field {
     fn needs_default(&self) -> bool {
-        self.skip
+        (self.skip || self.default.is_some())
+            && !matches!(self.default, Some(DefaultValue::Path(_)))
     }
 
     fn deserialize_target(&self) -> &syn::Type {
@@ -63,7 +88,7 @@ impl DeserializeCommonFieldAttrs for Field {
 // derive(DeserializeRow) for the new DeserializeRow trait
 pub(crate) fn deserialize_row_derive(
     tokens_input: proc_macro::TokenStream,
-) -> Result<syn::ItemImpl, syn::Error> {
+) -> Result<proc_macro2::TokenStream, syn::Error> {
     let input = syn::parse(tokens_input)?;
 
     let implemented_trait: syn::Path = parse_quote! { DeserializeRow };
@@ -75,21 +100,108 @@ pub(crate) fn deserialize_row_derive(
         .unraw()
         .to_string();
     let constraining_trait = parse_quote! { DeserializeValue };
-    let s = StructDesc::new(&input, &implemented_trait_name, constraining_trait)?;
+    let mut s = StructDesc::new(&input, &implemented_trait_name, constraining_trait)?;
+    for field in s.fields.iter_mut() {
+        field.rename_all = s.attrs.rename_all;
+    }
 
     validate_attrs(&s.attrs, &s.fields)?;
 
+    // Structs matched by name can be flattened into other structs.
+    let by_name_impl = (s.attrs.flavor == Flavor::MatchByName).then(|| {
+        let by_name_trait: syn::Path = parse_quote! { DeserializeRowByName };
+        s.generate_impl(by_name_trait, ByNameGenerator(&s).generate())
+    });
+
+    if s.fields.iter().any(Field::is_flattened) {
+        // Columns of flattened fields can only be found by name, so
+        // the struct is deserialized through its `DeserializeRowByName` impl.
+        // `Strict<T>` and `Lenient<T>` are not supported.
+        let deserialize_row_impl =
+            s.generate_impl(implemented_trait, ByNameGenerator(&s).generate_delegates());
+        return Ok(quote::quote! {
+            #by_name_impl
+            #deserialize_row_impl
+        });
+    }
+
     let items = [
         s.generate_type_check_method().into(),
         s.generate_deserialize_method().into(),
     ];
+    let deserialize_row_impl = s.generate_impl(implemented_trait, items);
+
+    // Modes selectable at call time, through `Strict<T>` and `Lenient<T>` wrappers.
+    let with_mode_trait: syn::Path = parse_quote! { DeserializeRowWithMode };
+    let with_mode_items = [
+        rename_method(
+            TypeCheckAssumeOrderGenerator(&s).generate(),
+            "type_check_strict",
+        ),
+        rename_method(
+            DeserializeAssumeOrderGenerator(&s).generate(),
+            "deserialize_strict",
+        ),
+        rename_method(
+            TypeCheckUnorderedGenerator(&s, true).generate(),
+            "type_check_lenient",
+        ),
+        rename_method(
+            DeserializeUnorderedGenerator(&s, true).generate(),
+            "deserialize_lenient",
+        ),
+    ];
+    let with_mode_impl = s.generate_impl(with_mode_trait, with_mode_items);
 
-    Ok(s.generate_impl(implemented_trait, items))
+    Ok(quote::quote! {
+        #by_name_impl
+        #deserialize_row_impl
+        #with_mode_impl
+    })
+}
+
+fn rename_method(mut method: syn::ImplItemFn, name: &str) -> syn::ImplItem {
+    method.sig.ident = syn::Ident::new(name, Span::call_site());
+    method.into()
 }
 
 fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::Error> {
     let mut errors = darling::Error::accumulator();
 
+    // <default> and <flatten> rely on matching columns by name
+    if attrs.flavor == Flavor::EnforceOrder {
+        for field in fields.iter().filter(|f| f.default.is_some()) {
+            let err =
+                darling::Error::custom("<default> annotations require <flavor = match_by_name>")
+                    .with_span(&field.ident);
+            errors.push(err);
+        }
+        for field in fields.iter().filter(|f| f.flatten) {
+            let err =
+                darling::Error::custom("<flatten> annotations require <flavor = match_by_name>")
+                    .with_span(&field.ident);
+            errors.push(err);
+        }
+    }
+
+    // The name of a flattened field is ignored, and so is a missing column
+    for field in fields.iter().filter(|f| f.flatten) {
+        if field.rename.is_some() {
+            let err = darling::Error::custom(
+                "<rename> and <flatten> annotations don't make sense together",
+            )
+            .with_span(&field.ident);
+            errors.push(err);
+        }
+        if field.default.is_some() {
+            let err = darling::Error::custom(
+                "<default> and <flatten> annotations don't make sense together",
+            )
+            .with_span(&field.ident);
+            errors.push(err);
+        }
+    }
+
     if attrs.skip_name_checks {
         // Skipping name checks is only available in enforce_order mode
         if attrs.flavor != Flavor::EnforceOrder {
@@ -108,10 +220,18 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
                 errors.push(err);
             }
         }
+
+        // Neither does <rename_all>
+        if attrs.rename_all.is_some() {
+            let err = darling::Error::custom(
+                "<rename_all> attribute doesn't make sense with <skip_name_checks> attribute",
+            );
+            errors.push(err);
+        }
     } else {
         // Detect name collisions caused by `rename`.
         let mut used_names = HashMap::<String, &Field>::new();
-        for field in fields {
+        for field in fields.iter().filter(|f| !f.flatten) {
             let column_name = field.column_name();
             if let Some(other_field) = used_names.get(&column_name) {
                 let other_field_ident = other_field.ident.as_ref().unwrap();
@@ -130,14 +250,30 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
 impl Field {
     // Returns whether this field is mandatory for deserialization.
     fn is_required(&self) -> bool {
-        !self.skip
+        !self.skip && self.default.is_none()
+    }
+
+    // Returns whether the columns of this field are those of a nested struct.
+    fn is_flattened(&self) -> bool {
+        self.flatten && !self.skip
+    }
+
+    // An expression initializing this field if it is skipped or missing.
+    fn default_expr(&self) -> syn::Expr {
+        default_value_expr(self.default.as_ref())
     }
 
     // The name of the column corresponding to this Rust struct field
     fn column_name(&self) -> String {
         match self.rename.as_ref() {
             Some(rename) => rename.to_owned(),
-            None => self.ident.as_ref().unwrap().unraw().to_string(),
+            None => {
+                let name = self.ident.as_ref().unwrap().unraw().to_string();
+                match self.rename_all {
+                    Some(rule) => rule.apply(&name),
+                    None => name,
+                }
+            }
         }
     }
 
@@ -152,14 +288,14 @@ type StructDesc = super::StructDescForDeserialize<StructAttrs, Field>;
 impl StructDesc {
     fn generate_type_check_method(&self) -> syn::ImplItemFn {
         match self.attrs.flavor {
-            Flavor::MatchByName => TypeCheckUnorderedGenerator(self).generate(),
+            Flavor::MatchByName => TypeCheckUnorderedGenerator(self, false).generate(),
             Flavor::EnforceOrder => TypeCheckAssumeOrderGenerator(self).generate(),
         }
     }
 
     fn generate_deserialize_method(&self) -> syn::ImplItemFn {
         match self.attrs.flavor {
-            Flavor::MatchByName => DeserializeUnorderedGenerator(self).generate(),
+            Flavor::MatchByName => DeserializeUnorderedGenerator(self, false).generate(),
             Flavor::EnforceOrder => DeserializeAssumeOrderGenerator(self).generate(),
         }
     }
@@ -204,13 +340,9 @@ impl TypeCheckAssumeOrderGenerator<'_> {
         let macro_internal = self.0.struct_attrs().macro_internal_path();
         let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
 
-        let required_fields_iter = || {
-            self.0
-                .fields()
-                .iter()
-                .enumerate()
-                .filter(|(_, f)| f.is_required())
-        };
+        // Columns are matched by position here, so also the fields with
+        // `default` need their columns (this matters for `Strict<T>`).
+        let required_fields_iter = || self.0.fields().iter().enumerate().filter(|(_, f)| !f.skip);
         let required_fields_count = required_fields_iter().count();
         let required_fields_idents: Vec<_> = (0..required_fields_count)
             .map(|i| quote::format_ident!("f_{}", i))
@@ -270,8 +402,8 @@ struct DeserializeAssumeOrderGenerator<'sd>(&'sd StructDesc);
 impl DeserializeAssumeOrderGenerator<'_> {
     fn generate_finalize_field(&self, field_index: usize, field: &Field) -> syn::Expr {
         if field.skip {
-            // Skipped fields are initialized with Default::default()
-            return parse_quote!(::std::default::Default::default());
+            // Skipped fields are initialized with their default value
+            return field.default_expr();
         }
 
         let macro_internal = self.0.struct_attrs().macro_internal_path();
@@ -332,7 +464,8 @@ impl DeserializeAssumeOrderGenerator<'_> {
     }
 }
 
-struct TypeCheckUnorderedGenerator<'sd>(&'sd StructDesc);
+// The flag tells whether columns which do not correspond to any field are ignored.
+struct TypeCheckUnorderedGenerator<'sd>(&'sd StructDesc, bool);
 
 impl TypeCheckUnorderedGenerator<'_> {
     // An identifier for a bool variable that represents whether given
@@ -425,6 +558,23 @@ impl TypeCheckUnorderedGenerator<'_> {
             .filter(|f| !f.skip)
             .map(|f| f.cql_name_literal());
         let field_count_lit = fields.iter().filter(|f| f.is_required()).count();
+        let unknown_arm: syn::Arm = if self.1 {
+            parse_quote! { _ => {} }
+        } else {
+            parse_quote! {
+                _unknown => {
+                    return ::std::result::Result::Err(
+                        #macro_internal::mk_row_typck_err::<Self>(
+                            column_types_iter(),
+                            #macro_internal::DeserBuiltinRowTypeCheckErrorKind::ColumnWithUnknownName {
+                                column_index,
+                                column_name: <_ as ::std::borrow::ToOwned>::to_owned(spec.name())
+                            }
+                        )
+                    )
+                }
+            }
+        };
 
         parse_quote! {
             fn type_check(
@@ -442,17 +592,7 @@ impl TypeCheckUnorderedGenerator<'_> {
                     // Pattern match on the name and verify that the type is correct.
                     match spec.name() {
                         #(#nonskipped_field_names => #type_check_blocks,)*
-                        _unknown => {
-                            return ::std::result::Result::Err(
-                                #macro_internal::mk_row_typck_err::<Self>(
-                                    column_types_iter(),
-                                    #macro_internal::DeserBuiltinRowTypeCheckErrorKind::ColumnWithUnknownName {
-                                        column_index,
-                                        column_name: <_ as ::std::borrow::ToOwned>::to_owned(spec.name())
-                                    }
-                                )
-                            )
-                        }
+                        #unknown_arm
                     }
                 }
 
@@ -477,7 +617,8 @@ impl TypeCheckUnorderedGenerator<'_> {
     }
 }
 
-struct DeserializeUnorderedGenerator<'sd>(&'sd StructDesc);
+// The flag tells whether columns which do not correspond to any field are ignored.
+struct DeserializeUnorderedGenerator<'sd>(&'sd StructDesc, bool);
 
 impl DeserializeUnorderedGenerator<'_> {
     // An identifier for a variable that is meant to store the parsed variable
@@ -490,13 +631,19 @@ impl DeserializeUnorderedGenerator<'_> {
     // of the target structure
     fn generate_finalize_field(&self, field: &Field) -> syn::Expr {
         if field.skip {
-            // Skipped fields are initialized with Default::default()
+            // Skipped fields are initialized with their default value
+            return field.default_expr();
+        }
+
+        let deserialize_field = Self::deserialize_field_variable(field);
+        if field.default.is_some() {
+            // Generate the default value if the column was missing
+            let default_expr = field.default_expr();
             return parse_quote! {
-                ::std::default::Default::default()
+                #deserialize_field.unwrap_or_else(|| #default_expr)
             };
         }
 
-        let deserialize_field = Self::deserialize_field_variable(field);
         let cql_name_literal = field.cql_name_literal();
         parse_quote! {
             #deserialize_field.unwrap_or_else(|| ::std::panic!(
@@ -570,6 +717,13 @@ impl DeserializeUnorderedGenerator<'_> {
             .map(|f| f.cql_name_literal());
 
         let field_finalizers = fields.iter().map(|f| self.generate_finalize_field(f));
+        let unknown_arm: syn::Arm = if self.1 {
+            parse_quote! { _ => {} }
+        } else {
+            parse_quote! {
+                unknown => ::std::unreachable!("Typecheck should have prevented this scenario! Unknown column name: {}", unknown),
+            }
+        };
 
         // TODO: Allow collecting unrecognized fields into some special field
 
@@ -587,7 +741,7 @@ impl DeserializeUnorderedGenerator<'_> {
                     // Pattern match on the field name and deserialize.
                     match col.spec.name() {
                         #(#nonskipped_field_names => #deserialize_blocks,)*
-                        unknown => ::std::unreachable!("Typecheck should have prevented this scenario! Unknown column name: {}", unknown),
+                        #unknown_arm
                     }
                 }
 
@@ -603,3 +757,321 @@ impl DeserializeUnorderedGenerator<'_> {
         }
     }
 }
+
+// Generates the `DeserializeRowByName` impl, which matches the columns
+// one by one, so that the struct can be flattened into another one.
+//
+// The partial state is a tuple with an element for each non-skipped field:
+// a pair of a "visited" flag (set during type check) and the deserialized value
+// for a regular field, or the partial state of the nested struct for a flattened field.
+struct ByNameGenerator<'sd>(&'sd StructDesc);
+
+impl ByNameGenerator<'_> {
+    // Non-skipped fields, along with the index of their element in the partial state
+    fn partial_fields(&self) -> impl Iterator<Item = (syn::Index, &Field)> {
+        self.0
+            .fields()
+            .iter()
+            .filter(|f| !f.skip)
+            .enumerate()
+            .map(|(idx, f)| (syn::Index::from(idx), f))
+    }
+
+    fn by_name_trait(&self) -> syn::Path {
+        let macro_internal = self.0.struct_attrs().macro_internal_path();
+        let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
+        parse_quote!(#macro_internal::DeserializeRowByName<#frame_lifetime, #metadata_lifetime>)
+    }
+
+    fn value_trait(&self) -> syn::Path {
+        let macro_internal = self.0.struct_attrs().macro_internal_path();
+        let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
+        parse_quote!(#macro_internal::DeserializeValue<#frame_lifetime, #metadata_lifetime>)
+    }
+
+    fn generate_partial(&self) -> [syn::ImplItem; 2] {
+        let by_name_trait = self.by_name_trait();
+        let (partial_types, partial_inits): (Vec<syn::Type>, Vec<syn::Expr>) = self
+            .partial_fields()
+            .map(|(_, f)| {
+                let typ = f.deserialize_target();
+                if f.flatten {
+                    (
+                        parse_quote!(<#typ as #by_name_trait>::Partial),
+                        parse_quote!(<#typ as #by_name_trait>::partial()),
+                    )
+                } else {
+                    (
+                        parse_quote!((::std::primitive::bool, ::std::option::Option<#typ>)),
+                        parse_quote!((false, ::std::option::Option::None)),
+                    )
+                }
+            })
+            .unzip();
+
+        [
+            parse_quote! {
+                type Partial = (#(#partial_types,)*);
+            },
+            parse_quote! {
+                // The tuple is empty if there are no fields to deserialize.
+                #[allow(clippy::unused_unit)]
+                fn partial() -> Self::Partial {
+                    (#(#partial_inits,)*)
+                }
+            },
+        ]
+    }
+
+    fn generate_type_check_column(&self) -> syn::ImplItemFn {
+        let macro_internal = self.0.struct_attrs().macro_internal_path();
+        let by_name_trait = self.by_name_trait();
+        let value_trait = self.value_trait();
+
+        let (own_names, own_type_checks): (Vec<_>, Vec<syn::Block>) = self
+            .partial_fields()
+            .filter(|(_, f)| !f.flatten)
+            .map(|(idx, f)| {
+                let typ = f.deserialize_target();
+                let cql_name_literal = f.cql_name_literal();
+                let type_check = parse_quote! {
+                    {
+                        if partial.#idx.0 {
+                            return ::std::result::Result::Err(
+                                #macro_internal::mk_row_typck_err::<Self>(
+                                    column_types_iter(),
+                                    #macro_internal::DeserBuiltinRowTypeCheckErrorKind::DuplicatedColumn {
+                                        column_index,
+                                        column_name: #cql_name_literal,
+                                    }
+                                )
+                            );
+                        }
+                        <#typ as #value_trait>::type_check(spec.typ())
+                            .map_err(|err| {
+                                #macro_internal::mk_row_typck_err::<Self>(
+                                    column_types_iter(),
+                                    #macro_internal::DeserBuiltinRowTypeCheckErrorKind::ColumnTypeCheckFailed {
+                                        column_index,
+                                        column_name: <_ as ::std::borrow::ToOwned>::to_owned(#cql_name_literal),
+                                        err,
+                                    }
+                                )
+                            })?;
+                        partial.#idx.0 = true;
+                        ::std::result::Result::Ok(true)
+                    }
+                };
+                (cql_name_literal, type_check)
+            })
+            .unzip();
+
+        let (flattened_idxs, flattened_types): (Vec<_>, Vec<_>) = self
+            .partial_fields()
+            .filter(|(_, f)| f.flatten)
+            .map(|(idx, f)| (idx, f.deserialize_target()))
+            .unzip();
+
+        parse_quote! {
+            #[allow(unused_variables)]
+            fn type_check_column(
+                partial: &mut Self::Partial,
+                column_index: ::std::primitive::usize,
+                specs: &[#macro_internal::ColumnSpec],
+            ) -> ::std::result::Result<::std::primitive::bool, #macro_internal::TypeCheckError> {
+                let spec = &specs[column_index];
+                let column_types_iter = || ::std::iter::Iterator::map(specs.iter(), |spec| ::std::clone::Clone::clone(spec.typ()).into_owned());
+
+                match spec.name() {
+                    #(#own_names => #own_type_checks,)*
+                    _ => {
+                        // The column may belong to one of the flattened fields.
+                        #(
+                            if <#flattened_types as #by_name_trait>::type_check_column(&mut partial.#flattened_idxs, column_index, specs)? {
+                                return ::std::result::Result::Ok(true);
+                            }
+                        )*
+                        ::std::result::Result::Ok(false)
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_missing_columns(&self) -> syn::ImplItemFn {
+        let by_name_trait = self.by_name_trait();
+        let append_missing = self
+            .partial_fields()
+            .flat_map(|(idx, f)| -> Option<syn::Stmt> {
+                if f.flatten {
+                    let typ = f.deserialize_target();
+                    Some(parse_quote! {
+                        <#typ as #by_name_trait>::missing_columns(&partial.#idx, missing);
+                    })
+                } else {
+                    let cql_name_literal = f.cql_name_literal();
+                    f.is_required().then(|| {
+                        parse_quote! {
+                            if !partial.#idx.0 {
+                                missing.push(#cql_name_literal);
+                            }
+                        }
+                    })
+                }
+            });
+
+        parse_quote! {
+            #[allow(unused_variables)]
+            fn missing_columns(
+                partial: &Self::Partial,
+                missing: &mut ::std::vec::Vec<&'static ::std::primitive::str>,
+            ) {
+                #(#append_missing)*
+            }
+        }
+    }
+
+    fn generate_deserialize_column(&self) -> syn::ImplItemFn {
+        let macro_internal = self.0.struct_attrs().macro_internal_path();
+        let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
+        let by_name_trait = self.by_name_trait();
+        let value_trait = self.value_trait();
+
+        let (own_names, own_deserializations): (Vec<_>, Vec<syn::Block>) = self
+            .partial_fields()
+            .filter(|(_, f)| !f.flatten)
+            .map(|(idx, f)| {
+                let typ = f.deserialize_target();
+                let cql_name_literal = f.cql_name_literal();
+                let deserialization = parse_quote! {
+                    {
+                        assert!(
+                            partial.#idx.1.is_none(),
+                            "duplicated column {} - type check should have prevented this!",
+                            #cql_name_literal
+                        );
+                        partial.#idx.1 = ::std::option::Option::Some(
+                            <#typ as #value_trait>::deserialize(column.spec.typ(), column.slice)
+                                .map_err(|err| {
+                                    #macro_internal::mk_row_deser_err::<Self>(
+                                        #macro_internal::BuiltinRowDeserializationErrorKind::ColumnDeserializationFailed {
+                                            column_index: column.index,
+                                            column_name: <_ as ::std::borrow::ToOwned>::to_owned(column.spec.name()),
+                                            err,
+                                        }
+                                    )
+                                })?
+                        );
+                        ::std::result::Result::Ok(true)
+                    }
+                };
+                (cql_name_literal, deserialization)
+            })
+            .unzip();
+
+        let (flattened_idxs, flattened_types): (Vec<_>, Vec<_>) = self
+            .partial_fields()
+            .filter(|(_, f)| f.flatten)
+            .map(|(idx, f)| (idx, f.deserialize_target()))
+            .unzip();
+
+        parse_quote! {
+            #[allow(unused_variables)]
+            fn deserialize_column(
+                partial: &mut Self::Partial,
+                column: &#macro_internal::RawColumn<#frame_lifetime, #metadata_lifetime>,
+            ) -> ::std::result::Result<::std::primitive::bool, #macro_internal::DeserializationError> {
+                match column.spec.name() {
+                    #(#own_names => #own_deserializations,)*
+                    _ => {
+                        // The column may belong to one of the flattened fields.
+                        #(
+                            if <#flattened_types as #by_name_trait>::deserialize_column(&mut partial.#flattened_idxs, column)? {
+                                return ::std::result::Result::Ok(true);
+                            }
+                        )*
+                        ::std::result::Result::Ok(false)
+                    }
+                }
+            }
+        }
+    }
+
+    fn generate_finish(&self) -> syn::ImplItemFn {
+        let by_name_trait = self.by_name_trait();
+
+        let mut partial_idxs = self.partial_fields().map(|(idx, _)| idx);
+        let (field_idents, field_finalizers): (Vec<_>, Vec<syn::Expr>) = self
+            .0
+            .fields()
+            .iter()
+            .map(|f| {
+                let ident = f.ident.as_ref().unwrap();
+                if f.skip {
+                    // Skipped fields are initialized with their default value
+                    return (ident, f.default_expr());
+                }
+
+                let idx = partial_idxs.next().unwrap();
+                let finalizer = if f.flatten {
+                    let typ = f.deserialize_target();
+                    parse_quote!(<#typ as #by_name_trait>::finish(partial.#idx))
+                } else if f.default.is_some() {
+                    // Generate the default value if the column was missing
+                    let default_expr = f.default_expr();
+                    parse_quote!(partial.#idx.1.unwrap_or_else(|| #default_expr))
+                } else {
+                    let cql_name_literal = f.cql_name_literal();
+                    parse_quote! {
+                        partial.#idx.1.unwrap_or_else(|| ::std::panic!(
+                            "column {} missing in DB row - type check should have prevented this!",
+                            #cql_name_literal
+                        ))
+                    }
+                };
+                (ident, finalizer)
+            })
+            .unzip();
+
+        parse_quote! {
+            #[allow(unused_variables)]
+            fn finish(partial: Self::Partial) -> Self {
+                Self {
+                    #(#field_idents: #field_finalizers,)*
+                }
+            }
+        }
+    }
+
+    fn generate(&self) -> Vec<syn::ImplItem> {
+        let mut items = Vec::from(self.generate_partial());
+        items.push(self.generate_type_check_column().into());
+        items.push(self.generate_missing_columns().into());
+        items.push(self.generate_deserialize_column().into());
+        items.push(self.generate_finish().into());
+        items
+    }
+
+    // Generates `DeserializeRow` methods which delegate to the `DeserializeRowByName` impl
+    fn generate_delegates(&self) -> [syn::ImplItem; 2] {
+        let macro_internal = self.0.struct_attrs().macro_internal_path();
+        let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
+
+        [
+            parse_quote! {
+                fn type_check(
+                    specs: &[#macro_internal::ColumnSpec],
+                ) -> ::std::result::Result<(), #macro_internal::TypeCheckError> {
+                    #macro_internal::de::row::type_check_by_name::<Self>(specs)
+                }
+            },
+            parse_quote! {
+                fn deserialize(
+                    row: #macro_internal::ColumnIterator<#frame_lifetime, #metadata_lifetime>,
+                ) -> ::std::result::Result<Self, #macro_internal::DeserializationError> {
+                    #macro_internal::de::row::deserialize_by_name::<Self>(row)
+                }
+            },
+        ]
+    }
+}
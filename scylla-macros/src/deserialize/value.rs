@@ -5,9 +5,11 @@ use proc_macro::TokenStream;
 use proc_macro2::Span;
 use syn::{ext::IdentExt, parse_quote};
 
-use crate::Flavor;
+use crate::{Flavor, RenameRule};
 
-use super::{DeserializeCommonFieldAttrs, DeserializeCommonStructAttrs};
+use super::{
+    default_value_expr, DefaultValue, DeserializeCommonFieldAttrs, DeserializeCommonStructAttrs,
+};
 
 #[derive(FromAttributes)]
 #[darling(attributes(scylla))]
@@ -25,6 +27,11 @@ struct StructAttrs {
     #[darling(default)]
     skip_name_checks: bool,
 
+    // If set, then the names of the UDT fields are derived from the Rust field
+    // names according to the given naming convention, e.g. `camelCase`.
+    // The `rename` field attribute takes precedence over it.
+    rename_all: Option<RenameRule>,
+
     // If true, then the type checking code will require that the UDT does not
     // contain excess fields at its suffix. Otherwise, if UDT has some fields
     // at its suffix that do not correspond to Rust struct's fields,
@@ -43,19 +50,25 @@ impl DeserializeCommonStructAttrs for StructAttrs {
 #[darling(attributes(scylla))]
 struct Field {
     // If true, then the field is not parsed at all, but it is initialized
-    // with Default::default() instead. All other attributes are ignored.
+    // with its default value instead. All other attributes are ignored.
     #[darling(default)]
     skip: bool,
 
     // If true, then - if this field is missing from the UDT fields metadata
-    // - it will be initialized to Default::default().
+    // - it will be initialized to its default value.
     #[darling(default)]
     #[darling(rename = "allow_missing")]
     default_when_missing: bool,
 
+    // If set, then - if this field is missing from the UDT fields metadata
+    // - it will be initialized with Default::default() or with the result
+    // of the given function. Implies `allow_missing`.
+    #[darling(default)]
+    default: Option<DefaultValue>,
+
     // If true, then - if this field is present among UDT fields metadata
     // but at the same time missing from serialized data or set to null
-    // - it will be initialized to Default::default().
+    // - it will be initialized to its default value.
     #[darling(default)]
     default_when_null: bool,
 
@@ -64,13 +77,18 @@ struct Field {
     #[darling(default)]
     rename: Option<String>,
 
+    // Copied from the struct attributes after parsing, so that the field
+    // is able to compute its name on its own.
+    #[darling(skip)]
+    rename_all: Option<RenameRule>,
+
     ident: Option<syn::Ident>,
     ty: syn::Type,
 }
 
 impl DeserializeCommonFieldAttrs for Field {
     fn needs_default(&self) -> bool {
-        self.skip || self.default_when_missing
+        (self.skip || self.allows_missing()) && !matches!(self.default, Some(DefaultValue::Path(_)))
     }
 
     fn deserialize_target(&self) -> &syn::Type {
@@ -93,7 +111,10 @@ pub(crate) fn deserialize_value_derive(
         .unraw()
         .to_string();
     let constraining_trait = implemented_trait.clone();
-    let s = StructDesc::new(&input, &implemented_trait_name, constraining_trait)?;
+    let mut s = StructDesc::new(&input, &implemented_trait_name, constraining_trait)?;
+    for field in s.fields.iter_mut() {
+        field.rename_all = s.attrs.rename_all;
+    }
 
     validate_attrs(&s.attrs, s.fields())?;
 
@@ -127,7 +148,7 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
             .skip_while(|field| !field.is_required())
             // skip_while finished either because the iterator is empty or it found a field without both <allow_missing> and <skip>.
             // In either case, there aren't allowed to be any more fields with `allow_missing`.
-            .find(|field| field.default_when_missing);
+            .find(|field| field.allows_missing());
         if let Some(invalid) = invalid_default_when_missing_field {
             let error =
                 darling::Error::custom(
@@ -147,6 +168,14 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
                 errors.push(err);
             }
         }
+
+        // Neither does <rename_all>
+        if attrs.rename_all.is_some() {
+            let err = darling::Error::custom(
+                "<rename_all> attribute doesn't make sense with <skip_name_checks> attribute",
+            );
+            errors.push(err);
+        }
     } else {
         // Detect name collisions caused by <rename>.
         let mut used_names = HashMap::<String, &Field>::new();
@@ -169,14 +198,30 @@ fn validate_attrs(attrs: &StructAttrs, fields: &[Field]) -> Result<(), darling::
 impl Field {
     // Returns whether this field is mandatory for deserialization.
     fn is_required(&self) -> bool {
-        !self.skip && !self.default_when_missing
+        !self.skip && !self.allows_missing()
+    }
+
+    // Returns whether this field may be absent from the UDT fields metadata.
+    fn allows_missing(&self) -> bool {
+        self.default_when_missing || self.default.is_some()
+    }
+
+    // An expression initializing this field if it is skipped, missing or null.
+    fn default_expr(&self) -> syn::Expr {
+        default_value_expr(self.default.as_ref())
     }
 
     // The name of UDT field corresponding to this Rust struct field
     fn udt_field_name(&self) -> String {
         match self.rename.as_ref() {
             Some(rename) => rename.to_owned(),
-            None => self.ident.as_ref().unwrap().unraw().to_string(),
+            None => {
+                let name = self.ident.as_ref().unwrap().unraw().to_string();
+                match self.rename_all {
+                    Some(rule) => rule.apply(&name),
+                    None => name,
+                }
+            }
         }
     }
 
@@ -229,7 +274,7 @@ impl TypeCheckAssumeOrderGenerator<'_> {
         let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
         let rust_field_name = field.cql_name_literal();
         let rust_field_typ = field.deserialize_target();
-        let default_when_missing = field.default_when_missing;
+        let default_when_missing = field.allows_missing();
         let skip_name_checks = self.0.attrs.skip_name_checks;
 
         // Action performed in case of field name mismatch.
@@ -401,18 +446,17 @@ struct DeserializeAssumeOrderGenerator<'sd>(&'sd StructDesc);
 impl DeserializeAssumeOrderGenerator<'_> {
     fn generate_finalize_field(&self, field: &Field) -> syn::Expr {
         if field.skip {
-            // Skipped fields are initialized with Default::default()
-            return parse_quote! {
-                ::std::default::Default::default()
-            };
+            // Skipped fields are initialized with their default value
+            return field.default_expr();
         }
 
         let macro_internal = self.0.struct_attrs().macro_internal_path();
         let cql_name_literal = field.cql_name_literal();
         let deserializer = field.deserialize_target();
         let (frame_lifetime, metadata_lifetime) = self.0.constraint_lifetimes();
-        let default_when_missing = field.default_when_missing;
+        let default_when_missing = field.allows_missing();
         let default_when_null = field.default_when_null;
+        let default_expr = field.default_expr();
         let skip_name_checks = self.0.attrs.skip_name_checks;
 
         let deserialize: syn::Expr = parse_quote! {
@@ -429,7 +473,7 @@ impl DeserializeAssumeOrderGenerator<'_> {
         let maybe_default_deserialize: syn::Expr = if default_when_null {
             parse_quote! {
                 if value.is_none() {
-                    ::std::default::Default::default()
+                    #default_expr
                 } else {
                     #deserialize
                 }
@@ -449,7 +493,7 @@ impl DeserializeAssumeOrderGenerator<'_> {
                     // For that, store the read UDT field to be fit against the next Rust struct field.
                     saved_cql_field = ::std::option::Option::Some(next_cql_field);
 
-                    ::std::default::Default::default()
+                    #default_expr
                 }
             }
         } else {
@@ -480,7 +524,7 @@ impl DeserializeAssumeOrderGenerator<'_> {
 
         let no_more_fields: syn::Expr = if default_when_missing {
             parse_quote! {
-                ::std::default::Default::default()
+                #default_expr
             }
         } else {
             parse_quote! {
@@ -741,15 +785,16 @@ impl DeserializeUnorderedGenerator<'_> {
     /// of the target structure.
     fn generate_finalize_field(&self, field: &Field) -> syn::Expr {
         if field.skip {
-            // Skipped fields are initialized with Default::default()
-            return parse_quote!(::std::default::Default::default());
+            // Skipped fields are initialized with their default value
+            return field.default_expr();
         }
 
         let deserialize_field = Self::deserialize_field_variable(field);
-        if field.default_when_missing {
-            // Generate Default::default if the field was missing
+        if field.allows_missing() {
+            // Generate the default value if the field was missing
+            let default_expr = field.default_expr();
             parse_quote! {
-                #deserialize_field.unwrap_or_default()
+                #deserialize_field.unwrap_or_else(|| #default_expr)
             }
         } else {
             let cql_name_literal = field.cql_name_literal();
@@ -784,11 +829,12 @@ impl DeserializeUnorderedGenerator<'_> {
             };
 
             let deserialize_action: syn::Expr = if field.default_when_null {
+                let default_expr = field.default_expr();
                 parse_quote! {
                     if value.is_some() {
                         #do_deserialize
                     } else {
-                        ::std::default::Default::default()
+                        #default_expr
                     }
                 }
             } else {
@@ -43,6 +43,72 @@ impl FromMeta for Flavor {
     }
 }
 
+// Naming convention applied to all fields of a struct by the `rename_all`
+// attribute of serialization/deserialization macros. Rust field names are
+// assumed to be in snake_case, like the rules of `serde(rename_all)`.
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum RenameRule {
+    Lower,
+    Upper,
+    Pascal,
+    Camel,
+    Snake,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+impl RenameRule {
+    // Converts a snake_case Rust field name according to the rule.
+    fn apply(self, field_name: &str) -> String {
+        match self {
+            Self::Lower | Self::Snake => field_name.to_owned(),
+            Self::Upper | Self::ScreamingSnake => field_name.to_ascii_uppercase(),
+            Self::Pascal => {
+                let mut res = String::with_capacity(field_name.len());
+                let mut capitalize = true;
+                for c in field_name.chars() {
+                    if c == '_' {
+                        capitalize = true;
+                    } else if capitalize {
+                        res.push(c.to_ascii_uppercase());
+                        capitalize = false;
+                    } else {
+                        res.push(c);
+                    }
+                }
+                res
+            }
+            Self::Camel => {
+                let pascal = Self::Pascal.apply(field_name);
+                let mut chars = pascal.chars();
+                match chars.next() {
+                    Some(first) => first.to_ascii_lowercase().to_string() + chars.as_str(),
+                    None => pascal,
+                }
+            }
+            Self::Kebab => field_name.replace('_', "-"),
+            Self::ScreamingKebab => Self::ScreamingSnake.apply(field_name).replace('_', "-"),
+        }
+    }
+}
+
+impl FromMeta for RenameRule {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "lowercase" => Ok(Self::Lower),
+            "UPPERCASE" => Ok(Self::Upper),
+            "PascalCase" => Ok(Self::Pascal),
+            "camelCase" => Ok(Self::Camel),
+            "snake_case" => Ok(Self::Snake),
+            "SCREAMING_SNAKE_CASE" => Ok(Self::ScreamingSnake),
+            "kebab-case" => Ok(Self::Kebab),
+            "SCREAMING-KEBAB-CASE" => Ok(Self::ScreamingKebab),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
 mod serialize;
 
 /// Derive macro for the [`SerializeValue`](./serialize/value/trait.SerializeValue.html) trait
@@ -138,6 +204,17 @@ mod serialize;
 /// struct field names and UDT field names, i.e. it's OK if i-th field has a
 /// different name in Rust and in the UDT. Fields are still being type-checked.
 ///
+/// `#[scylla(rename_all = "naming_convention")]`
+///
+/// Derives the names of the UDT fields from the Rust field names using the given
+/// naming convention, so that the `rename` attribute doesn't have to be repeated
+/// on every field. The Rust field names are expected to be in snake_case.
+/// Supported conventions are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`
+/// and `"SCREAMING-KEBAB-CASE"`. The `rename` field attribute takes precedence.
+/// Keep in mind that names which are not lowercase have to be double-quoted
+/// in CQL statements, e.g. `CREATE TYPE ks.my_udt ("firstName" text)`.
+///
 /// `#[scylla(forbid_excess_udt_fields)]`
 ///
 /// Forces Rust struct to have all the fields present in UDT, otherwise
@@ -247,6 +324,17 @@ pub fn serialize_value_derive(tokens_input: TokenStream) -> TokenStream {
 /// OK if i-th Rust struct field has a different name than the column / bind
 /// marker. The values are still being type-checked.
 ///
+/// `#[scylla(rename_all = "naming_convention")]`
+///
+/// Derives the names of the columns / bind markers from the Rust field names using the given
+/// naming convention, so that the `rename` attribute doesn't have to be repeated
+/// on every field. The Rust field names are expected to be in snake_case.
+/// Supported conventions are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`
+/// and `"SCREAMING-KEBAB-CASE"`. The `rename` field attribute takes precedence.
+/// Keep in mind that names which are not lowercase have to be double-quoted
+/// in CQL statements, e.g. `CREATE TYPE ks.my_udt ("firstName" text)`.
+///
 /// # Field attributes
 ///
 /// `#[scylla(rename = "column_or_bind_marker_name")]`
@@ -266,6 +354,12 @@ pub fn serialize_value_derive(tokens_input: TokenStream) -> TokenStream {
 ///
 /// Note that the name of this field is ignored and hence the `rename` attribute does not make sense
 /// here and will cause a compilation error.
+///
+/// `#[scylla(none_as_unset)]`
+///
+/// The field must be an `Option`. If it is `None`, the bind marker is left unset
+/// instead of being set to NULL, so that the column is not modified and no tombstone
+/// is created. This is equivalent to declaring the field as `MaybeUnset`.
 #[proc_macro_derive(SerializeRow, attributes(scylla))]
 pub fn serialize_row_derive(tokens_input: TokenStream) -> TokenStream {
     match serialize::row::derive_serialize_row(tokens_input) {
@@ -378,6 +472,26 @@ mod deserialize;
 /// column into the first field, second column into the second field and so on.
 /// It will still still verify that the column types and field types match.
 ///
+/// `#[scylla(rename_all = "naming_convention")]`
+///
+/// Derives the names of the columns from the Rust field names using the given
+/// naming convention, so that the `rename` attribute doesn't have to be repeated
+/// on every field. The Rust field names are expected to be in snake_case.
+/// Supported conventions are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`
+/// and `"SCREAMING-KEBAB-CASE"`. The `rename` field attribute takes precedence.
+/// Keep in mind that names which are not lowercase have to be double-quoted
+/// in CQL statements, e.g. `CREATE TYPE ks.my_udt ("firstName" text)`.
+///
+/// ## Choosing the matching at call time
+///
+/// Besides [`DeserializeRow`](./deserialize/row/trait.DeserializeRow.html), the macro
+/// implements [`DeserializeRowWithMode`](./deserialize/row/trait.DeserializeRowWithMode.html),
+/// so that the matching can be chosen at call time, regardless of the flavor, by wrapping
+/// the struct in [`Strict`](./deserialize/row/struct.Strict.html) (columns must match the fields
+/// in order, by name and by count) or [`Lenient`](./deserialize/row/struct.Lenient.html)
+/// (columns are matched by name, and columns not corresponding to any field are ignored).
+///
 /// ## Field attributes
 ///
 /// `#[scylla(skip)]`
@@ -390,6 +504,30 @@ mod deserialize;
 /// By default, the generated implementation will try to match the Rust field
 /// to a column with the same name. This attribute allows to match to a column
 /// with provided name.
+///
+/// `#[scylla(default)]`, `#[scylla(default = "path::to::function")]`
+///
+/// _Only supported by the `match_by_name` flavor._
+///
+/// If there is no column for this field, it will be initialized with
+/// `Default::default()`, or with the result of calling the given function
+/// (which takes no arguments), respectively. Fields marked with `skip`
+/// are initialized in the same way.
+///
+/// `#[scylla(flatten)]`
+///
+/// _Only supported by the `match_by_name` flavor._
+///
+/// The field must be a struct which also derives `DeserializeRow` with the
+/// `match_by_name` flavor. Instead of being matched to a single column,
+/// its fields are matched to the columns of the same row, as if they were
+/// fields of the parent struct. This allows to share a group of columns
+/// (e.g. audit columns) between many row structs.
+///
+/// Note that the name of this field is ignored and hence the `rename` attribute
+/// does not make sense here and will cause a compilation error. The same goes
+/// for the `default` attribute. Structs with flattened fields do not implement
+/// [`DeserializeRowWithMode`](./deserialize/row/trait.DeserializeRowWithMode.html).
 #[proc_macro_derive(DeserializeRow, attributes(scylla))]
 pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
     match deserialize::row::deserialize_row_derive(tokens_input) {
@@ -496,6 +634,17 @@ pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
 /// If more strictness is desired, this flag makes sure that no excess fields
 /// are present and forces error in case there are some.
 ///
+/// `#[scylla(rename_all = "naming_convention")]`
+///
+/// Derives the names of the UDT fields from the Rust field names using the given
+/// naming convention, so that the `rename` attribute doesn't have to be repeated
+/// on every field. The Rust field names are expected to be in snake_case.
+/// Supported conventions are `"lowercase"`, `"UPPERCASE"`, `"PascalCase"`,
+/// `"camelCase"`, `"snake_case"`, `"SCREAMING_SNAKE_CASE"`, `"kebab-case"`
+/// and `"SCREAMING-KEBAB-CASE"`. The `rename` field attribute takes precedence.
+/// Keep in mind that names which are not lowercase have to be double-quoted
+/// in CQL statements, e.g. `CREATE TYPE ks.my_udt ("firstName" text)`.
+///
 /// ## Field attributes
 ///
 /// `#[scylla(skip)]`
@@ -518,6 +667,14 @@ pub fn deserialize_row_derive(tokens_input: TokenStream) -> TokenStream {
 /// By default, the generated implementation will try to match the Rust field
 /// to a UDT field with the same name. This attribute instead allows to match
 /// to a UDT field with provided name.
+///
+/// `#[scylla(default)]`, `#[scylla(default = "path::to::function")]`
+///
+/// Like `allow_missing`, but lets choose how the field is initialized if the UDT
+/// definition does not contain it: with `Default::default()`, or with the result
+/// of calling the given function (which takes no arguments), respectively.
+/// This is useful after adding fields to a UDT, when the old schema may still
+/// be in use. The same value is used for `skip` and `default_when_null`.
 #[proc_macro_derive(DeserializeValue, attributes(scylla))]
 pub fn deserialize_value_derive(tokens_input: TokenStream) -> TokenStream {
     match deserialize::value::deserialize_value_derive(tokens_input) {
@@ -525,3 +682,85 @@ pub fn deserialize_value_derive(tokens_input: TokenStream) -> TokenStream {
         Err(err) => err.into_compile_error().into(),
     }
 }
+
+mod table;
+
+/// Derive macro for the [`Table`](./table/trait.Table.html) trait
+/// which maps Rust structure to a CQL table, one struct per row.
+///
+/// At the moment, only structs with named fields are supported.
+///
+/// Every field is mapped to a column with the same name, and the CQL type of the column
+/// is determined by the [`CqlTypeName`](./table/trait.CqlTypeName.html) trait.
+/// The generated implementation only describes the table; in order to insert
+/// and select rows, the struct should also derive
+/// [`SerializeRow`](./serialize/row/trait.SerializeRow.html) and
+/// [`DeserializeRow`](./deserialize/row/trait.DeserializeRow.html).
+/// Their `#[scylla(rename_all = "...")]` struct attribute and `#[scylla(rename = "...")]`
+/// and `#[scylla(skip)]` field attributes are respected by this macro as well,
+/// so that all three derives agree on the columns.
+///
+/// # Example
+///
+/// The following struct:
+///
+/// ```rust
+/// # use scylla::{DeserializeRow, SerializeRow, Table};
+/// #[derive(Table, SerializeRow, DeserializeRow)]
+/// #[table(keyspace = "ks", name = "my_t")]
+/// struct MyRow {
+///     #[table(partition_key)]
+///     a: i32,
+///     #[table(clustering_key)]
+///     b: String,
+///     c: Vec<u8>,
+/// }
+/// ```
+///
+/// ...is mapped to a table defined like this:
+///
+/// ```text
+/// CREATE TABLE ks.my_t (a int, b text, c blob, PRIMARY KEY ((a), b));
+/// ```
+///
+/// # Struct attributes
+///
+/// `#[table(name = "table_name")]`
+///
+/// Name of the table. By default, the struct name converted to snake_case is used.
+///
+/// `#[table(keyspace = "keyspace_name")]`
+///
+/// If set, the table name is qualified with this keyspace name in generated statements.
+/// Otherwise, the keyspace of the session is used.
+///
+/// `#[table(crate = crate_name)]`
+///
+/// By default, the code generated by the derive macro will refer to the items
+/// defined by the driver via the `::scylla` path. This attribute allows to provide
+/// an alternative path to the `scylla` crate, e.g. if it is imported under a different
+/// name. Unlike other derive macros, it cannot be used with the `scylla-cql` crate.
+///
+/// # Field attributes
+///
+/// `#[table(partition_key)]`
+///
+/// The column is a part of the partition key. Columns of a composite partition key
+/// follow the order of struct fields. At least one field must be marked with this attribute.
+///
+/// `#[table(clustering_key)]`
+///
+/// The column is a part of the clustering key. Columns of the clustering key
+/// follow the order of struct fields.
+///
+/// `#[table(cql_type = "cql_type")]`
+///
+/// Sets the CQL type of the column, e.g. for user defined types (`"frozen<my_udt>"`),
+/// instead of deriving it from the Rust type of the field.
+#[proc_macro_derive(Table, attributes(table, scylla))]
+pub fn table_derive(tokens_input: TokenStream) -> TokenStream {
+    match table::derive_table(tokens_input) {
+        Ok(tokens) => tokens.into_token_stream().into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
@@ -3,9 +3,10 @@ use std::collections::HashMap;
 use darling::FromAttributes;
 use proc_macro::TokenStream;
 use quote::format_ident;
+use syn::ext::IdentExt;
 use syn::parse_quote;
 
-use crate::Flavor;
+use crate::{Flavor, RenameRule};
 
 #[derive(FromAttributes)]
 #[darling(attributes(scylla))]
@@ -22,6 +23,11 @@ struct Attributes {
     // This annotation only works if `enforce_order` flavor is specified.
     #[darling(default)]
     skip_name_checks: bool,
+
+    // If set, then the names of the column / bind marker(s) are derived from the Rust field
+    // names according to the given naming convention, e.g. `camelCase`.
+    // The `rename` field attribute takes precedence over it.
+    rename_all: Option<RenameRule>,
 }
 
 impl Attributes {
@@ -37,13 +43,28 @@ struct Field {
     ident: syn::Ident,
     typ: syn::Type,
     attrs: FieldAttributes,
+    rename_all: Option<RenameRule>,
 }
 
 impl Field {
     fn column_name(&self) -> String {
         match &self.attrs.rename {
             Some(name) => name.clone(),
-            None => self.ident.to_string(),
+            None => match self.rename_all {
+                Some(rule) => rule.apply(&self.ident.unraw().to_string()),
+                None => self.ident.to_string(),
+            },
+        }
+    }
+
+    // The expression which yields a reference to the value to be serialized
+    // for this (non-flattened) field.
+    fn value_ref(&self, crate_path: &syn::Path) -> syn::Expr {
+        let ident = &self.ident;
+        if self.attrs.none_as_unset {
+            parse_quote!(&#crate_path::ser::row::none_as_unset(&self.#ident))
+        } else {
+            parse_quote!(&self.#ident)
         }
     }
 }
@@ -65,6 +86,16 @@ struct FieldAttributes {
     // All other attributes are ignored.
     #[darling(default)]
     skip: bool,
+
+    // If true, then the field (which must be an `Option`) is serialized
+    // as an unset value instead of NULL when it is `None`.
+    #[darling(default)]
+    none_as_unset: bool,
+
+    // Used for deserialization only. Ignored in serialization.
+    #[darling(default)]
+    #[darling(rename = "default")]
+    _default: Option<darling::util::Ignored>,
 }
 
 struct Context {
@@ -92,6 +123,7 @@ pub(crate) fn derive_serialize_row(tokens_input: TokenStream) -> Result<syn::Ite
                 ident: f.ident.clone().unwrap(),
                 typ: f.ty.clone(),
                 attrs,
+                rename_all: attributes.rename_all,
             })
         })
         // Filter the fields now instead of at the places that use them later
@@ -148,6 +180,15 @@ impl Context {
                     errors.push(err);
                 }
             }
+
+            // Neither does `rename_all`
+            if self.attributes.rename_all.is_some() {
+                let err = darling::Error::custom(
+                    "the `rename_all` attribute doesn't make sense with `skip_name_checks` attribute",
+                )
+                .with_span(struct_ident);
+                errors.push(err);
+            }
         }
 
         // Check that no renames are attempted on flattened fields
@@ -163,6 +204,19 @@ impl Context {
             });
         errors.extend(rename_flatten_errors);
 
+        // Check that flattened fields are not treated as optional values
+        let none_as_unset_flatten_errors = self
+            .fields
+            .iter()
+            .filter(|f| f.attrs.flatten && f.attrs.none_as_unset)
+            .map(|f| {
+                darling::Error::custom(
+                    "`none_as_unset` and `flatten` annotations do not make sense together",
+                )
+                .with_span(&f.ident)
+            });
+        errors.extend(none_as_unset_flatten_errors);
+
         // Check for name collisions
         let mut used_names = HashMap::<String, &Field>::new();
         for field in self.fields.iter() {
@@ -264,6 +318,10 @@ impl Generator for ColumnSortingGenerator<'_> {
             .map(|ident| format_ident!("__visited_flag_{}", ident))
             .collect();
         let nonflattened_types: Vec<_> = nonflattened.iter().map(|f| &f.typ).collect();
+        let nonflattened_values: Vec<_> = nonflattened
+            .iter()
+            .map(|f| f.value_ref(&crate_path))
+            .collect();
 
         let partial_struct: syn::ItemStruct = parse_quote! {
             pub struct #partial_struct_name #partial_generics {
@@ -288,7 +346,7 @@ impl Generator for ColumnSortingGenerator<'_> {
                     // first check if the spec name matches a non-flattened column
                     #(#nonflattened_columns => {
                         #crate_path::ser::row::serialize_column::<#struct_name #ty_generics>(
-                            &self.#nonflattened_fields, spec, writer,
+                            #nonflattened_values, spec, writer,
                         )?;
                         if !self.#nonflattened_visited_flag_names {
                             self.#nonflattened_visited_flag_names = true;
@@ -435,8 +493,9 @@ impl Generator for ColumnOrderedGenerator<'_> {
             } else {
                 let column = f.column_name();
                 let enforce_name = !self.ctx.attributes.skip_name_checks;
+                let value = f.value_ref(&crate_path);
                 syn::parse_quote! {
-                    #crate_path::ser::row::NextColumnSerializer::serialize::<Self, #enforce_name>(columns, #column, &self.#field, writer)?;
+                    #crate_path::ser::row::NextColumnSerializer::serialize::<Self, #enforce_name>(columns, #column, #value, writer)?;
                 }
             }
         });
@@ -2,9 +2,10 @@ use std::collections::HashMap;
 
 use darling::FromAttributes;
 use proc_macro::TokenStream;
+use syn::ext::IdentExt;
 use syn::parse_quote;
 
-use crate::Flavor;
+use crate::{Flavor, RenameRule};
 
 #[derive(FromAttributes)]
 #[darling(attributes(scylla))]
@@ -22,6 +23,11 @@ struct Attributes {
     #[darling(default)]
     skip_name_checks: bool,
 
+    // If set, then the names of the UDT field(s) are derived from the Rust field
+    // names according to the given naming convention, e.g. `camelCase`.
+    // The `rename` field attribute takes precedence over it.
+    rename_all: Option<RenameRule>,
+
     // If true, then the type checking code will require that the UDT does not
     // contain excess fields at its suffix. Otherwise, if UDT has some fields
     // at its suffix that do not correspond to Rust struct's fields,
@@ -45,13 +51,17 @@ struct Field {
     ident: syn::Ident,
     typ: syn::Type,
     attrs: FieldAttributes,
+    rename_all: Option<RenameRule>,
 }
 
 impl Field {
     fn field_name(&self) -> String {
         match &self.attrs.rename {
             Some(name) => name.clone(),
-            None => self.ident.to_string(),
+            None => match self.rename_all {
+                Some(rule) => rule.apply(&self.ident.unraw().to_string()),
+                None => self.ident.to_string(),
+            },
         }
     }
 
@@ -84,6 +94,11 @@ struct FieldAttributes {
     #[darling(default)]
     #[darling(rename = "default_when_null")]
     _default_when_null: bool,
+
+    // Used for deserialization only. Ignored in serialization.
+    #[darling(default)]
+    #[darling(rename = "default")]
+    _default: Option<darling::util::Ignored>,
 }
 
 struct Context {
@@ -111,6 +126,7 @@ pub(crate) fn derive_serialize_value(
                 ident: f.ident.clone().unwrap(),
                 typ: f.ty.clone(),
                 attrs,
+                rename_all: attributes.rename_all,
             })
         })
         // Filter the fields now instead of at the places that use them later
@@ -182,6 +198,15 @@ impl Context {
                     errors.push(err);
                 }
             }
+
+            // Neither does `rename_all`
+            if self.attributes.rename_all.is_some() {
+                let err = darling::Error::custom(
+                    "the `rename_all` attribute doesn't make sense with `skip_name_checks` attribute",
+                )
+                .with_span(struct_ident);
+                errors.push(err);
+            }
         }
 
         // Check for name collisions
@@ -0,0 +1,248 @@
+use darling::{FromAttributes, FromMeta};
+use proc_macro::TokenStream;
+use syn::ext::IdentExt;
+use syn::parse_quote;
+
+use crate::RenameRule;
+
+#[derive(FromAttributes)]
+#[darling(attributes(table))]
+struct Attributes {
+    #[darling(rename = "crate")]
+    crate_path: Option<syn::Path>,
+
+    // Name of the table. If not set, the struct name converted to snake_case is used.
+    name: Option<String>,
+
+    // If set, the table name in generated statements is qualified with this keyspace.
+    keyspace: Option<String>,
+}
+
+impl Attributes {
+    fn crate_path(&self) -> syn::Path {
+        self.crate_path
+            .as_ref()
+            .map(|p| parse_quote!(#p::_macro_internal))
+            .unwrap_or_else(|| parse_quote!(::scylla::_macro_internal))
+    }
+}
+
+#[derive(FromAttributes)]
+#[darling(attributes(table))]
+struct FieldAttributes {
+    // If set, the column is a part of the partition key.
+    #[darling(default)]
+    partition_key: bool,
+
+    // If set, the column is a part of the clustering key.
+    #[darling(default)]
+    clustering_key: bool,
+
+    // If set, overrides the CQL type of the column, which is otherwise
+    // taken from the `CqlTypeName` implementation of the field type.
+    cql_type: Option<String>,
+}
+
+// The subset of `#[scylla(...)]` field attributes that is relevant for the table mapping.
+// Columns must be named in the same way as by `SerializeRow` and `DeserializeRow`
+// derived for the same struct, so `rename` and `skip` are respected here, too.
+#[derive(Default)]
+struct ScyllaFieldAttributes {
+    rename: Option<String>,
+    skip: bool,
+    flatten: bool,
+}
+
+impl ScyllaFieldAttributes {
+    fn from_attributes(attrs: &[syn::Attribute]) -> Result<Self, syn::Error> {
+        let mut res = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("scylla")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    res.rename = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("skip") {
+                    res.skip = true;
+                } else if meta.path.is_ident("flatten") {
+                    res.flatten = true;
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Attributes of other derives are validated by them.
+                    meta.value()?.parse::<syn::Expr>()?;
+                }
+                Ok(())
+            })?;
+        }
+        Ok(res)
+    }
+}
+
+// The `rename_all` struct attribute of `#[scylla(...)]`, which affects column names
+// in the same way as `rename` does.
+fn scylla_rename_all(attrs: &[syn::Attribute]) -> Result<Option<RenameRule>, syn::Error> {
+    let mut res = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("scylla")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename_all") {
+                let rule = meta.value()?.parse::<syn::LitStr>()?;
+                res = Some(
+                    RenameRule::from_string(&rule.value())
+                        .map_err(|err| syn::Error::new(rule.span(), err))?,
+                );
+            } else if meta.input.peek(syn::Token![=]) {
+                // Attributes of other derives are validated by them.
+                meta.value()?.parse::<syn::Expr>()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(res)
+}
+
+struct Field {
+    ident: syn::Ident,
+    typ: syn::Type,
+    column_name: String,
+    attrs: FieldAttributes,
+}
+
+pub(crate) fn derive_table(tokens_input: TokenStream) -> Result<syn::ItemImpl, syn::Error> {
+    let input: syn::DeriveInput = syn::parse(tokens_input)?;
+    let struct_name = input.ident.clone();
+    let named_fields = crate::parser::parse_named_fields(&input, "Table")?;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let attributes = Attributes::from_attributes(&input.attrs)?;
+    let rename_all = scylla_rename_all(&input.attrs)?;
+
+    let crate_path = attributes.crate_path();
+    let implemented_trait: syn::Path = parse_quote!(#crate_path::Table);
+    let cql_type_name_trait: syn::Path = parse_quote!(#crate_path::CqlTypeName);
+
+    let mut errors = darling::Error::accumulator();
+    let mut fields = Vec::new();
+    for f in named_fields.named.iter() {
+        let ident = f.ident.clone().unwrap();
+        let Some(scylla_attrs) = errors
+            .handle(ScyllaFieldAttributes::from_attributes(&f.attrs).map_err(darling::Error::from))
+        else {
+            continue;
+        };
+        let Some(attrs) = errors.handle(FieldAttributes::from_attributes(&f.attrs)) else {
+            continue;
+        };
+        if scylla_attrs.skip {
+            if attrs.partition_key || attrs.clustering_key || attrs.cql_type.is_some() {
+                errors.push(
+                    darling::Error::custom("a skipped field cannot be mapped to a column")
+                        .with_span(&ident),
+                );
+            }
+            continue;
+        }
+        if scylla_attrs.flatten {
+            errors.push(
+                darling::Error::custom("flattened fields are not supported by derive(Table)")
+                    .with_span(&ident),
+            );
+            continue;
+        }
+        if attrs.partition_key && attrs.clustering_key {
+            errors.push(
+                darling::Error::custom(
+                    "a column cannot be a part of both the partition key and the clustering key",
+                )
+                .with_span(&ident),
+            );
+        }
+        fields.push(Field {
+            column_name: scylla_attrs.rename.unwrap_or_else(|| match rename_all {
+                Some(rule) => rule.apply(&ident.unraw().to_string()),
+                None => ident.to_string(),
+            }),
+            ident,
+            typ: f.ty.clone(),
+            attrs,
+        });
+    }
+    if !fields.iter().any(|f| f.attrs.partition_key) {
+        errors.push(
+            darling::Error::custom(
+                "the table must have at least one field marked with #[table(partition_key)]",
+            )
+            .with_span(&struct_name),
+        );
+    }
+    errors.finish()?;
+
+    let keyspace: syn::Expr = match &attributes.keyspace {
+        Some(keyspace) => parse_quote!(::std::option::Option::Some(#keyspace)),
+        None => parse_quote!(::std::option::Option::None),
+    };
+    let table_name = attributes
+        .name
+        .clone()
+        .unwrap_or_else(|| to_snake_case(&struct_name.to_string()));
+
+    let column_names = fields.iter().map(|f| &f.column_name);
+    let column_cql_types = fields.iter().map(|f| -> syn::Expr {
+        match &f.attrs.cql_type {
+            Some(cql_type) => parse_quote!(::std::string::String::from(#cql_type)),
+            None => {
+                let typ = &f.typ;
+                parse_quote!(<#typ as #cql_type_name_trait>::cql_type_name())
+            }
+        }
+    });
+
+    // Partition key columns come first, followed by clustering key columns.
+    let partition_key = fields.iter().filter(|f| f.attrs.partition_key);
+    let clustering_key = fields.iter().filter(|f| f.attrs.clustering_key);
+    let partition_key_names = partition_key.clone().map(|f| &f.column_name);
+    let clustering_key_names = clustering_key.clone().map(|f| &f.column_name);
+    let primary_key_types = partition_key
+        .clone()
+        .chain(clustering_key.clone())
+        .map(|f| &f.typ);
+    let primary_key_idents = partition_key.chain(clustering_key).map(|f| &f.ident);
+
+    let res = parse_quote! {
+        #[automatically_derived]
+        impl #impl_generics #implemented_trait for #struct_name #ty_generics #where_clause {
+            const KEYSPACE: ::std::option::Option<&'static ::std::primitive::str> = #keyspace;
+            const NAME: &'static ::std::primitive::str = #table_name;
+            const COLUMNS: &'static [&'static ::std::primitive::str] = &[#(#column_names),*];
+            const PARTITION_KEY: &'static [&'static ::std::primitive::str] =
+                &[#(#partition_key_names),*];
+            const CLUSTERING_KEY: &'static [&'static ::std::primitive::str] =
+                &[#(#clustering_key_names),*];
+
+            type PrimaryKey<'table_key> = (#(&'table_key #primary_key_types,)*)
+            where
+                Self: 'table_key;
+
+            fn column_cql_types() -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(#column_cql_types),*]
+            }
+
+            fn primary_key(&self) -> Self::PrimaryKey<'_> {
+                (#(&self.#primary_key_idents,)*)
+            }
+        }
+    };
+    Ok(res)
+}
+
+// Converts a struct name, e.g. `HTTPRequestLog`, to a table name, e.g. `http_request_log`.
+fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut res = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lowercase = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            if prev != '_' && (prev.is_lowercase() || prev.is_numeric() || next_is_lowercase) {
+                res.push('_');
+            }
+        }
+        res.extend(c.to_lowercase());
+    }
+    res
+}
@@ -888,6 +888,10 @@ mod compression {
         #[error("Snap compression error: {0}")]
         SnapCompressError(Arc<dyn Error + Sync + Send>),
 
+        /// Body Zstd compression failed.
+        #[error("Zstd compression error: {0}")]
+        ZstdCompressError(Arc<dyn Error + Sync + Send>),
+
         /// Frame is to be compressed, but no compression was negotiated for the connection.
         #[error("Frame is to be compressed, but no compression negotiated for connection.")]
         NoCompressionNegotiated,
@@ -951,11 +955,16 @@ mod compression {
             match (flags & flag::COMPRESSION != 0, self.get().flatten()) {
                 (true, Some(compression)) => {
                     let mut buf = Vec::new();
-                    compress_append(body, compression, &mut buf).map_err(|err| {
-                        let CqlRequestSerializationError::SnapCompressError(err) = err else {
-                            unreachable!("BUG: compress_append returned variant different than SnapCompressError")
-                        };
-                        CompressionError::SnapCompressError(err)
+                    compress_append(body, compression, &mut buf).map_err(|err| match err {
+                        CqlRequestSerializationError::SnapCompressError(err) => {
+                            CompressionError::SnapCompressError(err)
+                        }
+                        CqlRequestSerializationError::ZstdCompressError(err) => {
+                            CompressionError::ZstdCompressError(err)
+                        }
+                        _ => unreachable!(
+                            "BUG: compress_append returned variant different than a compression error"
+                        ),
                     })?;
                     Ok(Some(Bytes::from(buf)))
                 }
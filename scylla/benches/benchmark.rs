@@ -3,8 +3,8 @@
 use criterion::{criterion_group, criterion_main, Criterion};
 
 use bytes::BytesMut;
-use scylla::internal_testing::calculate_token_for_partition_key;
 use scylla::routing::partitioner::PartitionerName;
+use scylla::routing::Token;
 use scylla_cql::frame::response::result::{ColumnType, NativeType};
 use scylla_cql::frame::types;
 use scylla_cql::serialize::row::SerializedValues;
@@ -87,37 +87,30 @@ fn calculate_token_bench(c: &mut Criterion) {
         .unwrap();
 
     c.bench_function("calculate_token_from_partition_key simple pk", |b| {
-        b.iter(|| {
-            calculate_token_for_partition_key(&serialized_simple_pk, &PartitionerName::Murmur3)
-        })
+        b.iter(|| Token::from_partition_key(&PartitionerName::Murmur3, &serialized_simple_pk))
     });
 
     c.bench_function(
         "calculate_token_from_partition_key simple pk long column",
         |b| {
             b.iter(|| {
-                calculate_token_for_partition_key(
-                    &serialized_simple_pk_long_column,
+                Token::from_partition_key(
                     &PartitionerName::Murmur3,
+                    &serialized_simple_pk_long_column,
                 )
             })
         },
     );
 
     c.bench_function("calculate_token_from_partition_key complex pk", |b| {
-        b.iter(|| {
-            calculate_token_for_partition_key(&serialized_complex_pk, &PartitionerName::Murmur3)
-        })
+        b.iter(|| Token::from_partition_key(&PartitionerName::Murmur3, &serialized_complex_pk))
     });
 
     c.bench_function(
         "calculate_token_from_partition_key complex pk long column",
         |b| {
             b.iter(|| {
-                calculate_token_for_partition_key(
-                    &serialized_values_long_column,
-                    &PartitionerName::Murmur3,
-                )
+                Token::from_partition_key(&PartitionerName::Murmur3, &serialized_values_long_column)
             })
         },
     );
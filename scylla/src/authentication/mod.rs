@@ -1,10 +1,21 @@
 //! Traits and implementations for custom authentication against a server.
 
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
 use async_trait::async_trait;
-use bytes::{BufMut, BytesMut};
+use tokio::sync::Mutex;
+use tracing::warn;
 
 pub use crate::frame::Authenticator;
 
+mod sasl;
+pub use sasl::{PlainSaslMechanism, SaslAuthenticator, SaslMechanism};
+#[cfg(feature = "scram")]
+mod scram;
+#[cfg(feature = "scram")]
+pub use scram::ScramSha256Mechanism;
+
 /// Type to represent an authentication error message.
 pub type AuthError = String;
 
@@ -22,6 +33,19 @@ pub trait AuthenticatorSession: Send + Sync {
     /// To handle the success phase of exchange.
     /// The token parameters contain information that may be used to finalize the request.
     async fn success(&mut self, token: Option<&[u8]>) -> Result<(), AuthError>;
+
+    /// Returns the point in time after which the credentials used by this session
+    /// should no longer be relied upon, if they have limited lifetime.
+    ///
+    /// It is queried after the authentication exchange succeeds. If `Some` is returned,
+    /// the connection pool will replace the connection authenticated with this session
+    /// by a fresh one (authenticated with a new session) once that point in time is reached.
+    /// In-flight requests on the replaced connection are allowed to complete.
+    ///
+    /// Default: `None`, i.e. the credentials never expire.
+    fn credentials_expiry(&self) -> Option<SystemTime> {
+        None
+    }
 }
 
 /// Trait used to represent a factory of [`AuthenticatorSession`] instances.
@@ -57,16 +81,138 @@ impl AuthenticatorSession for PlainTextAuthenticatorSession {
     }
 }
 
+/// Password used for plain text authentication.
+///
+/// It can be created from a `String` or a `&str`, and, with the `secrecy-08` feature,
+/// from a `secrecy::SecretString`. With that feature enabled, the password is always kept
+/// in a `SecretString`, so that it is zeroized when dropped, as are the AUTH_RESPONSE
+/// requests it is sent in.
+#[derive(Clone)]
+pub struct Password {
+    #[cfg(feature = "secrecy-08")]
+    secret: secrecy_08::SecretString,
+    #[cfg(not(feature = "secrecy-08"))]
+    secret: String,
+}
+
+impl Password {
+    /// Returns the password.
+    pub fn expose(&self) -> &str {
+        #[cfg(feature = "secrecy-08")]
+        return secrecy_08::ExposeSecret::expose_secret(&self.secret);
+        #[cfg(not(feature = "secrecy-08"))]
+        return &self.secret;
+    }
+}
+
+impl From<String> for Password {
+    fn from(password: String) -> Self {
+        Self {
+            #[cfg(feature = "secrecy-08")]
+            secret: secrecy_08::SecretString::new(password),
+            #[cfg(not(feature = "secrecy-08"))]
+            secret: password,
+        }
+    }
+}
+
+impl From<&str> for Password {
+    fn from(password: &str) -> Self {
+        Self::from(password.to_owned())
+    }
+}
+
+#[cfg(feature = "secrecy-08")]
+impl From<secrecy_08::SecretString> for Password {
+    fn from(secret: secrecy_08::SecretString) -> Self {
+        Self { secret }
+    }
+}
+
+impl std::fmt::Debug for Password {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+/// Username and password used for plain text authentication.
+#[derive(Clone)]
+pub struct Credentials {
+    username: String,
+    password: Password,
+}
+
+impl Credentials {
+    /// Creates new credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<Password>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    /// Returns the username.
+    pub fn username(&self) -> &str {
+        &self.username
+    }
+
+    /// Returns the password.
+    pub fn password(&self) -> &str {
+        self.password.expose()
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credentials")
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Source of credentials used by [`PlainTextAuthenticator`].
+///
+/// The credentials are requested each time a connection is opened, including
+/// reconnections, so implementations can return credentials that change over time,
+/// e.g. ones leased from a secret store such as Vault. Connections that are already
+/// open are not affected by a change of credentials.
+#[async_trait]
+pub trait CredentialsProvider: Send + Sync {
+    /// Returns the credentials to authenticate a new connection with.
+    async fn get_credentials(&self) -> Result<Credentials, AuthError>;
+}
+
+#[async_trait]
+impl CredentialsProvider for Credentials {
+    async fn get_credentials(&self) -> Result<Credentials, AuthError> {
+        Ok(self.clone())
+    }
+}
+
 /// Default authenticator provider that requires username and password if authentication is required.
 pub struct PlainTextAuthenticator {
-    username: String,
-    password: String,
+    credentials_provider: Arc<dyn CredentialsProvider>,
 }
 
 impl PlainTextAuthenticator {
     /// Creates new [`PlainTextAuthenticator`] instance with provided username and password.
     pub fn new(username: String, password: String) -> Self {
-        PlainTextAuthenticator { username, password }
+        Self::with_password(username, Password::from(password))
+    }
+
+    /// Creates new [`PlainTextAuthenticator`] instance with provided username and [`Password`],
+    /// which can be e.g. a `secrecy::SecretString` with the `secrecy-08` feature.
+    pub fn with_password(username: String, password: Password) -> Self {
+        Self::from_credentials_provider(Arc::new(Credentials::new(username, password)))
+    }
+
+    /// Creates new [`PlainTextAuthenticator`] instance which fetches credentials
+    /// from the given provider for every new connection.
+    pub fn from_credentials_provider(credentials_provider: Arc<dyn CredentialsProvider>) -> Self {
+        PlainTextAuthenticator {
+            credentials_provider,
+        }
     }
 }
 
@@ -76,18 +222,367 @@ impl AuthenticatorProvider for PlainTextAuthenticator {
         &self,
         _authenticator_name: &str,
     ) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
-        let mut response = BytesMut::new();
-        let username_as_bytes = self.username.as_bytes();
-        let password_as_bytes = self.password.as_bytes();
+        let credentials = self.credentials_provider.get_credentials().await?;
+        Ok(plain_text_initial_response(
+            credentials.username(),
+            credentials.password(),
+        ))
+    }
+}
+
+// Builds the initial response of the `PLAIN` SASL mechanism.
+fn plain_text_initial_response(
+    username: &str,
+    password: &str,
+) -> (Option<Vec<u8>>, Box<dyn AuthenticatorSession>) {
+    // The exact capacity avoids reallocations, which would leave copies
+    // of the password behind.
+    let mut response = Vec::with_capacity(username.len() + password.len() + 2);
+
+    response.push(0);
+    response.extend_from_slice(username.as_bytes());
+    response.push(0);
+    response.extend_from_slice(password.as_bytes());
+
+    (Some(response), Box::new(PlainTextAuthenticatorSession))
+}
+
+/// A short-lived authentication token, e.g. a JWT or an OAuth access token,
+/// returned by a [`TokenProvider`].
+#[derive(Clone)]
+pub struct AuthToken {
+    value: Vec<u8>,
+    expires_at: Option<SystemTime>,
+}
+
+impl AuthToken {
+    /// Creates a new token. `value` is sent to the server as-is, as the initial
+    /// authentication response. `expires_at` is the point in time when the server
+    /// stops accepting the token; `None` means that the token does not expire.
+    pub fn new(value: impl Into<Vec<u8>>, expires_at: Option<SystemTime>) -> Self {
+        Self {
+            value: value.into(),
+            expires_at,
+        }
+    }
+
+    /// Returns the raw token bytes.
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Returns the point in time when the token expires, if it does.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+}
 
-        response.put_u8(0);
-        response.put_slice(username_as_bytes);
-        response.put_u8(0);
-        response.put_slice(password_as_bytes);
+impl std::fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuthToken")
+            .field("value", &"<redacted>")
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Source of short-lived authentication tokens used by [`TokenAuthenticator`].
+///
+/// Implementations typically contact an identity provider (e.g. an OAuth server)
+/// to obtain a fresh token.
+#[async_trait]
+pub trait TokenProvider: Send + Sync {
+    /// Fetches a fresh token.
+    async fn fetch_token(&self) -> Result<AuthToken, AuthError>;
+}
+
+/// Authenticator provider which authenticates connections with short-lived tokens
+/// obtained from a [`TokenProvider`].
+///
+/// The token is cached and shared by all connections. It is refreshed before opening
+/// a connection if it expires within the configured refresh margin. Connections
+/// authenticated with a token are rotated by the connection pool when the token
+/// gets close to its expiry, so that no connection outlives the token it was opened with.
+///
+/// Tokens whose lifetime is not longer than the refresh margin are refreshed
+/// halfway through their lifetime instead.
+pub struct TokenAuthenticator {
+    provider: Arc<dyn TokenProvider>,
+    refresh_margin: Duration,
+    cached_token: Mutex<Option<CachedToken>>,
+}
+
+struct CachedToken {
+    token: AuthToken,
+    // When the token should be refreshed and connections authenticated with it rotated.
+    // `None` if the token does not expire.
+    refresh_at: Option<SystemTime>,
+}
+
+impl CachedToken {
+    fn new(token: AuthToken, refresh_margin: Duration) -> Self {
+        let refresh_at = token.expires_at.map(|expires_at| {
+            let lifetime = expires_at
+                .duration_since(SystemTime::now())
+                .unwrap_or_default();
+            let margin = if refresh_margin >= lifetime {
+                // Otherwise the token would be refreshed (and connections rotated)
+                // every time it is used.
+                warn!(
+                    "Token lifetime ({:?}) is not longer than the refresh margin ({:?}), \
+                    the token will be refreshed halfway through its lifetime",
+                    lifetime, refresh_margin
+                );
+                lifetime / 2
+            } else {
+                refresh_margin
+            };
+            expires_at
+                .checked_sub(margin)
+                .unwrap_or(SystemTime::UNIX_EPOCH)
+        });
+        Self { token, refresh_at }
+    }
+
+    fn needs_refresh(&self) -> bool {
+        self.refresh_at
+            .is_some_and(|refresh_at| refresh_at <= SystemTime::now())
+    }
+}
+
+impl TokenAuthenticator {
+    /// Default time before the token expiry when the token is refreshed
+    /// and connections authenticated with it are rotated.
+    pub const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+    /// Creates a new [`TokenAuthenticator`] fetching tokens from the given provider.
+    pub fn new(provider: Arc<dyn TokenProvider>) -> Self {
+        Self {
+            provider,
+            refresh_margin: Self::DEFAULT_REFRESH_MARGIN,
+            cached_token: Mutex::new(None),
+        }
+    }
+
+    /// Sets how long before the token expiry it should be refreshed
+    /// and connections using it should be rotated.
+    ///
+    /// Default: [`TokenAuthenticator::DEFAULT_REFRESH_MARGIN`].
+    pub fn with_refresh_margin(mut self, refresh_margin: Duration) -> Self {
+        self.refresh_margin = refresh_margin;
+        self
+    }
+
+    /// Returns a valid token, fetching a new one from the provider
+    /// if the cached one is missing or about to expire.
+    pub async fn current_token(&self) -> Result<AuthToken, AuthError> {
+        self.current_token_with_refresh_time()
+            .await
+            .map(|(token, _)| token)
+    }
+
+    // Returns a valid token along with the time when it should be refreshed.
+    async fn current_token_with_refresh_time(
+        &self,
+    ) -> Result<(AuthToken, Option<SystemTime>), AuthError> {
+        let mut cached_token = self.cached_token.lock().await;
+        match &*cached_token {
+            Some(cached) if !cached.needs_refresh() => {
+                Ok((cached.token.clone(), cached.refresh_at))
+            }
+            _ => {
+                let token = self.provider.fetch_token().await?;
+                let cached = CachedToken::new(token.clone(), self.refresh_margin);
+                let refresh_at = cached.refresh_at;
+                *cached_token = Some(cached);
+                Ok((token, refresh_at))
+            }
+        }
+    }
+}
+
+struct TokenAuthenticatorSession {
+    rotate_at: Option<SystemTime>,
+}
+
+#[async_trait]
+impl AuthenticatorSession for TokenAuthenticatorSession {
+    async fn evaluate_challenge(
+        &mut self,
+        _token: Option<&[u8]>,
+    ) -> Result<Option<Vec<u8>>, AuthError> {
+        Err("Challenges are not expected during token authentication".to_string())
+    }
+
+    async fn success(&mut self, _token: Option<&[u8]>) -> Result<(), AuthError> {
+        Ok(())
+    }
+
+    fn credentials_expiry(&self) -> Option<SystemTime> {
+        self.rotate_at
+    }
+}
+
+#[async_trait]
+impl AuthenticatorProvider for TokenAuthenticator {
+    async fn start_authentication_session(
+        &self,
+        _authenticator_name: &str,
+    ) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        let (token, rotate_at) = self.current_token_with_refresh_time().await?;
 
         Ok((
-            Some(response.to_vec()),
-            Box::new(PlainTextAuthenticatorSession),
+            Some(token.value),
+            Box::new(TokenAuthenticatorSession { rotate_at }),
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    use async_trait::async_trait;
+
+    use super::{
+        AuthError, AuthToken, AuthenticatorProvider, Credentials, CredentialsProvider, Password,
+        PlainTextAuthenticator, TokenAuthenticator, TokenProvider,
+    };
+
+    struct RotatingCredentialsProvider {
+        fetches: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl CredentialsProvider for RotatingCredentialsProvider {
+        async fn get_credentials(&self) -> Result<Credentials, AuthError> {
+            let n = self.fetches.fetch_add(1, Ordering::Relaxed);
+            Ok(Credentials::new("user", format!("password-{n}")))
+        }
+    }
+
+    #[tokio::test]
+    async fn plain_text_authenticator_fetches_credentials_per_connection() {
+        let authenticator = PlainTextAuthenticator::from_credentials_provider(Arc::new(
+            RotatingCredentialsProvider {
+                fetches: AtomicUsize::new(0),
+            },
+        ));
+
+        for n in 0..2 {
+            let (response, _) = authenticator
+                .start_authentication_session("")
+                .await
+                .unwrap();
+            assert_eq!(
+                response.unwrap(),
+                format!("\0user\0password-{n}").into_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn password_is_redacted() {
+        let credentials = Credentials::new("user", "password");
+        assert_eq!(credentials.password(), "password");
+        assert!(!format!("{credentials:?}").contains("password\""));
+        assert_eq!(format!("{:?}", Password::from("password")), "<redacted>");
+    }
+
+    #[cfg(feature = "secrecy-08")]
+    #[tokio::test]
+    async fn plain_text_authenticator_accepts_secret_password() {
+        let password = secrecy_08::SecretString::new("password".to_owned());
+        let authenticator =
+            PlainTextAuthenticator::with_password("user".to_owned(), Password::from(password));
+
+        let (response, _) = authenticator
+            .start_authentication_session("")
+            .await
+            .unwrap();
+        assert_eq!(response.unwrap(), b"\0user\0password");
+    }
+
+    struct CountingTokenProvider {
+        fetches: AtomicUsize,
+        lifetime: Duration,
+    }
+
+    #[async_trait]
+    impl TokenProvider for CountingTokenProvider {
+        async fn fetch_token(&self) -> Result<AuthToken, AuthError> {
+            let n = self.fetches.fetch_add(1, Ordering::Relaxed);
+            Ok(AuthToken::new(
+                format!("token-{n}"),
+                Some(SystemTime::now() + self.lifetime),
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn token_authenticator_caches_valid_token() {
+        let provider = Arc::new(CountingTokenProvider {
+            fetches: AtomicUsize::new(0),
+            lifetime: Duration::from_secs(3600),
+        });
+        let authenticator = TokenAuthenticator::new(provider.clone());
+
+        for _ in 0..3 {
+            let (response, session) = authenticator
+                .start_authentication_session("")
+                .await
+                .unwrap();
+            assert_eq!(response.as_deref(), Some(b"token-0".as_slice()));
+            let rotate_at = session.credentials_expiry().unwrap();
+            assert!(rotate_at > SystemTime::now());
+        }
+        assert_eq!(provider.fetches.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn token_authenticator_clamps_refresh_margin_to_token_lifetime() {
+        let provider = Arc::new(CountingTokenProvider {
+            fetches: AtomicUsize::new(0),
+            lifetime: Duration::from_secs(30),
+        });
+        // The token lifetime is shorter than the refresh margin,
+        // so the token should be refreshed halfway through its lifetime.
+        let authenticator =
+            TokenAuthenticator::new(provider.clone()).with_refresh_margin(Duration::from_secs(60));
+
+        for _ in 0..2 {
+            let (response, session) = authenticator
+                .start_authentication_session("")
+                .await
+                .unwrap();
+            assert_eq!(response.as_deref(), Some(b"token-0".as_slice()));
+            let rotate_at = session.credentials_expiry().unwrap();
+            assert!(rotate_at > SystemTime::now() + Duration::from_secs(10));
+            assert!(rotate_at <= SystemTime::now() + Duration::from_secs(15));
+        }
+        assert_eq!(provider.fetches.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn token_authenticator_refreshes_expired_token() {
+        let provider = Arc::new(CountingTokenProvider {
+            fetches: AtomicUsize::new(0),
+            lifetime: Duration::ZERO,
+        });
+        let authenticator = TokenAuthenticator::new(provider.clone());
+
+        let (first, _) = authenticator
+            .start_authentication_session("")
+            .await
+            .unwrap();
+        let (second, _) = authenticator
+            .start_authentication_session("")
+            .await
+            .unwrap();
+        assert_eq!(first.as_deref(), Some(b"token-0".as_slice()));
+        assert_eq!(second.as_deref(), Some(b"token-1".as_slice()));
+        assert_eq!(provider.fetches.load(Ordering::Relaxed), 2);
+    }
+}
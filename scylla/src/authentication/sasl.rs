@@ -0,0 +1,201 @@
+//! SASL mechanisms negotiated according to the server's authenticator class.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use super::{plain_text_initial_response, AuthError, AuthenticatorProvider, AuthenticatorSession};
+
+/// A SASL mechanism, e.g. `PLAIN` or `SCRAM-SHA-256`.
+///
+/// Mechanisms are registered in a [`SaslAuthenticator`], which picks one
+/// of them depending on the authenticator class announced by the server.
+#[async_trait]
+pub trait SaslMechanism: Send + Sync {
+    /// Name of the mechanism, as registered by IANA, e.g. `SCRAM-SHA-256`.
+    fn name(&self) -> &str;
+
+    /// Starts an exchange of this mechanism. Returns the initial client response
+    /// and the session handling the rest of the exchange.
+    async fn start(&self) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError>;
+}
+
+/// Authenticator provider which negotiates one of several SASL mechanisms
+/// depending on the authenticator class announced by the server.
+///
+/// An authenticator class configured with [`SaslAuthenticator::with_mechanism`]
+/// matches the server's class if they are equal, or if the configured class is the
+/// unqualified name of the server's class (e.g. `PasswordAuthenticator` matches
+/// `org.apache.cassandra.auth.PasswordAuthenticator`).
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// use scylla::authentication::{PlainSaslMechanism, SaslAuthenticator};
+///
+/// let authenticator = SaslAuthenticator::new()
+///     .with_mechanism(
+///         "com.example.ProxyAuthenticator",
+///         Arc::new(PlainSaslMechanism::new("proxy-user", "password")),
+///     )
+///     .with_default_mechanism(Arc::new(PlainSaslMechanism::new("user", "password")));
+/// ```
+#[derive(Default)]
+pub struct SaslAuthenticator {
+    mechanisms: Vec<(String, Arc<dyn SaslMechanism>)>,
+    default_mechanism: Option<Arc<dyn SaslMechanism>>,
+}
+
+impl SaslAuthenticator {
+    /// Creates an authenticator without any mechanisms.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Uses `mechanism` when the server announces the given authenticator class.
+    ///
+    /// If several mechanisms match the server's class, the one registered first is used.
+    pub fn with_mechanism(
+        mut self,
+        authenticator_class: impl Into<String>,
+        mechanism: Arc<dyn SaslMechanism>,
+    ) -> Self {
+        self.mechanisms
+            .push((authenticator_class.into(), mechanism));
+        self
+    }
+
+    /// Uses `mechanism` when no mechanism was registered for the authenticator class
+    /// announced by the server.
+    ///
+    /// Without a default mechanism, authentication against such a server fails.
+    pub fn with_default_mechanism(mut self, mechanism: Arc<dyn SaslMechanism>) -> Self {
+        self.default_mechanism = Some(mechanism);
+        self
+    }
+
+    fn select_mechanism(&self, authenticator_name: &str) -> Option<&Arc<dyn SaslMechanism>> {
+        let unqualified_name = authenticator_name
+            .rsplit_once('.')
+            .map_or(authenticator_name, |(_, name)| name);
+        self.mechanisms
+            .iter()
+            .find(|(class, _)| class == authenticator_name || class == unqualified_name)
+            .map(|(_, mechanism)| mechanism)
+            .or(self.default_mechanism.as_ref())
+    }
+}
+
+impl std::fmt::Debug for SaslAuthenticator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaslAuthenticator")
+            .field(
+                "mechanisms",
+                &self
+                    .mechanisms
+                    .iter()
+                    .map(|(class, mechanism)| (class, mechanism.name()))
+                    .collect::<Vec<_>>(),
+            )
+            .field(
+                "default_mechanism",
+                &self.default_mechanism.as_ref().map(|m| m.name()),
+            )
+            .finish()
+    }
+}
+
+#[async_trait]
+impl AuthenticatorProvider for SaslAuthenticator {
+    async fn start_authentication_session(
+        &self,
+        authenticator_name: &str,
+    ) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        let mechanism = self.select_mechanism(authenticator_name).ok_or_else(|| {
+            format!(
+                "No SASL mechanism configured for authenticator {}",
+                authenticator_name
+            )
+        })?;
+        mechanism.start().await
+    }
+}
+
+/// The `PLAIN` SASL mechanism ([RFC 4616](https://www.rfc-editor.org/rfc/rfc4616)),
+/// sending the username and password in plain text.
+///
+/// It is the mechanism used by [`PlainTextAuthenticator`](super::PlainTextAuthenticator).
+pub struct PlainSaslMechanism {
+    username: String,
+    password: String,
+}
+
+impl PlainSaslMechanism {
+    /// Creates the mechanism with the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl SaslMechanism for PlainSaslMechanism {
+    fn name(&self) -> &str {
+        "PLAIN"
+    }
+
+    async fn start(&self) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        Ok(plain_text_initial_response(&self.username, &self.password))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::{PlainSaslMechanism, SaslAuthenticator};
+    use crate::authentication::AuthenticatorProvider;
+
+    #[tokio::test]
+    async fn sasl_authenticator_selects_mechanism_by_authenticator_class() {
+        let authenticator = SaslAuthenticator::new()
+            .with_mechanism(
+                "com.example.ProxyAuthenticator",
+                Arc::new(PlainSaslMechanism::new("proxy-user", "pencil")),
+            )
+            .with_mechanism(
+                "PasswordAuthenticator",
+                Arc::new(PlainSaslMechanism::new("user", "pencil")),
+            );
+
+        let (response, _) = authenticator
+            .start_authentication_session("com.example.ProxyAuthenticator")
+            .await
+            .unwrap();
+        assert_eq!(
+            response.as_deref(),
+            Some(b"\0proxy-user\0pencil".as_slice())
+        );
+
+        let (response, _) = authenticator
+            .start_authentication_session("org.apache.cassandra.auth.PasswordAuthenticator")
+            .await
+            .unwrap();
+        assert_eq!(response.as_deref(), Some(b"\0user\0pencil".as_slice()));
+
+        assert!(authenticator
+            .start_authentication_session("com.example.KerberosAuthenticator")
+            .await
+            .is_err());
+
+        let authenticator = authenticator
+            .with_default_mechanism(Arc::new(PlainSaslMechanism::new("other", "secret")));
+        let (response, _) = authenticator
+            .start_authentication_session("com.example.KerberosAuthenticator")
+            .await
+            .unwrap();
+        assert_eq!(response.as_deref(), Some(b"\0other\0secret".as_slice()));
+    }
+}
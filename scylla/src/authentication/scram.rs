@@ -0,0 +1,286 @@
+//! The `SCRAM-SHA-256` SASL mechanism.
+
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+use super::{AuthError, AuthenticatorSession, SaslMechanism};
+
+/// The `SCRAM-SHA-256` SASL mechanism ([RFC 7677](https://www.rfc-editor.org/rfc/rfc7677)).
+///
+/// The password is never sent to the server, and the server is required to prove
+/// that it knows the credentials too. Channel binding is not supported.
+///
+/// The iteration count requested by the server must be between 4096 (the minimum
+/// required by RFC 7677) and 1 000 000, as deriving the key takes time proportional to it.
+pub struct ScramSha256Mechanism {
+    username: String,
+    password: String,
+}
+
+impl ScramSha256Mechanism {
+    /// Creates the mechanism with the given credentials.
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    fn start_with_nonce(&self, client_nonce: String) -> (Vec<u8>, ScramSha256Session) {
+        // Commas and equal signs have to be escaped in the username (RFC 5802, section 5.1).
+        let username = self.username.replace('=', "=3D").replace(',', "=2C");
+        let client_first_message_bare = format!("n={},r={}", username, client_nonce);
+        let initial_response = format!("{}{}", GS2_HEADER, client_first_message_bare);
+        let session = ScramSha256Session {
+            password: self.password.clone(),
+            client_nonce,
+            client_first_message_bare,
+            state: ScramState::AwaitingServerFirst,
+        };
+        (initial_response.into_bytes(), session)
+    }
+}
+
+#[async_trait]
+impl SaslMechanism for ScramSha256Mechanism {
+    fn name(&self) -> &str {
+        "SCRAM-SHA-256"
+    }
+
+    async fn start(&self) -> Result<(Option<Vec<u8>>, Box<dyn AuthenticatorSession>), AuthError> {
+        let nonce: [u8; 18] = rand::rng().random();
+        let (initial_response, session) = self.start_with_nonce(BASE64.encode(nonce));
+        Ok((Some(initial_response), Box::new(session)))
+    }
+}
+
+// No channel binding, no authorization identity.
+const GS2_HEADER: &str = "n,,";
+
+// Bounds of the iteration count accepted from the server. The lower one is required
+// by RFC 7677; the upper one keeps a hostile or misconfigured server from making
+// the key derivation take arbitrarily long.
+const MIN_ITERATIONS: u32 = 4096;
+const MAX_ITERATIONS: u32 = 1_000_000;
+
+type HmacSha256 = Hmac<Sha256>;
+
+enum ScramState {
+    AwaitingServerFirst,
+    AwaitingServerFinal { server_signature: Vec<u8> },
+    Done,
+}
+
+struct ScramSha256Session {
+    password: String,
+    client_nonce: String,
+    client_first_message_bare: String,
+    state: ScramState,
+}
+
+// Returns values of the leading attributes of a SCRAM message, in the given order.
+fn scram_attributes<const N: usize>(
+    message: &str,
+    names: [char; N],
+) -> Result<[&str; N], AuthError> {
+    let mut attributes = message.split(',');
+    let mut values = [""; N];
+    for (value, name) in values.iter_mut().zip(names) {
+        *value = attributes
+            .next()
+            .and_then(|attribute| attribute.strip_prefix(name))
+            .and_then(|attribute| attribute.strip_prefix('='))
+            .ok_or_else(|| format!("Malformed SCRAM message: missing attribute {}", name))?;
+    }
+    Ok(values)
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+// Derives the salted password. This takes a while for large iteration counts,
+// so it is done on a blocking thread rather than on the runtime's worker.
+async fn salted_password(
+    password: String,
+    salt: Vec<u8>,
+    iterations: u32,
+) -> Result<[u8; 32], AuthError> {
+    tokio::task::spawn_blocking(move || {
+        let mut salted_password = [0_u8; 32];
+        pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), &salt, iterations, &mut salted_password);
+        salted_password
+    })
+    .await
+    .map_err(|err| format!("SCRAM key derivation failed: {}", err))
+}
+
+impl ScramSha256Session {
+    async fn client_final_message(&mut self, server_first: &[u8]) -> Result<Vec<u8>, AuthError> {
+        let server_first = std::str::from_utf8(server_first)
+            .map_err(|_| "Malformed SCRAM message: not valid UTF-8".to_string())?;
+        if let Some(error) = server_first.strip_prefix("e=") {
+            return Err(format!("SCRAM authentication failed: {}", error));
+        }
+        let [nonce, salt, iterations] = scram_attributes(server_first, ['r', 's', 'i'])?;
+        if !nonce.starts_with(&self.client_nonce) || nonce.len() == self.client_nonce.len() {
+            return Err("SCRAM server nonce does not extend the client nonce".to_string());
+        }
+        let salt = BASE64
+            .decode(salt)
+            .map_err(|_| "Malformed SCRAM message: invalid salt".to_string())?;
+        let iterations: u32 = iterations
+            .parse()
+            .map_err(|_| "Malformed SCRAM message: invalid iteration count".to_string())?;
+        if !(MIN_ITERATIONS..=MAX_ITERATIONS).contains(&iterations) {
+            return Err(format!(
+                "SCRAM iteration count {} is outside of the accepted range {}..={}",
+                iterations, MIN_ITERATIONS, MAX_ITERATIONS
+            ));
+        }
+
+        let salted_password = salted_password(self.password.clone(), salt, iterations).await?;
+        let client_key = hmac(&salted_password, b"Client Key");
+        let stored_key = Sha256::digest(&client_key);
+        let server_key = hmac(&salted_password, b"Server Key");
+
+        let client_final_without_proof = format!("c={},r={}", BASE64.encode(GS2_HEADER), nonce);
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_message_bare, server_first, client_final_without_proof
+        );
+        let client_signature = hmac(&stored_key, auth_message.as_bytes());
+        let client_proof: Vec<u8> = client_key
+            .iter()
+            .zip(&client_signature)
+            .map(|(key, signature)| key ^ signature)
+            .collect();
+
+        self.state = ScramState::AwaitingServerFinal {
+            server_signature: hmac(&server_key, auth_message.as_bytes()),
+        };
+        Ok(format!(
+            "{},p={}",
+            client_final_without_proof,
+            BASE64.encode(client_proof)
+        )
+        .into_bytes())
+    }
+
+    fn verify_server_final(&mut self, server_final: &[u8]) -> Result<(), AuthError> {
+        let ScramState::AwaitingServerFinal { server_signature } = &self.state else {
+            unreachable!()
+        };
+        let server_final = std::str::from_utf8(server_final)
+            .map_err(|_| "Malformed SCRAM message: not valid UTF-8".to_string())?;
+        if let Some(error) = server_final.strip_prefix("e=") {
+            return Err(format!("SCRAM authentication failed: {}", error));
+        }
+        let [verifier] = scram_attributes(server_final, ['v'])?;
+        if BASE64.decode(verifier).ok().as_ref() != Some(server_signature) {
+            return Err("SCRAM server signature is invalid".to_string());
+        }
+        self.state = ScramState::Done;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AuthenticatorSession for ScramSha256Session {
+    async fn evaluate_challenge(
+        &mut self,
+        token: Option<&[u8]>,
+    ) -> Result<Option<Vec<u8>>, AuthError> {
+        let token = token.unwrap_or_default();
+        match self.state {
+            ScramState::AwaitingServerFirst => self.client_final_message(token).await.map(Some),
+            // Some servers send the final message as a challenge rather than along with success.
+            ScramState::AwaitingServerFinal { .. } => {
+                self.verify_server_final(token).map(|()| Some(Vec::new()))
+            }
+            ScramState::Done => Err("Unexpected SCRAM challenge".to_string()),
+        }
+    }
+
+    async fn success(&mut self, token: Option<&[u8]>) -> Result<(), AuthError> {
+        match (&self.state, token) {
+            (ScramState::Done, _) => Ok(()),
+            (ScramState::AwaitingServerFinal { .. }, Some(token)) => {
+                self.verify_server_final(token)
+            }
+            _ => Err("SCRAM authentication succeeded without server verification".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScramSha256Mechanism;
+    use crate::authentication::AuthenticatorSession;
+
+    // Test vector from RFC 7677, section 3.
+    const CLIENT_NONCE: &str = "rOprNGfwEbeRWgbNEkqO";
+    const SERVER_FIRST: &[u8] =
+        b"r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &[u8] = b"c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &[u8] = b"v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    #[tokio::test]
+    async fn scram_sha_256_exchange() {
+        let mechanism = ScramSha256Mechanism::new("user", "pencil");
+        let (initial_response, mut session) = mechanism.start_with_nonce(CLIENT_NONCE.to_owned());
+        assert_eq!(initial_response, b"n,,n=user,r=rOprNGfwEbeRWgbNEkqO");
+
+        let client_final = session.evaluate_challenge(Some(SERVER_FIRST)).await;
+        assert_eq!(client_final.unwrap().as_deref(), Some(CLIENT_FINAL));
+        session.success(Some(SERVER_FINAL)).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn scram_sha_256_rejects_invalid_server_signature() {
+        let mechanism = ScramSha256Mechanism::new("user", "pencil");
+        let (_, mut session) = mechanism.start_with_nonce(CLIENT_NONCE.to_owned());
+        session
+            .evaluate_challenge(Some(SERVER_FIRST))
+            .await
+            .unwrap();
+
+        assert!(session
+            .success(Some(b"v=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA="))
+            .await
+            .is_err());
+        assert!(session.success(None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn scram_sha_256_rejects_foreign_nonce() {
+        let mechanism = ScramSha256Mechanism::new("user", "pencil");
+        let (_, mut session) = mechanism.start_with_nonce(CLIENT_NONCE.to_owned());
+        assert!(session
+            .evaluate_challenge(Some(b"r=somethingelse,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096"))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn scram_sha_256_rejects_iteration_counts_out_of_range() {
+        let mechanism = ScramSha256Mechanism::new("user", "pencil");
+        for iterations in ["4095", "1000001", "4294967295"] {
+            let (_, mut session) = mechanism.start_with_nonce(CLIENT_NONCE.to_owned());
+            let server_first = format!(
+                "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,s=W22ZaJ0SNY7soEsUEjb6gQ==,i={}",
+                iterations
+            );
+            assert!(session
+                .evaluate_challenge(Some(server_first.as_bytes()))
+                .await
+                .is_err());
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //! prepared statements automatically and reuses them when possible.
 
 use crate::errors::{ExecutionError, PagerExecutionError, PrepareError};
+use crate::frame::response::event::{SchemaChangeEvent, SchemaChangeType};
 use crate::response::query_result::QueryResult;
 use crate::response::{PagingState, PagingStateResponse};
 use crate::routing::partitioner::PartitionerName;
@@ -10,14 +11,20 @@ use crate::statement::prepared::PreparedStatement;
 use crate::statement::unprepared::Statement;
 use bytes::Bytes;
 use dashmap::DashMap;
-use futures::future::try_join_all;
-use scylla_cql::frame::response::result::{PreparedMetadata, ResultMetadata};
+use futures::future::{try_join_all, RemoteHandle};
+use futures::FutureExt;
+use scylla_cql::frame::response::result::{
+    CollectionType, ColumnSpec, ColumnType, PreparedMetadata, ResultMetadata,
+};
 use scylla_cql::serialize::batch::BatchValues;
 use scylla_cql::serialize::row::SerializeRow;
 use std::collections::hash_map::RandomState;
 use std::fmt;
 use std::hash::BuildHasher;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::broadcast;
+use tracing::{debug, warn};
 
 use crate::client::pager::QueryPager;
 use crate::client::session::Session;
@@ -33,6 +40,98 @@ struct RawPreparedStatementData {
     metadata: PreparedMetadata,
     result_metadata: Arc<ResultMetadata<'static>>,
     partitioner_name: PartitionerName,
+    /// Value of [CacheCounters::usage_clock] at the last use of the statement.
+    last_used: AtomicU64,
+}
+
+impl RawPreparedStatementData {
+    fn new(prepared: &PreparedStatement, last_used: u64) -> Self {
+        Self {
+            id: prepared.get_id().clone(),
+            is_confirmed_lwt: prepared.is_confirmed_lwt(),
+            metadata: prepared.get_prepared_metadata().clone(),
+            result_metadata: prepared.get_result_metadata().clone(),
+            partitioner_name: prepared.get_partitioner_name().clone(),
+            last_used: AtomicU64::new(last_used),
+        }
+    }
+
+    /// Specifications of both the bound values and the result columns of the statement.
+    fn col_specs(&self) -> impl Iterator<Item = &ColumnSpec<'static>> {
+        self.metadata
+            .col_specs
+            .iter()
+            .chain(self.result_metadata.col_specs())
+    }
+}
+
+/// Identifies a cached statement. The same statement string prepared in different
+/// keyspaces (see [Statement::set_keyspace]) yields different prepared statements.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct StatementCacheKey {
+    contents: String,
+    keyspace: Option<String>,
+}
+
+impl StatementCacheKey {
+    fn new(statement: &Statement) -> Self {
+        Self {
+            contents: statement.contents.clone(),
+            keyspace: statement.get_keyspace().map(ToOwned::to_owned),
+        }
+    }
+
+    fn to_statement(&self) -> Statement {
+        let mut statement = Statement::new(self.contents.clone());
+        if let Some(keyspace) = &self.keyspace {
+            statement.set_keyspace(keyspace.clone());
+        }
+        statement
+    }
+}
+
+type PreparedStatementCache<S> = DashMap<StatementCacheKey, RawPreparedStatementData, S>;
+
+/// Statistics of the prepared statement cache of a [CachingSession],
+/// counted since the session was created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CacheMetrics {
+    /// Number of statements found in the cache.
+    pub hits: u64,
+    /// Number of statements which were not found in the cache, and were prepared.
+    pub misses: u64,
+    /// Number of statements removed from the cache to make room for new ones.
+    pub evictions: u64,
+    /// Number of statements removed from the cache because of schema changes,
+    /// see [CachingSessionBuilder::reprepare_on_schema_change].
+    pub invalidations: u64,
+}
+
+#[derive(Debug, Default)]
+struct CacheCounters {
+    /// Logical clock, advanced on every use of a cached statement.
+    /// Used to find the least recently used statement.
+    usage_clock: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    invalidations: AtomicU64,
+}
+
+impl CacheCounters {
+    fn tick(&self) -> u64 {
+        self.usage_clock.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    fn metrics(&self) -> CacheMetrics {
+        CacheMetrics {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+        }
+    }
 }
 
 /// Provides auto caching while executing queries
@@ -42,11 +141,15 @@ where
 {
     session: Arc<Session>,
     /// The prepared statement cache size
-    /// If a prepared statement is added while the limit is reached, the oldest prepared statement
-    /// is removed from the cache
+    /// If a prepared statement is added while the limit is reached, the least recently used
+    /// prepared statement is removed from the cache
     max_capacity: usize,
-    cache: DashMap<String, RawPreparedStatementData, S>,
+    cache: Arc<PreparedStatementCache<S>>,
+    counters: Arc<CacheCounters>,
     use_cached_metadata: bool,
+    /// Handle of the task which re-prepares cached statements after schema changes, if enabled.
+    /// Dropping it stops the task.
+    _repreparation_handle: Option<RemoteHandle<()>>,
 }
 
 impl<S> fmt::Debug for CachingSession<S>
@@ -58,6 +161,7 @@ where
             .field("session", &self.session)
             .field("max_capacity", &self.max_capacity)
             .field("cache", &self.cache)
+            .field("metrics", &self.counters.metrics())
             .finish()
     }
 }
@@ -72,7 +176,9 @@ where
             session: Arc::new(session),
             max_capacity: cache_size,
             cache: Default::default(),
+            counters: Default::default(),
             use_cached_metadata: false,
+            _repreparation_handle: None,
         }
     }
 }
@@ -87,8 +193,10 @@ where
         Self {
             session: Arc::new(session),
             max_capacity: cache_size,
-            cache: DashMap::with_hasher(hasher),
+            cache: Arc::new(DashMap::with_hasher(hasher)),
+            counters: Default::default(),
             use_cached_metadata: false,
+            _repreparation_handle: None,
         }
     }
 }
@@ -202,49 +310,34 @@ where
     ) -> Result<PreparedStatement, PrepareError> {
         let query = query.into();
 
-        if let Some(raw) = self.cache.get(&query.contents) {
-            let page_size = query.get_validated_page_size();
+        let cache_key = StatementCacheKey::new(&query);
+        if let Some(raw) = self.cache.get(&cache_key) {
+            self.counters.hits.fetch_add(1, Ordering::Relaxed);
+            raw.last_used.store(self.counters.tick(), Ordering::Relaxed);
             let mut stmt = PreparedStatement::new(
                 raw.id.clone(),
                 raw.is_confirmed_lwt,
                 raw.metadata.clone(),
                 raw.result_metadata.clone(),
-                query.contents,
-                page_size,
-                query.config,
+                query,
             );
             stmt.set_partitioner_name(raw.partitioner_name.clone());
             stmt.set_use_cached_result_metadata(self.use_cached_metadata);
             Ok(stmt)
         } else {
-            let query_contents = query.contents.clone();
+            self.counters.misses.fetch_add(1, Ordering::Relaxed);
             let prepared = {
                 let mut stmt = self.session.prepare(query).await?;
                 stmt.set_use_cached_result_metadata(self.use_cached_metadata);
                 stmt
             };
 
-            if self.max_capacity == self.cache.len() {
-                // Cache is full, remove the first entry
-                // Don't hold a reference into the map (that's why the to_string() is called)
-                // This is because the documentation of the remove fn tells us that it may deadlock
-                // when holding some sort of reference into the map
-                let query = self.cache.iter().next().map(|c| c.key().to_string());
+            evict_least_recently_used(&self.cache, self.max_capacity, &self.counters);
 
-                // Don't inline this: https://stackoverflow.com/questions/69873846/an-owned-value-is-still-references-somehow
-                if let Some(q) = query {
-                    self.cache.remove(&q);
-                }
-            }
-
-            let raw = RawPreparedStatementData {
-                id: prepared.get_id().clone(),
-                is_confirmed_lwt: prepared.is_confirmed_lwt(),
-                metadata: prepared.get_prepared_metadata().clone(),
-                result_metadata: prepared.get_result_metadata().clone(),
-                partitioner_name: prepared.get_partitioner_name().clone(),
-            };
-            self.cache.insert(query_contents, raw);
+            self.cache.insert(
+                cache_key,
+                RawPreparedStatementData::new(&prepared, self.counters.tick()),
+            );
 
             Ok(prepared)
         }
@@ -255,12 +348,50 @@ where
         self.max_capacity
     }
 
+    /// Retrieves the number of statements currently in the prepared statements cache.
+    pub fn get_cache_len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Retrieves the statistics of the prepared statements cache.
+    pub fn get_cache_metrics(&self) -> CacheMetrics {
+        self.counters.metrics()
+    }
+
     /// Retrieves the underlying [Session] instance.
     pub fn get_session(&self) -> &Session {
         &self.session
     }
 }
 
+/// Makes room for a new statement in a full cache, by removing the least recently used ones.
+///
+/// Finding them requires a scan of the whole cache, so it is linear in its capacity.
+fn evict_least_recently_used<S>(
+    cache: &PreparedStatementCache<S>,
+    max_capacity: usize,
+    counters: &CacheCounters,
+) where
+    S: BuildHasher + Clone,
+{
+    while !cache.is_empty() && cache.len() >= max_capacity {
+        // Don't hold a reference into the map while removing from it.
+        // This is because the documentation of the remove fn tells us that it may deadlock
+        // when holding some sort of reference into the map
+        let least_recently_used = cache
+            .iter()
+            .min_by_key(|entry| entry.last_used.load(Ordering::Relaxed))
+            .map(|entry| entry.key().clone());
+
+        // Don't inline this: https://stackoverflow.com/questions/69873846/an-owned-value-is-still-references-somehow
+        if let Some(query) = least_recently_used {
+            if cache.remove(&query).is_some() {
+                counters.evictions.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
+
 /// The default cache capacity set on the [CachingSessionBuilder].
 /// Can be changed using [CachingSessionBuilder::max_capacity].
 pub const DEFAULT_MAX_CAPACITY: usize = 128;
@@ -298,6 +429,7 @@ where
     max_capacity: usize,
     hasher: S,
     use_cached_metadata: bool,
+    reprepare_on_schema_change: bool,
 }
 
 impl CachingSessionBuilder<RandomState> {
@@ -315,6 +447,7 @@ impl CachingSessionBuilder<RandomState> {
             max_capacity: DEFAULT_MAX_CAPACITY,
             hasher: RandomState::default(),
             use_cached_metadata: false,
+            reprepare_on_schema_change: false,
         }
     }
 }
@@ -348,17 +481,244 @@ where
         self
     }
 
+    /// Keep cached statements up to date with schema changes.
+    ///
+    /// If true, the [CachingSession] listens to schema change events and,
+    /// after a table or a user-defined type used by some cached statements is altered,
+    /// re-prepares these statements in the background, once the schema is in agreement.
+    /// This way the first request after a DDL statement does not pay
+    /// the re-preparation latency, and does not use stale result metadata
+    /// (which matters if [use_cached_result_metadata](Self::use_cached_result_metadata)
+    /// is enabled). Statements using dropped tables, types or keyspaces are evicted,
+    /// and counted as [invalidations](CacheMetrics::invalidations).
+    ///
+    /// Only schema changes pushed by the cluster after the [CachingSession]
+    /// is built are taken into account. The statements are re-prepared by a task
+    /// spawned on the current Tokio runtime by [build](Self::build).
+    ///
+    /// This option is false by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scylla::client::session::Session;
+    /// # use scylla::client::caching_session::{CachingSession, CachingSessionBuilder};
+    /// # async fn example(session: Session) -> Result<(), Box<dyn std::error::Error>> {
+    /// let caching_session: CachingSession = CachingSessionBuilder::new(session)
+    ///     .reprepare_on_schema_change(true)
+    ///     .build();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn reprepare_on_schema_change(mut self, enabled: bool) -> Self {
+        self.reprepare_on_schema_change = enabled;
+        self
+    }
+}
+
+impl<S> CachingSessionBuilder<S>
+where
+    S: Clone + BuildHasher + Send + Sync + 'static,
+{
     /// Finishes configuration of [CachingSession].
+    ///
+    /// # Panics
+    ///
+    /// Panics if [reprepare_on_schema_change](Self::reprepare_on_schema_change)
+    /// is enabled and this method is called outside of a Tokio runtime.
     pub fn build(self) -> CachingSession<S> {
+        let cache = Arc::new(DashMap::with_hasher(self.hasher));
+        let counters = Arc::new(CacheCounters::default());
+        let repreparation_handle = self.reprepare_on_schema_change.then(|| {
+            spawn_repreparation(
+                Arc::clone(&self.session),
+                Arc::clone(&cache),
+                Arc::clone(&counters),
+            )
+        });
         CachingSession {
             session: self.session,
             max_capacity: self.max_capacity,
-            cache: DashMap::with_hasher(self.hasher),
+            cache,
+            counters,
             use_cached_metadata: self.use_cached_metadata,
+            _repreparation_handle: repreparation_handle,
         }
     }
 }
 
+fn spawn_repreparation<S>(
+    session: Arc<Session>,
+    cache: Arc<PreparedStatementCache<S>>,
+    counters: Arc<CacheCounters>,
+) -> RemoteHandle<()>
+where
+    S: Clone + BuildHasher + Send + Sync + 'static,
+{
+    let schema_changes = session.subscribe_to_schema_changes();
+    let (fut, handle) =
+        reprepare_on_schema_changes(session, cache, counters, schema_changes).remote_handle();
+    tokio::spawn(fut);
+    handle
+}
+
+async fn reprepare_on_schema_changes<S>(
+    session: Arc<Session>,
+    cache: Arc<PreparedStatementCache<S>>,
+    counters: Arc<CacheCounters>,
+    mut schema_changes: broadcast::Receiver<Arc<SchemaChangeEvent>>,
+) where
+    S: Clone + BuildHasher,
+{
+    loop {
+        // Wait for a schema change, then take all the ones that arrived in the meantime.
+        // If some were missed, we don't know what changed, so everything is re-prepared.
+        let mut changes = Vec::new();
+        let mut missed_changes = false;
+        match schema_changes.recv().await {
+            Ok(change) => changes.push(change),
+            Err(broadcast::error::RecvError::Lagged(_)) => missed_changes = true,
+            Err(broadcast::error::RecvError::Closed) => return,
+        }
+        loop {
+            match schema_changes.try_recv() {
+                Ok(change) => changes.push(change),
+                Err(broadcast::error::TryRecvError::Lagged(_)) => missed_changes = true,
+                Err(_) => break,
+            }
+        }
+
+        let mut to_evict = Vec::new();
+        let mut to_reprepare = Vec::new();
+        for entry in cache.iter() {
+            let impact = if missed_changes {
+                SchemaChangeImpact::Reprepare
+            } else {
+                changes
+                    .iter()
+                    .map(|change| schema_change_impact(entry.value(), change))
+                    .max()
+                    .unwrap_or(SchemaChangeImpact::None)
+            };
+            match impact {
+                SchemaChangeImpact::None => (),
+                SchemaChangeImpact::Reprepare => to_reprepare.push(entry.key().clone()),
+                SchemaChangeImpact::Evict => to_evict.push(entry.key().clone()),
+            }
+        }
+
+        // As in `add_prepared_statement_owned`, no reference into the map
+        // may be held while removing from it.
+        for query in to_evict {
+            debug!("Evicting statement {query:?} from the cache after a schema change");
+            if cache.remove(&query).is_some() {
+                counters.invalidations.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        if to_reprepare.is_empty() {
+            continue;
+        }
+
+        // Nodes which have not applied the change yet would prepare
+        // the statements against the old schema.
+        if let Err(err) = session.await_schema_agreement().await {
+            warn!("Failed to await schema agreement before re-preparing cached statements: {err}");
+        }
+
+        for query in to_reprepare {
+            match session.prepare(query.to_statement()).await {
+                Ok(prepared) => {
+                    // The statement might have been evicted in the meantime.
+                    if let Some(mut raw) = cache.get_mut(&query) {
+                        let last_used = raw.last_used.load(Ordering::Relaxed);
+                        *raw = RawPreparedStatementData::new(&prepared, last_used);
+                    }
+                }
+                Err(err) => {
+                    // Let the next execution of the statement prepare it (and report the error).
+                    warn!("Failed to re-prepare statement {query:?} after a schema change: {err}");
+                    if cache.remove(&query).is_some() {
+                        counters.invalidations.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// What a schema change means for a cached statement, from the least to the most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SchemaChangeImpact {
+    None,
+    Reprepare,
+    Evict,
+}
+
+fn schema_change_impact(
+    raw: &RawPreparedStatementData,
+    change: &SchemaChangeEvent,
+) -> SchemaChangeImpact {
+    let (change_type, is_affected) = match change {
+        SchemaChangeEvent::TableChange {
+            change_type,
+            keyspace_name,
+            object_name,
+        } => (
+            change_type,
+            raw.col_specs().any(|spec| {
+                spec.table_spec().ks_name() == keyspace_name
+                    && spec.table_spec().table_name() == object_name
+            }),
+        ),
+        SchemaChangeEvent::TypeChange {
+            change_type,
+            keyspace_name,
+            type_name,
+        } => (
+            change_type,
+            raw.col_specs()
+                .any(|spec| uses_udt(spec.typ(), keyspace_name, type_name)),
+        ),
+        // Dropping a keyspace drops its tables, but altering it
+        // does not change the metadata of the statements.
+        SchemaChangeEvent::KeyspaceChange {
+            change_type: change_type @ SchemaChangeType::Dropped,
+            keyspace_name,
+        } => (
+            change_type,
+            raw.col_specs()
+                .any(|spec| spec.table_spec().ks_name() == keyspace_name),
+        ),
+        _ => return SchemaChangeImpact::None,
+    };
+
+    match change_type {
+        _ if !is_affected => SchemaChangeImpact::None,
+        SchemaChangeType::Updated => SchemaChangeImpact::Reprepare,
+        SchemaChangeType::Dropped => SchemaChangeImpact::Evict,
+        _ => SchemaChangeImpact::None,
+    }
+}
+
+fn uses_udt(typ: &ColumnType, keyspace_name: &str, type_name: &str) -> bool {
+    let uses = |typ| uses_udt(typ, keyspace_name, type_name);
+    match typ {
+        ColumnType::UserDefinedType { definition, .. } => {
+            (definition.keyspace == keyspace_name && definition.name == type_name)
+                || definition.field_types.iter().any(|(_, typ)| uses(typ))
+        }
+        ColumnType::Collection { typ, .. } => match typ {
+            CollectionType::List(typ) | CollectionType::Set(typ) => uses(typ),
+            CollectionType::Map(key, value) => uses(key) || uses(value),
+            _ => false,
+        },
+        ColumnType::Vector { typ, .. } => uses(typ),
+        ColumnType::Tuple(types) => types.iter().any(uses),
+        _ => false,
+    }
+}
+
 impl<S> CachingSessionBuilder<S>
 where
     S: Clone + BuildHasher,
@@ -410,12 +770,14 @@ where
             max_capacity,
             hasher: _,
             use_cached_metadata,
+            reprepare_on_schema_change,
         } = self;
         CachingSessionBuilder {
             session,
             max_capacity,
             hasher,
             use_cached_metadata,
+            reprepare_on_schema_change,
         }
     }
 }
@@ -443,9 +805,19 @@ mod tests {
     use std::collections::{BTreeSet, HashMap};
     use std::hash::{BuildHasher, RandomState};
     use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicU64, Ordering};
     use std::sync::Arc;
 
-    use super::CachingSession;
+    use super::{
+        evict_least_recently_used, schema_change_impact, CacheCounters, CachingSession,
+        PreparedStatementCache, RawPreparedStatementData, SchemaChangeImpact, StatementCacheKey,
+    };
+    use crate::frame::response::event::{SchemaChangeEvent, SchemaChangeType};
+    use bytes::Bytes;
+    use scylla_cql::frame::response::result::{
+        CollectionType, ColumnSpec, ColumnType, NativeType, PreparedMetadata, ResultMetadata,
+        TableSpec, UserDefinedType,
+    };
 
     async fn new_for_test(with_tablet_support: bool) -> Session {
         let session = create_new_session_builder()
@@ -500,7 +872,7 @@ mod tests {
     }
 
     /// Test that when the cache is full and a different query comes in, that query will be added
-    /// to the cache and a random query is removed
+    /// to the cache and the least recently used query is removed
     #[tokio::test]
     async fn test_full() {
         setup_tracing();
@@ -518,21 +890,29 @@ mod tests {
             .add_prepared_statement(&middle_query.into())
             .await
             .unwrap();
+        // Use the first query again, so that the middle one is the least recently used.
+        session
+            .add_prepared_statement(&first_query.into())
+            .await
+            .unwrap();
         session
             .add_prepared_statement(&last_query.into())
             .await
             .unwrap();
 
-        assert_eq!(2, session.cache.len());
-
-        // This query should be in the cache
-        assert!(session.cache.get(last_query).is_some());
+        assert_eq!(2, session.get_cache_len());
 
-        // Either the first or middle query should be removed
-        let first_query_removed = session.cache.get(first_query).is_none();
-        let middle_query_removed = session.cache.get(middle_query).is_none();
+        let cache_key = |query: &str| StatementCacheKey::new(&query.into());
+        assert!(session.cache.get(&cache_key(first_query)).is_some());
+        assert!(session.cache.get(&cache_key(middle_query)).is_none());
+        assert!(session.cache.get(&cache_key(last_query)).is_some());
 
-        assert!(first_query_removed || middle_query_removed);
+        // The insert executed by `create_caching_session` is counted, too.
+        let metrics = session.get_cache_metrics();
+        assert_eq!(metrics.hits, 1);
+        assert_eq!(metrics.misses, 4);
+        assert_eq!(metrics.evictions, 1);
+        assert_eq!(metrics.invalidations, 0);
     }
 
     /// Checks that the same prepared statement is reused when executing the same query twice
@@ -881,6 +1261,139 @@ mod tests {
         assert_eq!(h1.hash_one(TO_BE_HASHED), h2.hash_one(TO_BE_HASHED));
     }
 
+    #[test]
+    fn test_schema_change_impact() {
+        let address = ColumnType::UserDefinedType {
+            frozen: true,
+            definition: Arc::new(UserDefinedType {
+                name: "address".into(),
+                keyspace: "ks".into(),
+                field_types: vec![("street".into(), ColumnType::Native(NativeType::Text))],
+            }),
+        };
+        let raw = RawPreparedStatementData {
+            id: Bytes::new(),
+            is_confirmed_lwt: false,
+            metadata: PreparedMetadata {
+                flags: 0,
+                col_count: 1,
+                pk_indexes: Vec::new(),
+                col_specs: vec![ColumnSpec::owned(
+                    "addresses".to_owned(),
+                    ColumnType::Collection {
+                        frozen: false,
+                        typ: CollectionType::List(Box::new(address)),
+                    },
+                    TableSpec::owned("ks".to_owned(), "users".to_owned()),
+                )],
+            },
+            result_metadata: Arc::new(ResultMetadata::mock_empty()),
+            partitioner_name: PartitionerName::default(),
+            last_used: AtomicU64::new(0),
+        };
+
+        let table_change = |change_type, table: &str| SchemaChangeEvent::TableChange {
+            change_type,
+            keyspace_name: "ks".to_owned(),
+            object_name: table.to_owned(),
+        };
+        let type_change = |change_type, type_name: &str| SchemaChangeEvent::TypeChange {
+            change_type,
+            keyspace_name: "ks".to_owned(),
+            type_name: type_name.to_owned(),
+        };
+        let keyspace_change = |change_type| SchemaChangeEvent::KeyspaceChange {
+            change_type,
+            keyspace_name: "ks".to_owned(),
+        };
+
+        let cases = [
+            (
+                table_change(SchemaChangeType::Updated, "users"),
+                SchemaChangeImpact::Reprepare,
+            ),
+            (
+                table_change(SchemaChangeType::Dropped, "users"),
+                SchemaChangeImpact::Evict,
+            ),
+            (
+                table_change(SchemaChangeType::Updated, "orders"),
+                SchemaChangeImpact::None,
+            ),
+            (
+                type_change(SchemaChangeType::Updated, "address"),
+                SchemaChangeImpact::Reprepare,
+            ),
+            (
+                type_change(SchemaChangeType::Created, "address"),
+                SchemaChangeImpact::None,
+            ),
+            (
+                type_change(SchemaChangeType::Updated, "phone"),
+                SchemaChangeImpact::None,
+            ),
+            (
+                keyspace_change(SchemaChangeType::Updated),
+                SchemaChangeImpact::None,
+            ),
+            (
+                keyspace_change(SchemaChangeType::Dropped),
+                SchemaChangeImpact::Evict,
+            ),
+        ];
+        for (change, expected_impact) in cases {
+            assert_eq!(
+                schema_change_impact(&raw, &change),
+                expected_impact,
+                "{change:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_evict_least_recently_used() {
+        let raw = |last_used| RawPreparedStatementData {
+            id: Bytes::new(),
+            is_confirmed_lwt: false,
+            metadata: PreparedMetadata {
+                flags: 0,
+                col_count: 0,
+                pk_indexes: Vec::new(),
+                col_specs: Vec::new(),
+            },
+            result_metadata: Arc::new(ResultMetadata::mock_empty()),
+            partitioner_name: PartitionerName::default(),
+            last_used: AtomicU64::new(last_used),
+        };
+        let cache_key = |query: &str| StatementCacheKey::new(&query.into());
+
+        let cache = PreparedStatementCache::<RandomState>::default();
+        cache.insert(cache_key("a"), raw(3));
+        cache.insert(cache_key("b"), raw(1));
+        cache.insert(cache_key("c"), raw(2));
+        let counters = CacheCounters::default();
+
+        // There is room for a new statement.
+        evict_least_recently_used(&cache, 4, &counters);
+        assert_eq!(cache.len(), 3);
+
+        evict_least_recently_used(&cache, 3, &counters);
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&cache_key("b")).is_none());
+
+        // The capacity might have been lower than the number of cached statements.
+        cache
+            .get(&cache_key("c"))
+            .unwrap()
+            .last_used
+            .store(4, Ordering::Relaxed);
+        evict_least_recently_used(&cache, 1, &counters);
+        assert!(cache.is_empty());
+
+        assert_eq!(counters.metrics().evictions, 3);
+        assert_eq!(counters.metrics().hits, 0);
+    }
+
     /// Tests that [CachingSessionBuilder] passes its config options to the built [CachingSession].
     #[tokio::test]
     async fn test_builder() {
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio::sync::Notify;
+
+/// A token which allows cancelling requests executed with statements it is attached to.
+///
+/// Attach the token to a statement with e.g.
+/// [Statement::set_cancellation_token()](crate::statement::unprepared::Statement::set_cancellation_token).
+/// Once [CancellationToken::cancel()] is called, requests which are being executed
+/// with the statement stop waiting for their responses and fail with
+/// [ExecutionError::Cancelled](crate::errors::ExecutionError::Cancelled),
+/// and pagers stop fetching further pages. Requests started afterwards fail immediately.
+///
+/// Note that the CQL protocol has no way to abort a request on the server side;
+/// cancellation only prevents the driver from waiting for responses and from
+/// sending further requests (retries, speculative executions or subsequent pages).
+///
+/// The token is cheaply clonable; all clones share the cancellation state.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<CancellationState>);
+
+#[derive(Debug, Default)]
+struct CancellationState {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancellationToken {
+    /// Creates a new token, which is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels requests which use this token. Calling it more than once has no effect.
+    pub fn cancel(&self) {
+        self.0.cancelled.store(true, Ordering::Release);
+        self.0.notify.notify_waiters();
+    }
+
+    /// Checks whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Completes once the token is cancelled.
+    pub async fn cancelled(&self) {
+        let notified = self.0.notify.notified();
+        tokio::pin!(notified);
+        // Register for notifications before checking the flag,
+        // so that a concurrent `cancel()` is not missed.
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Completes once the token, if any, is cancelled.
+pub(crate) async fn cancelled(token: Option<&CancellationToken>) {
+    match token {
+        Some(token) => token.cancelled().await,
+        None => std::future::pending().await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::test_utils::setup_tracing;
+
+    use super::CancellationToken;
+
+    #[tokio::test]
+    async fn cancellation_wakes_up_waiters() {
+        setup_tracing();
+        let token = CancellationToken::new();
+        assert!(!token.is_cancelled());
+
+        let waiter = tokio::spawn({
+            let token = token.clone();
+            async move { token.cancelled().await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        token.cancel();
+        waiter.await.unwrap();
+        assert!(token.is_cancelled());
+
+        // Waiting on an already cancelled token completes immediately.
+        tokio::time::timeout(Duration::from_secs(1), token.cancelled())
+            .await
+            .unwrap();
+    }
+}
@@ -167,6 +167,7 @@ use std::{fmt::Debug, sync::Arc, time::Duration};
 use arc_swap::ArcSwap;
 use scylla_cql::{frame::types::SerialConsistency, Consistency};
 
+use crate::client::interceptor::RequestInterceptor;
 use crate::policies::load_balancing::LoadBalancingPolicy;
 use crate::policies::retry::RetryPolicy;
 use crate::policies::speculative_execution::SpeculativeExecutionPolicy;
@@ -208,6 +209,7 @@ pub(crate) mod defaults {
                 load_balancing_policy: load_balancing_policy(),
                 retry_policy: retry_policy(),
                 speculative_execution_policy: speculative_execution_policy(),
+                request_interceptors: Vec::new(),
             }
         }
     }
@@ -237,6 +239,7 @@ pub struct ExecutionProfileBuilder {
     load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
     retry_policy: Option<Arc<dyn RetryPolicy>>,
     speculative_execution_policy: Option<Option<Arc<dyn SpeculativeExecutionPolicy>>>,
+    request_interceptors: Vec<Arc<dyn RequestInterceptor>>,
 }
 
 impl ExecutionProfileBuilder {
@@ -350,6 +353,39 @@ impl ExecutionProfileBuilder {
         self
     }
 
+    /// Registers a request interceptor, which observes the execution of requests
+    /// executed with this profile, including each of their attempts.
+    /// Interceptors registered on the profile are called after the ones registered
+    /// on the session, in the order of registration, except for their completion hooks,
+    /// which are called in the reverse order.
+    ///
+    /// See the [interceptor](crate::client::interceptor) module for details.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::client::execution_profile::ExecutionProfile;
+    /// # use scylla::client::interceptor::{InterceptedRequest, RequestInterceptor};
+    /// # use std::sync::Arc;
+    /// #[derive(Debug)]
+    /// struct RequestLogger;
+    ///
+    /// impl RequestInterceptor for RequestLogger {
+    ///     fn on_request_start(&self, request: InterceptedRequest<'_>) {
+    ///         if let InterceptedRequest::Statement(statement) = request {
+    ///             println!("Executing {}", statement.contents);
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let profile: ExecutionProfile = ExecutionProfile::builder()
+    ///     .request_interceptor(Arc::new(RequestLogger))
+    ///     .build();
+    /// ```
+    pub fn request_interceptor(mut self, interceptor: Arc<dyn RequestInterceptor>) -> Self {
+        self.request_interceptors.push(interceptor);
+        self
+    }
+
     /// Builds the ExecutionProfile after setting all the options.
     ///
     /// # Example
@@ -380,6 +416,7 @@ impl ExecutionProfileBuilder {
             speculative_execution_policy: self
                 .speculative_execution_policy
                 .unwrap_or_else(defaults::speculative_execution_policy),
+            request_interceptors: self.request_interceptors,
         }))
     }
 }
@@ -409,6 +446,7 @@ pub(crate) struct ExecutionProfileInner {
     pub(crate) load_balancing_policy: Arc<dyn LoadBalancingPolicy>,
     pub(crate) retry_policy: Arc<dyn RetryPolicy>,
     pub(crate) speculative_execution_policy: Option<Arc<dyn SpeculativeExecutionPolicy>>,
+    pub(crate) request_interceptors: Vec<Arc<dyn RequestInterceptor>>,
 }
 
 impl ExecutionProfileInner {
@@ -421,6 +459,7 @@ impl ExecutionProfileInner {
             load_balancing_policy: Some(self.load_balancing_policy.clone()),
             retry_policy: Some(self.retry_policy.clone()),
             speculative_execution_policy: Some(self.speculative_execution_policy.clone()),
+            request_interceptors: self.request_interceptors.clone(),
         }
     }
 }
@@ -439,6 +478,7 @@ impl ExecutionProfile {
             load_balancing_policy: None,
             retry_policy: None,
             speculative_execution_policy: None,
+            request_interceptors: Vec::new(),
         }
     }
 
@@ -489,6 +529,11 @@ impl ExecutionProfile {
     pub fn get_speculative_execution_policy(&self) -> Option<&Arc<dyn SpeculativeExecutionPolicy>> {
         self.0.speculative_execution_policy.as_ref()
     }
+
+    /// Gets request interceptors associated with this profile.
+    pub fn get_request_interceptors(&self) -> &[Arc<dyn RequestInterceptor>] {
+        &self.0.request_interceptors
+    }
 }
 
 /// A handle that points to an ExecutionProfile.
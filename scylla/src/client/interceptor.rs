@@ -0,0 +1,478 @@
+//! Interceptors, which let the application hook into the execution of requests
+//! by the [`Session`](crate::client::session::Session).
+//!
+//! There are two kinds of interceptors:
+//! - [`StatementInterceptor`]s inspect and rewrite requests before they are executed,
+//!   and observe their outcomes afterwards. They are registered on the session with
+//!   [`SessionBuilder::interceptor`](crate::client::session_builder::SessionBuilder::interceptor).
+//! - [`RequestInterceptor`]s observe the execution of (already rewritten) requests,
+//!   including each attempt of sending them to a node. They can be registered on the session with
+//!   [`SessionBuilder::request_interceptor`](crate::client::session_builder::SessionBuilder::request_interceptor),
+//!   or on an execution profile with
+//!   [`ExecutionProfileBuilder::request_interceptor`](crate::client::execution_profile::ExecutionProfileBuilder::request_interceptor),
+//!   in which case they only apply to requests executed with that profile.
+//!   The session's interceptors come before the execution profile's ones.
+//!
+//! Both kinds of interceptors form a stack, in which the first registered interceptor
+//! is the outermost one: the hooks called before and during the execution
+//! (`intercept_*`, [`RequestInterceptor::on_request_start`] and [`RequestInterceptor::on_attempt`])
+//! are called in the order of registration, while the completion hooks
+//! ([`StatementInterceptor::on_completion`] and [`RequestInterceptor::on_complete`])
+//! are called in the reverse order.
+
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
+
+use scylla_cql::serialize::row::SerializedValues;
+use scylla_cql::Consistency;
+
+use crate::client::execution_profile::ExecutionProfileInner;
+use crate::cluster::NodeRef;
+use crate::errors::{ExecutionError, RequestAttemptError};
+use crate::response::query_result::QueryResult;
+use crate::routing::Shard;
+use crate::statement::batch::{Batch, BatchStatement};
+use crate::statement::prepared::PreparedStatement;
+use crate::statement::unprepared::Statement;
+
+/// Inspects and rewrites requests executed by the session.
+///
+/// All methods have no-op default implementations, so an interceptor
+/// only needs to implement the hooks it is interested in.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::interceptor::StatementInterceptor;
+/// # use scylla::statement::unprepared::Statement;
+/// /// Stamps every statement with a comment, so that it can be correlated
+/// /// with the application's logs on the server side.
+/// #[derive(Debug)]
+/// struct CommentStamper {
+///     service: String,
+/// }
+///
+/// impl StatementInterceptor for CommentStamper {
+///     fn intercept_statement(&self, statement: &mut Statement) {
+///         statement.contents = format!("/* {} */ {}", self.service, statement.contents);
+///     }
+/// }
+/// ```
+pub trait StatementInterceptor: Debug + Send + Sync {
+    /// Called with an unprepared statement before it is executed or prepared.
+    ///
+    /// Applies to [`Session::query_unpaged`](crate::client::session::Session::query_unpaged),
+    /// [`Session::query_single_page`](crate::client::session::Session::query_single_page),
+    /// [`Session::query_iter`](crate::client::session::Session::query_iter) and
+    /// [`Session::prepare`](crate::client::session::Session::prepare), so rewriting
+    /// the statement's text here also affects the prepared statements created from it.
+    fn intercept_statement(&self, _statement: &mut Statement) {}
+
+    /// Called with a prepared statement and its serialized bound values before it is executed.
+    ///
+    /// The statement itself cannot be changed anymore, but the values can be replaced,
+    /// e.g. with ones built using [`SerializedValues::add_value`].
+    fn intercept_prepared(&self, _prepared: &PreparedStatement, _values: &mut SerializedValues) {}
+
+    /// Called with a batch before it is executed.
+    ///
+    /// Unprepared statements of the batch are also passed through
+    /// [`StatementInterceptor::intercept_statement`] before this hook is called.
+    fn intercept_batch(&self, _batch: &mut Batch) {}
+
+    /// Called with the (possibly rewritten) request and its outcome after it has been executed.
+    ///
+    /// Unlike the other hooks, it is called in the reverse order of registration.
+    ///
+    /// This is not called for requests executed with `Session::{query,execute}_iter`,
+    /// as they return a pager rather than a single result.
+    fn on_completion(
+        &self,
+        _request: InterceptedRequest<'_>,
+        _outcome: Result<&QueryResult, &ExecutionError>,
+    ) {
+    }
+}
+
+/// A request passed to [`StatementInterceptor::on_completion`] and to the hooks of [`RequestInterceptor`].
+#[derive(Clone, Copy)]
+#[non_exhaustive]
+pub enum InterceptedRequest<'a> {
+    /// An unprepared statement.
+    Statement(&'a Statement),
+    /// A prepared statement.
+    Prepared(&'a PreparedStatement),
+    /// A batch.
+    Batch(&'a Batch),
+}
+
+/// Observes the execution of requests by the session, including each attempt
+/// of sending a request to a node.
+///
+/// Applies to [`Session::query_unpaged`](crate::client::session::Session::query_unpaged),
+/// [`Session::query_single_page`](crate::client::session::Session::query_single_page),
+/// [`Session::execute_unpaged`](crate::client::session::Session::execute_unpaged),
+/// [`Session::execute_single_page`](crate::client::session::Session::execute_single_page)
+/// and [`Session::batch`](crate::client::session::Session::batch). The request passed to the hooks
+/// is the one rewritten by [`StatementInterceptor`]s, if there are any; use a
+/// [`StatementInterceptor`] to rewrite requests.
+///
+/// All methods have no-op default implementations, so an interceptor
+/// only needs to implement the hooks it is interested in.
+/// The hooks are called synchronously on the request's execution path,
+/// so they should be cheap.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::interceptor::{AttemptInfo, InterceptedRequest, RequestInterceptor};
+/// /// Logs failed attempts of executing unprepared statements.
+/// #[derive(Debug)]
+/// struct FailedAttemptLogger;
+///
+/// impl RequestInterceptor for FailedAttemptLogger {
+///     fn on_attempt(&self, request: InterceptedRequest<'_>, attempt: &AttemptInfo<'_>) {
+///         if let (InterceptedRequest::Statement(statement), Some(error)) = (request, attempt.error) {
+///             println!(
+///                 "{} failed on node {}: {}",
+///                 statement.contents, attempt.node.address, error
+///             );
+///         }
+///     }
+/// }
+/// ```
+pub trait RequestInterceptor: Debug + Send + Sync {
+    /// Called when the session starts executing the request,
+    /// after it has been validated and before the first attempt.
+    fn on_request_start(&self, _request: InterceptedRequest<'_>) {}
+
+    /// Called after each attempt of sending the request to a node, with the attempt's outcome.
+    ///
+    /// There may be multiple attempts per request, due to retries and speculative executions.
+    /// Attempts which could not be sent, e.g. because there was no working connection
+    /// to the node, are not reported.
+    fn on_attempt(&self, _request: InterceptedRequest<'_>, _attempt: &AttemptInfo<'_>) {}
+
+    /// Called with the outcome of the request, after all its attempts.
+    ///
+    /// Unlike the other hooks, it is called in the reverse order of registration.
+    ///
+    /// It is called exactly once for each request for which
+    /// [`RequestInterceptor::on_request_start`] has been called.
+    fn on_complete(
+        &self,
+        _request: InterceptedRequest<'_>,
+        _outcome: Result<&QueryResult, &ExecutionError>,
+    ) {
+    }
+}
+
+/// Information about an attempt of sending a request to a node,
+/// passed to [`RequestInterceptor::on_attempt`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AttemptInfo<'a> {
+    /// The node the attempt was sent to.
+    pub node: NodeRef<'a>,
+    /// The shard the attempt was sent to, if the node is sharded.
+    pub shard: Option<Shard>,
+    /// The consistency used by the attempt.
+    pub consistency: Consistency,
+    /// Time elapsed between sending the request and receiving the response.
+    pub latency: Duration,
+    /// The error returned by the attempt, or `None` if it succeeded.
+    pub error: Option<&'a RequestAttemptError>,
+}
+
+/// The request interceptors applying to a single request:
+/// the session's ones, followed by the execution profile's ones.
+pub(crate) struct RequestHooks<'a> {
+    request: InterceptedRequest<'a>,
+    session_interceptors: &'a [Arc<dyn RequestInterceptor>],
+    execution_profile: Arc<ExecutionProfileInner>,
+}
+
+impl<'a> RequestHooks<'a> {
+    pub(crate) fn new(
+        request: InterceptedRequest<'a>,
+        session_interceptors: &'a [Arc<dyn RequestInterceptor>],
+        execution_profile: Arc<ExecutionProfileInner>,
+    ) -> Self {
+        Self {
+            request,
+            session_interceptors,
+            execution_profile,
+        }
+    }
+
+    fn interceptors(&self) -> impl DoubleEndedIterator<Item = &Arc<dyn RequestInterceptor>> {
+        self.session_interceptors
+            .iter()
+            .chain(&self.execution_profile.request_interceptors)
+    }
+
+    pub(crate) fn on_request_start(&self) {
+        for interceptor in self.interceptors() {
+            interceptor.on_request_start(self.request);
+        }
+    }
+
+    pub(crate) fn on_attempt(&self, attempt: &AttemptInfo<'_>) {
+        for interceptor in self.interceptors() {
+            interceptor.on_attempt(self.request, attempt);
+        }
+    }
+
+    pub(crate) fn on_complete(&self, outcome: Result<&QueryResult, &ExecutionError>) {
+        for interceptor in self.interceptors().rev() {
+            interceptor.on_complete(self.request, outcome);
+        }
+    }
+}
+
+/// The interceptors registered on a session, in the order of registration.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct InterceptorChain {
+    interceptors: Vec<Arc<dyn StatementInterceptor>>,
+}
+
+impl InterceptorChain {
+    pub(crate) fn new(interceptors: Vec<Arc<dyn StatementInterceptor>>) -> Self {
+        Self { interceptors }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.interceptors.is_empty()
+    }
+
+    pub(crate) fn intercept_statement(&self, mut statement: Statement) -> Statement {
+        for interceptor in &self.interceptors {
+            interceptor.intercept_statement(&mut statement);
+        }
+        statement
+    }
+
+    pub(crate) fn intercept_prepared<'v>(
+        &self,
+        prepared: &PreparedStatement,
+        values: &'v SerializedValues,
+    ) -> Cow<'v, SerializedValues> {
+        if self.is_empty() {
+            return Cow::Borrowed(values);
+        }
+        let mut values = values.clone();
+        for interceptor in &self.interceptors {
+            interceptor.intercept_prepared(prepared, &mut values);
+        }
+        Cow::Owned(values)
+    }
+
+    pub(crate) fn intercept_batch<'b>(&self, batch: &'b Batch) -> Cow<'b, Batch> {
+        if self.is_empty() {
+            return Cow::Borrowed(batch);
+        }
+        let mut batch = batch.clone();
+        for statement in &mut batch.statements {
+            if let BatchStatement::Query(query) = statement {
+                *query = self.intercept_statement(query.clone());
+            }
+        }
+        for interceptor in &self.interceptors {
+            interceptor.intercept_batch(&mut batch);
+        }
+        Cow::Owned(batch)
+    }
+
+    pub(crate) fn on_completion(
+        &self,
+        request: InterceptedRequest<'_>,
+        outcome: Result<&QueryResult, &ExecutionError>,
+    ) {
+        for interceptor in self.interceptors.iter().rev() {
+            interceptor.on_completion(request, outcome);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use std::time::Duration;
+
+    use scylla_cql::Consistency;
+
+    use super::{
+        AttemptInfo, InterceptedRequest, InterceptorChain, RequestHooks, RequestInterceptor,
+        StatementInterceptor,
+    };
+    use crate::client::execution_profile::ExecutionProfile;
+    use crate::cluster::Node;
+    use crate::errors::{BadQuery, ExecutionError, RequestAttemptError};
+    use crate::response::query_result::QueryResult;
+    use crate::statement::batch::{Batch, BatchStatement};
+    use crate::statement::unprepared::Statement;
+
+    #[derive(Debug)]
+    struct Recorder {
+        name: &'static str,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl StatementInterceptor for Recorder {
+        fn intercept_statement(&self, statement: &mut Statement) {
+            statement.contents = format!("/* {} */ {}", self.name, statement.contents);
+        }
+
+        fn intercept_batch(&self, batch: &mut Batch) {
+            batch.set_tracing(true);
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("batch {}", self.name));
+        }
+
+        fn on_completion(
+            &self,
+            request: InterceptedRequest<'_>,
+            outcome: Result<&QueryResult, &ExecutionError>,
+        ) {
+            let InterceptedRequest::Statement(statement) = request else {
+                panic!("unexpected request kind");
+            };
+            self.log.lock().unwrap().push(format!(
+                "{} completed {} ok={}",
+                self.name,
+                statement.contents,
+                outcome.is_ok()
+            ));
+        }
+    }
+
+    #[test]
+    fn interceptor_chain_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let chain = InterceptorChain::new(vec![
+            Arc::new(Recorder {
+                name: "a",
+                log: log.clone(),
+            }),
+            Arc::new(Recorder {
+                name: "b",
+                log: log.clone(),
+            }),
+        ]);
+
+        let statement = chain.intercept_statement(Statement::new("SELECT 1"));
+        assert_eq!(statement.contents, "/* b */ /* a */ SELECT 1");
+
+        let error = ExecutionError::BadQuery(BadQuery::PartitionKeyExtraction);
+        chain.on_completion(InterceptedRequest::Statement(&statement), Err(&error));
+
+        let mut batch = Batch::default();
+        batch.append_statement("INSERT INTO t (a) VALUES (1)");
+        let batch = chain.intercept_batch(&batch);
+        assert!(batch.get_tracing());
+        match &batch.statements[..] {
+            [BatchStatement::Query(q)] => {
+                assert_eq!(q.contents, "/* b */ /* a */ INSERT INTO t (a) VALUES (1)")
+            }
+            _ => panic!("unexpected batch statements"),
+        }
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "b completed /* b */ /* a */ SELECT 1 ok=false",
+                "a completed /* b */ /* a */ SELECT 1 ok=false",
+                "batch a",
+                "batch b",
+            ]
+        );
+    }
+
+    impl RequestInterceptor for Recorder {
+        fn on_request_start(&self, request: InterceptedRequest<'_>) {
+            let InterceptedRequest::Statement(statement) = request else {
+                panic!("unexpected request kind");
+            };
+            self.log
+                .lock()
+                .unwrap()
+                .push(format!("{} started {}", self.name, statement.contents));
+        }
+
+        fn on_attempt(&self, _request: InterceptedRequest<'_>, attempt: &AttemptInfo<'_>) {
+            self.log.lock().unwrap().push(format!(
+                "{} attempt {} failed={}",
+                self.name,
+                attempt.consistency,
+                attempt.error.is_some()
+            ));
+        }
+
+        fn on_complete(
+            &self,
+            _request: InterceptedRequest<'_>,
+            outcome: Result<&QueryResult, &ExecutionError>,
+        ) {
+            self.log.lock().unwrap().push(format!(
+                "{} completed ok={}",
+                self.name,
+                outcome.is_ok()
+            ));
+        }
+    }
+
+    #[test]
+    fn request_hooks_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let recorder = |name| {
+            Arc::new(Recorder {
+                name,
+                log: log.clone(),
+            })
+        };
+        let session_interceptors: Vec<Arc<dyn RequestInterceptor>> =
+            vec![recorder("session1"), recorder("session2")];
+        let profile = ExecutionProfile::builder()
+            .request_interceptor(recorder("profile"))
+            .build();
+        assert_eq!(profile.get_request_interceptors().len(), 1);
+
+        let statement = Statement::new("SELECT 1");
+        let hooks = RequestHooks::new(
+            InterceptedRequest::Statement(&statement),
+            &session_interceptors,
+            profile.0.clone(),
+        );
+        let node = Arc::new(Node::new_for_test(None, None, None, None));
+        let attempt_error = RequestAttemptError::NonfinishedPagingState;
+
+        hooks.on_request_start();
+        hooks.on_attempt(&AttemptInfo {
+            node: &node,
+            shard: None,
+            consistency: Consistency::One,
+            latency: Duration::from_millis(1),
+            error: Some(&attempt_error),
+        });
+        let error = ExecutionError::BadQuery(BadQuery::PartitionKeyExtraction);
+        hooks.on_complete(Err(&error));
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![
+                "session1 started SELECT 1",
+                "session2 started SELECT 1",
+                "profile started SELECT 1",
+                "session1 attempt One failed=true",
+                "session2 attempt One failed=true",
+                "profile attempt One failed=true",
+                "profile completed ok=false",
+                "session2 completed ok=false",
+                "session1 completed ok=false",
+            ]
+        );
+    }
+}
@@ -8,19 +8,43 @@
 //! - [SessionBuilder](session_builder::SessionBuilder) - just a convenient builder for a `Session`.
 //! - [CachingSession](caching_session::CachingSession) - a wrapper over a [Session](session::Session)
 //!   that keeps and manages a cache of prepared statements, so that a user can be free of such considerations.
+//! - [PartitionOrderedExecutor](ordered_executor::PartitionOrderedExecutor) - a wrapper over a [Session](session::Session)
+//!   that applies writes to the same partition in submission order, and writes to distinct partitions concurrently.
 //! - [SelfIdentity] - configuresd driver and application self-identifying information,
 //!   to be sent in STARTUP message.
 //! - [ExecutionProfile](execution_profile::ExecutionProfile) - a profile that groups various configuration
 //!   options relevant when executing a request against the DB.
+//! - [StatementInterceptor](interceptor::StatementInterceptor) - a hook that can inspect and rewrite
+//!   requests before they are executed, and observe their outcomes.
+//! - [SchemaAgreementProgress](schema_agreement::SchemaAgreementProgress) - a report on the progress
+//!   of awaiting schema agreement, listing schema versions of all nodes.
+//! - `RawRequest` and `RawResponse` (behind the `unstable-raw-requests` feature) - a way to send
+//!   custom CQL requests which the driver has no typed support for, and receive raw responses.
+//! - [SessionSettings](settings::SessionSettings) - commonly configured options of a session,
+//!   loaded from a YAML file or environment variables and turned into a [SessionBuilder](session_builder::SessionBuilder).
+//! - [TlsContextProvider](tls::TlsContextProvider) - a source of TLS contexts for new connections,
+//!   which allows rotating certificates at runtime.
 //! - [QueryPager](pager::QueryPager) and [TypedRowStream](pager::TypedRowStream) - entities that provide
 //!   automated transparent paging of a query.
 
+pub(crate) mod cancellation;
+pub use cancellation::CancellationToken;
+
 pub mod execution_profile;
 
+pub mod interceptor;
+
 pub mod pager;
 
+pub mod schema_agreement;
+
 pub mod caching_session;
 
+pub mod ordered_executor;
+
+#[cfg(feature = "unstable-raw-requests")]
+pub mod raw_request;
+
 mod self_identity;
 pub use self_identity::SelfIdentity;
 
@@ -28,6 +52,13 @@ pub mod session;
 
 pub mod session_builder;
 
+pub mod settings;
+
+pub mod tls;
+
 pub use scylla_cql::frame::Compression;
 
-pub use crate::network::{PoolSize, WriteCoalescingDelay};
+pub use crate::network::{
+    ConnectionStatus, NodePoolStatus, PoolSize, PoolState, ProtocolNegotiationPolicy,
+    ShardPoolState, WriteCoalescingDelay,
+};
@@ -0,0 +1,294 @@
+//! Provides [`PartitionOrderedExecutor`], a wrapper over the [`Session`] that applies
+//! writes targeting the same partition in the order they were submitted in,
+//! while writes targeting distinct partitions are executed concurrently.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use scylla_cql::frame::response::result::TableSpec;
+use scylla_cql::serialize::row::SerializeRow;
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+use crate::client::session::Session;
+use crate::errors::ExecutionError;
+use crate::response::query_result::QueryResult;
+use crate::statement::prepared::PreparedStatement;
+
+/// Executes prepared statements so that the ones targeting the same partition
+/// are applied strictly in submission order.
+///
+/// Statements for the same partition (the same table and partition key) are serialized:
+/// a statement is sent only after all statements for that partition submitted
+/// before it have completed - successfully or not. Statements for distinct
+/// partitions do not wait for each other and run concurrently.
+///
+/// This is useful e.g. for event-sourcing pipelines, which consume an ordered
+/// stream of events and must apply events concerning the same entity in order.
+///
+/// Submitted statements are executed on background tasks spawned on the executor's
+/// Tokio runtime, so they keep running (and keep their place in the partition's queue)
+/// even if the returned [`OrderedExecution`] is dropped.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # use std::error::Error;
+/// # use std::sync::Arc;
+/// # async fn check_only_compiles(session: Arc<Session>) -> Result<(), Box<dyn Error>> {
+/// use scylla::client::ordered_executor::PartitionOrderedExecutor;
+///
+/// let executor = PartitionOrderedExecutor::new(session.clone());
+/// let prepared = session
+///     .prepare("INSERT INTO ks.events (entity, seq, payload) VALUES (?, ?, ?)")
+///     .await?;
+///
+/// // Submission order is fixed when `submit` returns, so both writes are applied
+/// // in order, even though they are awaited together.
+/// let first = executor.submit(&prepared, ("a", 1_i32, "created"))?;
+/// let second = executor.submit(&prepared, ("a", 2_i32, "updated"))?;
+/// let (first, second) = futures::join!(first, second);
+/// first?;
+/// second?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PartitionOrderedExecutor {
+    session: Arc<Session>,
+    runtime: Handle,
+    sequencer: Arc<KeyedSequencer<PartitionQueueKey>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct PartitionQueueKey {
+    table: Option<TableSpec<'static>>,
+    partition_key: Bytes,
+}
+
+impl PartitionOrderedExecutor {
+    /// Creates a new executor, which sends statements through the given session
+    /// and runs them on the current Tokio runtime.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    /// Use [`PartitionOrderedExecutor::new_with_runtime`] to pass the runtime explicitly.
+    pub fn new(session: Arc<Session>) -> Self {
+        Self::new_with_runtime(session, Handle::current())
+    }
+
+    /// Creates a new executor, which sends statements through the given session
+    /// and runs them on the given Tokio runtime.
+    pub fn new_with_runtime(session: Arc<Session>, runtime: Handle) -> Self {
+        Self {
+            session,
+            runtime,
+            sequencer: Arc::new(KeyedSequencer::new()),
+        }
+    }
+
+    /// Returns the session used by this executor.
+    pub fn get_session(&self) -> &Arc<Session> {
+        &self.session
+    }
+
+    /// Submits a prepared statement for execution.
+    ///
+    /// The values are serialized and the partition key is computed immediately,
+    /// so that the statement's position in the partition's queue is determined
+    /// when this method returns. The statement is then executed (unpaged) in the background,
+    /// once all previously submitted statements for the same partition have completed.
+    ///
+    /// Fails if the values cannot be serialized or if the partition key
+    /// cannot be computed from them.
+    pub fn submit(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<OrderedExecution, Box<ExecutionError>> {
+        let serialized_values = prepared
+            .serialize_values(&values)
+            .map_err(|err| Box::new(ExecutionError::from(err)))?;
+        let partition_key = prepared
+            .compute_partition_key_untyped(&serialized_values)
+            .map_err(|err| Box::new(err.into_execution_error()))?;
+        let key = PartitionQueueKey {
+            table: prepared.get_table_spec().map(TableSpec::to_owned),
+            partition_key,
+        };
+
+        let mut ticket = self.sequencer.enqueue(key);
+        let session = Arc::clone(&self.session);
+        let prepared = prepared.clone();
+        let handle = self.runtime.spawn(async move {
+            ticket.wait_for_turn().await;
+            session
+                .execute_unpaged_serialized(&prepared, &serialized_values)
+                .await
+                .map_err(Box::new)
+            // Dropping the ticket lets the next statement for this partition proceed.
+        });
+
+        Ok(OrderedExecution { handle })
+    }
+
+    /// Returns the number of partitions which currently have statements
+    /// submitted and not yet completed.
+    pub fn pending_partitions(&self) -> usize {
+        self.sequencer.len()
+    }
+}
+
+/// A statement submitted to a [`PartitionOrderedExecutor`].
+///
+/// Resolves to the result of the statement's execution.
+/// Dropping it does not cancel the execution.
+#[derive(Debug)]
+pub struct OrderedExecution {
+    handle: JoinHandle<Result<QueryResult, Box<ExecutionError>>>,
+}
+
+impl Future for OrderedExecution {
+    type Output = Result<QueryResult, Box<ExecutionError>>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.handle).poll(cx).map(|res| match res {
+            Ok(result) => result,
+            Err(err) => match err.try_into_panic() {
+                Ok(panic) => std::panic::resume_unwind(panic),
+                Err(_) => panic!("Ordered execution task was cancelled by the runtime"),
+            },
+        })
+    }
+}
+
+/// Chains operations on the same key, so that each one starts only
+/// after the previous one for that key has finished.
+struct KeyedSequencer<K> {
+    inner: Mutex<KeyedSequencerInner<K>>,
+}
+
+struct KeyedSequencerInner<K> {
+    next_id: u64,
+    // For each key, the most recently enqueued operation.
+    tails: HashMap<K, QueueTail>,
+}
+
+struct QueueTail {
+    id: u64,
+    // Completes (with an error) when the operation's ticket is dropped.
+    done: oneshot::Receiver<()>,
+}
+
+impl<K: Hash + Eq + Clone> KeyedSequencer<K> {
+    fn new() -> Self {
+        Self {
+            inner: Mutex::new(KeyedSequencerInner {
+                next_id: 0,
+                tails: HashMap::new(),
+            }),
+        }
+    }
+
+    fn enqueue(self: &Arc<Self>, key: K) -> SequencerTicket<K> {
+        let (done_sender, done) = oneshot::channel();
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        let previous = inner
+            .tails
+            .insert(key.clone(), QueueTail { id, done })
+            .map(|tail| tail.done);
+
+        SequencerTicket {
+            sequencer: Arc::clone(self),
+            key,
+            id,
+            previous,
+            _done_sender: done_sender,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.inner.lock().unwrap().tails.len()
+    }
+}
+
+/// A place in a key's queue. The next operation for the key
+/// may proceed once the ticket is dropped.
+struct SequencerTicket<K: Hash + Eq> {
+    sequencer: Arc<KeyedSequencer<K>>,
+    key: K,
+    id: u64,
+    previous: Option<oneshot::Receiver<()>>,
+    _done_sender: oneshot::Sender<()>,
+}
+
+impl<K: Hash + Eq> SequencerTicket<K> {
+    async fn wait_for_turn(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            // The sender is never used, so this only completes when it is dropped.
+            let _ = previous.await;
+        }
+    }
+}
+
+impl<K: Hash + Eq> Drop for SequencerTicket<K> {
+    fn drop(&mut self) {
+        let mut inner = self.sequencer.inner.lock().unwrap();
+        // Forget the key unless another operation has been queued behind us.
+        if inner
+            .tails
+            .get(&self.key)
+            .is_some_and(|tail| tail.id == self.id)
+        {
+            inner.tails.remove(&self.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use super::KeyedSequencer;
+
+    #[tokio::test(start_paused = true)]
+    async fn keyed_sequencer_orders_same_key_only() {
+        let sequencer = Arc::new(KeyedSequencer::new());
+        let log = Arc::new(Mutex::new(Vec::new()));
+
+        // Earlier operations take longer, so without sequencing they would finish last.
+        let ops = [("a", 1, 30), ("b", 1, 20), ("a", 2, 10), ("a", 3, 0)];
+        let handles: Vec<_> = ops
+            .into_iter()
+            .map(|(key, seq, delay_ms)| {
+                let mut ticket = sequencer.enqueue(key);
+                let log = Arc::clone(&log);
+                tokio::spawn(async move {
+                    ticket.wait_for_turn().await;
+                    tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+                    log.lock().unwrap().push((key, seq));
+                })
+            })
+            .collect();
+        assert_eq!(sequencer.len(), 2);
+
+        for handle in handles {
+            handle.await.unwrap();
+        }
+
+        assert_eq!(
+            *log.lock().unwrap(),
+            vec![("b", 1), ("a", 1), ("a", 2), ("a", 3)]
+        );
+        assert_eq!(sequencer.len(), 0);
+    }
+}
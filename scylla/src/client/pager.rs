@@ -6,40 +6,57 @@ use std::future::Future;
 use std::net::SocketAddr;
 use std::ops::ControlFlow;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
+use bytes::{BufMut, Bytes};
 use futures::Stream;
+#[cfg(feature = "opentelemetry-031")]
+use opentelemetry::context::FutureExt as _;
 use scylla_cql::deserialize::result::RawRowLendingIterator;
 use scylla_cql::deserialize::row::{ColumnIterator, DeserializeRow};
 use scylla_cql::deserialize::{DeserializationError, TypeCheckError};
-use scylla_cql::frame::frame_errors::ResultMetadataAndRowsCountParseError;
-use scylla_cql::frame::request::query::PagingState;
+use scylla_cql::frame::frame_errors::{
+    LowLevelDeserializationError, ResultMetadataAndRowsCountParseError,
+};
+use scylla_cql::frame::request::query::{PagingState, PagingStateResponse};
 use scylla_cql::frame::response::result::RawMetadataAndRawRows;
 use scylla_cql::frame::response::NonErrorResponse;
-use scylla_cql::frame::types::SerialConsistency;
+use scylla_cql::frame::types::{self, RawValue, SerialConsistency};
 use scylla_cql::serialize::row::SerializedValues;
 use scylla_cql::Consistency;
 use std::result::Result;
 use thiserror::Error;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, Notify, Semaphore};
 
+use crate::client::cancellation::{self, CancellationToken};
 use crate::client::execution_profile::ExecutionProfileInner;
 use crate::cluster::{ClusterState, NodeRef};
 use crate::deserialize::DeserializeOwnedRow;
-use crate::errors::{RequestAttemptError, RequestError};
+use crate::errors::{AttemptContext, RequestAttemptError, RequestError};
 use crate::frame::response::result;
-use crate::network::Connection;
+use crate::network::{Connection, InFlightLimiting};
 use crate::observability::driver_tracing::RequestSpan;
 use crate::observability::history::{self, HistoryListener};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
+#[cfg(feature = "opentelemetry-031")]
+use crate::observability::opentelemetry::{end_attempt_span, start_attempt_span};
 use crate::policies::load_balancing::{self, LoadBalancingPolicy, RoutingInfo};
+use crate::policies::rate_limiter::{RateLimitPermit, RequestRateLimiter};
 use crate::policies::retry::{RequestInfo, RetryDecision, RetrySession};
 use crate::response::query_result::ColumnSpecs;
-use crate::response::{NonErrorQueryResponse, QueryResponse};
+use crate::response::{NonErrorQueryResponse, QueryResponse, RequestAttempt};
+#[cfg(feature = "metrics")]
+use crate::routing::locator::tablets::has_tablet_info;
+#[cfg(feature = "metrics")]
+use crate::routing::Shard;
 use crate::statement::prepared::{PartitionKeyError, PreparedStatement};
 use crate::statement::unprepared::Statement;
+#[cfg(feature = "metrics")]
+use crate::statement::StatementLabels;
+use crate::statement::{PageSize, PagerPrefetch, StatementConfig};
 use tracing::{trace, trace_span, warn, Instrument};
 use uuid::Uuid;
 
@@ -57,17 +74,185 @@ macro_rules! ready_some_ok {
     };
 }
 
+/// Information about how a single page of a [QueryPager] was fetched.
+///
+/// Returned by [QueryPager::current_page_execution_info] and
+/// [TypedRowStream::current_page_execution_info].
+#[derive(Debug, Clone)]
+pub struct PageExecutionInfo {
+    page_number: usize,
+    coordinator: Option<Coordinator>,
+    attempts: usize,
+    latency: Duration,
+    tracing_id: Option<Uuid>,
+    warnings: Vec<String>,
+    attempt_history: Vec<RequestAttempt>,
+}
+
+impl PageExecutionInfo {
+    // Info of a page which was not fetched from the cluster, e.g. an empty page
+    // sent when fetching stopped.
+    fn unfetched(page_number: usize) -> Self {
+        Self {
+            page_number,
+            coordinator: None,
+            attempts: 0,
+            latency: Duration::ZERO,
+            tracing_id: None,
+            warnings: Vec::new(),
+            attempt_history: Vec::new(),
+        }
+    }
+
+    /// The (1-based) number of the page among the pages of the query.
+    #[inline]
+    pub fn page_number(&self) -> usize {
+        self.page_number
+    }
+
+    /// The node+shard that served the page.
+    ///
+    /// `None` if the page was not fetched through the load balancing policy,
+    /// e.g. for pagers of schema queries, which work on a single connection.
+    #[inline]
+    pub fn coordinator(&self) -> Option<&Coordinator> {
+        self.coordinator.as_ref()
+    }
+
+    /// The number of attempts it took to fetch the page, including the successful one.
+    ///
+    /// Values greater than 1 mean that fetching the page was retried,
+    /// on the same node or on other ones, according to the retry policy.
+    #[inline]
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    /// The latency of the successful attempt to fetch the page.
+    #[inline]
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// The tracing id of the page query, if tracing was enabled.
+    #[inline]
+    pub fn tracing_id(&self) -> Option<Uuid> {
+        self.tracing_id
+    }
+
+    /// The warnings sent by the coordinator along with the page.
+    #[inline]
+    pub fn warnings(&self) -> impl Iterator<Item = &str> {
+        self.warnings.iter().map(String::as_str)
+    }
+
+    /// Attempts made to fetch the page, including the successful one,
+    /// in the order in which they were made.
+    ///
+    /// Empty if the page was not fetched through the load balancing policy.
+    #[inline]
+    pub fn attempt_history(&self) -> &[RequestAttempt] {
+        &self.attempt_history
+    }
+}
+
 struct ReceivedPage {
     rows: RawMetadataAndRawRows,
-    tracing_id: Option<Uuid>,
-    request_coordinator: Option<Coordinator>,
+    execution_info: PageExecutionInfo,
+    // Tells where to resume the query to fetch the page following this one.
+    paging_state_response: PagingStateResponse,
+}
+
+impl ReceivedPage {
+    // Size accounted for in PrefetchControl.
+    fn bytes_size(&self) -> usize {
+        self.rows.metadata_and_rows_bytes_size()
+    }
+}
+
+// Makes the worker wait before fetching a page which would be too far
+// ahead of the page consumed by QueryPager.
+#[derive(Debug)]
+struct PrefetchControl {
+    config: PagerPrefetch,
+    state: Mutex<PrefetchState>,
+    // Notifies the worker that the consumer has made progress.
+    progress: Notify,
+}
+
+#[derive(Debug)]
+struct PrefetchState {
+    // Number of pages requested by the consumer, including the one being consumed.
+    requested_pages: usize,
+    // Total size of pages sent by the worker which the consumer has not received yet.
+    prefetched_bytes: usize,
+}
+
+impl PrefetchControl {
+    fn new(config: PagerPrefetch) -> Self {
+        Self {
+            config,
+            state: Mutex::new(PrefetchState {
+                // The first page is always requested.
+                requested_pages: 1,
+                prefetched_bytes: 0,
+            }),
+            progress: Notify::new(),
+        }
+    }
+
+    // Capacity of the channel which is enough for the worker never to wait on it.
+    fn channel_capacity(&self) -> usize {
+        self.config
+            .pages
+            .saturating_add(1)
+            .min(Semaphore::MAX_PERMITS)
+    }
+
+    fn may_fetch(&self, page_number: usize) -> bool {
+        let state = self.state.lock().unwrap();
+        if page_number <= state.requested_pages {
+            // The consumer waits for this page.
+            return true;
+        }
+        page_number - state.requested_pages <= self.config.pages
+            && self
+                .config
+                .max_bytes
+                .is_none_or(|max_bytes| state.prefetched_bytes < max_bytes.get())
+    }
+
+    // Waits until the worker is allowed to fetch the page with given (1-based) number.
+    async fn wait_until_may_fetch(&self, page_number: usize) {
+        while !self.may_fetch(page_number) {
+            self.progress.notified().await;
+        }
+    }
+
+    fn on_page_sent(&self, bytes: usize) {
+        self.state.lock().unwrap().prefetched_bytes += bytes;
+    }
+
+    fn on_page_received(&self, bytes: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.prefetched_bytes = state.prefetched_bytes.saturating_sub(bytes);
+        drop(state);
+        self.progress.notify_one();
+    }
+
+    fn on_page_requested(&self) {
+        self.state.lock().unwrap().requested_pages += 1;
+        self.progress.notify_one();
+    }
 }
 
 pub(crate) struct PreparedPagerConfig {
     pub(crate) prepared: PreparedStatement,
     pub(crate) values: SerializedValues,
+    pub(crate) paging_state: PagingState,
     pub(crate) execution_profile: Arc<ExecutionProfileInner>,
     pub(crate) cluster_state: Arc<ClusterState>,
+    pub(crate) rate_limiter: Option<Arc<dyn RequestRateLimiter>>,
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Arc<Metrics>,
 }
@@ -75,14 +260,12 @@ pub(crate) struct PreparedPagerConfig {
 // A separate module is used here so that the parent module cannot construct
 // SendAttemptedProof directly.
 mod checked_channel_sender {
+    use scylla_cql::frame::request::query::PagingStateResponse;
     use scylla_cql::frame::response::result::RawMetadataAndRawRows;
     use std::marker::PhantomData;
     use tokio::sync::mpsc;
-    use uuid::Uuid;
 
-    use crate::response::Coordinator;
-
-    use super::{NextPageError, ReceivedPage};
+    use super::{NextPageError, PageExecutionInfo, ReceivedPage};
 
     /// A value whose existence proves that there was an attempt
     /// to send an item of type T through a channel.
@@ -105,6 +288,11 @@ mod checked_channel_sender {
         ) -> (SendAttemptedProof<T>, Result<(), mpsc::error::SendError<T>>) {
             (SendAttemptedProof(PhantomData), self.0.send(value).await)
         }
+
+        /// Completes once the receiver is dropped.
+        pub(crate) async fn closed(&self) {
+            self.0.closed().await
+        }
     }
 
     type ResultPage = Result<ReceivedPage, NextPageError>;
@@ -112,16 +300,15 @@ mod checked_channel_sender {
     impl ProvingSender<ResultPage> {
         pub(crate) async fn send_empty_page(
             &self,
-            tracing_id: Option<Uuid>,
-            request_coordinator: Option<Coordinator>,
+            execution_info: PageExecutionInfo,
         ) -> (
             SendAttemptedProof<ResultPage>,
             Result<(), mpsc::error::SendError<ResultPage>>,
         ) {
             let empty_page = ReceivedPage {
                 rows: RawMetadataAndRawRows::mock_empty(),
-                tracing_id,
-                request_coordinator,
+                execution_info,
+                paging_state_response: PagingStateResponse::NoMorePages,
             };
             self.send(Ok(empty_page)).await
         }
@@ -134,10 +321,54 @@ use crate::response::Coordinator;
 
 type PageSendAttemptedProof = SendAttemptedProof<Result<ReceivedPage, NextPageError>>;
 
+// Reasons for PagerWorker to stop waiting, either for its turn to fetch a page
+// or for the page to be fetched.
+enum PagerInterruption {
+    // QueryPager was dropped.
+    PagerDropped,
+    // The cancellation token of the statement was cancelled.
+    Cancelled,
+    // The deadline of the statement has passed.
+    DeadlineExceeded,
+}
+
+// Completes once the deadline, if any, passes.
+async fn deadline_passed(deadline: Option<Instant>) {
+    match deadline {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => std::future::pending().await,
+    }
+}
+
+// Waits until the page with the given (1-based) number may be fetched.
+// Returns an error if the worker should stop in the meantime.
+async fn wait_for_page_turn(
+    prefetch: &PrefetchControl,
+    sender: &ProvingSender<Result<ReceivedPage, NextPageError>>,
+    cancellation_token: Option<&CancellationToken>,
+    page_number: usize,
+) -> Result<(), PagerInterruption> {
+    tokio::select! {
+        biased;
+        () = cancellation::cancelled(cancellation_token) => Err(PagerInterruption::Cancelled),
+        () = sender.closed() => Err(PagerInterruption::PagerDropped),
+        () = prefetch.wait_until_may_fetch(page_number) => Ok(()),
+    }
+}
+
 // PagerWorker works in the background to fetch pages
 // QueryPager receives them through a channel
 struct PagerWorker<'a, QueryFunc, SpanCreatorFunc> {
     sender: ProvingSender<Result<ReceivedPage, NextPageError>>,
+    prefetch: Arc<PrefetchControl>,
+    // Number of pages sent to QueryPager so far.
+    pages_sent: usize,
+    // Number of attempts made to fetch the current page so far.
+    page_attempts: usize,
+    // Attempts made to fetch the current page so far.
+    attempt_history: Vec<RequestAttempt>,
+    // Latency of the most recent attempt.
+    last_attempt_latency: Duration,
 
     // Closure used to perform a single page query
     // AsyncFn(Arc<Connection>, Option<Arc<[u8]>>) -> Result<QueryResponse, RequestAttemptError>
@@ -150,6 +381,15 @@ struct PagerWorker<'a, QueryFunc, SpanCreatorFunc> {
     retry_session: Box<dyn RetrySession>,
     #[cfg(feature = "metrics")]
     metrics: Arc<Metrics>,
+    #[cfg(feature = "metrics")]
+    labels: StatementLabels,
+    // Whether the table targeted by the statement is known to use tablets,
+    // or `None` if the statement doesn't target a known table.
+    #[cfg(feature = "metrics")]
+    tablet_table: Option<bool>,
+    // Label of the table targeted by the statement, if known.
+    #[cfg(feature = "metrics")]
+    table_label: Option<&'a str>,
 
     paging_state: PagingState,
 
@@ -157,8 +397,22 @@ struct PagerWorker<'a, QueryFunc, SpanCreatorFunc> {
     current_request_id: Option<history::RequestId>,
     current_attempt_id: Option<history::AttemptId>,
 
+    cancellation_token: Option<CancellationToken>,
+    deadline: Option<Instant>,
+
+    rate_limiter: Option<Arc<dyn RequestRateLimiter>>,
+    // Permit of the rate limiter for fetching the current page, held until
+    // the page (including all its retries) is fetched.
+    page_permit: Option<RateLimitPermit>,
+
     parent_span: tracing::Span,
     span_creator: SpanCreatorFunc,
+
+    #[cfg(feature = "opentelemetry-031")]
+    otel_parent: opentelemetry::Context,
+    // Number of retries of fetching the current page.
+    #[cfg(feature = "opentelemetry-031")]
+    otel_retry_count: usize,
 }
 
 impl<QueryFunc, QueryFut, SpanCreator> PagerWorker<'_, QueryFunc, SpanCreator>
@@ -245,19 +499,38 @@ where
                 );
 
                 self.log_attempt_error(&request_error, &retry_decision);
-
-                last_error = request_error.into();
+                self.attempt_history.push(RequestAttempt::failed(
+                    coordinator.clone(),
+                    self.last_attempt_latency,
+                    request_error.clone(),
+                    retry_decision.clone(),
+                ));
+
+                // `page_attempts` counts the attempts of fetching the current page,
+                // so it is the ordinal of the attempt that has just failed.
+                last_error = RequestError::from_attempt(
+                    request_error,
+                    AttemptContext::new(coordinator.clone(), self.page_attempts),
+                );
 
                 match retry_decision {
                     RetryDecision::RetrySameTarget(cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        #[cfg(feature = "opentelemetry-031")]
+                        {
+                            self.otel_retry_count += 1;
+                        }
                         current_consistency = cl.unwrap_or(current_consistency);
                         continue 'same_node_retries;
                     }
                     RetryDecision::RetryNextTarget(cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        #[cfg(feature = "opentelemetry-031")]
+                        {
+                            self.otel_retry_count += 1;
+                        }
                         current_consistency = cl.unwrap_or(current_consistency);
                         continue 'nodes_in_plan;
                     }
@@ -272,7 +545,12 @@ where
                         // the iterator expects it.
                         let (proof, _) = self
                             .sender
-                            .send_empty_page(None, Some(coordinator.clone()))
+                            .send_empty_page(PageExecutionInfo {
+                                coordinator: Some(coordinator.clone()),
+                                attempts: self.page_attempts,
+                                attempt_history: std::mem::take(&mut self.attempt_history),
+                                ..PageExecutionInfo::unfetched(self.pages_sent + 1)
+                            })
                             .await;
                         return proof;
                     }
@@ -281,6 +559,7 @@ where
         }
 
         self.log_request_error(&last_error);
+        let last_error = last_error.with_attempt_history(std::mem::take(&mut self.attempt_history));
         let (proof, _) = self
             .sender
             .send(Err(NextPageError::RequestFailure(last_error)))
@@ -327,9 +606,40 @@ where
         coordinator: Coordinator,
         request_span: &RequestSpan,
     ) -> Result<ControlFlow<PageSendAttemptedProof, ()>, RequestAttemptError> {
+        if let Err(interruption) = wait_for_page_turn(
+            &self.prefetch,
+            &self.sender,
+            self.cancellation_token.as_ref(),
+            self.pages_sent + 1,
+        )
+        .await
+        {
+            return Ok(ControlFlow::Break(self.interrupt(interruption).await));
+        }
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            return Ok(ControlFlow::Break(
+                self.interrupt(PagerInterruption::DeadlineExceeded).await,
+            ));
+        }
+        if let Some(proof) = self.acquire_page_permit().await {
+            return Ok(ControlFlow::Break(proof));
+        }
+
         #[cfg(feature = "metrics")]
-        self.metrics.inc_total_paged_queries();
-        let query_start = std::time::Instant::now();
+        {
+            self.metrics.inc_total_paged_queries();
+            if let (Some(target_shard), Some(shard_info)) =
+                (coordinator.shard(), connection.get_shard_info())
+            {
+                self.metrics
+                    .log_shard_routing(shard_info.shard as Shard == target_shard);
+            }
+        }
+        self.page_attempts += 1;
+        let query_start = Instant::now();
 
         let connect_address = connection.get_connect_address();
         trace!(
@@ -338,12 +648,60 @@ where
         );
         self.log_attempt_start(connect_address);
 
-        let query_response =
-            (self.page_query)(connection.clone(), consistency, self.paging_state.clone())
-                .await
-                .and_then(QueryResponse::into_non_error_query_response);
+        let query_future =
+            (self.page_query)(connection.clone(), consistency, self.paging_state.clone());
+        #[cfg(feature = "opentelemetry-031")]
+        let otel_cx = start_attempt_span(
+            &self.otel_parent,
+            node,
+            coordinator.shard(),
+            consistency,
+            self.otel_retry_count,
+        );
+        #[cfg(feature = "opentelemetry-031")]
+        let query_future = query_future.with_context(otel_cx.clone());
+        let in_flight_guard = node.start_request();
+        // Stop waiting for the page as soon as it is no longer needed. Dropping
+        // the request future orphans its stream id, which gets released
+        // once the response arrives.
+        let query_response = tokio::select! {
+            biased;
+            () = cancellation::cancelled(self.cancellation_token.as_ref()) => {
+                Err(PagerInterruption::Cancelled)
+            }
+            () = self.sender.closed() => Err(PagerInterruption::PagerDropped),
+            () = deadline_passed(self.deadline) => Err(PagerInterruption::DeadlineExceeded),
+            response = query_future => Ok(response),
+        };
+        drop(in_flight_guard);
+        let query_response = match query_response {
+            Ok(response) => response.and_then(QueryResponse::into_non_error_query_response),
+            Err(interruption) => return Ok(ControlFlow::Break(self.interrupt(interruption).await)),
+        };
 
         let elapsed = query_start.elapsed();
+        self.last_attempt_latency = elapsed;
+        #[cfg(feature = "opentelemetry-031")]
+        end_attempt_span(&otel_cx, query_response.as_ref().err());
+        #[cfg(feature = "metrics")]
+        if let (Some(tablet_table), Ok(response)) = (self.tablet_table, &query_response) {
+            self.metrics.log_tablet_routing(
+                tablet_table,
+                has_tablet_info(response.custom_payload.as_ref()),
+            );
+        }
+        #[cfg(feature = "metrics")]
+        self.metrics.log_node_request(
+            node,
+            elapsed.as_millis() as u64,
+            !matches!(
+                query_response,
+                Ok(NonErrorQueryResponse {
+                    response: NonErrorResponse::Result(_),
+                    ..
+                })
+            ),
+        );
 
         request_span.record_shard_id(connection);
 
@@ -352,29 +710,48 @@ where
                 response:
                     NonErrorResponse::Result(result::Result::Rows((rows, paging_state_response))),
                 tracing_id,
+                warnings,
                 ..
             }) => {
                 #[cfg(feature = "metrics")]
-                let _ = self.metrics.log_query_latency(elapsed.as_millis() as u64);
+                {
+                    let _ = self.metrics.log_query_latency(elapsed.as_millis() as u64);
+                    self.log_labeled_page(elapsed, false);
+                }
                 self.log_attempt_success();
                 self.log_request_success();
+                node.record_latency(elapsed);
                 self.load_balancing_policy
                     .on_request_success(&self.statement_info, elapsed, node);
 
                 request_span.record_raw_rows_fields(&rows);
 
+                self.attempt_history
+                    .push(RequestAttempt::succeeded(coordinator.clone(), elapsed));
                 let received_page = ReceivedPage {
                     rows,
-                    tracing_id,
-                    request_coordinator: Some(coordinator),
+                    execution_info: PageExecutionInfo {
+                        page_number: self.pages_sent + 1,
+                        coordinator: Some(coordinator),
+                        attempts: self.page_attempts,
+                        latency: elapsed,
+                        tracing_id,
+                        warnings,
+                        attempt_history: std::mem::take(&mut self.attempt_history),
+                    },
+                    paging_state_response: paging_state_response.clone(),
                 };
 
                 // Send next page to QueryPager
+                self.prefetch.on_page_sent(received_page.bytes_size());
                 let (proof, res) = self.sender.send(Ok(received_page)).await;
                 if res.is_err() {
                     // channel was closed, QueryPager was dropped - should shutdown
                     return Ok(ControlFlow::Break(proof));
                 }
+                self.pages_sent += 1;
+                self.page_attempts = 0;
+                self.page_permit = None;
 
                 match paging_state_response.into_paging_control_flow() {
                     ControlFlow::Continue(paging_state) => {
@@ -388,13 +765,20 @@ where
 
                 // Query succeeded, reset retry policy for future retries
                 self.retry_session.reset();
+                #[cfg(feature = "opentelemetry-031")]
+                {
+                    self.otel_retry_count = 0;
+                }
                 self.log_request_start();
 
                 Ok(ControlFlow::Continue(()))
             }
             Err(err) => {
                 #[cfg(feature = "metrics")]
-                self.metrics.inc_failed_paged_queries();
+                {
+                    self.metrics.inc_failed_paged_queries();
+                    self.log_labeled_page(elapsed, true);
+                }
                 self.load_balancing_policy.on_request_failure(
                     &self.statement_info,
                     elapsed,
@@ -406,15 +790,26 @@ where
             Ok(NonErrorQueryResponse {
                 response: NonErrorResponse::Result(_),
                 tracing_id,
+                warnings,
                 ..
             }) => {
                 // We have most probably sent a modification statement (e.g. INSERT or UPDATE),
                 // so let's return an empty iterator as suggested in #631.
 
                 // We must attempt to send something because the iterator expects it.
+                self.attempt_history
+                    .push(RequestAttempt::succeeded(coordinator.clone(), elapsed));
                 let (proof, _) = self
                     .sender
-                    .send_empty_page(tracing_id, Some(coordinator))
+                    .send_empty_page(PageExecutionInfo {
+                        page_number: self.pages_sent + 1,
+                        coordinator: Some(coordinator),
+                        attempts: self.page_attempts,
+                        latency: elapsed,
+                        tracing_id,
+                        warnings,
+                        attempt_history: std::mem::take(&mut self.attempt_history),
+                    })
                     .await;
                 Ok(ControlFlow::Break(proof))
             }
@@ -457,6 +852,66 @@ where
         history_listener.log_request_success(request_id);
     }
 
+    // Stops the worker, letting QueryPager know why if it still exists.
+    // Waits for the rate limiter, if any, to admit fetching the current page.
+    // Returns the proof of sending an error if the worker should stop instead.
+    async fn acquire_page_permit(&mut self) -> Option<PageSendAttemptedProof> {
+        if self.page_permit.is_some() {
+            return None;
+        }
+        let rate_limiter = self.rate_limiter.as_ref()?;
+
+        let acquired = tokio::select! {
+            biased;
+            () = cancellation::cancelled(self.cancellation_token.as_ref()) => {
+                Err(PagerInterruption::Cancelled)
+            }
+            () = self.sender.closed() => Err(PagerInterruption::PagerDropped),
+            () = deadline_passed(self.deadline) => Err(PagerInterruption::DeadlineExceeded),
+            permit = rate_limiter.acquire() => Ok(permit),
+        };
+        match acquired {
+            Ok(Ok(permit)) => {
+                self.page_permit = Some(permit);
+                None
+            }
+            Ok(Err(err)) => Some(self.fail(RequestError::RateLimited(err)).await),
+            Err(interruption) => Some(self.interrupt(interruption).await),
+        }
+    }
+
+    async fn interrupt(&mut self, interruption: PagerInterruption) -> PageSendAttemptedProof {
+        match interruption {
+            PagerInterruption::PagerDropped => {
+                let (proof, _) = self
+                    .sender
+                    .send_empty_page(PageExecutionInfo::unfetched(self.pages_sent + 1))
+                    .await;
+                proof
+            }
+            PagerInterruption::Cancelled => {
+                trace!("Fetching pages cancelled");
+                self.fail(RequestError::Cancelled).await
+            }
+            PagerInterruption::DeadlineExceeded => {
+                trace!("Deadline exceeded, not fetching further pages");
+                #[cfg(feature = "metrics")]
+                self.metrics.inc_request_timeouts();
+                self.fail(RequestError::DeadlineExceeded).await
+            }
+        }
+    }
+
+    async fn fail(&mut self, error: RequestError) -> PageSendAttemptedProof {
+        self.log_request_error(&error);
+        let error = error.with_attempt_history(std::mem::take(&mut self.attempt_history));
+        let (proof, _) = self
+            .sender
+            .send(Err(NextPageError::RequestFailure(error)))
+            .await;
+        proof
+    }
+
     fn log_request_error(&mut self, error: &RequestError) {
         let history_listener: &dyn HistoryListener = match &self.history_listener {
             Some(hl) => &**hl,
@@ -468,7 +923,17 @@ where
             None => return,
         };
 
-        history_listener.log_request_error(request_id, error);
+        history_listener.log_request_error(request_id, error.inner());
+    }
+
+    #[cfg(feature = "metrics")]
+    fn log_labeled_page(&self, elapsed: std::time::Duration, failed: bool) {
+        self.metrics.log_labeled_request(
+            &self.labels,
+            self.table_label,
+            elapsed.as_millis() as u64,
+            failed,
+        );
     }
 
     fn log_attempt_start(&mut self, node_addr: SocketAddr) {
@@ -520,6 +985,7 @@ where
 /// a single connection.
 struct SingleConnectionPagerWorker<Fetcher> {
     sender: ProvingSender<Result<ReceivedPage, NextPageError>>,
+    prefetch: Arc<PrefetchControl>,
     fetcher: Fetcher,
 }
 
@@ -545,24 +1011,47 @@ where
 
     async fn do_work(&mut self) -> Result<PageSendAttemptedProof, RequestAttemptError> {
         let mut paging_state = PagingState::start();
+        let mut pages_sent = 0;
         loop {
+            // Requests issued on a single connection are internal to the driver,
+            // so there is no cancellation token to observe.
+            if wait_for_page_turn(&self.prefetch, &self.sender, None, pages_sent + 1)
+                .await
+                .is_err()
+            {
+                // QueryPager was dropped - should shutdown
+                let (proof, _) = self
+                    .sender
+                    .send_empty_page(PageExecutionInfo::unfetched(pages_sent + 1))
+                    .await;
+                return Ok(proof);
+            }
+
+            let query_start = Instant::now();
             let result = (self.fetcher)(paging_state).await?;
+            let execution_info = PageExecutionInfo {
+                attempts: 1,
+                latency: query_start.elapsed(),
+                tracing_id: result.tracing_id,
+                warnings: result.warnings.clone(),
+                ..PageExecutionInfo::unfetched(pages_sent + 1)
+            };
             let response = result.into_non_error_query_response()?;
             match response.response {
                 NonErrorResponse::Result(result::Result::Rows((rows, paging_state_response))) => {
-                    let (proof, send_result) = self
-                        .sender
-                        .send(Ok(ReceivedPage {
-                            rows,
-                            tracing_id: response.tracing_id,
-                            request_coordinator: None,
-                        }))
-                        .await;
+                    let received_page = ReceivedPage {
+                        rows,
+                        execution_info,
+                        paging_state_response: paging_state_response.clone(),
+                    };
+                    self.prefetch.on_page_sent(received_page.bytes_size());
+                    let (proof, send_result) = self.sender.send(Ok(received_page)).await;
 
                     if send_result.is_err() {
                         // channel was closed, QueryPager was dropped - should shutdown
                         return Ok(proof);
                     }
+                    pages_sent += 1;
 
                     match paging_state_response.into_paging_control_flow() {
                         ControlFlow::Continue(new_paging_state) => {
@@ -579,7 +1068,7 @@ where
                     // so let's return an empty iterator as suggested in #631.
 
                     // We must attempt to send something because the iterator expects it.
-                    let (proof, _) = self.sender.send_empty_page(response.tracing_id, None).await;
+                    let (proof, _) = self.sender.send_empty_page(execution_info).await;
                     return Ok(proof);
                 }
                 _ => {
@@ -602,9 +1091,16 @@ where
 #[derive(Debug)]
 pub struct QueryPager {
     current_page: RawRowLendingIterator,
+    current_page_paging_state: PagingStateResponse,
     page_receiver: mpsc::Receiver<Result<ReceivedPage, NextPageError>>,
+    prefetch: Arc<PrefetchControl>,
+    // Whether the worker has been told that the page following the current one is needed.
+    next_page_requested: bool,
     tracing_ids: Vec<Uuid>,
     request_coordinators: Vec<Coordinator>,
+    current_page_execution_info: PageExecutionInfo,
+    // None for pagers which cannot be resumed, i.e. the ones working on a single connection.
+    cursor_base: Option<Arc<PagerCursorBase>>,
 }
 
 // QueryPager is not an iterator or a stream! However, it implements
@@ -668,20 +1164,27 @@ impl QueryPager {
     ) -> Poll<Option<Result<(), NextRowError>>> {
         let mut s = self.as_mut();
 
+        if !s.next_page_requested {
+            s.prefetch.on_page_requested();
+            s.next_page_requested = true;
+        }
+
         let received_page = ready_some_ok!(Pin::new(&mut s.page_receiver).poll_recv(cx));
+        s.prefetch.on_page_received(received_page.bytes_size());
+        s.next_page_requested = false;
 
         let raw_rows_with_deserialized_metadata =
             received_page.rows.deserialize_metadata().map_err(|err| {
                 NextRowError::NextPageError(NextPageError::ResultMetadataParseError(err))
             })?;
         s.current_page = RawRowLendingIterator::new(raw_rows_with_deserialized_metadata);
+        s.current_page_paging_state = received_page.paging_state_response;
 
-        if let Some(tracing_id) = received_page.tracing_id {
-            s.tracing_ids.push(tracing_id);
-        }
-
+        let execution_info = received_page.execution_info;
+        s.tracing_ids.extend(execution_info.tracing_id);
         s.request_coordinators
-            .extend(received_page.request_coordinator);
+            .extend(execution_info.coordinator.clone());
+        s.current_page_execution_info = execution_info;
 
         Poll::Ready(Some(Ok(())))
     }
@@ -708,13 +1211,60 @@ impl QueryPager {
         TypedRowStream::<RowT>::new(self)
     }
 
+    /// Casts the iterator to a given owned row type, returning a self-contained stream.
+    ///
+    /// This is [rows_stream](Self::rows_stream) with the `Send` bound on the row type
+    /// spelled out, so that the returned stream is guaranteed to be movable between tasks.
+    ///
+    /// The returned stream does not borrow from the [Session](crate::client::session::Session)
+    /// nor from the executed statement: everything needed to fetch subsequent pages
+    /// (the statement, its bound values, the paging state and the cluster state)
+    /// is owned by the background paging task. Hence the stream is `Send + 'static`
+    /// and can be freely moved into spawned tasks or stored in structs.
+    /// Fetching pages stops once the stream is dropped.
+    ///
+    /// Begins with performing type check.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use futures::StreamExt;
+    ///
+    /// let mut rows = session
+    ///     .query_iter("SELECT a, b FROM ks.t", &[])
+    ///     .await?
+    ///     .into_owned_stream::<(i32, String)>()?;
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(row) = rows.next().await {
+    ///         let (a, b) = row.unwrap();
+    ///         println!("a, b: {a}, {b}");
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[inline]
+    pub fn into_owned_stream<RowT>(self) -> Result<TypedRowStream<RowT>, TypeCheckError>
+    where
+        RowT: for<'frame, 'metadata> DeserializeRow<'frame, 'metadata> + Send + 'static,
+    {
+        TypedRowStream::<RowT>::new(self)
+    }
+
     pub(crate) async fn new_for_query(
         statement: Statement,
+        paging_state: PagingState,
         execution_profile: Arc<ExecutionProfileInner>,
         cluster_state: Arc<ClusterState>,
+        rate_limiter: Option<Arc<dyn RequestRateLimiter>>,
         #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
     ) -> Result<Self, NextPageError> {
-        let (sender, receiver) = mpsc::channel::<Result<ReceivedPage, NextPageError>>(1);
+        let prefetch = Arc::new(PrefetchControl::new(statement.config.pager_prefetch));
+        let (sender, receiver) =
+            mpsc::channel::<Result<ReceivedPage, NextPageError>>(prefetch.channel_capacity());
 
         let consistency = statement
             .config
@@ -727,11 +1277,14 @@ impl QueryPager {
 
         let page_size = statement.get_validated_page_size();
 
-        let routing_info = RoutingInfo {
+        let cursor_base = Arc::new(PagerCursorBase {
+            statement: statement.contents.clone(),
+            prepared_id: None,
+            values: SerializedValues::new(),
             consistency,
             serial_consistency,
-            ..Default::default()
-        };
+            page_size,
+        });
 
         let load_balancing_policy = Arc::clone(
             statement
@@ -746,9 +1299,21 @@ impl QueryPager {
             .new_session();
 
         let parent_span = tracing::Span::current();
+        #[cfg(feature = "opentelemetry-031")]
+        let otel_parent = opentelemetry::Context::current();
+        let worker_prefetch = Arc::clone(&prefetch);
         let worker_task = async move {
             let statement_ref = &statement;
 
+            let routing = statement_ref.get_routing();
+            let routing_info = RoutingInfo {
+                consistency,
+                serial_consistency,
+                token: routing.and_then(|routing| routing.token(&cluster_state)),
+                table: routing.map(|routing| routing.table()),
+                ..Default::default()
+            };
+
             let page_query = |connection: Arc<Connection>,
                               consistency: Consistency,
                               paging_state: PagingState| {
@@ -760,6 +1325,7 @@ impl QueryPager {
                             serial_consistency,
                             Some(page_size),
                             paging_state,
+                            InFlightLimiting::Apply,
                         )
                         .await
                 }
@@ -770,11 +1336,17 @@ impl QueryPager {
             let span_creator = move || {
                 let span = RequestSpan::new_query(&query_ref.contents);
                 span.record_request_size(0);
+                span.record_labels(&query_ref.config.labels);
                 span
             };
 
             let worker = PagerWorker {
                 sender: sender.into(),
+                prefetch: worker_prefetch,
+                pages_sent: 0,
+                page_attempts: 0,
+                attempt_history: Vec::new(),
+                last_attempt_latency: Duration::ZERO,
                 page_query,
                 statement_info: routing_info,
                 query_is_idempotent: statement.config.is_idempotent,
@@ -783,24 +1355,40 @@ impl QueryPager {
                 retry_session,
                 #[cfg(feature = "metrics")]
                 metrics,
-                paging_state: PagingState::start(),
+                #[cfg(feature = "metrics")]
+                labels: statement.config.labels.clone(),
+                #[cfg(feature = "metrics")]
+                tablet_table: None,
+                #[cfg(feature = "metrics")]
+                table_label: routing.map(|routing| routing.table_label()),
+                paging_state,
                 history_listener: statement.config.history_listener.clone(),
                 current_request_id: None,
                 current_attempt_id: None,
+                cancellation_token: statement.config.cancellation_token.clone(),
+                deadline: statement.config.deadline,
+                rate_limiter,
+                page_permit: None,
                 parent_span,
                 span_creator,
+                #[cfg(feature = "opentelemetry-031")]
+                otel_parent,
+                #[cfg(feature = "opentelemetry-031")]
+                otel_retry_count: 0,
             };
 
             worker.work(cluster_state).await
         };
 
-        Self::new_from_worker_future(worker_task, receiver).await
+        Self::new_from_worker_future(worker_task, receiver, prefetch, Some(cursor_base)).await
     }
 
     pub(crate) async fn new_for_prepared_statement(
         config: PreparedPagerConfig,
     ) -> Result<Self, NextPageError> {
-        let (sender, receiver) = mpsc::channel::<Result<ReceivedPage, NextPageError>>(1);
+        let prefetch = Arc::new(PrefetchControl::new(config.prepared.config.pager_prefetch));
+        let (sender, receiver) =
+            mpsc::channel::<Result<ReceivedPage, NextPageError>>(prefetch.channel_capacity());
 
         let consistency = config
             .prepared
@@ -815,6 +1403,15 @@ impl QueryPager {
 
         let page_size = config.prepared.get_validated_page_size();
 
+        let cursor_base = Arc::new(PagerCursorBase {
+            statement: config.prepared.get_statement().to_owned(),
+            prepared_id: Some(config.prepared.get_id().clone()),
+            values: config.values.clone(),
+            consistency,
+            serial_consistency,
+            page_size,
+        });
+
         let load_balancing_policy = Arc::clone(
             config
                 .prepared
@@ -830,6 +1427,9 @@ impl QueryPager {
             .new_session();
 
         let parent_span = tracing::Span::current();
+        #[cfg(feature = "opentelemetry-031")]
+        let otel_parent = opentelemetry::Context::current();
+        let worker_prefetch = Arc::clone(&prefetch);
         let worker_task = async move {
             let prepared_ref = &config.prepared;
             let values_ref = &config.values;
@@ -868,6 +1468,7 @@ impl QueryPager {
                         serial_consistency,
                         Some(page_size),
                         paging_state,
+                        InFlightLimiting::Apply,
                     )
                     .await
             };
@@ -895,6 +1496,7 @@ impl QueryPager {
                     token,
                     serialized_values_size,
                 );
+                span.record_labels(&prepared_ref.config.labels);
                 if let Some(replicas) = replicas.as_ref() {
                     span.record_replicas(replicas.iter().map(|(node, shard)| (node, *shard)));
                 }
@@ -903,6 +1505,11 @@ impl QueryPager {
 
             let worker = PagerWorker {
                 sender: sender.into(),
+                prefetch: worker_prefetch,
+                pages_sent: 0,
+                page_attempts: 0,
+                attempt_history: Vec::new(),
+                last_attempt_latency: Duration::ZERO,
                 page_query,
                 statement_info,
                 query_is_idempotent: config.prepared.config.is_idempotent,
@@ -911,18 +1518,36 @@ impl QueryPager {
                 retry_session,
                 #[cfg(feature = "metrics")]
                 metrics: config.metrics,
-                paging_state: PagingState::start(),
+                #[cfg(feature = "metrics")]
+                labels: config.prepared.config.labels.clone(),
+                #[cfg(feature = "metrics")]
+                tablet_table: table_spec.map(|spec| {
+                    config
+                        .cluster_state
+                        .is_tablet_table(spec.ks_name(), spec.table_name())
+                }),
+                #[cfg(feature = "metrics")]
+                table_label: config.prepared.get_table_label(),
+                paging_state: config.paging_state,
                 history_listener: config.prepared.config.history_listener.clone(),
                 current_request_id: None,
                 current_attempt_id: None,
+                cancellation_token: config.prepared.config.cancellation_token.clone(),
+                deadline: config.prepared.config.deadline,
+                rate_limiter: config.rate_limiter,
+                page_permit: None,
                 parent_span,
                 span_creator,
+                #[cfg(feature = "opentelemetry-031")]
+                otel_parent,
+                #[cfg(feature = "opentelemetry-031")]
+                otel_retry_count: 0,
             };
 
             worker.work(config.cluster_state).await
         };
 
-        Self::new_from_worker_future(worker_task, receiver).await
+        Self::new_from_worker_future(worker_task, receiver, prefetch, Some(cursor_base)).await
     }
 
     pub(crate) async fn new_for_connection_query_iter(
@@ -931,13 +1556,17 @@ impl QueryPager {
         consistency: Consistency,
         serial_consistency: Option<SerialConsistency>,
     ) -> Result<Self, NextPageError> {
-        let (sender, receiver) = mpsc::channel::<Result<ReceivedPage, NextPageError>>(1);
+        let prefetch = Arc::new(PrefetchControl::new(query.config.pager_prefetch));
+        let (sender, receiver) =
+            mpsc::channel::<Result<ReceivedPage, NextPageError>>(prefetch.channel_capacity());
 
         let page_size = query.get_validated_page_size();
 
+        let worker_prefetch = Arc::clone(&prefetch);
         let worker_task = async move {
             let worker = SingleConnectionPagerWorker {
                 sender: sender.into(),
+                prefetch: worker_prefetch,
                 fetcher: |paging_state| {
                     connection.query_raw_with_consistency(
                         &query,
@@ -945,13 +1574,14 @@ impl QueryPager {
                         serial_consistency,
                         Some(page_size),
                         paging_state,
+                        InFlightLimiting::Exempt,
                     )
                 },
             };
             worker.work().await
         };
 
-        Self::new_from_worker_future(worker_task, receiver).await
+        Self::new_from_worker_future(worker_task, receiver, prefetch, None).await
     }
 
     pub(crate) async fn new_for_connection_execute_iter(
@@ -961,13 +1591,17 @@ impl QueryPager {
         consistency: Consistency,
         serial_consistency: Option<SerialConsistency>,
     ) -> Result<Self, NextPageError> {
-        let (sender, receiver) = mpsc::channel::<Result<ReceivedPage, NextPageError>>(1);
+        let prefetch = Arc::new(PrefetchControl::new(prepared.config.pager_prefetch));
+        let (sender, receiver) =
+            mpsc::channel::<Result<ReceivedPage, NextPageError>>(prefetch.channel_capacity());
 
         let page_size = prepared.get_validated_page_size();
 
+        let worker_prefetch = Arc::clone(&prefetch);
         let worker_task = async move {
             let worker = SingleConnectionPagerWorker {
                 sender: sender.into(),
+                prefetch: worker_prefetch,
                 fetcher: |paging_state| {
                     connection.execute_raw_with_consistency(
                         &prepared,
@@ -976,18 +1610,21 @@ impl QueryPager {
                         serial_consistency,
                         Some(page_size),
                         paging_state,
+                        InFlightLimiting::Exempt,
                     )
                 },
             };
             worker.work().await
         };
 
-        Self::new_from_worker_future(worker_task, receiver).await
+        Self::new_from_worker_future(worker_task, receiver, prefetch, None).await
     }
 
     async fn new_from_worker_future(
         worker_task: impl Future<Output = PageSendAttemptedProof> + Send + 'static,
         mut receiver: mpsc::Receiver<Result<ReceivedPage, NextPageError>>,
+        prefetch: Arc<PrefetchControl>,
+        cursor_base: Option<Arc<PagerCursorBase>>,
     ) -> Result<Self, NextPageError> {
         tokio::task::spawn(worker_task);
 
@@ -997,17 +1634,19 @@ impl QueryPager {
         // - That future is polled in a tokio::task which isn't going to be
         //   cancelled
         let page_received = receiver.recv().await.unwrap()?;
+        prefetch.on_page_received(page_received.bytes_size());
         let raw_rows_with_deserialized_metadata = page_received.rows.deserialize_metadata()?;
 
         Ok(Self {
             current_page: RawRowLendingIterator::new(raw_rows_with_deserialized_metadata),
+            current_page_paging_state: page_received.paging_state_response,
             page_receiver: receiver,
-            tracing_ids: if let Some(tracing_id) = page_received.tracing_id {
-                vec![tracing_id]
-            } else {
-                Vec::new()
-            },
-            request_coordinators: Vec::from_iter(page_received.request_coordinator),
+            prefetch,
+            next_page_requested: false,
+            tracing_ids: Vec::from_iter(page_received.execution_info.tracing_id),
+            request_coordinators: Vec::from_iter(page_received.execution_info.coordinator.clone()),
+            current_page_execution_info: page_received.execution_info,
+            cursor_base,
         })
     }
 
@@ -1023,17 +1662,271 @@ impl QueryPager {
         self.request_coordinators.iter()
     }
 
+    /// Returns information about how the current page, i.e. the one the next row
+    /// comes from, was fetched: its coordinator, number of attempts, latency and warnings.
+    ///
+    /// Once the current page is exhausted, this still describes it until the next page is received.
+    #[inline]
+    pub fn current_page_execution_info(&self) -> &PageExecutionInfo {
+        &self.current_page_execution_info
+    }
+
     /// Returns specification of row columns
     #[inline]
     pub fn column_specs(&self) -> ColumnSpecs<'_, '_> {
         ColumnSpecs::new(self.current_page.metadata().col_specs())
     }
 
+    /// Returns the number of rows of the current page which have not been consumed yet.
+    ///
+    /// Once it drops to zero, consuming the next row makes the pager move to the next page.
+    /// This is the moment to take a [cursor](QueryPager::cursor) which resumes
+    /// exactly where the consumption stopped.
+    #[inline]
+    pub fn rows_remaining_in_current_page(&self) -> usize {
+        self.current_page.rows_remaining()
+    }
+
+    /// Snapshots the position of the pager into a [PagerCursor], which can be serialized
+    /// into an opaque byte blob and later used to resume the paged query,
+    /// possibly by a different [Session](crate::client::session::Session) instance
+    /// - see [Session::resume_iter](crate::client::session::Session::resume_iter).
+    ///
+    /// The cursor points to the beginning of the page following the current one.
+    /// Rows of the current page which have not been consumed yet are not covered
+    /// by the cursor, so take it once [QueryPager::rows_remaining_in_current_page]
+    /// returns zero in order not to lose any rows.
+    ///
+    /// Returns None if the current page is the last one.
+    pub fn cursor(&self) -> Option<PagerCursor> {
+        let base = self.cursor_base.as_ref()?;
+        match &self.current_page_paging_state {
+            PagingStateResponse::HasMorePages { state } => Some(PagerCursor {
+                base: Arc::clone(base),
+                paging_state: state.clone(),
+            }),
+            PagingStateResponse::NoMorePages => None,
+        }
+    }
+
     fn is_current_page_exhausted(&self) -> bool {
         self.current_page.rows_remaining() == 0
     }
 }
 
+// Everything needed to resume a paged query, apart from its position.
+#[derive(Debug, PartialEq, Eq)]
+struct PagerCursorBase {
+    statement: String,
+    prepared_id: Option<Bytes>,
+    values: SerializedValues,
+    consistency: Consistency,
+    serial_consistency: Option<SerialConsistency>,
+    page_size: PageSize,
+}
+
+/// A snapshot of a [QueryPager]'s position, which allows resuming the paged query later.
+///
+/// Apart from the [PagingState], the cursor holds the statement, its bound values,
+/// its consistency and its page size, so that the query can be resumed by any
+/// [Session](crate::client::session::Session) connected to the same cluster -
+/// see [Session::resume_iter](crate::client::session::Session::resume_iter).
+/// The cursor can be converted into an opaque byte blob with [PagerCursor::to_bytes]
+/// and restored with [PagerCursor::from_bytes], e.g. to be passed to the client
+/// of a stateless REST service as a page token.
+///
+/// The blob is neither encrypted nor signed: it reveals the statement and the bound values,
+/// and a client can tamper with it. Do not hand it out to untrusted parties as is.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # use scylla::client::pager::PagerCursor;
+/// # use std::error::Error;
+/// # async fn check_only_compiles(session: &Session, other_session: &Session) -> Result<(), Box<dyn Error>> {
+/// use futures::StreamExt;
+///
+/// let mut rows_stream = session
+///     .query_iter("SELECT a, b FROM ks.t", &[])
+///     .await?
+///     .rows_stream::<(i32, i32)>()?;
+///
+/// // Consume the first page.
+/// while let Some(next_row_res) = rows_stream.next().await {
+///     let (a, b): (i32, i32) = next_row_res?;
+///     println!("a, b: {}, {}", a, b);
+///     if rows_stream.rows_remaining_in_current_page() == 0 {
+///         break;
+///     }
+/// }
+///
+/// if let Some(cursor) = rows_stream.cursor() {
+///     let token: Vec<u8> = cursor.to_bytes();
+///
+///     // Later, possibly in a different process:
+///     let cursor = PagerCursor::from_bytes(&token)?;
+///     let mut rows_stream = other_session
+///         .resume_iter(&cursor)
+///         .await?
+///         .rows_stream::<(i32, i32)>()?;
+///     // Consume the next page...
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PagerCursor {
+    base: Arc<PagerCursorBase>,
+    paging_state: PagingState,
+}
+
+impl PagerCursor {
+    // Bumped whenever the layout of the serialized cursor changes.
+    const FORMAT_VERSION: u8 = 1;
+
+    /// Returns the CQL text of the paged statement.
+    #[inline]
+    pub fn get_statement(&self) -> &str {
+        &self.base.statement
+    }
+
+    /// Returns the paging state to resume the paged query with.
+    #[inline]
+    pub fn get_paging_state(&self) -> &PagingState {
+        &self.paging_state
+    }
+
+    /// Serializes the cursor into an opaque byte blob,
+    /// which can be turned back into the cursor with [PagerCursor::from_bytes].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let base = &*self.base;
+        let mut buf = Vec::new();
+        buf.put_u8(Self::FORMAT_VERSION);
+        // Lengths always fit: the statement and the values have already been
+        // sent in a request, and the paging state has been received in a response.
+        types::write_long_string(&base.statement, &mut buf).unwrap();
+        types::write_bytes_opt(base.prepared_id.as_deref(), &mut buf).unwrap();
+        types::write_consistency(base.consistency, &mut buf);
+        match base.serial_consistency {
+            Some(sc) => types::write_serial_consistency(sc, &mut buf),
+            None => buf.put_i16(-1),
+        }
+        types::write_int(base.page_size.inner(), &mut buf);
+        types::write_short(base.values.element_count(), &mut buf);
+        for value in base.values.iter() {
+            match value {
+                RawValue::Null => types::write_int(-1, &mut buf),
+                RawValue::Unset => types::write_int(-2, &mut buf),
+                RawValue::Value(v) => types::write_bytes(v, &mut buf).unwrap(),
+            }
+        }
+        types::write_bytes_opt(self.paging_state.as_bytes_slice().map(|s| &**s), &mut buf).unwrap();
+        buf
+    }
+
+    /// Deserializes the cursor from a byte blob returned by [PagerCursor::to_bytes].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PagerCursorParseError> {
+        let (&version, mut buf) =
+            bytes
+                .split_first()
+                .ok_or(LowLevelDeserializationError::TooFewBytesReceived {
+                    expected: 1,
+                    received: 0,
+                })?;
+        if version != Self::FORMAT_VERSION {
+            return Err(PagerCursorParseError::UnsupportedVersion(version));
+        }
+        let buf = &mut buf;
+
+        let statement = types::read_long_string(buf)?.to_owned();
+        let prepared_id = types::read_bytes_opt(buf)?.map(Bytes::copy_from_slice);
+        let consistency = types::read_consistency(buf)?;
+        let serial_consistency = match types::read_short(buf)? as i16 {
+            -1 => None,
+            raw => Some(
+                SerialConsistency::try_from(raw)
+                    .map_err(|_| PagerCursorParseError::BadSerialConsistency(raw))?,
+            ),
+        };
+        let raw_page_size = types::read_int(buf)?;
+        let page_size = PageSize::new(raw_page_size)
+            .map_err(|_| PagerCursorParseError::BadPageSize(raw_page_size))?;
+
+        let values_count = types::read_short(buf)?;
+        let raw_values = (0..values_count)
+            .map(|_| types::read_value(buf))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (values, ()) = SerializedValues::from_closure(|writer| {
+            for value in raw_values {
+                let cell_writer = writer.make_cell_writer();
+                match value {
+                    RawValue::Null => cell_writer.set_null(),
+                    RawValue::Unset => cell_writer.set_unset(),
+                    // The value has just been read from a [value], so its length fits.
+                    RawValue::Value(v) => cell_writer.set_value(v).unwrap(),
+                };
+            }
+            Ok(())
+        })
+        // There are at most u16::MAX values, so it cannot fail.
+        .unwrap();
+
+        let paging_state = match types::read_bytes_opt(buf)? {
+            Some(raw) => PagingState::new_from_raw_bytes(raw),
+            None => PagingState::start(),
+        };
+        if !buf.is_empty() {
+            return Err(PagerCursorParseError::TrailingBytes(buf.len()));
+        }
+
+        Ok(Self {
+            base: Arc::new(PagerCursorBase {
+                statement,
+                prepared_id,
+                values,
+                consistency,
+                serial_consistency,
+                page_size,
+            }),
+            paging_state,
+        })
+    }
+
+    /// Returns the unprepared statement to resume the query with,
+    /// or None if the query was executed as a prepared statement.
+    pub(crate) fn to_statement(&self) -> Option<Statement> {
+        if self.base.prepared_id.is_some() {
+            return None;
+        }
+        let mut statement = Statement::new(self.base.statement.clone());
+        self.base.apply_config(&mut statement.config);
+        statement.set_page_size(self.base.page_size.inner());
+        Some(statement)
+    }
+
+    /// Adjusts the prepared statement to resume the query with, checking that
+    /// it is the same statement that was paged when the cursor was taken.
+    pub(crate) fn configure_prepared(&self, prepared: &mut PreparedStatement) -> bool {
+        if self.base.prepared_id.as_ref() != Some(prepared.get_id()) {
+            return false;
+        }
+        self.base.apply_config(&mut prepared.config);
+        prepared.set_page_size(self.base.page_size.inner());
+        true
+    }
+
+    pub(crate) fn values(&self) -> &SerializedValues {
+        &self.base.values
+    }
+}
+
+impl PagerCursorBase {
+    fn apply_config(&self, config: &mut StatementConfig) {
+        config.consistency = Some(self.consistency);
+        config.serial_consistency = Some(self.serial_consistency);
+    }
+}
+
 /// Returned by [QueryPager::rows_stream].
 ///
 /// Implements [Stream], but only permits deserialization of owned types.
@@ -1083,11 +1976,32 @@ impl<RowT> TypedRowStream<RowT> {
         self.raw_row_lending_stream.request_coordinators()
     }
 
+    /// Returns information about how the page of the most recently returned row was fetched.
+    /// See [QueryPager::current_page_execution_info].
+    #[inline]
+    pub fn current_page_execution_info(&self) -> &PageExecutionInfo {
+        self.raw_row_lending_stream.current_page_execution_info()
+    }
+
     /// Returns specification of row columns
     #[inline]
     pub fn column_specs(&self) -> ColumnSpecs {
         self.raw_row_lending_stream.column_specs()
     }
+
+    /// Returns the number of rows of the current page which have not been consumed yet.
+    /// See [QueryPager::rows_remaining_in_current_page].
+    #[inline]
+    pub fn rows_remaining_in_current_page(&self) -> usize {
+        self.raw_row_lending_stream.rows_remaining_in_current_page()
+    }
+
+    /// Snapshots the position of the stream into a [PagerCursor].
+    /// See [QueryPager::cursor].
+    #[inline]
+    pub fn cursor(&self) -> Option<PagerCursor> {
+        self.raw_row_lending_stream.cursor()
+    }
 }
 
 /// Stream implementation for TypedRowStream.
@@ -1144,3 +2058,190 @@ pub enum NextRowError {
     #[error("Row deserialization error: {0}")]
     RowDeserializationError(#[from] DeserializationError),
 }
+
+/// An error returned by [PagerCursor::from_bytes] when the blob does not hold a valid cursor.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum PagerCursorParseError {
+    /// The blob was produced by an incompatible version of the driver.
+    #[error("Unsupported pager cursor format version: {0}")]
+    UnsupportedVersion(u8),
+
+    /// The blob is truncated or otherwise malformed.
+    #[error("Malformed pager cursor: {0}")]
+    Malformed(#[from] LowLevelDeserializationError),
+
+    /// The blob holds an invalid serial consistency.
+    #[error("Malformed pager cursor: invalid serial consistency {0}")]
+    BadSerialConsistency(i16),
+
+    /// The blob holds an invalid page size.
+    #[error("Malformed pager cursor: invalid page size {0}")]
+    BadPageSize(i32),
+
+    /// The blob has unexpected bytes after the cursor.
+    #[error("Malformed pager cursor: {0} trailing bytes")]
+    TrailingBytes(usize),
+}
+
+impl From<std::io::Error> for PagerCursorParseError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Malformed(err.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use scylla_cql::frame::request::query::PagingState;
+    use scylla_cql::frame::response::result::{ColumnType, NativeType};
+    use scylla_cql::frame::types::{Consistency, SerialConsistency};
+    use scylla_cql::serialize::row::SerializedValues;
+    use scylla_cql::value::MaybeUnset;
+
+    use super::{
+        PagerCursor, PagerCursorBase, PagerCursorParseError, PrefetchControl, QueryPager,
+        TypeCheckError,
+    };
+    use crate::statement::{PageSize, PagerPrefetch};
+
+    #[test]
+    fn pager_cursor_bytes_roundtrip() {
+        let mut values = SerializedValues::new();
+        values
+            .add_value(&42_i32, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        values
+            .add_value(&None::<i32>, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        values
+            .add_value(
+                &MaybeUnset::<i32>::Unset,
+                &ColumnType::Native(NativeType::Int),
+            )
+            .unwrap();
+
+        let cursors = [
+            PagerCursor {
+                base: Arc::new(PagerCursorBase {
+                    statement: "SELECT a FROM ks.t WHERE p = ? AND c > ? AND d < ?".to_owned(),
+                    prepared_id: Some(Bytes::from_static(b"some id")),
+                    values,
+                    consistency: Consistency::LocalQuorum,
+                    serial_consistency: Some(SerialConsistency::LocalSerial),
+                    page_size: PageSize::new(100).unwrap(),
+                }),
+                paging_state: PagingState::new_from_raw_bytes(&b"some state"[..]),
+            },
+            PagerCursor {
+                base: Arc::new(PagerCursorBase {
+                    statement: "SELECT a FROM ks.t".to_owned(),
+                    prepared_id: None,
+                    values: SerializedValues::new(),
+                    consistency: Consistency::One,
+                    serial_consistency: None,
+                    page_size: PageSize::default(),
+                }),
+                paging_state: PagingState::start(),
+            },
+        ];
+
+        for cursor in cursors {
+            let bytes = cursor.to_bytes();
+            assert_eq!(PagerCursor::from_bytes(&bytes).unwrap(), cursor);
+
+            assert!(matches!(
+                PagerCursor::from_bytes(&bytes[..bytes.len() - 1]),
+                Err(PagerCursorParseError::Malformed(_))
+            ));
+
+            let mut with_trailing_bytes = bytes.clone();
+            with_trailing_bytes.push(0);
+            assert!(matches!(
+                PagerCursor::from_bytes(&with_trailing_bytes),
+                Err(PagerCursorParseError::TrailingBytes(1))
+            ));
+
+            let mut with_other_version = bytes;
+            with_other_version[0] += 1;
+            assert!(matches!(
+                PagerCursor::from_bytes(&with_other_version),
+                Err(PagerCursorParseError::UnsupportedVersion(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn prefetch_limits_pages_ahead_of_consumer() {
+        let strict = PrefetchControl::new(PagerPrefetch {
+            pages: 0,
+            max_bytes: None,
+        });
+        assert!(strict.may_fetch(1));
+        assert!(!strict.may_fetch(2));
+        strict.on_page_requested();
+        assert!(strict.may_fetch(2));
+        assert!(!strict.may_fetch(3));
+
+        let ahead = PrefetchControl::new(PagerPrefetch {
+            pages: 2,
+            max_bytes: None,
+        });
+        assert!(ahead.may_fetch(3));
+        assert!(!ahead.may_fetch(4));
+        ahead.on_page_requested();
+        assert!(ahead.may_fetch(4));
+        assert!(!ahead.may_fetch(5));
+    }
+
+    #[test]
+    fn prefetch_limits_prefetched_bytes() {
+        let prefetch = PrefetchControl::new(PagerPrefetch {
+            pages: 10,
+            max_bytes: Some(1000.try_into().unwrap()),
+        });
+        prefetch.on_page_sent(600);
+        assert!(prefetch.may_fetch(2));
+        prefetch.on_page_sent(600);
+        // The bound is exceeded, so only the page the consumer waits for may be fetched.
+        assert!(!prefetch.may_fetch(3));
+        prefetch.on_page_requested();
+        assert!(prefetch.may_fetch(2));
+        assert!(!prefetch.may_fetch(3));
+        prefetch.on_page_received(600);
+        assert!(prefetch.may_fetch(3));
+    }
+
+    #[tokio::test]
+    async fn prefetch_wakes_worker_on_request() {
+        let prefetch = Arc::new(PrefetchControl::new(PagerPrefetch {
+            pages: 0,
+            max_bytes: None,
+        }));
+        let worker = tokio::spawn({
+            let prefetch = Arc::clone(&prefetch);
+            async move { prefetch.wait_until_may_fetch(2).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!worker.is_finished());
+
+        prefetch.on_page_requested();
+        worker.await.unwrap();
+    }
+
+    // Compile-time check that `into_owned_stream` yields a stream which can be
+    // moved into spawned tasks and stored in structs.
+    #[test]
+    fn owned_stream_is_send_and_static() {
+        fn assert_send_static<T: Send + 'static>(_: &T) {}
+
+        #[expect(dead_code)]
+        fn check(pager: QueryPager) -> Result<(), TypeCheckError> {
+            let stream = pager.into_owned_stream::<(i32, String)>()?;
+            assert_send_static(&stream);
+            Ok(())
+        }
+    }
+}
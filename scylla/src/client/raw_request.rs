@@ -0,0 +1,74 @@
+//! An escape hatch for sending custom CQL requests, which the driver has no typed support for,
+//! e.g. to experiment with new server features. Requests are sent with
+//! [`Session::send_raw_request`](crate::client::session::Session::send_raw_request).
+//!
+//! **This API is unstable**: it is only available with the `unstable-raw-requests` feature
+//! and may change or be removed in any release.
+
+use std::collections::HashMap;
+
+use bytes::Bytes;
+use uuid::Uuid;
+
+pub use scylla_cql::frame::request::RequestOpcode;
+pub use scylla_cql::frame::response::ResponseOpcode;
+
+/// A CQL request with an already serialized body.
+///
+/// The driver takes care of framing: it assigns a stream id, applies
+/// the connection's compression and routes the response back to the caller.
+/// The body is otherwise sent as is, so it has to be valid for the opcode.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RawRequest {
+    /// Opcode of the request.
+    pub opcode: RequestOpcode,
+
+    /// Serialized body of the request.
+    pub body: Bytes,
+
+    /// Whether to request tracing of the request.
+    pub tracing: bool,
+}
+
+impl RawRequest {
+    /// Creates a request with the given opcode and body, without tracing.
+    pub fn new(opcode: RequestOpcode, body: impl Into<Bytes>) -> Self {
+        Self {
+            opcode,
+            body: body.into(),
+            tracing: false,
+        }
+    }
+
+    /// Sets whether to request tracing of the request.
+    pub fn with_tracing(mut self, tracing: bool) -> Self {
+        self.tracing = tracing;
+        self
+    }
+}
+
+/// A CQL response to a [`RawRequest`].
+///
+/// The body is decompressed and stripped of the frame body extensions
+/// (tracing id, warnings and custom payload), which are parsed into the other fields.
+/// It is not parsed any further, so e.g. an `ERROR` response is returned as is
+/// rather than as an error.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RawResponse {
+    /// Opcode of the response.
+    pub opcode: ResponseOpcode,
+
+    /// Serialized body of the response.
+    pub body: Bytes,
+
+    /// Tracing id, if tracing was requested.
+    pub tracing_id: Option<Uuid>,
+
+    /// Warnings returned by the server.
+    pub warnings: Vec<String>,
+
+    /// Custom payload returned by the server, if any.
+    pub custom_payload: Option<HashMap<String, Bytes>>,
+}
@@ -0,0 +1,189 @@
+//! Types describing the progress of awaiting schema agreement, reported by
+//! [`Session::await_schema_agreement_with_progress`](crate::client::session::Session::await_schema_agreement_with_progress),
+//! and options of awaiting it, see
+//! [`Session::await_schema_agreement_with_options`](crate::client::session::Session::await_schema_agreement_with_options).
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+/// Schema version reported by a single node during a schema agreement check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct NodeSchemaVersion {
+    /// Host ID of the node.
+    pub host_id: Uuid,
+
+    /// Schema version reported by the node, or `None` if it could not be read
+    /// because all connections to the node turned out to be broken.
+    pub schema_version: Option<Uuid>,
+}
+
+impl Display for NodeSchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.schema_version {
+            Some(version) => write!(f, "{} (schema version {})", self.host_id, version),
+            None => write!(f, "{} (schema version unknown)", self.host_id),
+        }
+    }
+}
+
+/// State of schema agreement after a single check, passed to the progress callback
+/// of [`Session::await_schema_agreement_with_progress`](crate::client::session::Session::await_schema_agreement_with_progress).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct SchemaAgreementProgress {
+    /// Time elapsed since awaiting schema agreement started.
+    pub elapsed: Duration,
+
+    /// Schema versions reported by all reachable nodes.
+    pub node_versions: Vec<NodeSchemaVersion>,
+}
+
+impl SchemaAgreementProgress {
+    /// Returns the schema version reported by most nodes,
+    /// or `None` if no node has reported its version.
+    pub fn prevailing_version(&self) -> Option<Uuid> {
+        let mut counts: HashMap<Uuid, usize> = HashMap::new();
+        let mut prevailing: Option<(Uuid, usize)> = None;
+        for version in self
+            .node_versions
+            .iter()
+            .filter_map(|node| node.schema_version)
+        {
+            let count = counts.entry(version).or_default();
+            *count += 1;
+            // On a tie, the version reported first wins.
+            if prevailing.is_none_or(|(_, max)| *count > max) {
+                prevailing = Some((version, *count));
+            }
+        }
+        prevailing.map(|(version, _)| version)
+    }
+
+    /// Returns nodes which do not agree on the [prevailing version](Self::prevailing_version),
+    /// including the ones whose version is unknown.
+    pub fn lagging_nodes(&self) -> impl Iterator<Item = &NodeSchemaVersion> {
+        let prevailing = self.prevailing_version();
+        self.node_versions
+            .iter()
+            .filter(move |node| node.schema_version.is_none() || node.schema_version != prevailing)
+    }
+
+    /// Returns true if all nodes which reported their version agree on it.
+    pub fn is_in_agreement(&self) -> bool {
+        agreed_version(&self.node_versions).is_some()
+    }
+}
+
+/// Options of a single wait for schema agreement, overriding the session-level
+/// [`schema_agreement_timeout`](crate::client::session::SessionConfig::schema_agreement_timeout)
+/// and [`schema_agreement_interval`](crate::client::session::SessionConfig::schema_agreement_interval).
+///
+/// See [`Session::await_schema_agreement_with_options`](crate::client::session::Session::await_schema_agreement_with_options).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaAgreementOptions {
+    timeout: Option<Duration>,
+    interval: Option<Duration>,
+    required_hosts: Vec<Uuid>,
+}
+
+impl SchemaAgreementOptions {
+    /// Creates options which use the session-level timeout and interval,
+    /// and don't require any hosts to take part in the agreement.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long to wait for schema agreement before giving up.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the interval between subsequent schema agreement checks.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets hosts which must take part in the agreement.
+    ///
+    /// By default, nodes whose connections are all broken are ignored, and the agreement
+    /// is reached once all other nodes report the same schema version. Each of the required
+    /// hosts has to report its schema version in every check, otherwise awaiting the agreement fails.
+    pub fn with_required_hosts(mut self, host_ids: impl IntoIterator<Item = Uuid>) -> Self {
+        self.required_hosts = host_ids.into_iter().collect();
+        self
+    }
+
+    /// Gets the timeout, or `None` if the session-level timeout is used.
+    pub fn get_timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Gets the interval between checks, or `None` if the session-level interval is used.
+    pub fn get_interval(&self) -> Option<Duration> {
+        self.interval
+    }
+
+    /// Gets the host IDs of the hosts which must take part in the agreement.
+    pub fn get_required_hosts(&self) -> &[Uuid] {
+        &self.required_hosts
+    }
+}
+
+/// Returns the version all nodes that reported their version agree on, if there is one.
+pub(crate) fn agreed_version(node_versions: &[NodeSchemaVersion]) -> Option<Uuid> {
+    let mut versions = node_versions.iter().filter_map(|node| node.schema_version);
+    let first = versions.next()?;
+    versions.all(|v| v == first).then_some(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use super::{NodeSchemaVersion, SchemaAgreementProgress};
+
+    #[test]
+    fn lagging_nodes_are_the_ones_off_the_prevailing_version() {
+        let (old, new) = (Uuid::new_v4(), Uuid::new_v4());
+        let node = |schema_version| NodeSchemaVersion {
+            host_id: Uuid::new_v4(),
+            schema_version,
+        };
+        let progress = SchemaAgreementProgress {
+            elapsed: Duration::from_secs(1),
+            node_versions: vec![
+                node(Some(old)),
+                node(Some(new)),
+                node(None),
+                node(Some(new)),
+            ],
+        };
+
+        assert_eq!(progress.prevailing_version(), Some(new));
+        assert!(!progress.is_in_agreement());
+        let lagging: Vec<_> = progress.lagging_nodes().cloned().collect();
+        assert_eq!(
+            lagging,
+            vec![
+                progress.node_versions[0].clone(),
+                progress.node_versions[2].clone()
+            ]
+        );
+
+        // Nodes with unknown versions do not prevent agreement.
+        let progress = SchemaAgreementProgress {
+            elapsed: Duration::from_secs(1),
+            node_versions: vec![node(Some(old)), node(None), node(Some(old))],
+        };
+        assert!(progress.is_in_agreement());
+        assert_eq!(progress.lagging_nodes().count(), 1);
+    }
+}
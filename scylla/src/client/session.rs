@@ -2,68 +2,100 @@
 //! It manages all connections to the cluster and allows to execute CQL requests.
 
 use super::execution_profile::{ExecutionProfile, ExecutionProfileHandle, ExecutionProfileInner};
-use super::pager::{PreparedPagerConfig, QueryPager};
-use super::{Compression, PoolSize, SelfIdentity, WriteCoalescingDelay};
+use super::interceptor::{
+    AttemptInfo, InterceptedRequest, InterceptorChain, RequestHooks, RequestInterceptor,
+    StatementInterceptor,
+};
+use super::pager::{PagerCursor, PreparedPagerConfig, QueryPager};
+use super::schema_agreement::{
+    agreed_version, NodeSchemaVersion, SchemaAgreementOptions, SchemaAgreementProgress,
+};
+use super::tls::{CertificateVerifier, TlsContextProvider};
+use super::{
+    Compression, NodePoolStatus, PoolSize, ProtocolNegotiationPolicy, SelfIdentity,
+    WriteCoalescingDelay,
+};
 use crate::authentication::AuthenticatorProvider;
 #[cfg(feature = "unstable-cloud")]
 use crate::cloud::CloudConfig;
 #[cfg(feature = "unstable-cloud")]
 use crate::cluster::node::CloudEndpoint;
 use crate::cluster::node::{InternalKnownNode, KnownNode, NodeRef};
-use crate::cluster::{Cluster, ClusterNeatDebug, ClusterState};
+use crate::cluster::{Cluster, ClusterEvent, ClusterNeatDebug, ClusterState};
 use crate::errors::{
-    BadQuery, BrokenConnectionError, ExecutionError, MetadataError, NewSessionError,
-    PagerExecutionError, PrepareError, RequestAttemptError, RequestError, SchemaAgreementError,
-    TracingError, UseKeyspaceError,
+    AttemptContext, BadQuery, BrokenConnectionError, ChunkedBatchError, DrainNodeError,
+    ExecutionError, FailedBatchChunk, MetadataError, NewSessionError, PagerExecutionError,
+    PrepareError, RequestAttemptError, RequestError, SchemaAgreementError, TracingError,
+    UseKeyspaceError,
 };
+use crate::frame::response::event::SchemaChangeEvent;
 use crate::frame::response::result;
 use crate::network::tls::TlsProvider;
-use crate::network::{Connection, ConnectionConfig, PoolConfig, VerifiedKeyspaceName};
+use crate::network::{
+    Connection, ConnectionConfig, InFlightLimiting, PoolConfig, VerifiedKeyspaceName,
+};
 use crate::observability::driver_tracing::RequestSpan;
 use crate::observability::history::{self, HistoryListener};
 #[cfg(feature = "metrics")]
-use crate::observability::metrics::Metrics;
+use crate::observability::metrics::{Metrics, DEFAULT_FRAME_SIZE_BUCKETS};
+#[cfg(feature = "opentelemetry-031")]
+use crate::observability::opentelemetry::{end_attempt_span, start_attempt_span};
+use crate::observability::repreparation::RepreparationListener;
 use crate::observability::tracing::TracingInfo;
 use crate::policies::address_translator::AddressTranslator;
 use crate::policies::host_filter::HostFilter;
+use crate::policies::host_id_change::HostIdChangePolicy;
 use crate::policies::load_balancing::{self, RoutingInfo};
+use crate::policies::rate_limiter::RequestRateLimiter;
+use crate::policies::reconnection::{ExponentialReconnectionPolicy, ReconnectionPolicy};
 use crate::policies::retry::{RequestInfo, RetryDecision, RetrySession};
 use crate::policies::speculative_execution;
 use crate::policies::timestamp_generator::TimestampGenerator;
 use crate::response::query_result::{MaybeFirstRowError, QueryResult, RowsError};
 use crate::response::{
     Coordinator, NonErrorQueryResponse, PagingState, PagingStateResponse, QueryResponse,
+    RequestAttempt,
+};
+#[cfg(feature = "metrics")]
+use crate::routing::locator::tablets::has_tablet_info;
+use crate::routing::partitioner::CustomPartitioner;
+use crate::routing::{RoutingExplanation, RoutingTarget, Shard, ShardAwarePortRange};
+use crate::statement::batch::{batch_values, counter_update, using_clause};
+use crate::statement::batch::{
+    chunking as batch_chunking, Batch, BatchChunking, BatchStatement, BatchType, TypedBatch,
 };
-use crate::routing::partitioner::PartitionerName;
-use crate::routing::{Shard, ShardAwarePortRange};
-use crate::statement::batch::batch_values;
-use crate::statement::batch::{Batch, BatchStatement};
 use crate::statement::prepared::{PartitionKeyError, PreparedStatement};
 use crate::statement::unprepared::Statement;
 use crate::statement::{Consistency, PageSize, StatementConfig};
 use arc_swap::ArcSwapOption;
 use futures::future::join_all;
 use futures::future::try_join_all;
+use futures::stream::{self, Stream, StreamExt as _};
+use futures::FutureExt as _;
 use itertools::Itertools;
+#[cfg(feature = "opentelemetry-031")]
+use opentelemetry::context::FutureExt as _;
 use scylla_cql::frame::response::NonErrorResponse;
 use scylla_cql::serialize::batch::BatchValues;
 use scylla_cql::serialize::row::{SerializeRow, SerializedValues};
 use std::borrow::Borrow;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
 use std::net::{IpAddr, SocketAddr};
-use std::num::NonZeroU32;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::timeout;
-#[cfg(feature = "unstable-cloud")]
-use tracing::warn;
-use tracing::{debug, error, trace, trace_span, Instrument};
+use tracing::{debug, error, trace, trace_span, warn, Instrument};
 use uuid::Uuid;
 
 pub(crate) const TABLET_CHANNEL_SIZE: usize = 8192;
 
 const TRACING_QUERY_PAGE_SIZE: i32 = 1024;
 
+// How often `Session::drain_node` checks whether requests to the drained node have finished.
+const DRAIN_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
 /// `Session` manages connections to the cluster and allows to execute CQL requests.
 pub struct Session {
     cluster: Cluster,
@@ -78,6 +110,9 @@ pub struct Session {
     tracing_info_fetch_attempts: NonZeroU32,
     tracing_info_fetch_interval: Duration,
     tracing_info_fetch_consistency: Consistency,
+    interceptors: InterceptorChain,
+    request_interceptors: Vec<Arc<dyn RequestInterceptor>>,
+    request_rate_limiter: Option<Arc<dyn RequestRateLimiter>>,
 }
 
 /// This implementation deliberately omits some details from Cluster in order
@@ -120,6 +155,9 @@ impl std::fmt::Debug for Session {
             "tracing_info_fetch_consistency",
             &self.tracing_info_fetch_consistency,
         )
+        .field("interceptors", &self.interceptors)
+        .field("request_interceptors", &self.request_interceptors)
+        .field("request_rate_limiter", &self.request_rate_limiter)
         .finish()
     }
 }
@@ -178,6 +216,11 @@ pub struct SessionConfig {
     /// If it's not supported by database server Session will fall back to no compression.
     pub compression: Option<Compression>,
 
+    /// Minimal size (in bytes) of a request body to compress it, if compression is enabled.
+    /// Smaller requests are sent uncompressed, avoiding wasted CPU and latency for tiny requests.
+    /// By default all requests are compressed.
+    pub compression_threshold: usize,
+
     /// Whether to set the nodelay TCP flag.
     pub tcp_nodelay: bool,
 
@@ -186,6 +229,29 @@ pub struct SessionConfig {
     /// If `None`, no TCP keepalive messages are sent.
     pub tcp_keepalive_interval: Option<Duration>,
 
+    /// Time between subsequent TCP keepalive probes, sent once a connection
+    /// has been idle for [`tcp_keepalive_interval`](Self::tcp_keepalive_interval)
+    /// until it responds. Has no effect if TCP keepalives are disabled.
+    pub tcp_keepalive_probe_interval: Duration,
+
+    /// Number of unanswered TCP keepalive probes after which the connection is dropped.
+    /// Has no effect if TCP keepalives are disabled. Not supported on Windows.
+    pub tcp_keepalive_probe_count: u32,
+
+    /// TCP user timeout (`TCP_USER_TIMEOUT` socket option), i.e. maximum time for which
+    /// data sent on a connection may remain unacknowledged before the connection is dropped.
+    /// If `None`, the system default is used.
+    /// Supported on Linux, Android and Fuchsia only, ignored on other platforms.
+    pub tcp_user_timeout: Option<Duration>,
+
+    /// Size of the send buffer of connection sockets (`SO_SNDBUF` socket option), in bytes.
+    /// If `None`, the system default is used.
+    pub tcp_send_buffer_size: Option<u32>,
+
+    /// Size of the receive buffer of connection sockets (`SO_RCVBUF` socket option), in bytes.
+    /// If `None`, the system default is used.
+    pub tcp_recv_buffer_size: Option<u32>,
+
     /// Handle to the default execution profile, which is used
     /// for all statements that do not specify an execution profile.
     pub default_execution_profile_handle: ExecutionProfileHandle,
@@ -204,6 +270,13 @@ pub struct SessionConfig {
     /// TLS context used configure TLS connections to DB nodes.
     pub tls_context: Option<TlsContext>,
 
+    /// Provider of the TLS context used to configure new TLS connections to DB nodes,
+    /// allowing the context to be refreshed at runtime. Takes precedence over `tls_context`.
+    pub tls_context_provider: Option<Arc<dyn TlsContextProvider>>,
+
+    /// Custom verifier of the certificates presented by DB nodes in TLS connections.
+    pub tls_certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+
     /// Custom authenticator provider to create an authenticator instance
     /// upon session creation.
     pub authenticator: Option<Arc<dyn AuthenticatorProvider>>,
@@ -218,6 +291,11 @@ pub struct SessionConfig {
     /// The default is `PerShard(1)`, which is the recommended setting for ScyllaDB clusters.
     pub connection_pool_size: PoolSize,
 
+    /// Policy deciding how long the connection pool of a node waits before it tries
+    /// to open connections again, after opening them failed.
+    /// The default is [`ExponentialReconnectionPolicy`] with default delays.
+    pub reconnection_policy: Arc<dyn ReconnectionPolicy>,
+
     /// If true, prevents the driver from connecting to the shard-aware port, even if the node supports it.
     /// Generally, this options is best left as default (false).
     pub disallow_shard_aware_port: bool,
@@ -235,6 +313,34 @@ pub struct SessionConfig {
     /// Custom timeout for requests that query metadata.
     pub metadata_request_serverside_timeout: Option<Duration>,
 
+    /// Maximum number of requests sent concurrently on a single connection.
+    /// Requests exceeding the limit are sent on another connection to the same shard
+    /// if one is available, or wait until one of the in-flight requests completes.
+    /// If `None`, the number of in-flight requests is only bounded by the number
+    /// of stream ids allowed by the protocol.
+    pub max_inflight_requests_per_connection: Option<NonZeroUsize>,
+
+    /// If true, warnings returned by the database along with responses
+    /// (e.g. about a batch being too large) are logged with `tracing::warn!`.
+    /// Regardless of this option, they are available through [`QueryResult::warnings`].
+    pub log_server_warnings: bool,
+
+    /// If true, the OpenTelemetry context of each request attempt is injected
+    /// into the custom payload of the request, using the globally registered text map propagator.
+    /// See [`crate::observability::opentelemetry`].
+    #[cfg(feature = "opentelemetry-031")]
+    pub opentelemetry_propagation: bool,
+
+    /// Upper bounds, in bytes, of the buckets of the histograms of request and response
+    /// frame sizes collected in [`Metrics`].
+    #[cfg(feature = "metrics")]
+    pub frame_size_buckets: Vec<u64>,
+
+    /// Notified whenever the driver transparently reprepares a statement
+    /// after a node reported it as unprepared.
+    /// See [`crate::observability::repreparation`].
+    pub repreparation_listener: Option<Arc<dyn RepreparationListener>>,
+
     /// Interval of sending keepalive requests.
     /// If `None`, keepalives are never sent, so `Self::keepalive_timeout` has no effect.
     pub keepalive_interval: Option<Duration>,
@@ -270,6 +376,15 @@ pub struct SessionConfig {
     /// re-establishing the control connection.
     pub host_filter: Option<Arc<dyn HostFilter>>,
 
+    /// Decides what the driver does when a node at an already known address
+    /// reports a different host id, e.g. after the node has been replaced.
+    /// By default, both nodes are kept for as long as cluster metadata lists them.
+    pub host_id_change_policy: HostIdChangePolicy,
+
+    /// Partitioners which the driver doesn't implement itself, used to compute
+    /// tokens for tables (or clusters) which employ them.
+    pub custom_partitioners: Vec<Arc<dyn CustomPartitioner>>,
+
     /// If the driver is to connect to ScyllaCloud, there is a config for it.
     #[cfg(feature = "unstable-cloud")]
     pub cloud_config: Option<Arc<CloudConfig>>,
@@ -318,6 +433,30 @@ pub struct SessionConfig {
     /// Driver and application self-identifying information,
     /// to be sent to server in STARTUP message.
     pub identity: SelfIdentity<'static>,
+
+    /// Controls which CQL protocol versions may be used when connecting to nodes.
+    ///
+    /// By default, all versions supported by the driver are allowed
+    /// and the newest one accepted by a node is used.
+    pub protocol_negotiation: ProtocolNegotiationPolicy,
+
+    /// Interceptors, which can inspect and rewrite statements before they are executed
+    /// and observe the results afterwards. They are called in the order of the vector.
+    ///
+    /// See the [interceptor](crate::client::interceptor) module for details.
+    pub interceptors: Vec<Arc<dyn StatementInterceptor>>,
+
+    /// Request interceptors, which observe the execution of requests, including each of their attempts.
+    /// They are called in the order of the vector, before the interceptors of the execution profile.
+    ///
+    /// See the [interceptor](crate::client::interceptor) module for details.
+    pub request_interceptors: Vec<Arc<dyn RequestInterceptor>>,
+
+    /// A rate limiter consulted before each request is sent, which may delay
+    /// or reject the request.
+    ///
+    /// See the [rate_limiter](crate::policies::rate_limiter) module for details.
+    pub request_rate_limiter: Option<Arc<dyn RequestRateLimiter>>,
 }
 
 impl SessionConfig {
@@ -337,17 +476,26 @@ impl SessionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_nodelay: true,
             tcp_keepalive_interval: None,
+            tcp_keepalive_probe_interval: Duration::from_secs(1),
+            tcp_keepalive_probe_count: 10,
+            tcp_user_timeout: None,
+            tcp_send_buffer_size: None,
+            tcp_recv_buffer_size: None,
             schema_agreement_interval: Duration::from_millis(200),
             default_execution_profile_handle: ExecutionProfile::new_from_inner(Default::default())
                 .into_handle(),
             used_keyspace: None,
             keyspace_case_sensitive: false,
             tls_context: None,
+            tls_context_provider: None,
+            tls_certificate_verifier: None,
             authenticator: None,
             connect_timeout: Duration::from_secs(5),
             connection_pool_size: Default::default(),
+            reconnection_policy: Arc::new(ExponentialReconnectionPolicy::default()),
             disallow_shard_aware_port: false,
             timestamp_generator: None,
             keyspaces_to_fetch: Vec::new(),
@@ -355,10 +503,19 @@ impl SessionConfig {
             metadata_request_serverside_timeout: Some(Duration::from_secs(2)),
             keepalive_interval: Some(Duration::from_secs(30)),
             keepalive_timeout: Some(Duration::from_secs(30)),
+            max_inflight_requests_per_connection: None,
+            log_server_warnings: true,
+            #[cfg(feature = "opentelemetry-031")]
+            opentelemetry_propagation: false,
+            #[cfg(feature = "metrics")]
+            frame_size_buckets: DEFAULT_FRAME_SIZE_BUCKETS.to_vec(),
+            repreparation_listener: None,
             schema_agreement_timeout: Duration::from_secs(60),
             schema_agreement_automatic_waiting: true,
             address_translator: None,
             host_filter: None,
+            host_id_change_policy: HostIdChangePolicy::default(),
+            custom_partitioners: Vec::new(),
             refresh_metadata_on_auto_schema_agreement: true,
             #[cfg(feature = "unstable-cloud")]
             cloud_config: None,
@@ -369,6 +526,10 @@ impl SessionConfig {
             tracing_info_fetch_consistency: Consistency::One,
             cluster_metadata_refresh_interval: Duration::from_secs(60),
             identity: SelfIdentity::default(),
+            protocol_negotiation: ProtocolNegotiationPolicy::default(),
+            interceptors: Vec::new(),
+            request_interceptors: Vec::new(),
+            request_rate_limiter: None,
         }
     }
 
@@ -510,7 +671,11 @@ impl Session {
         statement: impl Into<Statement>,
         values: impl SerializeRow,
     ) -> Result<QueryResult, ExecutionError> {
-        self.do_query_unpaged(&statement.into(), values).await
+        let statement = self.interceptors.intercept_statement(statement.into());
+        let result = self.do_query_unpaged(&statement, values).await;
+        self.interceptors
+            .on_completion(InterceptedRequest::Statement(&statement), result.as_ref());
+        result
     }
 
     /// Queries a single page from the database, optionally continuing from a saved point.
@@ -570,8 +735,15 @@ impl Session {
         values: impl SerializeRow,
         paging_state: PagingState,
     ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
-        self.do_query_single_page(&statement.into(), values, paging_state)
-            .await
+        let statement = self.interceptors.intercept_statement(statement.into());
+        let result = self
+            .do_query_single_page(&statement, values, paging_state)
+            .await;
+        self.interceptors.on_completion(
+            InterceptedRequest::Statement(&statement),
+            result.as_ref().map(|(result, _)| result),
+        );
+        result
     }
 
     /// Execute an unprepared CQL statement with paging\
@@ -615,7 +787,8 @@ impl Session {
         statement: impl Into<Statement>,
         values: impl SerializeRow,
     ) -> Result<QueryPager, PagerExecutionError> {
-        self.do_query_iter(statement.into(), values).await
+        let statement = self.interceptors.intercept_statement(statement.into());
+        self.do_query_iter(statement, values).await
     }
 
     /// Execute a prepared statement. Requires a [PreparedStatement]
@@ -782,6 +955,121 @@ impl Session {
         self.do_execute_iter(prepared.into(), values).await
     }
 
+    /// Resumes a paged query from a [PagerCursor] taken with [QueryPager::cursor],
+    /// possibly by a different [Session] instance connected to the same cluster.
+    ///
+    /// The query is resumed with the statement, bound values, consistency and page size
+    /// stored in the cursor, while the rest of the configuration (e.g. the execution profile)
+    /// is taken from this session's defaults. A prepared statement is prepared again
+    /// by this session first, and it must be the same statement as before - in particular,
+    /// statements referring to unqualified tables must be resumed by a session which
+    /// uses the same keyspace.
+    ///
+    /// See the documentation of [PagerCursor] for an example.
+    pub async fn resume_iter(
+        &self,
+        cursor: &PagerCursor,
+    ) -> Result<QueryPager, PagerExecutionError> {
+        let execution_profile = self.get_default_execution_profile_handle().access();
+
+        if let Some(statement) = cursor.to_statement() {
+            return QueryPager::new_for_query(
+                statement,
+                cursor.get_paging_state().clone(),
+                execution_profile,
+                self.cluster.get_state(),
+                self.request_rate_limiter.clone(),
+                #[cfg(feature = "metrics")]
+                Arc::clone(&self.metrics),
+            )
+            .await
+            .map_err(PagerExecutionError::NextPageError);
+        }
+
+        let mut prepared = self.prepare(cursor.get_statement()).await?;
+        if !cursor.configure_prepared(&mut prepared) {
+            return Err(PagerExecutionError::CursorStatementMismatch);
+        }
+        QueryPager::new_for_prepared_statement(PreparedPagerConfig {
+            prepared,
+            values: cursor.values().clone(),
+            paging_state: cursor.get_paging_state().clone(),
+            execution_profile,
+            cluster_state: self.cluster.get_state(),
+            rate_limiter: self.request_rate_limiter.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: Arc::clone(&self.metrics),
+        })
+        .await
+        .map_err(PagerExecutionError::NextPageError)
+    }
+
+    /// Executes many prepared statements, running at most `max_concurrency` of them at a time.
+    ///
+    /// Each statement is executed with [`Session::execute_unpaged`], so requests are routed
+    /// to the replicas owning their partition keys like any other prepared statement.
+    /// Statements are taken from the iterator lazily, as the returned stream is polled,
+    /// and the stream yields their results in the order of the iterator.
+    /// A failed execution does not stop the others.
+    ///
+    /// See [`Session::execute_concurrently_unordered`] for a variant yielding
+    /// the results in the order of completion.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use futures::TryStreamExt as _;
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let prepared = session
+    ///     .prepare("INSERT INTO ks.tab (a, b) VALUES(?, ?)")
+    ///     .await?;
+    ///
+    /// let statements = (0..10_000_i32).map(|i| (&prepared, (i, i * 2)));
+    /// session
+    ///     .execute_concurrently(statements, NonZeroUsize::new(64).unwrap())
+    ///     .try_for_each(|_| async { Ok(()) })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn execute_concurrently<'a, V>(
+        &'a self,
+        statements: impl IntoIterator<Item = (&'a PreparedStatement, V)> + 'a,
+        max_concurrency: NonZeroUsize,
+    ) -> impl Stream<Item = Result<QueryResult, ExecutionError>> + 'a
+    where
+        V: SerializeRow + 'a,
+    {
+        stream::iter(statements)
+            .map(move |(prepared, values)| self.execute_unpaged(prepared, values))
+            .buffered(max_concurrency.get())
+    }
+
+    /// Executes many prepared statements, running at most `max_concurrency` of them at a time.
+    ///
+    /// Works like [`Session::execute_concurrently`], but the returned stream yields
+    /// the results as soon as the executions complete. Each result is paired
+    /// with the position of its statement in the iterator.
+    pub fn execute_concurrently_unordered<'a, V>(
+        &'a self,
+        statements: impl IntoIterator<Item = (&'a PreparedStatement, V)> + 'a,
+        max_concurrency: NonZeroUsize,
+    ) -> impl Stream<Item = (usize, Result<QueryResult, ExecutionError>)> + 'a
+    where
+        V: SerializeRow + 'a,
+    {
+        stream::iter(statements)
+            .enumerate()
+            .map(move |(i, (prepared, values))| {
+                self.execute_unpaged(prepared, values)
+                    .map(move |result| (i, result))
+            })
+            .buffer_unordered(max_concurrency.get())
+    }
+
     /// Execute a batch statement\
     /// Batch contains many `unprepared` or `prepared` statements which are executed at once\
     /// Batch doesn't return any rows.
@@ -832,7 +1120,189 @@ impl Session {
         batch: &Batch,
         values: impl BatchValues,
     ) -> Result<QueryResult, ExecutionError> {
-        self.do_batch(batch, values).await
+        let batch = self.interceptors.intercept_batch(batch);
+        let result = self.do_batch(&batch, values).await;
+        self.interceptors
+            .on_completion(InterceptedRequest::Batch(&batch), result.as_ref());
+        result
+    }
+
+    /// Executes a [`TypedBatch`], whose statements were appended together with their values.
+    ///
+    /// See [`Session::batch`].
+    pub async fn typed_batch<V: BatchValues>(
+        &self,
+        batch: &TypedBatch<V>,
+    ) -> Result<QueryResult, ExecutionError> {
+        self.batch(batch.batch(), batch.values()).await
+    }
+
+    /// Executes the given counter updates in a single counter batch.
+    ///
+    /// This is a shorthand for building a [`Batch`] of type [`BatchType::Counter`]
+    /// and executing it with [`Session::batch`]. Statements which surely aren't counter updates
+    /// make it fail with [`BadQuery::BatchStatementKindMismatch`] before it's sent to the cluster,
+    /// see [`Batch::try_append_statement`].
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::value::Counter;
+    ///
+    /// let increment = session
+    ///     .prepare("UPDATE ks.page_views SET views = views + ? WHERE page = ?")
+    ///     .await?;
+    /// session
+    ///     .update_counters([
+    ///         (increment.clone(), (Counter(1), "/index.html")),
+    ///         (increment, (Counter(3), "/about.html")),
+    ///     ])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn update_counters<S, V>(
+        &self,
+        updates: impl IntoIterator<Item = (S, V)>,
+    ) -> Result<QueryResult, ExecutionError>
+    where
+        S: Into<BatchStatement>,
+        V: SerializeRow,
+    {
+        let mut batch = Batch::new(BatchType::Counter);
+        let mut values = Vec::new();
+        for (statement, statement_values) in updates {
+            batch.append_statement(statement);
+            values.push(statement_values);
+        }
+        self.batch(&batch, values).await
+    }
+
+    /// Executes any number of statements in batches, splitting them into as many batches
+    /// as needed to respect the limits given in [`BatchChunking`].
+    ///
+    /// Each batch is a copy of `batch` (its type and configuration, e.g. consistency),
+    /// with its statements replaced by consecutive statements from `statements`.
+    /// The batches are executed concurrently, and all of them are executed even
+    /// if some fail. Results of the batches are returned in the order of their statements.
+    ///
+    /// Values are serialized before the statements are split, in order to compute
+    /// the size of the batches. Unprepared statements with non-empty values are prepared
+    /// for that purpose (each distinct statement string once).
+    ///
+    /// Note that the statements are not applied atomically as a whole, even if `batch`
+    /// is a logged batch - atomicity is only guaranteed within each of the batches.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::statement::batch::{Batch, BatchChunking, BatchType};
+    ///
+    /// let prepared = session
+    ///     .prepare("INSERT INTO ks.tab (a, b) VALUES(?, ?)")
+    ///     .await?;
+    /// let statements = (0..100_000_i32).map(|i| (prepared.clone(), (i, i * 2)));
+    ///
+    /// session
+    ///     .batch_chunked(
+    ///         &Batch::new(BatchType::Unlogged),
+    ///         statements,
+    ///         &BatchChunking::new(),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn batch_chunked<S, V>(
+        &self,
+        batch: &Batch,
+        statements: impl IntoIterator<Item = (S, V)>,
+        chunking: &BatchChunking,
+    ) -> Result<Vec<QueryResult>, ChunkedBatchError>
+    where
+        S: Into<BatchStatement>,
+        V: SerializeRow,
+    {
+        let mut prepared_statements: HashMap<String, PreparedStatement> = HashMap::new();
+        let mut entries = Vec::new();
+        for (index, (statement, values)) in statements.into_iter().enumerate() {
+            let entry = async {
+                let statement = match statement.into() {
+                    BatchStatement::Query(query) if !values.is_empty() => {
+                        let prepared = match prepared_statements.get(&query.contents) {
+                            Some(prepared) => prepared.clone(),
+                            None => {
+                                let contents = query.contents.clone();
+                                let prepared = self.prepare(query).await?;
+                                prepared_statements.insert(contents, prepared.clone());
+                                prepared
+                            }
+                        };
+                        BatchStatement::PreparedStatement(prepared)
+                    }
+                    statement => statement,
+                };
+                let values = match &statement {
+                    BatchStatement::PreparedStatement(prepared) => {
+                        prepared.serialize_values(&values)?
+                    }
+                    BatchStatement::Query(_) => SerializedValues::new(),
+                };
+                Ok::<_, ExecutionError>((statement, values))
+            }
+            .await
+            .map_err(|error| ChunkedBatchError::BadStatement { index, error })?;
+            entries.push(entry);
+        }
+
+        let chunks = batch_chunking::split(
+            entries
+                .iter()
+                .map(|(statement, values)| batch_chunking::entry_size(statement, values)),
+            chunking,
+        );
+        let chunk_count = chunks.len();
+
+        let results: Vec<_> = stream::iter(chunks)
+            .map(|range| {
+                let entries = &entries[range.clone()];
+                let mut chunk = Batch::new_from(batch);
+                chunk.statements = entries
+                    .iter()
+                    .map(|(statement, _)| statement.clone())
+                    .collect();
+                async move {
+                    let values: Vec<_> = entries
+                        .iter()
+                        .map(|(_, values)| batch_chunking::SerializedRow(values))
+                        .collect();
+                    (range, self.batch(&chunk, values).await)
+                }
+            })
+            .buffered(chunking.get_max_concurrency().get())
+            .collect()
+            .await;
+
+        let mut query_results = Vec::with_capacity(chunk_count);
+        let mut failed_chunks = Vec::new();
+        for (statements, result) in results {
+            match result {
+                Ok(query_result) => query_results.push(query_result),
+                Err(error) => failed_chunks.push(FailedBatchChunk { statements, error }),
+            }
+        }
+        if failed_chunks.is_empty() {
+            Ok(query_results)
+        } else {
+            Err(ChunkedBatchError::ChunksFailed {
+                chunk_count,
+                failed_chunks,
+            })
+        }
     }
 
     /// Estabilishes a CQL session with the database
@@ -914,7 +1384,7 @@ impl Session {
         let tls_provider = 'provider: {
             #[cfg(feature = "unstable-cloud")]
             if let Some(cloud_config) = config.cloud_config {
-                if config.tls_context.is_some() {
+                if config.tls_context.is_some() || config.tls_context_provider.is_some() {
                     // This can only happen if the user builds SessionConfig by hand, as SessionBuilder in cloud mode prevents setting custom TlsContext.
                     warn!(
                         "Overriding user-provided TlsContext with Scylla Cloud TlsContext due \
@@ -926,25 +1396,41 @@ impl Session {
                 let provider = TlsProvider::new_cloud(cloud_config);
                 break 'provider Some(provider);
             }
+            if let Some(tls_context_provider) = config.tls_context_provider {
+                let provider = TlsProvider::new_with_global_context(tls_context_provider);
+                break 'provider Some(provider);
+            }
             if let Some(tls_context) = config.tls_context {
                 // To silence warnings when TlsContext is an empty enum (tls features are disabled).
                 // In such case, TlsProvider is uninhabited.
                 #[allow(unused_variables)]
-                let provider = TlsProvider::new_with_global_context(tls_context);
+                let provider = TlsProvider::new_with_global_context(Arc::new(tls_context));
                 #[allow(unreachable_code)]
                 break 'provider Some(provider);
             }
             None
         };
 
+        #[cfg(feature = "metrics")]
+        let metrics = Arc::new(Metrics::with_frame_size_buckets(
+            config.frame_size_buckets.clone(),
+        ));
+
         let connection_config = ConnectionConfig {
             local_ip_address: config.local_ip_address,
             shard_aware_local_port_range: config.shard_aware_local_port_range,
             compression: config.compression,
+            compression_threshold: config.compression_threshold,
             tcp_nodelay: config.tcp_nodelay,
             tcp_keepalive_interval: config.tcp_keepalive_interval,
+            tcp_keepalive_probe_interval: config.tcp_keepalive_probe_interval,
+            tcp_keepalive_probe_count: config.tcp_keepalive_probe_count,
+            tcp_user_timeout: config.tcp_user_timeout,
+            tcp_send_buffer_size: config.tcp_send_buffer_size,
+            tcp_recv_buffer_size: config.tcp_recv_buffer_size,
             timestamp_generator: config.timestamp_generator,
             tls_provider,
+            tls_certificate_verifier: config.tls_certificate_verifier,
             authenticator: config.authenticator,
             connect_timeout: config.connect_timeout,
             event_sender: None,
@@ -957,17 +1443,23 @@ impl Session {
             keepalive_timeout: config.keepalive_timeout,
             tablet_sender: Some(tablet_sender),
             identity: config.identity,
+            protocol_negotiation: config.protocol_negotiation,
+            max_inflight_requests: config.max_inflight_requests_per_connection,
+            log_server_warnings: config.log_server_warnings,
+            #[cfg(feature = "opentelemetry-031")]
+            opentelemetry_propagation: config.opentelemetry_propagation,
+            repreparation_listener: config.repreparation_listener,
+            #[cfg(feature = "metrics")]
+            metrics: Some(Arc::clone(&metrics)),
         };
 
         let pool_config = PoolConfig {
             connection_config,
             pool_size: config.connection_pool_size,
             can_use_shard_aware_port: !config.disallow_shard_aware_port,
+            reconnection_policy: config.reconnection_policy,
         };
 
-        #[cfg(feature = "metrics")]
-        let metrics = Arc::new(Metrics::new());
-
         let cluster = Cluster::new(
             known_nodes,
             pool_config,
@@ -975,6 +1467,8 @@ impl Session {
             config.fetch_schema_metadata,
             config.metadata_request_serverside_timeout,
             config.host_filter,
+            config.host_id_change_policy,
+            config.custom_partitioners,
             config.cluster_metadata_refresh_interval,
             tablet_receiver,
             #[cfg(feature = "metrics")]
@@ -998,6 +1492,9 @@ impl Session {
             tracing_info_fetch_attempts: config.tracing_info_fetch_attempts,
             tracing_info_fetch_interval: config.tracing_info_fetch_interval,
             tracing_info_fetch_consistency: config.tracing_info_fetch_consistency,
+            interceptors: InterceptorChain::new(config.interceptors),
+            request_interceptors: config.request_interceptors,
+            request_rate_limiter: config.request_rate_limiter,
         };
 
         if let Some(keyspace_name) = config.used_keyspace {
@@ -1064,6 +1561,7 @@ impl Session {
             .unwrap_or_else(|| self.get_default_execution_profile_handle())
             .access();
 
+        let routing = statement.get_routing();
         let statement_info = RoutingInfo {
             consistency: statement
                 .config
@@ -1073,18 +1571,26 @@ impl Session {
                 .config
                 .serial_consistency
                 .unwrap_or(execution_profile.serial_consistency),
+            token: routing.and_then(|routing| routing.token(&self.cluster.get_state())),
+            table: routing.map(|routing| routing.table()),
             ..Default::default()
         };
 
         let span = RequestSpan::new_query(&statement.contents);
+        span.record_labels(&statement.config.labels);
         let span_ref = &span;
-        let (run_request_result, coordinator): (
-            RunRequestResult<NonErrorQueryResponse>,
-            Coordinator,
-        ) = self
+        let request_hooks = RequestHooks::new(
+            InterceptedRequest::Statement(statement),
+            &self.request_interceptors,
+            Arc::clone(&execution_profile),
+        );
+        request_hooks.on_request_start();
+        let run_request_result = self
             .run_request(
                 statement_info,
                 &statement.config,
+                #[cfg(feature = "metrics")]
+                routing.map(|routing| routing.table_label()),
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
@@ -1106,6 +1612,7 @@ impl Session {
                                     serial_consistency,
                                     page_size,
                                     paging_state_ref.clone(),
+                                    InFlightLimiting::Apply,
                                 )
                                 .await
                                 .and_then(QueryResponse::into_non_error_query_response)
@@ -1121,6 +1628,7 @@ impl Session {
                                     serial_consistency,
                                     page_size,
                                     paging_state_ref.clone(),
+                                    InFlightLimiting::Apply,
                                 )
                                 .await
                                 .and_then(QueryResponse::into_non_error_query_response)
@@ -1128,24 +1636,36 @@ impl Session {
                     }
                 },
                 &span,
+                &request_hooks,
             )
             .instrument(span.span().clone())
-            .await?;
+            .await;
 
-        let response = match run_request_result {
-            RunRequestResult::IgnoredWriteError => NonErrorQueryResponse {
-                response: NonErrorResponse::Result(result::Result::Void),
-                tracing_id: None,
-                warnings: Vec::new(),
-            },
-            RunRequestResult::Completed(response) => response,
-        };
+        let result = match run_request_result {
+            Ok((run_request_result, coordinator, attempt_history)) => {
+                let response = match run_request_result {
+                    RunRequestResult::IgnoredWriteError => NonErrorQueryResponse {
+                        response: NonErrorResponse::Result(result::Result::Void),
+                        tracing_id: None,
+                        warnings: Vec::new(),
+                        custom_payload: None,
+                    },
+                    RunRequestResult::Completed(response) => response,
+                };
 
-        let (result, paging_state_response) =
-            response.into_query_result_and_paging_state(coordinator)?;
-        span.record_result_fields(&result);
+                response
+                    .into_query_result_and_paging_state(coordinator)
+                    .map(|(result, paging_state)| {
+                        (result.with_attempt_history(attempt_history), paging_state)
+                    })
+                    .map_err(ExecutionError::from)
+                    .inspect(|(result, _)| span.record_result_fields(result))
+            }
+            Err(error) => Err(error),
+        };
+        request_hooks.on_complete(result.as_ref().map(|(result, _)| result));
 
-        Ok((result, paging_state_response))
+        result
     }
 
     async fn handle_set_keyspace_response(
@@ -1171,7 +1691,7 @@ impl Session {
     ) -> Result<(), ExecutionError> {
         if self.schema_agreement_automatic_waiting {
             if response.as_schema_change().is_some() {
-                self.await_schema_agreement_with_required_node(Some(coordinator_id))
+                self.await_schema_agreement_with_required_node(coordinator_id)
                     .await?;
             }
 
@@ -1198,8 +1718,10 @@ impl Session {
         if values.is_empty() {
             QueryPager::new_for_query(
                 statement,
+                PagingState::start(),
                 execution_profile,
                 self.cluster.get_state(),
+                self.request_rate_limiter.clone(),
                 #[cfg(feature = "metrics")]
                 Arc::clone(&self.metrics),
             )
@@ -1214,8 +1736,10 @@ impl Session {
             QueryPager::new_for_prepared_statement(PreparedPagerConfig {
                 prepared,
                 values,
+                paging_state: PagingState::start(),
                 execution_profile,
                 cluster_state: self.cluster.get_state(),
+                rate_limiter: self.request_rate_limiter.clone(),
                 #[cfg(feature = "metrics")]
                 metrics: Arc::clone(&self.metrics),
             })
@@ -1267,7 +1791,7 @@ impl Session {
         &self,
         statement: impl Into<Statement>,
     ) -> Result<PreparedStatement, PrepareError> {
-        let statement = statement.into();
+        let statement = self.interceptors.intercept_statement(statement.into());
         self.prepare_nongeneric(&statement).await
     }
 
@@ -1358,9 +1882,9 @@ impl Session {
         // This is the first preparation that succeeded.
         // Let's return the PreparedStatement.
         prepared.set_partitioner_name(
-            Self::extract_partitioner_name(&prepared, cluster_state)
-                .and_then(PartitionerName::from_str)
-                .unwrap_or_default(),
+            cluster_state
+                .partitioners
+                .table_partitioner(Self::extract_partitioner_name(&prepared, cluster_state)),
         );
 
         Ok(prepared)
@@ -1386,16 +1910,34 @@ impl Session {
         values: impl SerializeRow,
     ) -> Result<QueryResult, ExecutionError> {
         let serialized_values = prepared.serialize_values(&values)?;
-        let (result, paging_state) = self
+        self.execute_unpaged_serialized(prepared, &serialized_values)
+            .await
+    }
+
+    pub(crate) async fn execute_unpaged_serialized(
+        &self,
+        prepared: &PreparedStatement,
+        serialized_values: &SerializedValues,
+    ) -> Result<QueryResult, ExecutionError> {
+        let serialized_values = self
+            .interceptors
+            .intercept_prepared(prepared, serialized_values);
+        let result = match self
             .execute(prepared, &serialized_values, None, PagingState::start())
-            .await?;
-        if !paging_state.finished() {
-            error!("Unpaged prepared query returned a non-empty paging state! This is a driver-side or server-side bug.");
-            return Err(ExecutionError::LastAttemptError(
-                RequestAttemptError::NonfinishedPagingState,
-            ));
-        }
-        Ok(result)
+            .await
+        {
+            Ok((_, paging_state)) if !paging_state.finished() => {
+                error!("Unpaged prepared query returned a non-empty paging state! This is a driver-side or server-side bug.");
+                Err(ExecutionError::LastAttemptError(
+                    RequestAttemptError::NonfinishedPagingState,
+                ))
+            }
+            Ok((result, _)) => Ok(result),
+            Err(error) => Err(error),
+        };
+        self.interceptors
+            .on_completion(InterceptedRequest::Prepared(prepared), result.as_ref());
+        result
     }
 
     async fn do_execute_single_page(
@@ -1405,9 +1947,18 @@ impl Session {
         paging_state: PagingState,
     ) -> Result<(QueryResult, PagingStateResponse), ExecutionError> {
         let serialized_values = prepared.serialize_values(&values)?;
+        let serialized_values = self
+            .interceptors
+            .intercept_prepared(prepared, &serialized_values);
         let page_size = prepared.get_validated_page_size();
-        self.execute(prepared, &serialized_values, Some(page_size), paging_state)
-            .await
+        let result = self
+            .execute(prepared, &serialized_values, Some(page_size), paging_state)
+            .await;
+        self.interceptors.on_completion(
+            InterceptedRequest::Prepared(prepared),
+            result.as_ref().map(|(result, _)| result),
+        );
+        result
     }
 
     /// Sends a prepared request to the database, optionally continuing from a saved point.
@@ -1463,6 +2014,7 @@ impl Session {
             token,
             serialized_values.buffer_size(),
         );
+        span.record_labels(&prepared.config.labels);
 
         if !span.span().is_disabled() {
             if let (Some(table_spec), Some(token)) = (statement_info.table, token) {
@@ -1472,13 +2024,24 @@ impl Session {
             }
         }
 
-        let (run_request_result, coordinator): (
-            RunRequestResult<NonErrorQueryResponse>,
-            Coordinator,
-        ) = self
+        #[cfg(feature = "metrics")]
+        let tablet_table = table_spec.map(|spec| {
+            self.get_cluster_state()
+                .is_tablet_table(spec.ks_name(), spec.table_name())
+        });
+
+        let request_hooks = RequestHooks::new(
+            InterceptedRequest::Prepared(prepared),
+            &self.request_interceptors,
+            Arc::clone(&execution_profile),
+        );
+        request_hooks.on_request_start();
+        let run_request_result = self
             .run_request(
                 statement_info,
                 &prepared.config,
+                #[cfg(feature = "metrics")]
+                prepared.get_table_label(),
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
@@ -1488,7 +2051,7 @@ impl Session {
                         .serial_consistency
                         .unwrap_or(execution_profile.serial_consistency);
                     async move {
-                        connection
+                        let response = connection
                             .execute_raw_with_consistency(
                                 prepared,
                                 serialized_values,
@@ -1496,30 +2059,51 @@ impl Session {
                                 serial_consistency,
                                 page_size,
                                 paging_state_ref.clone(),
+                                InFlightLimiting::Apply,
                             )
                             .await
-                            .and_then(QueryResponse::into_non_error_query_response)
+                            .and_then(QueryResponse::into_non_error_query_response);
+                        #[cfg(feature = "metrics")]
+                        if let (Some(tablet_table), Ok(response)) = (tablet_table, &response) {
+                            self.metrics.log_tablet_routing(
+                                tablet_table,
+                                has_tablet_info(response.custom_payload.as_ref()),
+                            );
+                        }
+                        response
                     }
                 },
                 &span,
+                &request_hooks,
             )
             .instrument(span.span().clone())
-            .await?;
+            .await;
 
-        let response = match run_request_result {
-            RunRequestResult::IgnoredWriteError => NonErrorQueryResponse {
-                response: NonErrorResponse::Result(result::Result::Void),
-                tracing_id: None,
-                warnings: Vec::new(),
-            },
-            RunRequestResult::Completed(response) => response,
-        };
+        let result = match run_request_result {
+            Ok((run_request_result, coordinator, attempt_history)) => {
+                let response = match run_request_result {
+                    RunRequestResult::IgnoredWriteError => NonErrorQueryResponse {
+                        response: NonErrorResponse::Result(result::Result::Void),
+                        tracing_id: None,
+                        warnings: Vec::new(),
+                        custom_payload: None,
+                    },
+                    RunRequestResult::Completed(response) => response,
+                };
 
-        let (result, paging_state_response) =
-            response.into_query_result_and_paging_state(coordinator)?;
-        span.record_result_fields(&result);
+                response
+                    .into_query_result_and_paging_state(coordinator)
+                    .map(|(result, paging_state)| {
+                        (result.with_attempt_history(attempt_history), paging_state)
+                    })
+                    .map_err(ExecutionError::from)
+                    .inspect(|(result, _)| span.record_result_fields(result))
+            }
+            Err(error) => Err(error),
+        };
+        request_hooks.on_complete(result.as_ref().map(|(result, _)| result));
 
-        Ok((result, paging_state_response))
+        result
     }
 
     async fn do_execute_iter(
@@ -1528,6 +2112,10 @@ impl Session {
         values: impl SerializeRow,
     ) -> Result<QueryPager, PagerExecutionError> {
         let serialized_values = prepared.serialize_values(&values)?;
+        let serialized_values = self
+            .interceptors
+            .intercept_prepared(&prepared, &serialized_values)
+            .into_owned();
 
         let execution_profile = prepared
             .get_execution_profile_handle()
@@ -1537,8 +2125,10 @@ impl Session {
         QueryPager::new_for_prepared_statement(PreparedPagerConfig {
             prepared,
             values: serialized_values,
+            paging_state: PagingState::start(),
             execution_profile,
             cluster_state: self.cluster.get_state(),
+            rate_limiter: self.request_rate_limiter.clone(),
             #[cfg(feature = "metrics")]
             metrics: Arc::clone(&self.metrics),
         })
@@ -1562,6 +2152,31 @@ impl Session {
             ));
         }
 
+        // A statement's own `USING TIMESTAMP` would silently override the batch-level
+        // timestamp, so refuse ambiguous batches instead.
+        if batch.get_timestamp().is_some() {
+            let conflicting = batch.statements.iter().position(|statement| {
+                let contents = match statement {
+                    BatchStatement::Query(q) => q.contents.as_str(),
+                    BatchStatement::PreparedStatement(ps) => ps.get_statement(),
+                };
+                using_clause::has_using_timestamp(contents)
+            });
+            if let Some(idx) = conflicting {
+                return Err(ExecutionError::BadQuery(
+                    BadQuery::ConflictingTimestampInBatchStatement(idx),
+                ));
+            }
+        }
+
+        // Mixing counter updates with other statements fails on the server side
+        // with an obscure error, so catch it early.
+        for (idx, statement) in batch.statements.iter().enumerate() {
+            counter_update::check_statement_kind(batch.get_type(), statement).map_err(|err| {
+                ExecutionError::BadQuery(BadQuery::BatchStatementKindMismatch(idx, err))
+            })?;
+        }
+
         let execution_profile = batch
             .get_execution_profile_handle()
             .unwrap_or_else(|| self.get_default_execution_profile_handle())
@@ -1581,12 +2196,11 @@ impl Session {
             batch_values::peek_first_token(values, batch.statements.first())?;
         let values_ref = &values;
 
-        let table_spec =
-            if let Some(BatchStatement::PreparedStatement(ps)) = batch.statements.first() {
-                ps.get_table_spec()
-            } else {
-                None
-            };
+        let first_prepared = match batch.statements.first() {
+            Some(BatchStatement::PreparedStatement(ps)) => Some(ps),
+            _ => None,
+        };
+        let table_spec = first_prepared.and_then(|ps| ps.get_table_spec());
 
         let statement_info = RoutingInfo {
             consistency,
@@ -1597,14 +2211,20 @@ impl Session {
         };
 
         let span = RequestSpan::new_batch();
+        span.record_labels(&batch.config.labels);
 
-        let (run_request_result, coordinator): (
-            RunRequestResult<NonErrorQueryResponse>,
-            Coordinator,
-        ) = self
+        let request_hooks = RequestHooks::new(
+            InterceptedRequest::Batch(batch),
+            &self.request_interceptors,
+            Arc::clone(&execution_profile),
+        );
+        request_hooks.on_request_start();
+        let run_request_result = self
             .run_request(
                 statement_info,
                 &batch.config,
+                #[cfg(feature = "metrics")]
+                first_prepared.and_then(|ps| ps.get_table_label()),
                 execution_profile,
                 |connection: Arc<Connection>,
                  consistency: Consistency,
@@ -1626,20 +2246,29 @@ impl Session {
                     }
                 },
                 &span,
+                &request_hooks,
             )
             .instrument(span.span().clone())
-            .await?;
+            .await;
 
         let result = match run_request_result {
-            RunRequestResult::IgnoredWriteError => QueryResult::mock_empty(coordinator),
-            RunRequestResult::Completed(non_error_query_response) => {
-                let result = non_error_query_response.into_query_result(coordinator)?;
-                span.record_result_fields(&result);
-                result
+            Ok((RunRequestResult::IgnoredWriteError, coordinator, attempt_history)) => {
+                Ok(QueryResult::mock_empty(coordinator).with_attempt_history(attempt_history))
             }
+            Ok((
+                RunRequestResult::Completed(non_error_query_response),
+                coordinator,
+                attempt_history,
+            )) => non_error_query_response
+                .into_query_result(coordinator)
+                .map(|result| result.with_attempt_history(attempt_history))
+                .map_err(ExecutionError::from)
+                .inspect(|result| span.record_result_fields(result)),
+            Err(error) => Err(error),
         };
+        request_hooks.on_complete(result.as_ref());
 
-        Ok(result)
+        result
     }
 
     /// Prepares all statements within the batch and returns a new batch where every
@@ -1678,7 +2307,8 @@ impl Session {
                 .iter_mut()
                 .map(|statement| async move {
                     if let BatchStatement::Query(query) = statement {
-                        let prepared = self.prepare_nongeneric(query).await?;
+                        let query = self.interceptors.intercept_statement(query.clone());
+                        let prepared = self.prepare_nongeneric(&query).await?;
                         *statement = BatchStatement::PreparedStatement(prepared);
                     }
                     Ok::<(), PrepareError>(())
@@ -1748,12 +2378,90 @@ impl Session {
     /// Manually trigger a metadata refresh\
     /// The driver will fetch current nodes in the cluster and update its metadata
     ///
+    /// Completes once the new cluster state, including connection pools
+    /// to newly discovered nodes, is installed, and returns it.
+    /// Subsequent calls to [Session::get_cluster_state] return the same or a newer state.
+    ///
     /// Normally this is not needed,
     /// the driver should automatically detect all metadata changes in the cluster
-    pub async fn refresh_metadata(&self) -> Result<(), MetadataError> {
+    pub async fn refresh_metadata(&self) -> Result<Arc<ClusterState>, MetadataError> {
         self.cluster.refresh_metadata().await
     }
 
+    /// Drains the node with the given host ID, e.g. before restarting it.
+    ///
+    /// The node is disabled, so the driver stops routing new requests to it,
+    /// and then this method waits (for at most `timeout`) until the requests already
+    /// sent to the node finish. Afterwards, the node's connection pool is dropped,
+    /// closing its connections once the last request holding them is done.
+    ///
+    /// The node stays drained, also across metadata refreshes, until
+    /// [`Session::undrain_node`] is called. This holds even if an error is returned.
+    ///
+    /// Note that custom load balancing policies must skip disabled nodes
+    /// (see [`Node::is_enabled`](crate::cluster::Node::is_enabled)),
+    /// like the default policy does, for requests not to be routed to drained nodes.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # use std::time::Duration;
+    /// # use uuid::Uuid;
+    /// # async fn check_only_compiles(session: &Session, host_id: Uuid) -> Result<(), Box<dyn Error>> {
+    /// session.drain_node(host_id, Duration::from_secs(30)).await?;
+    /// // Restart the node...
+    /// session.undrain_node(host_id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn drain_node(&self, host_id: Uuid, timeout: Duration) -> Result<(), DrainNodeError> {
+        let node = self
+            .cluster
+            .get_state()
+            .known_peers
+            .get(&host_id)
+            .cloned()
+            .ok_or(DrainNodeError::UnknownNode(host_id))?;
+
+        self.cluster.set_node_drained(host_id, true).await?;
+
+        // The cluster state no longer refers to the node's pool,
+        // so only the requests already sent to the node may still use it.
+        let connections = node.get_working_connections().unwrap_or_default();
+        let in_flight_requests = || {
+            connections
+                .iter()
+                .map(|connection| connection.in_flight_requests())
+                .sum::<usize>()
+        };
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            let in_flight = in_flight_requests();
+            if in_flight == 0 {
+                break;
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(DrainNodeError::Timeout {
+                    host_id,
+                    timeout,
+                    in_flight_requests: in_flight,
+                });
+            }
+            tokio::time::sleep(DRAIN_POLL_INTERVAL).await;
+        }
+
+        Ok(())
+    }
+
+    /// Reverts [`Session::drain_node`]: the node is enabled again and a new
+    /// connection pool is opened to it.
+    ///
+    /// Undraining a node which is not drained is a no-op, apart from refreshing the metadata.
+    pub async fn undrain_node(&self, host_id: Uuid) -> Result<(), MetadataError> {
+        self.cluster.set_node_drained(host_id, false).await
+    }
+
     /// Access metrics collected by the driver\
     /// Driver collects various metrics like number of queries or query latencies.
     /// They can be read using this method
@@ -1762,6 +2470,45 @@ impl Session {
         Arc::clone(&self.metrics)
     }
 
+    /// Encodes metrics collected by the driver, together with sizes of connection pools
+    /// of all nodes, in the Prometheus text exposition format.
+    /// See [prometheus](crate::observability::prometheus) module for more information.
+    #[cfg(feature = "metrics-prometheus")]
+    pub fn encode_prometheus_metrics(&self) -> String {
+        crate::observability::prometheus::encode(&self.metrics, Some(&self.cluster.get_state()))
+    }
+
+    /// Returns a snapshot of the connection pools of all known nodes:
+    /// whether they are connected, connecting or broken (along with the last
+    /// connection error), and the number of in-flight requests on each connection
+    /// to each shard. This is useful e.g. for health checks of applications.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # fn example(session: &Session) {
+    /// use scylla::client::PoolState;
+    ///
+    /// let connected_nodes = session
+    ///     .pool_status()
+    ///     .iter()
+    ///     .filter(|status| matches!(status.state, PoolState::Connected { .. }))
+    ///     .count();
+    /// println!("Connected to {connected_nodes} nodes");
+    /// # }
+    /// ```
+    pub fn pool_status(&self) -> Vec<NodePoolStatus> {
+        self.cluster
+            .get_state()
+            .get_nodes_info()
+            .iter()
+            .map(|node| NodePoolStatus {
+                node: Arc::clone(node),
+                state: node.pool_state(),
+            })
+            .collect()
+    }
+
     /// Access cluster state visible by the driver.
     ///
     /// Driver collects various information about network topology or schema.
@@ -1770,6 +2517,197 @@ impl Session {
         self.cluster.get_state()
     }
 
+    /// Sends a custom CQL request to the given node and returns its raw response.
+    ///
+    /// This is an escape hatch for experimenting with server features which the driver
+    /// has no typed support for yet. The request is sent on a random connection to the node,
+    /// and is not subject to the load balancing, retry or speculative execution policies,
+    /// nor to the request timeout.
+    ///
+    /// **This API is unstable**: it is only available with the `unstable-raw-requests` feature
+    /// and may change or be removed in any release.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// use scylla::client::raw_request::{RawRequest, RequestOpcode, ResponseOpcode};
+    ///
+    /// let cluster_state = session.get_cluster_state();
+    /// let node = &cluster_state.get_nodes_info()[0];
+    /// // OPTIONS has an empty body.
+    /// let response = session
+    ///     .send_raw_request(node, &RawRequest::new(RequestOpcode::Options, Vec::new()))
+    ///     .await?;
+    /// assert_eq!(response.opcode, ResponseOpcode::Supported);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "unstable-raw-requests")]
+    pub async fn send_raw_request(
+        &self,
+        node: NodeRef<'_>,
+        request: &crate::client::raw_request::RawRequest,
+    ) -> Result<crate::client::raw_request::RawResponse, ExecutionError> {
+        let connection = node.get_random_connection()?;
+        let coordinator = Coordinator::new(
+            node,
+            connection
+                .get_shard_info()
+                .as_ref()
+                .map(|info| info.shard as Shard),
+            &connection,
+        );
+        connection.send_raw_request(request).await.map_err(|err| {
+            RequestError::from_attempt(err.into(), AttemptContext::new(coordinator, 1))
+                .into_execution_error()
+        })
+    }
+
+    /// Subscribes to topology and status change events received by the driver's control connection,
+    /// e.g. about nodes joining the cluster or going down.
+    ///
+    /// The returned stream yields events received after the moment of subscription.
+    /// It ends when the session is dropped. If the stream is not polled for a long time,
+    /// it may miss some events - a warning is logged when that happens.
+    ///
+    /// See [`ClusterEvent`] for details about delivery guarantees.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # async fn check_only_compiles(session: &Session) {
+    /// use futures::StreamExt;
+    /// use scylla::cluster::ClusterEvent;
+    ///
+    /// let mut events = session.subscribe_events();
+    /// tokio::spawn(async move {
+    ///     while let Some(event) = events.next().await {
+    ///         match event {
+    ///             ClusterEvent::NodeDown(addr) => println!("Node {addr} went down"),
+    ///             other => println!("Cluster event: {other:?}"),
+    ///         }
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    pub fn subscribe_events(&self) -> impl Stream<Item = ClusterEvent> + Send + Unpin + 'static {
+        let receiver = self.cluster.subscribe_to_cluster_events();
+        stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) => return Some((event, receiver)),
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(missed)) => {
+                        warn!("Cluster event subscriber lagged behind and missed {missed} events");
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+                }
+            }
+        })
+        .boxed()
+    }
+
+    /// Subscribes to schema change events received by the driver's control connection.
+    pub(crate) fn subscribe_to_schema_changes(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<Arc<SchemaChangeEvent>> {
+        self.cluster.subscribe_to_schema_changes()
+    }
+
+    /// Explains how a prepared statement executed with the given values would be routed.
+    ///
+    /// Computes the token of the partition key, and returns the targets (node and shard)
+    /// in the order in which the load balancing policy (of the statement or its execution profile)
+    /// would try them, marking which of them are replicas of the token.
+    /// No request is sent to the cluster.
+    ///
+    /// This is meant for verifying token- and shard-awareness for specific keys while troubleshooting.
+    /// Note that the actual order may differ between calls, e.g. because of replica shuffling
+    /// or latency awareness.
+    ///
+    /// The targets are obtained by building a regular query plan with the load balancing policy,
+    /// exactly as it is done for executed requests, and draining it. Thus, the policy observes
+    /// the call as if a request was about to be sent: stateful policies may be affected, e.g.
+    /// a round-robin position may advance, or a circuit breaker may start probing a quarantined node.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use std::error::Error;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+    /// let prepared = session
+    ///     .prepare("SELECT a FROM ks.tab WHERE pk = ?")
+    ///     .await?;
+    ///
+    /// let explanation = session.explain_routing(&prepared, (42_i32,))?;
+    /// println!("Token: {:?}", explanation.token);
+    /// for target in &explanation.targets {
+    ///     println!(
+    ///         "{} shard {:?} (replica: {})",
+    ///         target.node.address, target.shard, target.is_replica
+    ///     );
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn explain_routing(
+        &self,
+        prepared: &PreparedStatement,
+        values: impl SerializeRow,
+    ) -> Result<RoutingExplanation, Box<ExecutionError>> {
+        let serialized_values = prepared
+            .serialize_values(&values)
+            .map_err(|err| Box::new(ExecutionError::from(err)))?;
+        let token = prepared
+            .calculate_token_untyped(&serialized_values)
+            .map_err(|err| Box::new(err.into_execution_error()))?;
+
+        let execution_profile = prepared
+            .get_execution_profile_handle()
+            .unwrap_or_else(|| self.get_default_execution_profile_handle())
+            .access();
+        let load_balancer = prepared
+            .config
+            .load_balancing_policy
+            .as_deref()
+            .unwrap_or(execution_profile.load_balancing_policy.as_ref());
+
+        let table_spec = prepared.get_table_spec();
+        let routing_info = RoutingInfo {
+            consistency: prepared
+                .config
+                .consistency
+                .unwrap_or(execution_profile.consistency),
+            serial_consistency: prepared
+                .config
+                .serial_consistency
+                .unwrap_or(execution_profile.serial_consistency),
+            token,
+            table: table_spec,
+            is_confirmed_lwt: prepared.is_confirmed_lwt(),
+        };
+
+        let cluster_state = self.get_cluster_state();
+        let replicas: HashSet<Uuid> = match (table_spec, token) {
+            (Some(table_spec), Some(token)) => cluster_state
+                .get_token_endpoints_iter(table_spec, token)
+                .map(|(node, _shard)| node.host_id)
+                .collect(),
+            _ => HashSet::new(),
+        };
+
+        let targets = load_balancing::Plan::new(load_balancer, &routing_info, &cluster_state)
+            .map(|(node, shard)| RoutingTarget {
+                node: Arc::clone(node),
+                shard: node.sharder().is_some().then_some(shard),
+                is_replica: replicas.contains(&node.host_id),
+            })
+            .collect();
+
+        Ok(RoutingExplanation { token, targets })
+    }
+
     /// Get [`TracingInfo`] of a traced query performed earlier
     ///
     /// See [the book](https://rust-driver.docs.scylladb.com/stable/tracing/tracing.html)
@@ -1878,14 +2816,24 @@ impl Session {
     /// On success, this request's result is returned.
     // I tried to make this closures take a reference instead of an Arc but failed
     // maybe once async closures get stabilized this can be fixed
+    #[cfg_attr(feature = "metrics", expect(clippy::too_many_arguments))]
     async fn run_request<'a, QueryFut>(
         &'a self,
         statement_info: RoutingInfo<'a>,
         statement_config: &'a StatementConfig,
+        #[cfg(feature = "metrics")] table_label: Option<&'a str>,
         execution_profile: Arc<ExecutionProfileInner>,
         run_request_once: impl Fn(Arc<Connection>, Consistency, &ExecutionProfileInner) -> QueryFut,
         request_span: &'a RequestSpan,
-    ) -> Result<(RunRequestResult<NonErrorQueryResponse>, Coordinator), ExecutionError>
+        request_hooks: &'a RequestHooks<'a>,
+    ) -> Result<
+        (
+            RunRequestResult<NonErrorQueryResponse>,
+            Coordinator,
+            Vec<RequestAttempt>,
+        ),
+        ExecutionError,
+    >
     where
         QueryFut: Future<Output = Result<NonErrorQueryResponse, RequestAttemptError>>,
     {
@@ -1900,7 +2848,19 @@ impl Session {
             .as_deref()
             .unwrap_or(execution_profile.load_balancing_policy.as_ref());
 
+        #[cfg(feature = "metrics")]
+        let request_start = std::time::Instant::now();
+
+        // Shared by all speculative fibers of the request.
+        let attempt_history = std::sync::Mutex::new(Vec::new());
         let runner = async {
+            // Acquired within the request timeout, deadline and cancellation.
+            // Held until the request, including all its retries, is finished.
+            let _rate_limit_permit = match &self.request_rate_limiter {
+                Some(limiter) => Some(limiter.acquire().await?),
+                None => None,
+            };
+
             let cluster_state = self.cluster.get_state();
             let request_plan =
                 load_balancing::Plan::new(load_balancer, &statement_info, &cluster_state);
@@ -1912,7 +2872,17 @@ impl Session {
             where
                 I: Iterator<Item = (NodeRef<'a>, Shard)>,
             {
-                iter: std::sync::Mutex<I>,
+                iter: std::sync::Mutex<std::iter::Peekable<I>>,
+            }
+
+            impl<'a, I> SharedPlan<'a, I>
+            where
+                I: Iterator<Item = (NodeRef<'a>, Shard)>,
+            {
+                // The node which the next fiber is going to be sent to.
+                fn peek_node(&self) -> Option<NodeRef<'a>> {
+                    self.iter.lock().unwrap().peek().map(|(node, _)| *node)
+                }
             }
 
             impl<'a, I> Iterator for &SharedPlan<'a, I>
@@ -1936,7 +2906,7 @@ impl Session {
             match speculative_policy {
                 Some(speculative) if statement_config.is_idempotent => {
                     let shared_request_plan = SharedPlan {
-                        iter: std::sync::Mutex::new(request_plan),
+                        iter: std::sync::Mutex::new(request_plan.peekable()),
                     };
 
                     let request_runner_generator = |is_speculative: bool| {
@@ -1974,6 +2944,9 @@ impl Session {
                                 load_balancing_policy: load_balancer,
                                 query_info: &statement_info,
                                 request_span,
+                                request_hooks,
+                                attempt_history: &attempt_history,
+                                deadline: statement_config.deadline,
                             },
                         )
                     };
@@ -1987,6 +2960,7 @@ impl Session {
                         speculative.as_ref(),
                         &context,
                         request_runner_generator,
+                        || shared_request_plan.peek_node(),
                     )
                     .await
                 }
@@ -2011,6 +2985,9 @@ impl Session {
                             load_balancing_policy: load_balancer,
                             query_info: &statement_info,
                             request_span,
+                            request_hooks,
+                            attempt_history: &attempt_history,
+                            deadline: statement_config.deadline,
                         },
                     )
                     .await
@@ -2022,24 +2999,56 @@ impl Session {
         let effective_timeout = statement_config
             .request_timeout
             .or(execution_profile.request_timeout);
-        let result = match effective_timeout {
-            Some(timeout) => tokio::time::timeout(timeout, runner).await.unwrap_or_else(
-                |_: tokio::time::error::Elapsed| {
-                    #[cfg(feature = "metrics")]
-                    self.metrics.inc_request_timeouts();
-                    Err(RequestError::RequestTimeout(timeout))
-                },
-            ),
+        let runner = async {
+            match effective_timeout {
+                Some(timeout) => tokio::time::timeout(timeout, runner).await.unwrap_or_else(
+                    |_: tokio::time::error::Elapsed| {
+                        #[cfg(feature = "metrics")]
+                        self.metrics.inc_request_timeouts();
+                        Err(RequestError::RequestTimeout(timeout))
+                    },
+                ),
+                None => runner.await,
+            }
+        };
+        let runner = async {
+            match statement_config.deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), runner)
+                    .await
+                    .unwrap_or_else(|_: tokio::time::error::Elapsed| {
+                        #[cfg(feature = "metrics")]
+                        self.metrics.inc_request_timeouts();
+                        Err(RequestError::DeadlineExceeded)
+                    }),
+                None => runner.await,
+            }
+        };
+        // Dropping the runner upon cancellation drops the in-flight attempts,
+        // which releases their stream ids once the responses arrive.
+        let result = match &statement_config.cancellation_token {
+            Some(token) => tokio::select! {
+                biased;
+                () = token.cancelled() => Err(RequestError::Cancelled),
+                result = runner => result,
+            },
             None => runner.await,
         };
 
         if let Some((history_listener, request_id)) = history_listener_and_id {
             match &result {
                 Ok(_) => history_listener.log_request_success(request_id),
-                Err(e) => history_listener.log_request_error(request_id, e),
+                Err(e) => history_listener.log_request_error(request_id, e.inner()),
             }
         }
 
+        #[cfg(feature = "metrics")]
+        self.metrics.log_labeled_request(
+            &statement_config.labels,
+            table_label,
+            request_start.elapsed().as_millis() as u64,
+            result.is_err(),
+        );
+
         // Automatically handle meaningful responses.
         if let Ok((RunRequestResult::Completed(ref response), ref coordinator)) = result {
             self.handle_set_keyspace_response(response).await?;
@@ -2047,7 +3056,15 @@ impl Session {
                 .await?;
         }
 
-        result.map_err(RequestError::into_execution_error)
+        let attempt_history = attempt_history.into_inner().unwrap();
+        match result {
+            Ok((run_request_result, coordinator)) => {
+                Ok((run_request_result, coordinator, attempt_history))
+            }
+            Err(error) => Err(error
+                .with_attempt_history(attempt_history)
+                .into_execution_error()),
+        }
     }
 
     /// Executes the closure `run_request_once`, provided the load balancing plan and some information
@@ -2069,10 +3086,22 @@ impl Session {
         let mut current_consistency: Consistency = context
             .consistency_set_on_statement
             .unwrap_or(execution_profile.consistency);
+        #[cfg(feature = "opentelemetry-031")]
+        let otel_parent = opentelemetry::Context::current();
+        #[cfg(feature = "opentelemetry-031")]
+        let mut retry_count: usize = 0;
+        let mut attempts: usize = 0;
 
         'nodes_in_plan: for (node, shard) in request_plan {
             let span = trace_span!("Executing request", node = %node.address, shard = %shard);
             'same_node_retries: loop {
+                if context
+                    .deadline
+                    .is_some_and(|deadline| std::time::Instant::now() >= deadline)
+                {
+                    trace!(parent: &span, "Deadline exceeded, not starting another attempt");
+                    return Some(Err(RequestError::DeadlineExceeded));
+                }
                 trace!(parent: &span, "Execution started");
                 let connection = match node.connection_for_shard(shard).await {
                     Ok(connection) => connection,
@@ -2090,7 +3119,13 @@ impl Session {
                 context.request_span.record_shard_id(&connection);
 
                 #[cfg(feature = "metrics")]
-                self.metrics.inc_total_nonpaged_queries();
+                {
+                    self.metrics.inc_total_nonpaged_queries();
+                    if let Some(shard_info) = connection.get_shard_info() {
+                        self.metrics
+                            .log_shard_routing(shard_info.shard as Shard == shard);
+                    }
+                }
                 let request_start = std::time::Instant::now();
 
                 let connect_address = connection.get_connect_address();
@@ -2101,21 +3136,56 @@ impl Session {
                 );
                 let coordinator =
                     Coordinator::new(node, node.sharder().is_some().then_some(shard), &connection);
+                attempts += 1;
 
                 let attempt_id: Option<history::AttemptId> =
                     context.log_attempt_start(connect_address);
-                let request_result: Result<NonErrorQueryResponse, RequestAttemptError> =
+                let request_future =
                     run_request_once(connection, current_consistency, execution_profile)
-                        .instrument(span.clone())
-                        .await;
+                        .instrument(span.clone());
+                #[cfg(feature = "opentelemetry-031")]
+                let otel_cx = start_attempt_span(
+                    &otel_parent,
+                    node,
+                    node.sharder().is_some().then_some(shard),
+                    current_consistency,
+                    retry_count,
+                );
+                #[cfg(feature = "opentelemetry-031")]
+                let request_future = request_future.with_context(otel_cx.clone());
+                let in_flight_guard = node.start_request();
+                let request_result: Result<NonErrorQueryResponse, RequestAttemptError> =
+                    request_future.await;
+                drop(in_flight_guard);
 
                 let elapsed = request_start.elapsed();
+                #[cfg(feature = "opentelemetry-031")]
+                end_attempt_span(&otel_cx, request_result.as_ref().err());
+                context.request_hooks.on_attempt(&AttemptInfo {
+                    node,
+                    shard: coordinator.shard(),
+                    consistency: current_consistency,
+                    latency: elapsed,
+                    error: request_result.as_ref().err(),
+                });
+                #[cfg(feature = "metrics")]
+                self.metrics.log_node_request(
+                    node,
+                    elapsed.as_millis() as u64,
+                    request_result.is_err(),
+                );
                 let request_error: RequestAttemptError = match request_result {
                     Ok(response) => {
                         trace!(parent: &span, "Request succeeded");
                         #[cfg(feature = "metrics")]
                         let _ = self.metrics.log_query_latency(elapsed.as_millis() as u64);
                         context.log_attempt_success(&attempt_id);
+                        context
+                            .attempt_history
+                            .lock()
+                            .unwrap()
+                            .push(RequestAttempt::succeeded(coordinator.clone(), elapsed));
+                        node.record_latency(elapsed);
                         context.load_balancing_policy.on_request_success(
                             context.query_info,
                             elapsed,
@@ -2157,19 +3227,40 @@ impl Session {
                 );
 
                 context.log_attempt_error(&attempt_id, &request_error, &retry_decision);
-
-                last_error = Some(request_error.into());
+                context
+                    .attempt_history
+                    .lock()
+                    .unwrap()
+                    .push(RequestAttempt::failed(
+                        coordinator.clone(),
+                        elapsed,
+                        request_error.clone(),
+                        retry_decision.clone(),
+                    ));
+
+                last_error = Some(RequestError::from_attempt(
+                    request_error,
+                    AttemptContext::new(coordinator.clone(), attempts),
+                ));
 
                 match retry_decision {
                     RetryDecision::RetrySameTarget(new_cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        #[cfg(feature = "opentelemetry-031")]
+                        {
+                            retry_count += 1;
+                        }
                         current_consistency = new_cl.unwrap_or(current_consistency);
                         continue 'same_node_retries;
                     }
                     RetryDecision::RetryNextTarget(new_cl) => {
                         #[cfg(feature = "metrics")]
                         self.metrics.inc_retries_num();
+                        #[cfg(feature = "opentelemetry-031")]
+                        {
+                            retry_count += 1;
+                        }
                         current_consistency = new_cl.unwrap_or(current_consistency);
                         continue 'nodes_in_plan;
                     }
@@ -2187,80 +3278,222 @@ impl Session {
 
     /// Awaits schema agreement among all reachable nodes.
     ///
-    /// Issues an agreement check each `Session::schema_agreement_interval`.
+    /// Issues an agreement check each `interval`.
     /// Loops indefinitely until the agreement is reached.
     ///
-    /// If `required_node` is Some, only returns Ok if this node successfully
-    /// returned its schema version during the agreement process.
+    /// Only returns Ok if all `required_nodes` successfully
+    /// returned their schema versions during the agreement process.
+    ///
+    /// `on_progress` is called after each check, and the last check's state
+    /// is stored in `last_progress`, so that it is available after a timeout.
     async fn await_schema_agreement_indefinitely(
         &self,
-        required_node: Option<Uuid>,
+        interval: Duration,
+        required_nodes: &[Uuid],
+        mut on_progress: impl FnMut(&SchemaAgreementProgress),
+        last_progress: &mut Option<SchemaAgreementProgress>,
     ) -> Result<Uuid, SchemaAgreementError> {
+        let start = std::time::Instant::now();
         loop {
-            tokio::time::sleep(self.schema_agreement_interval).await;
-            if let Some(agreed_version) = self
-                .check_schema_agreement_with_required_node(required_node)
-                .await?
-            {
+            tokio::time::sleep(interval).await;
+            let node_versions = self.read_schema_versions(required_nodes).await?;
+            let agreed_version = agreed_version(&node_versions);
+            let progress = last_progress.insert(SchemaAgreementProgress {
+                elapsed: start.elapsed(),
+                node_versions,
+            });
+            on_progress(progress);
+            if let Some(agreed_version) = agreed_version {
                 return Ok(agreed_version);
             }
         }
     }
 
+    /// Awaits schema agreement among all reachable nodes, giving up after `timeout`
+    /// with `SchemaAgreementError::TimeoutWithLaggingNodes`.
+    async fn await_schema_agreement_with_timeout(
+        &self,
+        timeout_duration: Duration,
+        interval: Duration,
+        required_nodes: &[Uuid],
+        on_progress: impl FnMut(&SchemaAgreementProgress),
+    ) -> Result<Uuid, SchemaAgreementError> {
+        let mut last_progress = None;
+        let result = timeout(
+            timeout_duration,
+            self.await_schema_agreement_indefinitely(
+                interval,
+                required_nodes,
+                on_progress,
+                &mut last_progress,
+            ),
+        )
+        .await;
+        match result {
+            Ok(result) => result,
+            Err(_) => Err(SchemaAgreementError::TimeoutWithLaggingNodes {
+                timeout: timeout_duration,
+                lagging_nodes: last_progress
+                    .as_ref()
+                    .map(|progress| progress.lagging_nodes().cloned().collect())
+                    .unwrap_or_default(),
+            }),
+        }
+    }
+
     /// Awaits schema agreement among all reachable nodes.
     ///
     /// Issues an agreement check each `Session::schema_agreement_interval`.
     /// If agreement is not reached in `Session::schema_agreement_timeout`,
-    /// `SchemaAgreementError::Timeout` is returned.
+    /// `SchemaAgreementError::TimeoutWithLaggingNodes` is returned, listing the nodes
+    /// which did not agree on the prevailing schema version in the last check.
+    ///
+    /// Returns the agreed schema version.
     pub async fn await_schema_agreement(&self) -> Result<Uuid, SchemaAgreementError> {
-        timeout(
-            self.schema_agreement_timeout,
-            self.await_schema_agreement_indefinitely(None),
+        self.await_schema_agreement_with_required_nodes(&[]).await
+    }
+
+    /// Awaits schema agreement among all reachable nodes, with the timeout, interval
+    /// and required hosts given in `options` instead of the session-level configuration.
+    ///
+    /// Issues an agreement check each [interval](SchemaAgreementOptions::with_interval).
+    /// If agreement is not reached within the [timeout](SchemaAgreementOptions::with_timeout),
+    /// `SchemaAgreementError::TimeoutWithLaggingNodes` is returned, listing the nodes
+    /// which did not agree on the prevailing schema version in the last check.
+    /// If some [required host](SchemaAgreementOptions::with_required_hosts) fails
+    /// to report its schema version, awaiting fails immediately.
+    ///
+    /// Returns the agreed schema version.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+    /// use scylla::client::schema_agreement::SchemaAgreementOptions;
+    /// use std::time::Duration;
+    ///
+    /// // A long migration: wait up to 10 minutes, checking every 2 seconds.
+    /// let options = SchemaAgreementOptions::new()
+    ///     .with_timeout(Duration::from_secs(600))
+    ///     .with_interval(Duration::from_secs(2));
+    /// let version = session.await_schema_agreement_with_options(&options).await?;
+    /// println!("Agreed on schema version {version}");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn await_schema_agreement_with_options(
+        &self,
+        options: &SchemaAgreementOptions,
+    ) -> Result<Uuid, SchemaAgreementError> {
+        self.await_schema_agreement_with_timeout(
+            options
+                .get_timeout()
+                .unwrap_or(self.schema_agreement_timeout),
+            options
+                .get_interval()
+                .unwrap_or(self.schema_agreement_interval),
+            options.get_required_hosts(),
+            |_| (),
         )
         .await
-        .unwrap_or(Err(SchemaAgreementError::Timeout(
+    }
+
+    /// Awaits schema agreement among all reachable nodes, reporting progress
+    /// of the process to `on_progress`.
+    ///
+    /// Issues an agreement check each `Session::schema_agreement_interval`,
+    /// and calls `on_progress` after each check with schema versions reported
+    /// by all reachable nodes and the time elapsed since the start.
+    /// If agreement is not reached in `Session::schema_agreement_timeout`,
+    /// `SchemaAgreementError::TimeoutWithLaggingNodes` is returned, listing the nodes
+    /// which did not agree on the prevailing schema version in the last check.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::client::session::Session;
+    /// # use scylla::errors::SchemaAgreementError;
+    /// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+    /// session
+    ///     .query_unpaged("CREATE TABLE IF NOT EXISTS ks.t (a int PRIMARY KEY)", &[])
+    ///     .await?;
+    ///
+    /// let result = session
+    ///     .await_schema_agreement_with_progress(|progress| {
+    ///         println!(
+    ///             "{:?} elapsed, {} node(s) lagging",
+    ///             progress.elapsed,
+    ///             progress.lagging_nodes().count()
+    ///         );
+    ///     })
+    ///     .await;
+    /// if let Err(SchemaAgreementError::TimeoutWithLaggingNodes { lagging_nodes, .. }) = &result {
+    ///     for node in lagging_nodes {
+    ///         println!("Lagging node: {node}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn await_schema_agreement_with_progress(
+        &self,
+        on_progress: impl FnMut(&SchemaAgreementProgress),
+    ) -> Result<Uuid, SchemaAgreementError> {
+        self.await_schema_agreement_with_timeout(
             self.schema_agreement_timeout,
-        )))
+            self.schema_agreement_interval,
+            &[],
+            on_progress,
+        )
+        .await
     }
 
     /// Awaits schema agreement among all reachable nodes.
     ///
     /// Issues an agreement check each `Session::schema_agreement_interval`.
     /// If agreement is not reached in `Session::schema_agreement_timeout`,
-    /// `SchemaAgreementError::Timeout` is returned.
+    /// `SchemaAgreementError::TimeoutWithLaggingNodes` is returned.
     ///
-    /// If `required_node` is Some, only returns Ok if this node successfully
-    /// returned its schema version during the agreement process.
-    async fn await_schema_agreement_with_required_node(
+    /// Only returns Ok if all `required_nodes` successfully
+    /// returned their schema versions during the agreement process.
+    async fn await_schema_agreement_with_required_nodes(
         &self,
-        required_node: Option<Uuid>,
+        required_nodes: &[Uuid],
     ) -> Result<Uuid, SchemaAgreementError> {
-        timeout(
+        self.await_schema_agreement_with_timeout(
             self.schema_agreement_timeout,
-            self.await_schema_agreement_indefinitely(required_node),
+            self.schema_agreement_interval,
+            required_nodes,
+            |_| (),
         )
         .await
-        .unwrap_or(Err(SchemaAgreementError::Timeout(
-            self.schema_agreement_timeout,
-        )))
+    }
+
+    /// Awaits schema agreement among all reachable nodes, which must include `required_node`.
+    async fn await_schema_agreement_with_required_node(
+        &self,
+        required_node: Uuid,
+    ) -> Result<Uuid, SchemaAgreementError> {
+        self.await_schema_agreement_with_required_nodes(&[required_node])
+            .await
     }
 
     /// Checks if all reachable nodes have the same schema version.
     ///
     /// If so, returns that agreed upon version.
     pub async fn check_schema_agreement(&self) -> Result<Option<Uuid>, SchemaAgreementError> {
-        self.check_schema_agreement_with_required_node(None).await
+        let node_versions = self.read_schema_versions(&[]).await?;
+        Ok(agreed_version(&node_versions))
     }
 
-    /// Checks if all reachable nodes have the same schema version.
-    /// If so, returns that agreed upon version.
+    /// Reads schema versions of all reachable nodes.
+    /// The returned vector is nonempty and contains at least one known version.
     ///
-    /// If `required_node` is Some, only returns Ok if this node successfully
-    /// returned its schema version.
-    async fn check_schema_agreement_with_required_node(
+    /// Only returns Ok if all `required_nodes` successfully
+    /// returned their schema versions.
+    async fn read_schema_versions(
         &self,
-        required_node: Option<Uuid>,
-    ) -> Result<Option<Uuid>, SchemaAgreementError> {
+        required_nodes: &[Uuid],
+    ) -> Result<Vec<NodeSchemaVersion>, SchemaAgreementError> {
         let cluster_state = self.get_cluster_state();
         // The iterator is guaranteed to be nonempty.
         let per_node_connections = cluster_state.iter_working_connections_per_node()?;
@@ -2272,8 +3505,8 @@ impl Session {
         // Hence, this is nonempty, too.
         let versions_results = join_all(handles).await;
 
-        // Verify that required host is present, and returned success.
-        if let Some(required_node) = required_node {
+        // Verify that required hosts are present, and returned success.
+        for &required_node in required_nodes {
             match versions_results
                 .iter()
                 .find(|(host_id, _)| *host_id == required_node)
@@ -2293,35 +3526,32 @@ impl Session {
 
         // Now we no longer need all the errors. We can return if there is
         // irrecoverable one, and collect the Ok values otherwise.
-        let versions_results: Vec<_> = versions_results
-            .into_iter()
-            .map(|(_, result)| result)
-            .try_collect()?;
+        let mut node_results = Vec::with_capacity(versions_results.len());
+        for (host_id, result) in versions_results {
+            node_results.push((host_id, result?));
+        }
 
-        // unwrap is safe because iterator is still not empty.
-        let local_version = match versions_results
+        // If there are only broken connection errors, there is nothing better to do
+        // than to return an error.
+        if let Some((_, SchemaNodeResult::BrokenConnection(err))) = node_results
             .iter()
-            .find_or_first(|r| matches!(r, SchemaNodeResult::Success(_)))
-            .unwrap()
+            .find_or_first(|(_, r)| matches!(r, SchemaNodeResult::Success(_)))
         {
-            SchemaNodeResult::Success(v) => *v,
-            SchemaNodeResult::BrokenConnection(err) => {
-                // There are only broken connection errors. Nothing better to do
-                // than to return an error.
-                return Err(SchemaAgreementError::RequestError(
-                    RequestAttemptError::BrokenConnectionError(err.clone()),
-                ));
-            }
-        };
+            return Err(SchemaAgreementError::RequestError(
+                RequestAttemptError::BrokenConnectionError(err.clone()),
+            ));
+        }
 
-        let in_agreement = versions_results
+        Ok(node_results
             .into_iter()
-            .filter_map(|v_r| match v_r {
-                SchemaNodeResult::Success(v) => Some(v),
-                SchemaNodeResult::BrokenConnection(_) => None,
+            .map(|(host_id, result)| NodeSchemaVersion {
+                host_id,
+                schema_version: match result {
+                    SchemaNodeResult::Success(v) => Some(v),
+                    SchemaNodeResult::BrokenConnection(_) => None,
+                },
             })
-            .all(|v| v == local_version);
-        Ok(in_agreement.then_some(local_version))
+            .collect())
     }
 
     /// Iterate over connections to the node.
@@ -2381,6 +3611,9 @@ struct ExecuteRequestContext<'a> {
     load_balancing_policy: &'a dyn load_balancing::LoadBalancingPolicy,
     query_info: &'a load_balancing::RoutingInfo<'a>,
     request_span: &'a RequestSpan,
+    request_hooks: &'a RequestHooks<'a>,
+    attempt_history: &'a std::sync::Mutex<Vec<RequestAttempt>>,
+    deadline: Option<std::time::Instant>,
 }
 
 struct HistoryData<'a> {
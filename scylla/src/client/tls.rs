@@ -0,0 +1,359 @@
+//! Providing TLS contexts for connections, possibly refreshed at runtime.
+//!
+//! A [TlsContextProvider] is consulted each time the driver opens a new connection,
+//! so that certificates and keys can be rotated without rebuilding the [Session](super::session::Session).
+//! Connections that are already open keep using the context they were established with.
+//!
+//! [ReloadableTlsContext] is a provider that (re)builds the context with a user-supplied
+//! loader, either on explicit [ReloadableTlsContext::reload] calls or whenever the watched
+//! certificate files change (see [ReloadableTlsContext::watch_files]).
+//!
+//! A [CertificateVerifier] can additionally check the certificates presented by DB nodes,
+//! e.g. match a SPIFFE ID or a pinned fingerprint, regardless of the TLS backend in use.
+
+use std::fmt;
+use std::io;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwap;
+use tracing::{debug, warn};
+
+use crate::client::session::TlsContext;
+use crate::errors::CertificateRejected;
+
+/// Provides the TLS context used to establish new connections to DB nodes.
+///
+/// The provider is asked for a context every time a new connection is opened,
+/// so the returned context may change over time. A [TlsContext] is a provider
+/// which always returns itself.
+pub trait TlsContextProvider: Send + Sync {
+    /// Returns the TLS context to be used for a new connection.
+    fn tls_context(&self) -> TlsContext;
+}
+
+impl TlsContextProvider for TlsContext {
+    fn tls_context(&self) -> TlsContext {
+        self.clone()
+    }
+}
+
+type TlsContextLoader = dyn Fn() -> io::Result<TlsContext> + Send + Sync;
+
+/// A [TlsContextProvider] whose context can be replaced at runtime.
+///
+/// The context is built by a loader, typically reading certificates and keys from files.
+/// The loader is called once upon creation, and then again on each
+/// [reload](ReloadableTlsContext::reload). If reloading fails, the previous context
+/// stays in use.
+///
+/// # Example
+#[cfg_attr(
+    feature = "rustls-023",
+    doc = r#"
+```
+# async fn example() -> Result<(), Box<dyn std::error::Error>> {
+use std::sync::Arc;
+use std::time::Duration;
+use rustls::pki_types::{pem::PemObject, CertificateDer};
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::client::tls::ReloadableTlsContext;
+
+let tls_context = Arc::new(ReloadableTlsContext::new(|| {
+    let ca = CertificateDer::from_pem_file("ca.crt").map_err(std::io::Error::other)?;
+    let mut root_store = rustls::RootCertStore::empty();
+    root_store.add(ca).map_err(std::io::Error::other)?;
+    let config = rustls::ClientConfig::builder()
+        .with_root_certificates(root_store)
+        .with_no_client_auth();
+    Ok(Arc::new(config).into())
+})?);
+tls_context.watch_files(["ca.crt"], Duration::from_secs(60));
+
+let session: Session = SessionBuilder::new()
+    .known_node("127.0.0.1:9142")
+    .tls_context_provider(tls_context)
+    .build()
+    .await?;
+# Ok(())
+# }
+```
+"#
+)]
+pub struct ReloadableTlsContext {
+    current: ArcSwap<TlsContext>,
+    loader: Box<TlsContextLoader>,
+}
+
+impl ReloadableTlsContext {
+    /// Creates a provider with the context returned by the loader.
+    ///
+    /// Errors returned by TLS backends can be converted into [io::Error]
+    /// through [TlsError](crate::errors::TlsError).
+    pub fn new(
+        loader: impl Fn() -> io::Result<TlsContext> + Send + Sync + 'static,
+    ) -> io::Result<Self> {
+        let context = loader()?;
+        Ok(Self {
+            current: ArcSwap::from_pointee(context),
+            loader: Box::new(loader),
+        })
+    }
+
+    /// Rebuilds the context with the loader. New connections will use the new context.
+    ///
+    /// On failure, the previous context is kept.
+    pub fn reload(&self) -> io::Result<()> {
+        let context = (self.loader)()?;
+        self.current.store(Arc::new(context));
+        Ok(())
+    }
+
+    /// Starts a background task which reloads the context whenever
+    /// the modification time of any of the given files changes.
+    ///
+    /// The files are checked every `interval`. If reloading fails (e.g. because only
+    /// some of the files were replaced so far), it is retried on the next check.
+    /// The task stops once the provider is dropped.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn watch_files(
+        self: &Arc<Self>,
+        paths: impl IntoIterator<Item = impl Into<PathBuf>>,
+        interval: Duration,
+    ) {
+        let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+        tokio::spawn(Self::watch(Arc::downgrade(self), paths, interval));
+    }
+
+    async fn watch(provider: Weak<Self>, paths: Vec<PathBuf>, interval: Duration) {
+        let mut last_modified = modification_times(&paths);
+        let mut ticker = tokio::time::interval(interval);
+        // The first tick completes immediately.
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            let Some(provider) = provider.upgrade() else {
+                return;
+            };
+            let modified = modification_times(&paths);
+            if modified == last_modified {
+                continue;
+            }
+            match provider.reload() {
+                Ok(()) => {
+                    debug!("Reloaded TLS context after a change of {:?}", paths);
+                    last_modified = modified;
+                }
+                Err(err) => warn!(
+                    "Failed to reload TLS context after a change of {:?}: {}",
+                    paths, err
+                ),
+            }
+        }
+    }
+}
+
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+impl TlsContextProvider for ReloadableTlsContext {
+    fn tls_context(&self) -> TlsContext {
+        TlsContext::clone(&self.current.load())
+    }
+}
+
+impl fmt::Debug for ReloadableTlsContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReloadableTlsContext")
+            .finish_non_exhaustive()
+    }
+}
+
+/// Certificates presented by a DB node during a TLS handshake.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PeerCertificates<'a> {
+    /// The IP address of the node.
+    pub node_address: IpAddr,
+    /// DER-encoded certificate chain presented by the node, starting with the node's own certificate.
+    /// Empty if the node presented no certificates.
+    pub chain: &'a [&'a [u8]],
+}
+
+/// Custom verification of the certificates presented by DB nodes.
+///
+/// The verifier is called for every new TLS connection, once the handshake completes,
+/// i.e. after the TLS backend has verified the certificates according to the [TlsContext].
+/// If the verifier rejects the certificates, the connection is closed before any request
+/// is sent over it. To rely solely on the verifier, disable verification in the context
+/// (e.g. with `openssl::ssl::SslVerifyMode::NONE`).
+///
+/// Closures of matching signature implement this trait.
+///
+/// # Example
+/// ```
+/// # use std::sync::Arc;
+/// # use scylla::client::session_builder::SessionBuilder;
+/// use scylla::client::tls::PeerCertificates;
+/// use scylla::errors::CertificateRejected;
+///
+/// // SHA-256 fingerprints would be used in practice.
+/// let pinned_certificate: Vec<u8> = Vec::new();
+/// let builder = SessionBuilder::new().tls_certificate_verifier(Arc::new(
+///     move |peer: &PeerCertificates<'_>| match peer.chain.first() {
+///         Some(&cert) if cert == pinned_certificate.as_slice() => Ok(()),
+///         _ => Err(CertificateRejected::new(format!(
+///             "node {} presented an unexpected certificate",
+///             peer.node_address
+///         ))),
+///     },
+/// ));
+/// ```
+pub trait CertificateVerifier: Send + Sync {
+    /// Verifies the certificates presented by a node.
+    fn verify(&self, peer: &PeerCertificates<'_>) -> Result<(), CertificateRejected>;
+}
+
+impl<F> CertificateVerifier for F
+where
+    F: Fn(&PeerCertificates<'_>) -> Result<(), CertificateRejected> + Send + Sync,
+{
+    fn verify(&self, peer: &PeerCertificates<'_>) -> Result<(), CertificateRejected> {
+        self(peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::net::{IpAddr, Ipv4Addr};
+
+    use super::{CertificateVerifier, PeerCertificates};
+    use crate::errors::{CertificateRejected, TlsError};
+
+    #[test]
+    fn closure_certificate_verifier() {
+        let verifier = |peer: &PeerCertificates<'_>| match peer.chain {
+            [b"trusted", ..] => Ok(()),
+            _ => Err(CertificateRejected::new("untrusted")),
+        };
+        let node_address = IpAddr::V4(Ipv4Addr::LOCALHOST);
+
+        verifier
+            .verify(&PeerCertificates {
+                node_address,
+                chain: &[b"trusted", b"ca"],
+            })
+            .unwrap();
+        let err = verifier
+            .verify(&PeerCertificates {
+                node_address,
+                chain: &[],
+            })
+            .unwrap_err();
+        assert_eq!(err.reason, "untrusted");
+
+        let io_err = io::Error::from(TlsError::from(err));
+        assert_eq!(io_err.kind(), io::ErrorKind::PermissionDenied);
+    }
+
+    #[cfg(feature = "rustls-023")]
+    mod reloadable_tls_context {
+        use std::io;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        use super::super::{ReloadableTlsContext, TlsContextProvider};
+        use crate::client::session::TlsContext;
+
+        fn rustls_config(context: &TlsContext) -> &Arc<rustls::ClientConfig> {
+            #[allow(unreachable_patterns)]
+            match context {
+                TlsContext::Rustls023(config) => config,
+                _ => unreachable!(),
+            }
+        }
+
+        fn counting_provider() -> (Arc<AtomicUsize>, io::Result<ReloadableTlsContext>) {
+            let loads = Arc::new(AtomicUsize::new(0));
+            let provider = ReloadableTlsContext::new({
+                let loads = loads.clone();
+                move || {
+                    if loads.fetch_add(1, Ordering::Relaxed) == 2 {
+                        return Err(io::Error::other("broken certificate"));
+                    }
+                    let config = rustls::ClientConfig::builder()
+                        .with_root_certificates(rustls::RootCertStore::empty())
+                        .with_no_client_auth();
+                    Ok(TlsContext::from(Arc::new(config)))
+                }
+            });
+            (loads, provider)
+        }
+
+        #[test]
+        fn reloadable_tls_context_reload() {
+            let (loads, provider) = counting_provider();
+            let provider = provider.unwrap();
+            assert_eq!(loads.load(Ordering::Relaxed), 1);
+
+            let first = provider.tls_context();
+            assert!(Arc::ptr_eq(
+                rustls_config(&first),
+                rustls_config(&provider.tls_context())
+            ));
+
+            provider.reload().unwrap();
+            let second = provider.tls_context();
+            assert!(!Arc::ptr_eq(rustls_config(&first), rustls_config(&second)));
+
+            // A failed reload keeps the previous context.
+            provider.reload().unwrap_err();
+            assert!(Arc::ptr_eq(
+                rustls_config(&second),
+                rustls_config(&provider.tls_context())
+            ));
+            assert_eq!(loads.load(Ordering::Relaxed), 3);
+        }
+
+        #[tokio::test]
+        async fn reloadable_tls_context_watches_files() {
+            let dir = std::env::temp_dir().join(format!("scylla-tls-watch-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let cert = dir.join("client.crt");
+            std::fs::write(&cert, "first").unwrap();
+
+            let (loads, provider) = counting_provider();
+            let provider = Arc::new(provider.unwrap());
+            provider.watch_files([&cert], Duration::from_millis(10));
+
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            assert_eq!(loads.load(Ordering::Relaxed), 1);
+
+            // Make sure the modification time changes even on coarse-grained filesystems.
+            let file = std::fs::File::options().write(true).open(&cert).unwrap();
+            file.set_modified(std::time::SystemTime::now() + Duration::from_secs(10))
+                .unwrap();
+            drop(file);
+
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while loads.load(Ordering::Relaxed) < 2 {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            })
+            .await
+            .unwrap();
+
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
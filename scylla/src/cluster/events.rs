@@ -0,0 +1,100 @@
+//! Cluster events, which notify about changes in the cluster's topology
+//! and nodes' status observed by the driver.
+
+use std::net::SocketAddr;
+
+use crate::frame::response::event::{StatusChangeEvent, TopologyChangeEvent};
+
+/// An event pushed by the cluster to the driver's control connection,
+/// notifying about a change in the cluster's topology or in a node's status.
+///
+/// Nodes are identified by their broadcast RPC addresses. To get more information
+/// about the node, look it up in [`ClusterState`](super::ClusterState) - note that
+/// for [`ClusterEvent::NodeAdded`] the node may appear there only after the next
+/// metadata refresh, which the driver triggers upon receiving the event.
+///
+/// Events are delivered on a best-effort basis: those pushed while the control
+/// connection is broken are lost, and the cluster may send duplicates
+/// (e.g., several `NodeUp` events for the same node).
+///
+/// See [`Session::subscribe_events`](crate::client::session::Session::subscribe_events).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+// Check triggers because all variants start with "Node".
+#[expect(clippy::enum_variant_names)]
+pub enum ClusterEvent {
+    /// A new node joined the cluster.
+    NodeAdded(SocketAddr),
+    /// A node was removed from the cluster.
+    NodeRemoved(SocketAddr),
+    /// A node became available.
+    NodeUp(SocketAddr),
+    /// A node became unavailable.
+    NodeDown(SocketAddr),
+}
+
+impl ClusterEvent {
+    /// The address of the node the event concerns.
+    pub fn address(&self) -> SocketAddr {
+        match *self {
+            ClusterEvent::NodeAdded(addr)
+            | ClusterEvent::NodeRemoved(addr)
+            | ClusterEvent::NodeUp(addr)
+            | ClusterEvent::NodeDown(addr) => addr,
+        }
+    }
+}
+
+impl From<&TopologyChangeEvent> for ClusterEvent {
+    fn from(event: &TopologyChangeEvent) -> Self {
+        match *event {
+            TopologyChangeEvent::NewNode(addr) => ClusterEvent::NodeAdded(addr),
+            TopologyChangeEvent::RemovedNode(addr) => ClusterEvent::NodeRemoved(addr),
+        }
+    }
+}
+
+impl From<&StatusChangeEvent> for ClusterEvent {
+    fn from(event: &StatusChangeEvent) -> Self {
+        match *event {
+            StatusChangeEvent::Up(addr) => ClusterEvent::NodeUp(addr),
+            StatusChangeEvent::Down(addr) => ClusterEvent::NodeDown(addr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use super::ClusterEvent;
+    use crate::frame::response::event::{StatusChangeEvent, TopologyChangeEvent};
+
+    #[test]
+    fn cluster_event_from_server_events() {
+        let addr: SocketAddr = "10.0.0.1:9042".parse().unwrap();
+
+        let cases = [
+            (
+                ClusterEvent::from(&TopologyChangeEvent::NewNode(addr)),
+                ClusterEvent::NodeAdded(addr),
+            ),
+            (
+                ClusterEvent::from(&TopologyChangeEvent::RemovedNode(addr)),
+                ClusterEvent::NodeRemoved(addr),
+            ),
+            (
+                ClusterEvent::from(&StatusChangeEvent::Up(addr)),
+                ClusterEvent::NodeUp(addr),
+            ),
+            (
+                ClusterEvent::from(&StatusChangeEvent::Down(addr)),
+                ClusterEvent::NodeDown(addr),
+            ),
+        ];
+        for (converted, expected) in cases {
+            assert_eq!(converted, expected);
+            assert_eq!(converted.address(), addr);
+        }
+    }
+}
@@ -6,9 +6,12 @@
 //!   - [Keyspace],
 //!   - [Strategy] - replication strategy employed by a keyspace,
 //!   - [Table],
+//!   - [TableOptions],
 //!   - [Column],
 //!   - [ColumnKind],
 //!   - [MaterializedView],
+//!   - [UserDefinedFunction],
+//!   - [UserDefinedAggregate],
 //!   - CQL types (re-exported from scylla-cql):
 //!     - [ColumnType],
 //!     - [NativeType],
@@ -27,6 +30,8 @@ use crate::network::{ConnectionConfig, NodeConnectionPool, PoolConfig, PoolSize}
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 use crate::policies::host_filter::HostFilter;
+use crate::policies::reconnection::ReconnectionPolicy;
+use crate::routing::partitioner::{PartitionerName, PartitionerResolver};
 use crate::routing::Token;
 use crate::statement::unprepared::Statement;
 use crate::utils::safe_format::IteratorSafeFormatExt;
@@ -48,15 +53,15 @@ use std::num::NonZeroUsize;
 use std::str::FromStr;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use thiserror::Error;
 use tokio::sync::{broadcast, mpsc};
 use tracing::{debug, error, trace, warn};
 use uuid::Uuid;
 
 use crate::cluster::node::{InternalKnownNode, NodeAddr, ResolvedContactPoint};
 use crate::errors::{
-    KeyspaceStrategyError, KeyspacesMetadataError, MetadataError, PeersMetadataError, RequestError,
-    TablesMetadataError, UdtMetadataError,
+    FunctionsMetadataError, KeyspaceStrategyError, MetadataError, MissingUserDefinedType,
+    PeersMetadataError, RequestError, SingleKeyspaceMetadataError, TablesMetadataError,
+    UdtMetadataError,
 };
 
 // Re-export of CQL types.
@@ -72,22 +77,6 @@ type PerTable<T> = HashMap<String, T>;
 type PerKsTable<T> = HashMap<(String, String), T>;
 type PerKsTableResult<T, E> = PerKsTable<Result<T, E>>;
 
-/// Indicates that reading metadata failed, but in a way
-/// that we can handle, by throwing out data for a keyspace.
-/// It is possible that some of the errors could be handled in even
-/// more granular way (e.g. throwing out a single table), but keyspace
-/// granularity seems like a good choice given how independent keyspaces
-/// are from each other.
-#[derive(Clone, Debug, Error)]
-pub(crate) enum SingleKeyspaceMetadataError {
-    #[error(transparent)]
-    MissingUDT(MissingUserDefinedType),
-    #[error("Partition key column with position {0} is missing from metadata")]
-    IncompletePartitionKey(i32),
-    #[error("Clustering key column with position {0} is missing from metadata")]
-    IncompleteClusteringKey(i32),
-}
-
 /// Allows to read current metadata from the cluster
 pub(crate) struct MetadataReader {
     control_connection_pool_config: PoolConfig,
@@ -101,6 +90,7 @@ pub(crate) struct MetadataReader {
     keyspaces_to_fetch: Vec<String>,
     fetch_schema: bool,
     host_filter: Option<Arc<dyn HostFilter>>,
+    partitioners: PartitionerResolver,
 
     // When no known peer is reachable, initial known nodes are resolved once again as a fallback
     // and establishing control connection to them is attempted.
@@ -117,7 +107,17 @@ pub(crate) struct MetadataReader {
 /// Describes all metadata retrieved from the cluster
 pub(crate) struct Metadata {
     pub(crate) peers: Vec<Peer>,
-    pub(crate) keyspaces: HashMap<String, Result<Keyspace, SingleKeyspaceMetadataError>>,
+    /// Errors are handled by throwing out data for the failed part and keeping
+    /// its previous version: all keyspaces if the outer result is an error,
+    /// or a single keyspace if its inner result is an error.
+    /// It is possible that some of the errors could be handled in even
+    /// more granular way (e.g. throwing out a single table), but keyspace
+    /// granularity seems like a good choice given how independent keyspaces
+    /// are from each other.
+    pub(crate) keyspaces:
+        Result<PerKeyspaceResult<Keyspace, SingleKeyspaceMetadataError>, MetadataError>,
+    /// Partitioners used to compute tokens, including the cluster-wide one.
+    pub(crate) partitioners: PartitionerResolver,
 }
 
 /// Represents a node in the cluster, as fetched from the `system.{peers,local}` tables.
@@ -134,6 +134,11 @@ pub struct Peer {
     pub datacenter: Option<String>,
     /// Rack this node is in, if known.
     pub rack: Option<String>,
+    /// Version of the node, as reported in the `release_version` column.
+    ///
+    /// Note that ScyllaDB reports here the version of Cassandra it is
+    /// compatible with, not its own version.
+    pub release_version: Option<String>,
 }
 
 /// An endpoint for a node that the driver is to issue connections to,
@@ -216,6 +221,32 @@ pub struct Keyspace {
     ///
     /// Empty HashMap may as well mean that the client disabled schema fetching in SessionConfig.
     pub user_defined_types: HashMap<String, Arc<UserDefinedType<'static>>>,
+    /// User defined functions in the keyspace, keyed by function name.
+    /// A function may be overloaded, so each name maps to all its overloads,
+    /// which differ in argument types.
+    ///
+    /// Empty HashMap may as well mean that the client disabled schema fetching in SessionConfig.
+    pub functions: HashMap<String, Vec<UserDefinedFunction>>,
+    /// User defined aggregates in the keyspace, keyed by aggregate name.
+    /// An aggregate may be overloaded, so each name maps to all its overloads,
+    /// which differ in argument types.
+    ///
+    /// Empty HashMap may as well mean that the client disabled schema fetching in SessionConfig.
+    pub aggregates: HashMap<String, Vec<UserDefinedAggregate>>,
+}
+
+impl Keyspace {
+    /// Returns the materialized views of the given table of the keyspace,
+    /// paired with their names.
+    pub fn views_of_table<'a>(
+        &'a self,
+        base_table_name: &'a str,
+    ) -> impl Iterator<Item = (&'a str, &'a MaterializedView)> + 'a {
+        self.views
+            .iter()
+            .filter(move |(_, view)| view.base_table_name == base_table_name)
+            .map(|(name, view)| (name.as_str(), view))
+    }
 }
 
 /// Describes a table in the cluster.
@@ -232,10 +263,53 @@ pub struct Table {
     pub clustering_key: Vec<String>,
     /// Name of the partitioner used by the table.
     pub partitioner: Option<String>,
+    /// Options that the table was created or altered with.
+    pub options: TableOptions,
     /// Column specs for the partition key columns.
     pub(crate) pk_column_specs: Vec<ColumnSpec<'static>>,
 }
 
+/// Options of a table or a materialized view, e.g. set with `CREATE TABLE ... WITH ...`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TableOptions {
+    /// Compaction strategy and its options,
+    /// e.g. `{"class": "SizeTieredCompactionStrategy", "min_threshold": "4"}`.
+    pub compaction: HashMap<String, String>,
+    /// Compression options,
+    /// e.g. `{"sstable_compression": "org.apache.cassandra.io.compress.LZ4Compressor"}`.
+    pub compression: HashMap<String, String>,
+    /// Caching options, e.g. `{"keys": "ALL", "rows_per_partition": "ALL"}`.
+    pub caching: HashMap<String, String>,
+    /// Number of seconds after which tombstones may be garbage collected.
+    pub gc_grace_seconds: Option<i32>,
+    /// Default TTL of the data written to the table, in seconds. Zero means no expiration.
+    pub default_time_to_live: Option<i32>,
+    /// CDC options, e.g. `{"enabled": "true", "preimage": "false"}`.
+    ///
+    /// Only ScyllaDB supports CDC configured this way, so this is `None` for Cassandra.
+    /// It may also be `None` for ScyllaDB tables which never had CDC configured.
+    pub cdc: Option<HashMap<String, String>>,
+}
+
+impl TableOptions {
+    /// Sets the options that are stored in `system_schema.tables` or `system_schema.views`.
+    fn set_schema_options(
+        &mut self,
+        compaction: Option<HashMap<String, String>>,
+        compression: Option<HashMap<String, String>>,
+        caching: Option<HashMap<String, String>>,
+        gc_grace_seconds: Option<i32>,
+        default_time_to_live: Option<i32>,
+    ) {
+        self.compaction = compaction.unwrap_or_default();
+        self.compression = compression.unwrap_or_default();
+        self.caching = caching.unwrap_or_default();
+        self.gc_grace_seconds = gc_grace_seconds;
+        self.default_time_to_live = default_time_to_live;
+    }
+}
+
 /// Describes a materialized view in the cluster.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
@@ -245,6 +319,53 @@ pub struct MaterializedView {
     pub view_metadata: Table,
     /// The name of a table that the materialized view is an index of.
     pub base_table_name: String,
+    /// The `WHERE` clause of the statement which created the view,
+    /// i.e. the condition selecting rows of the base table which are included in the view,
+    /// e.g. `v IS NOT NULL AND id IS NOT NULL`.
+    pub where_clause: String,
+    /// Whether the view was created with `SELECT *`, and so includes all columns of the base table,
+    /// including ones added to it later.
+    pub include_all_columns: bool,
+}
+
+/// Describes a user defined function in the cluster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UserDefinedFunction {
+    /// Names of the arguments of the function.
+    pub argument_names: Vec<String>,
+    /// CQL types of the arguments of the function, in the same order as `argument_names`.
+    pub argument_types: Vec<ColumnType<'static>>,
+    /// CQL type of the value returned by the function.
+    pub return_type: ColumnType<'static>,
+    /// Language that the function is written in, e.g. `lua` or `wasm`.
+    pub language: String,
+    /// Source code of the function.
+    pub body: String,
+    /// Whether the function is called when any of its arguments is null.
+    /// If not, the function returns null in such case without being called.
+    pub called_on_null_input: bool,
+}
+
+/// Describes a user defined aggregate in the cluster.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UserDefinedAggregate {
+    /// CQL types of the arguments of the aggregate.
+    pub argument_types: Vec<ColumnType<'static>>,
+    /// CQL type of the value returned by the aggregate.
+    pub return_type: ColumnType<'static>,
+    /// Name of the function which is called for each aggregated row
+    /// and computes the next state of the aggregate.
+    pub state_function: String,
+    /// CQL type of the state of the aggregate.
+    pub state_type: ColumnType<'static>,
+    /// Name of the function which computes the result of the aggregate from its final state,
+    /// if there is any. Otherwise, the final state is the result.
+    pub final_function: Option<String>,
+    /// Initial state of the aggregate, as a CQL literal, if there is any.
+    /// Otherwise, the state is initially null.
+    pub initial_condition: Option<String>,
 }
 
 /// Describes a column of the table.
@@ -316,14 +437,6 @@ impl PreColumnType {
     }
 }
 
-/// Represents a user defined type whose definition is missing from the metadata.
-#[derive(Clone, Debug, Error)]
-#[error("Missing UDT: {keyspace}, {name}")]
-pub(crate) struct MissingUserDefinedType {
-    name: String,
-    keyspace: String,
-}
-
 #[derive(Clone, Debug, PartialEq, Eq)]
 enum PreCollectionType {
     List(Box<PreColumnType>),
@@ -445,7 +558,10 @@ impl Metadata {
     ///
     /// It can be used as a replacement for real metadata when initial
     /// metadata read fails.
-    pub(crate) fn new_dummy(initial_peers: &[UntranslatedEndpoint]) -> Self {
+    pub(crate) fn new_dummy(
+        initial_peers: &[UntranslatedEndpoint],
+        partitioners: PartitionerResolver,
+    ) -> Self {
         let peers = initial_peers
             .iter()
             .enumerate()
@@ -460,13 +576,15 @@ impl Metadata {
                     datacenter: None,
                     rack: None,
                     host_id: Uuid::new_v4(),
+                    release_version: None,
                 }
             })
             .collect();
 
         Metadata {
             peers,
-            keyspaces: HashMap::new(),
+            keyspaces: Ok(HashMap::new()),
+            partitioners,
         }
     }
 }
@@ -483,10 +601,12 @@ impl MetadataReader {
         keyspaces_to_fetch: Vec<String>,
         fetch_schema: bool,
         host_filter: &Option<Arc<dyn HostFilter>>,
+        reconnection_policy: Arc<dyn ReconnectionPolicy>,
+        partitioners: PartitionerResolver,
         #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
     ) -> Result<Self, NewSessionError> {
         let (initial_peers, resolved_hostnames) =
-            resolve_contact_points(&initial_known_nodes).await;
+            resolve_contact_points(&initial_known_nodes, &connection_config).await;
         // Ensure there is at least one resolved node
         if initial_peers.is_empty() {
             return Err(NewSessionError::FailedToResolveAnyHostname(
@@ -515,6 +635,8 @@ impl MetadataReader {
             // The shard-aware port won't be used with PerHost pool size anyway,
             // so explicitly disable it here
             can_use_shard_aware_port: false,
+
+            reconnection_policy,
         };
 
         let control_connection = Self::make_control_connection_pool(
@@ -537,6 +659,7 @@ impl MetadataReader {
             keyspaces_to_fetch,
             fetch_schema,
             host_filter: host_filter.clone(),
+            partitioners,
             initial_known_nodes,
             control_connection_repair_requester,
             #[cfg(feature = "metrics")]
@@ -560,7 +683,57 @@ impl MetadataReader {
         };
 
         // At this point, we known that fetching metadata on currect control connection failed.
-        // Therefore, we try to fetch metadata from other known peers, in order.
+
+        // Re-resolve the initial contact points, in hope that there are some hostnames there
+        // which resolve to reachable new addresses. There is no point in doing that
+        // upon an initial connection attempt, as they have just been resolved.
+        let (new_contact_points, known_contact_points) = if initial {
+            (Vec::new(), Vec::new())
+        } else {
+            let connection_config = &self.control_connection_pool_config.connection_config;
+            let (contact_points, _hostnames) =
+                resolve_contact_points(&self.initial_known_nodes, connection_config).await;
+            contact_points.into_iter().partition(|contact_point| {
+                !self
+                    .known_peers
+                    .iter()
+                    .any(|peer| peer.address().into_inner() == contact_point.address)
+            })
+        };
+
+        // If hostnames resolve to addresses that are not known, the cluster might have been
+        // replaced behind stable hostnames, in which case none of the known peers is reachable.
+        // Try the new addresses first, instead of waiting for all known peers to fail.
+        let mut prev_err = prev_err;
+        if !new_contact_points.is_empty() {
+            warn!(
+                "Initial contact points resolved to new addresses: {}. Trying to establish control connection to them first.",
+                new_contact_points
+                    .iter()
+                    .map(|contact_point| contact_point.address)
+                    .safe_format(", ")
+            );
+            result = self
+                .retry_fetch_metadata_on_nodes(
+                    initial,
+                    new_contact_points
+                        .into_iter()
+                        .map(UntranslatedEndpoint::ContactPoint),
+                    prev_err,
+                )
+                .await;
+            prev_err = match result {
+                Ok(metadata) => {
+                    debug!("Fetched new metadata");
+                    self.update_known_peers(&metadata);
+                    self.handle_unaccepted_host_in_control_connection(&metadata);
+                    return Ok(metadata);
+                }
+                Err(err) => err,
+            };
+        }
+
+        // Otherwise, we try to fetch metadata from other known peers, in order.
 
         // shuffle known_peers to iterate through them in random order later
         self.known_peers.shuffle(&mut rng());
@@ -584,15 +757,14 @@ impl MetadataReader {
 
         if let Err(prev_err) = result {
             if !initial {
-                // If no known peer is reachable, try falling back to initial contact points, in hope that
-                // there are some hostnames there which will resolve to reachable new addresses.
+                // If no known peer is reachable, try falling back to initial contact points
+                // whose addresses were among known peers. They could have been unreachable
+                // when tried as known peers only transiently.
                 warn!("Failed to establish control connection and fetch metadata on all known peers. Falling back to initial contact points.");
-                let (initial_peers, _hostnames) =
-                    resolve_contact_points(&self.initial_known_nodes).await;
                 result = self
                     .retry_fetch_metadata_on_nodes(
                         initial,
-                        initial_peers
+                        known_contact_points
                             .into_iter()
                             .map(UntranslatedEndpoint::ContactPoint),
                         prev_err,
@@ -669,6 +841,7 @@ impl MetadataReader {
                 self.control_connection_endpoint.address().port(),
                 &self.keyspaces_to_fetch,
                 self.fetch_schema,
+                &self.partitioners,
             )
             .await;
 
@@ -681,7 +854,10 @@ impl MetadataReader {
                     This might result in suboptimal performance and schema \
                     information not being available."
                 );
-                return Ok(Metadata::new_dummy(&self.known_peers));
+                return Ok(Metadata::new_dummy(
+                    &self.known_peers,
+                    self.partitioners.clone(),
+                ));
             }
         }
 
@@ -781,11 +957,26 @@ impl ControlConnection {
         connect_port: u16,
         keyspace_to_fetch: &[String],
         fetch_schema: bool,
+        partitioners: &PartitionerResolver,
     ) -> Result<Metadata, MetadataError> {
-        let peers_query = self.query_peers(connect_port);
+        // Ring tokens are parsed according to the cluster-wide partitioner,
+        // so it has to be known before the peers are.
+        let partitioners = match self.query_partitioner().await {
+            Ok(name) => partitioners.with_cluster_partitioner(name.as_deref()),
+            Err(err) => {
+                warn!(
+                    error = %err,
+                    "Failed to fetch the cluster partitioner, assuming Murmur3Partitioner"
+                );
+                partitioners.clone()
+            }
+        };
+
+        let peers_query = self.query_peers(connect_port, partitioners.cluster_partitioner());
         let keyspaces_query = self.query_keyspaces(keyspace_to_fetch, fetch_schema);
 
-        let (peers, keyspaces) = tokio::try_join!(peers_query, keyspaces_query)?;
+        let (peers, keyspaces) = tokio::join!(peers_query, keyspaces_query);
+        let peers = peers?;
 
         // There must be at least one peer
         if peers.is_empty() {
@@ -797,7 +988,11 @@ impl ControlConnection {
             return Err(MetadataError::Peers(PeersMetadataError::EmptyTokenLists));
         }
 
-        Ok(Metadata { peers, keyspaces })
+        Ok(Metadata {
+            peers,
+            keyspaces,
+            partitioners,
+        })
     }
 }
 
@@ -811,6 +1006,7 @@ struct NodeInfoRow {
     datacenter: Option<String>,
     rack: Option<String>,
     tokens: Option<Vec<String>>,
+    release_version: Option<String>,
 }
 
 #[derive(Clone, Copy)]
@@ -831,9 +1027,29 @@ impl NodeInfoSource {
 const METADATA_QUERY_PAGE_SIZE: i32 = 1024;
 
 impl ControlConnection {
-    async fn query_peers(&self, connect_port: u16) -> Result<Vec<Peer>, MetadataError> {
+    async fn query_partitioner(&self) -> Result<Option<String>, MetadataFetchError> {
+        let mut query = Statement::new("select partitioner from system.local WHERE key='local'");
+        query.set_page_size(METADATA_QUERY_PAGE_SIZE);
+        let partitioner = async {
+            let pager = self.query_iter(query).await?;
+            let mut rows_stream = pager.rows_stream::<(Option<String>,)>()?;
+            let row = rows_stream.try_next().await?;
+            Ok::<_, MetadataFetchErrorKind>(row.and_then(|(partitioner,)| partitioner))
+        };
+
+        partitioner.await.map_err(|error| MetadataFetchError {
+            error,
+            table: "system.local",
+        })
+    }
+
+    async fn query_peers(
+        &self,
+        connect_port: u16,
+        partitioner: &PartitionerName,
+    ) -> Result<Vec<Peer>, MetadataError> {
         let mut peers_query = Statement::new(
-            "select host_id, rpc_address, data_center, rack, tokens from system.peers",
+            "select host_id, rpc_address, data_center, rack, tokens, release_version from system.peers",
         );
         peers_query.set_page_size(METADATA_QUERY_PAGE_SIZE);
         let peers_query_stream = self
@@ -854,7 +1070,7 @@ impl ControlConnection {
             .and_then(|row_result| future::ok((NodeInfoSource::Peer, row_result)));
 
         let mut local_query =
-        Statement::new("select host_id, rpc_address, data_center, rack, tokens from system.local WHERE key='local'");
+        Statement::new("select host_id, rpc_address, data_center, rack, tokens, release_version from system.local WHERE key='local'");
         local_query.set_page_size(METADATA_QUERY_PAGE_SIZE);
         let local_query_stream = self
             .query_iter(local_query)
@@ -880,7 +1096,9 @@ impl ControlConnection {
 
         let translated_peers_futures = untranslated_rows.map(|row_result| async {
             match row_result {
-                Ok((source, row)) => Self::create_peer_from_row(source, row, local_address).await,
+                Ok((source, row)) => {
+                    Self::create_peer_from_row(source, row, local_address, partitioner).await
+                }
                 Err(err) => {
                     warn!(
                         "system.peers or system.local has an invalid row, skipping it: {}",
@@ -903,6 +1121,7 @@ impl ControlConnection {
         source: NodeInfoSource,
         row: NodeInfoRow,
         local_address: SocketAddr,
+        partitioner: &PartitionerName,
     ) -> Option<Peer> {
         let NodeInfoRow {
             host_id,
@@ -910,6 +1129,7 @@ impl ControlConnection {
             datacenter,
             rack,
             tokens,
+            release_version,
         } = row;
 
         let host_id = match host_id {
@@ -939,19 +1159,17 @@ impl ControlConnection {
 
         let tokens_str: Vec<String> = tokens.unwrap_or_default();
 
-        // Parse string representation of tokens as integer values
+        // Parse string representation of tokens according to the cluster partitioner
         let tokens: Vec<Token> = match tokens_str
             .iter()
-            .map(|s| Token::from_str(s))
-            .collect::<Result<Vec<Token>, _>>()
+            .map(|s| partitioner.parse_token(s))
+            .collect::<Option<Vec<Token>>>()
         {
-            Ok(parsed) => parsed,
-            Err(e) => {
-                // FIXME: we could allow the users to provide custom partitioning information
-                // in order for it to work with non-standard token sizes.
-                // Also, we could implement support for Cassandra's other standard partitioners
-                // like RandomPartitioner or ByteOrderedPartitioner.
-                trace!("Couldn't parse tokens as 64-bit integers: {}, proceeding with a dummy token. If you're using a partitioner with different token size, consider migrating to murmur3", e);
+            Some(parsed) => parsed,
+            None => {
+                // FIXME: we could implement support for Cassandra's RandomPartitioner,
+                // whose tokens are 128-bit integers.
+                trace!("Couldn't parse tokens using {:?} partitioner, proceeding with a dummy token. If you're using a partitioner with different token size, consider migrating to murmur3 or registering a custom partitioner", partitioner);
                 vec![Token::new(rand::rng().random::<i64>())]
             }
         };
@@ -962,6 +1180,7 @@ impl ControlConnection {
             tokens,
             datacenter,
             rack,
+            release_version,
         })
     }
 
@@ -1029,36 +1248,62 @@ impl ControlConnection {
                 table: "system_schema.keyspaces",
             });
 
-        let (mut all_tables, mut all_views, mut all_user_defined_types) = if fetch_schema {
-            let udts = self.query_user_defined_types(keyspaces_to_fetch).await?;
-            let mut tables_schema = self.query_tables_schema(keyspaces_to_fetch, &udts).await?;
+        // If fetching the schema fails, the keyspaces are still listed (each with the error),
+        // so that the previous metadata of all of them is kept.
+        let (
+            mut all_tables,
+            mut all_views,
+            mut all_user_defined_types,
+            mut all_functions,
+            mut all_aggregates,
+            schema_error,
+        ) = if fetch_schema {
+            match self.query_schema(keyspaces_to_fetch).await {
+                Ok((tables, views, udts, functions, aggregates)) => {
+                    (tables, views, udts, functions, aggregates, None)
+                }
+                Err(err) => {
+                    warn!(error = %err, "Failed to fetch schema metadata");
+                    (
+                        HashMap::new(),
+                        HashMap::new(),
+                        HashMap::new(),
+                        HashMap::new(),
+                        HashMap::new(),
+                        Some(err),
+                    )
+                }
+            }
+        } else {
             (
-                // We pass the mutable reference to the same map to the both functions.
-                // First function fetches `system_schema.tables`, and removes found
-                // table from `tables_schema`.
-                // Second does the same for `system_schema.views`.
-                // The assumption here is that no keys (table names) can appear in both
-                // of those schema table.
-                // As far as we know this assumption is true for Scylla and Cassandra.
-                self.query_tables(keyspaces_to_fetch, &mut tables_schema)
-                    .await?,
-                self.query_views(keyspaces_to_fetch, &mut tables_schema)
-                    .await?,
-                udts,
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                HashMap::new(),
+                None,
             )
-        } else {
-            (HashMap::new(), HashMap::new(), HashMap::new())
         };
 
         rows.map(|row_result| {
             let (keyspace_name, strategy_map) = row_result?;
 
-            let strategy: Strategy = strategy_from_string_map(strategy_map).map_err(|error| {
-                KeyspacesMetadataError::Strategy {
-                    keyspace: keyspace_name.clone(),
-                    error,
+            if let Some(err) = &schema_error {
+                return Ok((
+                    keyspace_name,
+                    Err(SingleKeyspaceMetadataError::SchemaFetch(err.clone())),
+                ));
+            }
+
+            let strategy: Strategy = match strategy_from_string_map(strategy_map) {
+                Ok(strategy) => strategy,
+                Err(err) => {
+                    return Ok((
+                        keyspace_name,
+                        Err(SingleKeyspaceMetadataError::Strategy(err)),
+                    ))
                 }
-            })?;
+            };
             let tables = all_tables
                 .remove(&keyspace_name)
                 .unwrap_or_else(|| Ok(HashMap::new()));
@@ -1068,30 +1313,39 @@ impl ControlConnection {
             let user_defined_types = all_user_defined_types
                 .remove(&keyspace_name)
                 .unwrap_or_else(|| Ok(HashMap::new()));
+            let functions = all_functions
+                .remove(&keyspace_name)
+                .unwrap_or_else(|| Ok(HashMap::new()));
+            let aggregates = all_aggregates
+                .remove(&keyspace_name)
+                .unwrap_or_else(|| Ok(HashMap::new()));
 
             // As you can notice, in this file we generally operate on two layers of errors:
             // - Outer (MetadataError) if something went wrong with querying the cluster.
-            // - Inner (SingleKeyspaceMetadataError) if the fetched metadata turned out to not be fully consistent.
+            // - Inner (SingleKeyspaceMetadataError) if the fetched metadata of the keyspace
+            //   turned out to be invalid or not fully consistent.
             // If there is an inner error, we want to drop metadata for the whole keyspace.
-            // This logic checks if either tables, views, or UDTs have such inner error, and returns it if so.
+            // This logic checks if either tables, views, UDTs, functions or aggregates have such
+            // inner error, and returns it if so.
             // Notice that in the error branch, return value is wrapped in `Ok` - but this is the
             // outer error, so it just means there was no error while querying the cluster.
-            let (tables, views, user_defined_types) = match (tables, views, user_defined_types) {
-                (Ok(t), Ok(v), Ok(u)) => (t, v, u),
-                (Err(e), _, _) | (_, Err(e), _) => return Ok((keyspace_name, Err(e))),
-                (_, _, Err(e)) => {
-                    return Ok((
-                        keyspace_name,
-                        Err(SingleKeyspaceMetadataError::MissingUDT(e)),
-                    ))
-                }
-            };
+            let (tables, views, user_defined_types, functions, aggregates) =
+                match (tables, views, user_defined_types, functions, aggregates) {
+                    (Ok(t), Ok(v), Ok(u), Ok(f), Ok(a)) => (t, v, u, f, a),
+                    (Err(e), _, _, _, _)
+                    | (_, Err(e), _, _, _)
+                    | (_, _, Err(e), _, _)
+                    | (_, _, _, Err(e), _)
+                    | (_, _, _, _, Err(e)) => return Ok((keyspace_name, Err(e))),
+                };
 
             let keyspace = Keyspace {
                 strategy,
                 tables,
                 views,
                 user_defined_types,
+                functions,
+                aggregates,
             };
 
             Ok((keyspace_name, Ok(keyspace)))
@@ -1099,6 +1353,41 @@ impl ControlConnection {
         .try_collect()
         .await
     }
+
+    async fn query_schema(
+        &self,
+        keyspaces_to_fetch: &[String],
+    ) -> Result<
+        (
+            PerKeyspaceResult<PerTable<Table>, SingleKeyspaceMetadataError>,
+            PerKeyspaceResult<PerTable<MaterializedView>, SingleKeyspaceMetadataError>,
+            PerKeyspaceResult<PerTable<Arc<UserDefinedType<'static>>>, SingleKeyspaceMetadataError>,
+            PerKeyspaceResult<PerTable<Vec<UserDefinedFunction>>, SingleKeyspaceMetadataError>,
+            PerKeyspaceResult<PerTable<Vec<UserDefinedAggregate>>, SingleKeyspaceMetadataError>,
+        ),
+        MetadataError,
+    > {
+        let udts = self.query_user_defined_types(keyspaces_to_fetch).await?;
+        let mut tables_schema = self.query_tables_schema(keyspaces_to_fetch, &udts).await?;
+        let functions = self.query_functions(keyspaces_to_fetch, &udts).await?;
+        let aggregates = self.query_aggregates(keyspaces_to_fetch, &udts).await?;
+        Ok((
+            // We pass the mutable reference to the same map to the both functions.
+            // First function fetches `system_schema.tables`, and removes found
+            // table from `tables_schema`.
+            // Second does the same for `system_schema.views`.
+            // The assumption here is that no keys (table names) can appear in both
+            // of those schema table.
+            // As far as we know this assumption is true for Scylla and Cassandra.
+            self.query_tables(keyspaces_to_fetch, &mut tables_schema)
+                .await?,
+            self.query_views(keyspaces_to_fetch, &mut tables_schema)
+                .await?,
+            udts,
+            functions,
+            aggregates,
+        ))
+    }
 }
 
 #[derive(DeserializeRow, Debug)]
@@ -1145,7 +1434,7 @@ impl ControlConnection {
         &self,
         keyspaces_to_fetch: &[String],
     ) -> Result<
-        PerKeyspaceResult<PerTable<Arc<UserDefinedType<'static>>>, MissingUserDefinedType>,
+        PerKeyspaceResult<PerTable<Arc<UserDefinedType<'static>>>, SingleKeyspaceMetadataError>,
         MetadataError,
     > {
         let rows = self.query_filter_keyspace_name::<UdtRow>(
@@ -1157,20 +1446,30 @@ impl ControlConnection {
         table: "system_schema.types",
     });
 
-        let mut udt_rows: Vec<UdtRowWithParsedFieldTypes> = rows
-            .map(|row_result| {
-                let udt_row = row_result?.try_into().map_err(|err: InvalidCqlType| {
-                    MetadataError::Udts(UdtMetadataError::InvalidCqlType {
-                        typ: err.typ,
-                        position: err.position,
-                        reason: err.reason,
-                    })
-                })?;
+        let mut udts = HashMap::new();
 
-                Ok::<_, MetadataError>(udt_row)
-            })
-            .try_collect()
-            .await?;
+        // A type which fails to parse only invalidates its own keyspace.
+        let mut udt_rows: Vec<UdtRowWithParsedFieldTypes> = Vec::new();
+        let raw_udt_rows: Vec<UdtRow> = rows.try_collect().await?;
+        for udt_row in raw_udt_rows {
+            let keyspace_name = udt_row.keyspace_name.clone();
+            match UdtRowWithParsedFieldTypes::try_from(udt_row) {
+                Ok(udt_row) => udt_rows.push(udt_row),
+                Err(err) => {
+                    udts.insert(
+                        keyspace_name,
+                        Err(SingleKeyspaceMetadataError::Udts(
+                            UdtMetadataError::InvalidCqlType {
+                                typ: err.typ,
+                                position: err.position,
+                                reason: err.reason,
+                            },
+                        )),
+                    );
+                }
+            }
+        }
+        udt_rows.retain(|udt_row| !udts.contains_key(&udt_row.keyspace_name));
 
         let instant_before_toposort = Instant::now();
         topo_sort_udts(&mut udt_rows)?;
@@ -1181,7 +1480,6 @@ impl ControlConnection {
             udt_rows.len(),
         );
 
-        let mut udts = HashMap::new();
         'udts_loop: for udt_row in udt_rows {
             let UdtRowWithParsedFieldTypes {
                 keyspace_name,
@@ -1207,7 +1505,7 @@ impl ControlConnection {
                 match field_type.into_cql_type(&keyspace_name_clone, keyspace_udts) {
                     Ok(cql_type) => fields.push((field_name.into(), cql_type)),
                     Err(e) => {
-                        *keyspace_udts_result = Err(e);
+                        *keyspace_udts_result = Err(SingleKeyspaceMetadataError::MissingUDT(e));
                         continue 'udts_loop;
                     }
                 }
@@ -1486,6 +1784,33 @@ mod toposort_tests {
     }
 }
 
+#[derive(DeserializeRow, Debug)]
+#[scylla(crate = "crate")]
+struct TableRow {
+    keyspace_name: String,
+    table_name: String,
+    compaction: Option<HashMap<String, String>>,
+    compression: Option<HashMap<String, String>>,
+    caching: Option<HashMap<String, String>>,
+    gc_grace_seconds: Option<i32>,
+    default_time_to_live: Option<i32>,
+}
+
+#[derive(DeserializeRow, Debug)]
+#[scylla(crate = "crate")]
+struct ViewRow {
+    keyspace_name: String,
+    view_name: String,
+    base_table_name: String,
+    where_clause: Option<String>,
+    include_all_columns: Option<bool>,
+    compaction: Option<HashMap<String, String>>,
+    compression: Option<HashMap<String, String>>,
+    caching: Option<HashMap<String, String>>,
+    gc_grace_seconds: Option<i32>,
+    default_time_to_live: Option<i32>,
+}
+
 impl ControlConnection {
     async fn query_tables(
         &self,
@@ -1494,8 +1819,8 @@ impl ControlConnection {
     ) -> Result<PerKeyspaceResult<PerTable<Table>, SingleKeyspaceMetadataError>, MetadataError>
     {
         let rows = self
-            .query_filter_keyspace_name::<(String, String)>(
-                "SELECT keyspace_name, table_name FROM system_schema.tables",
+            .query_filter_keyspace_name::<TableRow>(
+                "SELECT keyspace_name, table_name, compaction, compression, caching, gc_grace_seconds, default_time_to_live FROM system_schema.tables",
                 keyspaces_to_fetch,
             )
             .map_err(|error| MetadataFetchError {
@@ -1505,15 +1830,38 @@ impl ControlConnection {
         let mut result = HashMap::new();
 
         rows.map(|row_result| {
-            let keyspace_and_table_name = row_result?;
+            let TableRow {
+                keyspace_name,
+                table_name,
+                compaction,
+                compression,
+                caching,
+                gc_grace_seconds,
+                default_time_to_live,
+            } = row_result?;
 
-            let table = tables.remove(&keyspace_and_table_name).unwrap_or(Ok(Table {
-                columns: HashMap::new(),
-                partition_key: vec![],
-                clustering_key: vec![],
-                partitioner: None,
-                pk_column_specs: vec![],
-            }));
+            let keyspace_and_table_name = (keyspace_name, table_name);
+
+            let table = tables
+                .remove(&keyspace_and_table_name)
+                .unwrap_or(Ok(Table {
+                    columns: HashMap::new(),
+                    partition_key: vec![],
+                    clustering_key: vec![],
+                    partitioner: None,
+                    options: TableOptions::default(),
+                    pk_column_specs: vec![],
+                }))
+                .map(|mut table| {
+                    table.options.set_schema_options(
+                        compaction,
+                        compression,
+                        caching,
+                        gc_grace_seconds,
+                        default_time_to_live,
+                    );
+                    table
+                });
 
             let mut entry = result
                 .entry(keyspace_and_table_name.0)
@@ -1543,8 +1891,8 @@ impl ControlConnection {
         MetadataError,
     > {
         let rows = self
-            .query_filter_keyspace_name::<(String, String, String)>(
-                "SELECT keyspace_name, view_name, base_table_name FROM system_schema.views",
+            .query_filter_keyspace_name::<ViewRow>(
+                "SELECT keyspace_name, view_name, base_table_name, where_clause, include_all_columns, compaction, compression, caching, gc_grace_seconds, default_time_to_live FROM system_schema.views",
                 keyspaces_to_fetch,
             )
             .map_err(|error| MetadataFetchError {
@@ -1555,7 +1903,18 @@ impl ControlConnection {
         let mut result = HashMap::new();
 
         rows.map(|row_result| {
-            let (keyspace_name, view_name, base_table_name) = row_result?;
+            let ViewRow {
+                keyspace_name,
+                view_name,
+                base_table_name,
+                where_clause,
+                include_all_columns,
+                compaction,
+                compression,
+                caching,
+                gc_grace_seconds,
+                default_time_to_live,
+            } = row_result?;
 
             let keyspace_and_view_name = (keyspace_name, view_name);
 
@@ -1566,11 +1925,23 @@ impl ControlConnection {
                     partition_key: vec![],
                     clustering_key: vec![],
                     partitioner: None,
+                    options: TableOptions::default(),
                     pk_column_specs: vec![],
                 }))
-                .map(|table| MaterializedView {
-                    view_metadata: table,
-                    base_table_name,
+                .map(|mut table| {
+                    table.options.set_schema_options(
+                        compaction,
+                        compression,
+                        caching,
+                        gc_grace_seconds,
+                        default_time_to_live,
+                    );
+                    MaterializedView {
+                        view_metadata: table,
+                        base_table_name,
+                        where_clause: where_clause.unwrap_or_default(),
+                        include_all_columns: include_all_columns.unwrap_or(false),
+                    }
                 });
 
             let mut entry = result
@@ -1596,7 +1967,10 @@ impl ControlConnection {
     async fn query_tables_schema(
         &self,
         keyspaces_to_fetch: &[String],
-        udts: &PerKeyspaceResult<PerTable<Arc<UserDefinedType<'static>>>, MissingUserDefinedType>,
+        udts: &PerKeyspaceResult<
+            PerTable<Arc<UserDefinedType<'static>>>,
+            SingleKeyspaceMetadataError,
+        >,
     ) -> Result<PerKsTableResult<Table, SingleKeyspaceMetadataError>, MetadataError> {
         // Upon migration from thrift to CQL, Cassandra internally creates a surrogate column "value" of
         // type EmptyType for dense tables. This resolves into this CQL type name.
@@ -1649,20 +2023,27 @@ impl ControlConnection {
                         // is minor enough to ignore. Note that the first issue also applies to
                         // solution 1: but the keyspace won't be present in the result at all,
                         // which is arguably worse.
-                        tables_schema.insert(
-                            (keyspace_name, table_name),
-                            Err(SingleKeyspaceMetadataError::MissingUDT(e.clone())),
-                        );
+                        tables_schema.insert((keyspace_name, table_name), Err(e.clone()));
                         return Ok::<_, MetadataError>(());
                     }
                 };
-            let pre_cql_type = map_string_to_cql_type(&type_).map_err(|err: InvalidCqlType| {
-                TablesMetadataError::InvalidCqlType {
-                    typ: err.typ,
-                    position: err.position,
-                    reason: err.reason,
+            // A column which fails to parse only invalidates its own table (and so its keyspace).
+            let pre_cql_type = match map_string_to_cql_type(&type_) {
+                Ok(t) => t,
+                Err(err) => {
+                    tables_schema.insert(
+                        (keyspace_name, table_name),
+                        Err(SingleKeyspaceMetadataError::Tables(
+                            TablesMetadataError::InvalidCqlType {
+                                typ: err.typ,
+                                position: err.position,
+                                reason: err.reason,
+                            },
+                        )),
+                    );
+                    return Ok::<_, MetadataError>(());
                 }
-            })?;
+            };
             let cql_type = match pre_cql_type.into_cql_type(&keyspace_name, keyspace_udts) {
                 Ok(t) => t,
                 Err(e) => {
@@ -1674,14 +2055,19 @@ impl ControlConnection {
                 }
             };
 
-            let kind = ColumnKind::from_str(&kind).map_err(|_| {
-                TablesMetadataError::UnknownColumnKind {
+            let Ok(kind) = ColumnKind::from_str(&kind) else {
+                let err = TablesMetadataError::UnknownColumnKind {
                     keyspace_name: keyspace_name.clone(),
                     table_name: table_name.clone(),
                     column_name: column_name.clone(),
                     column_kind: kind,
-                }
-            })?;
+                };
+                tables_schema.insert(
+                    (keyspace_name, table_name),
+                    Err(SingleKeyspaceMetadataError::Tables(err)),
+                );
+                return Ok::<_, MetadataError>(());
+            };
 
             let Ok(entry) = tables_schema
                 .entry((keyspace_name, table_name))
@@ -1717,7 +2103,7 @@ impl ControlConnection {
         .try_for_each(|_| future::ok(()))
         .await?;
 
-        let mut all_partitioners = self.query_table_partitioners().await?;
+        let mut all_scylla_table_options = self.query_scylla_table_options().await?;
         let mut result = HashMap::new();
 
         'tables_loop: for ((keyspace_name, table_name), table_result) in tables_schema {
@@ -1783,7 +2169,7 @@ impl ControlConnection {
                 }
             };
 
-            let partitioner = all_partitioners
+            let (partitioner, cdc) = all_scylla_table_options
                 .remove(&keyspace_and_table_name)
                 .unwrap_or_default();
 
@@ -1808,6 +2194,10 @@ impl ControlConnection {
                     partition_key,
                     clustering_key,
                     partitioner,
+                    options: TableOptions {
+                        cdc,
+                        ..Default::default()
+                    },
                     pk_column_specs,
                 }),
             );
@@ -1817,6 +2207,226 @@ impl ControlConnection {
     }
 }
 
+#[derive(DeserializeRow, Debug)]
+#[scylla(crate = "crate")]
+struct FunctionRow {
+    keyspace_name: String,
+    function_name: String,
+    argument_names: Option<Vec<String>>,
+    argument_types: Option<Vec<String>>,
+    return_type: String,
+    language: String,
+    body: String,
+    called_on_null_input: bool,
+}
+
+#[derive(DeserializeRow, Debug)]
+#[scylla(crate = "crate")]
+struct AggregateRow {
+    keyspace_name: String,
+    aggregate_name: String,
+    argument_types: Option<Vec<String>>,
+    return_type: String,
+    state_func: String,
+    state_type: String,
+    final_func: Option<String>,
+    initcond: Option<String>,
+}
+
+impl ControlConnection {
+    async fn query_functions(
+        &self,
+        keyspaces_to_fetch: &[String],
+        udts: &PerKeyspaceResult<
+            PerTable<Arc<UserDefinedType<'static>>>,
+            SingleKeyspaceMetadataError,
+        >,
+    ) -> Result<
+        PerKeyspaceResult<PerTable<Vec<UserDefinedFunction>>, SingleKeyspaceMetadataError>,
+        MetadataError,
+    > {
+        const TABLE: &str = "system_schema.functions";
+
+        let rows = self
+            .query_filter_keyspace_name::<FunctionRow>(
+                "select keyspace_name, function_name, argument_names, argument_types, return_type, language, body, called_on_null_input from system_schema.functions",
+                keyspaces_to_fetch,
+            )
+            .map_err(|error| MetadataFetchError {
+                error,
+                table: TABLE,
+            });
+
+        let empty_ok_map = Ok(HashMap::new());
+        let mut result = HashMap::new();
+
+        rows.map(|row_result| {
+            let FunctionRow {
+                keyspace_name,
+                function_name,
+                argument_names,
+                argument_types,
+                return_type,
+                language,
+                body,
+                called_on_null_input,
+            } = row_result?;
+
+            // Functions may take and return UDTs, so a keyspace with broken UDTs
+            // is considered to have broken functions, too.
+            let function = match udts.get(&keyspace_name).unwrap_or(&empty_ok_map) {
+                Ok(keyspace_udts) => {
+                    let resolve = |typ: &str| {
+                        resolve_function_type(typ, TABLE, &keyspace_name, keyspace_udts)
+                    };
+                    argument_types
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|typ| resolve(typ))
+                        .collect::<Result<Vec<_>, _>>()
+                        .and_then(|argument_types| {
+                            Ok(UserDefinedFunction {
+                                argument_names: argument_names.unwrap_or_default(),
+                                argument_types,
+                                return_type: resolve(&return_type)?,
+                                language,
+                                body,
+                                called_on_null_input,
+                            })
+                        })
+                }
+                Err(e) => Err(e.clone()),
+            };
+
+            let mut entry = result
+                .entry(keyspace_name)
+                .or_insert_with(|| Ok(HashMap::new()));
+            match (&mut entry, function) {
+                (Ok(functions), Ok(function)) => {
+                    functions
+                        .entry(function_name)
+                        .or_insert_with(Vec::new)
+                        .push(function);
+                }
+                (Err(_), _) => (),
+                (Ok(_), Err(e)) => *entry = Err(e),
+            };
+
+            Ok::<_, MetadataError>(())
+        })
+        .try_for_each(|_| future::ok(()))
+        .await?;
+
+        Ok(result)
+    }
+
+    async fn query_aggregates(
+        &self,
+        keyspaces_to_fetch: &[String],
+        udts: &PerKeyspaceResult<
+            PerTable<Arc<UserDefinedType<'static>>>,
+            SingleKeyspaceMetadataError,
+        >,
+    ) -> Result<
+        PerKeyspaceResult<PerTable<Vec<UserDefinedAggregate>>, SingleKeyspaceMetadataError>,
+        MetadataError,
+    > {
+        const TABLE: &str = "system_schema.aggregates";
+
+        let rows = self
+            .query_filter_keyspace_name::<AggregateRow>(
+                "select keyspace_name, aggregate_name, argument_types, return_type, state_func, state_type, final_func, initcond from system_schema.aggregates",
+                keyspaces_to_fetch,
+            )
+            .map_err(|error| MetadataFetchError {
+                error,
+                table: TABLE,
+            });
+
+        let empty_ok_map = Ok(HashMap::new());
+        let mut result = HashMap::new();
+
+        rows.map(|row_result| {
+            let AggregateRow {
+                keyspace_name,
+                aggregate_name,
+                argument_types,
+                return_type,
+                state_func,
+                state_type,
+                final_func,
+                initcond,
+            } = row_result?;
+
+            // See the analogous comment in `query_functions`.
+            let aggregate = match udts.get(&keyspace_name).unwrap_or(&empty_ok_map) {
+                Ok(keyspace_udts) => {
+                    let resolve = |typ: &str| {
+                        resolve_function_type(typ, TABLE, &keyspace_name, keyspace_udts)
+                    };
+                    argument_types
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|typ| resolve(typ))
+                        .collect::<Result<Vec<_>, _>>()
+                        .and_then(|argument_types| {
+                            Ok(UserDefinedAggregate {
+                                argument_types,
+                                return_type: resolve(&return_type)?,
+                                state_function: state_func,
+                                state_type: resolve(&state_type)?,
+                                final_function: final_func,
+                                initial_condition: initcond,
+                            })
+                        })
+                }
+                Err(e) => Err(e.clone()),
+            };
+
+            let mut entry = result
+                .entry(keyspace_name)
+                .or_insert_with(|| Ok(HashMap::new()));
+            match (&mut entry, aggregate) {
+                (Ok(aggregates), Ok(aggregate)) => {
+                    aggregates
+                        .entry(aggregate_name)
+                        .or_insert_with(Vec::new)
+                        .push(aggregate);
+                }
+                (Err(_), _) => (),
+                (Ok(_), Err(e)) => *entry = Err(e),
+            };
+
+            Ok::<_, MetadataError>(())
+        })
+        .try_for_each(|_| future::ok(()))
+        .await?;
+
+        Ok(result)
+    }
+}
+
+/// Parses a CQL type of an argument, a return value or a state of a function
+/// or an aggregate, as read from the given schema table.
+fn resolve_function_type(
+    typ: &str,
+    table: &'static str,
+    keyspace_name: &String,
+    keyspace_udts: &PerTable<Arc<UserDefinedType<'static>>>,
+) -> Result<ColumnType<'static>, SingleKeyspaceMetadataError> {
+    let pre_cql_type = map_string_to_cql_type(typ).map_err(|err| {
+        SingleKeyspaceMetadataError::Functions(FunctionsMetadataError::InvalidCqlType {
+            table,
+            typ: err.typ,
+            position: err.position,
+            reason: err.reason,
+        })
+    })?;
+    pre_cql_type
+        .into_cql_type(keyspace_name, keyspace_udts)
+        .map_err(SingleKeyspaceMetadataError::MissingUDT)
+}
+
 fn map_string_to_cql_type(typ: &str) -> Result<PreColumnType, InvalidCqlType> {
     match parse_cql_type(ParserState::new(typ)) {
         Err(err) => Err(InvalidCqlType {
@@ -1972,9 +2582,12 @@ fn freeze_type(typ: PreColumnType) -> PreColumnType {
 }
 
 impl ControlConnection {
-    async fn query_table_partitioners(
+    /// Fetches the partitioners and CDC options of tables, which are stored
+    /// in ScyllaDB-specific `system_schema.scylla_tables`.
+    async fn query_scylla_table_options(
         &self,
-    ) -> Result<PerKsTable<Option<String>>, MetadataFetchError> {
+    ) -> Result<PerKsTable<(Option<String>, Option<HashMap<String, String>>)>, MetadataFetchError>
+    {
         fn create_err(err: impl Into<MetadataFetchErrorKind>) -> MetadataFetchError {
             MetadataFetchError {
                 error: err.into(),
@@ -1982,17 +2595,22 @@ impl ControlConnection {
             }
         }
 
-        let mut partitioner_query = Statement::new(
-            "select keyspace_name, table_name, partitioner from system_schema.scylla_tables",
+        let mut scylla_tables_query = Statement::new(
+            "select keyspace_name, table_name, partitioner, cdc from system_schema.scylla_tables",
         );
-        partitioner_query.set_page_size(METADATA_QUERY_PAGE_SIZE);
+        scylla_tables_query.set_page_size(METADATA_QUERY_PAGE_SIZE);
 
         let rows = self
-            .query_iter(partitioner_query)
+            .query_iter(scylla_tables_query)
             .map(|pager_res| {
                 let pager = pager_res.map_err(create_err)?;
                 let stream = pager
-                    .rows_stream::<(String, String, Option<String>)>()
+                    .rows_stream::<(
+                        String,
+                        String,
+                        Option<String>,
+                        Option<HashMap<String, String>>,
+                    )>()
                     // Map the error of Result<TypedRowStream, TypecheckError>
                     .map_err(create_err)?
                     // Map the error of single stream iteration (NextRowError)
@@ -2004,8 +2622,8 @@ impl ControlConnection {
 
         let result = rows
             .map(|row_result| {
-                let (keyspace_name, table_name, partitioner) = row_result?;
-                Ok::<_, MetadataFetchError>(((keyspace_name, table_name), partitioner))
+                let (keyspace_name, table_name, partitioner, cdc) = row_result?;
+                Ok::<_, MetadataFetchError>(((keyspace_name, table_name), (partitioner, cdc)))
             })
             .try_collect::<HashMap<_, _>>()
             .await;
@@ -2018,12 +2636,16 @@ impl ControlConnection {
             Err(MetadataFetchError {
                 error:
                     MetadataFetchErrorKind::NextRowError(NextRowError::NextPageError(
-                        NextPageError::RequestFailure(RequestError::LastAttemptError(
-                            RequestAttemptError::DbError(DbError::Invalid, _),
-                        )),
+                        NextPageError::RequestFailure(err),
                     )),
                 ..
-            }) => Ok(HashMap::new()),
+            }) if matches!(
+                err.inner(),
+                RequestError::LastAttemptError(RequestAttemptError::DbError(DbError::Invalid, _))
+            ) =>
+            {
+                Ok(HashMap::new())
+            }
             result => result,
         }
     }
@@ -2265,4 +2887,50 @@ mod tests {
             assert_eq!(parsed, expected);
         }
     }
+
+    #[test]
+    fn test_function_type_resolution() {
+        setup_tracing();
+        let keyspace_name = "ks".to_string();
+        let my_type = Arc::new(UserDefinedType {
+            name: "my_type".into(),
+            keyspace: "ks".into(),
+            field_types: vec![("a".into(), ColumnType::Native(NativeType::Int))],
+        });
+        let keyspace_udts: PerTable<_> = [("my_type".to_string(), my_type.clone())].into();
+        let table = "system_schema.functions";
+
+        assert_eq!(
+            resolve_function_type(
+                "list<frozen<my_type>>",
+                table,
+                &keyspace_name,
+                &keyspace_udts
+            )
+            .unwrap(),
+            ColumnType::Collection {
+                frozen: false,
+                typ: CollectionType::List(Box::new(ColumnType::UserDefinedType {
+                    frozen: true,
+                    definition: my_type,
+                })),
+            }
+        );
+
+        assert_matches::assert_matches!(
+            resolve_function_type("other_type", table, &keyspace_name, &keyspace_udts),
+            Err(SingleKeyspaceMetadataError::MissingUDT(MissingUserDefinedType { name, .. }))
+                if name == "other_type"
+        );
+
+        assert_matches::assert_matches!(
+            resolve_function_type("map<int>", table, &keyspace_name, &keyspace_udts),
+            Err(SingleKeyspaceMetadataError::Functions(
+                FunctionsMetadataError::InvalidCqlType {
+                    table: "system_schema.functions",
+                    ..
+                }
+            ))
+        );
+    }
 }
@@ -8,6 +8,7 @@
 //!   - topology metadata,
 //!   - schema metadata,
 //    - tablet metadata,
+//! - [ClusterEvent], which notifies about changes in the cluster's topology and nodes' status.
 //! - [ClusterState], which is a snapshot of the cluster's state.
 //!   - [ClusterState] is replaced atomically upon a metadata refresh,
 //!     preventing any issues arising from mutability, including races.
@@ -21,6 +22,9 @@ pub(crate) use worker::{use_keyspace_result, Cluster, ClusterNeatDebug};
 mod state;
 pub use state::ClusterState;
 
+mod events;
+pub use events::ClusterEvent;
+
 pub(crate) mod node;
 pub use node::{KnownNode, Node, NodeAddr, NodeRef};
 
@@ -1,22 +1,26 @@
+use futures::stream::{FuturesUnordered, StreamExt};
 use itertools::Itertools;
 use tokio::net::lookup_host;
-use tracing::warn;
+use tracing::{debug, warn};
 use uuid::Uuid;
 
-use crate::errors::{ConnectionPoolError, UseKeyspaceError};
-use crate::network::Connection;
+use crate::errors::{ConnectionPoolError, ScyllaVersionError, UseKeyspaceError};
 use crate::network::VerifiedKeyspaceName;
-use crate::network::{NodeConnectionPool, PoolConfig};
+use crate::network::{connect_with_source_ip_and_port, Connection, ConnectionConfig};
+use crate::network::{NodeConnectionPool, PoolConfig, PoolState};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 /// Node represents a cluster node along with it's data and connections
 use crate::routing::{Shard, Sharder};
 
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::io;
 use std::net::IpAddr;
 #[cfg(test)]
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::Duration;
 use std::{
     hash::{Hash, Hasher},
     net::SocketAddr,
@@ -24,6 +28,7 @@ use std::{
 };
 
 use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
+use crate::utils::safe_format::IteratorSafeFormatExt;
 
 /// This enum is introduced to support address translation only upon opening a connection,
 /// as well as to cope with a bug present in older Cassandra and ScyllaDB releases.
@@ -91,12 +96,27 @@ pub struct Node {
     pub datacenter: Option<String>,
     /// Rack of the node, if known.
     pub rack: Option<String>,
+    /// Version of the node, as reported in the `release_version` column
+    /// of `system.local`/`system.peers`, if known.
+    ///
+    /// Note that ScyllaDB reports here the version of Cassandra it is
+    /// compatible with, not its own version. Use [`Node::fetch_scylla_version`]
+    /// to get the latter.
+    ///
+    /// The value is kept up to date by metadata refreshes, so it can be used
+    /// to make version-dependent decisions during rolling upgrades.
+    pub release_version: Option<String>,
 
     /// Connection pool for this node.
     ///
     /// If the node is filtered out by the host filter, this will be [None].
     pool: Option<NodeConnectionPool>,
 
+    /// Request statistics of this node, used by load balancing policies.
+    ///
+    /// Shared with the node definitions that replace this one after an IP or version change.
+    statistics: Arc<NodeStatistics>,
+
     // In unit tests Node objects are mocked, and don't have real connection
     // pools. We want DefaultPolicy to use is_connected to filter out nodes,
     // but it would mean that all nodes would be filtered out in unit tests.
@@ -114,6 +134,7 @@ impl Node {
     /// Creates a new node which starts connecting in the background.
     pub(crate) fn new(
         peer: PeerEndpoint,
+        release_version: Option<String>,
         pool_config: &PoolConfig,
         keyspace_name: Option<VerifiedKeyspaceName>,
         enabled: bool,
@@ -142,31 +163,42 @@ impl Node {
             address,
             datacenter,
             rack,
+            release_version,
             pool,
+            statistics: Default::default(),
             #[cfg(test)]
             enabled_as_connected: AtomicBool::new(false),
         }
     }
 
-    /// Recreates a Node after it changes its IP, preserving the pool.
+    /// Recreates a Node after it changes its IP or version, preserving the pool.
     ///
-    /// All settings except address are inherited from `node`.
+    /// All settings except address and version are inherited from `node`.
     /// The underlying pool is preserved and notified about the IP change.
     /// # Arguments
     ///
     /// - `node` - previous definition of that node
-    /// - `address` - new address to connect to
-    pub(crate) fn inherit_with_ip_changed(node: &Node, endpoint: PeerEndpoint) -> Self {
+    /// - `endpoint` - new address to connect to
+    /// - `release_version` - new version of the node
+    pub(crate) fn inherit_with_ip_or_version_changed(
+        node: &Node,
+        endpoint: PeerEndpoint,
+        release_version: Option<String>,
+    ) -> Self {
         let address = endpoint.address;
         if let Some(ref pool) = node.pool {
-            pool.update_endpoint(endpoint);
+            if node.address != address {
+                pool.update_endpoint(endpoint);
+            }
         }
         Self {
             address,
             datacenter: node.datacenter.clone(),
             rack: node.rack.clone(),
+            release_version,
             host_id: node.host_id,
             pool: node.pool.clone(),
+            statistics: node.statistics.clone(),
             #[cfg(test)]
             enabled_as_connected: AtomicBool::new(node.enabled_as_connected.load(Ordering::SeqCst)),
         }
@@ -183,6 +215,29 @@ impl Node {
         self.pool.as_ref()?.sharder()
     }
 
+    /// Retrieves the options advertised by this node in the SUPPORTED response
+    /// during the connection handshake, e.g. supported CQL versions, compression
+    /// algorithms or ScyllaDB protocol extensions.
+    ///
+    /// The options are taken from one of the currently open connections,
+    /// so they reflect the current state of the node even during rolling upgrades.
+    /// Returns `None` if the node is disabled or there are no open connections to it.
+    pub fn supported_options(&self) -> Option<Arc<HashMap<String, Vec<String>>>> {
+        self.get_random_connection()
+            .ok()
+            .map(|conn| conn.get_supported_options())
+    }
+
+    /// Fetches the ScyllaDB version of this node (e.g. `2025.1.0`)
+    /// from its `system.versions` table, over one of the connections to the node.
+    ///
+    /// Unlike [`Node::release_version`], the version is not cached, so it reflects
+    /// the version the node runs at the moment, even during rolling upgrades.
+    /// Returns `Ok(None)` if the node is not a ScyllaDB node.
+    pub async fn fetch_scylla_version(&self) -> Result<Option<String>, ScyllaVersionError> {
+        self.get_random_connection()?.fetch_scylla_version().await
+    }
+
     /// Get a connection targetting the given shard
     /// If such connection is broken, get any random connection to this `Node`
     pub(crate) async fn connection_for_shard(
@@ -205,6 +260,14 @@ impl Node {
         pool.is_connected()
     }
 
+    /// Returns a snapshot of the state of the connection pool of this node.
+    pub fn pool_state(&self) -> PoolState {
+        match &self.pool {
+            Some(pool) => pool.status(),
+            None => PoolState::Disabled,
+        }
+    }
+
     /// Returns a boolean which indicates whether this node was is enabled.
     /// Only enabled nodes will have connections open. For disabled nodes,
     /// no connections will be opened.
@@ -243,6 +306,70 @@ impl Node {
             .as_ref()
             .ok_or(ConnectionPoolError::NodeDisabledByHostFilter)
     }
+
+    /// Returns the number of request attempts currently in flight to this node.
+    pub fn in_flight_requests(&self) -> usize {
+        self.statistics.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Returns an exponentially weighted moving average of latencies
+    /// of successful request attempts sent to this node,
+    /// or `None` if no attempt has succeeded yet.
+    pub fn average_latency(&self) -> Option<Duration> {
+        match self.statistics.latency_ewma_micros.load(Ordering::Relaxed) {
+            0 => None,
+            micros => Some(Duration::from_micros(micros)),
+        }
+    }
+
+    /// Marks the start of a request attempt sent to this node.
+    /// The attempt is counted as in flight until the returned guard is dropped.
+    pub(crate) fn start_request(&self) -> InFlightRequestGuard {
+        self.statistics.in_flight.fetch_add(1, Ordering::Relaxed);
+        InFlightRequestGuard {
+            statistics: self.statistics.clone(),
+        }
+    }
+
+    /// Records the latency of a successful request attempt sent to this node.
+    pub(crate) fn record_latency(&self, latency: Duration) {
+        // Zero is reserved for "no samples yet".
+        let sample = (latency.as_micros() as u64).max(1);
+        let _ = self.statistics.latency_ewma_micros.fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |average| {
+                Some(match average {
+                    0 => sample,
+                    average => ((average * (LATENCY_EWMA_WEIGHT - 1) + sample)
+                        / LATENCY_EWMA_WEIGHT)
+                        .max(1),
+                })
+            },
+        );
+    }
+}
+
+/// The weight of the previous average when a new latency sample is recorded;
+/// a sample contributes 1/LATENCY_EWMA_WEIGHT to the new average.
+const LATENCY_EWMA_WEIGHT: u64 = 8;
+
+#[derive(Debug, Default)]
+struct NodeStatistics {
+    in_flight: AtomicUsize,
+    /// Average latency in microseconds, 0 if there are no samples yet.
+    latency_ewma_micros: AtomicU64,
+}
+
+/// Counts a request attempt as in flight to a node until dropped.
+pub(crate) struct InFlightRequestGuard {
+    statistics: Arc<NodeStatistics>,
+}
+
+impl Drop for InFlightRequestGuard {
+    fn drop(&mut self) {
+        self.statistics.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl PartialEq for Node {
@@ -311,29 +438,138 @@ pub(crate) struct ResolvedContactPoint {
 // Resolve the given hostname using a DNS lookup if necessary.
 // The resolution may return multiple IPs and the function returns one of them.
 // It prefers to return IPv4s first, and only if there are none, IPv6s.
+#[cfg(feature = "unstable-cloud")]
 pub(crate) async fn resolve_hostname(hostname: &str) -> Result<SocketAddr, io::Error> {
-    let addrs = match lookup_host(hostname).await {
-        Ok(addrs) => itertools::Either::Left(addrs),
+    pick_address(hostname, lookup_addresses(hostname).await?)
+}
+
+// Resolve the given hostname using a DNS lookup if necessary, returning all resolved addresses.
+async fn lookup_addresses(hostname: &str) -> Result<Vec<SocketAddr>, io::Error> {
+    match lookup_host(hostname).await {
+        Ok(addrs) => Ok(addrs.collect()),
         // Use a default port in case of error, but propagate the original error on failure
         Err(e) => {
             let addrs = lookup_host((hostname, 9042)).await.or(Err(e))?;
-            itertools::Either::Right(addrs)
+            Ok(addrs.collect())
         }
-    };
+    }
+}
 
+fn pick_address(hostname: &str, addrs: Vec<SocketAddr>) -> Result<SocketAddr, io::Error> {
     addrs
+        .into_iter()
         .find_or_last(|addr| matches!(addr, SocketAddr::V4(_)))
         .ok_or_else(|| {
             io::Error::other(format!("Empty address list returned by DNS for {hostname}"))
         })
 }
 
+/// Delay after which the next connection attempt is started if the previous one
+/// has not completed yet, as recommended by RFC 8305 ("Connection Attempt Delay").
+const CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
+// Resolve the given hostname of a contact point using a DNS lookup if necessary.
+// If it resolves to both IPv4 and IPv6 addresses, the one to which a connection can be
+// established first is returned, as determined by the Happy Eyeballs algorithm (RFC 8305).
+// Otherwise, or if no connection can be established, behaves like `resolve_hostname`.
+async fn resolve_contact_point_hostname(
+    hostname: &str,
+    connection_config: &ConnectionConfig,
+) -> Result<SocketAddr, io::Error> {
+    let mut addrs = lookup_addresses(hostname).await?;
+    // Addresses of a different family than the bound local address are unreachable.
+    if let Some(local_ip_address) = connection_config.local_ip_address {
+        let matching_family = |addr: &SocketAddr| addr.is_ipv4() == local_ip_address.is_ipv4();
+        if addrs.iter().any(matching_family) {
+            addrs.retain(matching_family);
+        }
+    }
+
+    if addrs.iter().any(SocketAddr::is_ipv4) && addrs.iter().any(SocketAddr::is_ipv6) {
+        match race_connections(&interleave_address_families(&addrs), connection_config).await {
+            Some(addr) => {
+                debug!(
+                    "Chose address {} of {} by racing connections",
+                    addr, hostname
+                );
+                return Ok(addr);
+            }
+            None => warn!(
+                "Failed to connect to any of the addresses of {}: {}",
+                hostname,
+                addrs.iter().safe_format(", ")
+            ),
+        }
+    }
+
+    pick_address(hostname, addrs)
+}
+
+// Orders the addresses as recommended by RFC 8305: alternately from each
+// address family, starting with IPv6, preserving the order within each family.
+fn interleave_address_families(addrs: &[SocketAddr]) -> Vec<SocketAddr> {
+    let (ipv6, ipv4): (Vec<SocketAddr>, Vec<SocketAddr>) =
+        addrs.iter().partition(|addr| addr.is_ipv6());
+    ipv6.into_iter().interleave(ipv4).collect()
+}
+
+// Attempts to establish a TCP connection to the given addresses, in order.
+// The next attempt is started once the previous one fails, or if it does not complete
+// within `CONNECTION_ATTEMPT_DELAY`, without abandoning the attempts in progress.
+// Returns the address to which a connection was established first, if any.
+//
+// The sockets are opened like the control connection's one, i.e. bound to the configured
+// local address and an ephemeral port, so that the chosen address is reachable by it.
+async fn race_connections(
+    addrs: &[SocketAddr],
+    connection_config: &ConnectionConfig,
+) -> Option<SocketAddr> {
+    let attempt = |addr: SocketAddr| async move {
+        let connect = connect_with_source_ip_and_port(
+            addr,
+            connection_config.local_ip_address,
+            None,
+            connection_config.tcp_send_buffer_size,
+            connection_config.tcp_recv_buffer_size,
+        );
+        let result = tokio::time::timeout(connection_config.connect_timeout, connect).await;
+        (addr, result)
+    };
+
+    let mut remaining = addrs.iter().copied();
+    let mut attempts = FuturesUnordered::new();
+    loop {
+        if attempts.is_empty() {
+            attempts.push(attempt(remaining.next()?));
+        }
+        tokio::select! {
+            Some((addr, result)) = attempts.next() => {
+                match result {
+                    // The probing connection is not needed anymore, the driver
+                    // opens its own connections to the chosen address.
+                    Ok(Ok(_stream)) => return Some(addr),
+                    Ok(Err(err)) => debug!("Failed to connect to {}: {}", addr, err),
+                    Err(_) => debug!("Connecting to {} timed out", addr),
+                }
+                // Don't wait for the delay after a failure.
+                if let Some(next) = remaining.next() {
+                    attempts.push(attempt(next));
+                }
+            }
+            () = tokio::time::sleep(CONNECTION_ATTEMPT_DELAY), if remaining.len() > 0 => {
+                attempts.push(attempt(remaining.next().unwrap()));
+            }
+        }
+    }
+}
+
 /// Transforms the given [`InternalKnownNode`]s into [`ContactPoint`]s.
 ///
 /// In case of a hostname, resolves it using a DNS lookup.
 /// In case of a plain IP address, parses it and uses straight.
 pub(crate) async fn resolve_contact_points(
     known_nodes: &[InternalKnownNode],
+    connection_config: &ConnectionConfig,
 ) -> (Vec<ResolvedContactPoint>, Vec<String>) {
     // Find IP addresses of all known nodes passed in the config
     let mut initial_peers: Vec<ResolvedContactPoint> = Vec::with_capacity(known_nodes.len());
@@ -361,7 +597,7 @@ pub(crate) async fn resolve_contact_points(
     let resolve_futures = to_resolve
         .into_iter()
         .map(|(hostname, datacenter)| async move {
-            match resolve_hostname(hostname).await {
+            match resolve_contact_point_hostname(hostname, connection_config).await {
                 Ok(address) => Some(ResolvedContactPoint {
                     address,
                     datacenter,
@@ -397,7 +633,9 @@ mod tests {
                 )))),
                 datacenter,
                 rack,
+                release_version: None,
                 pool: None,
+                statistics: Default::default(),
                 enabled_as_connected: AtomicBool::new(false),
             }
         }
@@ -406,4 +644,91 @@ mod tests {
             self.enabled_as_connected.store(true, Ordering::SeqCst);
         }
     }
+
+    #[test]
+    fn node_statistics() {
+        let node = Node::new_for_test(None, None, None, None);
+        assert_eq!(node.in_flight_requests(), 0);
+        assert_eq!(node.average_latency(), None);
+
+        let first = node.start_request();
+        let second = node.start_request();
+        assert_eq!(node.in_flight_requests(), 2);
+        drop(first);
+        assert_eq!(node.in_flight_requests(), 1);
+        drop(second);
+        assert_eq!(node.in_flight_requests(), 0);
+
+        node.record_latency(Duration::from_micros(800));
+        assert_eq!(node.average_latency(), Some(Duration::from_micros(800)));
+        node.record_latency(Duration::from_micros(1600));
+        assert_eq!(node.average_latency(), Some(Duration::from_micros(900)));
+        node.record_latency(Duration::ZERO);
+        assert!(node.average_latency().is_some());
+    }
+
+    #[test]
+    fn address_families_are_interleaved() {
+        let addr = |addr: &str| addr.parse::<SocketAddr>().unwrap();
+        let addrs = [
+            addr("10.0.0.1:9042"),
+            addr("10.0.0.2:9042"),
+            addr("10.0.0.3:9042"),
+            addr("[fd00::1]:9042"),
+            addr("[fd00::2]:9042"),
+        ];
+        assert_eq!(
+            interleave_address_families(&addrs),
+            vec![
+                addr("[fd00::1]:9042"),
+                addr("10.0.0.1:9042"),
+                addr("[fd00::2]:9042"),
+                addr("10.0.0.2:9042"),
+                addr("10.0.0.3:9042"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn racing_connections_skips_unreachable_addresses() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let reachable = listener.local_addr().unwrap();
+        let unreachable = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let config = ConnectionConfig {
+            connect_timeout: Duration::from_secs(5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            race_connections(&[unreachable, reachable], &config).await,
+            Some(reachable)
+        );
+        assert_eq!(
+            race_connections(&[reachable, unreachable], &config).await,
+            Some(reachable)
+        );
+        assert_eq!(race_connections(&[unreachable], &config).await, None);
+        assert_eq!(race_connections(&[], &config).await, None);
+    }
+
+    #[tokio::test]
+    async fn racing_connections_binds_local_address() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let reachable = listener.local_addr().unwrap();
+        let config = |local_ip_address: &str| ConnectionConfig {
+            local_ip_address: Some(local_ip_address.parse().unwrap()),
+            connect_timeout: Duration::from_secs(5),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            race_connections(&[reachable], &config("127.0.0.1")).await,
+            Some(reachable)
+        );
+        // An IPv4 address can't be reached from an IPv6 local address.
+        assert_eq!(race_connections(&[reachable], &config("::1")).await, None);
+    }
 }
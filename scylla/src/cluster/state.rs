@@ -1,24 +1,27 @@
-use crate::errors::{ClusterStateTokenError, ConnectionPoolError};
+use crate::errors::{ClusterStateTokenError, ConnectionPoolError, PartialMetadataError};
 use crate::network::{Connection, PoolConfig, VerifiedKeyspaceName};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 use crate::policies::host_filter::HostFilter;
+use crate::policies::host_id_change::{HostIdChangeAction, HostIdChangePolicy};
 use crate::routing::locator::tablets::{RawTablet, Tablet, TabletsInfo};
 use crate::routing::locator::ReplicaLocator;
-use crate::routing::partitioner::{calculate_token_for_partition_key, PartitionerName};
-use crate::routing::{Shard, Token};
+use crate::routing::partitioner::{calculate_token_for_partition_key, PartitionerResolver};
+use crate::routing::{Shard, Token, TokenRange};
 use crate::utils::safe_format::IteratorSafeFormatExt;
 
 use itertools::Itertools;
 use scylla_cql::frame::response::result::TableSpec;
 use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
+use scylla_cql::value::UdtValueBuilder;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{debug, warn};
 use uuid::Uuid;
 
-use super::metadata::{Keyspace, Metadata, Strategy};
+use super::metadata::{Keyspace, Metadata, Peer, Strategy};
 use super::node::{Node, NodeRef};
+use super::NodeAddr;
 
 /// Represents the state of the cluster, including known nodes, keyspaces, and replica locator.
 ///
@@ -39,14 +42,24 @@ pub struct ClusterState {
     // Then, remove this field.
     pub(crate) all_nodes: Vec<Arc<Node>>,
 
+    /// Host IDs of nodes which have been replaced by another node at the same address
+    /// (see [HostIdChangeAction::Replace]), but are still listed in cluster metadata.
+    pub(crate) replaced_host_ids: HashSet<Uuid>,
+
     /// All keyspaces in the cluster, accessible by their name.
     /// Often refered to as "schema metadata".
     pub(crate) keyspaces: HashMap<String, Keyspace>,
 
+    /// Parts of schema metadata which could not be refreshed during the latest refresh.
+    pub(crate) partial_metadata_error: Option<Arc<PartialMetadataError>>,
+
     /// The entity which provides a way to find the set of owning nodes (+shards, in case of ScyllaDB)
     /// for a given (token, replication strategy, table) tuple.
     /// It relies on both topology and schema metadata.
     pub(crate) locator: ReplicaLocator,
+
+    /// Resolves partitioners of tables, falling back to the cluster-wide one.
+    pub(crate) partitioners: PartitionerResolver,
 }
 
 /// Enables printing [ClusterState] struct in a neat way, skipping the clutter involved by
@@ -88,6 +101,9 @@ impl ClusterState {
         known_peers: &HashMap<Uuid, Arc<Node>>,
         used_keyspace: &Option<VerifiedKeyspaceName>,
         host_filter: Option<&dyn HostFilter>,
+        drained_host_ids: &HashSet<Uuid>,
+        host_id_change_policy: &HostIdChangePolicy,
+        replaced_host_ids: &HashSet<Uuid>,
         mut tablets: TabletsInfo,
         old_keyspaces: &HashMap<String, Keyspace>,
         #[cfg(feature = "metrics")] metrics: &Arc<Metrics>,
@@ -97,31 +113,69 @@ impl ClusterState {
             HashMap::with_capacity(metadata.peers.len());
         let mut ring: Vec<(Token, Arc<Node>)> = Vec::new();
 
+        // Used to detect nodes which appear at an already known address with a different host id.
+        let previous_host_ids: HashMap<NodeAddr, Uuid> = known_peers
+            .iter()
+            .map(|(host_id, node)| (node.address, *host_id))
+            .collect();
+        let mut new_replaced_host_ids: HashSet<Uuid> = HashSet::new();
+
         for peer in metadata.peers {
-            // Take existing Arc<Node> if possible, otherwise create new one
-            // Changing rack/datacenter but not ip address seems improbable
-            // so we can just create new node and connections then
+            // Nodes which have been replaced stay out of the cluster state
+            // for as long as the metadata keeps listing them.
+            if replaced_host_ids.contains(&peer.host_id) {
+                new_replaced_host_ids.insert(peer.host_id);
+                continue;
+            }
+
             let peer_host_id = peer.host_id;
             let peer_address = peer.address;
+            let peer_release_version = peer.release_version.clone();
             let peer_tokens;
 
+            if !known_peers.contains_key(&peer_host_id) {
+                if let Some(&previous_host_id) = previous_host_ids.get(&peer_address) {
+                    warn!(
+                        "Node at address {} changed its host id from {} to {}",
+                        peer_address, previous_host_id, peer_host_id
+                    );
+                    host_id_change_policy.notify(peer_address, previous_host_id, peer_host_id);
+                    if host_id_change_policy.action() == HostIdChangeAction::Replace {
+                        new_replaced_host_ids.insert(previous_host_id);
+                    }
+                }
+            }
+
+            // Drained nodes are kept disabled, so that no connections are opened to them.
+            let is_drained = drained_host_ids.contains(&peer_host_id);
             let node: Arc<Node> = match known_peers.get(&peer_host_id) {
-                Some(node) if node.datacenter == peer.datacenter && node.rack == peer.rack => {
+                Some(node)
+                    if node.datacenter == peer.datacenter
+                        && node.rack == peer.rack
+                        && !drain_state_changed(node, is_drained, &peer, host_filter) =>
+                {
                     let (peer_endpoint, tokens) = peer.into_peer_endpoint_and_tokens();
                     peer_tokens = tokens;
-                    if node.address == peer_address {
+                    if node.address == peer_address && node.release_version == peer_release_version
+                    {
                         Arc::clone(node)
                     } else {
-                        // If IP changes, the Node struct is recreated, but the underlying pool is preserved and notified about the IP change.
-                        Arc::new(Node::inherit_with_ip_changed(node, peer_endpoint))
+                        // If IP or version changes, the Node struct is recreated, but the underlying pool is preserved
+                        // (and notified about the IP change, if applicable).
+                        Arc::new(Node::inherit_with_ip_or_version_changed(
+                            node,
+                            peer_endpoint,
+                            peer_release_version,
+                        ))
                     }
                 }
                 _ => {
-                    let is_enabled = host_filter.is_none_or(|f| f.accept(&peer));
+                    let is_enabled = !is_drained && host_filter.is_none_or(|f| f.accept(&peer));
                     let (peer_endpoint, tokens) = peer.into_peer_endpoint_and_tokens();
                     peer_tokens = tokens;
                     Arc::new(Node::new(
                         peer_endpoint,
+                        peer_release_version,
                         pool_config,
                         used_keyspace.clone(),
                         is_enabled,
@@ -138,31 +192,61 @@ impl ClusterState {
             }
         }
 
-        let keyspaces: HashMap<String, Keyspace> = metadata
-            .keyspaces
-            .into_iter()
-            .filter_map(|(ks_name, ks)| match ks {
-                Ok(ks) => Some((ks_name, ks)),
-                Err(e) => {
-                    if let Some(old_ks) = old_keyspaces.get(&ks_name) {
-                        warn!(
-                            "Encountered an error while processing\
-                            metadata of keyspace \"{ks_name}\": {e}.\
-                            Re-using older version of this keyspace metadata"
-                        );
-                        Some((ks_name, old_ks.clone()))
-                    } else {
-                        warn!(
-                            "Encountered an error while processing metadata\
-                            of keyspace \"{ks_name}\": {e}.\
-                            No previous version of this keyspace metadata found, so it will not be\
-                            present in ClusterData until next refresh."
-                        );
-                        None
-                    }
-                }
-            })
-            .collect();
+        if !new_replaced_host_ids.is_empty() {
+            for host_id in &new_replaced_host_ids {
+                new_known_peers.remove(host_id);
+            }
+            ring.retain(|(_, node)| !new_replaced_host_ids.contains(&node.host_id));
+        }
+
+        let partitioners = metadata.partitioners;
+        let mut keyspace_errors = HashMap::new();
+        let (keyspaces, keyspaces_error): (HashMap<String, Keyspace>, _) = match metadata.keyspaces
+        {
+            Ok(keyspaces) => (
+                keyspaces
+                    .into_iter()
+                    .filter_map(|(ks_name, ks)| match ks {
+                        Ok(ks) => Some((ks_name, ks)),
+                        Err(e) => {
+                            let old_ks = if let Some(old_ks) = old_keyspaces.get(&ks_name) {
+                                warn!(
+                                    "Encountered an error while processing\
+                                    metadata of keyspace \"{ks_name}\": {e}.\
+                                    Re-using older version of this keyspace metadata"
+                                );
+                                Some((ks_name.clone(), old_ks.clone()))
+                            } else {
+                                warn!(
+                                    "Encountered an error while processing metadata\
+                                    of keyspace \"{ks_name}\": {e}.\
+                                    No previous version of this keyspace metadata found, so it will not be\
+                                    present in ClusterData until next refresh."
+                                );
+                                None
+                            };
+                            keyspace_errors.insert(ks_name, e);
+                            old_ks
+                        }
+                    })
+                    .collect(),
+                None,
+            ),
+            Err(e) => {
+                warn!(
+                    "Encountered an error while fetching keyspaces metadata: {e}. \
+                    Re-using older version of metadata of all keyspaces"
+                );
+                (old_keyspaces.clone(), Some(e))
+            }
+        };
+        let partial_metadata_error = (keyspaces_error.is_some() || !keyspace_errors.is_empty())
+            .then(|| {
+                Arc::new(PartialMetadataError {
+                    keyspaces_error,
+                    keyspace_errors,
+                })
+            });
 
         {
             let removed_nodes = {
@@ -216,8 +300,11 @@ impl ClusterState {
         ClusterState {
             all_nodes: new_known_peers.values().cloned().collect(),
             known_peers: new_known_peers,
+            replaced_host_ids: new_replaced_host_ids,
             keyspaces,
+            partial_metadata_error,
             locator,
+            partitioners,
         }
     }
 
@@ -226,11 +313,36 @@ impl ClusterState {
         self.keyspaces.get(keyspace.as_ref())
     }
 
+    /// Returns a builder of values of the given user defined type, validating
+    /// them against its definition, or `None` if the type is not known to the driver.
+    ///
+    /// Type definitions are available only if schema metadata is fetched
+    /// (see [SessionBuilder::fetch_schema_metadata](crate::client::session_builder::GenericSessionBuilder::fetch_schema_metadata)).
+    pub fn udt_value_builder(
+        &self,
+        keyspace: impl AsRef<str>,
+        type_name: impl AsRef<str>,
+    ) -> Option<UdtValueBuilder> {
+        let definition = self
+            .get_keyspace(keyspace)?
+            .user_defined_types
+            .get(type_name.as_ref())?;
+        Some(UdtValueBuilder::new(Arc::clone(definition)))
+    }
+
     /// Returns an iterator over keyspaces.
     pub fn keyspaces_iter(&self) -> impl Iterator<Item = (&str, &Keyspace)> {
         self.keyspaces.iter().map(|(k, v)| (k.as_str(), v))
     }
 
+    /// Describes the parts of schema metadata which could not be refreshed when
+    /// this cluster state was created, or returns `None` if all of it was refreshed.
+    ///
+    /// Metadata of these parts is kept from the previous cluster state, if it was available there.
+    pub fn partial_metadata_error(&self) -> Option<&PartialMetadataError> {
+        self.partial_metadata_error.as_deref()
+    }
+
     /// Access details about nodes known to the driver
     pub fn get_nodes_info(&self) -> &[Arc<Node>] {
         &self.all_nodes
@@ -263,11 +375,9 @@ impl ClusterState {
             &RowSerializationContext::from_specs(table.pk_column_specs.as_slice()),
             partition_key,
         )?;
-        let partitioner = table
-            .partitioner
-            .as_deref()
-            .and_then(PartitionerName::from_str)
-            .unwrap_or_default();
+        let partitioner = self
+            .partitioners
+            .table_partitioner(table.partitioner.as_deref());
         calculate_token_for_partition_key(&values, &partitioner)
             .map_err(ClusterStateTokenError::TokenCalculation)
     }
@@ -285,6 +395,82 @@ impl ClusterState {
             .collect()
     }
 
+    /// Splits the token ring into ranges of tokens which have the same replicas
+    /// in the given table, in the order of their tokens. Each range is paired with
+    /// its replicas, as returned by [ClusterState::get_token_endpoints].
+    ///
+    /// This is aware of tablets; see [ReplicaLocator::token_ranges] for details.
+    #[expect(clippy::type_complexity)]
+    pub fn get_token_ranges(
+        &self,
+        keyspace: &str,
+        table: &str,
+    ) -> Vec<(TokenRange, Vec<(Arc<Node>, Shard)>)> {
+        let table_spec = TableSpec::borrowed(keyspace, table);
+        let strategy = self
+            .keyspaces
+            .get(keyspace)
+            .map(|k| &k.strategy)
+            .unwrap_or(&Strategy::LocalStrategy);
+        self.replica_locator()
+            .token_ranges(strategy, None, &table_spec)
+            .into_iter()
+            .map(|(range, replica_set)| {
+                let replicas = replica_set
+                    .into_iter()
+                    .map(|(node, shard)| (node.clone(), shard))
+                    .collect();
+                (range, replicas)
+            })
+            .collect()
+    }
+
+    /// Returns the tablets of the given table known to the driver, in the order of their tokens.
+    /// Each tablet is paired with its replicas.
+    ///
+    /// Returns `None` if no tablet of the table is known, which is always the case for
+    /// tables not using tablets. The driver learns about tablets lazily, from responses
+    /// to requests routed to nodes which don't replicate the requested token,
+    /// so the tablets might not cover the whole ring.
+    #[expect(clippy::type_complexity)]
+    pub fn get_tablets(
+        &self,
+        keyspace: &str,
+        table: &str,
+    ) -> Option<Vec<(TokenRange, Vec<(Arc<Node>, Shard)>)>> {
+        let table_spec = TableSpec::borrowed(keyspace, table);
+        let tablets = self
+            .replica_locator()
+            .tablets
+            .tablets_for_table(&table_spec)?;
+        Some(
+            tablets
+                .tablets()
+                .map(|tablet| (tablet.token_range(), tablet.replicas().to_vec()))
+                .collect(),
+        )
+    }
+
+    /// Returns true if any tablet of the given table is known to the driver,
+    /// which means that requests against the table are routed using tablets.
+    pub fn is_tablet_table(&self, keyspace: &str, table: &str) -> bool {
+        let table_spec = TableSpec::borrowed(keyspace, table);
+        self.replica_locator()
+            .tablets
+            .tablets_for_table(&table_spec)
+            .is_some()
+    }
+
+    /// Returns the tables of which the driver knows any tablet, as `(keyspace, table)` pairs.
+    /// See [ClusterState::get_tablets] for the tablets of each such table.
+    pub fn get_tablet_tables(&self) -> Vec<(&str, &str)> {
+        self.replica_locator()
+            .tablets
+            .tables()
+            .map(|spec| (spec.ks_name(), spec.table_name()))
+            .collect()
+    }
+
     pub(crate) fn get_token_endpoints_iter(
         &self,
         table_spec: &TableSpec,
@@ -308,6 +494,12 @@ impl ClusterState {
     /// or named values (e.g. struct that derives `SerializeRow`), as you would
     /// when executing a request. No additional values are allowed besides values
     /// for primary key columns.
+    ///
+    /// Each replica is paired with the shard that owns the partition on it,
+    /// so the result can be used for scheduling work close to the data.
+    /// It is equivalent to computing the token with [ClusterState::compute_token]
+    /// and passing it to [ClusterState::get_token_endpoints].
+    #[doc(alias = "get_replicas_for_key")]
     pub fn get_endpoints(
         &self,
         keyspace: &str,
@@ -435,3 +627,175 @@ impl ClusterState {
         }
     }
 }
+
+/// Returns true if a known node has to be recreated, because it has been drained
+/// or undrained since it was created.
+fn drain_state_changed(
+    node: &Node,
+    is_drained: bool,
+    peer: &Peer,
+    host_filter: Option<&dyn HostFilter>,
+) -> bool {
+    if node.is_enabled() {
+        is_drained
+    } else {
+        // A disabled node which is not drained may have been disabled by the host filter.
+        !is_drained && host_filter.is_none_or(|f| f.accept(peer))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+
+    use uuid::Uuid;
+
+    use super::ClusterState;
+    use crate::cluster::metadata::Metadata;
+    use crate::errors::{
+        KeyspaceStrategyError, MetadataError, PeersMetadataError, SingleKeyspaceMetadataError,
+    };
+    use crate::routing::locator::tablets::{Tablet, TabletsInfo};
+    use crate::routing::locator::test::{
+        mock_metadata_for_token_aware_tests, KEYSPACE_NTS_RF_2, KEYSPACE_NTS_RF_3, TABLE_NTS_RF_2,
+        TABLE_SS_RF_2,
+    };
+    use crate::routing::TokenRange;
+
+    async fn state(metadata: Metadata, previous: Option<&ClusterState>) -> ClusterState {
+        state_with_drained(metadata, previous, &HashSet::new()).await
+    }
+
+    async fn state_with_drained(
+        metadata: Metadata,
+        previous: Option<&ClusterState>,
+        drained_host_ids: &HashSet<Uuid>,
+    ) -> ClusterState {
+        ClusterState::new(
+            metadata,
+            &Default::default(),
+            &previous.map_or_else(HashMap::new, |s| s.known_peers.clone()),
+            &None,
+            None,
+            drained_host_ids,
+            &Default::default(),
+            &HashSet::new(),
+            TabletsInfo::new(),
+            &previous.map_or_else(HashMap::new, |s| s.keyspaces.clone()),
+            #[cfg(feature = "metrics")]
+            &Default::default(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn failed_keyspaces_metadata_is_kept_and_reported() {
+        let initial = state(mock_metadata_for_token_aware_tests(), None).await;
+        assert!(initial.partial_metadata_error().is_none());
+        let keyspace_count = initial.keyspaces.len();
+
+        // A single keyspace fails.
+        let mut metadata = mock_metadata_for_token_aware_tests();
+        metadata.keyspaces.as_mut().unwrap().insert(
+            KEYSPACE_NTS_RF_2.to_owned(),
+            Err(SingleKeyspaceMetadataError::Strategy(
+                KeyspaceStrategyError::MissingClassForStrategyDefinition,
+            )),
+        );
+        let refreshed = state(metadata, Some(&initial)).await;
+        assert_eq!(refreshed.keyspaces.len(), keyspace_count);
+        assert!(refreshed.get_keyspace(KEYSPACE_NTS_RF_2).is_some());
+        let error = refreshed.partial_metadata_error().unwrap();
+        assert!(error.keyspaces_error.is_none());
+        assert_eq!(
+            error.keyspace_errors.keys().collect::<Vec<_>>(),
+            vec![KEYSPACE_NTS_RF_2]
+        );
+
+        // Fetching all keyspaces fails, but the topology is still refreshed.
+        let mut metadata = mock_metadata_for_token_aware_tests();
+        metadata.keyspaces = Err(MetadataError::Peers(PeersMetadataError::EmptyPeers));
+        metadata.peers.pop();
+        let refreshed = state(metadata, Some(&refreshed)).await;
+        assert_eq!(refreshed.keyspaces.len(), keyspace_count);
+        assert!(refreshed.get_keyspace(KEYSPACE_NTS_RF_3).is_some());
+        assert_eq!(refreshed.known_peers.len(), initial.known_peers.len() - 1);
+        let error = refreshed.partial_metadata_error().unwrap();
+        assert!(error.keyspaces_error.is_some());
+        assert!(error.keyspace_errors.is_empty());
+
+        // Everything is refreshed again.
+        let refreshed = state(mock_metadata_for_token_aware_tests(), Some(&refreshed)).await;
+        assert!(refreshed.partial_metadata_error().is_none());
+    }
+
+    #[tokio::test]
+    async fn drained_nodes_are_disabled_until_undrained() {
+        // Host ids of mock peers are random, so they are fixed for every refresh.
+        let host_ids: Vec<Uuid> = mock_metadata_for_token_aware_tests()
+            .peers
+            .iter()
+            .map(|peer| peer.host_id)
+            .collect();
+        let metadata = || {
+            let mut metadata = mock_metadata_for_token_aware_tests();
+            for (peer, host_id) in metadata.peers.iter_mut().zip(&host_ids) {
+                peer.host_id = *host_id;
+            }
+            metadata
+        };
+        let initial = state(metadata(), None).await;
+        let drained_id = host_ids[0];
+        assert!(initial.known_peers.values().all(|node| node.is_enabled()));
+
+        let drained =
+            state_with_drained(metadata(), Some(&initial), &HashSet::from([drained_id])).await;
+        for (host_id, node) in &drained.known_peers {
+            assert_eq!(node.is_enabled(), *host_id != drained_id);
+            // Other nodes keep their pools.
+            if *host_id != drained_id {
+                assert!(Arc::ptr_eq(node, &initial.known_peers[host_id]));
+            }
+        }
+
+        let undrained = state(metadata(), Some(&drained)).await;
+        assert!(undrained.known_peers.values().all(|node| node.is_enabled()));
+    }
+
+    #[tokio::test]
+    async fn known_tablets_are_exposed() {
+        let mut state = state(mock_metadata_for_token_aware_tests(), None).await;
+        assert!(state.get_tablet_tables().is_empty());
+        assert!(state
+            .get_tablets(TABLE_SS_RF_2.ks_name(), TABLE_SS_RF_2.table_name())
+            .is_none());
+
+        let node = state.all_nodes[0].clone();
+        for token in [300, 100] {
+            state.locator.tablets.add_tablet(
+                TABLE_SS_RF_2.clone(),
+                Tablet::new_for_test(token, vec![node.clone()], None),
+            );
+        }
+
+        assert_eq!(
+            state.get_tablet_tables(),
+            vec![(TABLE_SS_RF_2.ks_name(), TABLE_SS_RF_2.table_name())]
+        );
+        assert!(state.is_tablet_table(TABLE_SS_RF_2.ks_name(), TABLE_SS_RF_2.table_name()));
+        assert!(!state.is_tablet_table(TABLE_NTS_RF_2.ks_name(), TABLE_NTS_RF_2.table_name()));
+        let tablets = state
+            .get_tablets(TABLE_SS_RF_2.ks_name(), TABLE_SS_RF_2.table_name())
+            .unwrap();
+        let ranges: Vec<TokenRange> = tablets.iter().map(|(range, _)| *range).collect();
+        assert_eq!(
+            ranges,
+            vec![TokenRange::new(99, 100), TokenRange::new(299, 300)]
+        );
+        for (_, replicas) in &tablets {
+            assert_eq!(replicas.len(), 1);
+            assert!(Arc::ptr_eq(&replicas[0].0, &node));
+        }
+    }
+}
@@ -1,25 +1,33 @@
 use crate::client::session::TABLET_CHANNEL_SIZE;
 use crate::errors::{MetadataError, NewSessionError, RequestAttemptError, UseKeyspaceError};
-use crate::frame::response::event::Event;
+use crate::frame::response::event::{Event, SchemaChangeEvent};
 use crate::network::{PoolConfig, VerifiedKeyspaceName};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 use crate::policies::host_filter::HostFilter;
+use crate::policies::host_id_change::HostIdChangePolicy;
 use crate::routing::locator::tablets::{RawTablet, TabletsInfo};
+use crate::routing::partitioner::{CustomPartitioner, PartitionerResolver};
 
 use arc_swap::ArcSwap;
 use futures::future::join_all;
 use futures::{future::RemoteHandle, FutureExt};
 use scylla_cql::frame::response::result::TableSpec;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 use tracing::debug;
+use uuid::Uuid;
 
+use super::events::ClusterEvent;
 use super::metadata::MetadataReader;
 use super::node::InternalKnownNode;
 use super::state::{ClusterState, ClusterStateNeatDebug};
 
+// Capacity of the channel of cluster events. Subscribers which lag behind
+// by more events than that miss the oldest ones.
+const CLUSTER_EVENT_CHANNEL_SIZE: usize = 256;
+
 /// Cluster manages up to date information and connections to database nodes.
 /// All state can be accessed by cloning Arc<ClusterState> in the `state` field
 pub(crate) struct Cluster {
@@ -29,6 +37,13 @@ pub(crate) struct Cluster {
 
     refresh_channel: tokio::sync::mpsc::Sender<RefreshRequest>,
     use_keyspace_channel: tokio::sync::mpsc::Sender<UseKeyspaceRequest>,
+    drain_channel: tokio::sync::mpsc::Sender<DrainRequest>,
+
+    // Used to subscribe to schema change events received by the worker
+    schema_change_sender: tokio::sync::broadcast::Sender<Arc<SchemaChangeEvent>>,
+
+    // Used to subscribe to topology and status change events received by the worker
+    cluster_event_sender: tokio::sync::broadcast::Sender<ClusterEvent>,
 
     _worker_handle: RemoteHandle<()>,
 }
@@ -60,9 +75,18 @@ struct ClusterWorker {
     // Channel used to receive use keyspace requests
     use_keyspace_channel: tokio::sync::mpsc::Receiver<UseKeyspaceRequest>,
 
+    // Channel used to receive requests to drain or undrain nodes
+    drain_channel: tokio::sync::mpsc::Receiver<DrainRequest>,
+
     // Channel used to receive server events
     server_events_channel: tokio::sync::mpsc::Receiver<Event>,
 
+    // Channel used to forward schema change events to their subscribers
+    schema_change_sender: tokio::sync::broadcast::Sender<Arc<SchemaChangeEvent>>,
+
+    // Channel used to forward topology and status change events to their subscribers
+    cluster_event_sender: tokio::sync::broadcast::Sender<ClusterEvent>,
+
     // Channel used to receive signals that control connection is broken
     control_connection_repair_channel: tokio::sync::broadcast::Receiver<()>,
 
@@ -77,6 +101,13 @@ struct ClusterWorker {
     // connections
     host_filter: Option<Arc<dyn HostFilter>>,
 
+    // Nodes which were drained by the user - no connections are opened to them
+    // until they are undrained
+    drained_host_ids: HashSet<Uuid>,
+
+    // Decides what to do when a node at a known address reports a different host id
+    host_id_change_policy: HostIdChangePolicy,
+
     // This value determines how frequently the cluster
     // worker will refresh the cluster metadata
     cluster_metadata_refresh_interval: Duration,
@@ -85,9 +116,30 @@ struct ClusterWorker {
     metrics: Arc<Metrics>,
 }
 
-#[derive(Debug)]
 struct RefreshRequest {
-    response_chan: tokio::sync::oneshot::Sender<Result<(), MetadataError>>,
+    response_chan: tokio::sync::oneshot::Sender<Result<Arc<ClusterState>, MetadataError>>,
+}
+
+// ClusterState is not Debug, so the response channels are not printed.
+impl std::fmt::Debug for RefreshRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RefreshRequest").finish_non_exhaustive()
+    }
+}
+
+struct DrainRequest {
+    host_id: Uuid,
+    drained: bool,
+    response_chan: tokio::sync::oneshot::Sender<Result<Arc<ClusterState>, MetadataError>>,
+}
+
+impl std::fmt::Debug for DrainRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DrainRequest")
+            .field("host_id", &self.host_id)
+            .field("drained", &self.drained)
+            .finish_non_exhaustive()
+    }
 }
 
 #[derive(Debug)]
@@ -105,15 +157,20 @@ impl Cluster {
         fetch_schema_metadata: bool,
         metadata_request_serverside_timeout: Option<Duration>,
         host_filter: Option<Arc<dyn HostFilter>>,
+        host_id_change_policy: HostIdChangePolicy,
+        custom_partitioners: Vec<Arc<dyn CustomPartitioner>>,
         cluster_metadata_refresh_interval: Duration,
         tablet_receiver: tokio::sync::mpsc::Receiver<(TableSpec<'static>, RawTablet)>,
         #[cfg(feature = "metrics")] metrics: Arc<Metrics>,
     ) -> Result<Cluster, NewSessionError> {
         let (refresh_sender, refresh_receiver) = tokio::sync::mpsc::channel(32);
         let (use_keyspace_sender, use_keyspace_receiver) = tokio::sync::mpsc::channel(32);
+        let (drain_sender, drain_receiver) = tokio::sync::mpsc::channel(32);
         let (server_events_sender, server_events_receiver) = tokio::sync::mpsc::channel(32);
         let (control_connection_repair_sender, control_connection_repair_receiver) =
             tokio::sync::broadcast::channel(32);
+        let (schema_change_sender, _) = tokio::sync::broadcast::channel(32);
+        let (cluster_event_sender, _) = tokio::sync::broadcast::channel(CLUSTER_EVENT_CHANNEL_SIZE);
 
         let mut metadata_reader = MetadataReader::new(
             known_nodes,
@@ -124,6 +181,8 @@ impl Cluster {
             keyspaces_to_fetch,
             fetch_schema_metadata,
             &host_filter,
+            pool_config.reconnection_policy.clone(),
+            PartitionerResolver::new(custom_partitioners),
             #[cfg(feature = "metrics")]
             Arc::clone(&metrics),
         )
@@ -136,6 +195,9 @@ impl Cluster {
             &HashMap::new(),
             &None,
             host_filter.as_deref(),
+            &HashSet::new(),
+            &host_id_change_policy,
+            &HashSet::new(),
             TabletsInfo::new(),
             &HashMap::new(),
             #[cfg(feature = "metrics")]
@@ -154,13 +216,19 @@ impl Cluster {
 
             refresh_channel: refresh_receiver,
             server_events_channel: server_events_receiver,
+            schema_change_sender: schema_change_sender.clone(),
+            cluster_event_sender: cluster_event_sender.clone(),
             control_connection_repair_channel: control_connection_repair_receiver,
             tablets_channel: tablet_receiver,
 
             use_keyspace_channel: use_keyspace_receiver,
             used_keyspace: None,
 
+            drain_channel: drain_receiver,
+
             host_filter,
+            drained_host_ids: HashSet::new(),
+            host_id_change_policy,
             cluster_metadata_refresh_interval,
 
             #[cfg(feature = "metrics")]
@@ -174,6 +242,9 @@ impl Cluster {
             state: cluster_state,
             refresh_channel: refresh_sender,
             use_keyspace_channel: use_keyspace_sender,
+            drain_channel: drain_sender,
+            schema_change_sender,
+            cluster_event_sender,
             _worker_handle: worker_handle,
         };
 
@@ -184,7 +255,8 @@ impl Cluster {
         self.state.load_full()
     }
 
-    pub(crate) async fn refresh_metadata(&self) -> Result<(), MetadataError> {
+    /// Refreshes the cluster state and returns it once it is installed.
+    pub(crate) async fn refresh_metadata(&self) -> Result<Arc<ClusterState>, MetadataError> {
         let (response_sender, response_receiver) = tokio::sync::oneshot::channel();
 
         self.refresh_channel
@@ -218,6 +290,48 @@ impl Cluster {
 
         response_receiver.await.unwrap() // ClusterWorker always responds
     }
+
+    /// Marks the node as drained (or undrained) and refreshes the cluster state,
+    /// so that the pool of a drained node is no longer part of it.
+    pub(crate) async fn set_node_drained(
+        &self,
+        host_id: Uuid,
+        drained: bool,
+    ) -> Result<(), MetadataError> {
+        let (response_sender, response_receiver) = tokio::sync::oneshot::channel();
+
+        self.drain_channel
+            .send(DrainRequest {
+                host_id,
+                drained,
+                response_chan: response_sender,
+            })
+            .await
+            .expect("Bug in Cluster::set_node_drained sending");
+        // Other end of this channel is in ClusterWorker, can't be dropped while we have &self to Cluster with _worker_handle
+
+        response_receiver
+            .await
+            .expect("Bug in Cluster::set_node_drained receiving")
+            .map(|_| ())
+        // ClusterWorker always responds
+    }
+
+    /// Returns a receiver of schema change events pushed by the cluster
+    /// after the moment of subscription.
+    pub(crate) fn subscribe_to_schema_changes(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<Arc<SchemaChangeEvent>> {
+        self.schema_change_sender.subscribe()
+    }
+
+    /// Returns a receiver of topology and status change events pushed by the cluster
+    /// after the moment of subscription.
+    pub(crate) fn subscribe_to_cluster_events(
+        &self,
+    ) -> tokio::sync::broadcast::Receiver<ClusterEvent> {
+        self.cluster_event_sender.subscribe()
+    }
 }
 
 impl ClusterWorker {
@@ -288,8 +402,13 @@ impl ClusterWorker {
                     if let Some(event) = recv_res {
                         debug!("Received server event: {:?}", event);
                         match event {
-                            Event::TopologyChange(_) => (), // Refresh immediately
-                            Event::StatusChange(_status) => {
+                            Event::TopologyChange(topology) => {
+                                // Sending fails only if there are no subscribers, which is fine.
+                                let _ = self.cluster_event_sender.send(ClusterEvent::from(&topology));
+                                // Refresh immediately
+                            }
+                            Event::StatusChange(status) => {
+                                let _ = self.cluster_event_sender.send(ClusterEvent::from(&status));
                                 // TODO: Tracking status using events is unreliable because of
                                 // the possibility of losing events when control connection is broken.
                                 // Maybe a better thing to do here is to treat those events as hints?
@@ -301,7 +420,11 @@ impl ClusterWorker {
                                 //   then try to open new connections.
                                 continue;
                             },
-                            _ => continue, // Don't go to refreshing
+                            Event::SchemaChange(schema_change) => {
+                                // Sending fails only if there are no subscribers, which is fine.
+                                let _ = self.schema_change_sender.send(Arc::new(schema_change));
+                                continue; // Don't go to refreshing
+                            }
                         }
                     } else {
                         // If server_events_channel was closed, than MetadataReader was dropped,
@@ -323,6 +446,23 @@ impl ClusterWorker {
 
                     continue; // Don't go to refreshing, wait for the next event
                 }
+                recv_res = self.drain_channel.recv() => {
+                    match recv_res {
+                        Some(request) => {
+                            if request.drained {
+                                self.drained_host_ids.insert(request.host_id);
+                            } else {
+                                self.drained_host_ids.remove(&request.host_id);
+                            }
+                            // The new cluster state is built by the refresh below,
+                            // whose result is sent back to the requester.
+                            cur_request = Some(RefreshRequest {
+                                response_chan: request.response_chan,
+                            });
+                        }
+                        None => return, // If drain_channel was closed then cluster was dropped, we can stop working
+                    }
+                }
                 recv_res = self.control_connection_repair_channel.recv() => {
                     match recv_res {
                         Ok(()) => {
@@ -384,7 +524,7 @@ impl ClusterWorker {
         use_keyspace_result(use_keyspace_results.into_iter())
     }
 
-    async fn perform_refresh(&mut self) -> Result<(), MetadataError> {
+    async fn perform_refresh(&mut self) -> Result<Arc<ClusterState>, MetadataError> {
         // Read latest Metadata
         let metadata = self.metadata_reader.read_metadata(false).await?;
         let cluster_state: Arc<ClusterState> = self.cluster_state.load_full();
@@ -396,6 +536,9 @@ impl ClusterWorker {
                 &cluster_state.known_peers,
                 &self.used_keyspace,
                 self.host_filter.as_deref(),
+                &self.drained_host_ids,
+                &self.host_id_change_policy,
+                &cluster_state.replaced_host_ids,
                 cluster_state.locator.tablets.clone(),
                 &cluster_state.keyspaces,
                 #[cfg(feature = "metrics")]
@@ -408,9 +551,9 @@ impl ClusterWorker {
             .wait_until_all_pools_are_initialized()
             .await;
 
-        self.update_cluster_state(new_cluster_state);
+        self.update_cluster_state(Arc::clone(&new_cluster_state));
 
-        Ok(())
+        Ok(new_cluster_state)
     }
 
     fn update_cluster_state(&mut self, new_cluster_state: Arc<ClusterState>) {
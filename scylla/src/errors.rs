@@ -1,6 +1,8 @@
 //! This module contains various errors which can be returned by [`Session`](crate::client::session::Session).
 
+use std::collections::HashMap;
 use std::error::Error;
+use std::fmt::Display;
 use std::io::ErrorKind;
 use std::net::{AddrParseError, IpAddr, SocketAddr};
 use std::num::ParseIntError;
@@ -9,11 +11,20 @@ use thiserror::Error;
 use uuid::Uuid;
 
 use crate::frame::response;
+use crate::frame::ProtocolVersion;
 
 // Re-export error types from pager module.
-pub use crate::client::pager::{NextPageError, NextRowError};
+pub use crate::client::pager::{NextPageError, NextRowError, PagerCursorParseError};
 
+// Re-export error types from settings module.
+pub use crate::client::settings::SessionSettingsError;
+
+use crate::client::schema_agreement::NodeSchemaVersion;
+use crate::response::{Coordinator, RequestAttempt};
+use crate::routing::Shard;
+use crate::statement::batch::BatchStatementKindError;
 use crate::statement::prepared::TokenCalculationError;
+use crate::utils::safe_format::IteratorSafeFormatExt;
 // Re-export error types from query_result module.
 pub use crate::response::query_result::{
     FirstRowError, IntoRowsResultError, MaybeFirstRowError, ResultNotRowsError, RowsError,
@@ -24,7 +35,10 @@ pub use crate::response::query_result::{
 pub use crate::authentication::AuthError;
 
 // Re-export error type from network module.
-pub use crate::network::tls::TlsError;
+pub use crate::network::tls::{CertificateRejected, TlsError};
+
+// Re-export error type from policies module.
+pub use crate::policies::rate_limiter::RateLimitError;
 
 // Re-export error types from scylla-cql.
 pub use scylla_cql::deserialize::{DeserializationError, TypeCheckError};
@@ -76,6 +90,14 @@ pub enum ExecutionError {
     )]
     RequestTimeout(std::time::Duration),
 
+    /// Request execution was cancelled with a [CancellationToken](crate::client::CancellationToken).
+    #[error("Request execution was cancelled")]
+    Cancelled,
+
+    /// Request execution did not finish before the deadline set on the statement.
+    #[error("Request execution exceeded the deadline")]
+    DeadlineExceeded,
+
     /// 'USE KEYSPACE <>' request failed.
     #[error("'USE KEYSPACE <>' request failed: {0}")]
     UseKeyspaceError(#[from] UseKeyspaceError),
@@ -87,6 +109,193 @@ pub enum ExecutionError {
     /// A metadata error occurred during schema agreement.
     #[error("Cluster metadata fetch error occurred during automatic schema agreement: {0}")]
     MetadataError(#[from] MetadataError),
+
+    /// The request was rejected by the session's
+    /// [`RequestRateLimiter`](crate::policies::rate_limiter::RequestRateLimiter).
+    #[error(transparent)]
+    RateLimited(#[from] RateLimitError),
+
+    /// Request execution failed after attempts of it were sent to nodes.
+    ///
+    /// Wraps the error that the request failed with, together with the details
+    /// of its attempts. Use [`ExecutionError::inner`] to match on the wrapped error.
+    #[error("{error}{}", DisplayAttemptContext(.attempts.last_attempt()))]
+    #[non_exhaustive]
+    Attempted {
+        /// The error that the request failed with. Never [`ExecutionError::Attempted`] itself.
+        error: Box<ExecutionError>,
+        /// Details of the attempts made to execute the request.
+        attempts: Box<AttemptDetails>,
+    },
+}
+
+impl ExecutionError {
+    /// Returns the error that the request failed with, looking through
+    /// [`ExecutionError::Attempted`].
+    pub fn inner(&self) -> &ExecutionError {
+        match self {
+            ExecutionError::Attempted { error, .. } => error,
+            _ => self,
+        }
+    }
+
+    /// Converts this error into the error that the request failed with,
+    /// dropping the details of its attempts.
+    pub fn into_inner(self) -> ExecutionError {
+        match self {
+            ExecutionError::Attempted { error, .. } => *error,
+            _ => self,
+        }
+    }
+
+    /// Returns the context of the last attempt of request execution,
+    /// i.e. the node it was sent to and its ordinal, if the error
+    /// was returned by an attempt sent to a node.
+    pub fn attempt_context(&self) -> Option<&AttemptContext> {
+        match self {
+            ExecutionError::Attempted { attempts, .. } => attempts.last_attempt(),
+            _ => None,
+        }
+    }
+
+    /// Returns the attempts made to execute the request, in the order in which they
+    /// were completed, if the error was returned by an attempt sent to a node,
+    /// or the request timed out after some of its attempts were completed.
+    /// Otherwise, returns an empty slice.
+    ///
+    /// See [`AttemptDetails::history`].
+    pub fn attempt_history(&self) -> &[RequestAttempt] {
+        match self {
+            ExecutionError::Attempted { attempts, .. } => attempts.history(),
+            _ => &[],
+        }
+    }
+
+    /// Returns the error returned by an attempt of request execution, if this error
+    /// was caused by one, looking through the nested errors.
+    pub fn attempt_error(&self) -> Option<&RequestAttemptError> {
+        match self.inner() {
+            ExecutionError::LastAttemptError(err)
+            | ExecutionError::PrepareError(PrepareError::AllAttemptsFailed {
+                first_attempt: err,
+            })
+            | ExecutionError::UseKeyspaceError(UseKeyspaceError::RequestError(err))
+            | ExecutionError::SchemaAgreementError(SchemaAgreementError::RequestError(err)) => {
+                Some(err)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the error returned by the database, if this error was caused by one,
+    /// looking through the nested errors.
+    pub fn db_error(&self) -> Option<&DbError> {
+        self.attempt_error().and_then(RequestAttemptError::db_error)
+    }
+
+    /// Checks whether the error is a timeout, either of the driver waiting for
+    /// the request (or for schema agreement), or of the database waiting for replicas.
+    pub fn is_timeout(&self) -> bool {
+        match self.inner() {
+            ExecutionError::RequestTimeout(_)
+            | ExecutionError::DeadlineExceeded
+            | ExecutionError::UseKeyspaceError(UseKeyspaceError::RequestTimeout(_))
+            | ExecutionError::SchemaAgreementError(
+                SchemaAgreementError::Timeout(_)
+                | SchemaAgreementError::TimeoutWithLaggingNodes { .. },
+            ) => true,
+            _ => self
+                .attempt_error()
+                .is_some_and(RequestAttemptError::is_timeout),
+        }
+    }
+
+    /// Checks whether the error was caused by overload, i.e. the database reported
+    /// being overloaded or rate limited the request, or the session's
+    /// [`RequestRateLimiter`](crate::policies::rate_limiter::RequestRateLimiter)
+    /// rejected it.
+    pub fn is_overloaded(&self) -> bool {
+        matches!(self.inner(), ExecutionError::RateLimited(_))
+            || self
+                .attempt_error()
+                .is_some_and(RequestAttemptError::is_overloaded)
+    }
+
+    /// Checks whether the database reported that not enough replicas are alive
+    /// to satisfy the consistency level of the request.
+    pub fn is_unavailable(&self) -> bool {
+        self.attempt_error()
+            .is_some_and(RequestAttemptError::is_unavailable)
+    }
+
+    /// Checks whether the error is related to the schema, e.g. the database reported
+    /// that the created schema entity already exists, a prepared statement changed
+    /// or was missing after repreparation, or awaiting schema agreement
+    /// or fetching schema metadata failed.
+    ///
+    /// Note that requests referring to nonexistent keyspaces or tables fail with
+    /// [`DbError::Invalid`], which is not classified as schema-related, as the database
+    /// returns it for other kinds of invalid requests as well.
+    pub fn is_schema_related(&self) -> bool {
+        match self.inner() {
+            ExecutionError::SchemaAgreementError(_)
+            | ExecutionError::MetadataError(_)
+            | ExecutionError::UseKeyspaceError(
+                UseKeyspaceError::BadKeyspaceName(_)
+                | UseKeyspaceError::KeyspaceNameMismatch { .. },
+            )
+            | ExecutionError::PrepareError(PrepareError::PreparedStatementIdsMismatch) => true,
+            _ => self
+                .attempt_error()
+                .is_some_and(RequestAttemptError::is_schema_related),
+        }
+    }
+
+    /// Checks whether retrying the request may succeed, provided that the request
+    /// is idempotent, i.e. applying it multiple times is safe.
+    ///
+    /// This is the case for errors specific to a node or transient, like timeouts,
+    /// broken connections or overload. Errors that will almost certainly appear again,
+    /// like invalid requests or serialization errors, are not safe to retry.
+    pub fn is_safe_to_retry_idempotent(&self) -> bool {
+        // Do not remove this lint!
+        // It's there for a reason - we don't want new variants
+        // automatically fall under `_` pattern when they are introduced.
+        #[deny(clippy::wildcard_enum_match_arm)]
+        match self {
+            ExecutionError::RequestTimeout(_)
+            | ExecutionError::DeadlineExceeded
+            | ExecutionError::ConnectionPoolError(_)
+            | ExecutionError::RateLimited(_) => true,
+
+            ExecutionError::LastAttemptError(err) => err.is_safe_to_retry_idempotent(),
+
+            ExecutionError::Attempted { error, .. } => error.is_safe_to_retry_idempotent(),
+
+            ExecutionError::PrepareError(err) => match err {
+                PrepareError::ConnectionPoolError(_) => true,
+                PrepareError::AllAttemptsFailed { first_attempt } => {
+                    first_attempt.is_safe_to_retry_idempotent()
+                }
+                PrepareError::PreparedStatementIdsMismatch => false,
+            },
+
+            ExecutionError::UseKeyspaceError(err) => match err {
+                UseKeyspaceError::RequestError(err) => err.is_safe_to_retry_idempotent(),
+                UseKeyspaceError::RequestTimeout(_) => true,
+                UseKeyspaceError::BadKeyspaceName(_)
+                | UseKeyspaceError::KeyspaceNameMismatch { .. } => false,
+            },
+
+            // The request itself succeeded, only the steps following it failed.
+            ExecutionError::SchemaAgreementError(_) | ExecutionError::MetadataError(_) => false,
+
+            // The user gave up on the request, so retrying it would go against their will.
+            ExecutionError::Cancelled => false,
+
+            ExecutionError::BadQuery(_) | ExecutionError::EmptyPlan => false,
+        }
+    }
 }
 
 impl From<SerializationError> for ExecutionError {
@@ -95,6 +304,113 @@ impl From<SerializationError> for ExecutionError {
     }
 }
 
+/// Details of the attempts made to execute a request that failed,
+/// attached to its error in [`ExecutionError::Attempted`] and [`RequestError::Attempted`].
+#[derive(Debug, Clone)]
+pub struct AttemptDetails {
+    last_attempt: Option<AttemptContext>,
+    history: Vec<RequestAttempt>,
+}
+
+impl AttemptDetails {
+    /// Context of the attempt that returned the request's error,
+    /// if the error was returned by an attempt.
+    #[inline]
+    pub fn last_attempt(&self) -> Option<&AttemptContext> {
+        self.last_attempt.as_ref()
+    }
+
+    /// All attempts made to execute the request, in the order in which they were completed.
+    ///
+    /// Contains attempts of all speculative executions of the request.
+    /// Empty for requests whose attempts are not recorded,
+    /// e.g. raw requests sent to a single node.
+    #[inline]
+    pub fn history(&self) -> &[RequestAttempt] {
+        &self.history
+    }
+}
+
+/// Context of a single attempt of request execution: the node and shard
+/// it was sent to, and its ordinal.
+#[derive(Debug, Clone)]
+pub struct AttemptContext {
+    coordinator: Coordinator,
+    attempt: usize,
+}
+
+impl AttemptContext {
+    pub(crate) fn new(coordinator: Coordinator, attempt: usize) -> Self {
+        Self {
+            coordinator,
+            attempt,
+        }
+    }
+
+    /// The coordinator the attempt was sent to.
+    #[inline]
+    pub fn coordinator(&self) -> &Coordinator {
+        &self.coordinator
+    }
+
+    /// Host ID of the node the attempt was sent to.
+    #[inline]
+    pub fn host_id(&self) -> Uuid {
+        self.coordinator.node().host_id
+    }
+
+    /// Translated address of the node the attempt was sent to,
+    /// i.e., one that the connection is opened against.
+    #[inline]
+    pub fn address(&self) -> SocketAddr {
+        self.coordinator.connection_address()
+    }
+
+    /// Number of the shard the attempt was sent to, if applicable
+    /// (present for ScyllaDB nodes, absent for Cassandra).
+    #[inline]
+    pub fn shard(&self) -> Option<Shard> {
+        self.coordinator.shard()
+    }
+
+    /// Ordinal of the attempt, starting from 1.
+    ///
+    /// Attempts are counted separately for each speculative execution
+    /// of the request.
+    #[inline]
+    pub fn attempt(&self) -> usize {
+        self.attempt
+    }
+}
+
+impl Display for AttemptContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "attempt {} to node {}, host id {}",
+            self.attempt,
+            self.address(),
+            self.host_id()
+        )?;
+        if let Some(shard) = self.shard() {
+            write!(f, ", shard {shard}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Displays the context of an attempt, if present, as a suffix of the attempt's error.
+struct DisplayAttemptContext<'a>(Option<&'a AttemptContext>);
+
+impl Display for DisplayAttemptContext<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(context) => write!(f, " ({context})"),
+            None => Ok(()),
+        }
+    }
+}
+
 /// An error returned by [`Session::prepare()`][crate::client::session::Session::prepare].
 #[derive(Error, Debug, Clone)]
 #[non_exhaustive]
@@ -120,9 +436,7 @@ pub enum PrepareError {
 /// An error that occurred during construction of [`QueryPager`][crate::client::pager::QueryPager].
 #[derive(Error, Debug, Clone)]
 #[non_exhaustive]
-// Check triggers because all variants end with "Error".
 // TODO(2.0): Remove the "Error" postfix from variants.
-#[expect(clippy::enum_variant_names)]
 pub enum PagerExecutionError {
     /// Failed to prepare the statement.
     #[error("Failed to prepare the statement to be used by the pager: {0}")]
@@ -135,6 +449,94 @@ pub enum PagerExecutionError {
     /// Failed to fetch the first page of the result.
     #[error("Failed to fetch the first page of the result: {0}")]
     NextPageError(#[from] NextPageError),
+
+    /// The statement of a resumed [`PagerCursor`][crate::client::pager::PagerCursor]
+    /// was prepared with a different id than when the cursor was taken,
+    /// e.g. because the session uses a different keyspace.
+    #[error("The statement of the pager cursor was prepared with a different id than before")]
+    CursorStatementMismatch,
+}
+
+/// An error returned by [`Session::batch_chunked()`][crate::client::session::Session::batch_chunked].
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum ChunkedBatchError {
+    /// Failed to prepare a statement, or to serialize its values.
+    /// No batches were executed.
+    #[error("Failed to prepare statement {index} or to serialize its values: {error}")]
+    BadStatement {
+        /// Position of the statement.
+        index: usize,
+        /// The error.
+        error: ExecutionError,
+    },
+
+    /// Some of the batches failed. All the other batches were executed successfully.
+    #[error(
+        "{} out of {chunk_count} batches failed. First error: {}",
+        .failed_chunks.len(),
+        .failed_chunks[0].error
+    )]
+    ChunksFailed {
+        /// Number of all executed batches.
+        chunk_count: usize,
+        /// Batches which failed, in the order of their statements. Never empty.
+        failed_chunks: Vec<FailedBatchChunk>,
+    },
+}
+
+/// A batch which failed, see [`ChunkedBatchError::ChunksFailed`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct FailedBatchChunk {
+    /// Positions of the statements in the batch.
+    pub statements: std::ops::Range<usize>,
+    /// The error.
+    pub error: ExecutionError,
+}
+
+/// An error returned by [`TokenRangeScan::execute()`][crate::scan::TokenRangeScan::execute].
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum TokenRangeScanError {
+    /// Metadata of the scanned table is unknown, e.g. because fetching
+    /// schema metadata is disabled.
+    #[error("Metadata of the scanned table {keyspace}.{table} is unknown")]
+    UnknownTable {
+        /// Name of the keyspace.
+        keyspace: String,
+        /// Name of the table.
+        table: String,
+    },
+}
+
+/// A failure to scan a token range, returned by
+/// [`TokenRangeScanStream`][crate::scan::TokenRangeScanStream].
+#[derive(Error, Debug, Clone)]
+#[error("Failed to scan token range {range}: {kind}")]
+#[non_exhaustive]
+pub struct ScanRangeError {
+    /// The range which failed.
+    pub range: crate::scan::TokenRange,
+    /// The reason of the failure.
+    pub kind: ScanRangeErrorKind,
+}
+
+/// The reason of a [`ScanRangeError`].
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum ScanRangeErrorKind {
+    /// Failed to execute the query of the range.
+    #[error(transparent)]
+    Execution(#[from] PagerExecutionError),
+
+    /// Type check of the returned rows failed.
+    #[error(transparent)]
+    TypeCheck(#[from] TypeCheckError),
+
+    /// Failed to fetch or deserialize a row.
+    #[error(transparent)]
+    NextRow(#[from] NextRowError),
 }
 
 /// Error that occurred during session creation
@@ -213,9 +615,28 @@ pub enum SchemaAgreementError {
     SingleRowError(SingleRowError),
 
     /// Schema agreement timed out.
+    ///
+    /// Not returned by [`Session`](crate::client::session::Session) anymore, which reports
+    /// [`TimeoutWithLaggingNodes`](Self::TimeoutWithLaggingNodes) instead.
     #[error("Schema agreement exceeded {}ms", std::time::Duration::as_millis(.0))]
     Timeout(std::time::Duration),
 
+    /// Schema agreement timed out, while some nodes still disagreed on the schema version.
+    ///
+    /// Returned by [`Session::await_schema_agreement`](crate::client::session::Session::await_schema_agreement)
+    /// and its variants, as well as by the automatic schema agreement after schema changes.
+    #[error(
+        "Schema agreement exceeded {}ms, lagging nodes: [{}]",
+        std::time::Duration::as_millis(timeout),
+        lagging_nodes.iter().safe_format(", ")
+    )]
+    TimeoutWithLaggingNodes {
+        /// The schema agreement timeout that was exceeded.
+        timeout: std::time::Duration,
+        /// Nodes which did not agree on the prevailing schema version in the last check.
+        lagging_nodes: Vec<NodeSchemaVersion>,
+    },
+
     /// Some host mandatory for schema agreement is not present in the connection pool.
     #[error(
         "Host with id {} required for schema agreement is not present in connection pool",
@@ -224,6 +645,59 @@ pub enum SchemaAgreementError {
     RequiredHostAbsent(Uuid),
 }
 
+/// An error that occurred when draining a node with
+/// [`Session::drain_node`](crate::client::session::Session::drain_node).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum DrainNodeError {
+    /// The node is not present in the cluster state.
+    #[error("Node with host id {0} is not present in the cluster state")]
+    UnknownNode(Uuid),
+
+    /// Failed to refresh the cluster state after marking the node as drained.
+    /// The node stays drained and is excluded from the cluster state upon the next successful refresh.
+    #[error("Failed to refresh cluster state: {0}")]
+    MetadataError(#[from] MetadataError),
+
+    /// Requests sent to the node did not finish in time.
+    /// The node stays drained and no new requests are routed to it.
+    #[error(
+        "Draining node {host_id} exceeded {}ms with {in_flight_requests} requests still in flight",
+        std::time::Duration::as_millis(timeout)
+    )]
+    Timeout {
+        /// Host ID of the drained node.
+        host_id: Uuid,
+        /// The timeout that was exceeded.
+        timeout: std::time::Duration,
+        /// Number of requests to the node which were still in flight.
+        in_flight_requests: usize,
+    },
+}
+
+/// An error that occurred when fetching the ScyllaDB version of a node with
+/// [`Node::fetch_scylla_version`](crate::cluster::Node::fetch_scylla_version).
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+#[expect(clippy::enum_variant_names)]
+pub enum ScyllaVersionError {
+    /// The node has no working connection pool.
+    #[error("Failed to find a working connection to the node: {0}")]
+    ConnectionPoolError(#[from] ConnectionPoolError),
+
+    /// Failed to execute the version query.
+    #[error("Failed to execute version query: {0}")]
+    RequestError(#[from] RequestAttemptError),
+
+    /// Failed to convert the version query result into rows result.
+    #[error("Failed to convert version query result into rows result: {0}")]
+    IntoRowsResultError(IntoRowsResultError),
+
+    /// Failed to deserialize a single row from the version query response.
+    #[error(transparent)]
+    SingleRowError(SingleRowError),
+}
+
 /// An error that occurred during tracing info fetch.
 #[derive(Error, Debug, Clone)]
 #[non_exhaustive]
@@ -450,6 +924,114 @@ pub enum TablesMetadataError {
     },
 }
 
+/// An error that occurred during user defined functions or aggregates metadata fetch.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum FunctionsMetadataError {
+    /// Failed to parse CQL type returned from system_schema.functions
+    /// or system_schema.aggregates query.
+    #[error(
+        "Failed to parse a CQL type returned from {table} query. \
+        Type '{typ}', at position {position}: {reason}"
+    )]
+    InvalidCqlType {
+        /// Schema table which the invalid CQL type was read from.
+        table: &'static str,
+        /// (Invalid) name of the invalid CQL type.
+        typ: String,
+        /// Position in the CQL type string where the error occurred.
+        position: usize,
+        /// Reason why the CQL type name is invalid.
+        reason: String,
+    },
+}
+
+/// An error that prevented refreshing metadata of a single keyspace.
+///
+/// Such errors do not fail the whole metadata refresh. Instead, the previously
+/// fetched metadata of the keyspace is kept, if there is any.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum SingleKeyspaceMetadataError {
+    /// A user defined type used in the keyspace is missing from the metadata.
+    #[error(transparent)]
+    MissingUDT(MissingUserDefinedType),
+
+    /// A partition key column is missing from the metadata.
+    #[error("Partition key column with position {0} is missing from metadata")]
+    IncompletePartitionKey(i32),
+
+    /// A clustering key column is missing from the metadata.
+    #[error("Clustering key column with position {0} is missing from metadata")]
+    IncompleteClusteringKey(i32),
+
+    /// Bad replication strategy of the keyspace.
+    #[error("Bad keyspace replication strategy: {0}")]
+    Strategy(KeyspaceStrategyError),
+
+    /// Bad metadata of the keyspace's UDTs.
+    #[error("Bad UDTs metadata: {0}")]
+    Udts(UdtMetadataError),
+
+    /// Bad metadata of the keyspace's tables.
+    #[error("Bad tables metadata: {0}")]
+    Tables(TablesMetadataError),
+
+    /// Bad metadata of the keyspace's user defined functions or aggregates.
+    #[error("Bad functions metadata: {0}")]
+    Functions(FunctionsMetadataError),
+
+    /// Fetching the schema of tables, views, UDTs, functions or aggregates failed,
+    /// which affected all keyspaces.
+    #[error("Failed to fetch schema metadata: {0}")]
+    SchemaFetch(MetadataError),
+}
+
+/// A user defined type whose definition is missing from the metadata.
+#[derive(Error, Debug, Clone)]
+#[error("Missing UDT: {keyspace}, {name}")]
+#[non_exhaustive]
+pub struct MissingUserDefinedType {
+    /// Name of the missing type.
+    pub name: String,
+    /// Keyspace of the missing type.
+    pub keyspace: String,
+}
+
+/// Describes the parts of schema metadata which could not be refreshed
+/// during the latest metadata refresh, while the rest of it (including topology) could.
+///
+/// The cluster state keeps the previously fetched metadata of these parts, if there is any.
+/// Reported by [`ClusterState::partial_metadata_error`](crate::cluster::ClusterState::partial_metadata_error).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct PartialMetadataError {
+    /// An error which prevented fetching the list of keyspaces.
+    /// If set, metadata of all keyspaces is kept from the previous refresh.
+    pub keyspaces_error: Option<MetadataError>,
+
+    /// Keyspaces whose metadata could not be refreshed, along with the reasons.
+    pub keyspace_errors: HashMap<String, SingleKeyspaceMetadataError>,
+}
+
+impl std::fmt::Display for PartialMetadataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(err) = &self.keyspaces_error {
+            return write!(f, "Failed to fetch keyspaces metadata: {err}");
+        }
+        write!(
+            f,
+            "Failed to refresh metadata of keyspaces: {}",
+            self.keyspace_errors
+                .iter()
+                .map(|(keyspace, err)| format!("\"{keyspace}\": {err}"))
+                .safe_format(", ")
+        )
+    }
+}
+
+impl Error for PartialMetadataError {}
+
 /// Error caused by caller creating an invalid statement.
 #[derive(Error, Debug, Clone)]
 #[error("Invalid statement passed to Session")]
@@ -470,6 +1052,15 @@ pub enum BadQuery {
     /// Too many statements in the batch statement.
     #[error("Number of statements in Batch Statement supplied is {0} which has exceeded the max value of 65,535")]
     TooManyQueriesInBatchStatement(usize),
+
+    /// A batch statement sets its own timestamp, while the batch has a timestamp set too.
+    #[error("Batch statement at index {0} sets its own timestamp, which conflicts with the batch-level timestamp")]
+    ConflictingTimestampInBatchStatement(usize),
+
+    /// A batch statement doesn't fit the batch type, e.g. a counter batch contains
+    /// a statement which is not a counter update.
+    #[error("Batch statement at index {0} doesn't fit the batch type: {1}")]
+    BatchStatementKindMismatch(usize, BatchStatementKindError),
 }
 
 /// Invalid keyspace name given to `Session::use_keyspace()`
@@ -539,6 +1130,21 @@ pub enum ConnectionError {
     /// A request required to initialize a connection failed.
     #[error(transparent)]
     ConnectionSetupRequestError(#[from] ConnectionSetupRequestError),
+
+    /// The node rejected all protocol versions allowed by
+    /// [`ProtocolNegotiationPolicy`](crate::client::ProtocolNegotiationPolicy).
+    #[error(
+        "Node {node} rejected all allowed protocol versions, \
+        the last attempted version was {last_attempted_version}: {reason}"
+    )]
+    ProtocolVersionNegotiationFailed {
+        /// Address of the node that rejected the protocol versions.
+        node: SocketAddr,
+        /// The lowest attempted version.
+        last_attempted_version: ProtocolVersion,
+        /// Error returned when attempting the lowest version.
+        reason: Box<ConnectionError>,
+    },
 }
 
 impl From<std::io::Error> for ConnectionError {
@@ -809,19 +1415,122 @@ pub enum RequestError {
         )]
     RequestTimeout(std::time::Duration),
 
+    /// Request execution was cancelled with a [CancellationToken](crate::client::CancellationToken).
+    #[error("Request execution was cancelled")]
+    Cancelled,
+
+    /// Request execution did not finish before the deadline set on the statement.
+    #[error("Request execution exceeded the deadline")]
+    DeadlineExceeded,
+
+    /// Request was rejected by the session's
+    /// [`RequestRateLimiter`](crate::policies::rate_limiter::RequestRateLimiter).
+    #[error(transparent)]
+    RateLimited(#[from] RateLimitError),
+
     /// Failed to execute request.
     #[error(transparent)]
     LastAttemptError(#[from] RequestAttemptError),
+
+    /// Request execution failed after attempts of it were sent to nodes.
+    ///
+    /// Wraps the error that the request failed with, together with the details
+    /// of its attempts. Use [`RequestError::inner`] to match on the wrapped error.
+    #[error("{error}{}", DisplayAttemptContext(.attempts.last_attempt()))]
+    #[non_exhaustive]
+    Attempted {
+        /// The error that the request failed with. Never [`RequestError::Attempted`] itself.
+        error: Box<RequestError>,
+        /// Details of the attempts made to execute the request.
+        attempts: Box<AttemptDetails>,
+    },
 }
 
 impl RequestError {
+    /// Creates an error returned by an attempt sent to a node, along with the attempt's context.
+    pub(crate) fn from_attempt(error: RequestAttemptError, context: AttemptContext) -> Self {
+        RequestError::Attempted {
+            error: Box::new(RequestError::LastAttemptError(error)),
+            attempts: Box::new(AttemptDetails {
+                last_attempt: Some(context),
+                history: Vec::new(),
+            }),
+        }
+    }
+
+    /// Attaches the history of attempts of the request to the details of its attempts.
+    ///
+    /// Timeouts are wrapped in [`RequestError::Attempted`] if any attempts
+    /// were completed before the request timed out.
+    pub(crate) fn with_attempt_history(self, history: Vec<RequestAttempt>) -> Self {
+        match self {
+            RequestError::Attempted {
+                error,
+                mut attempts,
+            } => {
+                attempts.history = history;
+                RequestError::Attempted { error, attempts }
+            }
+            RequestError::RequestTimeout(_) | RequestError::DeadlineExceeded
+                if !history.is_empty() =>
+            {
+                RequestError::Attempted {
+                    error: Box::new(self),
+                    attempts: Box::new(AttemptDetails {
+                        last_attempt: None,
+                        history,
+                    }),
+                }
+            }
+            _ => self,
+        }
+    }
+
+    /// Returns the error that the request failed with, looking through
+    /// [`RequestError::Attempted`].
+    pub fn inner(&self) -> &RequestError {
+        match self {
+            RequestError::Attempted { error, .. } => error,
+            _ => self,
+        }
+    }
+
+    /// Returns the context of the last attempt of request execution,
+    /// if the error was returned by an attempt sent to a node.
+    pub fn attempt_context(&self) -> Option<&AttemptContext> {
+        match self {
+            RequestError::Attempted { attempts, .. } => attempts.last_attempt(),
+            _ => None,
+        }
+    }
+
+    /// Returns the attempts made to execute the request, in the order in which they
+    /// were completed, if the error was returned by an attempt sent to a node,
+    /// or the request timed out after some of its attempts were completed.
+    /// Otherwise, returns an empty slice.
+    ///
+    /// See [`AttemptDetails::history`].
+    pub fn attempt_history(&self) -> &[RequestAttempt] {
+        match self {
+            RequestError::Attempted { attempts, .. } => attempts.history(),
+            _ => &[],
+        }
+    }
+
     /// Converts (widens) this error into an [`ExecutionError`].
     pub fn into_execution_error(self) -> ExecutionError {
         match self {
             RequestError::EmptyPlan => ExecutionError::EmptyPlan,
             RequestError::ConnectionPoolError(e) => e.into(),
             RequestError::RequestTimeout(dur) => ExecutionError::RequestTimeout(dur),
+            RequestError::Cancelled => ExecutionError::Cancelled,
+            RequestError::DeadlineExceeded => ExecutionError::DeadlineExceeded,
+            RequestError::RateLimited(e) => ExecutionError::RateLimited(e),
             RequestError::LastAttemptError(e) => ExecutionError::LastAttemptError(e),
+            RequestError::Attempted { error, attempts } => ExecutionError::Attempted {
+                error: Box::new(error.into_execution_error()),
+                attempts,
+            },
         }
     }
 }
@@ -904,6 +1613,77 @@ pub enum RequestAttemptError {
     NonfinishedPagingState,
 }
 
+impl RequestAttemptError {
+    /// Returns the error returned by the database, if the attempt failed with one.
+    pub fn db_error(&self) -> Option<&DbError> {
+        match self {
+            RequestAttemptError::DbError(db_error, _) => Some(db_error),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the database reported that it timed out waiting for replicas.
+    pub fn is_timeout(&self) -> bool {
+        matches!(
+            self.db_error(),
+            Some(DbError::ReadTimeout { .. } | DbError::WriteTimeout { .. })
+        )
+    }
+
+    /// Checks whether the database reported being overloaded or rate limited the request.
+    pub fn is_overloaded(&self) -> bool {
+        matches!(
+            self.db_error(),
+            Some(DbError::Overloaded | DbError::RateLimitReached { .. })
+        )
+    }
+
+    /// Checks whether the database reported that not enough replicas are alive
+    /// to satisfy the consistency level of the request.
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self.db_error(), Some(DbError::Unavailable { .. }))
+    }
+
+    /// Checks whether the error is related to the schema, e.g. the database reported
+    /// that the created schema entity already exists, or a prepared statement changed
+    /// or was missing after repreparation.
+    pub fn is_schema_related(&self) -> bool {
+        matches!(
+            self,
+            RequestAttemptError::DbError(DbError::AlreadyExists { .. }, _)
+                | RequestAttemptError::RepreparedIdChanged { .. }
+                | RequestAttemptError::RepreparedIdMissingInBatch
+        )
+    }
+
+    /// Checks whether retrying the request, on this or another node, may succeed,
+    /// provided that the request is idempotent.
+    pub fn is_safe_to_retry_idempotent(&self) -> bool {
+        // Do not remove this lint!
+        // It's there for a reason - we don't want new variants
+        // automatically fall under `_` pattern when they are introduced.
+        #[deny(clippy::wildcard_enum_match_arm)]
+        match self {
+            // Errors that will almost certainly appear for other nodes as well
+            RequestAttemptError::SerializationError(_)
+            | RequestAttemptError::CqlRequestSerialization(_)
+            | RequestAttemptError::BodyExtensionsParseError(_)
+            | RequestAttemptError::CqlResultParseError(_)
+            | RequestAttemptError::CqlErrorParseError(_)
+            | RequestAttemptError::UnexpectedResponse(_)
+            | RequestAttemptError::RepreparedIdChanged { .. }
+            | RequestAttemptError::RepreparedIdMissingInBatch
+            | RequestAttemptError::NonfinishedPagingState => false,
+
+            // Errors specific to the connection
+            RequestAttemptError::BrokenConnectionError(_)
+            | RequestAttemptError::UnableToAllocStreamId => true,
+
+            RequestAttemptError::DbError(db_error, _) => db_error.can_speculative_retry(),
+        }
+    }
+}
+
 impl From<response::error::Error> for RequestAttemptError {
     fn from(value: response::error::Error) -> Self {
         RequestAttemptError::DbError(value.error, value.reason)
@@ -1007,9 +1787,22 @@ pub enum ClusterStateTokenError {
 
 #[cfg(test)]
 mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use assert_matches::assert_matches;
     use scylla_cql::Consistency;
+    use uuid::Uuid;
 
-    use super::{DbError, ExecutionError, RequestAttemptError, WriteType};
+    use crate::cluster::Node;
+    use crate::policies::retry::RetryDecision;
+    use crate::response::{Coordinator, RequestAttempt};
+
+    use super::{
+        AttemptContext, DbError, ExecutionError, RequestAttemptError, RequestError,
+        SchemaAgreementError, UseKeyspaceError, WriteType,
+    };
 
     #[test]
     fn write_type_from_str() {
@@ -1066,4 +1859,151 @@ mod tests {
 
         assert_eq!(execution_error_displayed, expected_execution_err_msg);
     }
+
+    #[test]
+    fn attempt_context_is_exposed_and_displayed() {
+        let host_id = Uuid::from_u128(0x1234);
+        let address: SocketAddr = "127.0.0.1:19042".parse().unwrap();
+        let node = Arc::new(Node::new_for_test(Some(host_id), None, None, None));
+        let context = AttemptContext::new(Coordinator::new_for_test(node, Some(3), address), 2);
+
+        let history = vec![RequestAttempt::failed(
+            context.coordinator().clone(),
+            Duration::from_millis(5),
+            RequestAttemptError::UnableToAllocStreamId,
+            RetryDecision::DontRetry,
+        )];
+        let request_error =
+            RequestError::from_attempt(RequestAttemptError::UnableToAllocStreamId, context)
+                .with_attempt_history(history);
+        let context = request_error.attempt_context().unwrap();
+        assert_eq!(context.host_id(), host_id);
+        assert_eq!(context.address(), address);
+        assert_eq!(context.shard(), Some(3));
+        assert_eq!(context.attempt(), 2);
+        assert_matches!(
+            request_error.inner(),
+            RequestError::LastAttemptError(RequestAttemptError::UnableToAllocStreamId)
+        );
+
+        let execution_error = request_error.into_execution_error();
+        assert_eq!(execution_error.attempt_context().unwrap().attempt(), 2);
+        assert!(execution_error.is_safe_to_retry_idempotent());
+        assert_matches!(
+            execution_error.inner(),
+            ExecutionError::LastAttemptError(RequestAttemptError::UnableToAllocStreamId)
+        );
+        let [attempt] = execution_error.attempt_history() else {
+            panic!("Expected exactly one attempt in the history");
+        };
+        assert_eq!(attempt.latency(), Duration::from_millis(5));
+        assert_eq!(attempt.retry_decision(), Some(&RetryDecision::DontRetry));
+        assert_eq!(
+            execution_error.to_string(),
+            "Unable to allocate stream id (attempt 2 to node 127.0.0.1:19042, \
+            host id 00000000-0000-0000-0000-000000001234, shard 3)"
+        );
+
+        // Errors which didn't come from an attempt sent to a node carry no context.
+        let execution_error: ExecutionError = RequestAttemptError::UnableToAllocStreamId.into();
+        assert!(execution_error.attempt_context().is_none());
+        assert!(execution_error.attempt_history().is_empty());
+        assert_eq!(execution_error.to_string(), "Unable to allocate stream id");
+    }
+
+    #[test]
+    fn timeouts_carry_attempt_history() {
+        let address: SocketAddr = "127.0.0.1:19042".parse().unwrap();
+        let node = Arc::new(Node::new_for_test(None, None, None, None));
+        let history = vec![RequestAttempt::failed(
+            Coordinator::new_for_test(node, None, address),
+            Duration::from_millis(5),
+            RequestAttemptError::UnableToAllocStreamId,
+            RetryDecision::RetryNextTarget(None),
+        )];
+
+        for timeout in [
+            RequestError::RequestTimeout(Duration::from_secs(1)),
+            RequestError::DeadlineExceeded,
+        ] {
+            let error = timeout.with_attempt_history(history.clone());
+            assert_eq!(error.attempt_history().len(), 1);
+            assert!(error.attempt_context().is_none());
+
+            let execution_error = error.into_execution_error();
+            assert_eq!(execution_error.attempt_history().len(), 1);
+            assert!(execution_error.is_timeout());
+            assert_matches!(
+                execution_error.inner(),
+                ExecutionError::RequestTimeout(_) | ExecutionError::DeadlineExceeded
+            );
+        }
+
+        // Without any completed attempts, there is nothing to attach.
+        let error = RequestError::DeadlineExceeded.with_attempt_history(Vec::new());
+        assert_matches!(error, RequestError::DeadlineExceeded);
+    }
+
+    #[test]
+    fn execution_errors_are_classified() {
+        fn attempt_error(db_error: DbError) -> ExecutionError {
+            RequestAttemptError::DbError(db_error, String::new()).into()
+        }
+
+        let read_timeout = attempt_error(DbError::ReadTimeout {
+            consistency: Consistency::Quorum,
+            received: 1,
+            required: 2,
+            data_present: false,
+        });
+        assert!(read_timeout.is_timeout());
+        assert!(read_timeout.is_safe_to_retry_idempotent());
+        assert!(!read_timeout.is_overloaded());
+
+        let client_timeout = ExecutionError::RequestTimeout(Duration::from_secs(1));
+        assert!(client_timeout.is_timeout());
+        assert!(client_timeout.is_safe_to_retry_idempotent());
+        assert!(client_timeout.db_error().is_none());
+
+        let overloaded = attempt_error(DbError::Overloaded);
+        assert!(overloaded.is_overloaded());
+        assert!(overloaded.is_safe_to_retry_idempotent());
+        assert!(!overloaded.is_timeout());
+
+        let unavailable = attempt_error(DbError::Unavailable {
+            consistency: Consistency::Three,
+            required: 3,
+            alive: 2,
+        });
+        assert!(unavailable.is_unavailable());
+        assert!(unavailable.is_safe_to_retry_idempotent());
+
+        let already_exists = attempt_error(DbError::AlreadyExists {
+            keyspace: "ks".to_owned(),
+            table: "t".to_owned(),
+        });
+        assert!(already_exists.is_schema_related());
+        assert!(!already_exists.is_safe_to_retry_idempotent());
+
+        let syntax_error = attempt_error(DbError::SyntaxError);
+        assert_eq!(syntax_error.db_error(), Some(&DbError::SyntaxError));
+        assert!(!syntax_error.is_timeout());
+        assert!(!syntax_error.is_overloaded());
+        assert!(!syntax_error.is_unavailable());
+        assert!(!syntax_error.is_schema_related());
+        assert!(!syntax_error.is_safe_to_retry_idempotent());
+
+        // Errors nested deeper in the hierarchy are classified as well.
+        let use_keyspace_error = ExecutionError::UseKeyspaceError(UseKeyspaceError::RequestError(
+            RequestAttemptError::DbError(DbError::Overloaded, String::new()),
+        ));
+        assert!(use_keyspace_error.is_overloaded());
+        assert_eq!(use_keyspace_error.db_error(), Some(&DbError::Overloaded));
+
+        let schema_agreement_timeout =
+            ExecutionError::SchemaAgreementError(SchemaAgreementError::Timeout(Duration::ZERO));
+        assert!(schema_agreement_timeout.is_timeout());
+        assert!(schema_agreement_timeout.is_schema_related());
+        assert!(!schema_agreement_timeout.is_safe_to_retry_idempotent());
+    }
 }
@@ -97,9 +97,12 @@
 #[doc(hidden)]
 pub mod _macro_internal {
     pub use scylla_cql::_macro_internal::*;
+
+    pub use crate::table::{CqlTypeName, Table};
 }
 
 pub use scylla_cql::{DeserializeRow, DeserializeValue, SerializeRow, SerializeValue};
+pub use scylla_macros::Table;
 
 pub mod value {
     //! Defines CQL values of various types and their representations,
@@ -107,17 +110,18 @@ pub mod value {
 
     // Every `pub` item is re-exported here, apart from `deser_cql_value`.
     pub use scylla_cql::value::{
-        Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime, CqlTimestamp,
-        CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, MaybeUnset, Row, Unset, ValueOverflow,
+        BytesStr, Counter, CqlDate, CqlDecimal, CqlDecimalBorrowed, CqlDuration, CqlTime,
+        CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint, CqlVarintBorrowed, CqlVector, MaybeUnset,
+        Row, UdtValueBuilder, UdtValueError, Unset, ValueOverflow,
     };
 }
 
 pub mod frame {
     //! Abstractions of the CQL wire protocol.
 
-    pub use scylla_cql::frame::{frame_errors, Authenticator, Compression};
+    pub use scylla_cql::frame::{frame_errors, Authenticator, Compression, ProtocolVersion};
     pub(crate) use scylla_cql::frame::{
-        parse_response_body_extensions, protocol_features, read_response_frame, request,
+        parse_response_body_extensions, protocol_features, read_response_frame, request, segment,
         server_event_type, FrameParams, SerializedRequest,
     };
 
@@ -167,7 +171,10 @@ pub mod serialize {
     /// Contains the [SerializeRow][row::SerializeRow] trait and its implementations.
     pub mod row {
         // Main types
-        pub use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow};
+        pub use scylla_cql::serialize::row::{
+            MissingValueBehavior, NamedValues, RowSerializationContext, SerializeRow,
+            SerializedValues,
+        };
 
         // Errors
         pub use scylla_cql::serialize::row::{
@@ -187,10 +194,16 @@ pub mod serialize {
             BuiltinTypeCheckErrorKind, MapSerializationErrorKind, MapTypeCheckErrorKind,
             SetOrListSerializationErrorKind, SetOrListTypeCheckErrorKind,
             TupleSerializationErrorKind, TupleTypeCheckErrorKind, UdtSerializationErrorKind,
-            UdtTypeCheckErrorKind,
+            UdtTypeCheckErrorKind, VectorSerializationErrorKind, VectorTypeCheckErrorKind,
         };
     }
 
+    /// Serializing JSON values as CQL values, guided by the types of the bind markers.
+    #[cfg(feature = "serde_json-1")]
+    pub mod json {
+        pub use scylla_cql::serialize::json::{json_to_cql_value, JsonConversionError};
+    }
+
     /// Contains types and traits used for safe serialization of values for a CQL statement.
     pub mod writers {
         pub use scylla_cql::serialize::writers::{
@@ -213,7 +226,8 @@ pub mod deserialize {
     pub mod row {
         pub use scylla_cql::deserialize::row::{
             BuiltinDeserializationError, BuiltinDeserializationErrorKind, BuiltinTypeCheckError,
-            BuiltinTypeCheckErrorKind, ColumnIterator, DeserializeRow, RawColumn,
+            BuiltinTypeCheckErrorKind, ColumnAccessError, ColumnIterator, DeserializeRow,
+            DeserializeRowWithMode, Lenient, RawColumn, RawRow, Strict,
         };
     }
 
@@ -225,10 +239,17 @@ pub mod deserialize {
             MapDeserializationErrorKind, MapIterator, MapTypeCheckErrorKind, MaybeEmpty,
             SetOrListDeserializationErrorKind, SetOrListTypeCheckErrorKind,
             TupleDeserializationErrorKind, TupleTypeCheckErrorKind, UdtIterator,
-            UdtTypeCheckErrorKind,
+            UdtTypeCheckErrorKind, VectorDeserializationErrorKind, VectorIterator,
+            VectorTypeCheckErrorKind,
         };
     }
 
+    /// Deserializing rows of arbitrary types into JSON objects.
+    #[cfg(feature = "serde_json-1")]
+    pub mod json {
+        pub use scylla_cql::deserialize::json::{BlobFormat, JsonFormat, JsonRow, TimestampFormat};
+    }
+
     // Shorthands for better readability.
     pub(crate) trait DeserializeOwnedRow:
         for<'frame, 'metadata> row::DeserializeRow<'frame, 'metadata>
@@ -250,9 +271,12 @@ pub mod errors;
 mod network;
 pub mod observability;
 pub mod policies;
+pub mod query_builder;
 pub mod response;
 pub mod routing;
+pub mod scan;
 pub mod statement;
+pub mod table;
 
 pub(crate) mod utils;
 
@@ -1,6 +1,7 @@
 use super::tls::{TlsConfig, TlsProvider};
 use crate::authentication::AuthenticatorProvider;
 use crate::client::pager::{NextRowError, QueryPager};
+use crate::client::tls::CertificateVerifier;
 use crate::client::Compression;
 use crate::client::SelfIdentity;
 use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
@@ -9,16 +10,21 @@ use crate::errors::{
     BadKeyspaceName, BrokenConnectionError, BrokenConnectionErrorKind, ConnectionError,
     ConnectionSetupRequestError, ConnectionSetupRequestErrorKind, CqlEventHandlingError, DbError,
     InternalRequestError, RequestAttemptError, ResponseParseError, SchemaAgreementError,
-    TranslationError, UseKeyspaceError,
+    ScyllaVersionError, TranslationError, UseKeyspaceError,
 };
+use crate::frame::frame_errors::FrameHeaderParseError;
 use crate::frame::protocol_features::ProtocolFeatures;
 use crate::frame::{
     self,
     request::{self, batch, execute, query, register, SerializableRequest},
     response::{event::Event, result, Response, ResponseOpcode},
+    segment::{SegmentEncoder, SegmentedFrameReader},
     server_event_type::EventType,
-    FrameParams, SerializedRequest,
+    FrameParams, ProtocolVersion, SerializedRequest,
 };
+#[cfg(feature = "metrics")]
+use crate::observability::metrics::Metrics;
+use crate::observability::repreparation::{RepreparationEvent, RepreparationListener};
 use crate::policies::address_translator::{AddressTranslator, UntranslatedPeer};
 use crate::policies::timestamp_generator::TimestampGenerator;
 use crate::response::query_result::QueryResult;
@@ -49,18 +55,18 @@ use std::borrow::Cow;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::convert::TryFrom;
 use std::net::{IpAddr, SocketAddr};
-use std::num::NonZeroU64;
-use std::sync::atomic::AtomicU64;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize};
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use std::{
     cmp::Ordering,
     net::{Ipv4Addr, Ipv6Addr},
 };
 use tokio::io::{split, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, BufWriter};
 use tokio::net::{TcpSocket, TcpStream};
-use tokio::sync::{mpsc, oneshot};
+use tokio::sync::{mpsc, oneshot, Semaphore};
 use tokio::time::Instant;
 use tracing::{debug, error, trace, warn};
 use uuid::Uuid;
@@ -68,6 +74,9 @@ use uuid::Uuid;
 // Queries for schema agreement
 const LOCAL_VERSION: &str = "SELECT schema_version FROM system.local WHERE key='local'";
 
+// Query for the ScyllaDB version of the node. The table does not exist in Cassandra.
+const LOCAL_SCYLLA_VERSION: &str = "SELECT version FROM system.versions WHERE key='local'";
+
 // FIXME: Make this constants configurable
 // The term "orphan" refers to stream ids, that were allocated for a {request, response} that no
 // one is waiting anymore (due to cancellation of `Connection::send_request`). Old orphan refers to
@@ -77,6 +86,10 @@ const LOCAL_VERSION: &str = "SELECT schema_version FROM system.local WHERE key='
 const OLD_ORPHAN_COUNT_THRESHOLD: usize = 1024;
 const OLD_AGE_ORPHAN_THRESHOLD: std::time::Duration = std::time::Duration::from_secs(1);
 
+// Size of the header of a CQL frame, which is not part of the response body.
+#[cfg(feature = "metrics")]
+const FRAME_HEADER_SIZE: usize = 9;
+
 /// Represents a write coalescing delay configuration option.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
@@ -96,6 +109,89 @@ pub enum WriteCoalescingDelay {
     Milliseconds(NonZeroU64),
 }
 
+/// Controls which versions of the CQL native protocol the driver may use
+/// when connecting to a node, and what happens if a node rejects a version.
+///
+/// When opening a connection, the driver starts with the highest allowed version.
+/// If the node rejects it and downgrade is allowed, the driver retries with
+/// subsequent lower versions, down to the lowest allowed one. Each downgrade
+/// is logged along with the address of the node that forced it.
+/// If no allowed version is accepted by the node, connecting fails with
+/// [`ConnectionError::ProtocolVersionNegotiationFailed`], which identifies the node.
+///
+/// Default: protocol versions up to v4 are allowed, and downgrade is allowed.
+/// Protocol v5 is not attempted by default, because ScyllaDB does not support it
+/// and every connection would first have to be downgraded. To use it (e.g. with Cassandra 4.0+),
+/// allow it explicitly, e.g. with `ProtocolNegotiationPolicy::new(ProtocolVersion::V4, ProtocolVersion::V5)`.
+#[derive(Debug, Clone)]
+pub struct ProtocolNegotiationPolicy {
+    min_version: ProtocolVersion,
+    max_version: ProtocolVersion,
+    allow_downgrade: bool,
+}
+
+impl ProtocolNegotiationPolicy {
+    /// Creates a policy that allows versions from `min_version` to `max_version` (inclusive).
+    ///
+    /// # Panics
+    /// Panics if `min_version` is greater than `max_version`.
+    pub fn new(min_version: ProtocolVersion, max_version: ProtocolVersion) -> Self {
+        assert!(
+            min_version <= max_version,
+            "min_version ({min_version}) must not be greater than max_version ({max_version})"
+        );
+        Self {
+            min_version,
+            max_version,
+            allow_downgrade: true,
+        }
+    }
+
+    /// Creates a policy that allows only the given version.
+    pub fn pinned(version: ProtocolVersion) -> Self {
+        Self::new(version, version)
+    }
+
+    /// Controls whether the driver may retry with a lower version if a node
+    /// rejects the highest allowed one. If disabled, a rejection of the highest
+    /// allowed version is reported as an error immediately.
+    pub fn with_downgrade_allowed(mut self, allow_downgrade: bool) -> Self {
+        self.allow_downgrade = allow_downgrade;
+        self
+    }
+
+    /// The lowest allowed protocol version.
+    pub fn min_version(&self) -> ProtocolVersion {
+        self.min_version
+    }
+
+    /// The highest allowed protocol version, which is attempted first.
+    pub fn max_version(&self) -> ProtocolVersion {
+        self.max_version
+    }
+
+    /// Whether downgrading below [`Self::max_version`] is allowed.
+    pub fn is_downgrade_allowed(&self) -> bool {
+        self.allow_downgrade
+    }
+
+    // Versions to attempt, in order of preference.
+    fn versions_to_attempt(&self) -> impl Iterator<Item = ProtocolVersion> + '_ {
+        let versions = ProtocolVersion::ALL
+            .iter()
+            .rev()
+            .copied()
+            .filter(|v| (self.min_version..=self.max_version).contains(v));
+        versions.take(if self.allow_downgrade { usize::MAX } else { 1 })
+    }
+}
+
+impl Default for ProtocolNegotiationPolicy {
+    fn default() -> Self {
+        Self::new(*ProtocolVersion::ALL.first().unwrap(), ProtocolVersion::V4)
+    }
+}
+
 pub(crate) struct Connection {
     _worker_handle: RemoteHandle<()>,
 
@@ -103,6 +199,8 @@ pub(crate) struct Connection {
     config: HostConnectionConfig,
     features: ConnectionFeatures,
     router_handle: Arc<RouterHandle>,
+    // Set if the credentials used to authenticate this connection have limited lifetime.
+    credentials_expiry: Option<SystemTime>,
 }
 
 struct RouterHandle {
@@ -117,6 +215,20 @@ struct RouterHandle {
     // pushing values in a synchronous way (without an `.await`), which is
     // needed for pushing values in `Drop` implementations.
     orphan_notification_sender: mpsc::UnboundedSender<RequestId>,
+    // Number of requests sent through this handle which are still awaiting their responses.
+    // Used to wait for in-flight requests when draining a node.
+    in_flight_requests: AtomicUsize,
+    // Bounds the number of user requests concurrently sent on the connection, if configured.
+    // Requests exceeding the limit wait for a permit before being submitted to the router.
+    // Requests issued by the driver itself (e.g. keepalives) are exempt, see `InFlightLimiting`.
+    in_flight_limit: Option<Semaphore>,
+    // Protocol version negotiated for the connection.
+    protocol_version: ProtocolVersion,
+    // Since protocol v5, frames are wrapped into segments once STARTUP is answered.
+    // Set by the router upon receiving READY or AUTHENTICATE.
+    segments_enabled: AtomicBool,
+    // Whether segments are compressed with LZ4. Set during the handshake, before STARTUP is sent.
+    segments_lz4: AtomicBool,
 }
 
 impl RouterHandle {
@@ -128,11 +240,66 @@ impl RouterHandle {
     async fn send_request(
         &self,
         request: &impl SerializableRequest,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        compression: Option<Compression>,
+        compression_threshold: usize,
+        tracing: bool,
+        in_flight_limiting: InFlightLimiting,
+    ) -> Result<TaskResponse, InternalRequestError> {
+        let serialized_request = self.serialize_request(
+            request,
+            custom_payload,
+            compression,
+            compression_threshold,
+            tracing,
+        )?;
+        self.send_serialized_request(serialized_request, in_flight_limiting)
+            .await
+    }
+
+    fn serialize_request(
+        &self,
+        request: &impl SerializableRequest,
+        custom_payload: Option<&HashMap<String, Bytes>>,
         compression: Option<Compression>,
+        compression_threshold: usize,
         tracing: bool,
+    ) -> Result<SerializedRequest, InternalRequestError> {
+        let serialized_request = match custom_payload {
+            Some(custom_payload) => SerializedRequest::make_with_custom_payload(
+                request,
+                self.protocol_version,
+                custom_payload,
+                compression,
+                compression_threshold,
+                tracing,
+            )?,
+            None => SerializedRequest::make_for_version(
+                request,
+                self.protocol_version,
+                compression,
+                compression_threshold,
+                tracing,
+            )?,
+        };
+        Ok(serialized_request)
+    }
+
+    async fn send_serialized_request(
+        &self,
+        serialized_request: SerializedRequest,
+        in_flight_limiting: InFlightLimiting,
     ) -> Result<TaskResponse, InternalRequestError> {
-        let serialized_request = SerializedRequest::make(request, compression, tracing)?;
         let request_id = self.allocate_request_id();
+        let _in_flight_permit = match (&self.in_flight_limit, in_flight_limiting) {
+            (Some(semaphore), InFlightLimiting::Apply) => Some(semaphore.acquire().await.map_err(
+                |_| -> BrokenConnectionError { BrokenConnectionErrorKind::ChannelError.into() },
+            )?),
+            _ => None,
+        };
+        // Counted only once admitted, so that requests waiting for a permit
+        // don't overstate the load of the connection.
+        let _in_flight_guard = InFlightRequestGuard::new(&self.in_flight_requests);
 
         let (response_sender, receiver) = oneshot::channel();
         let response_handler = ResponseHandler {
@@ -167,11 +334,24 @@ impl RouterHandle {
     }
 }
 
+/// Whether a request is subject to the per-connection in-flight limit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum InFlightLimiting {
+    /// The request waits for a permit if the connection is saturated.
+    /// Used for requests executing user statements.
+    Apply,
+    /// The request bypasses the limit. Used for keepalives, the handshake
+    /// and other requests issued by the driver itself, so that they are not
+    /// starved by slow user requests.
+    Exempt,
+}
+
 #[derive(Default)]
 pub(crate) struct ConnectionFeatures {
     shard_info: Option<ShardInfo>,
     shard_aware_port: Option<u16>,
     protocol_features: ProtocolFeatures,
+    supported_options: Arc<HashMap<String, Vec<String>>>,
 }
 
 type RequestId = u64;
@@ -214,6 +394,26 @@ impl Drop for OrphanhoodNotifier<'_> {
     }
 }
 
+// Counts a request as in flight for as long as it is alive,
+// including when the request future is dropped before completion.
+struct InFlightRequestGuard<'a> {
+    counter: &'a AtomicUsize,
+}
+
+impl<'a> InFlightRequestGuard<'a> {
+    fn new(counter: &'a AtomicUsize) -> Self {
+        counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self { counter }
+    }
+}
+
+impl Drop for InFlightRequestGuard<'_> {
+    fn drop(&mut self) {
+        self.counter
+            .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
 struct Task {
     serialized_request: SerializedRequest,
     response_handler: ResponseHandler,
@@ -276,10 +476,18 @@ pub(crate) struct ConnectionConfig {
     pub(crate) local_ip_address: Option<IpAddr>,
     pub(crate) shard_aware_local_port_range: ShardAwarePortRange,
     pub(crate) compression: Option<Compression>,
+    // Requests with smaller bodies are sent uncompressed.
+    pub(crate) compression_threshold: usize,
     pub(crate) tcp_nodelay: bool,
     pub(crate) tcp_keepalive_interval: Option<Duration>,
+    pub(crate) tcp_keepalive_probe_interval: Duration,
+    pub(crate) tcp_keepalive_probe_count: u32,
+    pub(crate) tcp_user_timeout: Option<Duration>,
+    pub(crate) tcp_send_buffer_size: Option<u32>,
+    pub(crate) tcp_recv_buffer_size: Option<u32>,
     pub(crate) timestamp_generator: Option<Arc<dyn TimestampGenerator>>,
     pub(crate) tls_provider: Option<TlsProvider>,
+    pub(crate) tls_certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
     pub(crate) connect_timeout: std::time::Duration,
     // should be Some only in control connections,
     pub(crate) event_sender: Option<mpsc::Sender<Event>>,
@@ -292,7 +500,21 @@ pub(crate) struct ConnectionConfig {
     pub(crate) keepalive_timeout: Option<Duration>,
     pub(crate) tablet_sender: Option<mpsc::Sender<(TableSpec<'static>, RawTablet)>>,
 
+    pub(crate) protocol_negotiation: ProtocolNegotiationPolicy,
     pub(crate) identity: SelfIdentity<'static>,
+
+    // Maximum number of requests sent concurrently on a single connection.
+    pub(crate) max_inflight_requests: Option<NonZeroUsize>,
+    // Whether warnings returned by the database are logged.
+    pub(crate) log_server_warnings: bool,
+    // Whether the OpenTelemetry context is injected into the custom payload of requests.
+    #[cfg(feature = "opentelemetry-031")]
+    pub(crate) opentelemetry_propagation: bool,
+    // Notified about statements reprepared after an `Unprepared` error.
+    pub(crate) repreparation_listener: Option<Arc<dyn RepreparationListener>>,
+    // Counts repreparations; `None` if the connection is not owned by a session.
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: Option<Arc<Metrics>>,
 }
 
 impl ConnectionConfig {
@@ -304,14 +526,23 @@ impl ConnectionConfig {
         let tls_config = self
             .tls_provider
             .as_ref()
-            .and_then(|provider| provider.make_tls_config(endpoint));
+            .and_then(|provider| provider.make_tls_config(endpoint))
+            .map(|tls_config| {
+                tls_config.with_certificate_verifier(self.tls_certificate_verifier.clone())
+            });
 
         HostConnectionConfig {
             local_ip_address: self.local_ip_address,
             shard_aware_local_port_range: self.shard_aware_local_port_range.clone(),
             compression: self.compression,
+            compression_threshold: self.compression_threshold,
             tcp_nodelay: self.tcp_nodelay,
             tcp_keepalive_interval: self.tcp_keepalive_interval,
+            tcp_keepalive_probe_interval: self.tcp_keepalive_probe_interval,
+            tcp_keepalive_probe_count: self.tcp_keepalive_probe_count,
+            tcp_user_timeout: self.tcp_user_timeout,
+            tcp_send_buffer_size: self.tcp_send_buffer_size,
+            tcp_recv_buffer_size: self.tcp_recv_buffer_size,
             timestamp_generator: self.timestamp_generator.clone(),
             tls_config,
             connect_timeout: self.connect_timeout,
@@ -324,6 +555,14 @@ impl ConnectionConfig {
             keepalive_timeout: self.keepalive_timeout,
             tablet_sender: self.tablet_sender.clone(),
             identity: self.identity.clone(),
+            protocol_negotiation: self.protocol_negotiation.clone(),
+            max_inflight_requests: self.max_inflight_requests,
+            log_server_warnings: self.log_server_warnings,
+            #[cfg(feature = "opentelemetry-031")]
+            opentelemetry_propagation: self.opentelemetry_propagation,
+            repreparation_listener: self.repreparation_listener.clone(),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics.clone(),
         }
     }
 }
@@ -336,8 +575,15 @@ pub(crate) struct HostConnectionConfig {
     pub(crate) local_ip_address: Option<IpAddr>,
     pub(crate) shard_aware_local_port_range: ShardAwarePortRange,
     pub(crate) compression: Option<Compression>,
+    // Requests with smaller bodies are sent uncompressed.
+    pub(crate) compression_threshold: usize,
     pub(crate) tcp_nodelay: bool,
     pub(crate) tcp_keepalive_interval: Option<Duration>,
+    pub(crate) tcp_keepalive_probe_interval: Duration,
+    pub(crate) tcp_keepalive_probe_count: u32,
+    pub(crate) tcp_user_timeout: Option<Duration>,
+    pub(crate) tcp_send_buffer_size: Option<u32>,
+    pub(crate) tcp_recv_buffer_size: Option<u32>,
     pub(crate) timestamp_generator: Option<Arc<dyn TimestampGenerator>>,
     pub(crate) tls_config: Option<TlsConfig>,
     pub(crate) connect_timeout: std::time::Duration,
@@ -352,7 +598,21 @@ pub(crate) struct HostConnectionConfig {
     pub(crate) keepalive_timeout: Option<Duration>,
     pub(crate) tablet_sender: Option<mpsc::Sender<(TableSpec<'static>, RawTablet)>>,
 
+    pub(crate) protocol_negotiation: ProtocolNegotiationPolicy,
     pub(crate) identity: SelfIdentity<'static>,
+
+    // Maximum number of requests sent concurrently on a single connection.
+    pub(crate) max_inflight_requests: Option<NonZeroUsize>,
+    // Whether warnings returned by the database are logged.
+    pub(crate) log_server_warnings: bool,
+    // Whether the OpenTelemetry context is injected into the custom payload of requests.
+    #[cfg(feature = "opentelemetry-031")]
+    pub(crate) opentelemetry_propagation: bool,
+    // Notified about statements reprepared after an `Unprepared` error.
+    pub(crate) repreparation_listener: Option<Arc<dyn RepreparationListener>>,
+    // Counts repreparations; `None` if the connection is not owned by a session.
+    #[cfg(feature = "metrics")]
+    pub(crate) metrics: Option<Arc<Metrics>>,
 }
 
 #[cfg(test)]
@@ -362,8 +622,14 @@ impl Default for HostConnectionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_nodelay: true,
             tcp_keepalive_interval: None,
+            tcp_keepalive_probe_interval: Duration::from_secs(1),
+            tcp_keepalive_probe_count: 10,
+            tcp_user_timeout: None,
+            tcp_send_buffer_size: None,
+            tcp_recv_buffer_size: None,
             timestamp_generator: None,
             event_sender: None,
             tls_config: None,
@@ -380,6 +646,14 @@ impl Default for HostConnectionConfig {
             tablet_sender: None,
 
             identity: SelfIdentity::default(),
+            protocol_negotiation: ProtocolNegotiationPolicy::default(),
+            max_inflight_requests: None,
+            log_server_warnings: true,
+            #[cfg(feature = "opentelemetry-031")]
+            opentelemetry_propagation: false,
+            repreparation_listener: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -391,11 +665,18 @@ impl Default for ConnectionConfig {
             local_ip_address: None,
             shard_aware_local_port_range: ShardAwarePortRange::EPHEMERAL_PORT_RANGE,
             compression: None,
+            compression_threshold: 0,
             tcp_nodelay: true,
             tcp_keepalive_interval: None,
+            tcp_keepalive_probe_interval: Duration::from_secs(1),
+            tcp_keepalive_probe_count: 10,
+            tcp_user_timeout: None,
+            tcp_send_buffer_size: None,
+            tcp_recv_buffer_size: None,
             timestamp_generator: None,
             event_sender: None,
             tls_provider: None,
+            tls_certificate_verifier: None,
             connect_timeout: std::time::Duration::from_secs(5),
             default_consistency: Default::default(),
             authenticator: None,
@@ -409,6 +690,14 @@ impl Default for ConnectionConfig {
             tablet_sender: None,
 
             identity: SelfIdentity::default(),
+            protocol_negotiation: ProtocolNegotiationPolicy::default(),
+            max_inflight_requests: None,
+            log_server_warnings: true,
+            #[cfg(feature = "opentelemetry-031")]
+            opentelemetry_propagation: false,
+            repreparation_listener: None,
+            #[cfg(feature = "metrics")]
+            metrics: None,
         }
     }
 }
@@ -430,10 +719,17 @@ impl Connection {
         connect_address: SocketAddr,
         source_port: Option<u16>,
         config: HostConnectionConfig,
+        protocol_version: ProtocolVersion,
     ) -> Result<(Self, ErrorReceiver), ConnectionError> {
         let stream_connector = tokio::time::timeout(
             config.connect_timeout,
-            connect_with_source_ip_and_port(connect_address, config.local_ip_address, source_port),
+            connect_with_source_ip_and_port(
+                connect_address,
+                config.local_ip_address,
+                source_port,
+                config.tcp_send_buffer_size,
+                config.tcp_recv_buffer_size,
+            ),
         )
         .await;
         let stream = match stream_connector {
@@ -445,7 +741,13 @@ impl Connection {
         stream.set_nodelay(config.tcp_nodelay)?;
 
         if let Some(tcp_keepalive_interval) = config.tcp_keepalive_interval {
-            Self::setup_tcp_keepalive(&stream, tcp_keepalive_interval)?;
+            Self::setup_tcp_keepalive(&stream, tcp_keepalive_interval, &config)?;
+        }
+
+        // TCP_USER_TIMEOUT is available on these platforms only; see also socket2.
+        #[cfg(any(target_os = "android", target_os = "fuchsia", target_os = "linux"))]
+        if let Some(tcp_user_timeout) = config.tcp_user_timeout {
+            SockRef::from(&stream).set_tcp_user_timeout(Some(tcp_user_timeout))?;
         }
 
         // TODO: What should be the size of the channel?
@@ -458,6 +760,13 @@ impl Connection {
             submit_channel: sender,
             request_id_generator: AtomicU64::new(0),
             orphan_notification_sender,
+            in_flight_requests: AtomicUsize::new(0),
+            in_flight_limit: config
+                .max_inflight_requests
+                .map(|limit| Semaphore::new(limit.get())),
+            protocol_version,
+            segments_enabled: AtomicBool::new(false),
+            segments_lz4: AtomicBool::new(false),
         });
 
         let _worker_handle = Self::run_router(
@@ -477,6 +786,7 @@ impl Connection {
             features: Default::default(),
             connect_address,
             router_handle,
+            credentials_expiry: None,
         };
 
         Ok((connection, error_receiver))
@@ -485,6 +795,7 @@ impl Connection {
     fn setup_tcp_keepalive(
         stream: &TcpStream,
         tcp_keepalive_interval: Duration,
+        config: &HostConnectionConfig,
     ) -> std::io::Result<()> {
         // It may be surprising why we call `with_time()` with `tcp_keepalive_interval`
         // and `with_interval() with some other value. This is due to inconsistent naming:
@@ -510,7 +821,7 @@ impl Connection {
             target_os = "windows",
         ))]
         {
-            tcp_keepalive = tcp_keepalive.with_interval(Duration::from_secs(1));
+            tcp_keepalive = tcp_keepalive.with_interval(config.tcp_keepalive_probe_interval);
         }
 
         #[cfg(any(
@@ -527,7 +838,7 @@ impl Connection {
             target_os = "watchos",
         ))]
         {
-            tcp_keepalive = tcp_keepalive.with_retries(10);
+            tcp_keepalive = tcp_keepalive.with_retries(config.tcp_keepalive_probe_count);
         }
 
         let sf = SockRef::from(&stream);
@@ -639,16 +950,33 @@ impl Connection {
         &self,
         statement: &'statement Statement,
     ) -> Result<RawPreparedStatement<'statement>, RequestAttemptError> {
-        let query_response = self
-            .send_request(
-                &request::Prepare {
-                    query: &statement.contents,
-                },
-                true,
-                statement.config.tracing,
-                None,
-            )
-            .await?;
+        let prepare_frame = request::Prepare {
+            query: &statement.contents,
+        };
+        let query_response = match statement.get_keyspace() {
+            Some(keyspace) => {
+                self.send_request_with_custom_payload(
+                    &request::WithKeyspace::new(&prepare_frame, keyspace),
+                    statement.get_custom_payload(),
+                    true,
+                    statement.config.tracing,
+                    None,
+                    InFlightLimiting::Exempt,
+                )
+                .await?
+            }
+            None => {
+                self.send_request_with_custom_payload(
+                    &prepare_frame,
+                    statement.get_custom_payload(),
+                    true,
+                    statement.config.tracing,
+                    None,
+                    InFlightLimiting::Exempt,
+                )
+                .await?
+            }
+        };
 
         match query_response.response {
             Response::Error(error::Error { error, reason }) => {
@@ -688,7 +1016,34 @@ impl Connection {
         query: impl Into<Statement>,
         previous_prepared: &PreparedStatement,
     ) -> Result<(), RequestAttemptError> {
-        let reprepare_query: Statement = query.into();
+        let start = Instant::now();
+        let result = self.reprepare_inner(query, previous_prepared).await;
+
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.config.metrics {
+            metrics.inc_repreparations_num();
+        }
+        if let Some(listener) = &self.config.repreparation_listener {
+            listener.on_repreparation(&RepreparationEvent {
+                statement_id: previous_prepared.get_id().clone(),
+                node_address: self.connect_address,
+                latency: start.elapsed(),
+                succeeded: result.is_ok(),
+            });
+        }
+
+        result
+    }
+
+    async fn reprepare_inner(
+        &self,
+        query: impl Into<Statement>,
+        previous_prepared: &PreparedStatement,
+    ) -> Result<(), RequestAttemptError> {
+        let mut reprepare_query: Statement = query.into();
+        if let Some(keyspace) = previous_prepared.get_keyspace() {
+            reprepare_query.set_keyspace(keyspace);
+        }
         let prepared_response = self.prepare_raw(&reprepare_query).await?.prepared_response;
 
         // Reprepared statement should keep its id - it's the md5 sum
@@ -742,6 +1097,7 @@ impl Connection {
                                 .map_err(|e| {
                                     err(ConnectionSetupRequestErrorKind::AuthFinishError(e))
                                 })?;
+                            self.credentials_expiry = auth_session.credentials_expiry();
                             break;
                         }
                     }
@@ -761,9 +1117,13 @@ impl Connection {
             ConnectionSetupRequestError::new(CqlRequestKind::AuthResponse, kind)
         };
 
-        let req_result = self
-            .send_request(&request::AuthResponse { response }, false, false, None)
-            .await;
+        let request = request::AuthResponse { response };
+        let req_result = self.send_request(&request, false, false, None).await;
+        // The response may carry credentials.
+        #[cfg(feature = "secrecy-08")]
+        if let Some(mut response) = request.response {
+            secrecy_08::Zeroize::zeroize(&mut response);
+        }
 
         // Extract non-error response to AUTH_RESPONSE request and tidy up errors.
         let response = match req_result {
@@ -839,6 +1199,7 @@ impl Connection {
             statement.config.serial_consistency.flatten(),
             None,
             PagingState::start(),
+            InFlightLimiting::Exempt,
         )
         .await
     }
@@ -850,6 +1211,7 @@ impl Connection {
         serial_consistency: Option<SerialConsistency>,
         page_size: Option<PageSize>,
         paging_state: PagingState,
+        in_flight_limiting: InFlightLimiting,
     ) -> Result<QueryResponse, RequestAttemptError> {
         let get_timestamp_from_gen = || {
             self.config
@@ -872,9 +1234,31 @@ impl Connection {
             },
         };
 
-        let response = self
-            .send_request(&query_frame, true, statement.config.tracing, None)
-            .await?;
+        let compress = !statement.config.skip_compression;
+        let response = match statement.get_keyspace() {
+            Some(keyspace) => {
+                self.send_request_with_custom_payload(
+                    &request::WithKeyspace::new(&query_frame, keyspace),
+                    statement.get_custom_payload(),
+                    compress,
+                    statement.config.tracing,
+                    None,
+                    in_flight_limiting,
+                )
+                .await?
+            }
+            None => {
+                self.send_request_with_custom_payload(
+                    &query_frame,
+                    statement.get_custom_payload(),
+                    compress,
+                    statement.config.tracing,
+                    None,
+                    in_flight_limiting,
+                )
+                .await?
+            }
+        };
 
         Ok(response)
     }
@@ -895,10 +1279,12 @@ impl Connection {
             prepared.config.serial_consistency.flatten(),
             None,
             PagingState::start(),
+            InFlightLimiting::Exempt,
         )
         .await
     }
 
+    #[expect(clippy::too_many_arguments)]
     pub(crate) async fn execute_raw_with_consistency(
         &self,
         prepared_statement: &PreparedStatement,
@@ -907,6 +1293,7 @@ impl Connection {
         serial_consistency: Option<SerialConsistency>,
         page_size: Option<PageSize>,
         paging_state: PagingState,
+        in_flight_limiting: InFlightLimiting,
     ) -> Result<QueryResponse, RequestAttemptError> {
         let get_timestamp_from_gen = || {
             self.config
@@ -930,17 +1317,24 @@ impl Connection {
                 paging_state,
             },
         };
+        let result_metadata_id = prepared_statement
+            .get_result_metadata_id()
+            .map_or(&[][..], |id| &id[..]);
+        let execute_frame =
+            execute::ExecuteWithResultMetadataId::new(&execute_frame, result_metadata_id);
 
         let cached_metadata = prepared_statement
             .get_use_cached_result_metadata()
             .then(|| prepared_statement.get_result_metadata());
 
         let query_response = self
-            .send_request(
+            .send_request_with_custom_payload(
                 &execute_frame,
-                true,
+                prepared_statement.get_custom_payload(),
+                !prepared_statement.config.skip_compression,
                 prepared_statement.config.tracing,
                 cached_metadata,
+                in_flight_limiting,
             )
             .await?;
 
@@ -963,11 +1357,13 @@ impl Connection {
                 self.reprepare(prepared_statement.get_statement(), prepared_statement)
                     .await?;
                 let new_response = self
-                    .send_request(
+                    .send_request_with_custom_payload(
                         &execute_frame,
-                        true,
+                        prepared_statement.get_custom_payload(),
+                        !prepared_statement.config.skip_compression,
                         prepared_statement.config.tracing,
                         cached_metadata,
+                        in_flight_limiting,
                     )
                     .await?;
 
@@ -1062,7 +1458,14 @@ impl Connection {
 
         loop {
             let query_response = self
-                .send_request(&batch_frame, true, batch.config.tracing, None)
+                .send_request_with_custom_payload(
+                    &batch_frame,
+                    batch.get_custom_payload(),
+                    !batch.config.skip_compression,
+                    batch.config.tracing,
+                    None,
+                    InFlightLimiting::Apply,
+                )
                 .await
                 .map_err(RequestAttemptError::from)?;
 
@@ -1239,12 +1642,83 @@ impl Connection {
         Ok(version_id)
     }
 
+    /// Fetches the ScyllaDB version of the node this connection is open to.
+    /// Returns `None` for nodes which are not ScyllaDB nodes.
+    pub(crate) async fn fetch_scylla_version(&self) -> Result<Option<String>, ScyllaVersionError> {
+        let result = match self.query_unpaged(LOCAL_SCYLLA_VERSION).await {
+            Ok(result) => result,
+            // Cassandra rejects the query, as it has no `system.versions` table.
+            Err(RequestAttemptError::DbError(DbError::Invalid, _)) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let (version,) = result
+            .into_rows_result()
+            .map_err(ScyllaVersionError::IntoRowsResultError)?
+            .single_row::<(Option<String>,)>()
+            .map_err(ScyllaVersionError::SingleRowError)?;
+
+        Ok(version)
+    }
+
+    #[cfg(feature = "unstable-raw-requests")]
+    pub(crate) async fn send_raw_request(
+        &self,
+        request: &crate::client::raw_request::RawRequest,
+    ) -> Result<crate::client::raw_request::RawResponse, InternalRequestError> {
+        let serialized_request = SerializedRequest::make_raw(
+            request.opcode,
+            self.router_handle.protocol_version,
+            &request.body,
+            self.config.compression,
+            self.config.compression_threshold,
+            request.tracing,
+        )?;
+        let task_response = self
+            .router_handle
+            .send_serialized_request(serialized_request, InFlightLimiting::Apply)
+            .await?;
+
+        let body_with_ext = frame::parse_response_body_extensions(
+            task_response.params.flags,
+            self.config.compression,
+            task_response.body,
+        )?;
+
+        Ok(crate::client::raw_request::RawResponse {
+            opcode: task_response.opcode,
+            body: body_with_ext.body,
+            tracing_id: body_with_ext.trace_id,
+            warnings: body_with_ext.warnings,
+            custom_payload: body_with_ext.custom_payload,
+        })
+    }
+
     async fn send_request(
         &self,
         request: &impl SerializableRequest,
         compress: bool,
         tracing: bool,
         cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+    ) -> Result<QueryResponse, InternalRequestError> {
+        self.send_request_with_custom_payload(
+            request,
+            None,
+            compress,
+            tracing,
+            cached_metadata,
+            InFlightLimiting::Exempt,
+        )
+        .await
+    }
+
+    async fn send_request_with_custom_payload(
+        &self,
+        request: &impl SerializableRequest,
+        custom_payload: Option<&HashMap<String, Bytes>>,
+        compress: bool,
+        tracing: bool,
+        cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
+        in_flight_limiting: InFlightLimiting,
     ) -> Result<QueryResponse, InternalRequestError> {
         let compression = if compress {
             self.config.compression
@@ -1252,18 +1726,50 @@ impl Connection {
             None
         };
 
+        #[cfg(feature = "opentelemetry-031")]
+        let payload_with_context = self
+            .config
+            .opentelemetry_propagation
+            .then(|| crate::observability::opentelemetry::inject_current_context(custom_payload))
+            .flatten();
+        #[cfg(feature = "opentelemetry-031")]
+        let custom_payload = payload_with_context.as_ref().or(custom_payload);
+
+        let serialized_request = self.router_handle.serialize_request(
+            request,
+            custom_payload,
+            compression,
+            self.config.compression_threshold,
+            tracing,
+        )?;
+        #[cfg(feature = "metrics")]
+        let request_size = serialized_request.get_data().len();
         let task_response = self
             .router_handle
-            .send_request(request, compression, tracing)
+            .send_serialized_request(serialized_request, in_flight_limiting)
             .await?;
+        #[cfg(feature = "metrics")]
+        if let Some(metrics) = &self.config.metrics {
+            metrics.log_frame_sizes(request_size, FRAME_HEADER_SIZE + task_response.body.len());
+        }
 
         let response = Self::parse_response(
             task_response,
             self.config.compression,
             &self.features.protocol_features,
+            self.router_handle.protocol_version,
             cached_metadata,
         )?;
 
+        if self.config.log_server_warnings {
+            for warn_description in &response.warnings {
+                warn!(
+                    warning = warn_description.as_str(),
+                    "Response from the database contains a warning",
+                );
+            }
+        }
+
         Ok(response)
     }
 
@@ -1271,6 +1777,7 @@ impl Connection {
         task_response: TaskResponse,
         compression: Option<Compression>,
         features: &ProtocolFeatures,
+        protocol_version: ProtocolVersion,
         cached_metadata: Option<&Arc<ResultMetadata<'static>>>,
     ) -> Result<QueryResponse, ResponseParseError> {
         let body_with_ext = frame::parse_response_body_extensions(
@@ -1279,15 +1786,9 @@ impl Connection {
             task_response.body,
         )?;
 
-        for warn_description in &body_with_ext.warnings {
-            warn!(
-                warning = warn_description.as_str(),
-                "Response from the database contains a warning",
-            );
-        }
-
-        let response = Response::deserialize(
+        let response = Response::deserialize_for_version(
             features,
+            protocol_version,
             task_response.opcode,
             body_with_ext.body,
             cached_metadata,
@@ -1345,6 +1846,7 @@ impl Connection {
                         .connect()
                         .await
                         .map_err(std::io::Error::other)?;
+                    tls_config.verify_openssl_peer(node_address, stream.ssl())?;
                     return Ok(spawn_router_and_get_handle(
                         config,
                         stream,
@@ -1369,6 +1871,7 @@ impl Connection {
                     #[cfg(not(feature = "unstable-cloud"))]
                     let server_name = ServerName::IpAddress(node_address.into());
                     let stream = connector.connect(server_name, stream).await?;
+                    tls_config.verify_rustls_peer(node_address, stream.get_ref().1)?;
                     return Ok(spawn_router_and_get_handle(
                         config,
                         stream,
@@ -1422,7 +1925,7 @@ impl Connection {
         let write_coalescing_delay = config.write_coalescing_delay;
 
         let k = Self::keepaliver(
-            router_handle,
+            router_handle.clone(),
             config.keepalive_interval,
             config.keepalive_timeout,
             node_address,
@@ -1431,14 +1934,17 @@ impl Connection {
         let r = Self::reader(
             BufReader::with_capacity(8192, read_half),
             &handler_map,
+            &router_handle,
             config.event_sender,
             config.compression,
         );
         let w = Self::writer(
             BufWriter::with_capacity(8192, write_half),
             &handler_map,
+            &router_handle,
             receiver,
             write_coalescing_delay,
+            config.compression_threshold,
         );
         let o = Self::orphaner(&handler_map, orphan_notification_receiver);
 
@@ -1465,13 +1971,38 @@ impl Connection {
     async fn reader(
         mut read_half: (impl AsyncRead + Unpin),
         handler_map: &StdMutex<ResponseHandlerMap>,
+        router_handle: &RouterHandle,
         event_sender: Option<mpsc::Sender<Event>>,
         compression: Option<Compression>,
     ) -> Result<(), BrokenConnectionError> {
+        let protocol_version = router_handle.protocol_version;
+        // Set once the frames start being wrapped into segments (protocol v5+).
+        let mut segmented_reader: Option<SegmentedFrameReader> = None;
         loop {
-            let (params, opcode, body) = frame::read_response_frame(&mut read_half)
-                .await
-                .map_err(BrokenConnectionErrorKind::FrameHeaderParseError)?;
+            let read_result = match segmented_reader.as_mut() {
+                Some(segmented_reader) => {
+                    segmented_reader.read_response_frame(&mut read_half).await
+                }
+                None => frame::read_response_frame(&mut read_half).await,
+            };
+            let (params, opcode, body) =
+                read_result.map_err(BrokenConnectionErrorKind::FrameHeaderParseError)?;
+
+            // The server switches to segments right after READY or AUTHENTICATE,
+            // so all further frames (including AUTH_RESPONSE) are wrapped into segments.
+            if protocol_version >= ProtocolVersion::V5
+                && segmented_reader.is_none()
+                && matches!(opcode, ResponseOpcode::Ready | ResponseOpcode::Authenticate)
+            {
+                let lz4 = router_handle
+                    .segments_lz4
+                    .load(std::sync::atomic::Ordering::Relaxed);
+                segmented_reader = Some(SegmentedFrameReader::new(lz4));
+                router_handle
+                    .segments_enabled
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
+
             let response = TaskResponse {
                 params,
                 opcode,
@@ -1487,7 +2018,7 @@ impl Connection {
                 }
                 Ordering::Equal => {
                     if let Some(event_sender) = event_sender.as_ref() {
-                        Self::handle_event(response, compression, event_sender)
+                        Self::handle_event(response, compression, protocol_version, event_sender)
                             .await
                             .map_err(BrokenConnectionErrorKind::CqlEventHandlingError)?
                     }
@@ -1550,25 +2081,70 @@ impl Connection {
     async fn writer(
         mut write_half: (impl AsyncWrite + Unpin),
         handler_map: &StdMutex<ResponseHandlerMap>,
+        router_handle: &RouterHandle,
         mut task_receiver: mpsc::Receiver<Task>,
         write_coalescing_delay: Option<WriteCoalescingDelay>,
+        compression_threshold: usize,
     ) -> Result<(), BrokenConnectionError> {
+        // Set once the frames start being wrapped into segments (protocol v5+).
+        let mut segment_encoder: Option<SegmentEncoder> = None;
+        let mut segments_buf = Vec::new();
+
         // When the Connection object is dropped, the sender half
         // of the channel will be dropped, this task will return an error
         // and the whole worker will be stopped
         while let Some(mut task) = task_receiver.recv().await {
+            if segment_encoder.is_none()
+                && router_handle
+                    .segments_enabled
+                    .load(std::sync::atomic::Ordering::Relaxed)
+            {
+                let lz4 = router_handle
+                    .segments_lz4
+                    .load(std::sync::atomic::Ordering::Relaxed);
+                segment_encoder = Some(SegmentEncoder::new(lz4, compression_threshold));
+            }
+
             let mut num_requests = 0;
             let mut total_sent = 0;
-            while let Some(stream_id) = Self::alloc_stream_id(handler_map, task.response_handler) {
-                let mut req = task.serialized_request;
-                req.set_stream(stream_id);
-                let req_data: &[u8] = req.get_data();
-                total_sent += req_data.len();
-                num_requests += 1;
-                write_half
-                    .write_all(req_data)
-                    .await
-                    .map_err(BrokenConnectionErrorKind::WriteError)?;
+            // Whether an AUTH_RESPONSE frame was copied into the segment buffers.
+            #[cfg(feature = "secrecy-08")]
+            let mut sent_auth_response = false;
+            loop {
+                // If the requester has already given up waiting (e.g. a losing speculative
+                // attempt has been cancelled), don't burden the server with the request.
+                if task.response_handler.response_sender.is_closed() {
+                    trace!(
+                        "Skipping request_id = {} cancelled before being sent",
+                        task.response_handler.request_id
+                    );
+                } else {
+                    let Some(stream_id) = Self::alloc_stream_id(handler_map, task.response_handler)
+                    else {
+                        break;
+                    };
+                    let mut req = task.serialized_request;
+                    req.set_stream(stream_id);
+                    #[cfg(feature = "secrecy-08")]
+                    {
+                        sent_auth_response |= req.is_auth_response();
+                    }
+                    let req_data: &[u8] = req.get_data();
+                    total_sent += req_data.len();
+                    num_requests += 1;
+                    let data_to_write = match segment_encoder.as_mut() {
+                        Some(segment_encoder) => {
+                            segments_buf.clear();
+                            segment_encoder.push_frame(req_data, &mut segments_buf);
+                            &segments_buf[..]
+                        }
+                        None => req_data,
+                    };
+                    write_half
+                        .write_all(data_to_write)
+                        .await
+                        .map_err(BrokenConnectionErrorKind::WriteError)?;
+                }
                 task = match task_receiver.try_recv() {
                     Ok(t) => t,
                     Err(_) => match write_coalescing_delay {
@@ -1593,6 +2169,20 @@ impl Connection {
                     },
                 }
             }
+            if let Some(segment_encoder) = segment_encoder.as_mut() {
+                segments_buf.clear();
+                segment_encoder.flush(&mut segments_buf);
+                write_half
+                    .write_all(&segments_buf)
+                    .await
+                    .map_err(BrokenConnectionErrorKind::WriteError)?;
+
+                #[cfg(feature = "secrecy-08")]
+                if sent_auth_response {
+                    secrecy_08::Zeroize::zeroize(&mut segments_buf);
+                    secrecy_08::Zeroize::zeroize(segment_encoder);
+                }
+            }
             trace!("Sending {} requests; {} bytes", num_requests, total_sent);
             write_half
                 .flush()
@@ -1652,7 +2242,7 @@ impl Connection {
             router_handle: &RouterHandle,
         ) -> Result<(), BrokenConnectionError> {
             router_handle
-                .send_request(&Options, None, false)
+                .send_request(&Options, None, None, 0, false, InFlightLimiting::Exempt)
                 .await
                 .map(|_| ())
                 .map_err(|req_err| {
@@ -1709,6 +2299,7 @@ impl Connection {
     async fn handle_event(
         task_response: TaskResponse,
         compression: Option<Compression>,
+        protocol_version: ProtocolVersion,
         event_sender: &mpsc::Sender<Event>,
     ) -> Result<(), CqlEventHandlingError> {
         // Protocol features are negotiated during connection handshake.
@@ -1722,7 +2313,13 @@ impl Connection {
         // future implementers.
         let features = ProtocolFeatures::default(); // TODO: Use the right features
 
-        let event = match Self::parse_response(task_response, compression, &features, None) {
+        let event = match Self::parse_response(
+            task_response,
+            compression,
+            &features,
+            protocol_version,
+            None,
+        ) {
             Ok(r) => match r.response {
                 Response::Event(event) => event,
                 _ => {
@@ -1760,6 +2357,10 @@ impl Connection {
         self.features.shard_aware_port
     }
 
+    pub(crate) fn get_supported_options(&self) -> Arc<HashMap<String, Vec<String>>> {
+        Arc::clone(&self.features.supported_options)
+    }
+
     fn set_features(&mut self, features: ConnectionFeatures) {
         self.features = features;
     }
@@ -1768,6 +2369,26 @@ impl Connection {
         self.connect_address
     }
 
+    /// Returns the number of requests sent on this connection which still await their responses.
+    pub(crate) fn in_flight_requests(&self) -> usize {
+        self.router_handle
+            .in_flight_requests
+            .load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Returns true if the number of in-flight requests reached the configured
+    /// per-connection limit, so that new requests sent on this connection would have to wait.
+    pub(crate) fn is_saturated(&self) -> bool {
+        self.router_handle
+            .in_flight_limit
+            .as_ref()
+            .is_some_and(|semaphore| semaphore.available_permits() == 0)
+    }
+
+    pub(crate) fn get_credentials_expiry(&self) -> Option<SystemTime> {
+        self.credentials_expiry
+    }
+
     async fn update_tablets_from_response(
         &self,
         table: &TableSpec<'_>,
@@ -1835,6 +2456,7 @@ async fn maybe_translated_addr(
 }
 
 /// Opens a connection and performs its setup on CQL level:
+/// - negotiates protocol version, according to [ProtocolNegotiationPolicy];
 /// - performs OPTIONS/STARTUP handshake (chooses desired connections options);
 /// - registers for all event types using REGISTER request (if this is control connection).
 ///
@@ -1847,14 +2469,71 @@ pub(crate) async fn open_connection(
     /* Translate the address, if applicable. */
     let addr = maybe_translated_addr(endpoint, config.address_translator.as_deref()).await?;
 
+    let mut versions = config.protocol_negotiation.versions_to_attempt().peekable();
+    while let Some(version) = versions.next() {
+        match open_connection_with_protocol_version(addr, source_port, config, version).await {
+            Err(err) if is_protocol_version_rejection(&err) => match versions.peek() {
+                Some(next_version) => {
+                    warn!(
+                        "[{}] Node rejected protocol version {}: {}. Downgrading to {}",
+                        addr, version, err, next_version
+                    );
+                }
+                None => {
+                    return Err(ConnectionError::ProtocolVersionNegotiationFailed {
+                        node: addr,
+                        last_attempted_version: version,
+                        reason: Box::new(err),
+                    });
+                }
+            },
+            result => return result,
+        }
+    }
+
+    // `ProtocolNegotiationPolicy` guarantees that there is at least one version to attempt.
+    unreachable!("No protocol version to attempt")
+}
+
+// Checks whether the error is a result of the node not supporting the protocol version
+// that the driver attempted to use.
+fn is_protocol_version_rejection(err: &ConnectionError) -> bool {
+    let ConnectionError::ConnectionSetupRequestError(setup_error) = err else {
+        return false;
+    };
+    match &setup_error.error {
+        // The node responded with an error in a frame of the version we requested.
+        ConnectionSetupRequestErrorKind::DbError(DbError::ProtocolError, _) => true,
+        // The node responded with an error in a frame of a version it supports.
+        ConnectionSetupRequestErrorKind::BrokenConnection(broken) => matches!(
+            broken.downcast_ref::<BrokenConnectionErrorKind>(),
+            Some(BrokenConnectionErrorKind::FrameHeaderParseError(
+                FrameHeaderParseError::VersionNotSupported(_)
+            ))
+        ),
+        _ => false,
+    }
+}
+
+async fn open_connection_with_protocol_version(
+    addr: SocketAddr,
+    source_port: Option<u16>,
+    config: &HostConnectionConfig,
+    protocol_version: ProtocolVersion,
+) -> Result<(Connection, ErrorReceiver), ConnectionError> {
     /* Setup connection on TCP level and prepare for sending/receiving CQL frames. */
     let (mut connection, error_receiver) =
-        Connection::new(addr, source_port, config.clone()).await?;
+        Connection::new(addr, source_port, config.clone(), protocol_version).await?;
+    debug!(
+        "[{}] Opening connection with protocol version {}",
+        addr, protocol_version
+    );
 
     /* Perform OPTIONS/SUPPORTED/STARTUP handshake. */
 
     // Get OPTIONS SUPPORTED by the cluster.
     let mut supported = connection.get_options().await?;
+    let supported_options = Arc::new(supported.options.clone());
 
     let shard_aware_port_key = match config.is_tls() {
         true => options::SCYLLA_SHARD_AWARE_PORT_SSL,
@@ -1899,6 +2578,7 @@ pub(crate) async fn open_connection(
         shard_info,
         shard_aware_port,
         protocol_features,
+        supported_options,
     };
     connection.set_features(features);
 
@@ -1918,13 +2598,27 @@ pub(crate) async fn open_connection(
     // Optional compression.
     if let Some(compression) = &config.compression {
         let compression_str = compression.as_str();
-        if supported_compression.iter().any(|c| c == compression_str) {
+        if protocol_version >= ProtocolVersion::V5 && *compression != Compression::Lz4 {
+            // Since protocol v5, segments are compressed instead of frames, and only lz4 is allowed.
+            tracing::warn!(
+                "Requested compression <{}> is not supported by protocol {}. Falling back to no compression",
+                compression_str,
+                protocol_version
+            );
+            connection.config.compression = None;
+        } else if supported_compression.iter().any(|c| c == compression_str) {
             // Compression is reported to be supported by the server,
             // request it from the server
             options.insert(
                 Cow::Borrowed(options::COMPRESSION),
                 Cow::Borrowed(compression_str),
             );
+            if protocol_version >= ProtocolVersion::V5 {
+                connection
+                    .router_handle
+                    .segments_lz4
+                    .store(true, std::sync::atomic::Ordering::Relaxed);
+            }
         } else {
             // Fall back to no compression
             tracing::warn!(
@@ -1980,30 +2674,40 @@ pub(super) async fn open_connection_to_shard_aware_port(
     Err(ConnectionError::NoSourcePortForShard(shard))
 }
 
-async fn connect_with_source_ip_and_port(
+pub(crate) async fn connect_with_source_ip_and_port(
     connect_address: SocketAddr,
     source_ip: Option<IpAddr>,
     source_port: Option<u16>,
+    send_buffer_size: Option<u32>,
+    recv_buffer_size: Option<u32>,
 ) -> Result<TcpStream, std::io::Error> {
     // Binding to port 0 is equivalent to choosing random ephemeral port.
     let source_port = source_port.unwrap_or(0);
 
-    match connect_address {
+    let (socket, source_ip) = match connect_address {
         SocketAddr::V4(_) => {
             // If source_ip not provided, bind to INADDR_ANY.
             let source_ipv4 = source_ip.unwrap_or(Ipv4Addr::UNSPECIFIED.into());
-            let socket = TcpSocket::new_v4()?;
-            socket.bind(SocketAddr::new(source_ipv4, source_port))?;
-            Ok(socket.connect(connect_address).await?)
+            (TcpSocket::new_v4()?, source_ipv4)
         }
         SocketAddr::V6(_) => {
             // If source_ip not provided, bind to in6addr_any.
             let source_ipv6 = source_ip.unwrap_or(Ipv6Addr::UNSPECIFIED.into());
-            let socket = TcpSocket::new_v6()?;
-            socket.bind(SocketAddr::new(source_ipv6, source_port))?;
-            Ok(socket.connect(connect_address).await?)
+            (TcpSocket::new_v6()?, source_ipv6)
         }
+    };
+
+    // Buffer sizes have to be set before connecting, as the TCP window scale
+    // is negotiated during the handshake.
+    if let Some(size) = send_buffer_size {
+        socket.set_send_buffer_size(size)?;
+    }
+    if let Some(size) = recv_buffer_size {
+        socket.set_recv_buffer_size(size)?;
     }
+
+    socket.bind(SocketAddr::new(source_ip, source_port))?;
+    socket.connect(connect_address).await
 }
 
 struct OrphanageTracker {
@@ -2655,4 +3359,299 @@ mod tests {
 
         let _ = proxy.finish().await;
     }
+
+    #[test]
+    fn protocol_negotiation_policy_versions() {
+        use super::ProtocolNegotiationPolicy;
+        use crate::frame::ProtocolVersion;
+
+        // Protocol v5 is not attempted by default, as ScyllaDB does not support it.
+        let default_policy = ProtocolNegotiationPolicy::default();
+        assert_eq!(default_policy.max_version(), ProtocolVersion::V4);
+        assert!(default_policy.is_downgrade_allowed());
+        assert_eq!(
+            default_policy.versions_to_attempt().collect::<Vec<_>>(),
+            vec![ProtocolVersion::V4]
+        );
+
+        let all_versions = ProtocolNegotiationPolicy::new(
+            *ProtocolVersion::ALL.first().unwrap(),
+            ProtocolVersion::LATEST,
+        );
+        assert_eq!(
+            all_versions.versions_to_attempt().collect::<Vec<_>>(),
+            ProtocolVersion::ALL
+                .iter()
+                .rev()
+                .copied()
+                .collect::<Vec<_>>()
+        );
+
+        let pinned = ProtocolNegotiationPolicy::pinned(ProtocolVersion::V4);
+        assert_eq!(
+            pinned.versions_to_attempt().collect::<Vec<_>>(),
+            vec![ProtocolVersion::V4]
+        );
+
+        let no_downgrade = all_versions.with_downgrade_allowed(false);
+        assert_eq!(
+            no_downgrade.versions_to_attempt().collect::<Vec<_>>(),
+            vec![ProtocolVersion::LATEST]
+        );
+    }
+
+    #[tokio::test]
+    async fn socket_buffer_sizes_are_set() {
+        setup_tracing();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let default_stream = super::connect_with_source_ip_and_port(addr, None, None, None, None)
+            .await
+            .unwrap();
+        let default_sock = socket2::SockRef::from(&default_stream);
+        let default_send_size = default_sock.send_buffer_size().unwrap();
+        let default_recv_size = default_sock.recv_buffer_size().unwrap();
+
+        // Request sizes smaller than the defaults, as larger ones may be capped by the system.
+        // The system may also adjust them, e.g. Linux doubles them, so only compare
+        // with the defaults.
+        let send_size = (default_send_size / 4) as u32;
+        let recv_size = (default_recv_size / 4) as u32;
+        let stream = super::connect_with_source_ip_and_port(
+            addr,
+            None,
+            None,
+            Some(send_size),
+            Some(recv_size),
+        )
+        .await
+        .unwrap();
+        let sock = socket2::SockRef::from(&stream);
+        assert_ne!(sock.send_buffer_size().unwrap(), default_send_size);
+        assert_ne!(sock.recv_buffer_size().unwrap(), default_recv_size);
+    }
+
+    #[tokio::test]
+    async fn v5_handshake_with_authentication_switches_to_segments() {
+        use super::ProtocolNegotiationPolicy;
+        use crate::authentication::PlainTextAuthenticator;
+        use crate::frame::ProtocolVersion;
+        use scylla_cql::frame::segment::{read_segment, write_segment};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        setup_tracing();
+
+        // Splits a request frame into its stream id, opcode and body.
+        fn parse_request(frame: &[u8]) -> (i16, u8, &[u8]) {
+            let stream = i16::from_be_bytes([frame[2], frame[3]]);
+            let length = u32::from_be_bytes(frame[5..9].try_into().unwrap()) as usize;
+            (stream, frame[4], &frame[9..9 + length])
+        }
+
+        fn response(stream: i16, opcode: u8, body: &[u8]) -> Vec<u8> {
+            let mut frame = vec![0x85, 0];
+            frame.extend_from_slice(&stream.to_be_bytes());
+            frame.push(opcode);
+            frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            frame.extend_from_slice(body);
+            frame
+        }
+
+        async fn read_bare_request(socket: &mut tokio::net::TcpStream) -> Vec<u8> {
+            let mut frame = vec![0u8; 9];
+            socket.read_exact(&mut frame).await.unwrap();
+            let length = u32::from_be_bytes(frame[5..9].try_into().unwrap()) as usize;
+            frame.resize(9 + length, 0);
+            socket.read_exact(&mut frame[9..]).await.unwrap();
+            frame
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            // OPTIONS and STARTUP are exchanged as bare frames.
+            let options = read_bare_request(&mut socket).await;
+            let (stream, opcode, _) = parse_request(&options);
+            assert_eq!(opcode, RequestOpcode::Options as u8);
+            // SUPPORTED with an empty string multimap.
+            socket
+                .write_all(&response(stream, 0x06, &[0, 0]))
+                .await
+                .unwrap();
+
+            let startup = read_bare_request(&mut socket).await;
+            let (stream, opcode, _) = parse_request(&startup);
+            assert_eq!(opcode, RequestOpcode::Startup as u8);
+            let authenticator = b"org.apache.cassandra.auth.PasswordAuthenticator";
+            let mut body = (authenticator.len() as u16).to_be_bytes().to_vec();
+            body.extend_from_slice(authenticator);
+            socket
+                .write_all(&response(stream, 0x03, &body))
+                .await
+                .unwrap();
+
+            // Since AUTHENTICATE, both sides exchange segments.
+            let segment = read_segment(&mut socket, false).await.unwrap();
+            assert!(segment.self_contained);
+            let (stream, opcode, body) = parse_request(&segment.payload);
+            assert_eq!(opcode, RequestOpcode::AuthResponse as u8);
+            assert_eq!(&body[4..], b"\0user\0pass");
+
+            // AUTH_SUCCESS with null token.
+            let mut out = Vec::new();
+            write_segment(
+                &response(stream, 0x10, &(-1_i32).to_be_bytes()),
+                true,
+                false,
+                0,
+                &mut out,
+            );
+            socket.write_all(&out).await.unwrap();
+            socket
+        };
+
+        let config = HostConnectionConfig {
+            authenticator: Some(Arc::new(PlainTextAuthenticator::new(
+                "user".to_owned(),
+                "pass".to_owned(),
+            ))),
+            protocol_negotiation: ProtocolNegotiationPolicy::pinned(ProtocolVersion::V5),
+            keepalive_interval: None,
+            ..Default::default()
+        };
+        let endpoint = UntranslatedEndpoint::ContactPoint(ResolvedContactPoint {
+            address: addr,
+            datacenter: None,
+        });
+        let client = open_connection(&endpoint, None, &config);
+
+        let (client_result, _socket) = tokio::join!(client, server);
+        let (conn, _) = client_result.unwrap();
+        assert!(conn
+            .router_handle
+            .segments_enabled
+            .load(std::sync::atomic::Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn in_flight_limit_does_not_hold_back_internal_requests() {
+        use super::{Connection, InFlightLimiting, ProtocolNegotiationPolicy};
+        use crate::frame::ProtocolVersion;
+        use scylla_cql::frame::request::query::PagingState;
+        use scylla_cql::frame::request::Options;
+        use scylla_cql::frame::response::ResponseOpcode;
+        use scylla_cql::Consistency;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        setup_tracing();
+
+        async fn read_request(socket: &mut tokio::net::TcpStream) -> (i16, u8) {
+            let mut header = [0u8; 9];
+            socket.read_exact(&mut header).await.unwrap();
+            let length = u32::from_be_bytes(header[5..9].try_into().unwrap()) as usize;
+            let mut body = vec![0u8; length];
+            socket.read_exact(&mut body).await.unwrap();
+            (i16::from_be_bytes([header[2], header[3]]), header[4])
+        }
+
+        async fn respond(
+            socket: &mut tokio::net::TcpStream,
+            stream: i16,
+            opcode: ResponseOpcode,
+            body: &[u8],
+        ) {
+            let mut frame = vec![0x84, 0];
+            frame.extend_from_slice(&stream.to_be_bytes());
+            frame.push(opcode as u8);
+            frame.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            frame.extend_from_slice(body);
+            socket.write_all(&frame).await.unwrap();
+        }
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+
+            let (stream, opcode) = read_request(&mut socket).await;
+            assert_eq!(opcode, RequestOpcode::Options as u8);
+            respond(&mut socket, stream, ResponseOpcode::Supported, &[0, 0]).await;
+            let (stream, opcode) = read_request(&mut socket).await;
+            assert_eq!(opcode, RequestOpcode::Startup as u8);
+            respond(&mut socket, stream, ResponseOpcode::Ready, &[]).await;
+
+            // The query is left unanswered, so that it keeps its permit.
+            let (_, opcode) = read_request(&mut socket).await;
+            assert_eq!(opcode, RequestOpcode::Query as u8);
+
+            // The second query waits for a permit, so OPTIONS comes next.
+            let (stream, opcode) = read_request(&mut socket).await;
+            assert_eq!(opcode, RequestOpcode::Options as u8);
+            respond(&mut socket, stream, ResponseOpcode::Supported, &[0, 0]).await;
+            socket
+        });
+
+        let config = HostConnectionConfig {
+            protocol_negotiation: ProtocolNegotiationPolicy::pinned(ProtocolVersion::V4),
+            keepalive_interval: None,
+            max_inflight_requests: Some(std::num::NonZeroUsize::new(1).unwrap()),
+            ..Default::default()
+        };
+        let endpoint = UntranslatedEndpoint::ContactPoint(ResolvedContactPoint {
+            address: addr,
+            datacenter: None,
+        });
+        let (conn, _error_receiver) = open_connection(&endpoint, None, &config).await.unwrap();
+        let conn = Arc::new(conn);
+
+        let spawn_query = |conn: Arc<Connection>| {
+            tokio::spawn(async move {
+                conn.query_raw_with_consistency(
+                    &Statement::new("SELECT * FROM ks.t"),
+                    Consistency::One,
+                    None,
+                    None,
+                    PagingState::start(),
+                    InFlightLimiting::Apply,
+                )
+                .await
+            })
+        };
+
+        let first = spawn_query(Arc::clone(&conn));
+        while conn.in_flight_requests() == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        assert!(conn.is_saturated());
+
+        // A request waiting for a permit is not counted as in flight.
+        let second = spawn_query(Arc::clone(&conn));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(conn.in_flight_requests(), 1);
+
+        // Keepalives bypass the limit.
+        tokio::time::timeout(
+            Duration::from_secs(5),
+            conn.router_handle.send_request(
+                &Options,
+                None,
+                None,
+                0,
+                false,
+                InFlightLimiting::Exempt,
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        first.abort();
+        second.abort();
+        let _socket = server.await.unwrap();
+    }
 }
@@ -6,6 +6,7 @@ use super::connection::{
 use crate::errors::{
     BrokenConnectionErrorKind, ConnectionError, ConnectionPoolError, UseKeyspaceError,
 };
+use crate::policies::reconnection::ReconnectionPolicy;
 use crate::routing::{Shard, ShardCount, Sharder};
 
 use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
@@ -13,18 +14,19 @@ use crate::cluster::metadata::{PeerEndpoint, UntranslatedEndpoint};
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 
-use crate::cluster::NodeAddr;
+use crate::cluster::{Node, NodeAddr};
 use crate::utils::safe_format::IteratorSafeFormatExt;
 
 use arc_swap::ArcSwap;
 use futures::{future::RemoteHandle, stream::FuturesUnordered, Future, FutureExt, StreamExt};
 use rand::Rng;
 use std::convert::TryInto;
+use std::net::SocketAddr;
 use std::num::NonZeroUsize;
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::pin::Pin;
 use std::sync::{Arc, RwLock, Weak};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 use tokio::sync::{broadcast, mpsc, Notify};
 use tracing::{debug, error, trace, warn};
@@ -53,11 +55,69 @@ impl Default for PoolSize {
     }
 }
 
+/// A snapshot of the state of a node's connection pool.
+///
+/// See [Session::pool_status](crate::client::session::Session::pool_status).
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum PoolState {
+    /// The node is filtered out by the host filter, so no connections are opened to it.
+    Disabled,
+    /// The pool is being filled for the first time.
+    Connecting,
+    /// The pool is empty, because all connections broke or could not be opened.
+    /// It is being refilled in the background.
+    Broken {
+        /// The error from the last connection attempt.
+        last_error: ConnectionError,
+    },
+    /// The pool has at least one open connection.
+    Connected {
+        /// States of the shards of the node, indexed by shard.
+        /// Nodes which are not sharded, e.g. Cassandra nodes, have a single shard.
+        shards: Vec<ShardPoolState>,
+    },
+}
+
+/// A snapshot of the state of connections to a single shard of a node.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub enum ShardPoolState {
+    /// There are open connections to the shard.
+    Connected {
+        /// The open connections.
+        connections: Vec<ConnectionStatus>,
+    },
+    /// There are no open connections to the shard yet, the pool is being (re)filled.
+    Connecting,
+}
+
+/// A snapshot of the state of a single connection in a pool.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct ConnectionStatus {
+    /// The address that the connection is connected to.
+    pub address: SocketAddr,
+    /// The number of requests sent over the connection which await responses.
+    pub in_flight_requests: usize,
+}
+
+/// A snapshot of the state of a node's connection pool, along with the node.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NodePoolStatus {
+    /// The node that the pool belongs to.
+    pub node: Arc<Node>,
+    /// The state of the pool.
+    pub state: PoolState,
+}
+
 #[derive(Clone)]
 pub(crate) struct PoolConfig {
     pub(crate) connection_config: ConnectionConfig,
     pub(crate) pool_size: PoolSize,
     pub(crate) can_use_shard_aware_port: bool,
+    pub(crate) reconnection_policy: Arc<dyn ReconnectionPolicy>,
 }
 
 #[cfg(test)]
@@ -67,6 +127,9 @@ impl Default for PoolConfig {
             connection_config: Default::default(),
             pool_size: Default::default(),
             can_use_shard_aware_port: true,
+            reconnection_policy: Arc::new(
+                crate::policies::reconnection::ExponentialReconnectionPolicy::default(),
+            ),
         }
     }
 }
@@ -77,6 +140,7 @@ impl PoolConfig {
             connection_config: self.connection_config.to_host_connection_config(endpoint),
             pool_size: self.pool_size,
             can_use_shard_aware_port: self.can_use_shard_aware_port,
+            reconnection_policy: self.reconnection_policy.clone(),
         }
     }
 }
@@ -86,6 +150,7 @@ struct HostPoolConfig {
     pub(crate) connection_config: HostConnectionConfig,
     pub(crate) pool_size: PoolSize,
     pub(crate) can_use_shard_aware_port: bool,
+    pub(crate) reconnection_policy: Arc<dyn ReconnectionPolicy>,
 }
 
 #[cfg(test)]
@@ -95,6 +160,9 @@ impl Default for HostPoolConfig {
             connection_config: Default::default(),
             pool_size: Default::default(),
             can_use_shard_aware_port: true,
+            reconnection_policy: Arc::new(
+                crate::policies::reconnection::ExponentialReconnectionPolicy::default(),
+            ),
         }
     }
 }
@@ -369,6 +437,42 @@ impl NodeConnectionPool {
         response_receiver.await.unwrap() // PoolRefiller always responds
     }
 
+    // Returns a snapshot of the pool's state: `Connecting` until the first filling ends,
+    // `Broken` with the last connection error if there are no connections, or `Connected`
+    // with the open connections of each shard otherwise.
+    pub(crate) fn status(&self) -> PoolState {
+        let shard_state = |conns: &Vec<Arc<Connection>>| {
+            if conns.is_empty() {
+                return ShardPoolState::Connecting;
+            }
+            let connections = conns
+                .iter()
+                .map(|conn| ConnectionStatus {
+                    address: conn.get_connect_address(),
+                    in_flight_requests: conn.in_flight_requests(),
+                })
+                .collect();
+            ShardPoolState::Connected { connections }
+        };
+
+        match self.conns.load().as_ref() {
+            MaybePoolConnections::Initializing => PoolState::Connecting,
+            MaybePoolConnections::Broken(err) => PoolState::Broken {
+                last_error: err.clone(),
+            },
+            MaybePoolConnections::Ready(PoolConnections::NotSharded(conns)) => {
+                PoolState::Connected {
+                    shards: vec![shard_state(conns)],
+                }
+            }
+            MaybePoolConnections::Ready(PoolConnections::Sharded { connections, .. }) => {
+                PoolState::Connected {
+                    shards: connections.iter().map(shard_state).collect(),
+                }
+            }
+        }
+    }
+
     // Waits until the pool becomes initialized.
     // The pool is considered initialized either if the first connection has been
     // established or after first filling ends, whichever comes first.
@@ -409,7 +513,14 @@ impl NodeConnectionPool {
             Some(v[0].clone())
         } else {
             let idx = rand::rng().random_range(0..v.len());
-            Some(v[idx].clone())
+            // If the chosen connection reached its in-flight request limit,
+            // spill over to the next connection which did not.
+            let conn = (idx..v.len())
+                .chain(0..idx)
+                .map(|i| &v[i])
+                .find(|conn| !conn.is_saturated())
+                .unwrap_or(&v[idx]);
+            Some(conn.clone())
         }
     }
 
@@ -430,36 +541,38 @@ impl NodeConnectionPool {
 
 const EXCESS_CONNECTION_BOUND_PER_SHARD_MULTIPLIER: usize = 10;
 
-// TODO: Make it configurable through a policy (issue #184)
+// Delay of refills which are not preceded by failures to open connections.
 const MIN_FILL_BACKOFF: Duration = Duration::from_millis(50);
-const MAX_FILL_BACKOFF: Duration = Duration::from_secs(10);
-const FILL_BACKOFF_MULTIPLIER: u32 = 2;
 
-// A simple exponential strategy for pool fill backoffs.
+// Computes delays of pool fills, using the reconnection policy after failed fills.
 struct RefillDelayStrategy {
-    current_delay: Duration,
+    reconnection_policy: Arc<dyn ReconnectionPolicy>,
+    consecutive_failures: u32,
 }
 
 impl RefillDelayStrategy {
-    fn new() -> Self {
+    fn new(reconnection_policy: Arc<dyn ReconnectionPolicy>) -> Self {
         Self {
-            current_delay: MIN_FILL_BACKOFF,
+            reconnection_policy,
+            consecutive_failures: 0,
         }
     }
 
     fn get_delay(&self) -> Duration {
-        self.current_delay
+        if self.consecutive_failures == 0 {
+            MIN_FILL_BACKOFF
+        } else {
+            self.reconnection_policy
+                .reconnection_delay(self.consecutive_failures)
+        }
     }
 
     fn on_successful_fill(&mut self) {
-        self.current_delay = MIN_FILL_BACKOFF;
+        self.consecutive_failures = 0;
     }
 
     fn on_fill_error(&mut self) {
-        self.current_delay = std::cmp::min(
-            MAX_FILL_BACKOFF,
-            self.current_delay * FILL_BACKOFF_MULTIPLIER,
-        );
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
     }
 }
 
@@ -494,6 +607,11 @@ struct PoolRefiller {
     connection_errors:
         FuturesUnordered<Pin<Box<dyn Future<Output = BrokenConnectionEvent> + Send + 'static>>>,
 
+    // Receives information about connections whose credentials are about to expire
+    // and which should be replaced by fresh ones.
+    connection_expirations:
+        FuturesUnordered<Pin<Box<dyn Future<Output = Weak<Connection>> + Send + 'static>>>,
+
     // When connecting, ScyllaDB always assigns the shard which handles the least
     // number of connections. If there are some non-shard-aware clients
     // connected to the same node, they might cause the shard distribution
@@ -526,6 +644,14 @@ struct UseKeyspaceRequest {
     response_sender: tokio::sync::oneshot::Sender<Result<(), UseKeyspaceError>>,
 }
 
+#[cfg(feature = "metrics")]
+fn dec_connections_in_metrics(metrics: &Metrics, endpoint: &RwLock<UntranslatedEndpoint>) {
+    metrics.dec_total_connections();
+    if let UntranslatedEndpoint::Peer(peer) = &*endpoint.read().unwrap() {
+        metrics.dec_node_connections(peer.host_id);
+    }
+}
+
 impl PoolRefiller {
     pub(crate) fn new(
         endpoint: Arc<RwLock<UntranslatedEndpoint>>,
@@ -539,6 +665,8 @@ impl PoolRefiller {
         // and assume that the node is a Cassandra node
         let conns = vec![Vec::new()];
         let shared_conns = Arc::new(ArcSwap::new(Arc::new(MaybePoolConnections::Initializing)));
+        let refill_delay_strategy =
+            RefillDelayStrategy::new(pool_config.reconnection_policy.clone());
 
         Self {
             endpoint,
@@ -551,10 +679,11 @@ impl PoolRefiller {
             conns,
 
             had_error_since_last_refill: false,
-            refill_delay_strategy: RefillDelayStrategy::new(),
+            refill_delay_strategy,
 
             ready_connections: FuturesUnordered::new(),
             connection_errors: FuturesUnordered::new(),
+            connection_expirations: FuturesUnordered::new(),
 
             excess_connections: Vec::new(),
 
@@ -617,6 +746,13 @@ impl PoolRefiller {
                     }
                 }
 
+                conn = self.connection_expirations.select_next_some(), if !self.connection_expirations.is_empty() => {
+                    if let Some(conn) = conn.upgrade() {
+                        debug!("[{}] Credentials of connection {:p} are about to expire, rotating it", self.endpoint_description(), Arc::as_ptr(&conn));
+                        self.retire_connection(conn);
+                    }
+                }
+
                 req = use_keyspace_request_receiver.recv() => {
                     if let Some(req) = req {
                         debug!("[{}] Requested keyspace change: {}", self.endpoint_description(), req.keyspace_name.as_str());
@@ -767,6 +903,8 @@ impl PoolRefiller {
                         self.endpoint_description(),
                         err,
                     );
+                    #[cfg(feature = "metrics")]
+                    self.metrics.inc_shard_aware_port_fallbacks();
                     self.start_opening_connection(None);
                 } else {
                     // Encountered an error while connecting to the non-shard-aware
@@ -845,6 +983,10 @@ impl PoolRefiller {
 
                     self.connection_errors
                         .push(wait_for_error(Arc::downgrade(&conn), error_receiver).boxed());
+                    if let Some(expiry) = conn.get_credentials_expiry() {
+                        self.connection_expirations
+                            .push(wait_for_expiry(Arc::downgrade(&conn), expiry).boxed());
+                    }
                     self.conns[shard_id].push(conn);
 
                     self.update_shared_conns(None);
@@ -861,6 +1003,8 @@ impl PoolRefiller {
                         shard_id,
                     );
 
+                    #[cfg(feature = "metrics")]
+                    self.metrics.inc_shard_aware_port_fallbacks();
                     self.start_opening_connection(None);
                 } else {
                     // We got unlucky and ScyllaDB didn't distribute
@@ -905,11 +1049,28 @@ impl PoolRefiller {
         #[cfg(feature = "metrics")]
         let count_in_metrics = {
             let metrics = Arc::clone(&self.metrics);
-            move |connect_result: &Result<_, ConnectionError>| {
-                if connect_result.is_ok() {
-                    metrics.inc_total_connections();
-                } else if let Err(ConnectionError::ConnectTimeout) = &connect_result {
-                    metrics.inc_connection_timeouts();
+            let peer = match &endpoint {
+                UntranslatedEndpoint::Peer(peer) => Some((peer.host_id, peer.address)),
+                UntranslatedEndpoint::ContactPoint(_) => None,
+            };
+            move |connect_result: &Result<(Connection, _), ConnectionError>,
+                  requested_shard: Option<Shard>| {
+                match connect_result {
+                    Ok((connection, _)) => {
+                        metrics.inc_total_connections();
+                        if let Some((host_id, address)) = peer {
+                            metrics.inc_node_connections(host_id, address);
+                        }
+                        if let (Some(requested_shard), Some(shard_info)) =
+                            (requested_shard, connection.get_shard_info())
+                        {
+                            metrics.log_shard_aware_connection(
+                                shard_info.shard as Shard == requested_shard,
+                            );
+                        }
+                    }
+                    Err(ConnectionError::ConnectTimeout) => metrics.inc_connection_timeouts(),
+                    Err(_) => (),
                 }
             }
         };
@@ -929,7 +1090,7 @@ impl PoolRefiller {
                 .await;
 
                 #[cfg(feature = "metrics")]
-                count_in_metrics(&result);
+                count_in_metrics(&result, Some(shard));
 
                 OpenedConnectionEvent {
                     result,
@@ -943,7 +1104,7 @@ impl PoolRefiller {
                 let result = open_connection(&non_shard_aware_endpoint, None, &cfg).await;
 
                 #[cfg(feature = "metrics")]
-                count_in_metrics(&result);
+                count_in_metrics(&result, None);
 
                 OpenedConnectionEvent {
                     result,
@@ -1022,7 +1183,7 @@ impl PoolRefiller {
                 Some(idx) => {
                     v.swap_remove(idx);
                     #[cfg(feature = "metrics")]
-                    self.metrics.dec_total_connections();
+                    dec_connections_in_metrics(&self.metrics, &self.endpoint);
                     true
                 }
                 None => false,
@@ -1068,6 +1229,43 @@ impl PoolRefiller {
         );
     }
 
+    // Removes given connection from the active connections, so that it gets
+    // replaced by a fresh one during the next refill. Unlike `remove_connection`,
+    // the connection is still healthy: requests that already use it can complete.
+    // If it was the last active connection, the published pool state is kept
+    // as-is until the replacement is ready, so that the pool does not appear
+    // broken in the meantime.
+    fn retire_connection(&mut self, connection: Arc<Connection>) {
+        let shard_id = connection
+            .get_shard_info()
+            .as_ref()
+            .map_or(0, |s| s.shard as usize);
+        let Some(shard_conns) = self.conns.get_mut(shard_id) else {
+            return;
+        };
+        let Some(idx) = shard_conns
+            .iter()
+            .position(|other_conn| Arc::ptr_eq(&connection, other_conn))
+        else {
+            return;
+        };
+        shard_conns.swap_remove(idx);
+        #[cfg(feature = "metrics")]
+        dec_connections_in_metrics(&self.metrics, &self.endpoint);
+
+        trace!(
+            "[{}] Connection {:p} retired from shard {} pool, now there is {} for the shard, total {}",
+            self.endpoint_description(),
+            Arc::as_ptr(&connection),
+            shard_id,
+            self.conns[shard_id].len(),
+            self.active_connection_count(),
+        );
+        if !self.is_empty() {
+            self.update_shared_conns(None);
+        }
+    }
+
     // Sets current keyspace for available connections.
     // Connections which are being currently opened and future connections
     // will have this keyspace set when they appear on `ready_connections`.
@@ -1187,6 +1385,20 @@ async fn wait_for_error(
     }
 }
 
+// Connections are never rotated more often than this, even if the authenticator
+// reports credentials which are already expired. This prevents a busy loop
+// of opening connections.
+const MIN_CONNECTION_LIFETIME: Duration = Duration::from_secs(1);
+
+async fn wait_for_expiry(connection: Weak<Connection>, expiry: SystemTime) -> Weak<Connection> {
+    let lifetime = expiry
+        .duration_since(SystemTime::now())
+        .unwrap_or_default()
+        .max(MIN_CONNECTION_LIFETIME);
+    tokio::time::sleep(lifetime).await;
+    connection
+}
+
 struct OpenedConnectionEvent {
     result: Result<(Connection, ErrorReceiver), ConnectionError>,
     requested_shard: Option<Shard>,
@@ -1196,11 +1408,42 @@ struct OpenedConnectionEvent {
 #[cfg(test)]
 mod tests {
     use super::super::connection::{open_connection_to_shard_aware_port, HostConnectionConfig};
+    use super::{NodeConnectionPool, PoolConfig, PoolState};
     use crate::cluster::metadata::UntranslatedEndpoint;
     use crate::cluster::node::ResolvedContactPoint;
     use crate::routing::{ShardCount, Sharder};
     use crate::test_utils::setup_tracing;
+    use assert_matches::assert_matches;
     use std::net::{SocketAddr, ToSocketAddrs};
+    use tokio::sync::broadcast;
+
+    #[tokio::test]
+    async fn status_of_unreachable_node_pool() {
+        setup_tracing();
+        // Bind to a free port and close it right away, so that connecting to it fails.
+        let address = {
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            listener.local_addr().unwrap()
+        };
+        let endpoint = UntranslatedEndpoint::ContactPoint(ResolvedContactPoint {
+            address,
+            datacenter: None,
+        });
+        let (pool_empty_notifier, _) = broadcast::channel(1);
+
+        let pool = NodeConnectionPool::new(
+            endpoint,
+            &PoolConfig::default(),
+            None,
+            pool_empty_notifier,
+            #[cfg(feature = "metrics")]
+            Default::default(),
+        );
+        assert_matches!(pool.status(), PoolState::Connecting);
+
+        pool.wait_until_initialized().await;
+        assert_matches!(pool.status(), PoolState::Broken { .. });
+    }
 
     // Open many connections to a node
     // Port collision should occur
@@ -9,12 +9,15 @@ mod connection;
 #[cfg(test)]
 pub(crate) use connection::open_connection;
 
-pub(crate) use connection::{Connection, ConnectionConfig, VerifiedKeyspaceName};
+pub(crate) use connection::{
+    connect_with_source_ip_and_port, Connection, ConnectionConfig, InFlightLimiting,
+    VerifiedKeyspaceName,
+};
 
 mod connection_pool;
 
-pub use connection::WriteCoalescingDelay;
-pub use connection_pool::PoolSize;
+pub use connection::{ProtocolNegotiationPolicy, WriteCoalescingDelay};
+pub use connection_pool::{ConnectionStatus, NodePoolStatus, PoolSize, PoolState, ShardPoolState};
 pub(crate) use connection_pool::{NodeConnectionPool, PoolConfig};
 
 pub(crate) mod tls;
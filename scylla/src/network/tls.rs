@@ -2,7 +2,7 @@
 //!
 //! The full picture looks like this:
 //!
-//! ┌─←─ TlsContext (openssl::SslContext / rustls::ClientConfig)
+//! ┌─←─ TlsContextProvider (e.g. TlsContext: openssl::SslContext / rustls::ClientConfig)
 //! │
 //! ├─←─ CloudConfig (powered by either TLS backend)
 //! │
@@ -12,14 +12,15 @@
 //!   │
 //!   │ produces
 //!   │
-//!   ↳TlsConfig (specific for the particular connection)
+//!   ↳TlsConfig (specific for the particular connection, possibly with a CertificateVerifier)
 //!     │
 //!     │ produces
 //!     │
 //!     ↳Tls (wrapper over TCP stream which adds encryption)
 
 use std::io;
-#[cfg(feature = "unstable-cloud")]
+#[cfg(any(feature = "openssl-010", feature = "rustls-023"))]
+use std::net::IpAddr;
 use std::sync::Arc;
 
 #[cfg(feature = "unstable-cloud")]
@@ -27,7 +28,11 @@ use tracing::warn;
 #[cfg(feature = "unstable-cloud")]
 use uuid::Uuid;
 
+#[cfg(any(feature = "openssl-010", feature = "rustls-023"))]
 use crate::client::session::TlsContext;
+#[cfg(any(feature = "openssl-010", feature = "rustls-023"))]
+use crate::client::tls::PeerCertificates;
+use crate::client::tls::{CertificateVerifier, TlsContextProvider};
 #[cfg(feature = "unstable-cloud")]
 use crate::cloud::CloudConfig;
 #[cfg(feature = "unstable-cloud")]
@@ -39,14 +44,15 @@ use crate::cluster::node::ResolvedContactPoint;
 /// Abstraction capable of producing [TlsConfig] for connections on-demand.
 #[derive(Clone)] // Cheaply clonable (reference-counted)
 pub(crate) enum TlsProvider {
-    GlobalContext(TlsContext),
+    GlobalContext(Arc<dyn TlsContextProvider>),
     #[cfg(feature = "unstable-cloud")]
     ScyllaCloud(Arc<CloudConfig>),
 }
 
 impl TlsProvider {
-    /// Used in case when the user provided their own [TlsContext] to be used in all connections.
-    pub(crate) fn new_with_global_context(context: TlsContext) -> Self {
+    /// Used in case when the user provided their own [TlsContext], or a provider of it,
+    /// to be used in all connections.
+    pub(crate) fn new_with_global_context(context: Arc<dyn TlsContextProvider>) -> Self {
         Self::GlobalContext(context)
     }
 
@@ -100,14 +106,16 @@ impl TlsProvider {
 /// Encapsulates TLS-regarding configuration that is specific for a particular endpoint.
 ///
 /// Both use cases are supported:
-/// 1. User-provided global TlsContext. Then, the global TlsContext is simply cloned here.
+/// 1. User-provided global TlsContext. Then, the global provider of TlsContext is simply cloned here,
+///    and asked for the context whenever a connection is opened.
 /// 2. Serverless Cloud. Then the TlsContext is customized for the given endpoint,
 ///    and its SNI information is stored alongside.
 #[derive(Clone)]
 pub(crate) struct TlsConfig {
-    context: TlsContext,
+    context: Arc<dyn TlsContextProvider>,
     #[cfg(feature = "unstable-cloud")]
     sni: Option<String>,
+    certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
 }
 
 /// An abstraction over connection's TLS layer which holds its state and configuration.
@@ -124,11 +132,14 @@ pub(crate) enum Tls {
 
 /// A wrapper around a TLS error.
 ///
-/// The original error came from one of the supported TLS backends.
+/// The original error came from one of the supported TLS backends,
+/// or from a user-provided [CertificateVerifier].
 #[derive(Debug, thiserror::Error)]
 #[error(transparent)]
 #[non_exhaustive]
 pub enum TlsError {
+    /// The certificates presented by the node were rejected by the [CertificateVerifier].
+    CertificateRejected(#[from] CertificateRejected),
     /// Collection of errors coming from OpenSSL 0.10.
     #[cfg(feature = "openssl-010")]
     OpenSsl010(#[from] openssl::error::ErrorStack),
@@ -143,9 +154,28 @@ pub enum TlsError {
     Rustls023(#[from] rustls::Error),
 }
 
+/// Error returned by a [CertificateVerifier] when it rejects the certificates presented by a node.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("Certificate rejected by the certificate verifier: {reason}")]
+#[non_exhaustive]
+pub struct CertificateRejected {
+    /// Why the certificates were rejected.
+    pub reason: String,
+}
+
+impl CertificateRejected {
+    /// Creates an error with the given reason of rejection.
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
 impl From<TlsError> for io::Error {
     fn from(value: TlsError) -> Self {
         match value {
+            TlsError::CertificateRejected(e) => io::Error::new(io::ErrorKind::PermissionDenied, e),
             #[cfg(feature = "openssl-010")]
             TlsError::OpenSsl010(e) => e.into(),
             #[cfg(feature = "rustls-023")]
@@ -160,11 +190,12 @@ impl From<TlsError> for io::Error {
 
 impl TlsConfig {
     /// Used in case when the user provided their own TlsContext to be used in all connections.
-    pub(crate) fn new_with_global_context(context: TlsContext) -> Self {
+    pub(crate) fn new_with_global_context(context: Arc<dyn TlsContextProvider>) -> Self {
         Self {
             context,
             #[cfg(feature = "unstable-cloud")]
             sni: None,
+            certificate_verifier: None,
         }
     }
 
@@ -176,23 +207,83 @@ impl TlsConfig {
         host_id: Option<Uuid>,
     ) -> Self {
         Self {
-            context,
+            context: Arc::new(context),
             #[cfg(feature = "unstable-cloud")]
             sni: Some(if let Some(host_id) = host_id {
                 format!("{host_id}.{domain_name}")
             } else {
                 domain_name.into()
             }),
+            certificate_verifier: None,
         }
     }
 
+    /// Sets the verifier to be called with the certificates presented by the node.
+    pub(crate) fn with_certificate_verifier(
+        mut self,
+        certificate_verifier: Option<Arc<dyn CertificateVerifier>>,
+    ) -> Self {
+        self.certificate_verifier = certificate_verifier;
+        self
+    }
+
+    /// Passes the certificates presented by the node during an OpenSSL handshake
+    /// to the certificate verifier, if there is one.
+    #[cfg(feature = "openssl-010")]
+    pub(crate) fn verify_openssl_peer(
+        &self,
+        node_address: IpAddr,
+        ssl: &openssl::ssl::SslRef,
+    ) -> Result<(), TlsError> {
+        let Some(verifier) = &self.certificate_verifier else {
+            return Ok(());
+        };
+        let chain = ssl
+            .peer_cert_chain()
+            .map(|chain| chain.iter().map(|cert| cert.to_der()).collect())
+            .transpose()?
+            .unwrap_or_else(Vec::new);
+        let chain: Vec<&[u8]> = chain.iter().map(Vec::as_slice).collect();
+        verifier.verify(&PeerCertificates {
+            node_address,
+            chain: &chain,
+        })?;
+        Ok(())
+    }
+
+    /// Passes the certificates presented by the node during a rustls handshake
+    /// to the certificate verifier, if there is one.
+    #[cfg(feature = "rustls-023")]
+    pub(crate) fn verify_rustls_peer(
+        &self,
+        node_address: IpAddr,
+        connection: &rustls::ClientConnection,
+    ) -> Result<(), TlsError> {
+        let Some(verifier) = &self.certificate_verifier else {
+            return Ok(());
+        };
+        let chain: Vec<&[u8]> = connection
+            .peer_certificates()
+            .unwrap_or_default()
+            .iter()
+            .map(|cert| cert.as_ref())
+            .collect();
+        verifier.verify(&PeerCertificates {
+            node_address,
+            chain: &chain,
+        })?;
+        Ok(())
+    }
+
     /// Produces a new Tls object that is able to wrap a TCP stream.
+    // To silence warnings when TlsContext is an empty enum (tls features are disabled).
+    #[allow(unreachable_code)]
     pub(crate) fn new_tls(&self) -> Result<Tls, TlsError> {
-        match self.context {
+        match self.context.tls_context() {
             #[cfg(feature = "openssl-010")]
-            TlsContext::OpenSsl010(ref context) => {
+            TlsContext::OpenSsl010(context) => {
                 #[allow(unused_mut)]
-                let mut ssl = openssl::ssl::Ssl::new(context)?;
+                let mut ssl = openssl::ssl::Ssl::new(&context)?;
                 #[cfg(feature = "unstable-cloud")]
                 if let Some(sni) = self.sni.as_ref() {
                     ssl.set_hostname(sni)?;
@@ -200,8 +291,8 @@ impl TlsConfig {
                 Ok(Tls::OpenSsl010(ssl))
             }
             #[cfg(feature = "rustls-023")]
-            TlsContext::Rustls023(ref config) => {
-                let connector = tokio_rustls::TlsConnector::from(config.clone());
+            TlsContext::Rustls023(config) => {
+                let connector = tokio_rustls::TlsConnector::from(config);
                 #[cfg(feature = "unstable-cloud")]
                 let sni = self
                     .sni
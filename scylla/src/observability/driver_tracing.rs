@@ -2,6 +2,7 @@ use crate::cluster::node::Node;
 use crate::network::Connection;
 use crate::response::query_result::QueryResult;
 use crate::routing::{Shard, Token};
+use crate::statement::StatementLabels;
 use crate::utils::safe_format::IteratorSafeFormatExt;
 use itertools::Either;
 use scylla_cql::frame::response::result::ColumnSpec;
@@ -34,6 +35,7 @@ impl RequestSpan {
             replicas = Empty,
             shard = Empty,
             speculative_executions = Empty,
+            labels = Empty,
         );
 
         Self {
@@ -61,6 +63,7 @@ impl RequestSpan {
             replicas = Empty,
             shard = Empty,
             speculative_executions = Empty,
+            labels = Empty,
         );
 
         if let Some(partition_key) = partition_key {
@@ -94,6 +97,7 @@ impl RequestSpan {
             replicas = Empty,
             shard = Empty,
             speculative_executions = Empty,
+            labels = Empty,
         );
 
         Self {
@@ -143,6 +147,12 @@ impl RequestSpan {
         );
     }
 
+    pub(crate) fn record_labels(&self, labels: &StatementLabels) {
+        if !labels.is_empty() {
+            self.span.record("labels", tracing::field::display(labels));
+        }
+    }
+
     pub(crate) fn record_request_size(&self, size: usize) {
         self.span.record("request_size", size);
     }
@@ -1,12 +1,34 @@
 //! Collecting metrics of driver operations.
 
 use histogram::{AtomicHistogram, Histogram};
+use scylla_cql::frame::response::result::TableSpec;
+use std::collections::HashMap;
+use std::fmt::Display;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 use thiserror::Error;
+use uuid::Uuid;
+
+use crate::cluster::NodeRef;
+use crate::statement::StatementLabels;
 
 const ORDER_TYPE: Ordering = Ordering::Relaxed;
 
+/// Key of the label derived by the driver from the table targeted by a request,
+/// with the value of the form `keyspace.table`.
+///
+/// The table is known for prepared statements (and batches, in which case
+/// the table of the first prepared statement is used), unless the statement
+/// carries an explicit label with this key, which takes precedence.
+pub const TABLE_LABEL_KEY: &str = "table";
+
+/// Formats the value of the [`TABLE_LABEL_KEY`] label of requests targeting the given table.
+///
+/// Statements compute it once, so that it is not allocated upon each request.
+pub(crate) fn table_label(table: &TableSpec<'_>) -> String {
+    format!("{}.{}", table.ks_name(), table.table_name())
+}
+
 /// Error that occured upon a metrics operation.
 #[non_exhaustive]
 #[derive(Error, Debug)]
@@ -23,7 +45,7 @@ pub enum MetricsError {
 /// min, max, mean, standard deviation, median, and most common percentiles
 /// collected in a certain moment.
 #[non_exhaustive]
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Snapshot {
     /// Minimum value in the histogram.
     pub min: u64,
@@ -47,6 +69,114 @@ pub struct Snapshot {
     pub percentile_99_9: u64,
 }
 
+/// Statistics of requests executed with a particular statement label,
+/// see e.g. [`Statement::set_label`](crate::statement::Statement::set_label).
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LabelStats {
+    /// Number of requests carrying the label.
+    pub requests: u64,
+    /// Number of requests carrying the label which failed.
+    pub errors: u64,
+    /// Average latency of requests carrying the label, in milliseconds.
+    pub latency_avg_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct LabelCounters {
+    requests: AtomicU64,
+    errors: AtomicU64,
+    latency_sum_ms: AtomicU64,
+}
+
+impl LabelCounters {
+    fn stats(&self) -> LabelStats {
+        let requests = self.requests.load(ORDER_TYPE);
+        LabelStats {
+            requests,
+            errors: self.errors.load(ORDER_TYPE),
+            latency_avg_ms: self
+                .latency_sum_ms
+                .load(ORDER_TYPE)
+                .checked_div(requests)
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Statistics of request attempts sent to a particular node,
+/// and of connections in its connection pool.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeStats {
+    /// Host id of the node.
+    pub host_id: Uuid,
+    /// Address of the node when it was first seen by metrics.
+    pub address: String,
+    /// Number of request attempts sent to the node.
+    pub requests: u64,
+    /// Number of request attempts sent to the node which failed.
+    pub errors: u64,
+    /// Statistics of latencies of successful request attempts sent to the node,
+    /// in milliseconds, or `None` if no attempt succeeded yet.
+    pub latency: Option<Snapshot>,
+    /// Number of open connections in the connection pool of the node.
+    pub connections: u64,
+}
+
+struct NodeCounters {
+    address: String,
+    requests: AtomicU64,
+    errors: AtomicU64,
+    histogram: AtomicHistogram,
+    latency_sum_ms: AtomicU64,
+    connections: AtomicU64,
+}
+
+impl NodeCounters {
+    fn new(address: String) -> Self {
+        // Per-node histograms are much smaller than the global one, at the cost of precision:
+        //  - relative error: e = 2^-7 = 0.0078,
+        //  - total number of buckets: (16 - 7 + 1) * 2^7 = 1280,
+        //  - histogram size: 10 KiB.
+        let max_value_power = 16;
+        let grouping_power = 7;
+
+        Self {
+            address,
+            requests: AtomicU64::new(0),
+            errors: AtomicU64::new(0),
+            histogram: AtomicHistogram::new(grouping_power, max_value_power).unwrap(),
+            latency_sum_ms: AtomicU64::new(0),
+            connections: AtomicU64::new(0),
+        }
+    }
+
+    fn stats(&self, host_id: Uuid) -> NodeStats {
+        NodeStats {
+            host_id,
+            address: self.address.clone(),
+            requests: self.requests.load(ORDER_TYPE),
+            errors: self.errors.load(ORDER_TYPE),
+            latency: Metrics::snapshot(&self.histogram.load()).ok(),
+            connections: self.connections.load(ORDER_TYPE),
+        }
+    }
+}
+
+impl std::fmt::Debug for NodeCounters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NodeCounters")
+            .field("address", &self.address)
+            .field("requests", &self.requests)
+            .field("errors", &self.errors)
+            .field("histogram", &self.histogram.load())
+            .field("latency_sum_ms", &self.latency_sum_ms)
+            .field("connections", &self.connections)
+            .finish()
+    }
+}
+
 /// The interval in seconds for which the rate is calculated.
 const INTERVAL: u64 = 5;
 
@@ -215,6 +345,85 @@ impl Default for RequestRateMeter {
     }
 }
 
+/// Default upper bounds of the buckets of frame size histograms, in bytes.
+pub const DEFAULT_FRAME_SIZE_BUCKETS: [u64; 10] = [
+    256,
+    1024,
+    4 * 1024,
+    16 * 1024,
+    64 * 1024,
+    256 * 1024,
+    1024 * 1024,
+    4 * 1024 * 1024,
+    16 * 1024 * 1024,
+    64 * 1024 * 1024,
+];
+
+/// Distribution of sizes of frames, in bytes, over buckets with configured upper bounds
+/// (see [`SessionBuilder::frame_size_buckets`](crate::client::session_builder::SessionBuilder::frame_size_buckets)).
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizeDistribution {
+    /// Pairs of the inclusive upper bound of each bucket and the number of frames
+    /// whose size is at most that bound, but above the bound of the previous bucket.
+    pub buckets: Vec<(u64, u64)>,
+    /// Number of frames larger than the upper bound of the last bucket.
+    pub overflow: u64,
+    /// Total number of frames.
+    pub count: u64,
+    /// Sum of sizes of all frames.
+    pub sum: u64,
+    /// Size of the largest frame.
+    pub max: u64,
+}
+
+#[derive(Debug)]
+struct SizeHistogram {
+    /// Sorted upper bounds of the buckets.
+    bounds: Vec<u64>,
+    /// Counts of the buckets, with an additional one for sizes above all bounds.
+    counts: Vec<AtomicU64>,
+    sum: AtomicU64,
+    max: AtomicU64,
+}
+
+impl SizeHistogram {
+    fn new(mut bounds: Vec<u64>) -> Self {
+        bounds.sort_unstable();
+        bounds.dedup();
+        let counts = (0..=bounds.len()).map(|_| AtomicU64::new(0)).collect();
+        Self {
+            bounds,
+            counts,
+            sum: AtomicU64::new(0),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, size: u64) {
+        let bucket = self.bounds.partition_point(|bound| *bound < size);
+        self.counts[bucket].fetch_add(1, ORDER_TYPE);
+        self.sum.fetch_add(size, ORDER_TYPE);
+        self.max.fetch_max(size, ORDER_TYPE);
+    }
+
+    fn distribution(&self) -> SizeDistribution {
+        let counts: Vec<u64> = self.counts.iter().map(|c| c.load(ORDER_TYPE)).collect();
+        SizeDistribution {
+            buckets: self
+                .bounds
+                .iter()
+                .copied()
+                .zip(counts.iter().copied())
+                .collect(),
+            overflow: counts[self.bounds.len()],
+            count: counts.iter().sum(),
+            sum: self.sum.load(ORDER_TYPE),
+            max: self.max.load(ORDER_TYPE),
+        }
+    }
+}
+
 /// Various metrics collected by the driver.
 pub struct Metrics {
     /// Number of errors that occurred in queries executed without `QueryPager`.
@@ -227,18 +436,63 @@ pub struct Metrics {
     queries_iter_num: AtomicU64,
     /// Number of times a retry policy has decided to retry a query.
     retries_num: AtomicU64,
+    /// Number of speculative executions started.
+    speculative_executions_num: AtomicU64,
+    /// Number of in-flight attempts cancelled because another attempt
+    /// of the same request completed first.
+    wasted_speculative_attempts_num: AtomicU64,
+    /// Number of times a prepared statement was transparently reprepared
+    /// after a node reported it as unprepared.
+    repreparations_num: AtomicU64,
+    /// Number of responses to requests against tablet tables which
+    /// came without tablet info, i.e. were sent to a replica of the tablet.
+    tablet_routing_hits: AtomicU64,
+    /// Number of responses which came with tablet info, i.e. were sent
+    /// to a node which doesn't replicate the tablet.
+    tablet_routing_misses: AtomicU64,
     /// Histogram that collects latencies of queries executed by the driver.
     histogram: Arc<AtomicHistogram>,
+    /// Sum of latencies collected in the histogram, in milliseconds.
+    latency_sum_ms: AtomicU64,
     /// Collects rates of queries executed by the driver.
     meter: Arc<RequestRateMeter>,
+    /// Number of connections opened through the shard-aware port
+    /// which landed on the requested shard.
+    shard_aware_connections: AtomicU64,
+    /// Number of connections opened through the shard-aware port
+    /// which landed on another shard than the requested one.
+    shard_aware_connection_misses: AtomicU64,
+    /// Number of times the driver fell back to opening a connection through
+    /// the non-shard-aware port, after using the shard-aware port failed or missed.
+    shard_aware_port_fallbacks: AtomicU64,
+    /// Number of request attempts sent to sharded nodes on a connection
+    /// to the shard targeted by the load balancing policy.
+    requests_on_target_shard: AtomicU64,
+    /// Number of request attempts sent to sharded nodes on a connection
+    /// to another shard than the targeted one.
+    cross_shard_requests: AtomicU64,
+    /// Sizes of request frames sent by the driver.
+    request_sizes: SizeHistogram,
+    /// Sizes of response frames received by the driver.
+    response_sizes: SizeHistogram,
     /// Total number of connections ever opened to the cluster by the driver.
     total_connections: AtomicU64,
     connection_timeouts: AtomicU64,
     request_timeouts: AtomicU64,
+    /// Statistics of labeled requests, by label key and value.
+    labels: RwLock<HashMap<String, HashMap<String, LabelCounters>>>,
+    /// Counters of requests sent to each node, by host id.
+    nodes: RwLock<HashMap<Uuid, NodeCounters>>,
 }
 
 impl Metrics {
+    #[cfg(test)]
     pub(crate) fn new() -> Self {
+        Self::with_frame_size_buckets(DEFAULT_FRAME_SIZE_BUCKETS.to_vec())
+    }
+
+    /// Creates metrics with the given upper bounds of buckets of frame size histograms.
+    pub(crate) fn with_frame_size_buckets(frame_size_buckets: Vec<u64>) -> Self {
         // Configuration:
         //  - exponent of max value: n = 16
         //  - inverse exponent of relative error: p = 12,
@@ -257,11 +511,26 @@ impl Metrics {
             errors_iter_num: AtomicU64::new(0),
             queries_iter_num: AtomicU64::new(0),
             retries_num: AtomicU64::new(0),
+            speculative_executions_num: AtomicU64::new(0),
+            wasted_speculative_attempts_num: AtomicU64::new(0),
+            repreparations_num: AtomicU64::new(0),
+            tablet_routing_hits: AtomicU64::new(0),
+            tablet_routing_misses: AtomicU64::new(0),
             histogram: Arc::new(AtomicHistogram::new(grouping_power, max_value_power).unwrap()),
+            latency_sum_ms: AtomicU64::new(0),
             meter: Arc::new(RequestRateMeter::new()),
+            shard_aware_connections: AtomicU64::new(0),
+            shard_aware_connection_misses: AtomicU64::new(0),
+            shard_aware_port_fallbacks: AtomicU64::new(0),
+            requests_on_target_shard: AtomicU64::new(0),
+            cross_shard_requests: AtomicU64::new(0),
+            request_sizes: SizeHistogram::new(frame_size_buckets.clone()),
+            response_sizes: SizeHistogram::new(frame_size_buckets),
             total_connections: AtomicU64::new(0),
             connection_timeouts: AtomicU64::new(0),
             request_timeouts: AtomicU64::new(0),
+            labels: RwLock::new(HashMap::new()),
+            nodes: RwLock::new(HashMap::new()),
         }
     }
 
@@ -288,11 +557,175 @@ impl Metrics {
         self.meter.mark();
     }
 
+    /// Records a request executed with the given statement labels, and the label
+    /// derived from the table targeted by the request (see [`table_label`]), if known.
+    pub(crate) fn log_labeled_request(
+        &self,
+        labels: &StatementLabels,
+        table_label: Option<&str>,
+        latency: u64,
+        failed: bool,
+    ) {
+        if labels.is_empty() && table_label.is_none() {
+            return;
+        }
+
+        let record = |counters: &LabelCounters| {
+            counters.requests.fetch_add(1, ORDER_TYPE);
+            counters.latency_sum_ms.fetch_add(latency, ORDER_TYPE);
+            if failed {
+                counters.errors.fetch_add(1, ORDER_TYPE);
+            }
+        };
+
+        let derived_labels = table_label
+            .filter(|_| labels.get(TABLE_LABEL_KEY).is_none())
+            .map(|table| (TABLE_LABEL_KEY, table));
+        let request_labels = labels.iter().chain(derived_labels);
+
+        // Labels seen for the first time are registered under the write lock.
+        let mut unseen_labels = Vec::new();
+        {
+            let all_labels = self.labels.read().unwrap();
+            for (key, value) in request_labels {
+                match all_labels.get(key).and_then(|values| values.get(value)) {
+                    Some(counters) => record(counters),
+                    None => unseen_labels.push((key, value)),
+                }
+            }
+        }
+        if unseen_labels.is_empty() {
+            return;
+        }
+
+        let mut all_labels = self.labels.write().unwrap();
+        for (key, value) in unseen_labels {
+            let counters = all_labels
+                .entry(key.to_owned())
+                .or_default()
+                .entry(value.to_owned())
+                .or_default();
+            record(counters);
+        }
+    }
+
+    /// Records a request attempt sent to the given node, which took `latency` milliseconds.
+    /// Both nonpaged queries and pages of paged queries are accounted for.
+    /// Only latencies of successful attempts are recorded in the node's histogram.
+    pub(crate) fn log_node_request(&self, node: NodeRef<'_>, latency: u64, failed: bool) {
+        self.with_node_counters(
+            node.host_id,
+            || node.address.to_string(),
+            |counters| {
+                counters.requests.fetch_add(1, ORDER_TYPE);
+                if failed {
+                    counters.errors.fetch_add(1, ORDER_TYPE);
+                } else if counters.histogram.increment(latency).is_ok() {
+                    counters.latency_sum_ms.fetch_add(latency, ORDER_TYPE);
+                }
+            },
+        );
+    }
+
+    /// Increments counter for open connections in the pool of the given node.
+    pub(crate) fn inc_node_connections(&self, host_id: Uuid, address: impl Display) {
+        self.with_node_counters(
+            host_id,
+            || address.to_string(),
+            |counters| {
+                counters.connections.fetch_add(1, ORDER_TYPE);
+            },
+        );
+    }
+
+    /// Decrements counter for open connections in the pool of the given node.
+    pub(crate) fn dec_node_connections(&self, host_id: Uuid) {
+        if let Some(counters) = self.nodes.read().unwrap().get(&host_id) {
+            counters.connections.fetch_sub(1, ORDER_TYPE);
+        }
+    }
+
+    fn with_node_counters(
+        &self,
+        host_id: Uuid,
+        address: impl FnOnce() -> String,
+        record: impl FnOnce(&NodeCounters),
+    ) {
+        if let Some(counters) = self.nodes.read().unwrap().get(&host_id) {
+            record(counters);
+            return;
+        }
+        let mut nodes = self.nodes.write().unwrap();
+        let counters = nodes
+            .entry(host_id)
+            .or_insert_with(|| NodeCounters::new(address()));
+        record(counters);
+    }
+
     /// Increments counter measuring how many times a retry policy has decided to retry a query
     pub(crate) fn inc_retries_num(&self) {
         self.retries_num.fetch_add(1, ORDER_TYPE);
     }
 
+    /// Increments counter for speculative executions started.
+    pub(crate) fn inc_speculative_executions_num(&self) {
+        self.speculative_executions_num.fetch_add(1, ORDER_TYPE);
+    }
+
+    /// Increases counter for attempts cancelled after another attempt
+    /// of the same request completed first.
+    pub(crate) fn add_wasted_speculative_attempts(&self, count: u64) {
+        self.wasted_speculative_attempts_num
+            .fetch_add(count, ORDER_TYPE);
+    }
+
+    /// Increments counter for repreparations of prepared statements.
+    pub(crate) fn inc_repreparations_num(&self) {
+        self.repreparations_num.fetch_add(1, ORDER_TYPE);
+    }
+
+    /// Accounts for a response to a request against a table in tablet routing counters.
+    /// A response carrying tablet info is a miss, while a response without it is a hit
+    /// if the table is known to use tablets.
+    pub(crate) fn log_tablet_routing(&self, tablet_table: bool, got_tablet_info: bool) {
+        if got_tablet_info {
+            self.tablet_routing_misses.fetch_add(1, ORDER_TYPE);
+        } else if tablet_table {
+            self.tablet_routing_hits.fetch_add(1, ORDER_TYPE);
+        }
+    }
+
+    /// Accounts for a connection opened through the shard-aware port, depending on
+    /// whether it landed on the requested shard.
+    pub(crate) fn log_shard_aware_connection(&self, on_requested_shard: bool) {
+        if on_requested_shard {
+            self.shard_aware_connections.fetch_add(1, ORDER_TYPE);
+        } else {
+            self.shard_aware_connection_misses.fetch_add(1, ORDER_TYPE);
+        }
+    }
+
+    /// Increments counter for fallbacks to the non-shard-aware port.
+    pub(crate) fn inc_shard_aware_port_fallbacks(&self) {
+        self.shard_aware_port_fallbacks.fetch_add(1, ORDER_TYPE);
+    }
+
+    /// Accounts for a request attempt sent to a sharded node, depending on whether
+    /// it was sent on a connection to the shard targeted by the load balancing policy.
+    pub(crate) fn log_shard_routing(&self, on_target_shard: bool) {
+        if on_target_shard {
+            self.requests_on_target_shard.fetch_add(1, ORDER_TYPE);
+        } else {
+            self.cross_shard_requests.fetch_add(1, ORDER_TYPE);
+        }
+    }
+
+    /// Records the sizes of a request frame and of the response frame received for it, in bytes.
+    pub(crate) fn log_frame_sizes(&self, request_size: usize, response_size: usize) {
+        self.request_sizes.record(request_size as u64);
+        self.response_sizes.record(response_size as u64);
+    }
+
     /// Increments counter for active number of connections to the cluster.
     /// Should be called when opening new connections, once per connection.
     pub(crate) fn inc_total_connections(&self) {
@@ -325,10 +758,18 @@ impl Metrics {
         if let Err(err) = self.histogram.increment(latency) {
             Err(MetricsError::HistogramError(Arc::new(err)))
         } else {
+            self.latency_sum_ms.fetch_add(latency, ORDER_TYPE);
             Ok(())
         }
     }
 
+    /// Returns the current state of the latency histogram
+    /// and the sum of latencies recorded in it, in milliseconds.
+    #[cfg(feature = "metrics-prometheus")]
+    pub(crate) fn latency_histogram(&self) -> (Histogram, u64) {
+        (self.histogram.load(), self.latency_sum_ms.load(ORDER_TYPE))
+    }
+
     /// Returns average latency in milliseconds
     pub fn get_latency_avg_ms(&self) -> Result<u64, MetricsError> {
         Self::mean(&self.histogram.load())
@@ -350,17 +791,39 @@ impl Metrics {
         }
     }
 
+    /// Returns latency of successful request attempts sent to the given node
+    /// for a given percentile, in milliseconds.
+    /// # Arguments
+    ///
+    /// * `host_id` - host id of the node
+    /// * `percentile` - float value (0.0 - 100.0)
+    pub fn get_node_latency_percentile_ms(
+        &self,
+        host_id: Uuid,
+        percentile: f64,
+    ) -> Result<u64, MetricsError> {
+        let histogram = match self.nodes.read().unwrap().get(&host_id) {
+            Some(counters) => counters.histogram.load(),
+            None => return Err(MetricsError::Empty),
+        };
+        let mut percentiles = Self::percentiles(&histogram, &[percentile])?;
+        // SAFETY: `unwrap()` is OK here, because exactly one percentile was requested.
+        Ok(percentiles.next().unwrap())
+    }
+
     /// Returns snapshot of histogram metrics taken at the moment of calling this function. \
     /// Available metrics: min, max, mean, std_dev, median,
     ///                    percentile_75, percentile_95, percentile_98,
     ///                    percentile_99, and percentile_99_9.
     pub fn get_snapshot(&self) -> Result<Snapshot, MetricsError> {
-        let h = self.histogram.load();
+        Self::snapshot(&self.histogram.load())
+    }
 
-        let (min, max) = Self::minmax(&h)?;
+    fn snapshot(h: &Histogram) -> Result<Snapshot, MetricsError> {
+        let (min, max) = Self::minmax(h)?;
 
         let percentile_args = [50.0, 75.0, 95.0, 98.0, 99.0, 99.9];
-        let mut percentiles = Self::percentiles(&h, &percentile_args)?;
+        let mut percentiles = Self::percentiles(h, &percentile_args)?;
 
         // SAFETY: `unwrap()`s are OK here, because `Self::percentiles()` returned iterator's length
         // is equal to number of elements in `percentile_args`.
@@ -374,8 +837,8 @@ impl Metrics {
         Ok(Snapshot {
             min,
             max,
-            mean: Self::mean(&h)?,
-            stddev: Self::stddev(&h)?,
+            mean: Self::mean(h)?,
+            stddev: Self::stddev(h)?,
             median,
             percentile_75,
             percentile_95,
@@ -410,6 +873,35 @@ impl Metrics {
         self.retries_num.load(ORDER_TYPE)
     }
 
+    /// Returns counter for speculative executions started
+    pub fn get_speculative_executions_num(&self) -> u64 {
+        self.speculative_executions_num.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for wasted speculative attempts, i.e. in-flight attempts
+    /// that were cancelled because another attempt of the same request completed first
+    pub fn get_wasted_speculative_attempts_num(&self) -> u64 {
+        self.wasted_speculative_attempts_num.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for repreparations of prepared statements, performed after
+    /// a node reported a statement as unprepared
+    pub fn get_repreparations_num(&self) -> u64 {
+        self.repreparations_num.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for requests against tablet tables which were sent to a replica
+    /// of the tablet owning their token, i.e. responses which came without tablet info
+    pub fn get_tablet_routing_hits(&self) -> u64 {
+        self.tablet_routing_hits.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for requests which were sent to a node not replicating the tablet
+    /// owning their token, i.e. responses which came with tablet info
+    pub fn get_tablet_routing_misses(&self) -> u64 {
+        self.tablet_routing_misses.load(ORDER_TYPE)
+    }
+
     /// Returns mean rate of queries per second
     pub fn get_mean_rate(&self) -> f64 {
         self.meter.mean_rate()
@@ -430,6 +922,46 @@ impl Metrics {
         self.meter.fifteen_minute_rate()
     }
 
+    /// Returns counter for connections opened through the shard-aware port
+    /// which landed on the requested shard
+    pub fn get_shard_aware_connections_num(&self) -> u64 {
+        self.shard_aware_connections.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for connections opened through the shard-aware port which landed
+    /// on another shard than the requested one, e.g. because of NAT changing the source port
+    pub fn get_shard_aware_connection_misses_num(&self) -> u64 {
+        self.shard_aware_connection_misses.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for fallbacks to opening a connection through the non-shard-aware port,
+    /// after a connection through the shard-aware port failed or landed on a wrong shard
+    pub fn get_shard_aware_port_fallbacks_num(&self) -> u64 {
+        self.shard_aware_port_fallbacks.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for request attempts sent to sharded nodes on a connection
+    /// to the shard targeted by the load balancing policy
+    pub fn get_requests_on_target_shard_num(&self) -> u64 {
+        self.requests_on_target_shard.load(ORDER_TYPE)
+    }
+
+    /// Returns counter for request attempts sent to sharded nodes on a connection to another
+    /// shard than the targeted one, because the pool had no connection to the targeted shard
+    pub fn get_cross_shard_requests_num(&self) -> u64 {
+        self.cross_shard_requests.load(ORDER_TYPE)
+    }
+
+    /// Returns the distribution of sizes of request frames sent by the driver, in bytes
+    pub fn get_request_size_distribution(&self) -> SizeDistribution {
+        self.request_sizes.distribution()
+    }
+
+    /// Returns the distribution of sizes of response frames received by the driver, in bytes
+    pub fn get_response_size_distribution(&self) -> SizeDistribution {
+        self.response_sizes.distribution()
+    }
+
     /// Returns total number of active connections
     pub fn get_total_connections(&self) -> u64 {
         self.total_connections.load(ORDER_TYPE)
@@ -445,6 +977,79 @@ impl Metrics {
         self.request_timeouts.load(ORDER_TYPE)
     }
 
+    /// Returns statistics of requests executed with the given statement label,
+    /// or `None` if no such request has been executed.
+    ///
+    /// Requests executed with `QueryPager` are accounted for once per page.
+    pub fn get_label_stats(&self, key: &str, value: &str) -> Option<LabelStats> {
+        self.labels
+            .read()
+            .unwrap()
+            .get(key)
+            .and_then(|values| values.get(value))
+            .map(LabelCounters::stats)
+    }
+
+    /// Returns statistics of requests for all statement labels seen so far,
+    /// as `(key, value, stats)` triples.
+    ///
+    /// Each distinct label value is tracked separately, so labels should have a bounded
+    /// number of possible values (e.g. endpoint names rather than user ids).
+    pub fn get_all_label_stats(&self) -> Vec<(String, String, LabelStats)> {
+        self.labels
+            .read()
+            .unwrap()
+            .iter()
+            .flat_map(|(key, values)| {
+                values
+                    .iter()
+                    .map(move |(value, counters)| (key.clone(), value.clone(), counters.stats()))
+            })
+            .collect()
+    }
+
+    /// Returns statistics of the node with the given host id, or `None` if it was
+    /// neither sent a request nor had any connection opened to it.
+    pub fn get_node_stats(&self, host_id: Uuid) -> Option<NodeStats> {
+        self.nodes
+            .read()
+            .unwrap()
+            .get(&host_id)
+            .map(|counters| counters.stats(host_id))
+    }
+
+    /// Returns statistics of all nodes which were sent a request or had any connection
+    /// opened to them, taken at the moment of calling this function.
+    ///
+    /// Both nonpaged queries and pages of paged queries are accounted for.
+    pub fn get_all_node_stats(&self) -> Vec<NodeStats> {
+        self.nodes
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(host_id, counters)| counters.stats(*host_id))
+            .collect()
+    }
+
+    /// Returns the current state of the latency histogram of each node
+    /// and the sum of latencies recorded in it, in milliseconds.
+    #[cfg(feature = "metrics-prometheus")]
+    pub(crate) fn node_latency_histograms(&self) -> Vec<(Uuid, String, Histogram, u64)> {
+        self.nodes
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(host_id, counters)| {
+                (
+                    *host_id,
+                    counters.address.clone(),
+                    counters.histogram.load(),
+                    counters.latency_sum_ms.load(ORDER_TYPE),
+                )
+            })
+            .collect()
+    }
+
     // Metric implementations
 
     // histogram crate used to implement Histogram::mean() method. Why did they remove it?
@@ -549,22 +1154,201 @@ impl std::fmt::Debug for Metrics {
             .field("errors_iter_num", &self.errors_iter_num)
             .field("queries_iter_num", &self.queries_iter_num)
             .field("retries_num", &self.retries_num)
+            .field(
+                "speculative_executions_num",
+                &self.speculative_executions_num,
+            )
+            .field(
+                "wasted_speculative_attempts_num",
+                &self.wasted_speculative_attempts_num,
+            )
             .field("histogram", &h)
+            .field("latency_sum_ms", &self.latency_sum_ms)
             .field("meter", &self.meter)
+            .field("shard_aware_connections", &self.shard_aware_connections)
+            .field(
+                "shard_aware_connection_misses",
+                &self.shard_aware_connection_misses,
+            )
+            .field(
+                "shard_aware_port_fallbacks",
+                &self.shard_aware_port_fallbacks,
+            )
+            .field("requests_on_target_shard", &self.requests_on_target_shard)
+            .field("cross_shard_requests", &self.cross_shard_requests)
+            .field("request_sizes", &self.request_sizes)
+            .field("response_sizes", &self.response_sizes)
             .field("total_connections", &self.total_connections)
             .field("connection_timeouts", &self.connection_timeouts)
             .field("request_timeouts", &self.request_timeouts)
+            .field("labels", &self.labels)
+            .field("nodes", &self.nodes)
             .finish()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
+    use assert_matches::assert_matches;
     use rand::{Rng, SeedableRng};
+    use scylla_cql::frame::response::result::TableSpec;
+
+    use crate::cluster::Node;
+    use crate::observability::metrics::{LabelStats, Snapshot};
+    use crate::statement::StatementLabels;
 
-    use crate::observability::metrics::Snapshot;
+    use super::{table_label, Metrics, MetricsError, TABLE_LABEL_KEY};
 
-    use super::Metrics;
+    #[test]
+    fn labeled_requests() {
+        let metrics = Metrics::new();
+        let mut labels = StatementLabels::default();
+        labels.set("endpoint".to_owned(), "checkout".to_owned());
+        labels.set("tenant".to_owned(), "acme".to_owned());
+
+        metrics.log_labeled_request(&labels, None, 10, false);
+        labels.set("tenant".to_owned(), "other".to_owned());
+        metrics.log_labeled_request(&labels, None, 20, true);
+
+        assert_eq!(
+            metrics.get_label_stats("endpoint", "checkout"),
+            Some(LabelStats {
+                requests: 2,
+                errors: 1,
+                latency_avg_ms: 15,
+            })
+        );
+        assert_eq!(
+            metrics.get_label_stats("tenant", "acme"),
+            Some(LabelStats {
+                requests: 1,
+                errors: 0,
+                latency_avg_ms: 10,
+            })
+        );
+        assert_eq!(metrics.get_label_stats("tenant", "unknown"), None);
+        assert_eq!(metrics.get_all_label_stats().len(), 3);
+        assert_eq!(labels.to_string(), "endpoint=checkout, tenant=other");
+    }
+
+    #[test]
+    fn table_label_is_derived() {
+        let metrics = Metrics::new();
+        let table = table_label(&TableSpec::borrowed("ks", "tab"));
+        let table = Some(table.as_str());
+        let mut labels = StatementLabels::default();
+
+        metrics.log_labeled_request(&labels, table, 10, false);
+        labels.set("endpoint".to_owned(), "checkout".to_owned());
+        metrics.log_labeled_request(&labels, table, 20, true);
+        // An explicit label takes precedence over the derived one.
+        labels.set(TABLE_LABEL_KEY.to_owned(), "orders".to_owned());
+        metrics.log_labeled_request(&labels, table, 30, false);
+
+        assert_eq!(
+            metrics.get_label_stats(TABLE_LABEL_KEY, "ks.tab"),
+            Some(LabelStats {
+                requests: 2,
+                errors: 1,
+                latency_avg_ms: 15,
+            })
+        );
+        assert_eq!(
+            metrics
+                .get_label_stats(TABLE_LABEL_KEY, "orders")
+                .map(|stats| stats.requests),
+            Some(1)
+        );
+        assert_eq!(
+            metrics
+                .get_label_stats("endpoint", "checkout")
+                .map(|stats| stats.requests),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn node_requests() {
+        let metrics = Metrics::new();
+        let node = Arc::new(Node::new_for_test(None, None, None, None));
+        let other_node = Arc::new(Node::new_for_test(None, None, None, None));
+
+        metrics.log_node_request(&node, 10, false);
+        metrics.log_node_request(&node, 500, true);
+        metrics.log_node_request(&other_node, 20, false);
+        metrics.inc_node_connections(other_node.host_id, other_node.address);
+        metrics.inc_node_connections(other_node.host_id, other_node.address);
+        metrics.dec_node_connections(other_node.host_id);
+
+        let mut stats = metrics.get_all_node_stats();
+        stats.sort_by_key(|stats| stats.requests);
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].host_id, other_node.host_id);
+        assert_eq!((stats[0].requests, stats[0].errors), (1, 0));
+        assert_eq!(stats[0].connections, 1);
+        assert_eq!(stats[1].host_id, node.host_id);
+        assert_eq!((stats[1].requests, stats[1].errors), (2, 1));
+        assert_eq!(stats[1].address, node.address.to_string());
+        assert_eq!(stats[1].connections, 0);
+        // Only the latency of the successful attempt is recorded.
+        let latency = stats[1].latency.clone().unwrap();
+        assert_eq!((latency.min, latency.max), (10, 10));
+        assert_eq!(metrics.get_node_stats(node.host_id), Some(stats[1].clone()));
+        assert_eq!(
+            metrics
+                .get_node_latency_percentile_ms(node.host_id, 99.0)
+                .unwrap(),
+            10
+        );
+
+        // Nodes which were only connected to are accounted for too.
+        let connected_node = Node::new_for_test(None, None, None, None);
+        metrics.inc_node_connections(connected_node.host_id, connected_node.address);
+        let stats = metrics.get_node_stats(connected_node.host_id).unwrap();
+        assert_eq!((stats.requests, stats.connections), (0, 1));
+        assert_eq!(stats.latency, None);
+        assert_matches!(
+            metrics.get_node_latency_percentile_ms(connected_node.host_id, 99.0),
+            Err(MetricsError::Empty)
+        );
+    }
+
+    #[test]
+    fn tablet_routing() {
+        let metrics = Metrics::new();
+
+        // Responses for tables not known to use tablets count only if they carry tablet info.
+        metrics.log_tablet_routing(false, false);
+        metrics.log_tablet_routing(false, true);
+        metrics.log_tablet_routing(true, false);
+        metrics.log_tablet_routing(true, false);
+        metrics.log_tablet_routing(true, true);
+
+        assert_eq!(metrics.get_tablet_routing_hits(), 2);
+        assert_eq!(metrics.get_tablet_routing_misses(), 2);
+    }
+
+    #[test]
+    fn shard_awareness() {
+        let metrics = Metrics::new();
+
+        metrics.log_shard_aware_connection(true);
+        metrics.log_shard_aware_connection(false);
+        metrics.log_shard_aware_connection(false);
+        metrics.inc_shard_aware_port_fallbacks();
+
+        metrics.log_shard_routing(true);
+        metrics.log_shard_routing(true);
+        metrics.log_shard_routing(false);
+
+        assert_eq!(metrics.get_shard_aware_connections_num(), 1);
+        assert_eq!(metrics.get_shard_aware_connection_misses_num(), 2);
+        assert_eq!(metrics.get_shard_aware_port_fallbacks_num(), 1);
+        assert_eq!(metrics.get_requests_on_target_shard_num(), 2);
+        assert_eq!(metrics.get_cross_shard_requests_num(), 1);
+    }
 
     // A regression test for a bug where we would return
     // the number of observations in the bucket for the given percentile.
@@ -3,10 +3,17 @@
 //! - driver-side tracing,
 //! - cluster-side tracing,
 //! - request execution history,
-//! - driver metrics.
+//! - notifications about repreparation of prepared statements,
+//! - driver metrics, and their export in the Prometheus format,
+//! - OpenTelemetry spans of request attempts.
 
 pub(crate) mod driver_tracing;
 pub mod history;
 #[cfg(feature = "metrics")]
 pub mod metrics;
+#[cfg(feature = "opentelemetry-031")]
+pub mod opentelemetry;
+#[cfg(feature = "metrics-prometheus")]
+pub mod prometheus;
+pub mod repreparation;
 pub mod tracing;
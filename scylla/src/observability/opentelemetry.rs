@@ -0,0 +1,181 @@
+//! Integration with [OpenTelemetry](https://opentelemetry.io/) tracing.
+//!
+//! With the `opentelemetry-031` feature enabled, the driver creates a span
+//! of kind [`SpanKind::Client`] for each attempt of executing a request,
+//! using the globally registered tracer provider. The span is a child of
+//! the OpenTelemetry context that was current when the request was issued,
+//! and carries the following attributes:
+//! - `db.system.name` - always `scylladb`,
+//! - `server.address`, `server.port` - the address of the node the attempt was sent to,
+//! - `db.scylladb.host_id` - the host id of that node,
+//! - `db.scylladb.shard` - the shard the attempt was sent to, if the node is sharded,
+//! - `db.cassandra.consistency_level` - the consistency used by the attempt,
+//! - `db.scylladb.retry_count` - the number of retries that preceded the attempt.
+//!
+//! Failed attempts have their span status set to [`Status::Error`].
+//!
+//! Optionally, the context of the attempt span can be propagated to the database
+//! in the custom payload of the request, using the globally registered text map propagator.
+//! See [`SessionBuilder::opentelemetry_context_propagation`](crate::client::session_builder::GenericSessionBuilder::opentelemetry_context_propagation).
+
+use std::collections::HashMap;
+use std::fmt::Display;
+
+use bytes::Bytes;
+use opentelemetry::global::{self, BoxedTracer};
+use opentelemetry::propagation::Injector;
+use opentelemetry::trace::{SpanKind, Status, TraceContextExt, Tracer};
+use opentelemetry::{Context, InstrumentationScope, KeyValue};
+use scylla_cql::Consistency;
+
+use crate::cluster::NodeRef;
+use crate::routing::Shard;
+
+fn tracer() -> BoxedTracer {
+    // The tracer is obtained anew for each span, so that a tracer provider
+    // registered after the session is created is respected.
+    global::tracer_with_scope(
+        InstrumentationScope::builder("scylla")
+            .with_version(env!("CARGO_PKG_VERSION"))
+            .build(),
+    )
+}
+
+/// Starts a span for an attempt of executing a request,
+/// and returns the context to run the attempt in.
+pub(crate) fn start_attempt_span(
+    parent: &Context,
+    node: NodeRef<'_>,
+    shard: Option<Shard>,
+    consistency: Consistency,
+    retry_count: usize,
+) -> Context {
+    let address = node.address.into_inner();
+    let mut attributes = vec![
+        KeyValue::new("db.system.name", "scylladb"),
+        KeyValue::new("server.address", address.ip().to_string()),
+        KeyValue::new("server.port", i64::from(address.port())),
+        KeyValue::new("db.scylladb.host_id", node.host_id.to_string()),
+        KeyValue::new("db.cassandra.consistency_level", consistency.to_string()),
+        KeyValue::new("db.scylladb.retry_count", retry_count as i64),
+    ];
+    if let Some(shard) = shard {
+        attributes.push(KeyValue::new("db.scylladb.shard", i64::from(shard)));
+    }
+
+    let tracer = tracer();
+    let span = tracer
+        .span_builder("CQL request attempt")
+        .with_kind(SpanKind::Client)
+        .with_attributes(attributes)
+        .start_with_context(&tracer, parent);
+    parent.with_span(span)
+}
+
+/// Ends the span of an attempt, marking it as failed if the attempt returned an error.
+pub(crate) fn end_attempt_span(cx: &Context, error: Option<&impl Display>) {
+    let span = cx.span();
+    if let Some(error) = error {
+        span.set_status(Status::error(error.to_string()));
+    }
+    span.end();
+}
+
+struct PayloadInjector<'a>(&'a mut HashMap<String, Bytes>);
+
+impl Injector for PayloadInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        self.0.insert(key.to_owned(), Bytes::from(value));
+    }
+}
+
+/// Returns the custom payload extended with the current OpenTelemetry context,
+/// or `None` if the propagator did not inject anything.
+pub(crate) fn inject_current_context(
+    custom_payload: Option<&HashMap<String, Bytes>>,
+) -> Option<HashMap<String, Bytes>> {
+    let cx = Context::current();
+    if !cx.span().span_context().is_valid() {
+        return None;
+    }
+
+    let mut payload = custom_payload.cloned().unwrap_or_default();
+    let len_before = payload.len();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut PayloadInjector(&mut payload))
+    });
+    (payload.len() != len_before).then_some(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use bytes::Bytes;
+    use opentelemetry::propagation::{Injector, TextMapPropagator};
+    use opentelemetry::trace::{
+        SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+    };
+    use opentelemetry::Context;
+
+    use super::{inject_current_context, PayloadInjector};
+
+    // A minimal propagator in the W3C Trace Context format, as the API crate doesn't ship one.
+    #[derive(Debug)]
+    struct TestPropagator;
+
+    impl TextMapPropagator for TestPropagator {
+        fn inject_context(&self, cx: &Context, injector: &mut dyn Injector) {
+            let span_context = cx.span().span_context().clone();
+            injector.set(
+                "traceparent",
+                format!(
+                    "00-{}-{}-01",
+                    span_context.trace_id(),
+                    span_context.span_id()
+                ),
+            );
+        }
+
+        fn extract_with_context(
+            &self,
+            cx: &Context,
+            _extractor: &dyn opentelemetry::propagation::Extractor,
+        ) -> Context {
+            cx.clone()
+        }
+
+        fn fields(&self) -> opentelemetry::propagation::text_map_propagator::FieldIter<'_> {
+            opentelemetry::propagation::text_map_propagator::FieldIter::new(&[])
+        }
+    }
+
+    #[test]
+    fn payload_injector_inserts_entries() {
+        let mut payload = HashMap::new();
+        let mut injector = PayloadInjector(&mut payload);
+        TestPropagator.inject_context(
+            &Context::new().with_remote_span_context(SpanContext::new(
+                TraceId::from(1),
+                SpanId::from(2),
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            )),
+            &mut injector,
+        );
+        assert_eq!(
+            payload.get("traceparent"),
+            Some(&Bytes::from_static(
+                b"00-00000000000000000000000000000001-0000000000000002-01"
+            ))
+        );
+    }
+
+    #[test]
+    fn nothing_is_injected_without_active_span() {
+        let payload = HashMap::from([("key".to_owned(), Bytes::from_static(b"value"))]);
+        assert_eq!(inject_current_context(Some(&payload)), None);
+        assert_eq!(inject_current_context(None), None);
+    }
+}
@@ -0,0 +1,461 @@
+//! Exporting driver metrics in the Prometheus text exposition format.
+//!
+//! The text returned by [`Session::encode_prometheus_metrics`](crate::client::session::Session::encode_prometheus_metrics) (or by
+//! [`Metrics::encode_prometheus`], which lacks the connection pool metrics)
+//! can be served as is from an HTTP endpoint scraped by Prometheus,
+//! with [`CONTENT_TYPE`] as its content type.
+//!
+//! All metric names are prefixed with `scylla_`. Latencies are reported in milliseconds.
+//!
+//! # Example
+//! ```rust
+//! # use scylla::client::session::Session;
+//! # fn check_only_compiles(session: &Session) {
+//! let text = session.encode_prometheus_metrics();
+//! // Serve `text` with `scylla::observability::prometheus::CONTENT_TYPE` content type.
+//! # }
+//! ```
+
+use std::fmt::{Display, Write as _};
+
+use histogram::Histogram;
+
+use crate::cluster::ClusterState;
+use crate::observability::metrics::{Metrics, SizeDistribution};
+
+/// Content type of the text exposition format, to be set on HTTP responses serving the metrics.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Upper bounds of the buckets of the latency histogram, in milliseconds.
+const LATENCY_BUCKETS_MS: [u64; 14] = [
+    1, 2, 5, 10, 20, 50, 100, 200, 500, 1000, 2000, 5000, 10000, 30000,
+];
+
+impl Metrics {
+    /// Encodes the metrics in the Prometheus text exposition format.
+    ///
+    /// See the [module documentation](crate::observability::prometheus)
+    /// for more information.
+    pub fn encode_prometheus(&self) -> String {
+        encode(self, None)
+    }
+}
+
+/// Encodes the metrics, and the connection pool sizes of nodes in the given cluster state.
+pub(crate) fn encode(metrics: &Metrics, cluster_state: Option<&ClusterState>) -> String {
+    let mut encoder = Encoder::default();
+
+    let counters = [
+        (
+            "queries_total",
+            "Number of nonpaged queries sent.",
+            metrics.get_queries_num(),
+        ),
+        (
+            "query_errors_total",
+            "Number of nonpaged queries which failed.",
+            metrics.get_errors_num(),
+        ),
+        (
+            "paged_queries_total",
+            "Number of pages requested by paged queries.",
+            metrics.get_queries_iter_num(),
+        ),
+        (
+            "paged_query_errors_total",
+            "Number of pages of paged queries which failed to be fetched.",
+            metrics.get_errors_iter_num(),
+        ),
+        (
+            "retries_total",
+            "Number of times a retry policy decided to retry a query.",
+            metrics.get_retries_num(),
+        ),
+        (
+            "speculative_executions_total",
+            "Number of speculative executions started.",
+            metrics.get_speculative_executions_num(),
+        ),
+        (
+            "wasted_speculative_attempts_total",
+            "Number of attempts cancelled because another attempt of the same request completed first.",
+            metrics.get_wasted_speculative_attempts_num(),
+        ),
+        (
+            "repreparations_total",
+            "Number of prepared statements reprepared after a node reported them as unprepared.",
+            metrics.get_repreparations_num(),
+        ),
+        (
+            "tablet_routing_hits_total",
+            "Number of requests against tablet tables sent to a replica of the tablet.",
+            metrics.get_tablet_routing_hits(),
+        ),
+        (
+            "tablet_routing_misses_total",
+            "Number of requests sent to a node not replicating the tablet.",
+            metrics.get_tablet_routing_misses(),
+        ),
+        (
+            "shard_aware_connections_total",
+            "Number of connections opened through the shard-aware port which landed on the requested shard.",
+            metrics.get_shard_aware_connections_num(),
+        ),
+        (
+            "shard_aware_connection_misses_total",
+            "Number of connections opened through the shard-aware port which landed on another shard.",
+            metrics.get_shard_aware_connection_misses_num(),
+        ),
+        (
+            "shard_aware_port_fallbacks_total",
+            "Number of connections opened through the non-shard-aware port as a fallback.",
+            metrics.get_shard_aware_port_fallbacks_num(),
+        ),
+        (
+            "requests_on_target_shard_total",
+            "Number of request attempts sent on a connection to the targeted shard.",
+            metrics.get_requests_on_target_shard_num(),
+        ),
+        (
+            "cross_shard_requests_total",
+            "Number of request attempts sent on a connection to another shard than the targeted one.",
+            metrics.get_cross_shard_requests_num(),
+        ),
+        (
+            "connection_timeouts_total",
+            "Number of timeouts of opening a connection.",
+            metrics.get_connection_timeouts(),
+        ),
+        (
+            "request_timeouts_total",
+            "Number of requests which exceeded the client timeout.",
+            metrics.get_request_timeouts(),
+        ),
+    ];
+    for (name, help, value) in counters {
+        encoder.header(name, "counter", help);
+        encoder.sample(name, &[], value);
+    }
+
+    encoder.header("connections", "gauge", "Number of open connections.");
+    encoder.sample("connections", &[], metrics.get_total_connections());
+
+    encoder.header(
+        "request_rate",
+        "gauge",
+        "Rate of queries per second, over a time window.",
+    );
+    for (window, rate) in [
+        ("mean", metrics.get_mean_rate()),
+        ("1m", metrics.get_one_minute_rate()),
+        ("5m", metrics.get_five_minute_rate()),
+        ("15m", metrics.get_fifteen_minute_rate()),
+    ] {
+        encoder.sample("request_rate", &[("window", window)], rate);
+    }
+
+    let (histogram, sum) = metrics.latency_histogram();
+    encoder.header(
+        "latency_ms",
+        "histogram",
+        "Latencies of queries and pages, in milliseconds.",
+    );
+    encoder.histogram("latency_ms", &[], &histogram, sum);
+
+    for (name, help, distribution) in [
+        (
+            "request_size_bytes",
+            "Sizes of request frames, in bytes.",
+            metrics.get_request_size_distribution(),
+        ),
+        (
+            "response_size_bytes",
+            "Sizes of response frames, in bytes.",
+            metrics.get_response_size_distribution(),
+        ),
+    ] {
+        encoder.header(name, "histogram", help);
+        encoder.size_distribution(name, &distribution);
+    }
+
+    let label_stats = metrics.get_all_label_stats();
+    encoder.header(
+        "label_requests_total",
+        "counter",
+        "Number of requests carrying a statement label.",
+    );
+    for (key, value, stats) in &label_stats {
+        encoder.sample(
+            "label_requests_total",
+            &[("key", key), ("value", value)],
+            stats.requests,
+        );
+    }
+    encoder.header(
+        "label_request_errors_total",
+        "counter",
+        "Number of failed requests carrying a statement label.",
+    );
+    for (key, value, stats) in &label_stats {
+        encoder.sample(
+            "label_request_errors_total",
+            &[("key", key), ("value", value)],
+            stats.errors,
+        );
+    }
+
+    let node_stats = metrics.get_all_node_stats();
+    encoder.header(
+        "node_requests_total",
+        "counter",
+        "Number of request attempts sent to a node.",
+    );
+    for stats in &node_stats {
+        encoder.sample(
+            "node_requests_total",
+            &[
+                ("host_id", &stats.host_id.to_string()),
+                ("node", &stats.address),
+            ],
+            stats.requests,
+        );
+    }
+    encoder.header(
+        "node_request_errors_total",
+        "counter",
+        "Number of request attempts sent to a node which failed.",
+    );
+    for stats in &node_stats {
+        encoder.sample(
+            "node_request_errors_total",
+            &[
+                ("host_id", &stats.host_id.to_string()),
+                ("node", &stats.address),
+            ],
+            stats.errors,
+        );
+    }
+
+    encoder.header(
+        "node_latency_ms",
+        "histogram",
+        "Latencies of successful request attempts sent to a node, in milliseconds.",
+    );
+    for (host_id, address, histogram, sum) in metrics.node_latency_histograms() {
+        encoder.histogram(
+            "node_latency_ms",
+            &[("host_id", &host_id.to_string()), ("node", &address)],
+            &histogram,
+            sum,
+        );
+    }
+
+    if let Some(cluster_state) = cluster_state {
+        encoder.header(
+            "node_pool_connections",
+            "gauge",
+            "Number of working connections in the connection pool of a node.",
+        );
+        for node in cluster_state.get_nodes_info() {
+            let connections = node
+                .get_working_connections()
+                .map_or(0, |connections| connections.len());
+            encoder.sample(
+                "node_pool_connections",
+                &[
+                    ("host_id", &node.host_id.to_string()),
+                    ("node", &node.address.to_string()),
+                    ("datacenter", node.datacenter.as_deref().unwrap_or("")),
+                    ("rack", node.rack.as_deref().unwrap_or("")),
+                ],
+                connections,
+            );
+        }
+    }
+
+    encoder.out
+}
+
+#[derive(Default)]
+struct Encoder {
+    out: String,
+}
+
+impl Encoder {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        // Writing to a String never fails.
+        let _ = writeln!(self.out, "# HELP scylla_{name} {help}");
+        let _ = writeln!(self.out, "# TYPE scylla_{name} {kind}");
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl Display) {
+        let _ = write!(self.out, "scylla_{name}");
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (label, label_value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{label}=\"");
+                for c in label_value.chars() {
+                    match c {
+                        '\\' => self.out.push_str("\\\\"),
+                        '"' => self.out.push_str("\\\""),
+                        '\n' => self.out.push_str("\\n"),
+                        c => self.out.push(c),
+                    }
+                }
+                self.out.push('"');
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {value}");
+    }
+
+    /// Writes the samples of a frame size histogram, bucketed by its configured bounds.
+    fn size_distribution(&mut self, name: &str, distribution: &SizeDistribution) {
+        let bucket_name = format!("{name}_bucket");
+        let mut cumulative_count = 0;
+        for (bound, count) in &distribution.buckets {
+            cumulative_count += count;
+            self.sample(
+                &bucket_name,
+                &[("le", &bound.to_string())],
+                cumulative_count,
+            );
+        }
+        self.sample(&bucket_name, &[("le", "+Inf")], distribution.count);
+        self.sample(&format!("{name}_sum"), &[], distribution.sum);
+        self.sample(&format!("{name}_count"), &[], distribution.count);
+    }
+
+    /// Writes the samples of a latency histogram, bucketed by [LATENCY_BUCKETS_MS].
+    fn histogram(&mut self, name: &str, labels: &[(&str, &str)], histogram: &Histogram, sum: u64) {
+        let mut bucket_counts = [0_u64; LATENCY_BUCKETS_MS.len()];
+        let mut count = 0;
+        for bucket in histogram {
+            count += bucket.count();
+            for (bound, bucket_count) in LATENCY_BUCKETS_MS.iter().zip(bucket_counts.iter_mut()) {
+                if bucket.end() <= *bound {
+                    *bucket_count += bucket.count();
+                }
+            }
+        }
+
+        let bucket_name = format!("{name}_bucket");
+        let bounds = LATENCY_BUCKETS_MS.iter().map(|bound| bound.to_string());
+        let bucket_counts = bucket_counts.into_iter().chain([count]);
+        for (bound, bucket_count) in bounds.chain(["+Inf".to_owned()]).zip(bucket_counts) {
+            let mut bucket_labels = labels.to_vec();
+            bucket_labels.push(("le", &bound));
+            self.sample(&bucket_name, &bucket_labels, bucket_count);
+        }
+        self.sample(&format!("{name}_sum"), labels, sum);
+        self.sample(&format!("{name}_count"), labels, count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::Encoder;
+    use crate::cluster::Node;
+    use crate::observability::metrics::Metrics;
+
+    #[test]
+    fn label_values_are_escaped() {
+        let mut encoder = Encoder::default();
+        encoder.sample("metric", &[("a", "x\"y\\z\n"), ("b", "plain")], 1);
+        encoder.sample("metric", &[], 2.5);
+        assert_eq!(
+            encoder.out,
+            "scylla_metric{a=\"x\\\"y\\\\z\\n\",b=\"plain\"} 1\nscylla_metric 2.5\n"
+        );
+    }
+
+    #[test]
+    fn latency_histogram_is_cumulative() {
+        let metrics = Metrics::new();
+        for latency in [0, 1, 3, 7, 7, 150, 40000] {
+            metrics.log_query_latency(latency).unwrap();
+        }
+
+        let text = metrics.encode_prometheus();
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|line| line.starts_with("scylla_latency_ms"))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                "scylla_latency_ms_bucket{le=\"1\"} 2",
+                "scylla_latency_ms_bucket{le=\"2\"} 2",
+                "scylla_latency_ms_bucket{le=\"5\"} 3",
+                "scylla_latency_ms_bucket{le=\"10\"} 5",
+                "scylla_latency_ms_bucket{le=\"20\"} 5",
+                "scylla_latency_ms_bucket{le=\"50\"} 5",
+                "scylla_latency_ms_bucket{le=\"100\"} 5",
+                "scylla_latency_ms_bucket{le=\"200\"} 6",
+                "scylla_latency_ms_bucket{le=\"500\"} 6",
+                "scylla_latency_ms_bucket{le=\"1000\"} 6",
+                "scylla_latency_ms_bucket{le=\"2000\"} 6",
+                "scylla_latency_ms_bucket{le=\"5000\"} 6",
+                "scylla_latency_ms_bucket{le=\"10000\"} 6",
+                "scylla_latency_ms_bucket{le=\"30000\"} 6",
+                "scylla_latency_ms_bucket{le=\"+Inf\"} 7",
+                "scylla_latency_ms_sum 40168",
+                "scylla_latency_ms_count 7",
+            ]
+        );
+        assert!(text.contains("# TYPE scylla_queries_total counter\nscylla_queries_total 0\n"));
+    }
+
+    #[test]
+    fn node_latency_histograms_are_labeled() {
+        let metrics = Metrics::new();
+        let node = Arc::new(Node::new_for_test(None, None, None, None));
+        metrics.log_node_request(&node, 3, false);
+
+        let text = metrics.encode_prometheus();
+        let labels = format!("host_id=\"{}\",node=\"{}\"", node.host_id, node.address);
+        assert!(text.contains(&format!(
+            "scylla_node_latency_ms_bucket{{{labels},le=\"2\"}} 0\n"
+        )));
+        assert!(text.contains(&format!(
+            "scylla_node_latency_ms_bucket{{{labels},le=\"5\"}} 1\n"
+        )));
+        assert!(text.contains(&format!("scylla_node_latency_ms_sum{{{labels}}} 3\n")));
+        assert!(text.contains(&format!("scylla_node_latency_ms_count{{{labels}}} 1\n")));
+    }
+
+    #[test]
+    fn frame_sizes_are_bucketed() {
+        let metrics = Metrics::with_frame_size_buckets(vec![1000, 100]);
+        metrics.log_frame_sizes(50, 100);
+        metrics.log_frame_sizes(150, 5000);
+
+        let request_sizes = metrics.get_request_size_distribution();
+        assert_eq!(request_sizes.buckets, [(100, 1), (1000, 1)]);
+        assert_eq!(
+            (
+                request_sizes.overflow,
+                request_sizes.count,
+                request_sizes.sum
+            ),
+            (0, 2, 200)
+        );
+        let response_sizes = metrics.get_response_size_distribution();
+        assert_eq!(response_sizes.buckets, [(100, 1), (1000, 0)]);
+        assert_eq!((response_sizes.overflow, response_sizes.max), (1, 5000));
+
+        let text = metrics.encode_prometheus();
+        assert!(text.contains(
+            "scylla_response_size_bytes_bucket{le=\"100\"} 1\n\
+             scylla_response_size_bytes_bucket{le=\"1000\"} 1\n\
+             scylla_response_size_bytes_bucket{le=\"+Inf\"} 2\n\
+             scylla_response_size_bytes_sum 5100\n\
+             scylla_response_size_bytes_count 2\n"
+        ));
+    }
+}
@@ -0,0 +1,41 @@
+//! Observing transparent repreparation of prepared statements.
+//!
+//! When a node responds to an EXECUTE (or a BATCH) with an `Unprepared` error,
+//! e.g. because its prepared statement cache was cleared after a restart or a schema change,
+//! the driver prepares the statement again on that node and resends the request.
+//! This is invisible to the application, so a [RepreparationListener] can be set
+//! with [SessionBuilder::repreparation_listener](crate::client::session_builder::SessionBuilder::repreparation_listener)
+//! to get notified about every repreparation. With the `metrics` feature enabled,
+//! repreparations are also counted in [Metrics](crate::observability::metrics::Metrics).
+
+use std::fmt::Debug;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use bytes::Bytes;
+
+/// Describes a single repreparation of a prepared statement.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RepreparationEvent {
+    /// Id of the statement which the node reported as unprepared.
+    pub statement_id: Bytes,
+
+    /// Address of the node on which the statement was reprepared.
+    pub node_address: SocketAddr,
+
+    /// Time it took to reprepare the statement.
+    pub latency: Duration,
+
+    /// Whether the statement was successfully reprepared. If not, the request
+    /// which triggered the repreparation fails.
+    pub succeeded: bool,
+}
+
+/// Gets notified whenever the driver transparently reprepares a statement.
+///
+/// The listener is called on the connection's task, so it should return quickly.
+pub trait RepreparationListener: Debug + Send + Sync {
+    /// Called after a statement has been reprepared, or the repreparation has failed.
+    fn on_repreparation(&self, event: &RepreparationEvent);
+}
@@ -13,7 +13,9 @@ use crate::DeserializeRow;
 use itertools::Itertools;
 use scylla_cql::value::CqlTimeuuid;
 use std::collections::HashMap;
+use std::fmt::{self, Write as _};
 use std::net::IpAddr;
+use std::time::Duration;
 
 /// Tracing info retrieved from `system_traces.sessions`
 /// with all events from `system_traces.events`
@@ -102,6 +104,285 @@ impl TracingInfo {
             .unique()
             .collect()
     }
+
+    /// Groups the events by the node and the thread which generated them,
+    /// and computes how long each stage of the execution took.
+    ///
+    /// See [`TracingTree`] for details.
+    pub fn into_tree(self) -> TracingTree {
+        let mut nodes: Vec<TracingTreeNode> = Vec::new();
+        for event in self.events {
+            let node = match nodes.iter_mut().position(|n| n.source == event.source) {
+                Some(idx) => &mut nodes[idx],
+                None => {
+                    nodes.push(TracingTreeNode {
+                        source: event.source,
+                        threads: Vec::new(),
+                    });
+                    nodes.last_mut().unwrap()
+                }
+            };
+            let thread = match node.threads.iter_mut().position(|t| t.name == event.thread) {
+                Some(idx) => &mut node.threads[idx],
+                None => {
+                    node.threads.push(TracingTreeThread {
+                        name: event.thread,
+                        stages: Vec::new(),
+                    });
+                    node.threads.last_mut().unwrap()
+                }
+            };
+            let elapsed = event.source_elapsed.and_then(micros);
+            if let Some(previous) = thread.stages.last_mut() {
+                previous.duration = previous
+                    .elapsed
+                    .zip(elapsed)
+                    .and_then(|(start, end)| end.checked_sub(start));
+            }
+            thread.stages.push(TracingStage {
+                activity: event.activity,
+                elapsed,
+                duration: None,
+            });
+        }
+
+        TracingTree {
+            command: self.command,
+            request: self.request,
+            coordinator: self.coordinator,
+            duration: self.duration.and_then(micros),
+            nodes,
+        }
+    }
+}
+
+fn micros(value: i32) -> Option<Duration> {
+    u64::try_from(value).ok().map(Duration::from_micros)
+}
+
+/// Events of a [`TracingInfo`] grouped by the node and the thread which generated them,
+/// created with [`TracingInfo::into_tree`].
+///
+/// Nodes, threads and stages are kept in the order in which their events were recorded.
+///
+/// The tree can be rendered in a human-readable form with [`Display`](fmt::Display),
+/// or as JSON with [`TracingTree::to_json`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TracingTree {
+    /// Kind of a command that was executed, see [`TracingInfo::command`].
+    pub command: Option<String>,
+
+    /// Kind of the request, see [`TracingInfo::request`].
+    pub request: Option<String>,
+
+    /// Address of the coordinator node that executed the query.
+    pub coordinator: Option<IpAddr>,
+
+    /// Duration of the query execution.
+    pub duration: Option<Duration>,
+
+    /// Nodes which took part in the execution.
+    pub nodes: Vec<TracingTreeNode>,
+}
+
+/// Events generated by a single node, see [`TracingTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TracingTreeNode {
+    /// Address of the node.
+    pub source: Option<IpAddr>,
+
+    /// Threads of the node which generated events.
+    pub threads: Vec<TracingTreeThread>,
+}
+
+/// Events generated by a single thread of a node, see [`TracingTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TracingTreeThread {
+    /// Name of the thread, e.g. `shard 0`.
+    pub name: Option<String>,
+
+    /// Stages of the execution on this thread, one per event.
+    pub stages: Vec<TracingStage>,
+}
+
+impl TracingTreeThread {
+    /// Time elapsed between the first and the last event of the thread.
+    pub fn duration(&self) -> Option<Duration> {
+        let first = self.stages.first()?.elapsed?;
+        let last = self.stages.last()?.elapsed?;
+        last.checked_sub(first)
+    }
+}
+
+/// A stage of the execution, starting with an event, see [`TracingTree`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TracingStage {
+    /// Description of the event which started the stage.
+    pub activity: Option<String>,
+
+    /// Time elapsed since the start of the query execution on the node
+    /// when the event was generated.
+    pub elapsed: Option<Duration>,
+
+    /// Time until the next event of the same thread.
+    /// `None` for the last event of a thread.
+    pub duration: Option<Duration>,
+}
+
+impl TracingTree {
+    /// Renders the tree as a JSON object. Durations are given in microseconds,
+    /// missing values are rendered as `null`.
+    ///
+    /// ```json
+    /// {"command":"QUERY","request":"Execute CQL3 query","coordinator":"127.0.0.1","duration_us":1234,
+    ///  "nodes":[{"source":"127.0.0.1","threads":[{"name":"shard 0","duration_us":120,
+    ///  "stages":[{"activity":"Parsing a statement","elapsed_us":5,"duration_us":100}, ...]}]}]}
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{");
+        write_json_string(&mut out, "command", self.command.as_deref());
+        out.push(',');
+        write_json_string(&mut out, "request", self.request.as_deref());
+        out.push(',');
+        write_json_string(
+            &mut out,
+            "coordinator",
+            self.coordinator.map(|c| c.to_string()).as_deref(),
+        );
+        out.push(',');
+        write_json_micros(&mut out, "duration_us", self.duration);
+        out.push_str(",\"nodes\":[");
+        for (i, node) in self.nodes.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            out.push('{');
+            write_json_string(
+                &mut out,
+                "source",
+                node.source.map(|s| s.to_string()).as_deref(),
+            );
+            out.push_str(",\"threads\":[");
+            for (j, thread) in node.threads.iter().enumerate() {
+                if j > 0 {
+                    out.push(',');
+                }
+                out.push('{');
+                write_json_string(&mut out, "name", thread.name.as_deref());
+                out.push(',');
+                write_json_micros(&mut out, "duration_us", thread.duration());
+                out.push_str(",\"stages\":[");
+                for (k, stage) in thread.stages.iter().enumerate() {
+                    if k > 0 {
+                        out.push(',');
+                    }
+                    out.push('{');
+                    write_json_string(&mut out, "activity", stage.activity.as_deref());
+                    out.push(',');
+                    write_json_micros(&mut out, "elapsed_us", stage.elapsed);
+                    out.push(',');
+                    write_json_micros(&mut out, "duration_us", stage.duration);
+                    out.push('}');
+                }
+                out.push_str("]}");
+            }
+            out.push_str("]}");
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+// Writing to a String never fails, so results of `write!` are ignored below.
+
+fn write_json_string(out: &mut String, name: &str, value: Option<&str>) {
+    let _ = write!(out, "\"{name}\":");
+    let Some(value) = value else {
+        out.push_str("null");
+        return;
+    };
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_json_micros(out: &mut String, name: &str, value: Option<Duration>) {
+    match value {
+        Some(value) => {
+            let _ = write!(out, "\"{name}\":{}", value.as_micros());
+        }
+        None => {
+            let _ = write!(out, "\"{name}\":null");
+        }
+    }
+}
+
+fn or_unknown(value: Option<impl fmt::Display>) -> String {
+    value.map_or_else(|| "?".to_owned(), |value| value.to_string())
+}
+
+fn display_micros(duration: Option<Duration>) -> String {
+    or_unknown(duration.map(|d| format!("{} µs", d.as_micros())))
+}
+
+/// Renders the tree in a human-readable form, e.g.:
+///
+/// ```text
+/// QUERY Execute CQL3 query, coordinator 127.0.0.1, 1234 µs
+/// node 127.0.0.1
+///   shard 0 (120 µs)
+///     [     5 µs] Parsing a statement (+100 µs)
+///     [   105 µs] Processing a statement (+20 µs)
+///     [   125 µs] Request complete
+/// ```
+impl fmt::Display for TracingTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} {}, coordinator {}, {}",
+            or_unknown(self.command.as_ref()),
+            or_unknown(self.request.as_ref()),
+            or_unknown(self.coordinator),
+            display_micros(self.duration),
+        )?;
+        for node in &self.nodes {
+            writeln!(f, "node {}", or_unknown(node.source))?;
+            for thread in &node.threads {
+                writeln!(
+                    f,
+                    "  {} ({})",
+                    or_unknown(thread.name.as_ref()),
+                    display_micros(thread.duration()),
+                )?;
+                for stage in &thread.stages {
+                    write!(
+                        f,
+                        "    [{:>6} µs] {}",
+                        or_unknown(stage.elapsed.map(|e| e.as_micros())),
+                        stage.activity.as_deref().unwrap_or_default()
+                    )?;
+                    if let Some(duration) = stage.duration {
+                        write!(f, " (+{} µs)", duration.as_micros())?;
+                    }
+                    writeln!(f)?;
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 // A query used to query TracingInfo from system_traces.sessions
@@ -113,3 +394,95 @@ pub(crate) const TRACES_SESSION_QUERY_STR: &str =
 pub(crate) const TRACES_EVENTS_QUERY_STR: &str =
     "SELECT event_id, activity, source, source_elapsed, thread \
     FROM system_traces.events WHERE session_id = ?";
+
+#[cfg(test)]
+mod tests {
+    use std::net::{IpAddr, Ipv4Addr};
+    use std::time::Duration;
+
+    use scylla_cql::value::CqlTimeuuid;
+    use uuid::Uuid;
+
+    use super::{TracingEvent, TracingInfo};
+
+    fn event(source: u8, thread: &str, elapsed: i32, activity: &str) -> TracingEvent {
+        TracingEvent {
+            event_id: CqlTimeuuid::from(Uuid::nil()),
+            activity: Some(activity.to_owned()),
+            source: Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, source))),
+            source_elapsed: Some(elapsed),
+            thread: Some(thread.to_owned()),
+        }
+    }
+
+    fn tracing_info() -> TracingInfo {
+        TracingInfo {
+            client: None,
+            command: Some("QUERY".to_owned()),
+            coordinator: Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))),
+            duration: Some(300),
+            parameters: None,
+            request: Some("Execute CQL3 query".to_owned()),
+            started_at: None,
+            events: vec![
+                event(1, "shard 0", 5, "Parsing a statement"),
+                event(1, "shard 0", 105, "Sending a mutation to /127.0.0.2"),
+                event(2, "shard 1", 10, "Message received from /127.0.0.1"),
+                event(1, "shard 1", 50, "Querying cache"),
+                event(2, "shard 1", 40, "Applying mutation \"x\""),
+                event(1, "shard 0", 250, "Request complete"),
+            ],
+        }
+    }
+
+    #[test]
+    fn tracing_tree_groups_events() {
+        let tree = tracing_info().into_tree();
+        assert_eq!(tree.duration, Some(Duration::from_micros(300)));
+        assert_eq!(tree.nodes.len(), 2);
+
+        let first = &tree.nodes[0];
+        assert_eq!(first.source, Some(IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))));
+        let threads: Vec<_> = first.threads.iter().map(|t| t.name.as_deref()).collect();
+        assert_eq!(threads, [Some("shard 0"), Some("shard 1")]);
+
+        let shard_0 = &first.threads[0];
+        let durations: Vec<_> = shard_0.stages.iter().map(|s| s.duration).collect();
+        assert_eq!(
+            durations,
+            [
+                Some(Duration::from_micros(100)),
+                Some(Duration::from_micros(145)),
+                None
+            ]
+        );
+        assert_eq!(shard_0.duration(), Some(Duration::from_micros(245)));
+        assert_eq!(first.threads[1].duration(), Some(Duration::ZERO));
+
+        let second = &tree.nodes[1];
+        assert_eq!(second.threads.len(), 1);
+        assert_eq!(
+            second.threads[0].stages[0].duration,
+            Some(Duration::from_micros(30))
+        );
+    }
+
+    #[test]
+    fn tracing_tree_rendering() {
+        let tree = tracing_info().into_tree();
+        let text = tree.to_string();
+        assert!(text.starts_with(
+            "QUERY Execute CQL3 query, coordinator 127.0.0.1, 300 µs\n\
+            node 127.0.0.1\n  shard 0 (245 µs)\n    [     5 µs] Parsing a statement (+100 µs)\n"
+        ));
+        assert!(text.ends_with("    [    40 µs] Applying mutation \"x\"\n"));
+
+        let json = tree.to_json();
+        assert!(json.starts_with(
+            r#"{"command":"QUERY","request":"Execute CQL3 query","coordinator":"127.0.0.1","duration_us":300,"nodes":[{"source":"127.0.0.1","threads":[{"name":"shard 0","duration_us":245,"stages":[{"activity":"Parsing a statement","elapsed_us":5,"duration_us":100},"#
+        ));
+        assert!(json.ends_with(
+            r#"{"activity":"Applying mutation \"x\"","elapsed_us":40,"duration_us":null}]}]}]}"#
+        ));
+    }
+}
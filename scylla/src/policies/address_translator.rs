@@ -3,12 +3,24 @@
 //! These are needed when a DB node broadcasts an address that is not reachable
 //! from the client, or when the address is not the preferred one to use to reach
 //! the node. In such cases, the driver may translate the address to another one.
+//!
+//! Besides translation rules given as a `HashMap`, translators for common
+//! setups are provided: [`Ec2MultiRegionAddressTranslator`] for clusters spanning
+//! multiple EC2 regions and [`KubernetesAddressTranslator`] for clusters deployed
+//! in Kubernetes.
 
 use std::collections::HashMap;
-use std::net::SocketAddr;
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::str::FromStr as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
+use tokio::net::lookup_host;
+use tokio::time::Instant;
+use tracing::debug;
 use uuid::Uuid;
 
 use crate::errors::TranslationError;
@@ -118,3 +130,495 @@ impl AddressTranslator for HashMap<&'static str, &'static str> {
         ))
     }
 }
+
+/// An [`AddressTranslator`] that caches the results of another translator.
+///
+/// Custom translators often consult external services (cloud metadata APIs, DNS)
+/// on every translation, and translation is performed on every connection attempt.
+/// This wrapper remembers successful translations for `ttl` and failed ones
+/// for `negative_ttl`, so that the wrapped translator is consulted at most once
+/// per period for each peer. Cache effectiveness can be inspected with
+/// [`CachingAddressTranslator::stats`].
+///
+/// Entries are keyed by the peer's host id and untranslated address.
+/// Expired entries are dropped whenever a new translation is cached.
+pub struct CachingAddressTranslator {
+    inner: Arc<dyn AddressTranslator>,
+    ttl: Duration,
+    negative_ttl: Duration,
+    cache: Mutex<HashMap<(Uuid, SocketAddr), CachedTranslation>>,
+    hits: AtomicU64,
+    negative_hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct CachedTranslation {
+    result: Result<SocketAddr, TranslationError>,
+    // `None` if the TTL is too long to be represented, i.e. the entry never expires.
+    valid_until: Option<Instant>,
+}
+
+impl CachedTranslation {
+    fn is_valid(&self, now: Instant) -> bool {
+        self.valid_until.is_none_or(|valid_until| valid_until > now)
+    }
+}
+
+/// Statistics of a [`CachingAddressTranslator`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct CachingAddressTranslatorStats {
+    /// Number of translations served from cached successful results.
+    pub hits: u64,
+    /// Number of translations served from cached failures.
+    pub negative_hits: u64,
+    /// Number of translations that required consulting the wrapped translator.
+    pub misses: u64,
+}
+
+impl CachingAddressTranslator {
+    /// Default time for which successful translations are cached.
+    pub const DEFAULT_TTL: Duration = Duration::from_secs(300);
+
+    /// Default time for which failed translations are cached.
+    pub const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(5);
+
+    /// Wraps the given translator, caching its results with the default TTLs.
+    pub fn new(inner: Arc<dyn AddressTranslator>) -> Self {
+        Self {
+            inner,
+            ttl: Self::DEFAULT_TTL,
+            negative_ttl: Self::DEFAULT_NEGATIVE_TTL,
+            cache: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            negative_hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the time for which successful translations are cached.
+    ///
+    /// Default: [`CachingAddressTranslator::DEFAULT_TTL`].
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Sets the time for which failed translations are cached.
+    /// Zero disables negative caching.
+    ///
+    /// Default: [`CachingAddressTranslator::DEFAULT_NEGATIVE_TTL`].
+    pub fn with_negative_ttl(mut self, negative_ttl: Duration) -> Self {
+        self.negative_ttl = negative_ttl;
+        self
+    }
+
+    /// Returns the statistics of cache usage gathered so far.
+    pub fn stats(&self) -> CachingAddressTranslatorStats {
+        CachingAddressTranslatorStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            negative_hits: self.negative_hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Drops all cached translations.
+    pub fn invalidate_all(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+
+    fn lookup(&self, key: &(Uuid, SocketAddr)) -> Option<Result<SocketAddr, TranslationError>> {
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(key) {
+            Some(entry) if entry.is_valid(Instant::now()) => Some(entry.result.clone()),
+            Some(_) => {
+                cache.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl AddressTranslator for CachingAddressTranslator {
+    async fn translate_address(
+        &self,
+        untranslated_peer: &UntranslatedPeer,
+    ) -> Result<SocketAddr, TranslationError> {
+        let key = (
+            untranslated_peer.host_id(),
+            untranslated_peer.untranslated_address(),
+        );
+
+        if let Some(result) = self.lookup(&key) {
+            let counter = match result {
+                Ok(_) => &self.hits,
+                Err(_) => &self.negative_hits,
+            };
+            counter.fetch_add(1, Ordering::Relaxed);
+            return result;
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let result = self.inner.translate_address(untranslated_peer).await;
+
+        let ttl = match result {
+            Ok(_) => self.ttl,
+            Err(_) => self.negative_ttl,
+        };
+        if !ttl.is_zero() {
+            let now = Instant::now();
+            let mut cache = self.cache.lock().unwrap();
+            // Entries of peers which are no longer translated would stay forever otherwise.
+            cache.retain(|_, entry| entry.is_valid(now));
+            cache.insert(
+                key,
+                CachedTranslation {
+                    result: result.clone(),
+                    valid_until: now.checked_add(ttl),
+                },
+            );
+        }
+
+        result
+    }
+}
+
+/// An [`AddressTranslator`] for clusters spanning multiple EC2 regions.
+///
+/// In such clusters nodes broadcast their public IPs, as the private ones are not
+/// reachable from other regions. Clients inside a region should, however, reach
+/// the nodes from the same region through their private IPs, which is cheaper and faster.
+///
+/// For each node, this translator resolves the public DNS hostname EC2 assigns
+/// to the broadcast IP in the client's region (e.g. `ec2-1-2-3-4.eu-west-1.compute.amazonaws.com`).
+/// Inside a VPC, such a hostname resolves to the private IP of the instance.
+/// Hostnames of instances from other regions do not resolve, so these nodes
+/// are reached through their broadcast addresses, as are nodes with IPv6 addresses.
+#[derive(Debug, Clone)]
+pub struct Ec2MultiRegionAddressTranslator {
+    region: String,
+}
+
+impl Ec2MultiRegionAddressTranslator {
+    /// Creates a translator for a client running in the given EC2 region, e.g. `eu-west-1`.
+    pub fn new(region: impl Into<String>) -> Self {
+        Self {
+            region: region.into(),
+        }
+    }
+
+    fn public_hostname(&self, ip: Ipv4Addr) -> String {
+        let [a, b, c, d] = ip.octets();
+        // us-east-1 predates the regional naming scheme.
+        if self.region == "us-east-1" {
+            format!("ec2-{a}-{b}-{c}-{d}.compute-1.amazonaws.com")
+        } else {
+            format!("ec2-{a}-{b}-{c}-{d}.{}.compute.amazonaws.com", self.region)
+        }
+    }
+}
+
+#[async_trait]
+impl AddressTranslator for Ec2MultiRegionAddressTranslator {
+    async fn translate_address(
+        &self,
+        untranslated_peer: &UntranslatedPeer,
+    ) -> Result<SocketAddr, TranslationError> {
+        let untranslated_address = untranslated_peer.untranslated_address();
+        let IpAddr::V4(ip) = untranslated_address.ip() else {
+            return Ok(untranslated_address);
+        };
+
+        let hostname = self.public_hostname(ip);
+        match resolve(&hostname, untranslated_address.port()).await {
+            Ok(translated_address) => Ok(translated_address),
+            Err(err) => {
+                debug!(
+                    "Failed to resolve {} for node {}, assuming it's in another region: {}",
+                    hostname, untranslated_address, err
+                );
+                Ok(untranslated_address)
+            }
+        }
+    }
+}
+
+/// An [`AddressTranslator`] for clusters deployed in Kubernetes, accessed
+/// by clients that cannot reach the pod IPs directly.
+///
+/// Nodes broadcast the IPs of their pods. This translator maps each broadcast IP
+/// to the name of the pod (see [`KubernetesAddressTranslator::with_pod`]) and connects
+/// to the DNS name of the service exposing that pod:
+/// - `<pod>.<namespace>.svc.<cluster domain>` by default, which is the name of a per-pod
+///   member service, as created e.g. by ScyllaDB Operator,
+/// - `<pod>.<service>.<namespace>.svc.<cluster domain>` if a headless service governing
+///   the pods is set with [`KubernetesAddressTranslator::with_headless_service`].
+///
+/// The port broadcast by the node is kept. Translation of an address which is not
+/// mapped to any pod fails with [`TranslationError::NoRuleForAddress`].
+#[derive(Debug, Clone)]
+pub struct KubernetesAddressTranslator {
+    namespace: String,
+    headless_service: Option<String>,
+    cluster_domain: String,
+    pods: HashMap<IpAddr, String>,
+}
+
+impl KubernetesAddressTranslator {
+    /// Default domain of the Kubernetes cluster.
+    pub const DEFAULT_CLUSTER_DOMAIN: &'static str = "cluster.local";
+
+    /// Creates a translator for nodes running in the given namespace, with no pods mapped yet.
+    pub fn new(namespace: impl Into<String>) -> Self {
+        Self {
+            namespace: namespace.into(),
+            headless_service: None,
+            cluster_domain: Self::DEFAULT_CLUSTER_DOMAIN.to_owned(),
+            pods: HashMap::new(),
+        }
+    }
+
+    /// Maps the IP broadcast by a node to the name of its pod.
+    pub fn with_pod(mut self, broadcast_ip: IpAddr, pod_name: impl Into<String>) -> Self {
+        self.pods.insert(broadcast_ip, pod_name.into());
+        self
+    }
+
+    /// Sets the headless service governing the pods, whose per-pod DNS records
+    /// are then used instead of per-pod member services.
+    pub fn with_headless_service(mut self, service: impl Into<String>) -> Self {
+        self.headless_service = Some(service.into());
+        self
+    }
+
+    /// Sets the domain of the Kubernetes cluster.
+    ///
+    /// Default: [`KubernetesAddressTranslator::DEFAULT_CLUSTER_DOMAIN`].
+    pub fn with_cluster_domain(mut self, cluster_domain: impl Into<String>) -> Self {
+        self.cluster_domain = cluster_domain.into();
+        self
+    }
+
+    fn service_hostname(&self, pod_name: &str) -> String {
+        match &self.headless_service {
+            Some(service) => format!(
+                "{pod_name}.{service}.{}.svc.{}",
+                self.namespace, self.cluster_domain
+            ),
+            None => format!("{pod_name}.{}.svc.{}", self.namespace, self.cluster_domain),
+        }
+    }
+}
+
+#[async_trait]
+impl AddressTranslator for KubernetesAddressTranslator {
+    async fn translate_address(
+        &self,
+        untranslated_peer: &UntranslatedPeer,
+    ) -> Result<SocketAddr, TranslationError> {
+        let untranslated_address = untranslated_peer.untranslated_address();
+        let pod_name = self
+            .pods
+            .get(&untranslated_address.ip())
+            .ok_or(TranslationError::NoRuleForAddress(untranslated_address))?;
+
+        resolve(
+            &self.service_hostname(pod_name),
+            untranslated_address.port(),
+        )
+        .await
+        .map_err(|err| TranslationError::IoError(Arc::new(err)))
+    }
+}
+
+/// Resolves the hostname to its first address.
+async fn resolve(hostname: &str, port: u16) -> Result<SocketAddr, io::Error> {
+    lookup_host((hostname, port)).await?.next().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{hostname} resolved to no addresses"),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use uuid::Uuid;
+
+    use super::{
+        AddressTranslator, CachingAddressTranslator, CachingAddressTranslatorStats,
+        Ec2MultiRegionAddressTranslator, KubernetesAddressTranslator, UntranslatedPeer,
+    };
+    use crate::errors::TranslationError;
+
+    const KNOWN_ADDR: SocketAddr = SocketAddr::new(
+        std::net::IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 1)),
+        9042,
+    );
+    const TRANSLATED_ADDR: SocketAddr = SocketAddr::new(
+        std::net::IpAddr::V4(std::net::Ipv4Addr::new(1, 2, 3, 4)),
+        9042,
+    );
+    const UNKNOWN_ADDR: SocketAddr = SocketAddr::new(
+        std::net::IpAddr::V4(std::net::Ipv4Addr::new(10, 0, 0, 2)),
+        9042,
+    );
+
+    struct CountingTranslator {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl AddressTranslator for CountingTranslator {
+        async fn translate_address(
+            &self,
+            untranslated_peer: &UntranslatedPeer,
+        ) -> Result<SocketAddr, TranslationError> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if untranslated_peer.untranslated_address() == KNOWN_ADDR {
+                Ok(TRANSLATED_ADDR)
+            } else {
+                Err(TranslationError::NoRuleForAddress(
+                    untranslated_peer.untranslated_address(),
+                ))
+            }
+        }
+    }
+
+    fn peer(addr: SocketAddr) -> UntranslatedPeer<'static> {
+        UntranslatedPeer {
+            host_id: Uuid::nil(),
+            untranslated_address: addr,
+            datacenter: None,
+            rack: None,
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn caching_translator_caches_with_ttl() {
+        let inner = Arc::new(CountingTranslator {
+            calls: AtomicUsize::new(0),
+        });
+        let translator = CachingAddressTranslator::new(inner.clone())
+            .with_ttl(Duration::from_secs(10))
+            .with_negative_ttl(Duration::from_secs(1));
+
+        for _ in 0..3 {
+            let translated = translator.translate_address(&peer(KNOWN_ADDR)).await;
+            assert_eq!(translated.unwrap(), TRANSLATED_ADDR);
+            assert!(translator
+                .translate_address(&peer(UNKNOWN_ADDR))
+                .await
+                .is_err());
+        }
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 2);
+        assert_eq!(
+            translator.stats(),
+            CachingAddressTranslatorStats {
+                hits: 2,
+                negative_hits: 2,
+                misses: 2,
+            }
+        );
+
+        // Negative entry expires, positive one is still valid.
+        tokio::time::advance(Duration::from_secs(2)).await;
+        translator
+            .translate_address(&peer(KNOWN_ADDR))
+            .await
+            .unwrap();
+        translator
+            .translate_address(&peer(UNKNOWN_ADDR))
+            .await
+            .unwrap_err();
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 3);
+
+        // Both entries expire.
+        tokio::time::advance(Duration::from_secs(10)).await;
+        translator
+            .translate_address(&peer(KNOWN_ADDR))
+            .await
+            .unwrap();
+        assert_eq!(inner.calls.load(Ordering::Relaxed), 4);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn caching_translator_prunes_expired_entries() {
+        let inner = Arc::new(CountingTranslator {
+            calls: AtomicUsize::new(0),
+        });
+        let translator = CachingAddressTranslator::new(inner)
+            .with_ttl(Duration::MAX)
+            .with_negative_ttl(Duration::from_secs(1));
+
+        translator
+            .translate_address(&peer(UNKNOWN_ADDR))
+            .await
+            .unwrap_err();
+        tokio::time::advance(Duration::from_secs(2)).await;
+
+        // Caching a new entry drops the expired one. A TTL too long
+        // to be represented makes the entry never expire.
+        translator
+            .translate_address(&peer(KNOWN_ADDR))
+            .await
+            .unwrap();
+        let cache = translator.cache.lock().unwrap();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.values().all(|entry| entry.valid_until.is_none()));
+    }
+
+    #[test]
+    fn ec2_public_hostname() {
+        let ip = std::net::Ipv4Addr::new(54, 1, 2, 3);
+        assert_eq!(
+            Ec2MultiRegionAddressTranslator::new("eu-west-1").public_hostname(ip),
+            "ec2-54-1-2-3.eu-west-1.compute.amazonaws.com"
+        );
+        assert_eq!(
+            Ec2MultiRegionAddressTranslator::new("us-east-1").public_hostname(ip),
+            "ec2-54-1-2-3.compute-1.amazonaws.com"
+        );
+    }
+
+    #[tokio::test]
+    async fn ec2_translator_keeps_ipv6_addresses() {
+        let addr: SocketAddr = "[2001:db8::1]:9042".parse().unwrap();
+        let translated = Ec2MultiRegionAddressTranslator::new("eu-west-1")
+            .translate_address(&peer(addr))
+            .await
+            .unwrap();
+        assert_eq!(translated, addr);
+    }
+
+    #[tokio::test]
+    async fn kubernetes_translator() {
+        let translator = KubernetesAddressTranslator::new("scylla")
+            .with_pod(KNOWN_ADDR.ip(), "scylla-dc1-rack1-0")
+            .with_cluster_domain("localhost");
+        assert_eq!(
+            translator.service_hostname("scylla-dc1-rack1-0"),
+            "scylla-dc1-rack1-0.scylla.svc.localhost"
+        );
+        assert_eq!(
+            translator
+                .clone()
+                .with_headless_service("scylla-client")
+                .service_hostname("scylla-dc1-rack1-0"),
+            "scylla-dc1-rack1-0.scylla-client.scylla.svc.localhost"
+        );
+
+        assert!(matches!(
+            translator.translate_address(&peer(UNKNOWN_ADDR)).await,
+            Err(TranslationError::NoRuleForAddress(addr)) if addr == UNKNOWN_ADDR
+        ));
+    }
+}
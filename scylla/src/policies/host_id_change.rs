@@ -0,0 +1,191 @@
+//! Configures what the driver does when a node at an already known address
+//! reports a different host id, which typically happens when a node is
+//! replaced by a new one reusing its address.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use uuid::Uuid;
+
+use crate::cluster::NodeAddr;
+
+/// What to do with the previously known node when a node at its address
+/// reports a different host id.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HostIdChangeAction {
+    /// The new node replaces the previous one: the previous node is removed
+    /// from the cluster state (and its connections are closed), even if
+    /// cluster metadata still lists it, e.g. until the replacement finishes.
+    Replace,
+
+    /// Both nodes are kept for as long as cluster metadata lists them.
+    #[default]
+    KeepBoth,
+}
+
+/// Gets notified when a node at an already known address reports a different host id.
+pub trait HostIdChangeListener: Debug + Send + Sync {
+    /// Called when the node at `address`, previously known with `previous_host_id`,
+    /// is now reported with `new_host_id`. `action` is the action the driver takes.
+    fn on_host_id_change(
+        &self,
+        address: NodeAddr,
+        previous_host_id: Uuid,
+        new_host_id: Uuid,
+        action: HostIdChangeAction,
+    );
+}
+
+/// Controls handling of host id changes for a node address.
+///
+/// By default, both nodes are kept for as long as cluster metadata lists them,
+/// and no listener is notified.
+///
+/// # Example
+/// ```
+/// # use scylla::policies::host_id_change::{HostIdChangeAction, HostIdChangePolicy};
+/// let policy = HostIdChangePolicy::new(HostIdChangeAction::Replace);
+/// assert_eq!(policy.action(), HostIdChangeAction::Replace);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HostIdChangePolicy {
+    action: HostIdChangeAction,
+    listener: Option<Arc<dyn HostIdChangeListener>>,
+}
+
+impl HostIdChangePolicy {
+    /// Creates a policy taking the given action.
+    pub fn new(action: HostIdChangeAction) -> Self {
+        Self {
+            action,
+            listener: None,
+        }
+    }
+
+    /// Sets a listener, notified about every detected host id change.
+    pub fn with_listener(mut self, listener: Arc<dyn HostIdChangeListener>) -> Self {
+        self.listener = Some(listener);
+        self
+    }
+
+    /// The action taken upon a host id change.
+    pub fn action(&self) -> HostIdChangeAction {
+        self.action
+    }
+
+    /// The listener notified about host id changes, if set.
+    pub fn listener(&self) -> Option<&Arc<dyn HostIdChangeListener>> {
+        self.listener.as_ref()
+    }
+
+    pub(crate) fn notify(&self, address: NodeAddr, previous_host_id: Uuid, new_host_id: Uuid) {
+        if let Some(listener) = &self.listener {
+            listener.on_host_id_change(address, previous_host_id, new_host_id, self.action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{Arc, Mutex};
+
+    use uuid::Uuid;
+
+    use super::{HostIdChangeAction, HostIdChangeListener, HostIdChangePolicy};
+    use crate::cluster::metadata::{Metadata, Peer};
+    use crate::cluster::{ClusterState, NodeAddr};
+    use crate::routing::locator::tablets::TabletsInfo;
+    use crate::routing::locator::test::id_to_invalid_addr;
+    use crate::routing::Token;
+
+    #[derive(Debug, Default)]
+    struct RecordingListener {
+        changes: Mutex<Vec<(NodeAddr, Uuid, Uuid)>>,
+    }
+
+    impl HostIdChangeListener for RecordingListener {
+        fn on_host_id_change(
+            &self,
+            address: NodeAddr,
+            previous_host_id: Uuid,
+            new_host_id: Uuid,
+            _action: HostIdChangeAction,
+        ) {
+            self.changes
+                .lock()
+                .unwrap()
+                .push((address, previous_host_id, new_host_id));
+        }
+    }
+
+    fn peer(host_id: Uuid, id: u16) -> Peer {
+        Peer {
+            host_id,
+            address: id_to_invalid_addr(id),
+            tokens: vec![Token::new(id as i64 * 100)],
+            datacenter: Some("dc".to_owned()),
+            rack: None,
+            release_version: None,
+        }
+    }
+
+    async fn state(
+        peers: Vec<Peer>,
+        previous: Option<&ClusterState>,
+        policy: &HostIdChangePolicy,
+    ) -> ClusterState {
+        let empty_peers = HashMap::new();
+        let empty_replaced = HashSet::new();
+        ClusterState::new(
+            Metadata {
+                peers,
+                keyspaces: Ok(HashMap::new()),
+                partitioners: Default::default(),
+            },
+            &Default::default(),
+            previous.map_or(&empty_peers, |s| &s.known_peers),
+            &None,
+            None,
+            &HashSet::new(),
+            policy,
+            previous.map_or(&empty_replaced, |s| &s.replaced_host_ids),
+            TabletsInfo::new(),
+            &HashMap::new(),
+            #[cfg(feature = "metrics")]
+            &Default::default(),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn host_id_change_actions() {
+        let (old_id, new_id, other_id) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let listener = Arc::new(RecordingListener::default());
+
+        for action in [HostIdChangeAction::KeepBoth, HostIdChangeAction::Replace] {
+            listener.changes.lock().unwrap().clear();
+            let policy = HostIdChangePolicy::new(action).with_listener(listener.clone());
+
+            let initial = state(vec![peer(old_id, 1), peer(other_id, 2)], None, &policy).await;
+            // The replacing node reuses the address, while the old one is still listed.
+            let both_listed = || vec![peer(old_id, 1), peer(new_id, 1), peer(other_id, 2)];
+            let changed = state(both_listed(), Some(&initial), &policy).await;
+            let refreshed = state(both_listed(), Some(&changed), &policy).await;
+
+            assert_eq!(
+                *listener.changes.lock().unwrap(),
+                vec![(id_to_invalid_addr(1), old_id, new_id)]
+            );
+            for cluster_state in [&changed, &refreshed] {
+                assert!(cluster_state.known_peers.contains_key(&new_id));
+                assert!(cluster_state.known_peers.contains_key(&other_id));
+                assert_eq!(
+                    cluster_state.known_peers.contains_key(&old_id),
+                    action == HostIdChangeAction::KeepBoth
+                );
+            }
+        }
+    }
+}
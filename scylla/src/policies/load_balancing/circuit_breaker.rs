@@ -0,0 +1,607 @@
+use std::collections::{HashMap, VecDeque};
+use std::iter::Fuse;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use scylla_cql::frame::response::error::DbError;
+use tokio::time::{Duration, Instant};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::cluster::{ClusterState, NodeRef};
+use crate::errors::RequestAttemptError;
+use crate::routing::Shard;
+
+use super::{FallbackPlan, LoadBalancingPolicy, RoutingInfo};
+
+/// A load balancing policy which wraps another policy and quarantines nodes
+/// whose requests fail too often.
+///
+/// The policy counts requests and failures of each node within a time window.
+/// Once at least `minimum_requests` requests were sent to a node in the current window,
+/// and the ratio of failures among them reaches `failure_rate_threshold`, the node
+/// is quarantined (the circuit is *open*): it is removed from the plans
+/// for `quarantine_duration`. This happens regardless of whether the connections
+/// to the node look healthy, so a node which quickly returns errors does not keep
+/// attracting traffic.
+///
+/// After the quarantine ends, the node is *half-open*: a single plan includes it,
+/// so that one request probes whether the node has recovered. If the probe succeeds,
+/// the node is used again, otherwise it is quarantined for another `quarantine_duration`.
+/// If the probe's result does not arrive within `quarantine_duration` (e.g. because
+/// the plan was satisfied by an earlier node), another plan is allowed to probe the node.
+///
+/// Only errors indicating problems with the node count as failures: broken connections,
+/// exhausted stream IDs, and the `Overloaded`, `IsBootstrapping`, `ServerError`,
+/// `ReadTimeout`, `WriteTimeout`, `ReadFailure` and `WriteFailure` database errors.
+/// Other errors, e.g. syntax errors, prove that the node is responsive
+/// and are counted like successes.
+///
+/// If all nodes of a plan are quarantined, the plan is left unchanged,
+/// so that requests are still attempted instead of failing without being sent.
+///
+/// Nodes which leave the cluster are forgotten, at most one `window` after they left.
+///
+/// # Example
+/// ```
+/// # fn example() {
+/// use std::time::Duration;
+/// use scylla::client::execution_profile::ExecutionProfile;
+/// use scylla::policies::load_balancing::{CircuitBreakerPolicy, DefaultPolicy};
+///
+/// let policy = CircuitBreakerPolicy::builder(DefaultPolicy::builder().build())
+///     .failure_rate_threshold(0.5)
+///     .quarantine_duration(Duration::from_secs(10))
+///     .build();
+///
+/// let profile = ExecutionProfile::builder()
+///     .load_balancing_policy(policy)
+///     .build();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CircuitBreakerPolicy {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    failure_rate_threshold: f64,
+    minimum_requests: u32,
+    window: Duration,
+    quarantine_duration: Duration,
+    nodes: Mutex<HashMap<Uuid, NodeHealth>>,
+    // Number of nodes in `nodes` which are not `Closed`. While it is zero, all nodes
+    // are admitted to plans without locking `nodes`.
+    unhealthy_nodes: AtomicUsize,
+    // When the nodes which left the cluster were last removed from `nodes`.
+    last_pruned: Mutex<Instant>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum NodeHealth {
+    /// The node is used normally, its requests are counted.
+    Closed {
+        window_start: Instant,
+        requests: u32,
+        failures: u32,
+    },
+    /// The node is quarantined. `None` if the quarantine is too long to be represented.
+    Open { until: Option<Instant> },
+    /// The quarantine ended, the node may be included in a single plan to probe it.
+    HalfOpen { probe_started: Instant },
+}
+
+impl NodeHealth {
+    fn closed(now: Instant) -> Self {
+        NodeHealth::Closed {
+            window_start: now,
+            requests: 0,
+            failures: 0,
+        }
+    }
+}
+
+impl CircuitBreakerPolicy {
+    /// Creates a builder of a policy wrapping the given policy.
+    pub fn builder(inner: Arc<dyn LoadBalancingPolicy>) -> CircuitBreakerPolicyBuilder {
+        CircuitBreakerPolicyBuilder::new(inner)
+    }
+
+    /// Decides whether the node can be included in a plan.
+    ///
+    /// Must only be called for a node which is about to be returned from the plan,
+    /// as a node whose quarantine has ended is admitted to a single plan only.
+    fn admit(&self, node: NodeRef<'_>) -> bool {
+        if self.unhealthy_nodes.load(Ordering::Relaxed) == 0 {
+            return true;
+        }
+        let mut nodes = self.nodes.lock().unwrap();
+        let Some(health) = nodes.get_mut(&node.host_id) else {
+            return true;
+        };
+        let now = Instant::now();
+        match *health {
+            NodeHealth::Closed { .. } => true,
+            NodeHealth::Open { until } if until.is_none_or(|until| now < until) => false,
+            NodeHealth::HalfOpen { probe_started }
+                if now.duration_since(probe_started) < self.quarantine_duration =>
+            {
+                false
+            }
+            NodeHealth::Open { .. } | NodeHealth::HalfOpen { .. } => {
+                *health = NodeHealth::HalfOpen { probe_started: now };
+                true
+            }
+        }
+    }
+
+    fn report_request(&self, node: NodeRef<'_>, failed: bool) {
+        let now = Instant::now();
+        let mut nodes = self.nodes.lock().unwrap();
+        let health = nodes
+            .entry(node.host_id)
+            .or_insert_with(|| NodeHealth::closed(now));
+        match health {
+            NodeHealth::Closed {
+                window_start,
+                requests,
+                failures,
+            } => {
+                if now.duration_since(*window_start) >= self.window {
+                    *window_start = now;
+                    *requests = 0;
+                    *failures = 0;
+                }
+                *requests += 1;
+                *failures += failed as u32;
+                if *requests >= self.minimum_requests
+                    && *failures as f64 >= self.failure_rate_threshold * *requests as f64
+                {
+                    warn!(
+                        "Circuit breaker: quarantining node {} (host_id={}) for {:?}, as {} out of its {} recent requests failed.",
+                        node.address, node.host_id, self.quarantine_duration, failures, requests
+                    );
+                    *health = NodeHealth::Open {
+                        until: now.checked_add(self.quarantine_duration),
+                    };
+                    self.unhealthy_nodes.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            // Responses to requests sent before the node was quarantined.
+            NodeHealth::Open { .. } => (),
+            NodeHealth::HalfOpen { .. } => {
+                if failed {
+                    warn!(
+                        "Circuit breaker: probe request to node {} (host_id={}) failed, quarantining it for another {:?}.",
+                        node.address, node.host_id, self.quarantine_duration
+                    );
+                    *health = NodeHealth::Open {
+                        until: now.checked_add(self.quarantine_duration),
+                    };
+                } else {
+                    info!(
+                        "Circuit breaker: node {} (host_id={}) recovered, ending its quarantine.",
+                        node.address, node.host_id
+                    );
+                    *health = NodeHealth::closed(now);
+                    self.unhealthy_nodes.fetch_sub(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    // Forgets the nodes which are no longer part of the cluster. Done at most once per window.
+    fn prune_departed_nodes(&self, cluster: &ClusterState) {
+        let now = Instant::now();
+        {
+            let mut last_pruned = self.last_pruned.lock().unwrap();
+            if now.duration_since(*last_pruned) < self.window {
+                return;
+            }
+            *last_pruned = now;
+        }
+        let mut nodes = self.nodes.lock().unwrap();
+        nodes.retain(|host_id, _| cluster.known_peers.contains_key(host_id));
+        let unhealthy_nodes = nodes
+            .values()
+            .filter(|health| !matches!(health, NodeHealth::Closed { .. }))
+            .count();
+        self.unhealthy_nodes
+            .store(unhealthy_nodes, Ordering::Relaxed);
+    }
+
+    fn indicates_node_failure(error: &RequestAttemptError) -> bool {
+        matches!(
+            error,
+            RequestAttemptError::BrokenConnectionError(_)
+                | RequestAttemptError::UnableToAllocStreamId
+                | RequestAttemptError::DbError(
+                    DbError::Overloaded
+                        | DbError::IsBootstrapping
+                        | DbError::ServerError
+                        | DbError::ReadTimeout { .. }
+                        | DbError::WriteTimeout { .. }
+                        | DbError::ReadFailure { .. }
+                        | DbError::WriteFailure { .. },
+                    _
+                )
+        )
+    }
+}
+
+impl LoadBalancingPolicy for CircuitBreakerPolicy {
+    fn pick<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> Option<(NodeRef<'a>, Option<Shard>)> {
+        self.prune_departed_nodes(cluster);
+        // If the picked node is quarantined, the driver turns to the fallback plan.
+        self.inner
+            .pick(request, cluster)
+            .filter(|(node, _shard)| self.admit(node))
+    }
+
+    fn fallback<'a>(
+        &'a self,
+        request: &'a RoutingInfo,
+        cluster: &'a ClusterState,
+    ) -> FallbackPlan<'a> {
+        Box::new(AdmittedTargets {
+            policy: self,
+            targets: self.inner.fallback(request, cluster).fuse(),
+            any_admitted: false,
+            quarantined: VecDeque::new(),
+        })
+    }
+
+    fn on_request_success(&self, request: &RoutingInfo, latency: Duration, node: NodeRef<'_>) {
+        self.report_request(node, false);
+        self.inner.on_request_success(request, latency, node);
+    }
+
+    fn on_request_failure(
+        &self,
+        request: &RoutingInfo,
+        latency: Duration,
+        node: NodeRef<'_>,
+        error: &RequestAttemptError,
+    ) {
+        self.report_request(node, Self::indicates_node_failure(error));
+        self.inner.on_request_failure(request, latency, node, error);
+    }
+
+    fn name(&self) -> String {
+        format!("CircuitBreakerPolicy({})", self.inner.name())
+    }
+}
+
+/// Lazily filters out quarantined nodes from a fallback plan. If no node
+/// of the plan is admitted, yields the quarantined ones instead.
+struct AdmittedTargets<'a> {
+    policy: &'a CircuitBreakerPolicy,
+    targets: Fuse<FallbackPlan<'a>>,
+    any_admitted: bool,
+    // Targets skipped so far, yielded if none of the targets is admitted.
+    quarantined: VecDeque<(NodeRef<'a>, Option<Shard>)>,
+}
+
+impl<'a> Iterator for AdmittedTargets<'a> {
+    type Item = (NodeRef<'a>, Option<Shard>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for target in self.targets.by_ref() {
+            if self.policy.admit(target.0) {
+                self.any_admitted = true;
+                self.quarantined.clear();
+                return Some(target);
+            }
+            if !self.any_admitted {
+                self.quarantined.push_back(target);
+            }
+        }
+        self.quarantined.pop_front()
+    }
+}
+
+/// Builder of [CircuitBreakerPolicy].
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerPolicyBuilder {
+    inner: Arc<dyn LoadBalancingPolicy>,
+    failure_rate_threshold: f64,
+    minimum_requests: u32,
+    window: Duration,
+    quarantine_duration: Duration,
+}
+
+impl CircuitBreakerPolicyBuilder {
+    /// Creates a builder of a policy wrapping the given policy, with default parameters.
+    pub fn new(inner: Arc<dyn LoadBalancingPolicy>) -> Self {
+        Self {
+            inner,
+            failure_rate_threshold: 0.5,
+            minimum_requests: 20,
+            window: Duration::from_secs(10),
+            quarantine_duration: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets the ratio of failed requests, greater than 0 and at most 1,
+    /// at which a node gets quarantined.
+    ///
+    /// Default: 0.5.
+    ///
+    /// # Panics
+    /// Panics if `threshold` is not in the (0, 1] range.
+    pub fn failure_rate_threshold(mut self, threshold: f64) -> Self {
+        assert!(
+            threshold > 0.0 && threshold <= 1.0,
+            "failure_rate_threshold must be in the (0, 1] range, got {threshold}"
+        );
+        self.failure_rate_threshold = threshold;
+        self
+    }
+
+    /// Sets the number of requests which have to be sent to a node within the window
+    /// before its failure rate is taken into account.
+    ///
+    /// Default: 20.
+    pub fn minimum_requests(mut self, minimum_requests: u32) -> Self {
+        self.minimum_requests = minimum_requests;
+        self
+    }
+
+    /// Sets the length of the window in which requests and failures of a node are counted.
+    /// The counters are reset once the window elapses.
+    ///
+    /// Default: 10 seconds.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets for how long a node is removed from the plans once it gets quarantined.
+    ///
+    /// Default: 10 seconds.
+    pub fn quarantine_duration(mut self, duration: Duration) -> Self {
+        self.quarantine_duration = duration;
+        self
+    }
+
+    /// Builds the policy.
+    pub fn build(self) -> Arc<dyn LoadBalancingPolicy> {
+        Arc::new(self.build_policy())
+    }
+
+    fn build_policy(self) -> CircuitBreakerPolicy {
+        CircuitBreakerPolicy {
+            inner: self.inner,
+            failure_rate_threshold: self.failure_rate_threshold,
+            minimum_requests: self.minimum_requests,
+            window: self.window,
+            quarantine_duration: self.quarantine_duration,
+            nodes: Mutex::new(HashMap::new()),
+            unhealthy_nodes: AtomicUsize::new(0),
+            last_pruned: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::atomic::Ordering;
+    use std::time::Duration;
+
+    use scylla_cql::frame::response::error::DbError;
+
+    use super::{CircuitBreakerPolicy, CircuitBreakerPolicyBuilder};
+    use crate::cluster::{ClusterState, NodeRef};
+    use crate::errors::RequestAttemptError;
+    use crate::policies::load_balancing::default::tests::framework::mock_cluster_state_for_token_unaware_tests;
+    use crate::policies::load_balancing::default::tests::EMPTY_ROUTING_INFO;
+    use crate::policies::load_balancing::{DefaultPolicy, LoadBalancingPolicy, Plan};
+    use crate::test_utils::setup_tracing;
+
+    const QUARANTINE: Duration = Duration::from_secs(10);
+
+    fn policy() -> std::sync::Arc<dyn LoadBalancingPolicy> {
+        CircuitBreakerPolicy::builder(DefaultPolicy::builder().build())
+            .minimum_requests(4)
+            .failure_rate_threshold(0.5)
+            .quarantine_duration(QUARANTINE)
+            .build()
+    }
+
+    fn plan_ports(policy: &dyn LoadBalancingPolicy, cluster: &ClusterState) -> HashSet<u16> {
+        Plan::new(policy, &EMPTY_ROUTING_INFO, cluster)
+            .map(|(node, _shard)| node.address.port())
+            .collect()
+    }
+
+    fn report_overloaded(policy: &dyn LoadBalancingPolicy, node: NodeRef<'_>) {
+        policy.on_request_failure(
+            &EMPTY_ROUTING_INFO,
+            Duration::ZERO,
+            node,
+            &RequestAttemptError::DbError(DbError::Overloaded, String::new()),
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failing_node_is_quarantined_and_probed() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = policy();
+        let all_ports = plan_ports(&*policy, &cluster);
+        let failing = &cluster.get_nodes_info()[0];
+        let failing_port = failing.address.port();
+
+        // Failure rate below the threshold and errors unrelated to the node's health
+        // do not cause a quarantine.
+        report_overloaded(&*policy, failing);
+        for _ in 0..2 {
+            policy.on_request_success(&EMPTY_ROUTING_INFO, Duration::ZERO, failing);
+        }
+        policy.on_request_failure(
+            &EMPTY_ROUTING_INFO,
+            Duration::ZERO,
+            failing,
+            &RequestAttemptError::DbError(DbError::SyntaxError, String::new()),
+        );
+        assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+
+        report_overloaded(&*policy, failing);
+        report_overloaded(&*policy, failing);
+        let healthy_ports = &all_ports - &HashSet::from([failing_port]);
+        for _ in 0..16 {
+            assert_eq!(plan_ports(&*policy, &cluster), healthy_ports);
+        }
+
+        // After the quarantine, exactly one plan probes the node.
+        tokio::time::advance(QUARANTINE).await;
+        assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+        assert_eq!(plan_ports(&*policy, &cluster), healthy_ports);
+
+        // A failed probe quarantines the node again.
+        report_overloaded(&*policy, failing);
+        tokio::time::advance(QUARANTINE / 2).await;
+        assert_eq!(plan_ports(&*policy, &cluster), healthy_ports);
+
+        // A successful probe ends the quarantine.
+        tokio::time::advance(QUARANTINE / 2).await;
+        assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+        policy.on_request_success(&EMPTY_ROUTING_INFO, Duration::ZERO, failing);
+        for _ in 0..16 {
+            assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn plan_is_not_emptied_when_all_nodes_are_quarantined() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = policy();
+        let all_ports = plan_ports(&*policy, &cluster);
+
+        for node in cluster.get_nodes_info() {
+            for _ in 0..4 {
+                report_overloaded(&*policy, node);
+            }
+        }
+        assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn probe_is_started_only_when_node_is_returned() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = policy();
+        let failing = &cluster.get_nodes_info()[0];
+        let failing_port = failing.address.port();
+
+        for _ in 0..4 {
+            report_overloaded(&*policy, failing);
+        }
+        tokio::time::advance(QUARANTINE).await;
+
+        // Plans which end before reaching the node do not consume its probe.
+        // The second target comes from the fallback plan.
+        let mut probed = false;
+        for _ in 0..16 {
+            probed = Plan::new(&*policy, &EMPTY_ROUTING_INFO, &cluster)
+                .take(2)
+                .any(|(node, _shard)| node.address.port() == failing_port);
+            if probed {
+                break;
+            }
+        }
+        assert_eq!(
+            plan_ports(&*policy, &cluster).contains(&failing_port),
+            !probed
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn departed_nodes_are_forgotten() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let other_cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = CircuitBreakerPolicyBuilder::new(DefaultPolicy::builder().build())
+            .window(Duration::from_secs(10))
+            .build_policy();
+
+        report_overloaded(&policy, &cluster.get_nodes_info()[0]);
+        report_overloaded(&policy, &other_cluster.get_nodes_info()[0]);
+        assert_eq!(policy.nodes.lock().unwrap().len(), 2);
+
+        tokio::time::advance(Duration::from_secs(10)).await;
+        plan_ports(&policy, &cluster);
+        let nodes = policy.nodes.lock().unwrap();
+        assert_eq!(nodes.len(), 1);
+        assert!(nodes.contains_key(&cluster.get_nodes_info()[0].host_id));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn unhealthy_nodes_are_counted() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let other_cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = CircuitBreakerPolicyBuilder::new(DefaultPolicy::builder().build())
+            .minimum_requests(1)
+            .window(QUARANTINE)
+            .quarantine_duration(QUARANTINE)
+            .build_policy();
+        let unhealthy_nodes = || policy.unhealthy_nodes.load(Ordering::Relaxed);
+        let node = &cluster.get_nodes_info()[0];
+
+        report_overloaded(&policy, node);
+        report_overloaded(&policy, &other_cluster.get_nodes_info()[0]);
+        assert_eq!(unhealthy_nodes(), 2);
+
+        // Probing the node and its recovery.
+        tokio::time::advance(QUARANTINE).await;
+        plan_ports(&policy, &cluster);
+        assert_eq!(unhealthy_nodes(), 1);
+        policy.on_request_success(&EMPTY_ROUTING_INFO, Duration::ZERO, node);
+        assert_eq!(unhealthy_nodes(), 0);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn long_quarantine_does_not_overflow() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = CircuitBreakerPolicy::builder(DefaultPolicy::builder().build())
+            .minimum_requests(1)
+            .quarantine_duration(Duration::MAX)
+            .build();
+        let all_ports = plan_ports(&*policy, &cluster);
+        let failing = &cluster.get_nodes_info()[0];
+
+        report_overloaded(&*policy, failing);
+        tokio::time::advance(Duration::from_secs(3600)).await;
+        assert_eq!(
+            plan_ports(&*policy, &cluster),
+            &all_ports - &HashSet::from([failing.address.port()])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn failure_rate_threshold_out_of_range() {
+        CircuitBreakerPolicy::builder(DefaultPolicy::builder().build()).failure_rate_threshold(1.5);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn counters_are_reset_after_window() {
+        setup_tracing();
+        let cluster = mock_cluster_state_for_token_unaware_tests().await;
+        let policy = policy();
+        let all_ports = plan_ports(&*policy, &cluster);
+        let node = &cluster.get_nodes_info()[0];
+
+        for _ in 0..3 {
+            report_overloaded(&*policy, node);
+        }
+        tokio::time::advance(Duration::from_secs(10)).await;
+        report_overloaded(&*policy, node);
+        assert_eq!(plan_ports(&*policy, &cluster), all_ports);
+    }
+}
@@ -11,7 +11,7 @@ use crate::{
     routing::{Shard, Token},
 };
 use itertools::{Either, Itertools};
-use rand::{prelude::SliceRandom, rng, Rng};
+use rand::{prelude::SliceRandom, rng, Rng, RngCore};
 use rand_pcg::Pcg32;
 use scylla_cql::frame::response::result::TableSpec;
 use std::hash::{Hash, Hasher};
@@ -106,8 +106,8 @@ enum PickedReplica<'a> {
 /// nodes for recently measures latencies is believed to not be very stable
 /// and beneficial. The number of in-flight requests, for instance, seems
 /// to be a better metric showing how (over)loaded a target node/shard is.
-/// For now, however, we don't have an implementation of the
-/// in-flight-requests-aware policy.
+/// Such a metric is used by the power of two choices mode, which can be enabled
+/// with [`DefaultPolicyBuilder::power_of_two_choices`].
 #[expect(clippy::type_complexity)]
 pub struct DefaultPolicy {
     /// Preferences regarding node location. One of: rack and DC, DC, or no preference.
@@ -143,6 +143,15 @@ pub struct DefaultPolicy {
     /// based on random number generator. For sake of deterministic testing,
     /// a fixed seed can be used.
     fixed_seed: Option<u64>,
+
+    /// Whether confirmed LWT statements are routed to replicas in a deterministic order,
+    /// so that all clients prefer the same replica. This reduces contention caused
+    /// by Paxos conflicts.
+    is_lwt_optimisation_enabled: bool,
+
+    /// Whether `pick` samples two random targets and chooses the one which is less loaded,
+    /// judging by its recent average latency and the number of requests in flight.
+    is_power_of_two_choices_enabled: bool,
 }
 
 impl fmt::Debug for DefaultPolicy {
@@ -153,6 +162,14 @@ impl fmt::Debug for DefaultPolicy {
             .field("permit_dc_failover", &self.permit_dc_failover)
             .field("latency_awareness", &self.latency_awareness)
             .field("fixed_seed", &self.fixed_seed)
+            .field(
+                "is_lwt_optimisation_enabled",
+                &self.is_lwt_optimisation_enabled,
+            )
+            .field(
+                "is_power_of_two_choices_enabled",
+                &self.is_power_of_two_choices_enabled,
+            )
             .finish_non_exhaustive()
     }
 }
@@ -185,11 +202,7 @@ or refrain from preferring datacenters (which may ban all other datacenters, if
         }
 
         /* LWT statements need to be routed differently: always to the same replica, to avoid Paxos contention. */
-        let statement_type = if query.is_confirmed_lwt {
-            StatementType::Lwt
-        } else {
-            StatementType::NonLwt
-        };
+        let statement_type = self.statement_type(query);
 
         /* Token-aware logic - if routing info is available, we know what are the replicas
          * for the statement. Try to pick one of them. */
@@ -339,11 +352,7 @@ or refrain from preferring datacenters (which may ban all other datacenters, if
         let routing_info = self.routing_info(query, cluster);
 
         /* LWT statements need to be routed differently: always to the same replica, to avoid Paxos contention. */
-        let statement_type = if query.is_confirmed_lwt {
-            StatementType::Lwt
-        } else {
-            StatementType::NonLwt
-        };
+        let statement_type = self.statement_type(query);
 
         /* Token-aware logic - if routing info is available, we know what are the replicas for the statement.
          * Get a list of alive replicas:
@@ -795,6 +804,9 @@ impl DefaultPolicy {
 
     /// Picks a random replica for given token and table spec which meets the provided
     /// location criteria and the predicate.
+    ///
+    /// In the power of two choices mode, two distinct replicas are sampled
+    /// and the less loaded one is returned.
     fn pick_random_replica<'a>(
         &'a self,
         ts: &TokenWithStrategy<'a>,
@@ -805,13 +817,31 @@ impl DefaultPolicy {
     ) -> Option<(NodeRef<'a>, Shard)> {
         let predicate = Self::make_sharded_rack_predicate(predicate, replica_location);
 
-        let replica_set = self.nonfiltered_replica_set(ts, replica_location, cluster, table_spec);
-
-        if let Some(fixed) = self.fixed_seed {
-            let mut gen = Pcg32::new(fixed, 0);
-            replica_set.choose_filtered(&mut gen, |(node, shard)| predicate(node, *shard))
+        let mut fixed_gen;
+        let mut thread_gen;
+        let gen: &mut dyn RngCore = if let Some(fixed) = self.fixed_seed {
+            fixed_gen = Pcg32::new(fixed, 0);
+            &mut fixed_gen
         } else {
-            replica_set.choose_filtered(&mut rng(), |(node, shard)| predicate(node, *shard))
+            thread_gen = rng();
+            &mut thread_gen
+        };
+
+        let first = self
+            .nonfiltered_replica_set(ts, replica_location, cluster, table_spec)
+            .choose_filtered(gen, |(node, shard)| predicate(node, *shard))?;
+        if !self.is_power_of_two_choices_enabled {
+            return Some(first);
+        }
+
+        let second = self
+            .nonfiltered_replica_set(ts, replica_location, cluster, table_spec)
+            .choose_filtered(gen, |(node, shard)| {
+                node.host_id != first.0.host_id && predicate(node, *shard)
+            });
+        match second {
+            Some(second) if Self::load_score(second.0) < Self::load_score(first.0) => Some(second),
+            _ => Some(first),
         }
     }
 
@@ -862,13 +892,38 @@ impl DefaultPolicy {
     }
 
     /// Picks a random node from the slice of nodes. The node must satisfy the given predicate.
+    ///
+    /// In the power of two choices mode, two distinct nodes are sampled
+    /// and the less loaded one is returned.
     fn pick_node<'a>(
         &'a self,
         nodes: &'a [Arc<Node>],
         predicate: impl Fn(NodeRef<'a>) -> bool,
     ) -> Option<NodeRef<'a>> {
         // Select the first node that matches the predicate
-        Self::randomly_rotated_nodes(nodes).find(|&node| predicate(node))
+        let first = Self::randomly_rotated_nodes(nodes).find(|&node| predicate(node))?;
+        if !self.is_power_of_two_choices_enabled {
+            return Some(first);
+        }
+
+        let second = Self::randomly_rotated_nodes(nodes)
+            .find(|&node| node.host_id != first.host_id && predicate(node));
+        match second {
+            Some(second) if Self::load_score(second) < Self::load_score(first) => Some(second),
+            _ => Some(first),
+        }
+    }
+
+    /// Estimates how loaded the node is, for the power of two choices mode. Lower is better.
+    ///
+    /// The primary component approximates the time the node needs to serve its queue
+    /// of requests: the average latency multiplied by the number of requests in flight
+    /// (including the one being routed). Nodes without latency measurements are preferred,
+    /// so that they get measured. Ties are broken by the number of requests in flight.
+    fn load_score(node: NodeRef<'_>) -> (u128, usize) {
+        let in_flight = node.in_flight_requests();
+        let latency = node.average_latency().unwrap_or(Duration::ZERO);
+        (latency.as_micros() * (in_flight as u128 + 1), in_flight)
     }
 
     /// Returns an iterator over the given slice of nodes, rotated by a random shift
@@ -907,6 +962,15 @@ impl DefaultPolicy {
         node.is_connected()
     }
 
+    /// Returns the kind of the statement, determining how replicas are ordered.
+    fn statement_type(&self, query: &RoutingInfo) -> StatementType {
+        if query.is_confirmed_lwt && self.is_lwt_optimisation_enabled {
+            StatementType::Lwt
+        } else {
+            StatementType::NonLwt
+        }
+    }
+
     /// Returns true iff the datacenter failover is permitted for the statement being executed.
     fn is_datacenter_failover_possible(&self) -> bool {
         self.preferences.datacenter().is_some() && self.permit_dc_failover
@@ -922,6 +986,8 @@ impl Default for DefaultPolicy {
             pick_predicate: Box::new(Self::is_alive),
             latency_awareness: None,
             fixed_seed: None,
+            is_lwt_optimisation_enabled: true,
+            is_power_of_two_choices_enabled: false,
         }
     }
 }
@@ -947,6 +1013,8 @@ pub struct DefaultPolicyBuilder {
     permit_dc_failover: bool,
     latency_awareness: Option<LatencyAwarenessBuilder>,
     enable_replica_shuffle: bool,
+    enable_lwt_optimisation: bool,
+    enable_power_of_two_choices: bool,
 }
 
 impl DefaultPolicyBuilder {
@@ -958,6 +1026,8 @@ impl DefaultPolicyBuilder {
             permit_dc_failover: false,
             latency_awareness: None,
             enable_replica_shuffle: true,
+            enable_lwt_optimisation: true,
+            enable_power_of_two_choices: false,
         }
     }
 
@@ -985,6 +1055,8 @@ impl DefaultPolicyBuilder {
                 debug!("DefaultPolicy: setting fixed seed to {}", seed);
                 seed
             }),
+            is_lwt_optimisation_enabled: self.enable_lwt_optimisation,
+            is_power_of_two_choices_enabled: self.enable_power_of_two_choices,
         })
     }
 
@@ -1108,6 +1180,41 @@ impl DefaultPolicyBuilder {
         self.enable_replica_shuffle = enable;
         self
     }
+
+    /// Sets whether this policy should route LWT statements to replicas
+    /// in a deterministic order.
+    ///
+    /// Concurrent LWTs on the same partition which are coordinated by different replicas
+    /// may fail or be retried due to Paxos conflicts. To prevent that, confirmed
+    /// LWT statements (see [`RoutingInfo::is_confirmed_lwt`]) are by default sent
+    /// to the replicas in the ring order, so that all clients prefer the same, primary
+    /// replica. The preferred replica has to bear the load of all LWTs on the partitions
+    /// it owns, though. When disabled, LWT statements are routed like any other statement.
+    ///
+    /// This option is enabled by default. It only has an effect if the policy is token-aware.
+    pub fn enable_lwt_optimisation(mut self, enable: bool) -> Self {
+        self.enable_lwt_optimisation = enable;
+        self
+    }
+
+    /// Sets whether this policy should choose the target of a request using
+    /// the power of two choices.
+    ///
+    /// In this mode, instead of picking a single random replica (or node, if the request
+    /// is not token-aware), the policy samples two distinct candidates satisfying
+    /// the same criteria and picks the less loaded one. The load of a node is estimated
+    /// from its recent average latency (see [`Node::average_latency`]) and the number
+    /// of requests currently in flight to it (see [`Node::in_flight_requests`]).
+    /// This steers requests away from overloaded nodes, while still spreading the load
+    /// over all of them. Only the first target in the plan is affected; the rest of
+    /// the plan is unchanged. LWT statements routed with the LWT optimisation are
+    /// not affected either.
+    ///
+    /// This option is disabled by default.
+    pub fn power_of_two_choices(mut self, enable: bool) -> Self {
+        self.enable_power_of_two_choices = enable;
+        self
+    }
 }
 
 impl Default for DefaultPolicyBuilder {
@@ -1144,8 +1251,10 @@ impl<'a> TokenWithStrategy<'a> {
 }
 
 #[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+pub(super) mod tests {
+    use std::collections::{HashMap, HashSet};
+    use std::sync::Arc;
+    use std::time::Duration;
 
     use scylla_cql::{frame::types::SerialConsistency, Consistency};
     use tracing::info;
@@ -1161,7 +1270,7 @@ mod tests {
         TABLE_SS_RF_2,
     };
     use crate::{
-        cluster::ClusterState,
+        cluster::{ClusterState, Node},
         policies::load_balancing::{
             default::tests::framework::mock_cluster_state_for_token_aware_tests, Plan, RoutingInfo,
         },
@@ -1401,6 +1510,9 @@ mod tests {
                 &HashMap::new(),
                 &None,
                 None,
+                &Default::default(),
+                &Default::default(),
+                &Default::default(),
                 TabletsInfo::new(),
                 &HashMap::new(),
                 #[cfg(feature = "metrics")]
@@ -1426,12 +1538,14 @@ mod tests {
                     address: id_to_invalid_addr(*id),
                     tokens: vec![Token::new(*id as i64 * 100)],
                     host_id: Uuid::new_v4(),
+                    release_version: None,
                 })
                 .collect::<Vec<_>>();
 
             let info = Metadata {
                 peers,
-                keyspaces: HashMap::new(),
+                keyspaces: Ok(HashMap::new()),
+                partitioners: Default::default(),
             };
 
             let state = ClusterState::new(
@@ -1440,6 +1554,9 @@ mod tests {
                 &HashMap::new(),
                 &None,
                 None,
+                &Default::default(),
+                &Default::default(),
+                &Default::default(),
                 TabletsInfo::new(),
                 &HashMap::new(),
                 #[cfg(feature = "metrics")]
@@ -1547,6 +1664,67 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_default_policy_power_of_two_choices() {
+        setup_tracing();
+
+        fn node_by_id(cluster: &ClusterState, id: u16) -> &Arc<Node> {
+            cluster
+                .get_nodes_info()
+                .iter()
+                .find(|node| node.address.port() == id)
+                .unwrap()
+        }
+
+        fn first_targets(
+            policy: &DefaultPolicy,
+            routing_info: &RoutingInfo,
+            cluster: &ClusterState,
+        ) -> HashSet<u16> {
+            (0..256)
+                .map(|_| get_plan_and_collect_node_identifiers(policy, routing_info, cluster)[0])
+                .collect()
+        }
+
+        let policy = DefaultPolicy {
+            preferences: NodeLocationPreference::Datacenter("eu".to_owned()),
+            is_power_of_two_choices_enabled: true,
+            ..Default::default()
+        };
+
+        // Token-unaware statements: the most loaded of the local nodes 1, 2, 3 is never picked,
+        // because it loses against any other candidate.
+        {
+            let cluster = mock_cluster_state_for_token_unaware_tests().await;
+            node_by_id(&cluster, 1).record_latency(Duration::from_millis(1));
+            node_by_id(&cluster, 2).record_latency(Duration::from_millis(10));
+            let _in_flight = node_by_id(&cluster, 2).start_request();
+
+            let picked = first_targets(&policy, &EMPTY_ROUTING_INFO, &cluster);
+            assert!(!picked.contains(&2), "{picked:?}");
+            assert!(picked.contains(&3), "{picked:?}");
+        }
+
+        // Token-aware statements: of the two local replicas, A and G, the less loaded is always picked.
+        {
+            use crate::routing::locator::test::{A, G};
+
+            let cluster = mock_cluster_state_for_token_aware_tests().await;
+            node_by_id(&cluster, A).record_latency(Duration::from_millis(5));
+            let _in_flight = node_by_id(&cluster, A).start_request();
+            node_by_id(&cluster, G).record_latency(Duration::from_millis(5));
+
+            let routing_info = RoutingInfo {
+                token: Some(Token::new(160)),
+                table: Some(TABLE_NTS_RF_2),
+                consistency: Consistency::Two,
+                ..Default::default()
+            };
+            let picked = first_targets(&policy, &routing_info, &cluster);
+            assert_eq!(picked, HashSet::from([G]));
+        }
+    }
+
     #[tokio::test]
     async fn test_default_policy_with_token_aware_statements() {
         setup_tracing();
@@ -2066,6 +2244,32 @@ mod tests {
                     .group([E]) // remote nodes
                     .build(),
             },
+            // Keyspace NTS with RF=2 with enabled DC failover, LWT optimisation disabled
+            Test {
+                policy: DefaultPolicy {
+                    preferences: NodeLocationPreference::Datacenter("eu".to_owned()),
+                    is_token_aware: true,
+                    permit_dc_failover: true,
+                    is_lwt_optimisation_enabled: false,
+                    ..Default::default()
+                },
+                routing_info: RoutingInfo {
+                    token: Some(Token::new(160)),
+                    table: Some(TABLE_NTS_RF_2),
+                    consistency: Consistency::Two,
+                    is_confirmed_lwt: true,
+                    ..Default::default()
+                },
+                // going through the ring, we get order: F , A , C , D , G , B , E
+                //                                      us  eu  eu  us  eu  eu  us
+                //                                      r2  r1  r1  r1  r2  r1  r1
+                expected_groups: ExpectedGroupsBuilder::new()
+                    .group([A, G]) // pick + fallback local replicas
+                    .group([F, D]) // remote replicas
+                    .group([C, B]) // local nodes
+                    .group([E]) // remote nodes
+                    .build(),
+            },
             // Keyspace NTS with RF=2 with enabled DC failover, shuffling replicas disabled
             Test {
                 policy: DefaultPolicy {
@@ -2506,6 +2710,9 @@ mod tests {
 
                 Some(&FHostFilter)
             },
+            &Default::default(),
+            &Default::default(),
+            &Default::default(),
             TabletsInfo::new(),
             &HashMap::new(),
             #[cfg(feature = "metrics")]
@@ -3208,6 +3415,8 @@ mod latency_awareness {
                 pick_predicate,
                 latency_awareness: Some(latency_awareness),
                 fixed_seed: None,
+                is_lwt_optimisation_enabled: true,
+                is_power_of_two_choices_enabled: false,
             }
         }
 
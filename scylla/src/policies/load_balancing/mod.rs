@@ -11,9 +11,11 @@ use scylla_cql::frame::{response::result::TableSpec, types};
 
 use std::time::Duration;
 
+mod circuit_breaker;
 mod default;
 mod plan;
 mod single_target;
+pub use circuit_breaker::{CircuitBreakerPolicy, CircuitBreakerPolicyBuilder};
 pub use default::{DefaultPolicy, DefaultPolicyBuilder, LatencyAwarenessBuilder};
 pub use plan::Plan;
 pub use single_target::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
@@ -231,8 +231,11 @@ mod tests {
         let cluster_state = ClusterState {
             known_peers: Default::default(),
             all_nodes: Default::default(),
+            replaced_host_ids: Default::default(),
             keyspaces: Default::default(),
+            partial_metadata_error: None,
             locator,
+            partitioners: Default::default(),
         };
         let routing_info = RoutingInfo::default();
         let plan = Plan::new(&policy, &routing_info, &cluster_state);
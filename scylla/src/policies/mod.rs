@@ -6,6 +6,8 @@
 //! This includes:
 //! - HostFilter, which is a way to filter out some nodes and thus
 //!   not contact them at all on any condition.
+//! - HostIdChangePolicy, which decides what to do when a node at an already known
+//!   address reports a different host id.
 //! - AddressTranslator, which allows contacting a node through a different address
 //!   than its broadcast address (e.g., when it's behind a NAT).
 //! - LoadBalancingPolicy, which decides which nodes and shards to contact for each
@@ -13,11 +15,18 @@
 //! - SpeculativeExecutionPolicy, which decides if the driver will send speculative
 //!   requests to the next hosts when the current host takes too long to respond.
 //! - RetryPolicy, which decides whether and how to retry a request.
+//! - RequestRateLimiter, which delays or rejects requests before they are sent,
+//!   to protect the cluster from bursts of load.
+//! - ReconnectionPolicy, which decides how long to wait before reopening connections
+//!   to a node after failing to open them.
 //! - TODO
 
 pub mod address_translator;
 pub mod host_filter;
+pub mod host_id_change;
 pub mod load_balancing;
+pub mod rate_limiter;
+pub mod reconnection;
 pub mod retry;
 pub mod speculative_execution;
 pub mod timestamp_generator;
@@ -0,0 +1,326 @@
+//! Request rate limiters.
+//!
+//! A [`RequestRateLimiter`] is consulted by the [`Session`](crate::client::session::Session)
+//! before each request is sent to the cluster. It may let the request through immediately,
+//! delay it until it is allowed to proceed, or reject it altogether. This protects
+//! the cluster from bursts of load generated by the application.
+//!
+//! Two implementations are provided:
+//! - [`TokenBucketRateLimiter`], which bounds the rate of requests,
+//! - [`ConcurrencyRateLimiter`], which bounds the number of requests in flight.
+//!
+//! A rate limiter is configured with
+//! [`SessionBuilder::request_rate_limiter`](crate::client::session_builder::SessionBuilder::request_rate_limiter).
+
+use std::fmt::Debug;
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use thiserror::Error;
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+/// Decides whether a request may be sent to the cluster.
+#[async_trait]
+pub trait RequestRateLimiter: Debug + Send + Sync {
+    /// Called before a request is sent to the cluster.
+    ///
+    /// The returned future resolves when the request is allowed to proceed.
+    /// The request is then executed while the returned [`RateLimitPermit`] is held,
+    /// and the permit is dropped when the request is finished (including all its
+    /// retries and speculative executions). If an error is returned, the request
+    /// fails with [`ExecutionError::RateLimited`](crate::errors::ExecutionError::RateLimited)
+    /// without being sent.
+    async fn acquire(&self) -> Result<RateLimitPermit, RateLimitError>;
+}
+
+/// Permission to execute a request, granted by a [`RequestRateLimiter`].
+///
+/// The permit may hold some resources of the rate limiter, which are released
+/// when the permit is dropped.
+pub struct RateLimitPermit {
+    on_release: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl RateLimitPermit {
+    /// Creates a permit that does not need to be released.
+    pub fn new() -> Self {
+        Self { on_release: None }
+    }
+
+    /// Creates a permit that calls `on_release` when dropped.
+    pub fn with_release(on_release: impl FnOnce() + Send + Sync + 'static) -> Self {
+        Self {
+            on_release: Some(Box::new(on_release)),
+        }
+    }
+}
+
+impl Default for RateLimitPermit {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for RateLimitPermit {
+    fn drop(&mut self) {
+        if let Some(on_release) = self.on_release.take() {
+            on_release();
+        }
+    }
+}
+
+impl Debug for RateLimitPermit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimitPermit")
+            .field("releasable", &self.on_release.is_some())
+            .finish()
+    }
+}
+
+/// An error returned by a [`RequestRateLimiter`] that rejected a request.
+#[derive(Error, Debug, Clone)]
+#[non_exhaustive]
+pub enum RateLimitError {
+    /// The request would have to wait longer than the limiter's maximum delay.
+    #[error(
+        "Request rejected by the rate limiter: it would have to wait longer than {}ms",
+        Duration::as_millis(.0)
+    )]
+    MaxDelayExceeded(Duration),
+
+    /// The rate limiter has been closed and does not admit any more requests.
+    #[error("Request rejected by the rate limiter: the limiter is closed")]
+    Closed,
+
+    /// A custom error returned by a user-provided rate limiter.
+    #[error("Request rejected by the rate limiter: {0}")]
+    Custom(Arc<dyn std::error::Error + Send + Sync>),
+}
+
+/// A rate limiter based on the token bucket algorithm.
+///
+/// The bucket holds at most `burst` tokens and is refilled with `rate` tokens
+/// per second. Each request consumes one token. If the bucket is empty,
+/// the request waits until a token becomes available, or is rejected
+/// if it would have to wait longer than the configured maximum delay.
+///
+/// # Example
+/// ```
+/// # use std::num::NonZeroU32;
+/// # use std::time::Duration;
+/// # use scylla::policies::rate_limiter::TokenBucketRateLimiter;
+/// // At most 1000 requests per second on average, with bursts of up to 100 requests.
+/// // Requests that would have to wait longer than 50ms are rejected.
+/// let limiter = TokenBucketRateLimiter::new(
+///     NonZeroU32::new(1000).unwrap(),
+///     NonZeroU32::new(100).unwrap(),
+/// )
+/// .with_max_delay(Duration::from_millis(50));
+/// ```
+#[derive(Debug)]
+pub struct TokenBucketRateLimiter {
+    interval: Duration,
+    burst: u32,
+    max_delay: Option<Duration>,
+    // The moment at which the bucket becomes full, assuming no more tokens are taken.
+    // Every taken token pushes it by `interval`.
+    full_at: Mutex<Instant>,
+}
+
+impl TokenBucketRateLimiter {
+    /// Creates a limiter admitting `rate` requests per second on average,
+    /// and at most `burst` requests at once.
+    pub fn new(rate: NonZeroU32, burst: NonZeroU32) -> Self {
+        Self {
+            interval: Duration::from_secs(1) / rate.get(),
+            burst: burst.get(),
+            max_delay: None,
+            full_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Sets the maximum time a request may wait for a token.
+    /// Requests that would have to wait longer are rejected with
+    /// [`RateLimitError::MaxDelayExceeded`]. Use [`Duration::ZERO`]
+    /// to reject requests instead of delaying them.
+    ///
+    /// By default, requests wait for as long as needed.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Takes a token and returns how long the caller has to wait before using it.
+    fn reserve(&self, now: Instant) -> Result<Duration, RateLimitError> {
+        let mut full_at = self.full_at.lock().unwrap();
+        let capacity = self.interval * self.burst;
+        // The bucket cannot hold more than `burst` tokens.
+        let start = (*full_at).max(now);
+        let new_full_at = start + self.interval;
+        let delay = new_full_at.saturating_duration_since(now + capacity);
+        if let Some(max_delay) = self.max_delay {
+            if delay > max_delay {
+                return Err(RateLimitError::MaxDelayExceeded(max_delay));
+            }
+        }
+        *full_at = new_full_at;
+        Ok(delay)
+    }
+}
+
+#[async_trait]
+impl RequestRateLimiter for TokenBucketRateLimiter {
+    async fn acquire(&self) -> Result<RateLimitPermit, RateLimitError> {
+        let delay = self.reserve(Instant::now())?;
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+        Ok(RateLimitPermit::new())
+    }
+}
+
+/// A rate limiter that bounds the number of concurrently executed requests.
+///
+/// A request which would exceed the limit waits until one of the in-flight
+/// requests finishes, or is rejected if it would have to wait longer than
+/// the configured maximum delay.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use scylla::policies::rate_limiter::ConcurrencyRateLimiter;
+/// // At most 256 requests in flight; reject requests instead of queuing them.
+/// let limiter = ConcurrencyRateLimiter::new(256).with_max_delay(Duration::ZERO);
+/// ```
+#[derive(Debug)]
+pub struct ConcurrencyRateLimiter {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    max_delay: Option<Duration>,
+}
+
+impl ConcurrencyRateLimiter {
+    /// Creates a limiter admitting at most `max_concurrent` requests at once.
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            max_delay: None,
+        }
+    }
+
+    /// Sets the maximum time a request may wait for one of the in-flight requests
+    /// to finish. Requests that would have to wait longer are rejected with
+    /// [`RateLimitError::MaxDelayExceeded`]. Use [`Duration::ZERO`]
+    /// to reject requests instead of queuing them.
+    ///
+    /// By default, requests wait for as long as needed.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = Some(max_delay);
+        self
+    }
+
+    /// Returns the number of requests currently admitted by this limiter.
+    pub fn in_flight(&self) -> usize {
+        self.max_concurrent - self.semaphore.available_permits()
+    }
+}
+
+#[async_trait]
+impl RequestRateLimiter for ConcurrencyRateLimiter {
+    async fn acquire(&self) -> Result<RateLimitPermit, RateLimitError> {
+        let semaphore = Arc::clone(&self.semaphore);
+        let permit = match self.max_delay {
+            Some(max_delay) if max_delay.is_zero() => {
+                semaphore.try_acquire_owned().map_err(|err| match err {
+                    tokio::sync::TryAcquireError::Closed => RateLimitError::Closed,
+                    tokio::sync::TryAcquireError::NoPermits => {
+                        RateLimitError::MaxDelayExceeded(max_delay)
+                    }
+                })?
+            }
+            Some(max_delay) => tokio::time::timeout(max_delay, semaphore.acquire_owned())
+                .await
+                .map_err(|_| RateLimitError::MaxDelayExceeded(max_delay))?
+                .map_err(|_| RateLimitError::Closed)?,
+            None => semaphore
+                .acquire_owned()
+                .await
+                .map_err(|_| RateLimitError::Closed)?,
+        };
+        Ok(RateLimitPermit::with_release(move || drop(permit)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::{
+        ConcurrencyRateLimiter, RateLimitError, RequestRateLimiter, TokenBucketRateLimiter,
+    };
+
+    fn non_zero(n: u32) -> NonZeroU32 {
+        NonZeroU32::new(n).unwrap()
+    }
+
+    #[test]
+    fn token_bucket_allows_burst_then_delays() {
+        let limiter = TokenBucketRateLimiter::new(non_zero(10), non_zero(3));
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve(now).unwrap(), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve(now).unwrap(), Duration::from_millis(100));
+        assert_eq!(limiter.reserve(now).unwrap(), Duration::from_millis(200));
+
+        // After a long pause, the bucket is full again, but not fuller.
+        let later = now + Duration::from_secs(10);
+        for _ in 0..3 {
+            assert_eq!(limiter.reserve(later).unwrap(), Duration::ZERO);
+        }
+        assert_eq!(limiter.reserve(later).unwrap(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn token_bucket_rejects_beyond_max_delay() {
+        let limiter = TokenBucketRateLimiter::new(non_zero(10), non_zero(1))
+            .with_max_delay(Duration::from_millis(150));
+        let now = Instant::now();
+
+        assert_eq!(limiter.reserve(now).unwrap(), Duration::ZERO);
+        assert_eq!(limiter.reserve(now).unwrap(), Duration::from_millis(100));
+        assert!(matches!(
+            limiter.reserve(now),
+            Err(RateLimitError::MaxDelayExceeded(_))
+        ));
+        // A rejected request does not consume a token.
+        let later = now + Duration::from_millis(100);
+        assert_eq!(limiter.reserve(later).unwrap(), Duration::from_millis(100));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn concurrency_limiter_bounds_in_flight_requests() {
+        let limiter = ConcurrencyRateLimiter::new(2).with_max_delay(Duration::from_millis(10));
+
+        let first = limiter.acquire().await.unwrap();
+        let _second = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.in_flight(), 2);
+        assert!(matches!(
+            limiter.acquire().await,
+            Err(RateLimitError::MaxDelayExceeded(_))
+        ));
+
+        drop(first);
+        assert_eq!(limiter.in_flight(), 1);
+        let _third = limiter.acquire().await.unwrap();
+        assert_eq!(limiter.in_flight(), 2);
+    }
+}
@@ -0,0 +1,116 @@
+//! Reconnection policies decide how long the connection pool of a node waits
+//! before it tries to open connections again, after opening them failed.
+//!
+//! Delays grow with the number of consecutive failed attempts, so that nodes
+//! which are down or recovering are not flooded with connection attempts.
+//! The counter is reset as soon as an attempt succeeds.
+
+use std::time::Duration;
+
+/// The policy that decides how long to wait before the next attempt to (re)establish
+/// connections to a node, after the previous attempts failed.
+///
+/// The policy is consulted independently by the connection pool of each node.
+pub trait ReconnectionPolicy: std::fmt::Debug + Send + Sync {
+    /// Returns the delay before the next attempt to open connections,
+    /// given the number of consecutive failed attempts (always at least 1).
+    fn reconnection_delay(&self, consecutive_failures: u32) -> Duration;
+}
+
+/// A [`ReconnectionPolicy`] that waits the same amount of time before each attempt.
+#[derive(Debug, Clone)]
+pub struct ConstantReconnectionPolicy {
+    delay: Duration,
+}
+
+impl ConstantReconnectionPolicy {
+    /// Creates a policy waiting `delay` before each attempt.
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+}
+
+impl ReconnectionPolicy for ConstantReconnectionPolicy {
+    fn reconnection_delay(&self, _consecutive_failures: u32) -> Duration {
+        self.delay
+    }
+}
+
+/// A [`ReconnectionPolicy`] that doubles the delay after each failed attempt,
+/// starting with a base delay, up to a maximum delay.
+///
+/// This is the default policy, with [`ExponentialReconnectionPolicy::DEFAULT_BASE_DELAY`]
+/// and [`ExponentialReconnectionPolicy::DEFAULT_MAX_DELAY`].
+#[derive(Debug, Clone)]
+pub struct ExponentialReconnectionPolicy {
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl ExponentialReconnectionPolicy {
+    /// Default delay after the first failed attempt.
+    pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(100);
+
+    /// Default upper bound of the delay.
+    pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(10);
+
+    /// Creates a policy waiting `base_delay` after the first failed attempt,
+    /// twice as long after the second one, and so on, but never longer than `max_delay`.
+    ///
+    /// # Panics
+    /// Panics if `base_delay` is greater than `max_delay`.
+    pub fn new(base_delay: Duration, max_delay: Duration) -> Self {
+        assert!(
+            base_delay <= max_delay,
+            "base_delay ({:?}) must not be greater than max_delay ({:?})",
+            base_delay,
+            max_delay
+        );
+        Self {
+            base_delay,
+            max_delay,
+        }
+    }
+}
+
+impl Default for ExponentialReconnectionPolicy {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_BASE_DELAY, Self::DEFAULT_MAX_DELAY)
+    }
+}
+
+impl ReconnectionPolicy for ExponentialReconnectionPolicy {
+    fn reconnection_delay(&self, consecutive_failures: u32) -> Duration {
+        let exponent = consecutive_failures.saturating_sub(1);
+        2_u32
+            .checked_pow(exponent)
+            .and_then(|multiplier| self.base_delay.checked_mul(multiplier))
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{ConstantReconnectionPolicy, ExponentialReconnectionPolicy, ReconnectionPolicy};
+
+    #[test]
+    fn constant_reconnection_policy() {
+        let policy = ConstantReconnectionPolicy::new(Duration::from_secs(1));
+        for failures in [1, 2, 100, u32::MAX] {
+            assert_eq!(policy.reconnection_delay(failures), Duration::from_secs(1));
+        }
+    }
+
+    #[test]
+    fn exponential_reconnection_policy() {
+        let policy =
+            ExponentialReconnectionPolicy::new(Duration::from_millis(100), Duration::from_secs(1));
+        let delays: Vec<_> = (1..=6)
+            .map(|failures| policy.reconnection_delay(failures).as_millis())
+            .collect();
+        assert_eq!(delays, [100, 200, 400, 800, 1000, 1000]);
+        assert_eq!(policy.reconnection_delay(u32::MAX), Duration::from_secs(1));
+    }
+}
@@ -10,9 +10,10 @@ use futures::{
 #[cfg(feature = "metrics")]
 use std::sync::Arc;
 use std::{future::Future, time::Duration};
-use tracing::{trace_span, Instrument};
+use tracing::{trace, trace_span, Instrument};
 
-use crate::errors::{RequestAttemptError, RequestError};
+use crate::cluster::NodeRef;
+use crate::errors::RequestError;
 #[cfg(feature = "metrics")]
 use crate::observability::metrics::Metrics;
 use crate::response::Coordinator;
@@ -36,6 +37,15 @@ pub trait SpeculativeExecutionPolicy: std::fmt::Debug + Send + Sync {
 
     /// The delay between each speculative execution
     fn retry_interval(&self, context: &Context) -> Duration;
+
+    /// The delay after which the next speculative execution is triggered,
+    /// when the latest execution was sent to the given node.
+    ///
+    /// By default, the node is disregarded and [retry_interval](Self::retry_interval) is used.
+    fn retry_interval_for_node(&self, context: &Context, node: NodeRef<'_>) -> Duration {
+        let _ = node;
+        self.retry_interval(context)
+    }
 }
 
 /// A [`SpeculativeExecutionPolicy`] that schedules a given number of speculative
@@ -64,6 +74,26 @@ pub struct PercentileSpeculativeExecutionPolicy {
     pub percentile: f64,
 }
 
+/// A policy that triggers speculative executions when the request to the current
+/// target takes longer than a given percentile of latencies of that particular node.
+///
+/// As opposed to [`PercentileSpeculativeExecutionPolicy`], which uses latencies of
+/// requests sent to all nodes, slow nodes do not cause needless speculative executions
+/// of requests sent to fast ones, and vice versa.
+/// Until any latency of the node is recorded, the percentile of latencies of all
+/// nodes is used instead.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone)]
+pub struct PerNodePercentileSpeculativeExecutionPolicy {
+    /// The maximum number of speculative executions that will be triggered
+    /// for a given request (does not include the initial request)
+    pub max_retry_count: usize,
+
+    /// The percentile that a request's latency must fall into to be considered
+    /// slow (ex: 99.0)
+    pub percentile: f64,
+}
+
 impl SpeculativeExecutionPolicy for SimpleSpeculativeExecutionPolicy {
     fn max_retry_count(&self, _: &Context) -> usize {
         self.max_retry_count
@@ -96,6 +126,49 @@ impl SpeculativeExecutionPolicy for PercentileSpeculativeExecutionPolicy {
     }
 }
 
+#[cfg(feature = "metrics")]
+impl SpeculativeExecutionPolicy for PerNodePercentileSpeculativeExecutionPolicy {
+    fn max_retry_count(&self, _: &Context) -> usize {
+        self.max_retry_count
+    }
+
+    fn retry_interval(&self, context: &Context) -> Duration {
+        PercentileSpeculativeExecutionPolicy {
+            max_retry_count: self.max_retry_count,
+            percentile: self.percentile,
+        }
+        .retry_interval(context)
+    }
+
+    fn retry_interval_for_node(&self, context: &Context, node: NodeRef<'_>) -> Duration {
+        match context
+            .metrics
+            .get_node_latency_percentile_ms(node.host_id, self.percentile)
+        {
+            // Sub-millisecond latencies must not trigger speculative executions immediately.
+            Ok(ms) => Duration::from_millis(ms.max(1)),
+            Err(_) => self.retry_interval(context),
+        }
+    }
+}
+
+/// Cancels the attempts that are still in flight after another attempt has completed.
+///
+/// Dropping an attempt's future marks its stream id as orphaned on the connection,
+/// and requests that have not been written to the socket yet are not sent at all.
+fn cancel_losing_attempts<F>(_context: &Context, in_flight: FuturesUnordered<F>) {
+    let wasted = in_flight.len();
+    if wasted == 0 {
+        return;
+    }
+    trace!("Cancelling {} superfluous in-flight attempt(s)", wasted);
+    #[cfg(feature = "metrics")]
+    _context
+        .metrics
+        .add_wasted_speculative_attempts(wasted as u64);
+    drop(in_flight);
+}
+
 /// Checks if a result created in a speculative execution branch can be ignored.
 ///
 /// We should ignore errors such that their presence when executing the request
@@ -103,70 +176,69 @@ impl SpeculativeExecutionPolicy for PercentileSpeculativeExecutionPolicy {
 fn can_be_ignored<ResT>(result: &Result<ResT, RequestError>) -> bool {
     match result {
         Ok(_) => false,
-        // Do not remove this lint!
-        // It's there for a reason - we don't want new variants
-        // automatically fall under `_` pattern when they are introduced.
-        #[deny(clippy::wildcard_enum_match_arm)]
-        Err(e) => match e {
-            // This error should not appear it. Anyway, if it possibly could
-            // in the future, it should not be ignored.
-            RequestError::EmptyPlan => false,
-
-            // Request execution timed out.
-            RequestError::RequestTimeout(_) => false,
-
-            // Can try on another node.
-            RequestError::ConnectionPoolError { .. } => true,
-
-            RequestError::LastAttemptError(e) => {
-                // Do not remove this lint!
-                // It's there for a reason - we don't want new variants
-                // automatically fall under `_` pattern when they are introduced.
-                #[deny(clippy::wildcard_enum_match_arm)]
-                match e {
-                    // Errors that will almost certainly appear for other nodes as well
-                    RequestAttemptError::SerializationError(_)
-                    | RequestAttemptError::CqlRequestSerialization(_)
-                    | RequestAttemptError::BodyExtensionsParseError(_)
-                    | RequestAttemptError::CqlResultParseError(_)
-                    | RequestAttemptError::CqlErrorParseError(_)
-                    | RequestAttemptError::UnexpectedResponse(_)
-                    | RequestAttemptError::RepreparedIdChanged { .. }
-                    | RequestAttemptError::RepreparedIdMissingInBatch
-                    | RequestAttemptError::NonfinishedPagingState => false,
-
-                    // Errors that can be ignored
-                    RequestAttemptError::BrokenConnectionError(_)
-                    | RequestAttemptError::UnableToAllocStreamId => true,
-
-                    // Handle DbErrors
-                    RequestAttemptError::DbError(db_error, _) => db_error.can_speculative_retry(),
-                }
-            }
-        },
+        Err(e) => can_error_be_ignored(e),
+    }
+}
+
+fn can_error_be_ignored(error: &RequestError) -> bool {
+    // Do not remove this lint!
+    // It's there for a reason - we don't want new variants
+    // automatically fall under `_` pattern when they are introduced.
+    #[deny(clippy::wildcard_enum_match_arm)]
+    match error {
+        // This error should not appear it. Anyway, if it possibly could
+        // in the future, it should not be ignored.
+        RequestError::EmptyPlan => false,
+
+        // Request execution timed out.
+        RequestError::RequestTimeout(_) | RequestError::DeadlineExceeded => false,
+
+        // Request execution was cancelled by the user.
+        RequestError::Cancelled => false,
+
+        // The rate limiter is consulted once per request, not per fiber.
+        RequestError::RateLimited(_) => false,
+
+        // Can try on another node.
+        RequestError::ConnectionPoolError { .. } => true,
+
+        // Errors specific to the node can be ignored,
+        // as other fibers may still succeed.
+        RequestError::LastAttemptError(e) => e.is_safe_to_retry_idempotent(),
+
+        RequestError::Attempted { error, .. } => can_error_be_ignored(error),
     }
 }
 
 const EMPTY_PLAN_ERROR: RequestError = RequestError::EmptyPlan;
 
-pub(crate) async fn execute<QueryFut, ResT>(
+/// Runs the request with speculative executions.
+///
+/// `next_target` returns the node which the next execution started by
+/// `query_runner_generator` is going to be sent to, if known.
+pub(crate) async fn execute<'a, QueryFut, ResT>(
     policy: &dyn SpeculativeExecutionPolicy,
     context: &Context,
     mut query_runner_generator: impl FnMut(bool) -> QueryFut,
+    next_target: impl Fn() -> Option<NodeRef<'a>>,
 ) -> Result<(ResT, Coordinator), RequestError>
 where
     QueryFut: Future<Output = Option<Result<(ResT, Coordinator), RequestError>>>,
 {
     let mut retries_remaining = policy.max_retry_count(context);
-    let retry_interval = policy.retry_interval(context);
+    let retry_interval = || match next_target() {
+        Some(node) => policy.retry_interval_for_node(context, node),
+        None => policy.retry_interval(context),
+    };
 
     let mut async_tasks = FuturesUnordered::new();
+    let interval = retry_interval();
     async_tasks.push(
         query_runner_generator(false)
             .instrument(trace_span!("Speculative execution: original query")),
     );
 
-    let sleep = tokio::time::sleep(retry_interval).fuse();
+    let sleep = tokio::time::sleep(interval).fuse();
     tokio::pin!(sleep);
 
     let mut last_error = None;
@@ -174,16 +246,20 @@ where
         futures::select! {
             _ = &mut sleep => {
                 if retries_remaining > 0 {
+                    #[cfg(feature = "metrics")]
+                    context.metrics.inc_speculative_executions_num();
+                    let interval = retry_interval();
                     async_tasks.push(query_runner_generator(true).instrument(trace_span!("Speculative execution", retries_remaining = retries_remaining)));
                     retries_remaining -= 1;
 
                     // reset the timeout
-                    sleep.set(tokio::time::sleep(retry_interval).fuse());
+                    sleep.set(tokio::time::sleep(interval).fuse());
                 }
             }
             res = async_tasks.select_next_some() => {
                 if let Some(r) = res {
                     if !can_be_ignored(&r) {
+                        cancel_losing_attempts(context, async_tasks);
                         return r;
                     } else {
                         last_error = Some(r)
@@ -212,17 +288,21 @@ mod tests {
     // Starting paused is done with `#[tokio::test(flavor = "current_thread", start_paused = true)]`.
     // Pausing can only be done with current_thread executor.
 
-    #[cfg(feature = "metrics")]
     use std::sync::Arc;
     use std::sync::LazyLock;
     use std::time::Duration;
 
     use assert_matches::assert_matches;
 
+    use crate::cluster::{Node, NodeAddr, NodeRef};
     use crate::errors::{RequestAttemptError, RequestError};
     #[cfg(feature = "metrics")]
     use crate::observability::metrics::Metrics;
-    use crate::policies::speculative_execution::{Context, SimpleSpeculativeExecutionPolicy};
+    #[cfg(feature = "metrics")]
+    use crate::policies::speculative_execution::PerNodePercentileSpeculativeExecutionPolicy;
+    use crate::policies::speculative_execution::{
+        Context, SimpleSpeculativeExecutionPolicy, SpeculativeExecutionPolicy,
+    };
     use crate::response::Coordinator;
 
     static EMPTY_CONTEXT: LazyLock<Context> = LazyLock::new(|| Context {
@@ -264,7 +344,7 @@ mod tests {
         };
 
         let now = tokio::time::Instant::now();
-        let res = super::execute(&policy, &EMPTY_CONTEXT, generator).await;
+        let res = super::execute(&policy, &EMPTY_CONTEXT, generator, || None).await;
         assert_matches!(
             res,
             Err(RequestError::LastAttemptError(
@@ -313,7 +393,7 @@ mod tests {
         };
 
         let now = tokio::time::Instant::now();
-        let res = super::execute(&policy, &EMPTY_CONTEXT, generator).await;
+        let res = super::execute(&policy, &EMPTY_CONTEXT, generator, || None).await;
         assert_matches!(
             res,
             Err(RequestError::LastAttemptError(
@@ -348,7 +428,7 @@ mod tests {
         };
 
         let now = tokio::time::Instant::now();
-        let res = super::execute(&policy, &EMPTY_CONTEXT, generator).await;
+        let res = super::execute(&policy, &EMPTY_CONTEXT, generator, || None).await;
         assert_matches!(
             res,
             Err(RequestError::LastAttemptError(
@@ -365,4 +445,152 @@ mod tests {
             now.checked_add(Duration::from_secs(10)).unwrap()
         )
     }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_losing_attempts_are_cancelled() {
+        let policy = SimpleSpeculativeExecutionPolicy {
+            max_retry_count: 5,
+            retry_interval: Duration::from_secs(1),
+        };
+        let context = Context {
+            #[cfg(feature = "metrics")]
+            metrics: Arc::new(Metrics::new()),
+        };
+
+        let running = std::sync::Arc::new(());
+        let generator = {
+            // Index of the fiber, 0 for first execution.
+            let mut counter = 0;
+            let running = running.clone();
+            move |_first: bool| {
+                let fiber_idx = counter;
+                counter += 1;
+                let running = running.clone();
+                async move {
+                    let _running = running;
+                    // The third execution (started at t+2) completes first, at t+2.5.
+                    // Any non-ignorable result completes the request.
+                    let duration = if fiber_idx == 2 { 500 } else { 10_000 };
+                    tokio::time::sleep(Duration::from_millis(duration)).await;
+                    Some(Err::<((), Coordinator), _>(RequestError::RequestTimeout(
+                        Duration::from_millis(duration),
+                    )))
+                }
+            }
+        };
+
+        let now = tokio::time::Instant::now();
+        let res = super::execute(&policy, &context, generator, || None).await;
+        assert_matches!(res, Err(RequestError::RequestTimeout(d)) if d == Duration::from_millis(500));
+        assert_eq!(
+            tokio::time::Instant::now(),
+            now.checked_add(Duration::from_millis(2500)).unwrap()
+        );
+        // All the other attempts have been dropped.
+        assert_eq!(std::sync::Arc::strong_count(&running), 1);
+
+        #[cfg(feature = "metrics")]
+        {
+            // Speculative executions were started at t+1 and t+2,
+            // the original execution and the first speculative one were wasted.
+            assert_eq!(context.metrics.get_speculative_executions_num(), 2);
+            assert_eq!(context.metrics.get_wasted_speculative_attempts_num(), 2);
+        }
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn test_per_node_percentile_intervals() {
+        let context = Context {
+            metrics: Arc::new(Metrics::new()),
+        };
+        let policy = PerNodePercentileSpeculativeExecutionPolicy {
+            max_retry_count: 1,
+            percentile: 99.0,
+        };
+        let fast_node = Arc::new(Node::new_for_test(None, None, None, None));
+        let slow_node = Arc::new(Node::new_for_test(None, None, None, None));
+        let unknown_node = Arc::new(Node::new_for_test(None, None, None, None));
+
+        // Without any latencies recorded, the default interval is used.
+        assert_eq!(
+            policy.retry_interval_for_node(&context, &fast_node),
+            Duration::from_millis(100)
+        );
+
+        for _ in 0..100 {
+            context.metrics.log_node_request(&fast_node, 5, false);
+            context.metrics.log_node_request(&slow_node, 50, false);
+        }
+        assert_eq!(
+            policy.retry_interval_for_node(&context, &fast_node),
+            Duration::from_millis(5)
+        );
+        assert_eq!(
+            policy.retry_interval_for_node(&context, &slow_node),
+            Duration::from_millis(50)
+        );
+        // Nodes without recorded latencies fall back to latencies of all nodes.
+        assert_eq!(
+            policy.retry_interval_for_node(&context, &unknown_node),
+            policy.retry_interval(&context)
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread", start_paused = true)]
+    async fn test_interval_depends_on_target() {
+        #[derive(Debug)]
+        struct TargetAwarePolicy;
+
+        impl SpeculativeExecutionPolicy for TargetAwarePolicy {
+            fn max_retry_count(&self, _: &Context) -> usize {
+                1
+            }
+
+            fn retry_interval(&self, _: &Context) -> Duration {
+                Duration::from_secs(1)
+            }
+
+            fn retry_interval_for_node(&self, _: &Context, node: NodeRef<'_>) -> Duration {
+                Duration::from_secs(node.address.port().into())
+            }
+        }
+
+        let node = Arc::new(Node::new_for_test(
+            None,
+            Some(NodeAddr::Translatable("127.0.0.1:3".parse().unwrap())),
+            None,
+            None,
+        ));
+        let generator = {
+            let mut counter = 0;
+            let node = node.clone();
+            move |_first: bool| {
+                let fiber_idx = counter;
+                counter += 1;
+                let node = node.clone();
+                async move {
+                    if fiber_idx == 0 {
+                        tokio::time::sleep(Duration::from_secs(10)).await;
+                    }
+                    Some(Ok((
+                        (),
+                        Coordinator::new_for_test(node, None, "127.0.0.1:3".parse().unwrap()),
+                    )))
+                }
+            }
+        };
+
+        let now = tokio::time::Instant::now();
+        let res = super::execute(&TargetAwarePolicy, &EMPTY_CONTEXT, generator, || {
+            Some(&node)
+        })
+        .await;
+        assert_matches!(res, Ok(((), _)));
+        // The speculative execution was started after the interval chosen for the node.
+        assert_eq!(
+            tokio::time::Instant::now(),
+            now.checked_add(Duration::from_secs(3)).unwrap()
+        );
+    }
 }
@@ -0,0 +1,91 @@
+//! Builder of `DELETE` statements.
+
+use crate::statement::Statement;
+
+use super::{write_columns, write_where, BoundValues, QueryBuilderError, Relation, Using};
+
+/// Builder of a `DELETE` statement.
+///
+/// If no columns are given, whole rows are deleted.
+#[derive(Debug)]
+pub struct Delete<'a> {
+    table: String,
+    columns: Vec<String>,
+    relations: Vec<Relation<'a>>,
+    if_exists: bool,
+    using: Using,
+}
+
+impl<'a> Delete<'a> {
+    /// Starts building a `DELETE` statement removing data from the given table.
+    /// The table name may be qualified with a keyspace name (`keyspace.table`).
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            columns: Vec::new(),
+            relations: Vec::new(),
+            if_exists: false,
+            using: Using::default(),
+        }
+    }
+
+    /// Deletes only the given column instead of whole rows.
+    pub fn column(mut self, column: impl Into<String>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Deletes only the given columns instead of whole rows.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.columns.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a relation to the `WHERE` clause, selecting the deleted rows.
+    pub fn where_(mut self, relation: Relation<'a>) -> Self {
+        self.relations.push(relation);
+        self
+    }
+
+    /// Makes the deletion conditional (lightweight transaction): it is only applied
+    /// if the row exists.
+    pub fn if_exists(mut self) -> Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// Sets the timestamp (in microseconds) of the deletion.
+    pub fn using_timestamp(mut self, timestamp: i64) -> Self {
+        self.using.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Builds the statement, together with the values bound to it.
+    ///
+    /// Fails if no relation is given in the `WHERE` clause,
+    /// as such a statement is not valid CQL.
+    pub fn build(self) -> Result<(Statement, BoundValues<'a>), QueryBuilderError> {
+        if self.relations.is_empty() {
+            return Err(QueryBuilderError::MissingWhereClause);
+        }
+
+        let mut cql = String::from("DELETE ");
+        let mut values = BoundValues::default();
+
+        if !self.columns.is_empty() {
+            write_columns(&mut cql, &self.columns);
+            cql.push(' ');
+        }
+        cql.push_str("FROM ");
+        cql.push_str(&self.table);
+
+        self.using.write(&mut cql, &mut values);
+        write_where(&mut cql, self.relations, &mut values);
+
+        if self.if_exists {
+            cql.push_str(" IF EXISTS");
+        }
+
+        Ok((Statement::new(cql), values))
+    }
+}
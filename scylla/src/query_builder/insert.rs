@@ -0,0 +1,90 @@
+//! Builder of `INSERT` statements.
+
+use scylla_cql::serialize::value::SerializeValue;
+
+use crate::statement::Statement;
+
+use super::{write_columns, BoundValues, QueryBuilderError, Using};
+
+/// Builder of an `INSERT` statement.
+#[derive(Debug)]
+pub struct Insert<'a> {
+    table: String,
+    columns: Vec<String>,
+    values: BoundValues<'a>,
+    if_not_exists: bool,
+    using: Using,
+}
+
+impl<'a> Insert<'a> {
+    /// Starts building an `INSERT` statement writing to the given table.
+    /// The table name may be qualified with a keyspace name (`keyspace.table`).
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            columns: Vec::new(),
+            values: BoundValues::default(),
+            if_not_exists: false,
+            using: Using::default(),
+        }
+    }
+
+    /// Sets the value of a column in the inserted row.
+    pub fn value(
+        mut self,
+        column: impl Into<String>,
+        value: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        self.columns.push(column.into());
+        self.values.push(Box::new(value));
+        self
+    }
+
+    /// Makes the insert conditional (lightweight transaction): it is only applied
+    /// if the row does not exist yet.
+    pub fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Sets the time to live (in seconds) of the inserted values.
+    pub fn using_ttl(mut self, ttl: i32) -> Self {
+        self.using.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the write timestamp (in microseconds) of the inserted values.
+    pub fn using_timestamp(mut self, timestamp: i64) -> Self {
+        self.using.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Builds the statement, together with the values bound to it.
+    ///
+    /// Fails if no [value](Self::value) is given, as such a statement is not valid CQL.
+    pub fn build(self) -> Result<(Statement, BoundValues<'a>), QueryBuilderError> {
+        if self.columns.is_empty() {
+            return Err(QueryBuilderError::InsertWithoutValues);
+        }
+
+        let mut cql = String::from("INSERT INTO ");
+        let mut values = self.values;
+
+        cql.push_str(&self.table);
+        cql.push_str(" (");
+        write_columns(&mut cql, &self.columns);
+        cql.push_str(") VALUES (");
+        for i in 0..self.columns.len() {
+            cql.push_str(if i == 0 { "?" } else { ", ?" });
+        }
+        cql.push(')');
+
+        if self.if_not_exists {
+            cql.push_str(" IF NOT EXISTS");
+        }
+
+        self.using.write(&mut cql, &mut values);
+
+        Ok((Statement::new(cql), values))
+    }
+}
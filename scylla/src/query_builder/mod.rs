@@ -0,0 +1,402 @@
+//! Programmatic construction of CQL statements.
+//!
+//! This module allows building `SELECT`, `INSERT`, `UPDATE` and `DELETE` statements
+//! without concatenating strings by hand. Values are never inlined into the statement
+//! text. Each of them is represented by a bind marker (`?`) and is returned alongside
+//! the [`Statement`](crate::statement::Statement) in [`BoundValues`], in the order in which the bind markers appear
+//! in the text, regardless of the order of builder method calls.
+//!
+//! Table and column names are put into the statement text as they are given.
+//! Case-sensitive names have to be quoted by the caller, e.g. `"\"MyColumn\""`.
+//!
+//! Building a statement which would not be valid CQL, e.g. an `UPDATE` without
+//! a `WHERE` clause, fails with a [`QueryBuilderError`].
+//!
+//! # Example
+//! ```rust
+//! # extern crate scylla;
+//! # use scylla::client::session::Session;
+//! # use std::error::Error;
+//! # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+//! use scylla::query_builder::{Insert, Relation, Select};
+//!
+//! let (statement, values) = Insert::new("ks.users")
+//!     .value("id", 42)
+//!     .value("name", "Ferris")
+//!     .using_ttl(3600)
+//!     .build()?;
+//! assert_eq!(
+//!     statement.contents,
+//!     "INSERT INTO ks.users (id, name) VALUES (?, ?) USING TTL ?"
+//! );
+//! session.query_unpaged(statement, values).await?;
+//!
+//! let (statement, values) = Select::new("ks.users")
+//!     .columns(["id", "name"])
+//!     .where_(Relation::eq("country", "PL"))
+//!     .where_(Relation::gt("age", 18))
+//!     .limit(100)
+//!     .allow_filtering()
+//!     .build();
+//! assert_eq!(
+//!     statement.contents,
+//!     "SELECT id, name FROM ks.users WHERE country = ? AND age > ? LIMIT ? ALLOW FILTERING"
+//! );
+//! let rows = session.query_unpaged(statement, values).await?.into_rows_result()?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla_cql::serialize::value::SerializeValue;
+use scylla_cql::serialize::{RowWriter, SerializationError};
+use thiserror::Error;
+
+mod delete;
+mod insert;
+mod select;
+mod update;
+
+pub use delete::Delete;
+pub use insert::Insert;
+pub use select::{Select, SortOrder};
+pub use update::Update;
+
+/// An error returned when building a statement which would not be valid CQL.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum QueryBuilderError {
+    /// An `INSERT` statement has no values.
+    #[error("INSERT statement has to insert at least one value")]
+    InsertWithoutValues,
+
+    /// An `UPDATE` statement does not set any column.
+    #[error("UPDATE statement has to set at least one column")]
+    UpdateWithoutAssignments,
+
+    /// An `UPDATE` or `DELETE` statement has no `WHERE` clause.
+    #[error("Modification statement has to have a WHERE clause")]
+    MissingWhereClause,
+}
+
+type BoxedValue<'a> = Box<dyn SerializeValue + Send + Sync + 'a>;
+
+/// Values bound to a statement produced by one of the builders in this module.
+///
+/// Values are positional: they follow the order of bind markers in the statement text.
+/// They can be passed to any `Session` method which executes a statement, both
+/// together with the built [`Statement`](crate::statement::Statement) and with a statement prepared from it.
+#[derive(Default)]
+pub struct BoundValues<'a> {
+    values: Vec<BoxedValue<'a>>,
+}
+
+impl<'a> BoundValues<'a> {
+    /// Returns the number of bound values.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no bound values.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn push(&mut self, value: BoxedValue<'a>) {
+        self.values.push(value);
+    }
+}
+
+impl SerializeRow for BoundValues<'_> {
+    fn serialize(
+        &self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Result<(), SerializationError> {
+        SerializeRow::serialize(&self.values.as_slice(), ctx, writer)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+impl fmt::Debug for BoundValues<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundValues")
+            .field("len", &self.values.len())
+            .finish()
+    }
+}
+
+/// A single relation in a `WHERE` clause, comparing a column with a bound value.
+///
+/// Relations passed to a builder are joined with `AND`.
+pub struct Relation<'a> {
+    column: String,
+    operator: &'static str,
+    value: BoxedValue<'a>,
+}
+
+impl<'a> Relation<'a> {
+    fn new(
+        column: impl Into<String>,
+        operator: &'static str,
+        value: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        Self {
+            column: column.into(),
+            operator,
+            value: Box::new(value),
+        }
+    }
+
+    /// `column = ?`
+    pub fn eq(column: impl Into<String>, value: impl SerializeValue + Send + Sync + 'a) -> Self {
+        Self::new(column, "=", value)
+    }
+
+    /// `column < ?`
+    pub fn lt(column: impl Into<String>, value: impl SerializeValue + Send + Sync + 'a) -> Self {
+        Self::new(column, "<", value)
+    }
+
+    /// `column <= ?`
+    pub fn le(column: impl Into<String>, value: impl SerializeValue + Send + Sync + 'a) -> Self {
+        Self::new(column, "<=", value)
+    }
+
+    /// `column > ?`
+    pub fn gt(column: impl Into<String>, value: impl SerializeValue + Send + Sync + 'a) -> Self {
+        Self::new(column, ">", value)
+    }
+
+    /// `column >= ?`
+    pub fn ge(column: impl Into<String>, value: impl SerializeValue + Send + Sync + 'a) -> Self {
+        Self::new(column, ">=", value)
+    }
+
+    /// `column IN ?`
+    ///
+    /// The value should be a collection of values of the column's type, e.g. a `Vec`.
+    pub fn is_in(
+        column: impl Into<String>,
+        values: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        Self::new(column, "IN", values)
+    }
+
+    /// `column CONTAINS ?`
+    pub fn contains(
+        column: impl Into<String>,
+        value: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        Self::new(column, "CONTAINS", value)
+    }
+
+    /// `column CONTAINS KEY ?`
+    pub fn contains_key(
+        column: impl Into<String>,
+        key: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        Self::new(column, "CONTAINS KEY", key)
+    }
+}
+
+impl fmt::Debug for Relation<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} ?", self.column, self.operator)
+    }
+}
+
+fn write_where<'a>(cql: &mut String, relations: Vec<Relation<'a>>, values: &mut BoundValues<'a>) {
+    for (i, relation) in relations.into_iter().enumerate() {
+        cql.push_str(if i == 0 { " WHERE " } else { " AND " });
+        cql.push_str(&relation.column);
+        cql.push(' ');
+        cql.push_str(relation.operator);
+        cql.push_str(" ?");
+        values.push(relation.value);
+    }
+}
+
+fn write_columns(cql: &mut String, columns: &[String]) {
+    for (i, column) in columns.iter().enumerate() {
+        if i > 0 {
+            cql.push_str(", ");
+        }
+        cql.push_str(column);
+    }
+}
+
+/// The `USING` clause of modification statements.
+#[derive(Debug, Default)]
+struct Using {
+    ttl: Option<i32>,
+    timestamp: Option<i64>,
+}
+
+impl Using {
+    fn write(self, cql: &mut String, values: &mut BoundValues<'_>) {
+        let mut first = true;
+        let mut start_option = |cql: &mut String| {
+            cql.push_str(if first { " USING " } else { " AND " });
+            first = false;
+        };
+        if let Some(ttl) = self.ttl {
+            start_option(cql);
+            cql.push_str("TTL ?");
+            values.push(Box::new(ttl));
+        }
+        if let Some(timestamp) = self.timestamp {
+            start_option(cql);
+            cql.push_str("TIMESTAMP ?");
+            values.push(Box::new(timestamp));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use scylla_cql::frame::response::result::{ColumnSpec, ColumnType, NativeType, TableSpec};
+    use scylla_cql::serialize::row::{RowSerializationContext, SerializedValues};
+
+    use super::{Delete, Insert, QueryBuilderError, Relation, Select, SortOrder, Update};
+
+    #[test]
+    fn select() {
+        let (statement, values) = Select::new("ks.t").build();
+        assert_eq!(statement.contents, "SELECT * FROM ks.t");
+        assert!(values.is_empty());
+
+        let (statement, values) = Select::new("ks.t")
+            .distinct()
+            .column("a")
+            .columns(["b", "c"])
+            .where_(Relation::eq("a", 1))
+            .where_(Relation::is_in("b", vec![2, 3]))
+            .where_(Relation::le("c", 4))
+            .order_by("b", SortOrder::Desc)
+            .order_by("c", SortOrder::Asc)
+            .allow_filtering()
+            .limit(10)
+            .build();
+        assert_eq!(
+            statement.contents,
+            "SELECT DISTINCT a, b, c FROM ks.t WHERE a = ? AND b IN ? AND c <= ? \
+             ORDER BY b DESC, c ASC LIMIT ? ALLOW FILTERING"
+        );
+        assert_eq!(values.len(), 4);
+        assert!(statement.get_is_idempotent());
+    }
+
+    #[test]
+    fn insert() {
+        let (statement, values) = Insert::new("t")
+            .value("a", 1)
+            .value("b", "foo")
+            .if_not_exists()
+            .using_timestamp(123)
+            .using_ttl(60)
+            .build()
+            .unwrap();
+        assert_eq!(
+            statement.contents,
+            "INSERT INTO t (a, b) VALUES (?, ?) IF NOT EXISTS USING TTL ? AND TIMESTAMP ?"
+        );
+        assert_eq!(values.len(), 4);
+    }
+
+    #[test]
+    fn update() {
+        // Values follow the order of bind markers, not the order of calls.
+        let (statement, values) = Update::new("t")
+            .where_(Relation::eq("k", 1_i32))
+            .set("v", "foo")
+            .using_ttl(60)
+            .if_exists()
+            .build()
+            .unwrap();
+        assert_eq!(
+            statement.contents,
+            "UPDATE t USING TTL ? SET v = ? WHERE k = ? IF EXISTS"
+        );
+
+        let specs = [
+            ColumnSpec::borrowed(
+                "[ttl]",
+                ColumnType::Native(NativeType::Int),
+                TableSpec::borrowed("ks", "t"),
+            ),
+            ColumnSpec::borrowed(
+                "v",
+                ColumnType::Native(NativeType::Text),
+                TableSpec::borrowed("ks", "t"),
+            ),
+            ColumnSpec::borrowed(
+                "k",
+                ColumnType::Native(NativeType::Int),
+                TableSpec::borrowed("ks", "t"),
+            ),
+        ];
+        let ctx = RowSerializationContext::from_specs(&specs);
+        let serialized = SerializedValues::from_serializable(&ctx, &values).unwrap();
+        let mut expected = SerializedValues::new();
+        expected
+            .add_value(&60_i32, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        expected
+            .add_value(&"foo", &ColumnType::Native(NativeType::Text))
+            .unwrap();
+        expected
+            .add_value(&1_i32, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        assert_eq!(serialized, expected);
+    }
+
+    #[test]
+    fn delete() {
+        let (statement, values) = Delete::new("ks.t")
+            .where_(Relation::eq("k", 1))
+            .where_(Relation::gt("c", 2))
+            .build()
+            .unwrap();
+        assert_eq!(statement.contents, "DELETE FROM ks.t WHERE k = ? AND c > ?");
+        assert_eq!(values.len(), 2);
+
+        let (statement, values) = Delete::new("ks.t")
+            .columns(["a", "b"])
+            .using_timestamp(123)
+            .where_(Relation::contains_key("m", "key"))
+            .if_exists()
+            .build()
+            .unwrap();
+        assert_eq!(
+            statement.contents,
+            "DELETE a, b FROM ks.t USING TIMESTAMP ? WHERE m CONTAINS KEY ? IF EXISTS"
+        );
+        assert_eq!(values.len(), 2);
+    }
+
+    #[test]
+    fn invalid_statements() {
+        assert!(matches!(
+            Insert::new("t").build(),
+            Err(QueryBuilderError::InsertWithoutValues)
+        ));
+        assert!(matches!(
+            Update::new("t").where_(Relation::eq("k", 1)).build(),
+            Err(QueryBuilderError::UpdateWithoutAssignments)
+        ));
+        assert!(matches!(
+            Update::new("t").set("v", 1).build(),
+            Err(QueryBuilderError::MissingWhereClause)
+        ));
+        assert!(matches!(
+            Delete::new("t").build(),
+            Err(QueryBuilderError::MissingWhereClause)
+        ));
+    }
+}
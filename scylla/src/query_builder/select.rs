@@ -0,0 +1,128 @@
+//! Builder of `SELECT` statements.
+
+use crate::statement::Statement;
+
+use super::{write_columns, write_where, BoundValues, Relation};
+
+/// Sort order of a column in the `ORDER BY` clause.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Ascending order (`ASC`).
+    Asc,
+    /// Descending order (`DESC`).
+    Desc,
+}
+
+/// Builder of a `SELECT` statement.
+///
+/// If no columns are selected, the statement selects all of them (`SELECT *`).
+/// The built statement is marked as idempotent.
+#[derive(Debug)]
+pub struct Select<'a> {
+    table: String,
+    distinct: bool,
+    columns: Vec<String>,
+    relations: Vec<Relation<'a>>,
+    order_by: Vec<(String, SortOrder)>,
+    limit: Option<i32>,
+    allow_filtering: bool,
+}
+
+impl<'a> Select<'a> {
+    /// Starts building a `SELECT` statement reading from the given table.
+    /// The table name may be qualified with a keyspace name (`keyspace.table`).
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            distinct: false,
+            columns: Vec::new(),
+            relations: Vec::new(),
+            order_by: Vec::new(),
+            limit: None,
+            allow_filtering: false,
+        }
+    }
+
+    /// Turns the statement into `SELECT DISTINCT`.
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Adds a column (or any other selector, e.g. `COUNT(*)`) to the selection.
+    pub fn column(mut self, column: impl Into<String>) -> Self {
+        self.columns.push(column.into());
+        self
+    }
+
+    /// Adds multiple columns to the selection.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.columns.extend(columns.into_iter().map(Into::into));
+        self
+    }
+
+    /// Adds a relation to the `WHERE` clause.
+    pub fn where_(mut self, relation: Relation<'a>) -> Self {
+        self.relations.push(relation);
+        self
+    }
+
+    /// Adds a column to the `ORDER BY` clause.
+    pub fn order_by(mut self, column: impl Into<String>, order: SortOrder) -> Self {
+        self.order_by.push((column.into(), order));
+        self
+    }
+
+    /// Limits the number of returned rows.
+    pub fn limit(mut self, limit: i32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Adds `ALLOW FILTERING` to the statement.
+    pub fn allow_filtering(mut self) -> Self {
+        self.allow_filtering = true;
+        self
+    }
+
+    /// Builds the statement, together with the values bound to it.
+    pub fn build(self) -> (Statement, BoundValues<'a>) {
+        let mut cql = String::from("SELECT ");
+        let mut values = BoundValues::default();
+
+        if self.distinct {
+            cql.push_str("DISTINCT ");
+        }
+        if self.columns.is_empty() {
+            cql.push('*');
+        } else {
+            write_columns(&mut cql, &self.columns);
+        }
+        cql.push_str(" FROM ");
+        cql.push_str(&self.table);
+
+        write_where(&mut cql, self.relations, &mut values);
+
+        for (i, (column, order)) in self.order_by.iter().enumerate() {
+            cql.push_str(if i == 0 { " ORDER BY " } else { ", " });
+            cql.push_str(column);
+            cql.push_str(match order {
+                SortOrder::Asc => " ASC",
+                SortOrder::Desc => " DESC",
+            });
+        }
+
+        if let Some(limit) = self.limit {
+            cql.push_str(" LIMIT ?");
+            values.push(Box::new(limit));
+        }
+
+        if self.allow_filtering {
+            cql.push_str(" ALLOW FILTERING");
+        }
+
+        let mut statement = Statement::new(cql);
+        statement.set_is_idempotent(true);
+        (statement, values)
+    }
+}
@@ -0,0 +1,103 @@
+//! Builder of `UPDATE` statements.
+
+use scylla_cql::serialize::value::SerializeValue;
+
+use crate::statement::Statement;
+
+use super::{write_where, BoundValues, QueryBuilderError, Relation, Using};
+
+/// Builder of an `UPDATE` statement.
+#[derive(Debug)]
+pub struct Update<'a> {
+    table: String,
+    assignments: Vec<String>,
+    assigned_values: BoundValues<'a>,
+    relations: Vec<Relation<'a>>,
+    if_exists: bool,
+    using: Using,
+}
+
+impl<'a> Update<'a> {
+    /// Starts building an `UPDATE` statement modifying the given table.
+    /// The table name may be qualified with a keyspace name (`keyspace.table`).
+    pub fn new(table: impl Into<String>) -> Self {
+        Self {
+            table: table.into(),
+            assignments: Vec::new(),
+            assigned_values: BoundValues::default(),
+            relations: Vec::new(),
+            if_exists: false,
+            using: Using::default(),
+        }
+    }
+
+    /// Sets a column to the given value (`column = ?`).
+    pub fn set(
+        mut self,
+        column: impl Into<String>,
+        value: impl SerializeValue + Send + Sync + 'a,
+    ) -> Self {
+        self.assignments.push(column.into());
+        self.assigned_values.push(Box::new(value));
+        self
+    }
+
+    /// Adds a relation to the `WHERE` clause, selecting the updated rows.
+    pub fn where_(mut self, relation: Relation<'a>) -> Self {
+        self.relations.push(relation);
+        self
+    }
+
+    /// Makes the update conditional (lightweight transaction): it is only applied
+    /// if the row exists.
+    pub fn if_exists(mut self) -> Self {
+        self.if_exists = true;
+        self
+    }
+
+    /// Sets the time to live (in seconds) of the written values.
+    pub fn using_ttl(mut self, ttl: i32) -> Self {
+        self.using.ttl = Some(ttl);
+        self
+    }
+
+    /// Sets the write timestamp (in microseconds) of the written values.
+    pub fn using_timestamp(mut self, timestamp: i64) -> Self {
+        self.using.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Builds the statement, together with the values bound to it.
+    ///
+    /// Fails if no column is [set](Self::set) or no relation is given in the `WHERE` clause,
+    /// as such a statement is not valid CQL.
+    pub fn build(self) -> Result<(Statement, BoundValues<'a>), QueryBuilderError> {
+        if self.assignments.is_empty() {
+            return Err(QueryBuilderError::UpdateWithoutAssignments);
+        }
+        if self.relations.is_empty() {
+            return Err(QueryBuilderError::MissingWhereClause);
+        }
+
+        let mut cql = String::from("UPDATE ");
+        let mut values = BoundValues::default();
+
+        cql.push_str(&self.table);
+        self.using.write(&mut cql, &mut values);
+
+        for (i, column) in self.assignments.iter().enumerate() {
+            cql.push_str(if i == 0 { " SET " } else { ", " });
+            cql.push_str(column);
+            cql.push_str(" = ?");
+        }
+        values.values.extend(self.assigned_values.values);
+
+        write_where(&mut cql, self.relations, &mut values);
+
+        if self.if_exists {
+            cql.push_str(" IF EXISTS");
+        }
+
+        Ok((Statement::new(cql), values))
+    }
+}
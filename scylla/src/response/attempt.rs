@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use crate::{errors::RequestAttemptError, policies::retry::RetryDecision, response::Coordinator};
+
+/// A single attempt of executing a request, as recorded in the request's attempt history.
+///
+/// The history contains attempts in the order in which they were completed,
+/// including attempts of speculative executions.
+#[derive(Debug, Clone)]
+pub struct RequestAttempt {
+    coordinator: Coordinator,
+    latency: Duration,
+    error: Option<RequestAttemptError>,
+    retry_decision: Option<RetryDecision>,
+}
+
+impl RequestAttempt {
+    pub(crate) fn succeeded(coordinator: Coordinator, latency: Duration) -> Self {
+        Self {
+            coordinator,
+            latency,
+            error: None,
+            retry_decision: None,
+        }
+    }
+
+    pub(crate) fn failed(
+        coordinator: Coordinator,
+        latency: Duration,
+        error: RequestAttemptError,
+        retry_decision: RetryDecision,
+    ) -> Self {
+        Self {
+            coordinator,
+            latency,
+            error: Some(error),
+            retry_decision: Some(retry_decision),
+        }
+    }
+
+    /// The node+shard that the attempt was sent to.
+    #[inline]
+    pub fn coordinator(&self) -> &Coordinator {
+        &self.coordinator
+    }
+
+    /// Time between sending the attempt and receiving its response or error.
+    #[inline]
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    /// The error that the attempt failed with, or `None` if it succeeded.
+    #[inline]
+    pub fn error(&self) -> Option<&RequestAttemptError> {
+        self.error.as_ref()
+    }
+
+    /// The decision made by the retry policy after the attempt failed,
+    /// or `None` if it succeeded.
+    #[inline]
+    pub fn retry_decision(&self) -> Option<&RetryDecision> {
+        self.retry_decision.as_ref()
+    }
+}
@@ -27,6 +27,19 @@ impl Coordinator {
         }
     }
 
+    #[cfg(test)]
+    pub(crate) fn new_for_test(
+        node: Arc<Node>,
+        shard: Option<Shard>,
+        connection_address: SocketAddr,
+    ) -> Self {
+        Self {
+            connection_address,
+            node,
+            shard,
+        }
+    }
+
     /// Translated address, i.e., one that the connection is opened against.
     #[inline]
     pub fn connection_address(&self) -> SocketAddr {
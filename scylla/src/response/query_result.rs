@@ -1,8 +1,10 @@
 //! Types for representing results of CQL queries and iterating
 //! over them.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 
+use bytes::Bytes;
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -14,9 +16,30 @@ use scylla_cql::frame::response::result::{
     ColumnSpec, DeserializedMetadataAndRawRows, RawMetadataAndRawRows,
 };
 
-use crate::response::Coordinator;
+use crate::response::{Coordinator, RequestAttempt};
 
 /// A view over specification of columns returned by the database.
+///
+/// Each [ColumnSpec] describes the name and the CQL type of a column, as well as
+/// the table it comes from. This allows rendering arbitrary result sets, without
+/// knowing their column types in advance.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn std::error::Error>> {
+/// let result = session
+///     .query_unpaged("SELECT * FROM ks.tab", &[])
+///     .await?
+///     .into_rows_result()?;
+///
+/// for spec in result.column_specs().iter() {
+///     // e.g. "ks.tab.a: map<text, frozen<list<int>>>"
+///     println!("{}.{}: {}", spec.table_spec(), spec.name(), spec.typ());
+/// }
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, Copy)]
 pub struct ColumnSpecs<'slice, 'spec> {
     specs: &'slice [ColumnSpec<'spec>],
@@ -61,6 +84,13 @@ impl<'slice, 'spec> ColumnSpecs<'slice, 'spec> {
     pub fn iter(&self) -> impl Iterator<Item = &'slice ColumnSpec<'spec>> {
         self.specs.iter()
     }
+
+    /// Returns iterator over names of columns returned from the database,
+    /// ordered by column order in the response.
+    #[inline]
+    pub fn names(&self) -> impl Iterator<Item = &'slice str> {
+        self.specs.iter().map(|spec| spec.name())
+    }
 }
 
 /// Result of a single request to the database. It represents any kind of Result frame.
@@ -80,6 +110,8 @@ pub struct QueryResult {
     raw_metadata_and_rows: Option<RawMetadataAndRawRows>,
     tracing_id: Option<Uuid>,
     warnings: Vec<String>,
+    custom_payload: Option<HashMap<String, Bytes>>,
+    attempt_history: Vec<RequestAttempt>,
 }
 
 impl QueryResult {
@@ -94,6 +126,8 @@ impl QueryResult {
             raw_metadata_and_rows: raw_rows,
             tracing_id,
             warnings,
+            custom_payload: None,
+            attempt_history: Vec::new(),
         }
     }
 
@@ -115,6 +149,8 @@ impl QueryResult {
             raw_metadata_and_rows: raw_rows,
             tracing_id,
             warnings,
+            custom_payload: None,
+            attempt_history: Vec::new(),
         }
     }
 
@@ -126,9 +162,24 @@ impl QueryResult {
             raw_metadata_and_rows: None,
             tracing_id: None,
             warnings: Vec::new(),
+            custom_payload: None,
+            attempt_history: Vec::new(),
         }
     }
 
+    pub(crate) fn with_custom_payload(
+        mut self,
+        custom_payload: Option<HashMap<String, Bytes>>,
+    ) -> Self {
+        self.custom_payload = custom_payload;
+        self
+    }
+
+    pub(crate) fn with_attempt_history(mut self, attempt_history: Vec<RequestAttempt>) -> Self {
+        self.attempt_history = attempt_history;
+        self
+    }
+
     pub(crate) fn raw_metadata_and_rows(&self) -> Option<&RawMetadataAndRawRows> {
         self.raw_metadata_and_rows.as_ref()
     }
@@ -153,6 +204,24 @@ impl QueryResult {
         self.tracing_id
     }
 
+    /// Custom payload returned by the database along with the response, if any.
+    ///
+    /// See [`Statement::set_custom_payload`](crate::statement::unprepared::Statement::set_custom_payload).
+    #[inline]
+    pub fn custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+
+    /// Attempts made to execute the request, including the successful one,
+    /// in the order in which they were completed.
+    ///
+    /// Contains more than one attempt if the request was retried or executed
+    /// speculatively. See [`RequestAttempt`].
+    #[inline]
+    pub fn attempt_history(&self) -> &[RequestAttempt] {
+        &self.attempt_history
+    }
+
     /// Returns a bool indicating the current response is of Rows type.
     #[inline]
     pub fn is_rows(&self) -> bool {
@@ -218,6 +287,8 @@ impl QueryResult {
         };
         let tracing_id = self.tracing_id;
         let warnings = self.warnings;
+        let custom_payload = self.custom_payload;
+        let attempt_history = self.attempt_history;
         let request_coordinator = self.request_coordinator;
 
         let raw_rows_with_metadata = raw_metadata_and_rows.deserialize_metadata()?;
@@ -226,6 +297,8 @@ impl QueryResult {
             raw_rows_with_metadata,
             warnings,
             tracing_id,
+            custom_payload,
+            attempt_history,
         })
     }
 }
@@ -267,6 +340,8 @@ pub struct QueryRowsResult {
     raw_rows_with_metadata: DeserializedMetadataAndRawRows,
     tracing_id: Option<Uuid>,
     warnings: Vec<String>,
+    custom_payload: Option<HashMap<String, Bytes>>,
+    attempt_history: Vec<RequestAttempt>,
 }
 
 impl QueryRowsResult {
@@ -282,6 +357,22 @@ impl QueryRowsResult {
         self.tracing_id
     }
 
+    /// Custom payload returned by the database along with the response, if any.
+    #[inline]
+    pub fn custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.custom_payload.as_ref()
+    }
+
+    /// Attempts made to execute the request, including the successful one,
+    /// in the order in which they were completed.
+    ///
+    /// Contains more than one attempt if the request was retried or executed
+    /// speculatively. See [`RequestAttempt`].
+    #[inline]
+    pub fn attempt_history(&self) -> &[RequestAttempt] {
+        &self.attempt_history
+    }
+
     /// The node+shard that served the request.
     #[inline]
     pub fn request_coordinator(&self) -> &Coordinator {
@@ -399,6 +490,7 @@ impl QueryRowsResult {
             tracing_id,
             warnings,
             request_coordinator,
+            ..
         } = self;
 
         (
@@ -619,6 +711,17 @@ mod tests {
                         assert_eq!(got_view, &expected_col_spec);
                     }
                 }
+
+                // By names
+                {
+                    for (got_name, expected_col_spec) in column_specs
+                        .names()
+                        .zip(column_spec_infinite_iter().take(n))
+                    {
+                        assert_eq!(got_name, expected_col_spec.name());
+                    }
+                    assert_eq!(column_specs.names().count(), n);
+                }
             }
         }
 
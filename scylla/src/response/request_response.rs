@@ -18,7 +18,6 @@ pub(crate) struct QueryResponse {
     pub(crate) response: Response,
     pub(crate) tracing_id: Option<Uuid>,
     pub(crate) warnings: Vec<String>,
-    // This is not exposed to user (yet?)
     pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
 }
 
@@ -27,6 +26,7 @@ pub(crate) struct NonErrorQueryResponse {
     pub(crate) response: NonErrorResponse,
     pub(crate) tracing_id: Option<Uuid>,
     pub(crate) warnings: Vec<String>,
+    pub(crate) custom_payload: Option<HashMap<String, Bytes>>,
 }
 
 impl QueryResponse {
@@ -37,6 +37,7 @@ impl QueryResponse {
             response: self.response.into_non_error_response()?,
             tracing_id: self.tracing_id,
             warnings: self.warnings,
+            custom_payload: self.custom_payload,
         })
     }
 }
@@ -64,6 +65,7 @@ impl NonErrorQueryResponse {
             response,
             tracing_id,
             warnings,
+            custom_payload,
         } = self;
         let (raw_rows, paging_state_response) = match response {
             NonErrorResponse::Result(result::Result::Rows((rs, paging_state_response))) => {
@@ -77,11 +79,12 @@ impl NonErrorQueryResponse {
             }
         };
 
+        let result = match request_coordinator {
+            Some(coordinator) => QueryResult::new(coordinator, raw_rows, tracing_id, warnings),
+            None => QueryResult::new_with_unknown_coordinator(raw_rows, tracing_id, warnings),
+        };
         Ok((
-            match request_coordinator {
-                Some(coordinator) => QueryResult::new(coordinator, raw_rows, tracing_id, warnings),
-                None => QueryResult::new_with_unknown_coordinator(raw_rows, tracing_id, warnings),
-            },
+            result.with_custom_payload(custom_payload),
             paging_state_response,
         ))
     }
@@ -189,9 +192,7 @@ impl RawPreparedStatement<'_> {
             is_lwt,
             prepared_response.prepared_metadata,
             Arc::new(prepared_response.result_metadata),
-            statement.contents.clone(),
-            statement.get_validated_page_size(),
-            statement.config.clone(),
+            statement.clone(),
         );
 
         if let Some(tracing_id) = tracing_id {
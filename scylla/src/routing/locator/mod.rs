@@ -25,7 +25,7 @@ use self::tablets::TabletsInfo;
 
 use crate::cluster::metadata::Strategy;
 use crate::cluster::{Node, NodeRef};
-use crate::routing::{Shard, Token};
+use crate::routing::{Shard, Token, TokenRange};
 use itertools::Itertools;
 use precomputed_replicas::PrecomputedReplicas;
 use replicas::{ReplicasArray, EMPTY_REPLICAS};
@@ -192,6 +192,55 @@ impl ReplicaLocator {
         }
     }
 
+    /// Splits the token ring into ranges of tokens which have the same replicas in the given table,
+    /// in the order of their tokens. Each range is paired with its replica set, which is
+    /// computed the same way as by [ReplicaLocator::replicas_for_token].
+    ///
+    /// If the table uses tablets, the ranges are the tablets of the table known to the driver.
+    /// As the driver learns about tablets lazily, from responses to requests sent
+    /// to nodes which are not replicas, the ranges might not cover the whole ring.
+    /// Otherwise, the ranges are the ones owned by the members of the [token ring](Self::ring),
+    /// which is empty if the ring is unknown.
+    pub fn token_ranges<'a>(
+        &'a self,
+        strategy: &'a Strategy,
+        datacenter: Option<&'a str>,
+        table_spec: &TableSpec,
+    ) -> Vec<(TokenRange, ReplicaSet<'a>)> {
+        if let Some(tablets) = self.tablets.tablets_for_table(table_spec) {
+            return tablets
+                .tablets()
+                .map(|tablet| {
+                    let replicas = match datacenter {
+                        Some(datacenter) => tablet.dc_replicas(datacenter),
+                        None => tablet.replicas(),
+                    };
+                    let replica_set = ReplicaSet {
+                        inner: ReplicaSetInner::PlainSharded(replicas),
+                        token: tablet.range().1,
+                    };
+                    (tablet.token_range(), replica_set)
+                })
+                .collect();
+        }
+
+        // All tokens of a range owned by a ring member have the same replicas
+        // as the token of the member, which is the end of the range.
+        self.ring()
+            .primary_ranges()
+            .into_iter()
+            .map(|(range, _)| {
+                let replica_set = self.replicas_for_token(
+                    Token::new(range.end()),
+                    strategy,
+                    datacenter,
+                    table_spec,
+                );
+                (range, replica_set)
+            })
+            .collect()
+    }
+
     /// Gives access to the token ring, based on which all token ranges/replica sets are computed.
     pub fn ring(&self) -> &TokenRing<Arc<Node>> {
         self.replication_data.get_global_ring()
@@ -848,6 +897,10 @@ impl<'a> IntoIterator for ReplicasOrdered<'a> {
 
 #[cfg(test)]
 mod tests {
+    use super::tablets::Tablet;
+    use super::ReplicaSet;
+    use crate::cluster::metadata::Strategy;
+    use crate::routing::TokenRange;
     use crate::{routing::locator::test::*, routing::Token, test_utils::setup_tracing};
 
     #[tokio::test]
@@ -878,6 +931,8 @@ mod tests {
             None,
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_NTS_RF_3)
                 .unwrap()
                 .as_ref()
@@ -891,6 +946,8 @@ mod tests {
             None,
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_NTS_RF_2)
                 .unwrap()
                 .as_ref()
@@ -904,6 +961,8 @@ mod tests {
             None,
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_SS_RF_2)
                 .unwrap()
                 .as_ref()
@@ -918,6 +977,8 @@ mod tests {
             Some("eu"),
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_NTS_RF_3)
                 .unwrap()
                 .as_ref()
@@ -931,6 +992,8 @@ mod tests {
             Some("us"),
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_NTS_RF_3)
                 .unwrap()
                 .as_ref()
@@ -944,6 +1007,8 @@ mod tests {
             Some("eu"),
             &metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .get(KEYSPACE_SS_RF_2)
                 .unwrap()
                 .as_ref()
@@ -953,4 +1018,54 @@ mod tests {
             vec![A],
         );
     }
+
+    #[tokio::test]
+    async fn test_token_ranges() {
+        setup_tracing();
+        let metadata = mock_metadata_for_token_aware_tests();
+        let mut locator = create_locator(&metadata);
+        let strategy = Strategy::SimpleStrategy {
+            replication_factor: 2,
+        };
+        let ports = |replica_set: ReplicaSet<'_>| {
+            replica_set
+                .into_iter()
+                .map(|(node, _shard)| node.address.port())
+                .collect::<Vec<_>>()
+        };
+
+        // Ring tokens: 50 (A), 100 (B), 150 (E), ..., 800 (G), 900 (B).
+        let ranges = locator.token_ranges(&strategy, None, TABLE_SS_RF_2);
+        assert_eq!(ranges.len(), 18);
+        let ranges = ranges
+            .into_iter()
+            .map(|(range, replica_set)| (range, ports(replica_set)))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges[0], (TokenRange::new(i64::MIN, 50), vec![A, B]));
+        assert_eq!(ranges[1], (TokenRange::new(50, 100), vec![B, E]));
+        assert_eq!(ranges[16], (TokenRange::new(800, 900), vec![B, A]));
+        assert_eq!(ranges[17], (TokenRange::new(900, i64::MAX), vec![A, B]));
+        for (range, replicas) in &ranges {
+            let replica_set = locator.replicas_for_token(
+                Token::new(range.start() + 1),
+                &strategy,
+                None,
+                TABLE_SS_RF_2,
+            );
+            assert_eq!(&ports(replica_set), replicas);
+        }
+
+        // Tablet tables are split into their known tablets.
+        let node = locator.ring().iter().next().unwrap().1.clone();
+        locator.tablets.add_tablet(
+            TABLE_SS_RF_2.clone(),
+            Tablet::new_for_test(200, vec![node], None),
+        );
+        let ranges = locator
+            .token_ranges(&strategy, None, TABLE_SS_RF_2)
+            .into_iter()
+            .map(|(range, replica_set)| (range, ports(replica_set)))
+            .collect::<Vec<_>>();
+        assert_eq!(ranges, vec![(TokenRange::new(199, 200), vec![A])]);
+    }
 }
@@ -229,7 +229,7 @@ mod tests {
     async fn test_simple_stategy() {
         setup_tracing();
         let mut metadata = mock_metadata_for_token_aware_tests();
-        metadata.keyspaces = [(
+        metadata.keyspaces = Ok([(
             "SimpleStrategy{rf=2}".into(),
             Ok(Keyspace {
                 strategy: Strategy::SimpleStrategy {
@@ -238,11 +238,13 @@ mod tests {
                 tables: HashMap::new(),
                 views: HashMap::new(),
                 user_defined_types: HashMap::new(),
+                functions: HashMap::new(),
+                aggregates: HashMap::new(),
             }),
         )]
         .iter()
         .cloned()
-        .collect();
+        .collect());
 
         let ring = create_ring(&metadata);
         let replication_info = ReplicationInfo::new(ring);
@@ -250,6 +252,8 @@ mod tests {
             &replication_info,
             metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .values()
                 .map(|keyspace| &keyspace.as_ref().unwrap().strategy),
         );
@@ -292,6 +296,8 @@ mod tests {
             &replication_info,
             metadata
                 .keyspaces
+                .as_ref()
+                .unwrap()
                 .values()
                 .map(|keyspace| &keyspace.as_ref().unwrap().strategy),
         );
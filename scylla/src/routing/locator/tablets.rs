@@ -7,7 +7,7 @@ use tracing::warn;
 use uuid::Uuid;
 
 use crate::cluster::Node;
-use crate::routing::{Shard, Token};
+use crate::routing::{Shard, Token, TokenRange};
 use crate::utils::safe_format::IteratorSafeFormatExt;
 
 use std::collections::{HashMap, HashSet};
@@ -57,6 +57,14 @@ static RAW_TABLETS_CQL_TYPE: LazyLock<ColumnType<'static>> = LazyLock::new(|| {
 
 const CUSTOM_PAYLOAD_TABLETS_V1_KEY: &str = "tablets-routing-v1";
 
+/// Returns true if the custom payload of a response carries tablet info.
+/// Scylla only sends it if the request was routed to a node which is not
+/// a replica of the tablet owning the requested token.
+#[cfg(feature = "metrics")]
+pub(crate) fn has_tablet_info(payload: Option<&HashMap<String, Bytes>>) -> bool {
+    payload.is_some_and(|payload| payload.contains_key(CUSTOM_PAYLOAD_TABLETS_V1_KEY))
+}
+
 impl RawTablet {
     pub(crate) fn from_custom_payload(
         payload: &HashMap<String, Bytes>,
@@ -260,6 +268,27 @@ impl Tablet {
         (self.first_token, self.last_token)
     }
 
+    /// Returns the tokens of the tablet as a [TokenRange].
+    pub(crate) fn token_range(&self) -> TokenRange {
+        // The first token of a tablet is inclusive, while the start of a range is not.
+        TokenRange::new(
+            self.first_token.value().saturating_sub(1),
+            self.last_token.value(),
+        )
+    }
+
+    pub(crate) fn replicas(&self) -> &[(Arc<Node>, Shard)] {
+        &self.replicas.all
+    }
+
+    pub(crate) fn dc_replicas(&self, dc: &str) -> &[(Arc<Node>, Shard)] {
+        self.replicas
+            .per_dc
+            .get(dc)
+            .map(|x| x.as_slice())
+            .unwrap_or(&[])
+    }
+
     // Returns `Ok(())` if after the operation Tablet replicas are fully resolved.
     // Return `Err(replicas)` if some replicas failed to resolve. `replicas` is a
     // list of Uuids that failed to resolve.
@@ -306,7 +335,11 @@ impl Tablet {
     }
 
     #[cfg(test)]
-    fn new_for_test(token: i64, replicas: Vec<Arc<Node>>, failed: Option<Vec<Uuid>>) -> Self {
+    pub(crate) fn new_for_test(
+        token: i64,
+        replicas: Vec<Arc<Node>>,
+        failed: Option<Vec<Uuid>>,
+    ) -> Self {
         Self {
             first_token: Token::new(token),
             last_token: Token::new(token),
@@ -357,8 +390,7 @@ impl TableTablets {
     }
 
     pub(crate) fn replicas_for_token(&self, token: Token) -> Option<&[(Arc<Node>, Shard)]> {
-        self.tablet_for_token(token)
-            .map(|tablet| tablet.replicas.all.as_ref())
+        self.tablet_for_token(token).map(Tablet::replicas)
     }
 
     pub(crate) fn dc_replicas_for_token(
@@ -366,14 +398,13 @@ impl TableTablets {
         token: Token,
         dc: &str,
     ) -> Option<&[(Arc<Node>, Shard)]> {
-        self.tablet_for_token(token).map(|tablet| {
-            tablet
-                .replicas
-                .per_dc
-                .get(dc)
-                .map(|x| x.as_slice())
-                .unwrap_or(&[])
-        })
+        self.tablet_for_token(token)
+            .map(|tablet| tablet.dc_replicas(dc))
+    }
+
+    /// Iterates over the known tablets of the table, in the order of their tokens.
+    pub(crate) fn tablets(&self) -> impl Iterator<Item = &Tablet> {
+        self.tablet_list.iter()
     }
 
     /// This method:
@@ -503,6 +534,11 @@ impl TabletsInfo {
         table_tablets
     }
 
+    /// Iterates over the tables of which any tablet is known.
+    pub(crate) fn tables(&self) -> impl Iterator<Item = &TableSpec<'static>> {
+        self.tablets.keys()
+    }
+
     pub(crate) fn add_tablet(&mut self, table_spec: TableSpec<'static>, tablet: Tablet) {
         if tablet.failed.is_some() {
             self.has_unknown_replicas = true;
@@ -1,4 +1,4 @@
-use crate::routing::Token;
+use crate::routing::{Token, TokenRange};
 
 /// A token ring is a continuous hash ring. It defines association by hashing a key
 /// onto the ring and then walking the ring in one direction.
@@ -28,6 +28,31 @@ impl<ElemT> TokenRing<ElemT> {
         self.ring.iter()
     }
 
+    /// Splits the whole ring into ranges owned by its members,
+    /// starting at the lowest token. Each range is paired with its owner.
+    ///
+    /// A member owns the tokens greater than the token of the previous member,
+    /// up to its own token inclusively. The first member also owns the range
+    /// wrapping around the end of the ring, which is split into two ranges:
+    /// one at the beginning and one at the end of the returned list.
+    pub fn primary_ranges(&self) -> Vec<(TokenRange, &ElemT)> {
+        let mut members = self.iter();
+        let Some((first_token, first)) = members.next() else {
+            return Vec::new();
+        };
+
+        let mut ranges = vec![(TokenRange::new(i64::MIN, first_token.value()), first)];
+        let mut previous = first_token.value();
+        for (token, member) in members {
+            ranges.push((TokenRange::new(previous, token.value()), member));
+            previous = token.value();
+        }
+        ranges.push((TokenRange::new(previous, i64::MAX), first));
+
+        ranges.retain(|(range, _)| !range.is_empty());
+        ranges
+    }
+
     /// Provides an iterator over the ring members starting at the given token.
     /// The iterator traverses the whole ring in the direction of increasing tokens.
     /// After reaching the maximum token it wraps around and continues from the lowest one.
@@ -72,6 +97,7 @@ impl<ElemT> TokenRing<ElemT> {
 #[cfg(test)]
 mod tests {
     use super::TokenRing;
+    use crate::routing::TokenRange;
     use crate::{routing::Token, test_utils::setup_tracing};
 
     #[test]
@@ -194,4 +220,31 @@ mod tests {
             vec![-3, -2, -1, 0, 1, 2, 3]
         );
     }
+
+    #[test]
+    fn primary_ranges_cover_the_whole_ring() {
+        let ring = TokenRing::new(
+            [(-100, 'a'), (0, 'b'), (100, 'c')]
+                .into_iter()
+                .map(|(token, member)| (Token::new(token), member)),
+        );
+
+        let ranges = ring
+            .primary_ranges()
+            .into_iter()
+            .map(|(range, member)| (range, *member))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ranges,
+            vec![
+                (TokenRange::new(i64::MIN, -100), 'a'),
+                (TokenRange::new(-100, 0), 'b'),
+                (TokenRange::new(0, 100), 'c'),
+                (TokenRange::new(100, i64::MAX), 'a'),
+            ]
+        );
+
+        let empty_ring = TokenRing::<char>::new(std::iter::empty());
+        assert!(empty_ring.primary_ranges().is_empty());
+    }
 }
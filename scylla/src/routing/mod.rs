@@ -4,17 +4,28 @@
 //!
 //! This includes:
 //! - token representation,
+//! - token range representation,
 //! - shard representation and shard computing logic,
 //! - partitioners, which compute token based on a partition key,
 //! - replica locator, which finds replicas (node + shard) for a given token.
 //!
 
+use std::sync::Arc;
+
+use scylla_cql::serialize::row::SerializedValues;
+
+use crate::cluster::Node;
+use crate::routing::partitioner::PartitionerName;
+use crate::statement::prepared::TokenCalculationError;
+
 pub mod locator;
 pub mod partitioner;
 mod sharding;
+mod token_range;
 
 pub use sharding::{InvalidShardAwarePortRange, Shard, ShardAwarePortRange, ShardCount, Sharder};
 pub(crate) use sharding::{ShardInfo, ShardingError};
+pub use token_range::TokenRange;
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
 
@@ -54,4 +65,63 @@ impl Token {
     pub fn value(&self) -> i64 {
         self.value
     }
+
+    /// Calculates the token of a partition key using the given partitioner.
+    ///
+    /// `partition_key` must consist of values of all partition key columns,
+    /// in the order in which they are defined in the table schema.
+    /// This yields the same token as [PreparedStatement::calculate_token()](crate::statement::prepared::PreparedStatement::calculate_token),
+    /// but requires neither a session nor a prepared statement.
+    ///
+    /// # Example
+    /// ```
+    /// # use scylla::cluster::metadata::{ColumnType, NativeType};
+    /// # use scylla::routing::partitioner::PartitionerName;
+    /// # use scylla::routing::Token;
+    /// # use scylla::serialize::row::SerializedValues;
+    /// # fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut partition_key = SerializedValues::new();
+    /// partition_key.add_value(&"test", &ColumnType::Native(NativeType::Text))?;
+    ///
+    /// let token = Token::from_partition_key(&PartitionerName::Murmur3, &partition_key)?;
+    /// assert_eq!(token.value(), -6017608668500074083);
+    /// # Ok(())
+    /// # }
+    /// # example().unwrap();
+    /// ```
+    pub fn from_partition_key(
+        partitioner: &PartitionerName,
+        partition_key: &SerializedValues,
+    ) -> Result<Token, TokenCalculationError> {
+        partitioner::calculate_token_for_partition_key(partition_key, partitioner)
+    }
+}
+
+/// Describes where a request would be routed to.
+///
+/// Returned by [`Session::explain_routing`](crate::client::session::Session::explain_routing),
+/// meant for verifying token- and shard-awareness when troubleshooting.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RoutingExplanation {
+    /// Token computed from the partition key, or `None` if the token
+    /// could not be computed (e.g. the partition key is not fully bound).
+    pub token: Option<Token>,
+
+    /// Targets in the order in which the load balancing policy would try them.
+    pub targets: Vec<RoutingTarget>,
+}
+
+/// A single target of a request, see [`RoutingExplanation`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct RoutingTarget {
+    /// The target node.
+    pub node: Arc<Node>,
+
+    /// The target shard, or `None` if the node is not sharded (e.g. Cassandra).
+    pub shard: Option<Shard>,
+
+    /// Whether the node is a replica of the token.
+    pub is_replica: bool,
 }
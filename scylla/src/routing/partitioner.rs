@@ -1,23 +1,31 @@
 //! Partitioners are algorithms that can compute token for a given partition key,
 //! ultimately allowing optimised routing of requests (such that a request is routed
 //! to replicas, which are nodes and shards that really own the data the request concerns).
-//! Currently, two partitioners are supported:
+//! Currently, three partitioners are supported:
 //! - Murmur3Partitioner
 //!     - the default partitioner,
 //!     - modified for compatibility with Cassandra's buggy implementation.
 //! - CDCPartitioner
 //!     - the partitioner employed when using CDC (_Change Data Capture_).
+//! - ByteOrderedPartitioner
+//!     - Cassandra's order-preserving partitioner, supported approximately
+//!       (see [ByteOrderedPartitioner]).
+//!
+//! Other partitioners can be provided by implementing [CustomPartitioner]
+//! and registering the implementation in the session configuration.
 
 use bytes::Buf;
 use scylla_cql::frame::types::RawValue;
 use scylla_cql::serialize::row::SerializedValues;
+use std::fmt::Debug;
 use std::num::Wrapping;
+use std::sync::Arc;
 
 use crate::routing::Token;
 use crate::statement::prepared::TokenCalculationError;
 
 /// Name of the partitioner employed by a table to partition its data.
-#[derive(Clone, PartialEq, Eq, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 #[non_exhaustive]
 pub enum PartitionerName {
     /// See [Murmur3Partitioner].
@@ -31,18 +39,50 @@ pub enum PartitionerName {
     // TODO(2.0): Rename variant to Cdc.
     #[expect(clippy::upper_case_acronyms)]
     CDC,
+
+    /// See [ByteOrderedPartitioner].
+    ByteOrdered,
+
+    /// A partitioner registered by the user, see [CustomPartitioner].
+    Custom(Arc<dyn CustomPartitioner>),
+}
+
+impl PartialEq for PartitionerName {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (PartitionerName::Murmur3, PartitionerName::Murmur3)
+            | (PartitionerName::CDC, PartitionerName::CDC)
+            | (PartitionerName::ByteOrdered, PartitionerName::ByteOrdered) => true,
+            (PartitionerName::Custom(a), PartitionerName::Custom(b)) => a.name() == b.name(),
+            _ => false,
+        }
+    }
 }
 
+impl Eq for PartitionerName {}
+
 impl PartitionerName {
     pub(crate) fn from_str(name: &str) -> Option<Self> {
         if name.ends_with("Murmur3Partitioner") {
             Some(PartitionerName::Murmur3)
         } else if name.ends_with("CDCPartitioner") {
             Some(PartitionerName::CDC)
+        } else if name.ends_with("ByteOrderedPartitioner") {
+            Some(PartitionerName::ByteOrdered)
         } else {
             None
         }
     }
+
+    /// Parses the string representation of a token, as found in the `tokens`
+    /// column of `system.local` and `system.peers`.
+    pub(crate) fn parse_token(&self, token: &str) -> Option<Token> {
+        match self {
+            PartitionerName::Murmur3 | PartitionerName::CDC => token.parse().ok(),
+            PartitionerName::ByteOrdered => ByteOrderedPartitioner::parse_token(token),
+            PartitionerName::Custom(partitioner) => partitioner.parse_token(token),
+        }
+    }
 }
 
 impl sealed::Sealed for PartitionerName {}
@@ -55,6 +95,15 @@ impl Partitioner for PartitionerName {
                 PartitionerHasherAny::Murmur3(Murmur3Partitioner.build_hasher())
             }
             PartitionerName::CDC => PartitionerHasherAny::CDC(CDCPartitioner.build_hasher()),
+            PartitionerName::ByteOrdered => {
+                PartitionerHasherAny::ByteOrdered(ByteOrderedPartitioner.build_hasher())
+            }
+            PartitionerName::Custom(partitioner) => {
+                PartitionerHasherAny::Custom(CustomPartitionerHasher {
+                    partitioner: Arc::clone(partitioner),
+                    partition_key: Vec::new(),
+                })
+            }
         }
     }
 }
@@ -73,6 +122,12 @@ pub enum PartitionerHasherAny {
     // TODO(2.0): Rename CDCPartitionerHasher to CdcPartitionerHasher
     #[expect(clippy::upper_case_acronyms)]
     CDC(CDCPartitionerHasher),
+
+    /// Hasher for [ByteOrderedPartitioner].
+    ByteOrdered(ByteOrderedPartitionerHasher),
+
+    /// Hasher for a [CustomPartitioner].
+    Custom(CustomPartitionerHasher),
 }
 
 impl sealed::Sealed for PartitionerHasherAny {}
@@ -81,6 +136,8 @@ impl PartitionerHasher for PartitionerHasherAny {
         match self {
             PartitionerHasherAny::Murmur3(h) => h.write(pk_part),
             PartitionerHasherAny::CDC(h) => h.write(pk_part),
+            PartitionerHasherAny::ByteOrdered(h) => h.write(pk_part),
+            PartitionerHasherAny::Custom(h) => h.write(pk_part),
         }
     }
 
@@ -88,8 +145,59 @@ impl PartitionerHasher for PartitionerHasherAny {
         match self {
             PartitionerHasherAny::Murmur3(h) => h.finish(),
             PartitionerHasherAny::CDC(h) => h.finish(),
+            PartitionerHasherAny::ByteOrdered(h) => h.finish(),
+            PartitionerHasherAny::Custom(h) => h.finish(),
+        }
+    }
+}
+
+/// Resolves partitioner names reported by the cluster to [PartitionerName]s,
+/// taking partitioners registered by the user into account.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct PartitionerResolver {
+    /// The partitioner configured cluster-wide, as reported by `system.local`.
+    /// Used for tables which don't specify their own partitioner.
+    cluster_partitioner: PartitionerName,
+    custom_partitioners: Vec<Arc<dyn CustomPartitioner>>,
+}
+
+impl PartitionerResolver {
+    pub(crate) fn new(custom_partitioners: Vec<Arc<dyn CustomPartitioner>>) -> Self {
+        Self {
+            cluster_partitioner: PartitionerName::default(),
+            custom_partitioners,
+        }
+    }
+
+    /// Returns a resolver using the partitioner of the given name as the cluster-wide one.
+    /// Unknown names leave the default Murmur3 partitioner in place.
+    pub(crate) fn with_cluster_partitioner(&self, name: Option<&str>) -> Self {
+        Self {
+            cluster_partitioner: self.table_partitioner(name),
+            custom_partitioners: self.custom_partitioners.clone(),
         }
     }
+
+    pub(crate) fn cluster_partitioner(&self) -> &PartitionerName {
+        &self.cluster_partitioner
+    }
+
+    /// Resolves a partitioner name. Custom partitioners take precedence over
+    /// the built-in ones, so that the latter can be overridden.
+    pub(crate) fn resolve(&self, name: &str) -> Option<PartitionerName> {
+        self.custom_partitioners
+            .iter()
+            .find(|partitioner| name.ends_with(partitioner.name()))
+            .map(|partitioner| PartitionerName::Custom(Arc::clone(partitioner)))
+            .or_else(|| PartitionerName::from_str(name))
+    }
+
+    /// Resolves the partitioner of a table, falling back to the cluster-wide
+    /// partitioner if the table doesn't specify a known one.
+    pub(crate) fn table_partitioner(&self, name: Option<&str>) -> PartitionerName {
+        name.and_then(|name| self.resolve(name))
+            .unwrap_or_else(|| self.cluster_partitioner.clone())
+    }
 }
 
 mod sealed {
@@ -380,6 +488,122 @@ impl PartitionerHasher for CDCPartitionerHasher {
     }
 }
 
+/// Cassandra's order-preserving partitioner, which uses the raw bytes of
+/// the partition key as its token.
+///
+/// Tokens of this partitioner are byte strings of arbitrary length, which
+/// don't fit into the driver's 64-bit [Token]. Therefore, both partition keys
+/// and ring tokens are projected onto 64-bit tokens by their first 8 bytes,
+/// in a way that preserves their order. Requests are routed precisely unless
+/// their partition key shares its first 8 bytes with a token of the ring;
+/// such requests may be routed to the neighbouring token's replicas.
+pub struct ByteOrderedPartitioner;
+
+/// Hasher for [ByteOrderedPartitioner].
+pub struct ByteOrderedPartitionerHasher {
+    len: usize,
+    buf: [u8; ByteOrderedPartitionerHasher::BUF_CAPACITY],
+}
+
+impl ByteOrderedPartitioner {
+    /// Parses a ring token, which is a hex representation of the token's bytes.
+    fn parse_token(token: &str) -> Option<Token> {
+        if token.len() % 2 != 0 {
+            return None;
+        }
+        let mut hasher = ByteOrderedPartitioner.build_hasher();
+        for i in (0..token.len()).step_by(2) {
+            let byte = u8::from_str_radix(token.get(i..i + 2)?, 16).ok()?;
+            hasher.write(&[byte]);
+        }
+        Some(hasher.finish())
+    }
+}
+
+impl sealed::Sealed for ByteOrderedPartitioner {}
+impl Partitioner for ByteOrderedPartitioner {
+    type Hasher = ByteOrderedPartitionerHasher;
+
+    fn build_hasher(&self) -> Self::Hasher {
+        Self::Hasher {
+            len: 0,
+            buf: Default::default(),
+        }
+    }
+}
+
+impl ByteOrderedPartitionerHasher {
+    const BUF_CAPACITY: usize = 8;
+}
+
+impl sealed::Sealed for ByteOrderedPartitionerHasher {}
+impl PartitionerHasher for ByteOrderedPartitionerHasher {
+    fn write(&mut self, pk_part: &[u8]) {
+        // Only the first 8 bytes take part in the projection, the rest is ignored.
+        let copied_len = Ord::min(pk_part.len(), Self::BUF_CAPACITY - self.len);
+        self.buf[self.len..self.len + copied_len].copy_from_slice(&pk_part[..copied_len]);
+        self.len += copied_len;
+    }
+
+    fn finish(&self) -> Token {
+        // Missing bytes are zeroes, so that shorter keys come first. Flipping the sign bit
+        // maps the unsigned big-endian order of bytes onto the signed order of tokens.
+        // i64::MIN is not a valid token and would be normalized to i64::MAX,
+        // so the smallest keys are mapped onto the smallest valid token instead.
+        let value = i64::from_be_bytes(self.buf) ^ i64::MIN;
+        Token::new(Ord::max(value, i64::MIN + 1))
+    }
+}
+
+/// A partitioner implemented outside of the driver.
+///
+/// Registering an implementation with
+/// [SessionBuilder::custom_partitioner()](crate::client::session_builder::SessionBuilder::custom_partitioner)
+/// lets the driver compute tokens for tables (or whole clusters) which use
+/// the partitioner, and thus route requests to their replicas.
+pub trait CustomPartitioner: Send + Sync + Debug {
+    /// Name of the partitioner, as reported by the cluster, e.g. `com.example.MyPartitioner`.
+    ///
+    /// The partitioner is used for every reported name that ends with this one,
+    /// so the package prefix may be omitted.
+    fn name(&self) -> &str;
+
+    /// Computes the token of a serialized partition key.
+    ///
+    /// Partition keys consisting of multiple columns are serialized the same way
+    /// Cassandra does it: each value is prefixed with its length as a big-endian
+    /// `u16` and followed by a zero byte.
+    fn token(&self, partition_key: &[u8]) -> Token;
+
+    /// Parses the string representation of a token, as found in the `tokens`
+    /// column of `system.local` and `system.peers`.
+    ///
+    /// By default, tokens are parsed as 64-bit signed integers.
+    fn parse_token(&self, token: &str) -> Option<Token> {
+        token.parse().ok()
+    }
+}
+
+/// Hasher for a [CustomPartitioner].
+///
+/// Collects the serialized partition key and hands it over
+/// to the partitioner when the token is computed.
+pub struct CustomPartitionerHasher {
+    partitioner: Arc<dyn CustomPartitioner>,
+    partition_key: Vec<u8>,
+}
+
+impl sealed::Sealed for CustomPartitionerHasher {}
+impl PartitionerHasher for CustomPartitionerHasher {
+    fn write(&mut self, pk_part: &[u8]) {
+        self.partition_key.extend_from_slice(pk_part);
+    }
+
+    fn finish(&self) -> Token {
+        self.partitioner.token(&self.partition_key)
+    }
+}
+
 /// Calculates the token for given partitioner and serialized partition key.
 ///
 /// The ordinary way to calculate token is based on a PreparedStatement
@@ -429,7 +653,14 @@ mod tests {
 
     use crate::test_utils::setup_tracing;
 
-    use super::{CDCPartitioner, Murmur3Partitioner, Partitioner, PartitionerHasher};
+    use std::sync::Arc;
+
+    use crate::routing::Token;
+
+    use super::{
+        ByteOrderedPartitioner, CDCPartitioner, CustomPartitioner, Murmur3Partitioner, Partitioner,
+        PartitionerHasher, PartitionerName, PartitionerResolver,
+    };
 
     fn assert_correct_murmur3_hash(pk: &'static str, expected_hash: i64) {
         let hash = Murmur3Partitioner.hash_one(pk.as_bytes()).value();
@@ -510,6 +741,7 @@ mod tests {
         for input in inputs {
             check_for_partitioner(Murmur3Partitioner, &mut randgen, input);
             check_for_partitioner(CDCPartitioner, &mut randgen, input);
+            check_for_partitioner(ByteOrderedPartitioner, &mut randgen, input);
         }
     }
 
@@ -525,4 +757,88 @@ mod tests {
             assert_correct_cdc_hash(s.0, s.1);
         }
     }
+
+    #[test]
+    fn byte_ordered_partitioner_preserves_order() {
+        setup_tracing();
+        let keys: &[&[u8]] = &[
+            b"",
+            b"\x00",
+            b"\x00\x01",
+            b"a",
+            b"ab",
+            b"abcdefgh",
+            b"abcdefgi",
+            b"b",
+            b"\xff\xff\xff\xff\xff\xff\xff\xff",
+        ];
+        let tokens: Vec<Token> = keys
+            .iter()
+            .map(|key| ByteOrderedPartitioner.hash_one(key))
+            .collect();
+        for window in tokens.windows(2) {
+            assert!(window[0] <= window[1], "{:?} > {:?}", window[0], window[1]);
+        }
+        assert!(tokens.iter().all(|token| token.value() != i64::MIN));
+
+        // Ring tokens are hex strings of the key bytes.
+        assert_eq!(
+            PartitionerName::ByteOrdered.parse_token("6162"),
+            Some(ByteOrderedPartitioner.hash_one(b"ab"))
+        );
+        assert_eq!(PartitionerName::ByteOrdered.parse_token("616"), None);
+        assert_eq!(PartitionerName::ByteOrdered.parse_token("zz"), None);
+        assert_eq!(
+            PartitionerName::Murmur3.parse_token("-42"),
+            Some(Token::new(-42))
+        );
+    }
+
+    #[derive(Debug)]
+    struct LengthPartitioner;
+
+    impl CustomPartitioner for LengthPartitioner {
+        fn name(&self) -> &str {
+            "LengthPartitioner"
+        }
+
+        fn token(&self, partition_key: &[u8]) -> Token {
+            Token::new(partition_key.len() as i64)
+        }
+    }
+
+    #[test]
+    fn partitioner_resolver_resolves_custom_and_cluster_partitioners() {
+        setup_tracing();
+        let resolver = PartitionerResolver::new(vec![Arc::new(LengthPartitioner)]);
+
+        let custom = resolver.table_partitioner(Some("com.example.LengthPartitioner"));
+        assert!(matches!(custom, PartitionerName::Custom(_)));
+        assert_eq!(custom.hash_one(b"four"), Token::new(4));
+        assert_eq!(
+            resolver.table_partitioner(Some("com.scylladb.dht.CDCPartitioner")),
+            PartitionerName::CDC
+        );
+        assert_eq!(resolver.table_partitioner(None), PartitionerName::Murmur3);
+
+        // Tables without a known partitioner use the cluster-wide one.
+        let resolver = resolver
+            .with_cluster_partitioner(Some("org.apache.cassandra.dht.ByteOrderedPartitioner"));
+        assert_eq!(
+            resolver.cluster_partitioner(),
+            &PartitionerName::ByteOrdered
+        );
+        assert_eq!(
+            resolver.table_partitioner(None),
+            PartitionerName::ByteOrdered
+        );
+        assert_eq!(
+            resolver.table_partitioner(Some("org.example.UnknownPartitioner")),
+            PartitionerName::ByteOrdered
+        );
+        assert_eq!(
+            resolver.table_partitioner(Some("org.apache.cassandra.dht.Murmur3Partitioner")),
+            PartitionerName::Murmur3
+        );
+    }
 }
@@ -0,0 +1,141 @@
+use std::fmt;
+
+use super::{Sharder, Token};
+
+/// A range of tokens, from `start` (exclusive) to `end` (inclusive).
+///
+/// This is the same convention as the one used by the token ring: a node owns
+/// the tokens greater than the token of the previous node on the ring,
+/// up to its own token inclusively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenRange {
+    start: i64,
+    end: i64,
+}
+
+impl TokenRange {
+    /// The range of all tokens.
+    ///
+    /// As `i64::MIN` is not a valid token, it is a safe exclusive lower bound.
+    pub const FULL: TokenRange = TokenRange {
+        start: i64::MIN,
+        end: i64::MAX,
+    };
+
+    /// Creates a range of tokens greater than `start` and lower than or equal to `end`.
+    pub fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// The exclusive lower bound of the range.
+    pub fn start(&self) -> i64 {
+        self.start
+    }
+
+    /// The inclusive upper bound of the range.
+    pub fn end(&self) -> i64 {
+        self.end
+    }
+
+    /// Returns `true` if the range contains no tokens.
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+
+    /// Returns `true` if the range contains the given token.
+    pub fn contains(&self, token: Token) -> bool {
+        self.start < token.value() && token.value() <= self.end
+    }
+
+    /// Splits the range into subranges, each of them owned by a single shard
+    /// of a node with the given sharder.
+    pub fn split_by_shards(&self, sharder: &Sharder) -> Vec<TokenRange> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        if sharder.nr_shards.get() == 1 {
+            return vec![*self];
+        }
+
+        // Shards are computed on tokens biased to the [0, 2^64) range.
+        // Each of the 2^msb_ignore consecutive cycles of `period` tokens
+        // is split evenly between all shards.
+        const BIAS: i128 = 1 << 63;
+        let bias = |token: i64| (token as i128 + BIAS) as u128;
+        let unbias = |biased: u128| (biased as i128 - BIAS) as i64;
+
+        let period: u128 = 1 << (64 - u32::from(sharder.msb_ignore.min(63)));
+        let nr_shards = u128::from(sharder.nr_shards.get());
+        // Returns the first biased token of the shard following the one owning `biased`.
+        let next_shard_start = |biased: u128| {
+            let offset = biased % period;
+            let shard = offset * nr_shards / period;
+            biased - offset + ((shard + 1) * period).div_ceil(nr_shards)
+        };
+
+        let end = bias(self.end);
+        let mut current = bias(self.start);
+        let mut subranges = Vec::new();
+        while current < end {
+            let subrange_end = std::cmp::min(next_shard_start(current + 1) - 1, end);
+            subranges.push(TokenRange::new(unbias(current), unbias(subrange_end)));
+            current = subrange_end;
+        }
+        subranges
+    }
+}
+
+impl fmt::Display for TokenRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}]", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+
+    use super::TokenRange;
+    use crate::routing::{Sharder, Token};
+
+    #[test]
+    fn split_by_shards_without_ignored_bits() {
+        let sharder = Sharder::new(NonZeroU16::new(2).unwrap(), 0);
+        assert_eq!(
+            TokenRange::FULL.split_by_shards(&sharder),
+            vec![TokenRange::new(i64::MIN, -1), TokenRange::new(-1, i64::MAX)]
+        );
+        assert_eq!(
+            TokenRange::new(-10, -5).split_by_shards(&sharder),
+            vec![TokenRange::new(-10, -5)]
+        );
+        assert!(TokenRange::new(5, 5).split_by_shards(&sharder).is_empty());
+    }
+
+    #[test]
+    fn split_by_shards_yields_contiguous_single_shard_ranges() {
+        for (nr_shards, msb_ignore) in [(2, 12), (3, 12), (7, 0), (12, 4)] {
+            let sharder = Sharder::new(NonZeroU16::new(nr_shards).unwrap(), msb_ignore);
+            let range = TokenRange::new(-(1 << 56), 1 << 56);
+            let subranges = range.split_by_shards(&sharder);
+
+            assert_eq!(subranges.first().unwrap().start(), range.start());
+            assert_eq!(subranges.last().unwrap().end(), range.end());
+            for pair in subranges.windows(2) {
+                assert_eq!(pair[0].end(), pair[1].start());
+                // Adjacent ranges belong to different shards.
+                assert_ne!(
+                    sharder.shard_of(Token::new(pair[0].end())),
+                    sharder.shard_of(Token::new(pair[1].start() + 1))
+                );
+            }
+            for subrange in subranges {
+                assert!(!subrange.is_empty());
+                assert_eq!(
+                    sharder.shard_of(Token::new(subrange.start() + 1)),
+                    sharder.shard_of(Token::new(subrange.end()))
+                );
+            }
+        }
+    }
+}
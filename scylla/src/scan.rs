@@ -0,0 +1,533 @@
+//! Parallel scans of whole tables, split by token ranges.
+//!
+//! Reading a whole table with a single `SELECT` query is slow: the query is served
+//! by one coordinator at a time, which has to gather pages from other replicas.
+//! [`TokenRangeScan`] splits the token ring into ranges, each owned by a single node
+//! (and, for ScyllaDB, a single shard), and scans the ranges concurrently with
+//! `SELECT ... WHERE token(pk) > ? AND token(pk) <= ?` queries, each sent
+//! directly to a replica owning the range. Rows of all ranges are merged
+//! into a single typed stream.
+//!
+//! Each range is read with a [QueryPager](crate::client::pager::QueryPager), which fetches
+//! the next pages of the range while the rows of the current one are being consumed.
+//! How far ahead it fetches can be configured with [TokenRangeScan::prefetch_pages].
+//!
+//! Progress of a scan is tracked per range, so an interrupted scan can be
+//! resumed by scanning only the ranges which were not completed.
+//!
+//! # Example
+//! ```rust
+//! # use scylla::client::session::Session;
+//! # use std::error::Error;
+//! # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+//! use futures::StreamExt as _;
+//! use scylla::scan::TokenRangeScan;
+//! use std::num::NonZeroUsize;
+//!
+//! let mut rows = TokenRangeScan::new("ks", "tab")
+//!     .columns(["a", "b"])
+//!     .max_concurrency(NonZeroUsize::new(32).unwrap())
+//!     .execute::<(i32, String)>(session)?;
+//!
+//! while let Some(row) = rows.next().await {
+//!     let (a, b) = row?;
+//!     println!("a, b: {}, {}", a, b);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures::stream::{self, SelectAll};
+use futures::{Stream, StreamExt as _};
+
+use crate::client::pager::TypedRowStream;
+use crate::client::session::Session;
+use crate::cluster::ClusterState;
+use crate::deserialize::row::DeserializeRow;
+use crate::deserialize::DeserializeOwnedRow;
+use crate::errors::{ScanRangeError, ScanRangeErrorKind, TokenRangeScanError};
+use crate::frame::types::Consistency;
+use crate::routing::Token;
+use crate::statement::unprepared::Statement;
+
+pub use crate::routing::TokenRange;
+
+const DEFAULT_MAX_CONCURRENCY: NonZeroUsize = match NonZeroUsize::new(16) {
+    Some(n) => n,
+    None => unreachable!(),
+};
+
+/// A scan of a whole table, split into token ranges which are queried concurrently.
+///
+/// See the [module documentation](self) for more information.
+#[derive(Debug, Clone)]
+pub struct TokenRangeScan {
+    keyspace: String,
+    table: String,
+    columns: Option<Vec<String>>,
+    max_concurrency: NonZeroUsize,
+    split_by_shards: bool,
+    consistency: Option<Consistency>,
+    page_size: Option<i32>,
+    prefetch_pages: Option<usize>,
+    max_prefetched_bytes: Option<NonZeroUsize>,
+}
+
+impl TokenRangeScan {
+    /// Creates a scan of the given table.
+    ///
+    /// The keyspace and table names are case-sensitive and must not be quoted.
+    pub fn new(keyspace: impl Into<String>, table: impl Into<String>) -> Self {
+        Self {
+            keyspace: keyspace.into(),
+            table: table.into(),
+            columns: None,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            split_by_shards: true,
+            consistency: None,
+            page_size: None,
+            prefetch_pages: None,
+            max_prefetched_bytes: None,
+        }
+    }
+
+    /// Selects the given columns instead of all columns of the table.
+    ///
+    /// Column names are put into the statement text as they are given.
+    /// Case-sensitive names have to be quoted by the caller, e.g. `"\"MyColumn\""`.
+    pub fn columns(mut self, columns: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.columns = Some(columns.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Sets the maximum number of token ranges scanned at the same time.
+    ///
+    /// Default: 16.
+    pub fn max_concurrency(mut self, max_concurrency: NonZeroUsize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Sets whether ranges owned by ScyllaDB nodes are further split
+    /// into ranges owned by a single shard.
+    ///
+    /// Queries of such ranges are served by a single shard, without cross-shard
+    /// communication, at the cost of a larger number of (smaller) queries.
+    ///
+    /// Default: true.
+    pub fn split_by_shards(mut self, split_by_shards: bool) -> Self {
+        self.split_by_shards = split_by_shards;
+        self
+    }
+
+    /// Sets the consistency of the queries of the scan.
+    /// By default, the consistency of the session's default execution profile is used.
+    pub fn consistency(mut self, consistency: Consistency) -> Self {
+        self.consistency = Some(consistency);
+        self
+    }
+
+    /// Sets the page size of the queries of the scan.
+    /// See [Statement::set_page_size].
+    pub fn page_size(mut self, page_size: i32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Sets how many pages of each range are fetched ahead of the page being consumed.
+    ///
+    /// With many ranges scanned concurrently, memory usage of the scan is bounded by
+    /// roughly `max_concurrency * (prefetch_pages + 1)` pages.
+    /// See [Statement::set_prefetch_pages].
+    pub fn prefetch_pages(mut self, pages: usize) -> Self {
+        self.prefetch_pages = Some(pages);
+        self
+    }
+
+    /// Bounds the total size of pages of each range fetched ahead and not consumed yet.
+    /// See [Statement::set_max_prefetched_bytes].
+    pub fn max_prefetched_bytes(mut self, max_bytes: NonZeroUsize) -> Self {
+        self.max_prefetched_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Splits the token ring of the cluster into the ranges which the scan consists of,
+    /// in the order of their tokens.
+    ///
+    /// Each range is owned by a single node, and, if [split by shards](Self::split_by_shards)
+    /// and the node is a ScyllaDB node, a single shard of the node.
+    /// If the token ring is unknown, the whole ring is returned as a single range.
+    pub fn token_ranges(&self, cluster_state: &ClusterState) -> Vec<TokenRange> {
+        let ranges = cluster_state.replica_locator().ring().primary_ranges();
+        if ranges.is_empty() {
+            return vec![TokenRange::FULL];
+        }
+
+        ranges
+            .into_iter()
+            .flat_map(|(range, node)| match node.sharder() {
+                Some(sharder) if self.split_by_shards => range.split_by_shards(&sharder),
+                _ => vec![range],
+            })
+            .collect()
+    }
+
+    /// Starts scanning the whole table, returning a stream of its rows.
+    ///
+    /// Rows are deserialized to `RowT`. Rows of a single range are returned in order,
+    /// but rows of different ranges are interleaved.
+    ///
+    /// Fails if the metadata of the table is unknown to the driver, because
+    /// names of its partition key columns are needed to build the queries.
+    pub fn execute<'a, RowT>(
+        &self,
+        session: &'a Session,
+    ) -> Result<TokenRangeScanStream<'a, RowT>, TokenRangeScanError>
+    where
+        RowT: for<'frame, 'metadata> DeserializeRow<'frame, 'metadata> + Send + 'static,
+    {
+        let ranges = self.token_ranges(&session.get_cluster_state());
+        self.execute_ranges(session, ranges)
+    }
+
+    /// Starts scanning the given token ranges of the table, returning a stream of their rows.
+    ///
+    /// This allows resuming an interrupted scan with the
+    /// [unfinished ranges](ScanProgress::unfinished_ranges) of its last progress.
+    /// See [TokenRangeScan::execute].
+    pub fn execute_ranges<'a, RowT>(
+        &self,
+        session: &'a Session,
+        ranges: impl IntoIterator<Item = TokenRange>,
+    ) -> Result<TokenRangeScanStream<'a, RowT>, TokenRangeScanError>
+    where
+        RowT: for<'frame, 'metadata> DeserializeRow<'frame, 'metadata> + Send + 'static,
+    {
+        let cluster_state = session.get_cluster_state();
+        let table = cluster_state
+            .get_keyspace(&self.keyspace)
+            .and_then(|keyspace| keyspace.tables.get(&self.table))
+            .ok_or_else(|| TokenRangeScanError::UnknownTable {
+                keyspace: self.keyspace.clone(),
+                table: self.table.clone(),
+            })?;
+
+        let columns = match &self.columns {
+            Some(columns) => columns.join(", "),
+            None => "*".to_owned(),
+        };
+        let partition_key = table
+            .partition_key
+            .iter()
+            .map(|column| quote_identifier(column))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let query = ScanQuery {
+            select: format!(
+                "SELECT {} FROM {}.{}",
+                columns,
+                quote_identifier(&self.keyspace),
+                quote_identifier(&self.table)
+            ),
+            token: format!("token({})", partition_key),
+            scan: self.clone(),
+        };
+
+        let pending: VecDeque<TokenRange> = ranges.into_iter().collect();
+        Ok(TokenRangeScanStream {
+            session,
+            query,
+            total: pending.len(),
+            pending,
+            in_flight: Vec::new(),
+            completed: Vec::new(),
+            failed: Vec::new(),
+            range_streams: SelectAll::new(),
+        })
+    }
+}
+
+fn quote_identifier(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
+
+/// Builds the queries of the ranges of a scan.
+struct ScanQuery {
+    select: String,
+    token: String,
+    scan: TokenRangeScan,
+}
+
+impl ScanQuery {
+    /// Builds a query of the given range.
+    ///
+    /// Bounds of the range are put into the statement text instead of being bound,
+    /// so that the statement does not have to be prepared to be routed to the replicas
+    /// owning the range.
+    fn statement(&self, range: TokenRange) -> Statement {
+        let mut statement = Statement::new(format!(
+            "{} WHERE {token} > {} AND {token} <= {}",
+            self.select,
+            range.start(),
+            range.end(),
+            token = self.token
+        ));
+        statement.set_routing_token(
+            self.scan.keyspace.clone(),
+            self.scan.table.clone(),
+            Token::new(range.end()),
+        );
+        if let Some(consistency) = self.scan.consistency {
+            statement.set_consistency(consistency);
+        }
+        if let Some(page_size) = self.scan.page_size {
+            statement.set_page_size(page_size);
+        }
+        if let Some(pages) = self.scan.prefetch_pages {
+            statement.set_prefetch_pages(pages);
+        }
+        if let Some(max_bytes) = self.scan.max_prefetched_bytes {
+            statement.set_max_prefetched_bytes(Some(max_bytes));
+        }
+        statement
+    }
+}
+
+/// A snapshot of the progress of a [TokenRangeScan].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanProgress {
+    total: usize,
+    completed: Vec<TokenRange>,
+    failed: Vec<TokenRange>,
+    remaining: Vec<TokenRange>,
+}
+
+impl ScanProgress {
+    /// The number of ranges which the scan consists of.
+    pub fn total_ranges(&self) -> usize {
+        self.total
+    }
+
+    /// Ranges all of whose rows were returned by the scan.
+    pub fn completed_ranges(&self) -> &[TokenRange] {
+        &self.completed
+    }
+
+    /// Ranges whose scanning failed. Some of their rows may have been returned.
+    pub fn failed_ranges(&self) -> &[TokenRange] {
+        &self.failed
+    }
+
+    /// Ranges which are being scanned or have not been started yet.
+    pub fn remaining_ranges(&self) -> &[TokenRange] {
+        &self.remaining
+    }
+
+    /// Ranges which were not completed, i.e. the remaining and the failed ones.
+    ///
+    /// Scanning them with [TokenRangeScan::execute_ranges] resumes the scan.
+    /// Rows of the ranges which were being scanned may be returned again.
+    pub fn unfinished_ranges(&self) -> Vec<TokenRange> {
+        self.remaining
+            .iter()
+            .chain(self.failed.iter())
+            .copied()
+            .collect()
+    }
+
+    /// Returns `true` if all ranges of the scan were completed.
+    pub fn is_completed(&self) -> bool {
+        self.completed.len() == self.total
+    }
+}
+
+enum RangeEvent<RowT> {
+    Row(RowT),
+    Completed(TokenRange),
+    Failed(ScanRangeError),
+}
+
+enum RangeScanState<RowT: 'static> {
+    NotStarted(Statement),
+    Fetching(TypedRowStream<RowT>),
+    Finished,
+}
+
+type RangeStream<'a, RowT> = Pin<Box<dyn Stream<Item = RangeEvent<RowT>> + Send + 'a>>;
+
+fn range_stream<RowT>(
+    session: &Session,
+    statement: Statement,
+    range: TokenRange,
+) -> RangeStream<'_, RowT>
+where
+    RowT: DeserializeOwnedRow + Send + 'static,
+{
+    let fail = move |kind: ScanRangeErrorKind| {
+        Some((
+            RangeEvent::Failed(ScanRangeError { range, kind }),
+            RangeScanState::Finished,
+        ))
+    };
+
+    stream::unfold(
+        RangeScanState::NotStarted(statement),
+        move |state| async move {
+            let mut rows = match state {
+                RangeScanState::NotStarted(statement) => {
+                    let pager = match session.query_iter(statement, &[]).await {
+                        Ok(pager) => pager,
+                        Err(err) => return fail(err.into()),
+                    };
+                    match pager.rows_stream::<RowT>() {
+                        Ok(rows) => rows,
+                        Err(err) => return fail(err.into()),
+                    }
+                }
+                RangeScanState::Fetching(rows) => rows,
+                RangeScanState::Finished => return None,
+            };
+            match rows.next().await {
+                Some(Ok(row)) => Some((RangeEvent::Row(row), RangeScanState::Fetching(rows))),
+                Some(Err(err)) => fail(err.into()),
+                None => Some((RangeEvent::Completed(range), RangeScanState::Finished)),
+            }
+        },
+    )
+    .boxed()
+}
+
+/// A stream of rows of a [TokenRangeScan], returned by [TokenRangeScan::execute].
+///
+/// A failure of a range is returned as an error, after which the stream
+/// continues with the other ranges.
+pub struct TokenRangeScanStream<'a, RowT> {
+    session: &'a Session,
+    query: ScanQuery,
+    total: usize,
+    pending: VecDeque<TokenRange>,
+    in_flight: Vec<TokenRange>,
+    completed: Vec<TokenRange>,
+    failed: Vec<TokenRange>,
+    range_streams: SelectAll<RangeStream<'a, RowT>>,
+}
+
+impl<RowT> TokenRangeScanStream<'_, RowT> {
+    /// Returns a snapshot of the progress of the scan.
+    ///
+    /// A range is reported as completed only after all of its rows were returned by the stream.
+    pub fn progress(&self) -> ScanProgress {
+        ScanProgress {
+            total: self.total,
+            completed: self.completed.clone(),
+            failed: self.failed.clone(),
+            remaining: self
+                .in_flight
+                .iter()
+                .chain(self.pending.iter())
+                .copied()
+                .collect(),
+        }
+    }
+
+    fn finish_range(&mut self, range: TokenRange) {
+        if let Some(position) = self.in_flight.iter().position(|r| *r == range) {
+            self.in_flight.swap_remove(position);
+        }
+    }
+}
+
+impl<RowT> Stream for TokenRangeScanStream<'_, RowT>
+where
+    RowT: DeserializeOwnedRow + Send + 'static,
+{
+    type Item = Result<RowT, ScanRangeError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        loop {
+            while this.in_flight.len() < this.query.scan.max_concurrency.get() {
+                let Some(range) = this.pending.pop_front() else {
+                    break;
+                };
+                this.in_flight.push(range);
+                this.range_streams.push(range_stream(
+                    this.session,
+                    this.query.statement(range),
+                    range,
+                ));
+            }
+
+            match futures::ready!(this.range_streams.poll_next_unpin(cx)) {
+                Some(RangeEvent::Row(row)) => return Poll::Ready(Some(Ok(row))),
+                Some(RangeEvent::Completed(range)) => {
+                    this.finish_range(range);
+                    this.completed.push(range);
+                }
+                Some(RangeEvent::Failed(err)) => {
+                    this.finish_range(err.range);
+                    this.failed.push(err.range);
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<RowT> fmt::Debug for TokenRangeScanStream<'_, RowT> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenRangeScanStream")
+            .field("progress", &self.progress())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use super::{ScanQuery, TokenRange, TokenRangeScan};
+    use crate::statement::unprepared::Statement;
+
+    #[test]
+    fn range_statements_inherit_paging_settings() {
+        let scan = TokenRangeScan::new("ks", "tab")
+            .page_size(100)
+            .prefetch_pages(5)
+            .max_prefetched_bytes(NonZeroUsize::new(1 << 20).unwrap());
+        let query = ScanQuery {
+            select: "SELECT * FROM \"ks\".\"tab\"".to_owned(),
+            token: "token(\"pk\")".to_owned(),
+            scan,
+        };
+
+        let statement = query.statement(TokenRange::new(-10, 10));
+        assert_eq!(
+            statement.contents,
+            "SELECT * FROM \"ks\".\"tab\" WHERE token(\"pk\") > -10 AND token(\"pk\") <= 10"
+        );
+        assert_eq!(statement.get_page_size(), 100);
+        assert_eq!(statement.get_prefetch_pages(), 5);
+        assert_eq!(
+            statement.get_max_prefetched_bytes(),
+            NonZeroUsize::new(1 << 20)
+        );
+
+        let default_statement = ScanQuery {
+            scan: TokenRangeScan::new("ks", "tab"),
+            ..query
+        }
+        .statement(TokenRange::FULL);
+        assert_eq!(
+            default_statement.get_prefetch_pages(),
+            Statement::new("").get_prefetch_pages()
+        );
+    }
+}
@@ -2,10 +2,13 @@
 //! that can be executed together.
 
 use std::borrow::Cow;
+use std::collections::HashMap;
+use std::num::{NonZeroU16, NonZeroUsize};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::client::CancellationToken;
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
 use crate::policies::retry::RetryPolicy;
@@ -15,6 +18,11 @@ use crate::statement::unprepared::Statement;
 use super::StatementConfig;
 use super::{Consistency, SerialConsistency};
 pub use crate::frame::request::batch::BatchType;
+use bytes::Bytes;
+use scylla_cql::serialize::batch::{BatchValues, BatchValuesIterator};
+use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow};
+use scylla_cql::serialize::{RowWriter, SerializationError};
+use thiserror::Error;
 
 /// CQL batch statement.
 ///
@@ -74,6 +82,83 @@ impl Batch {
         self.statements.push(statement.into());
     }
 
+    /// Appends a new unprepared statement to the batch, applying a per-entry
+    /// write timestamp and/or TTL to it.
+    ///
+    /// The CQL protocol only allows a single default timestamp for the whole batch,
+    /// so the attributes are applied by rewriting the statement to carry its own
+    /// `USING TIMESTAMP`/`USING TTL` clause. This is useful e.g. for CDC-style
+    /// writers, which must preserve original cell timestamps.
+    ///
+    /// Only `INSERT`, `UPDATE` and `DELETE` statements without an existing `USING`
+    /// clause are supported. For prepared statements, put `USING TIMESTAMP ?`
+    /// (and/or `TTL ?`) in the statement text and pass the values as bound values.
+    ///
+    /// Per-entry timestamps cannot be combined with a batch-level timestamp
+    /// (see [`Batch::set_timestamp`]), and neither timestamps nor TTLs are
+    /// allowed in counter batches.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::statement::batch::{Batch, BatchEntryAttributes};
+    /// let mut batch = Batch::default();
+    /// batch.append_statement_with_attributes(
+    ///     "INSERT INTO ks.tab (a, b) VALUES (?, ?)",
+    ///     BatchEntryAttributes::new().with_timestamp(1_700_000_000_000_000),
+    /// )?;
+    /// # Ok::<(), scylla::statement::batch::BatchEntryAttributesError>(())
+    /// ```
+    pub fn append_statement_with_attributes(
+        &mut self,
+        statement: impl Into<Statement>,
+        attributes: BatchEntryAttributes,
+    ) -> Result<(), BatchEntryAttributesError> {
+        if attributes.timestamp.is_some() && self.config.timestamp.is_some() {
+            return Err(BatchEntryAttributesError::ConflictsWithBatchTimestamp);
+        }
+        if matches!(self.batch_type, BatchType::Counter) && !attributes.is_empty() {
+            return Err(BatchEntryAttributesError::NotAllowedInCounterBatch);
+        }
+
+        let mut statement = statement.into();
+        statement.contents = using_clause::apply(&statement.contents, &attributes)?;
+        self.statements.push(BatchStatement::Query(statement));
+        Ok(())
+    }
+
+    /// Appends a new statement to the batch, checking that it fits the batch type:
+    /// a counter batch accepts only counter updates, and other batches accept
+    /// only statements which are not counter updates.
+    ///
+    /// The check looks at the statement text (counter updates have the form
+    /// `UPDATE ... SET c = c + ?, d = d - 1 WHERE ...`) and, for prepared statements,
+    /// at the types of the bound values. A statement is only rejected if it surely
+    /// doesn't fit the batch type. For example, `UPDATE ... SET s = s + ?` could add
+    /// to a counter or to a set, so it's only rejected once prepared.
+    ///
+    /// The same check is done when the batch is executed, so that mixing counter updates
+    /// with other statements fails before the batch is sent to the cluster.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use scylla::statement::batch::{Batch, BatchStatementKindError, BatchType};
+    /// let mut batch = Batch::new(BatchType::Counter);
+    /// batch.try_append_statement("UPDATE ks.tab SET c = c + 1 WHERE pk = ?")?;
+    /// assert!(batch
+    ///     .try_append_statement("INSERT INTO ks.tab (pk, v) VALUES (?, ?)")
+    ///     .is_err());
+    /// # Ok::<(), BatchStatementKindError>(())
+    /// ```
+    pub fn try_append_statement(
+        &mut self,
+        statement: impl Into<BatchStatement>,
+    ) -> Result<(), BatchStatementKindError> {
+        let statement = statement.into();
+        counter_update::check_statement_kind(self.batch_type, &statement)?;
+        self.statements.push(statement);
+        Ok(())
+    }
+
     /// Gets type of batch.
     pub fn get_type(&self) -> BatchType {
         self.batch_type
@@ -142,6 +227,18 @@ impl Batch {
         self.config.tracing
     }
 
+    /// Enable or disable skipping compression of requests sent for this batch.
+    /// If enabled, the requests are sent uncompressed even if compression
+    /// is enabled for the session, which saves CPU and latency for tiny requests.
+    pub fn set_skip_compression(&mut self, skip_compression: bool) {
+        self.config.skip_compression = skip_compression;
+    }
+
+    /// Gets whether compression of requests is skipped for this batch
+    pub fn get_skip_compression(&self) -> bool {
+        self.config.skip_compression
+    }
+
     /// Sets the default timestamp for this batch in microseconds.
     /// If not None, it will replace the server side assigned timestamp as default timestamp for
     /// all the statements contained in the batch.
@@ -154,6 +251,45 @@ impl Batch {
         self.config.timestamp
     }
 
+    /// Attaches an application-defined label to this batch, replacing the previous
+    /// value of the label with the same key.
+    ///
+    /// Labels are recorded in the request's tracing span and (with the `metrics` feature)
+    /// in per-label metrics, so that observability signals can be sliced by application-defined
+    /// dimensions, like endpoint or tenant.
+    pub fn set_label(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.config.labels.set(key.into(), value.into());
+    }
+
+    /// Removes the label with the given key from this batch, returning its value.
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.config.labels.remove(key)
+    }
+
+    /// Gets the value of the label with the given key.
+    pub fn get_label(&self, key: &str) -> Option<&str> {
+        self.config.labels.get(key)
+    }
+
+    /// Iterates over the labels attached to this batch, ordered by key.
+    pub fn get_labels(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.config.labels.iter()
+    }
+
+    /// Sets the custom payload sent along with the requests for this batch.
+    ///
+    /// The custom payload is a map of arbitrary key-value pairs, which is not interpreted
+    /// by the driver, but may be consumed by server-side plugins, e.g. a custom query handler.
+    /// If None, no custom payload is sent.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload.map(Arc::new);
+    }
+
+    /// Gets the custom payload sent along with the requests for this batch.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_deref()
+    }
+
     /// Sets the client-side timeout for this batch.
     /// If not None, the driver will stop waiting for the request
     /// to finish after `timeout` passed.
@@ -167,6 +303,33 @@ impl Batch {
         self.config.request_timeout
     }
 
+    /// Sets the deadline for executing this batch.
+    /// If not None, the driver stops executing requests with this batch once
+    /// the deadline passes, returning [ExecutionError::DeadlineExceeded](crate::errors::ExecutionError::DeadlineExceeded).
+    /// As opposed to the request timeout, which is measured anew for each request,
+    /// the deadline bounds all requests executed with the batch, including
+    /// their retries and speculative executions, and all pages of paged requests.
+    /// If the request timeout is set as well, whichever comes first applies.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.config.deadline = deadline
+    }
+
+    /// Gets the deadline for executing this batch.
+    pub fn get_deadline(&self) -> Option<Instant> {
+        self.config.deadline
+    }
+
+    /// Sets the token which allows cancelling requests executed with this batch.
+    /// See [CancellationToken] for details.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.config.cancellation_token = token
+    }
+
+    /// Gets the cancellation token associated with this batch.
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.config.cancellation_token.as_ref()
+    }
+
     /// Set the retry policy for this batch, overriding the one from execution profile if not None.
     #[inline]
     pub fn set_retry_policy(&mut self, retry_policy: Option<Arc<dyn RetryPolicy>>) {
@@ -283,6 +446,786 @@ impl<'a: 'b, 'b> From<&'a BatchStatement>
     }
 }
 
+/// Write attributes applied to a single batch entry.
+///
+/// See [`Batch::append_statement_with_attributes`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BatchEntryAttributes {
+    timestamp: Option<i64>,
+    ttl: Option<i32>,
+}
+
+impl BatchEntryAttributes {
+    /// Creates empty attributes, which leave the statement intact.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the write timestamp of the entry, in microseconds.
+    pub fn with_timestamp(mut self, timestamp: i64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Sets the TTL of the entry, in seconds.
+    pub fn with_ttl(mut self, ttl: i32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Gets the write timestamp of the entry, in microseconds.
+    pub fn get_timestamp(&self) -> Option<i64> {
+        self.timestamp
+    }
+
+    /// Gets the TTL of the entry, in seconds.
+    pub fn get_ttl(&self) -> Option<i32> {
+        self.ttl
+    }
+
+    fn is_empty(&self) -> bool {
+        self.timestamp.is_none() && self.ttl.is_none()
+    }
+}
+
+/// Error when applying [`BatchEntryAttributes`] to a batch entry.
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BatchEntryAttributesError {
+    /// Only INSERT, UPDATE and DELETE statements can be rewritten.
+    #[error("Per-entry attributes are only supported for INSERT, UPDATE and DELETE statements")]
+    UnsupportedStatement,
+
+    /// The statement already contains a USING clause.
+    #[error("The statement already contains a USING clause")]
+    UsingClauseAlreadyPresent,
+
+    /// DELETE statements do not accept a TTL.
+    #[error("TTL cannot be set on a DELETE statement")]
+    TtlNotAllowedForDelete,
+
+    /// TTL must not be negative.
+    #[error("TTL must not be negative, got {0}")]
+    NegativeTtl(i32),
+
+    /// Counter batches accept neither custom timestamps nor TTLs.
+    #[error("Per-entry timestamps and TTLs are not allowed in counter batches")]
+    NotAllowedInCounterBatch,
+
+    /// A per-entry timestamp conflicts with the batch-level timestamp.
+    #[error("Timestamp must be set either on the batch or on individual statements, not both")]
+    ConflictsWithBatchTimestamp,
+}
+
+/// A statement doesn't fit the type of the batch, see [`Batch::try_append_statement`].
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BatchStatementKindError {
+    /// A counter batch can only contain counter updates.
+    #[error("Counter batches can only contain counter updates")]
+    NotCounterUpdate,
+
+    /// Counter updates can only be put in counter batches.
+    #[error("Counter updates can only be put in counter batches")]
+    CounterUpdateInNonCounterBatch,
+}
+
+/// Configuration of splitting statements into multiple batches,
+/// see [`Session::batch_chunked`](crate::client::session::Session::batch_chunked).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BatchChunking {
+    max_statements: NonZeroU16,
+    max_serialized_size: usize,
+    max_concurrency: NonZeroUsize,
+}
+
+impl BatchChunking {
+    /// Creates the default configuration: at most 65,535 statements and 128 KiB
+    /// of serialized statements and values in a batch, and at most 8 batches executed
+    /// at the same time.
+    pub fn new() -> Self {
+        Self {
+            max_statements: NonZeroU16::MAX,
+            max_serialized_size: 128 * 1024,
+            max_concurrency: NonZeroUsize::new(8).unwrap(),
+        }
+    }
+
+    /// Sets the maximum number of statements in a single batch.
+    pub fn with_max_statements(mut self, max_statements: NonZeroU16) -> Self {
+        self.max_statements = max_statements;
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of statements (query strings or prepared statement ids)
+    /// and their serialized values in a single batch.
+    ///
+    /// A statement which alone exceeds the limit is put in a batch on its own.
+    /// Note that ScyllaDB warns about batches larger than `batch_size_warn_threshold_in_kb`
+    /// (128 KiB by default), and rejects ones larger than `batch_size_fail_threshold_in_kb`
+    /// (1 MiB by default).
+    pub fn with_max_serialized_size(mut self, max_serialized_size: usize) -> Self {
+        self.max_serialized_size = max_serialized_size;
+        self
+    }
+
+    /// Sets the maximum number of batches executed at the same time.
+    pub fn with_max_concurrency(mut self, max_concurrency: NonZeroUsize) -> Self {
+        self.max_concurrency = max_concurrency;
+        self
+    }
+
+    /// Gets the maximum number of statements in a single batch.
+    pub fn get_max_statements(&self) -> NonZeroU16 {
+        self.max_statements
+    }
+
+    /// Gets the maximum size, in bytes, of statements and their values in a single batch.
+    pub fn get_max_serialized_size(&self) -> usize {
+        self.max_serialized_size
+    }
+
+    /// Gets the maximum number of batches executed at the same time.
+    pub fn get_max_concurrency(&self) -> NonZeroUsize {
+        self.max_concurrency
+    }
+}
+
+impl Default for BatchChunking {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A batch whose statements are appended together with their bound values.
+///
+/// With a plain [`Batch`], statements and their values are passed separately,
+/// and it's easy to get them out of sync, e.g. by appending a statement and forgetting
+/// to extend the tuple of values. `TypedBatch` pairs each statement with its values
+/// when it's appended, and tracks the types of the values in its type parameter,
+/// so the values passed to [`Session::typed_batch`](crate::client::session::Session::typed_batch)
+/// always match the statements.
+///
+/// # Example
+/// ```rust
+/// # use scylla::client::session::Session;
+/// # use std::error::Error;
+/// # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+/// use scylla::statement::batch::{BatchType, TypedBatch};
+///
+/// let insert = session.prepare("INSERT INTO ks.tab (a, b) VALUES (?, ?)").await?;
+/// let batch = TypedBatch::new(BatchType::Logged)
+///     .append_statement(insert.clone(), (1_i32, "one"))
+///     .append_statement(insert, (2_i32, "two"))
+///     .append_statement("DELETE FROM ks.tab WHERE a = 3", ());
+///
+/// session.typed_batch(&batch).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TypedBatch<V = EmptyTypedBatchValues> {
+    batch: Batch,
+    values: V,
+}
+
+impl TypedBatch {
+    /// Creates a new, empty `TypedBatch` of `batch_type` type.
+    pub fn new(batch_type: BatchType) -> Self {
+        Self {
+            batch: Batch::new(batch_type),
+            values: EmptyTypedBatchValues,
+        }
+    }
+
+    /// Creates an empty `TypedBatch` with the type and configuration
+    /// (e.g. consistency or timestamp) of `template`. Statements of `template` are ignored.
+    pub fn from_template(template: &Batch) -> Self {
+        Self {
+            batch: Batch::new_from(template),
+            values: EmptyTypedBatchValues,
+        }
+    }
+}
+
+impl<V> TypedBatch<V> {
+    /// Appends a new statement to the batch, together with its bound values.
+    ///
+    /// Both prepared and unprepared statements are allowed. Use `()`
+    /// for statements without bound values.
+    pub fn append_statement<R: SerializeRow>(
+        self,
+        statement: impl Into<BatchStatement>,
+        values: R,
+    ) -> TypedBatch<TypedBatchValues<V, R>> {
+        let mut batch = self.batch;
+        batch.append_statement(statement);
+        TypedBatch {
+            batch,
+            values: TypedBatchValues {
+                init: self.values,
+                last: values,
+            },
+        }
+    }
+
+    /// The batch consisting of the appended statements.
+    pub fn batch(&self) -> &Batch {
+        &self.batch
+    }
+
+    /// The values of the appended statements, in the order of the statements.
+    pub fn values(&self) -> &V {
+        &self.values
+    }
+
+    /// Splits the batch into the plain [`Batch`] and its values,
+    /// e.g. to execute it with [`CachingSession::batch`](crate::client::caching_session::CachingSession::batch).
+    pub fn into_parts(self) -> (Batch, V) {
+        (self.batch, self.values)
+    }
+
+    /// Returns the number of statements in the batch.
+    pub fn len(&self) -> usize {
+        self.batch.statements.len()
+    }
+
+    /// Returns `true` if no statements were appended to the batch.
+    pub fn is_empty(&self) -> bool {
+        self.batch.statements.is_empty()
+    }
+}
+
+/// Values of an empty [`TypedBatch`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyTypedBatchValues;
+
+impl BatchValues for EmptyTypedBatchValues {
+    type BatchValuesIter<'r> = EmptyTypedBatchValues;
+
+    #[inline]
+    fn batch_values_iter(&self) -> Self::BatchValuesIter<'_> {
+        EmptyTypedBatchValues
+    }
+}
+
+impl BatchValuesIterator<'_> for EmptyTypedBatchValues {
+    #[inline]
+    fn serialize_next(
+        &mut self,
+        _ctx: &RowSerializationContext<'_>,
+        _writer: &mut RowWriter,
+    ) -> Option<Result<(), SerializationError>> {
+        None
+    }
+
+    #[inline]
+    fn is_empty_next(&mut self) -> Option<bool> {
+        None
+    }
+
+    #[inline]
+    fn skip_next(&mut self) -> Option<()> {
+        None
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        0
+    }
+}
+
+/// Values of a [`TypedBatch`]: the values of all its statements but the last one,
+/// followed by the values of the last statement.
+#[derive(Debug, Clone)]
+pub struct TypedBatchValues<Init, Last> {
+    init: Init,
+    last: Last,
+}
+
+impl<Init: BatchValues, Last: SerializeRow> BatchValues for TypedBatchValues<Init, Last> {
+    type BatchValuesIter<'r>
+        = TypedBatchValuesIter<'r, Init::BatchValuesIter<'r>, Last>
+    where
+        Self: 'r;
+
+    #[inline]
+    fn batch_values_iter(&self) -> Self::BatchValuesIter<'_> {
+        TypedBatchValuesIter {
+            init: self.init.batch_values_iter(),
+            last: Some(&self.last),
+        }
+    }
+}
+
+/// Iterates over [`TypedBatchValues`].
+pub struct TypedBatchValuesIter<'bv, InitIter, Last> {
+    init: InitIter,
+    last: Option<&'bv Last>,
+}
+
+impl<'bv, InitIter, Last> BatchValuesIterator<'bv> for TypedBatchValuesIter<'bv, InitIter, Last>
+where
+    InitIter: BatchValuesIterator<'bv>,
+    Last: SerializeRow,
+{
+    #[inline]
+    fn serialize_next(
+        &mut self,
+        ctx: &RowSerializationContext<'_>,
+        writer: &mut RowWriter,
+    ) -> Option<Result<(), SerializationError>> {
+        match self.init.serialize_next(ctx, writer) {
+            Some(result) => Some(result),
+            None => self.last.take().map(|last| last.serialize(ctx, writer)),
+        }
+    }
+
+    #[inline]
+    fn is_empty_next(&mut self) -> Option<bool> {
+        match self.init.is_empty_next() {
+            Some(is_empty) => Some(is_empty),
+            None => self.last.take().map(SerializeRow::is_empty),
+        }
+    }
+
+    #[inline]
+    fn skip_next(&mut self) -> Option<()> {
+        match self.init.skip_next() {
+            Some(()) => Some(()),
+            None => self.last.take().map(|_| ()),
+        }
+    }
+}
+
+/// Splitting statements into multiple batches, see [`BatchChunking`].
+pub(crate) mod chunking {
+    use std::ops::Range;
+
+    use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
+    use scylla_cql::serialize::{RowWriter, SerializationError};
+
+    use super::{BatchChunking, BatchStatement};
+
+    /// Returns the number of bytes taken by the statement and its values in a `BATCH` request.
+    pub(crate) fn entry_size(statement: &BatchStatement, values: &SerializedValues) -> usize {
+        // [byte] kind, then [long string] query or [short bytes] id.
+        let statement_size = match statement {
+            BatchStatement::Query(query) => 1 + 4 + query.contents.len(),
+            BatchStatement::PreparedStatement(prepared) => 1 + 2 + prepared.get_id().len(),
+        };
+        // [short] number of values, then the values.
+        statement_size + 2 + values.buffer_size()
+    }
+
+    /// Splits the entries with the given sizes into ranges of consecutive entries,
+    /// which fit in a single batch.
+    pub(crate) fn split(
+        entry_sizes: impl IntoIterator<Item = usize>,
+        chunking: &BatchChunking,
+    ) -> Vec<Range<usize>> {
+        let max_statements = chunking.max_statements.get() as usize;
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut chunk_size = 0;
+        let mut len = 0;
+        for (i, size) in entry_sizes.into_iter().enumerate() {
+            let is_full = i - start == max_statements
+                || (i > start && chunk_size + size > chunking.max_serialized_size);
+            if is_full {
+                chunks.push(start..i);
+                start = i;
+                chunk_size = 0;
+            }
+            chunk_size += size;
+            len = i + 1;
+        }
+        if start < len {
+            chunks.push(start..len);
+        }
+        chunks
+    }
+
+    /// Values of a batch entry, serialized upfront in order to know their size.
+    pub(crate) struct SerializedRow<'a>(pub(crate) &'a SerializedValues);
+
+    impl SerializeRow for SerializedRow<'_> {
+        fn serialize(
+            &self,
+            _ctx: &RowSerializationContext<'_>,
+            writer: &mut RowWriter,
+        ) -> Result<(), SerializationError> {
+            writer.append_serialize_row(self.0);
+            Ok(())
+        }
+
+        fn is_empty(&self) -> bool {
+            self.0.is_empty()
+        }
+    }
+}
+
+/// Minimal CQL scanner used to place `USING` clauses in statements.
+pub(crate) mod using_clause {
+    use super::{BatchEntryAttributes, BatchEntryAttributesError};
+
+    /// Yields (byte offset, word) pairs for all unquoted words in `cql`,
+    /// skipping string literals, quoted identifiers and comments.
+    pub(super) fn words(cql: &str) -> Vec<(usize, &str)> {
+        let bytes = cql.as_bytes();
+        let mut words = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            match bytes[i] {
+                quote @ (b'\'' | b'"') => {
+                    i += 1;
+                    while i < bytes.len() {
+                        if bytes[i] == quote {
+                            // A doubled quote is an escaped quote.
+                            if bytes.get(i + 1) == Some(&quote) {
+                                i += 2;
+                                continue;
+                            }
+                            break;
+                        }
+                        i += 1;
+                    }
+                    i += 1;
+                }
+                b'$' if bytes.get(i + 1) == Some(&b'$') => {
+                    i = cql[i + 2..]
+                        .find("$$")
+                        .map_or(bytes.len(), |end| i + 2 + end + 2);
+                }
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = cql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = cql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = cql[i + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |end| i + 2 + end + 2);
+                }
+                b if b.is_ascii_alphanumeric() || b == b'_' => {
+                    let start = i;
+                    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_')
+                    {
+                        i += 1;
+                    }
+                    words.push((start, &cql[start..i]));
+                }
+                _ => i += 1,
+            }
+        }
+        words
+    }
+
+    /// Returns whether the statement sets its own write timestamp.
+    pub(crate) fn has_using_timestamp(cql: &str) -> bool {
+        let words = words(cql);
+        let Some(using) = words
+            .iter()
+            .position(|(_, w)| w.eq_ignore_ascii_case("USING"))
+        else {
+            return false;
+        };
+        words[using + 1..]
+            .iter()
+            .map(|(_, w)| w)
+            .take_while(|w| !w.eq_ignore_ascii_case("SET") && !w.eq_ignore_ascii_case("WHERE"))
+            .any(|w| w.eq_ignore_ascii_case("TIMESTAMP"))
+    }
+
+    /// Rewrites `cql` so that it carries the given attributes in a `USING` clause.
+    pub(crate) fn apply(
+        cql: &str,
+        attributes: &BatchEntryAttributes,
+    ) -> Result<String, BatchEntryAttributesError> {
+        if let Some(ttl) = attributes.ttl.filter(|ttl| *ttl < 0) {
+            return Err(BatchEntryAttributesError::NegativeTtl(ttl));
+        }
+        if attributes.is_empty() {
+            return Ok(cql.to_owned());
+        }
+
+        let words = words(cql);
+        if words.iter().any(|(_, w)| w.eq_ignore_ascii_case("USING")) {
+            return Err(BatchEntryAttributesError::UsingClauseAlreadyPresent);
+        }
+
+        let find = |keyword: &str| {
+            words
+                .iter()
+                .find(|(_, w)| w.eq_ignore_ascii_case(keyword))
+                .map(|(pos, _)| *pos)
+        };
+        let kind = words.first().map(|(_, w)| w.to_ascii_uppercase());
+        let position = match kind.as_deref() {
+            // USING is the last clause of an INSERT.
+            Some("INSERT") => cql.trim_end().trim_end_matches(';').trim_end().len(),
+            Some("UPDATE") => find("SET").ok_or(BatchEntryAttributesError::UnsupportedStatement)?,
+            Some("DELETE") => {
+                if attributes.ttl.is_some() {
+                    return Err(BatchEntryAttributesError::TtlNotAllowedForDelete);
+                }
+                find("WHERE").ok_or(BatchEntryAttributesError::UnsupportedStatement)?
+            }
+            _ => return Err(BatchEntryAttributesError::UnsupportedStatement),
+        };
+
+        let mut clauses = Vec::with_capacity(2);
+        if let Some(timestamp) = attributes.timestamp {
+            clauses.push(format!("TIMESTAMP {}", timestamp));
+        }
+        if let Some(ttl) = attributes.ttl {
+            clauses.push(format!("TTL {}", ttl));
+        }
+        let (head, tail) = cql.split_at(position);
+        let tail = tail.trim_start();
+        let separator = if tail.is_empty() || tail.starts_with(';') {
+            ""
+        } else {
+            " "
+        };
+        Ok(format!(
+            "{} USING {}{}{}",
+            head.trim_end(),
+            clauses.join(" AND "),
+            separator,
+            tail
+        ))
+    }
+}
+
+/// Telling counter updates from other statements, see [`Batch::try_append_statement`].
+pub(crate) mod counter_update {
+    use scylla_cql::frame::response::result::{ColumnType, NativeType};
+
+    use super::using_clause::words;
+    use super::{BatchStatement, BatchStatementKindError, BatchType};
+
+    /// What can be told about a statement being a counter update.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum CounterUpdate {
+        Yes,
+        No,
+        Unknown,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    enum Token {
+        Identifier(String),
+        Number,
+        BindMarker,
+        Symbol(u8),
+        Other,
+    }
+
+    /// Kinds of the values added to (or subtracted from) columns in an assignment.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Term {
+        Number,
+        BindMarker,
+        Collection,
+        Other,
+    }
+
+    /// Splits a fragment of a statement into tokens, skipping comments.
+    /// Unquoted identifiers are lowercased, as they are case-insensitive.
+    fn tokens(cql: &str) -> Vec<Token> {
+        let bytes = cql.as_bytes();
+        let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < bytes.len() {
+            let start = i;
+            match bytes[i] {
+                b if b.is_ascii_whitespace() => i += 1,
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = cql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                    i = cql[i..].find('\n').map_or(bytes.len(), |end| i + end);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = cql[i + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |end| i + 2 + end + 2);
+                }
+                quote @ (b'\'' | b'"') => {
+                    let mut text = String::new();
+                    i += 1;
+                    while i < bytes.len() {
+                        if bytes[i] == quote {
+                            // A doubled quote is an escaped quote.
+                            if bytes.get(i + 1) != Some(&quote) {
+                                break;
+                            }
+                            i += 1;
+                        }
+                        let len = cql[i..].chars().next().map_or(1, char::len_utf8);
+                        text.push_str(&cql[i..i + len]);
+                        i += len;
+                    }
+                    i += 1;
+                    tokens.push(if quote == b'"' {
+                        Token::Identifier(text)
+                    } else {
+                        Token::Other
+                    });
+                }
+                b'?' => {
+                    i += 1;
+                    tokens.push(Token::BindMarker);
+                }
+                b':' if bytes.get(i + 1).is_some_and(|b| is_word_byte(*b)) => {
+                    i += 1;
+                    while i < bytes.len() && is_word_byte(bytes[i]) {
+                        i += 1;
+                    }
+                    tokens.push(Token::BindMarker);
+                }
+                b if is_word_byte(b) => {
+                    while i < bytes.len() && is_word_byte(bytes[i]) {
+                        i += 1;
+                    }
+                    let word = &cql[start..i];
+                    tokens.push(if bytes[start].is_ascii_digit() {
+                        Token::Number
+                    } else {
+                        Token::Identifier(word.to_ascii_lowercase())
+                    });
+                }
+                b if b.is_ascii() => {
+                    i += 1;
+                    tokens.push(Token::Symbol(b));
+                }
+                _ => {
+                    i += cql[i..].chars().next().map_or(1, char::len_utf8);
+                    tokens.push(Token::Other);
+                }
+            }
+        }
+        tokens
+    }
+
+    /// Parses a single assignment of the form `c = c + x`, `c = c - x`,
+    /// `c += x` or `c -= x`, returning the kind of `x`.
+    fn counter_assignment(assignment: &[Token]) -> Option<Term> {
+        use Token::{Identifier, Symbol};
+
+        let term = match assignment {
+            [Identifier(column), Symbol(b'='), Identifier(operand), Symbol(b'+' | b'-'), term @ ..]
+                if column == operand =>
+            {
+                term
+            }
+            [Identifier(_), Symbol(b'+' | b'-'), Symbol(b'='), term @ ..] => term,
+            _ => return None,
+        };
+        match term {
+            [] => None,
+            [Token::Number] | [Symbol(b'-'), Token::Number] => Some(Term::Number),
+            [Token::BindMarker] => Some(Term::BindMarker),
+            [Symbol(b'[' | b'{'), ..] => Some(Term::Collection),
+            _ => Some(Term::Other),
+        }
+    }
+
+    /// If `cql` has the form of a counter update, returns the kinds of values
+    /// added to the counters.
+    fn counter_update_terms(cql: &str) -> Option<Vec<Term>> {
+        let words = words(cql);
+        if !words.first()?.1.eq_ignore_ascii_case("UPDATE") {
+            return None;
+        }
+        let set = words
+            .iter()
+            .position(|(_, w)| w.eq_ignore_ascii_case("SET"))?;
+        let (where_offset, _) = words[set..]
+            .iter()
+            .find(|(_, w)| w.eq_ignore_ascii_case("WHERE"))?;
+        let (set_offset, set_word) = words[set];
+        let tokens = tokens(&cql[set_offset + set_word.len()..*where_offset]);
+
+        // Split the assignments on commas outside of brackets.
+        let mut assignments = vec![Vec::new()];
+        let mut depth = 0_usize;
+        for token in tokens {
+            match token {
+                Token::Symbol(b'(' | b'[' | b'{') => depth += 1,
+                Token::Symbol(b')' | b']' | b'}') => depth = depth.saturating_sub(1),
+                Token::Symbol(b',') if depth == 0 => {
+                    assignments.push(Vec::new());
+                    continue;
+                }
+                _ => {}
+            }
+            assignments.last_mut().unwrap().push(token);
+        }
+        assignments
+            .iter()
+            .map(|assignment| counter_assignment(assignment))
+            .collect()
+    }
+
+    /// Tells whether the statement is a counter update.
+    ///
+    /// Adding to a counter looks the same as adding to a collection, so a statement is only
+    /// known to be a counter update if it adds a number literal (which can't be added to
+    /// a collection), or binds a value of the counter type.
+    pub(crate) fn is_counter_update(statement: &BatchStatement) -> CounterUpdate {
+        let (contents, variable_types) = match statement {
+            BatchStatement::Query(query) => (query.contents.as_str(), Vec::new()),
+            BatchStatement::PreparedStatement(prepared) => (
+                prepared.get_statement(),
+                prepared
+                    .get_variable_col_specs()
+                    .iter()
+                    .map(|spec| spec.typ())
+                    .collect(),
+            ),
+        };
+
+        let Some(terms) = counter_update_terms(contents) else {
+            return CounterUpdate::No;
+        };
+        let binds_counter = variable_types
+            .iter()
+            .any(|typ| matches!(typ, ColumnType::Native(NativeType::Counter)));
+        // Tables with counters cannot have non-frozen collections.
+        let binds_collection = variable_types
+            .iter()
+            .any(|typ| matches!(typ, ColumnType::Collection { frozen: false, .. }));
+
+        if binds_collection || terms.contains(&Term::Collection) {
+            CounterUpdate::No
+        } else if binds_counter || terms.contains(&Term::Number) {
+            CounterUpdate::Yes
+        } else {
+            CounterUpdate::Unknown
+        }
+    }
+
+    /// Checks whether the statement can be put in a batch of the given type.
+    pub(crate) fn check_statement_kind(
+        batch_type: BatchType,
+        statement: &BatchStatement,
+    ) -> Result<(), BatchStatementKindError> {
+        match (batch_type, is_counter_update(statement)) {
+            (BatchType::Counter, CounterUpdate::No) => {
+                Err(BatchStatementKindError::NotCounterUpdate)
+            }
+            (BatchType::Logged | BatchType::Unlogged, CounterUpdate::Yes) => {
+                Err(BatchStatementKindError::CounterUpdateInNonCounterBatch)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
 pub(crate) mod batch_values {
     use scylla_cql::serialize::batch::BatchValues;
     use scylla_cql::serialize::batch::BatchValuesIterator;
@@ -425,3 +1368,235 @@ pub(crate) mod batch_values {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU16;
+
+    use scylla_cql::frame::response::result::{ColumnSpec, ColumnType, NativeType, TableSpec};
+    use scylla_cql::serialize::batch::{BatchValues, BatchValuesIterator};
+    use scylla_cql::serialize::row::{RowSerializationContext, SerializedValues};
+    use scylla_cql::serialize::RowWriter;
+
+    use super::chunking::{entry_size, split};
+    use super::counter_update::{is_counter_update, CounterUpdate};
+    use super::using_clause::{apply, has_using_timestamp};
+    use super::{
+        Batch, BatchChunking, BatchEntryAttributes, BatchEntryAttributesError, BatchStatement,
+        BatchStatementKindError, BatchType, TypedBatch,
+    };
+
+    #[test]
+    fn using_clause_is_placed_per_statement_kind() {
+        let ts = BatchEntryAttributes::new().with_timestamp(42);
+        let ts_ttl = ts.with_ttl(10);
+
+        assert_eq!(
+            apply(
+                "INSERT INTO t (a, b) VALUES (?, 'x;y') IF NOT EXISTS;",
+                &ts_ttl
+            )
+            .unwrap(),
+            "INSERT INTO t (a, b) VALUES (?, 'x;y') IF NOT EXISTS USING TIMESTAMP 42 AND TTL 10;"
+        );
+        assert_eq!(
+            apply("update t set a = 'SET' where b = 1", &ts).unwrap(),
+            "update t USING TIMESTAMP 42 set a = 'SET' where b = 1"
+        );
+        assert_eq!(
+            apply("DELETE a FROM \"where\" WHERE b = ?", &ts).unwrap(),
+            "DELETE a FROM \"where\" USING TIMESTAMP 42 WHERE b = ?"
+        );
+
+        assert_eq!(
+            apply("DELETE FROM t WHERE b = ?", &ts_ttl),
+            Err(BatchEntryAttributesError::TtlNotAllowedForDelete)
+        );
+        assert_eq!(
+            apply("UPDATE t USING TTL 5 SET a = 1 WHERE b = 1", &ts),
+            Err(BatchEntryAttributesError::UsingClauseAlreadyPresent)
+        );
+        assert_eq!(
+            apply("SELECT * FROM t", &ts),
+            Err(BatchEntryAttributesError::UnsupportedStatement)
+        );
+        assert_eq!(
+            apply(
+                "INSERT INTO t (a) VALUES (1)",
+                &BatchEntryAttributes::new().with_ttl(-1)
+            ),
+            Err(BatchEntryAttributesError::NegativeTtl(-1))
+        );
+
+        assert!(has_using_timestamp(
+            "UPDATE t USING TTL 1 AND TIMESTAMP 2 SET a = 1"
+        ));
+        assert!(!has_using_timestamp(
+            "INSERT INTO t (a) VALUES ('USING TIMESTAMP')"
+        ));
+        assert!(!has_using_timestamp(
+            "UPDATE t USING TTL 1 SET timestamp = 2"
+        ));
+    }
+
+    #[test]
+    fn batch_entry_attributes_conflicts() {
+        let ts = BatchEntryAttributes::new().with_timestamp(42);
+
+        let mut batch = Batch::default();
+        batch.set_timestamp(Some(1));
+        assert_eq!(
+            batch.append_statement_with_attributes("INSERT INTO t (a) VALUES (1)", ts),
+            Err(BatchEntryAttributesError::ConflictsWithBatchTimestamp)
+        );
+
+        let mut batch = Batch::new(BatchType::Counter);
+        assert_eq!(
+            batch.append_statement_with_attributes("UPDATE t SET c = c + 1 WHERE a = 1", ts),
+            Err(BatchEntryAttributesError::NotAllowedInCounterBatch)
+        );
+
+        let mut batch = Batch::default();
+        batch
+            .append_statement_with_attributes("INSERT INTO t (a) VALUES (1)", ts)
+            .unwrap();
+        match &batch.statements[..] {
+            [BatchStatement::Query(q)] => {
+                assert_eq!(
+                    q.contents,
+                    "INSERT INTO t (a) VALUES (1) USING TIMESTAMP 42"
+                )
+            }
+            _ => panic!("unexpected batch statements"),
+        }
+    }
+
+    #[test]
+    fn chunking_respects_limits() {
+        let chunking = BatchChunking::new()
+            .with_max_statements(NonZeroU16::new(3).unwrap())
+            .with_max_serialized_size(100);
+
+        assert!(split([], &chunking).is_empty());
+        assert_eq!(split([10; 7], &chunking), [0..3, 3..6, 6..7]);
+        assert_eq!(
+            split([40, 40, 40, 10, 50, 1], &chunking),
+            [0..2, 2..5, 5..6]
+        );
+        // Entries exceeding the size limit are put in batches on their own.
+        assert_eq!(
+            split([10, 150, 150, 10], &chunking),
+            [0..1, 1..2, 2..3, 3..4]
+        );
+        assert_eq!(split([100, 0, 0, 0], &chunking), [0..3, 3..4]);
+    }
+
+    #[test]
+    fn chunking_entry_size() {
+        let mut values = SerializedValues::new();
+        values
+            .add_value(&1_i32, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        let statement = BatchStatement::from("INSERT INTO t (a) VALUES (?)");
+        // Kind, query string, number of values and a single int value.
+        assert_eq!(entry_size(&statement, &values), 1 + (4 + 28) + 2 + (4 + 4));
+    }
+
+    #[test]
+    fn typed_batch_values_follow_statements() {
+        let batch = TypedBatch::new(BatchType::Unlogged)
+            .append_statement("INSERT INTO t (a) VALUES (?)", (1_i32,))
+            .append_statement("INSERT INTO t (a) VALUES (5)", ())
+            .append_statement("INSERT INTO t (a) VALUES (?)", (2_i32,));
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.batch().statements.len(), 3);
+        assert_eq!(batch.values().batch_values_iter().count(), 3);
+
+        let mut iter = batch.values().batch_values_iter();
+        assert_eq!(iter.is_empty_next(), Some(false));
+        assert_eq!(iter.is_empty_next(), Some(true));
+        assert_eq!(iter.is_empty_next(), Some(false));
+        assert_eq!(iter.is_empty_next(), None);
+
+        let specs = [ColumnSpec::borrowed(
+            "a",
+            ColumnType::Native(NativeType::Int),
+            TableSpec::borrowed("ks", "t"),
+        )];
+        let ctx = RowSerializationContext::from_specs(&specs);
+        let mut iter = batch.values().batch_values_iter();
+        let mut serialized = Vec::new();
+        let mut writer = RowWriter::new(&mut serialized);
+        iter.serialize_next(&ctx, &mut writer).unwrap().unwrap();
+        assert_eq!(iter.skip_next(), Some(()));
+        iter.serialize_next(&ctx, &mut writer).unwrap().unwrap();
+        assert!(iter.serialize_next(&ctx, &mut writer).is_none());
+        // The first and the third statement have a single int value each.
+        assert_eq!(serialized, [0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 2]);
+
+        let empty = TypedBatch::from_template(batch.batch());
+        assert!(empty.is_empty());
+        assert!(matches!(empty.batch().get_type(), BatchType::Unlogged));
+        assert_eq!(empty.values().batch_values_iter().count(), 0);
+    }
+
+    #[test]
+    fn counter_updates_are_recognized() {
+        let check = |cql: &str| is_counter_update(&BatchStatement::from(cql));
+
+        for cql in [
+            "UPDATE t SET c = c + 1 WHERE pk = ?",
+            "update ks.t using ttl 5 set c=c-1, \"D\" = \"D\" + ? where pk = 0",
+            "UPDATE t SET c += 5, d -= -3 WHERE pk = 1 -- increment",
+            "UPDATE t SET /* comment, with a comma */ C = c + 1 WHERE pk = 'SET c'",
+        ] {
+            assert_eq!(check(cql), CounterUpdate::Yes, "{cql}");
+        }
+        // Adding a bound value could also be adding to a collection.
+        for cql in [
+            "UPDATE t SET c = c + ? WHERE pk = ?",
+            "UPDATE t SET c = c + :inc WHERE pk = :pk",
+        ] {
+            assert_eq!(check(cql), CounterUpdate::Unknown, "{cql}");
+        }
+        for cql in [
+            "INSERT INTO t (pk, c) VALUES (?, ?)",
+            "UPDATE t SET c = ? WHERE pk = ?",
+            "UPDATE t SET c = d + 1 WHERE pk = ?",
+            "UPDATE t SET c = c + 1, v = 'x' WHERE pk = ?",
+            "UPDATE t SET l = [1] + l WHERE pk = ?",
+            "UPDATE t SET l = l + [1, 2] WHERE pk = ?",
+            "UPDATE t SET s = s + {'a', 'b'} WHERE pk = ?",
+            "DELETE FROM t WHERE pk = ?",
+            "UPDATE t SET c = c + 1",
+        ] {
+            assert_eq!(check(cql), CounterUpdate::No, "{cql}");
+        }
+    }
+
+    #[test]
+    fn try_append_statement_rejects_mixing() {
+        let mut counter_batch = Batch::new(BatchType::Counter);
+        counter_batch
+            .try_append_statement("UPDATE t SET c = c + 1 WHERE pk = 0")
+            .unwrap();
+        counter_batch
+            .try_append_statement("UPDATE t SET c = c + ? WHERE pk = ?")
+            .unwrap();
+        assert_eq!(
+            counter_batch.try_append_statement("INSERT INTO t (pk, v) VALUES (1, 2)"),
+            Err(BatchStatementKindError::NotCounterUpdate)
+        );
+        assert_eq!(counter_batch.statements.len(), 2);
+
+        let mut logged_batch = Batch::new(BatchType::Logged);
+        logged_batch
+            .try_append_statement("UPDATE t SET l = l + ? WHERE pk = ?")
+            .unwrap();
+        assert_eq!(
+            logged_batch.try_append_statement("UPDATE t SET c = c + 1 WHERE pk = 0"),
+            Err(BatchStatementKindError::CounterUpdateInNonCounterBatch)
+        );
+        assert_eq!(logged_batch.statements.len(), 1);
+    }
+}
@@ -5,11 +5,17 @@
 //! - PreparedStatement,
 //! - Batch.
 
-use std::{sync::Arc, time::Duration};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use bytes::Bytes;
 use thiserror::Error;
 
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::client::CancellationToken;
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
 use crate::policies::retry::RetryPolicy;
@@ -24,6 +30,8 @@ pub use unprepared::Statement;
 // This is the default common to drivers.
 const DEFAULT_PAGE_SIZE: i32 = 5000;
 
+const DEFAULT_PREFETCH_PAGES: usize = 2;
+
 #[derive(Debug, Clone, Default)]
 pub(crate) struct StatementConfig {
     pub(crate) consistency: Option<Consistency>,
@@ -33,14 +41,24 @@ pub(crate) struct StatementConfig {
 
     pub(crate) skip_result_metadata: bool,
     pub(crate) tracing: bool,
+    pub(crate) skip_compression: bool,
     pub(crate) timestamp: Option<i64>,
     pub(crate) request_timeout: Option<Duration>,
+    pub(crate) deadline: Option<Instant>,
 
     pub(crate) history_listener: Option<Arc<dyn HistoryListener>>,
 
     pub(crate) execution_profile_handle: Option<ExecutionProfileHandle>,
     pub(crate) load_balancing_policy: Option<Arc<dyn LoadBalancingPolicy>>,
     pub(crate) retry_policy: Option<Arc<dyn RetryPolicy>>,
+
+    pub(crate) labels: StatementLabels,
+
+    pub(crate) pager_prefetch: PagerPrefetch,
+
+    pub(crate) custom_payload: Option<Arc<HashMap<String, Bytes>>>,
+
+    pub(crate) cancellation_token: Option<CancellationToken>,
 }
 
 impl StatementConfig {
@@ -51,6 +69,69 @@ impl StatementConfig {
     }
 }
 
+/// Application-defined key-value labels attached to a statement,
+/// propagated to observability signals (tracing spans and metrics).
+/// Cheaply clonable.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct StatementLabels(Arc<BTreeMap<String, String>>);
+
+impl StatementLabels {
+    pub(crate) fn set(&mut self, key: String, value: String) {
+        Arc::make_mut(&mut self.0).insert(key, value);
+    }
+
+    pub(crate) fn remove(&mut self, key: &str) -> Option<String> {
+        if !self.0.contains_key(key) {
+            return None;
+        }
+        Arc::make_mut(&mut self.0).remove(key)
+    }
+
+    pub(crate) fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Display for StatementLabels {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Limits how far ahead of the consumer a [QueryPager](crate::client::pager::QueryPager)
+/// fetches pages. Configurable on statements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PagerPrefetch {
+    /// Maximum number of pages fetched ahead of the page being consumed.
+    pub(crate) pages: usize,
+    /// Maximum total size of pages fetched ahead, or `None` if unbounded.
+    pub(crate) max_bytes: Option<NonZeroUsize>,
+}
+
+impl Default for PagerPrefetch {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            pages: DEFAULT_PREFETCH_PAGES,
+            max_bytes: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Error)]
 #[error("Invalid page size provided: {0}; valid values are [1, i32::MAX]")]
 /// Invalid page size was provided.
@@ -9,14 +9,18 @@ use scylla_cql::frame::types::RawValue;
 use scylla_cql::serialize::row::{RowSerializationContext, SerializeRow, SerializedValues};
 use scylla_cql::serialize::SerializationError;
 use smallvec::{smallvec, SmallVec};
+use std::collections::HashMap;
 use std::convert::TryInto;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use thiserror::Error;
 use uuid::Uuid;
 
+use super::unprepared::Statement;
 use super::{PageSize, StatementConfig};
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::client::CancellationToken;
 use crate::errors::{BadQuery, ExecutionError};
 use crate::frame::response::result::PreparedMetadata;
 use crate::frame::types::{Consistency, SerialConsistency};
@@ -110,6 +114,10 @@ struct PreparedStatementSharedData {
     metadata: PreparedMetadata,
     result_metadata: Arc<ResultMetadata<'static>>,
     statement: String,
+    keyspace: Option<String>,
+    // Label of the table this statement is operating on, used to slice metrics.
+    #[cfg(feature = "metrics")]
+    table_label: Option<String>,
 }
 
 impl Clone for PreparedStatement {
@@ -132,20 +140,28 @@ impl PreparedStatement {
         is_lwt: bool,
         metadata: PreparedMetadata,
         result_metadata: Arc<ResultMetadata<'static>>,
-        statement: String,
-        page_size: PageSize,
-        config: StatementConfig,
+        statement: Statement,
     ) -> Self {
+        let page_size = statement.get_validated_page_size();
+        let keyspace = statement.get_keyspace().map(ToOwned::to_owned);
+        #[cfg(feature = "metrics")]
+        let table_label = metadata
+            .col_specs
+            .first()
+            .map(|spec| crate::observability::metrics::table_label(spec.table_spec()));
         Self {
             id,
             shared: Arc::new(PreparedStatementSharedData {
                 metadata,
                 result_metadata,
-                statement,
+                statement: statement.contents,
+                keyspace,
+                #[cfg(feature = "metrics")]
+                table_label,
             }),
             prepare_tracing_ids: Vec::new(),
             page_size,
-            config,
+            config: statement.config,
             partitioner_name: Default::default(),
             is_confirmed_lwt: is_lwt,
         }
@@ -161,6 +177,12 @@ impl PreparedStatement {
         &self.shared.statement
     }
 
+    /// Retrieves the keyspace this statement was prepared in, if it was set
+    /// on the statement with [Statement::set_keyspace].
+    pub fn get_keyspace(&self) -> Option<&str> {
+        self.shared.keyspace.as_deref()
+    }
+
     /// Sets the page size for this CQL query.
     ///
     /// Panics if given number is nonpositive.
@@ -180,6 +202,40 @@ impl PreparedStatement {
         self.page_size.inner()
     }
 
+    /// Sets how many pages a [QueryPager](crate::client::pager::QueryPager) executing
+    /// this statement fetches ahead of the page being consumed.
+    ///
+    /// With zero, the next page is only requested once the current one has been consumed,
+    /// so at most one page is held in memory, at the cost of waiting for every page.
+    /// Higher values let the consumer proceed without stalls when fetching a page
+    /// takes longer than processing it. Defaults to 2.
+    pub fn set_prefetch_pages(&mut self, pages: usize) {
+        self.config.pager_prefetch.pages = pages;
+    }
+
+    /// Returns how many pages are fetched ahead of the page being consumed
+    /// when paging this statement.
+    pub fn get_prefetch_pages(&self) -> usize {
+        self.config.pager_prefetch.pages
+    }
+
+    /// Bounds the total size of pages (in bytes of their serialized rows) which
+    /// a [QueryPager](crate::client::pager::QueryPager) executing this statement
+    /// has fetched ahead, but which have not been consumed yet.
+    ///
+    /// Once the bound is reached, no more pages are prefetched until the consumer catches up.
+    /// The bound is checked before fetching a page, so it may be exceeded by at most one page.
+    /// The page the consumer waits for is always fetched.
+    /// Defaults to `None`, which bounds only the number of pages (see [Self::set_prefetch_pages]).
+    pub fn set_max_prefetched_bytes(&mut self, max_bytes: Option<NonZeroUsize>) {
+        self.config.pager_prefetch.max_bytes = max_bytes;
+    }
+
+    /// Returns the bound of the total size of prefetched pages, if set.
+    pub fn get_max_prefetched_bytes(&self) -> Option<NonZeroUsize> {
+        self.config.pager_prefetch.max_bytes
+    }
+
     /// Gets tracing ids of queries used to prepare this statement
     pub fn get_prepare_tracing_ids(&self) -> &[Uuid] {
         &self.prepare_tracing_ids
@@ -217,7 +273,15 @@ impl PreparedStatement {
         bound_values: &impl SerializeRow,
     ) -> Result<Bytes, PartitionKeyError> {
         let serialized = self.serialize_values(bound_values)?;
-        let partition_key = self.extract_partition_key(&serialized)?;
+        self.compute_partition_key_untyped(&serialized)
+    }
+
+    // A version of compute_partition_key which skips serialization and uses SerializedValues directly.
+    pub(crate) fn compute_partition_key_untyped(
+        &self,
+        serialized: &SerializedValues,
+    ) -> Result<Bytes, PartitionKeyError> {
+        let partition_key = self.extract_partition_key(serialized)?;
         let mut buf = BytesMut::new();
         let mut writer = |chunk: &[u8]| buf.extend_from_slice(chunk);
 
@@ -283,6 +347,12 @@ impl PreparedStatement {
             .map(|spec| spec.table_spec())
     }
 
+    /// Returns the `keyspace.table` label of the table this statement is operating on.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn get_table_label(&self) -> Option<&str> {
+        self.shared.table_label.as_deref()
+    }
+
     /// Returns the name of the keyspace this statement is operating on.
     pub fn get_keyspace_name(&self) -> Option<&str> {
         self.get_prepared_metadata()
@@ -362,6 +432,18 @@ impl PreparedStatement {
         self.config.tracing
     }
 
+    /// Enable or disable skipping compression of requests sent for this statement.
+    /// If enabled, the requests are sent uncompressed even if compression
+    /// is enabled for the session, which saves CPU and latency for tiny requests.
+    pub fn set_skip_compression(&mut self, skip_compression: bool) {
+        self.config.skip_compression = skip_compression;
+    }
+
+    /// Gets whether compression of requests is skipped for this statement
+    pub fn get_skip_compression(&self) -> bool {
+        self.config.skip_compression
+    }
+
     /// Make use of cached metadata to decode results
     /// of the statement's execution.
     ///
@@ -396,6 +478,46 @@ impl PreparedStatement {
         self.config.timestamp
     }
 
+    /// Attaches an application-defined label to this statement, replacing the previous
+    /// value of the label with the same key.
+    ///
+    /// Labels are recorded in the request's tracing span and (with the `metrics` feature)
+    /// in per-label metrics, so that observability signals can be sliced by application-defined
+    /// dimensions, like endpoint or tenant. Metrics of prepared statements are also sliced
+    /// by their table, under a derived label with the `table` key, unless it is set explicitly.
+    pub fn set_label(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.config.labels.set(key.into(), value.into());
+    }
+
+    /// Removes the label with the given key from this statement, returning its value.
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.config.labels.remove(key)
+    }
+
+    /// Gets the value of the label with the given key.
+    pub fn get_label(&self, key: &str) -> Option<&str> {
+        self.config.labels.get(key)
+    }
+
+    /// Iterates over the labels attached to this statement, ordered by key.
+    pub fn get_labels(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.config.labels.iter()
+    }
+
+    /// Sets the custom payload sent along with the requests for this statement.
+    ///
+    /// The custom payload is a map of arbitrary key-value pairs, which is not interpreted
+    /// by the driver, but may be consumed by server-side plugins, e.g. a custom query handler.
+    /// If None, no custom payload is sent.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload.map(Arc::new);
+    }
+
+    /// Gets the custom payload sent along with the requests for this statement.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_deref()
+    }
+
     /// Sets the client-side timeout for this statement.
     /// If not None, the driver will stop waiting for the request
     /// to finish after `timeout` passed.
@@ -409,6 +531,33 @@ impl PreparedStatement {
         self.config.request_timeout
     }
 
+    /// Sets the deadline for executing this statement.
+    /// If not None, the driver stops executing requests with this statement once
+    /// the deadline passes, returning [ExecutionError::DeadlineExceeded](crate::errors::ExecutionError::DeadlineExceeded).
+    /// As opposed to the request timeout, which is measured anew for each request,
+    /// the deadline bounds all requests executed with the statement, including
+    /// their retries and speculative executions, and all pages of paged requests.
+    /// If the request timeout is set as well, whichever comes first applies.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.config.deadline = deadline
+    }
+
+    /// Gets the deadline for executing this statement.
+    pub fn get_deadline(&self) -> Option<Instant> {
+        self.config.deadline
+    }
+
+    /// Sets the token which allows cancelling requests executed with this statement.
+    /// See [CancellationToken] for details.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.config.cancellation_token = token
+    }
+
+    /// Gets the cancellation token associated with this statement.
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.config.cancellation_token.as_ref()
+    }
+
     /// Sets the name of the partitioner used for this statement.
     pub(crate) fn set_partitioner_name(&mut self, partitioner_name: PartitionerName) {
         self.partitioner_name = partitioner_name;
@@ -434,6 +583,11 @@ impl PreparedStatement {
         &self.shared.result_metadata
     }
 
+    /// Access the id of the result metadata, returned by the server since protocol v5.
+    pub(crate) fn get_result_metadata_id(&self) -> Option<&Bytes> {
+        self.shared.result_metadata.id()
+    }
+
     /// Access column specifications of the result set returned after the execution of this statement
     pub fn get_result_set_col_specs(&self) -> ColumnSpecs<'_, 'static> {
         ColumnSpecs::new(self.shared.result_metadata.col_specs())
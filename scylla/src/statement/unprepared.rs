@@ -2,12 +2,22 @@
 
 use super::{PageSize, StatementConfig};
 use crate::client::execution_profile::ExecutionProfileHandle;
+use crate::client::CancellationToken;
+use crate::cluster::ClusterState;
+use crate::errors::ClusterStateTokenError;
+use crate::frame::response::result::TableSpec;
 use crate::frame::types::{Consistency, SerialConsistency};
 use crate::observability::history::HistoryListener;
 use crate::policies::load_balancing::LoadBalancingPolicy;
 use crate::policies::retry::RetryPolicy;
+use crate::routing::Token;
+use crate::serialize::row::SerializeRow;
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
 
 /// **Unprepared** CQL statement.
 ///
@@ -19,6 +29,8 @@ pub struct Statement {
     /// The CQL statement text.
     pub contents: String,
     page_size: PageSize,
+    keyspace: Option<String>,
+    routing: Option<StatementRouting>,
 }
 
 impl Statement {
@@ -27,6 +39,8 @@ impl Statement {
         Self {
             contents: query_text.into(),
             page_size: PageSize::default(),
+            keyspace: None,
+            routing: None,
             config: Default::default(),
         }
     }
@@ -58,6 +72,40 @@ impl Statement {
         self.page_size.inner()
     }
 
+    /// Sets how many pages a [QueryPager](crate::client::pager::QueryPager) executing
+    /// this statement fetches ahead of the page being consumed.
+    ///
+    /// With zero, the next page is only requested once the current one has been consumed,
+    /// so at most one page is held in memory, at the cost of waiting for every page.
+    /// Higher values let the consumer proceed without stalls when fetching a page
+    /// takes longer than processing it. Defaults to 2.
+    pub fn set_prefetch_pages(&mut self, pages: usize) {
+        self.config.pager_prefetch.pages = pages;
+    }
+
+    /// Returns how many pages are fetched ahead of the page being consumed
+    /// when paging this statement.
+    pub fn get_prefetch_pages(&self) -> usize {
+        self.config.pager_prefetch.pages
+    }
+
+    /// Bounds the total size of pages (in bytes of their serialized rows) which
+    /// a [QueryPager](crate::client::pager::QueryPager) executing this statement
+    /// has fetched ahead, but which have not been consumed yet.
+    ///
+    /// Once the bound is reached, no more pages are prefetched until the consumer catches up.
+    /// The bound is checked before fetching a page, so it may be exceeded by at most one page.
+    /// The page the consumer waits for is always fetched.
+    /// Defaults to `None`, which bounds only the number of pages (see [Self::set_prefetch_pages]).
+    pub fn set_max_prefetched_bytes(&mut self, max_bytes: Option<NonZeroUsize>) {
+        self.config.pager_prefetch.max_bytes = max_bytes;
+    }
+
+    /// Returns the bound of the total size of prefetched pages, if set.
+    pub fn get_max_prefetched_bytes(&self) -> Option<NonZeroUsize> {
+        self.config.pager_prefetch.max_bytes
+    }
+
     /// Sets the consistency to be used when executing this statement.
     pub fn set_consistency(&mut self, c: Consistency) {
         self.config.consistency = Some(c);
@@ -121,6 +169,18 @@ impl Statement {
         self.config.tracing
     }
 
+    /// Enable or disable skipping compression of requests sent for this statement.
+    /// If enabled, the requests are sent uncompressed even if compression
+    /// is enabled for the session, which saves CPU and latency for tiny requests.
+    pub fn set_skip_compression(&mut self, skip_compression: bool) {
+        self.config.skip_compression = skip_compression;
+    }
+
+    /// Gets whether compression of requests is skipped for this statement
+    pub fn get_skip_compression(&self) -> bool {
+        self.config.skip_compression
+    }
+
     /// Sets the default timestamp for this statement in microseconds.
     /// If not None, it will replace the server side assigned timestamp as default timestamp
     /// If a statement contains a `USING TIMESTAMP` clause, calling this method won't change
@@ -134,6 +194,131 @@ impl Statement {
         self.config.timestamp
     }
 
+    /// Sets the keyspace in which this statement is executed (or prepared),
+    /// overriding the keyspace set on the session with `USE`.
+    ///
+    /// This makes it possible to run statements against different keyspaces
+    /// without qualifying table names and without issuing `USE` on the connections.
+    /// The keyspace name is sent as is, so it is case-sensitive and must not be quoted.
+    ///
+    /// This requires protocol v5 (see [GenericSessionBuilder::protocol_negotiation](crate::client::session_builder::GenericSessionBuilder::protocol_negotiation)),
+    /// which is not supported by ScyllaDB. With an older protocol version,
+    /// executing or preparing the statement fails with a serialization error.
+    pub fn set_keyspace(&mut self, keyspace: impl Into<String>) {
+        self.keyspace = Some(keyspace.into());
+    }
+
+    /// Unsets the keyspace set on this statement, so that it is executed
+    /// in the keyspace set on the session (if any).
+    pub fn unset_keyspace(&mut self) {
+        self.keyspace = None;
+    }
+
+    /// Gets the keyspace in which this statement is executed, if set with [Statement::set_keyspace].
+    pub fn get_keyspace(&self) -> Option<&str> {
+        self.keyspace.as_deref()
+    }
+
+    /// Makes the statement routed as if it operated on the partition with given token
+    /// of the given table, so that it is sent to the replicas owning that partition.
+    ///
+    /// Unprepared statements are not token-aware routed by default, because the driver
+    /// does not know which table and partition they operate on. This lets statements which
+    /// cannot be prepared, or which are executed too rarely to be worth preparing,
+    /// take advantage of token-aware routing nonetheless. The keyspace and table are needed
+    /// to determine the replicas of the token (replication strategy, tablets).
+    /// The keyspace and table names are case-sensitive and must not be quoted.
+    ///
+    /// The routing information is only a hint for the load balancing policy and is not
+    /// sent to the cluster - if it does not match the statement, the statement is still
+    /// executed correctly, but it is likely sent to a node which does not own the data.
+    pub fn set_routing_token(
+        &mut self,
+        keyspace: impl Into<String>,
+        table: impl Into<String>,
+        token: Token,
+    ) {
+        self.routing = Some(StatementRouting::new(
+            TableSpec::owned(keyspace.into(), table.into()),
+            RoutingKey::Token(token),
+        ));
+    }
+
+    /// Makes the statement routed to the replicas owning the partition with given
+    /// partition key of the given table. See [Statement::set_routing_token].
+    ///
+    /// `partition_key` contains the values of all partition key columns, in the same form
+    /// as for [ClusterState::compute_token]. The token is computed upon each execution,
+    /// using the table's metadata fetched by the driver. If the metadata of the table
+    /// is unknown (e.g. because fetching schema metadata is disabled), the statement
+    /// is executed without token-aware routing.
+    pub fn set_routing_partition_key(
+        &mut self,
+        keyspace: impl Into<String>,
+        table: impl Into<String>,
+        partition_key: impl SerializeRow + Send + Sync + 'static,
+    ) {
+        self.routing = Some(StatementRouting::new(
+            TableSpec::owned(keyspace.into(), table.into()),
+            RoutingKey::PartitionKey(Arc::new(partition_key)),
+        ));
+    }
+
+    /// Unsets the routing information set with [Statement::set_routing_token]
+    /// or [Statement::set_routing_partition_key].
+    pub fn unset_routing(&mut self) {
+        self.routing = None;
+    }
+
+    /// Gets the table that this statement is routed for, if set with
+    /// [Statement::set_routing_token] or [Statement::set_routing_partition_key].
+    pub fn get_routing_table(&self) -> Option<&TableSpec<'static>> {
+        self.routing.as_ref().map(|routing| &routing.table)
+    }
+
+    pub(crate) fn get_routing(&self) -> Option<&StatementRouting> {
+        self.routing.as_ref()
+    }
+
+    /// Attaches an application-defined label to this statement, replacing the previous
+    /// value of the label with the same key.
+    ///
+    /// Labels are recorded in the request's tracing span and (with the `metrics` feature)
+    /// in per-label metrics, so that observability signals can be sliced by application-defined
+    /// dimensions, like endpoint or tenant.
+    pub fn set_label(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.config.labels.set(key.into(), value.into());
+    }
+
+    /// Removes the label with the given key from this statement, returning its value.
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.config.labels.remove(key)
+    }
+
+    /// Gets the value of the label with the given key.
+    pub fn get_label(&self, key: &str) -> Option<&str> {
+        self.config.labels.get(key)
+    }
+
+    /// Iterates over the labels attached to this statement, ordered by key.
+    pub fn get_labels(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.config.labels.iter()
+    }
+
+    /// Sets the custom payload sent along with the requests for this statement.
+    ///
+    /// The custom payload is a map of arbitrary key-value pairs, which is not interpreted
+    /// by the driver, but may be consumed by server-side plugins, e.g. a custom query handler.
+    /// If None, no custom payload is sent.
+    pub fn set_custom_payload(&mut self, custom_payload: Option<HashMap<String, Bytes>>) {
+        self.config.custom_payload = custom_payload.map(Arc::new);
+    }
+
+    /// Gets the custom payload sent along with the requests for this statement.
+    pub fn get_custom_payload(&self) -> Option<&HashMap<String, Bytes>> {
+        self.config.custom_payload.as_deref()
+    }
+
     /// Sets the client-side timeout for this statement.
     /// If not None, the driver will stop waiting for the request
     /// to finish after `timeout` passed.
@@ -147,6 +332,33 @@ impl Statement {
         self.config.request_timeout
     }
 
+    /// Sets the deadline for executing this statement.
+    /// If not None, the driver stops executing requests with this statement once
+    /// the deadline passes, returning [ExecutionError::DeadlineExceeded](crate::errors::ExecutionError::DeadlineExceeded).
+    /// As opposed to the request timeout, which is measured anew for each request,
+    /// the deadline bounds all requests executed with the statement, including
+    /// their retries and speculative executions, and all pages of paged requests.
+    /// If the request timeout is set as well, whichever comes first applies.
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.config.deadline = deadline
+    }
+
+    /// Gets the deadline for executing this statement.
+    pub fn get_deadline(&self) -> Option<Instant> {
+        self.config.deadline
+    }
+
+    /// Sets the token which allows cancelling requests executed with this statement.
+    /// See [CancellationToken] for details.
+    pub fn set_cancellation_token(&mut self, token: Option<CancellationToken>) {
+        self.config.cancellation_token = token
+    }
+
+    /// Gets the cancellation token associated with this statement.
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.config.cancellation_token.as_ref()
+    }
+
     /// Set the retry policy for this statement, overriding the one from execution profile if not None.
     #[inline]
     pub fn set_retry_policy(&mut self, retry_policy: Option<Arc<dyn RetryPolicy>>) {
@@ -215,3 +427,68 @@ impl<'a> From<&'a str> for Statement {
         Statement::new(s.to_owned())
     }
 }
+
+#[derive(Clone)]
+enum RoutingKey {
+    Token(Token),
+    PartitionKey(Arc<dyn SerializeRow + Send + Sync>),
+}
+
+/// Routing information set explicitly on an unprepared statement.
+#[derive(Clone)]
+pub(crate) struct StatementRouting {
+    table: TableSpec<'static>,
+    key: RoutingKey,
+    #[cfg(feature = "metrics")]
+    table_label: Arc<str>,
+}
+
+impl StatementRouting {
+    fn new(table: TableSpec<'static>, key: RoutingKey) -> Self {
+        Self {
+            #[cfg(feature = "metrics")]
+            table_label: crate::observability::metrics::table_label(&table).into(),
+            table,
+            key,
+        }
+    }
+
+    /// Returns the `keyspace.table` label of the table the statement is routed to.
+    #[cfg(feature = "metrics")]
+    pub(crate) fn table_label(&self) -> &str {
+        &self.table_label
+    }
+
+    pub(crate) fn table(&self) -> &TableSpec<'static> {
+        &self.table
+    }
+
+    /// Returns the token to route the statement with.
+    ///
+    /// Routing is only an optimisation, so if the token can't be computed,
+    /// the statement is executed without it rather than failed.
+    pub(crate) fn token(&self, cluster_state: &ClusterState) -> Option<Token> {
+        let partition_key = match &self.key {
+            RoutingKey::Token(token) => return Some(*token),
+            RoutingKey::PartitionKey(partition_key) => partition_key,
+        };
+        match cluster_state.compute_token(
+            self.table.ks_name(),
+            self.table.table_name(),
+            partition_key.as_ref(),
+        ) {
+            Ok(token) => Some(token),
+            Err(err @ ClusterStateTokenError::UnknownTable { .. }) => {
+                debug!("Routing the statement without a token: {}", err);
+                None
+            }
+            Err(err) => {
+                warn!(
+                    "Failed to compute the token of the statement's routing partition key, routing without it: {}",
+                    err
+                );
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,552 @@
+//! Mapping Rust structs to CQL tables.
+//!
+//! The [`Table`] trait describes a table: its name, its columns and its primary key.
+//! It is usually implemented with `#[derive(Table)]` (see [`Table`](crate::Table) derive macro),
+//! and allows generating the `CREATE TABLE` statement together with the basic
+//! statements operating on a single row: an insert, a select and a delete by primary key.
+//! [`PreparedTable`] prepares those statements and executes them on a [`Session`].
+//!
+//! # Example
+//! ```rust
+//! # extern crate scylla;
+//! # use scylla::client::session::Session;
+//! # use std::error::Error;
+//! # async fn check_only_compiles(session: &Session) -> Result<(), Box<dyn Error>> {
+//! use scylla::table::{PreparedTable, Table};
+//! use scylla::{DeserializeRow, SerializeRow, Table};
+//!
+//! #[derive(Table, SerializeRow, DeserializeRow)]
+//! #[table(keyspace = "ks")]
+//! struct Message {
+//!     #[table(partition_key)]
+//!     channel: String,
+//!     #[table(clustering_key)]
+//!     id: i64,
+//!     text: String,
+//!     tags: Vec<String>,
+//! }
+//!
+//! assert_eq!(
+//!     Message::create_table_statement().contents,
+//!     "CREATE TABLE IF NOT EXISTS ks.message \
+//!      (channel text, id bigint, text text, tags list<text>, PRIMARY KEY ((channel), id))"
+//! );
+//! session.query_unpaged(Message::create_table_statement(), &[]).await?;
+//!
+//! let messages = PreparedTable::<Message>::prepare(session).await?;
+//! let message = Message {
+//!     channel: "general".to_owned(),
+//!     id: 1,
+//!     text: "Hello!".to_owned(),
+//!     tags: vec!["greeting".to_owned()],
+//! };
+//! messages.insert(session, &message).await?;
+//!
+//! let fetched: Option<Message> = messages
+//!     .select_by_primary_key(session, ("general", 1_i64))
+//!     .await?
+//!     .into_rows_result()?
+//!     .maybe_first_row()?;
+//!
+//! messages.delete(session, &message).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::marker::PhantomData;
+use std::net::IpAddr;
+
+use bytes::Bytes;
+use scylla_cql::serialize::row::SerializeRow;
+use scylla_cql::value::{
+    Counter, CqlDate, CqlDecimal, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlVarint,
+};
+use uuid::Uuid;
+
+use crate::client::session::Session;
+use crate::errors::{ExecutionError, PrepareError};
+use crate::response::query_result::QueryResult;
+use crate::statement::prepared::PreparedStatement;
+use crate::statement::Statement;
+
+/// A Rust type which is stored in a CQL table, one value per row.
+///
+/// All table and column names are put into the statement text as they are given.
+/// Case-sensitive names have to be quoted, e.g. `"\"MyColumn\""`.
+pub trait Table {
+    /// Name of the keyspace the table belongs to. If `None`, the table name
+    /// is not qualified and the keyspace of the session is used.
+    const KEYSPACE: Option<&'static str>;
+
+    /// Name of the table.
+    const NAME: &'static str;
+
+    /// Names of all columns of the table.
+    const COLUMNS: &'static [&'static str];
+
+    /// Names of the partition key columns, in order. Must not be empty.
+    const PARTITION_KEY: &'static [&'static str];
+
+    /// Names of the clustering key columns, in order.
+    const CLUSTERING_KEY: &'static [&'static str];
+
+    /// Values of the primary key columns of a row, in the order of
+    /// [`PARTITION_KEY`](Table::PARTITION_KEY) followed by [`CLUSTERING_KEY`](Table::CLUSTERING_KEY).
+    type PrimaryKey<'a>: SerializeRow
+    where
+        Self: 'a;
+
+    /// CQL types of the columns, in the order of [`COLUMNS`](Table::COLUMNS).
+    fn column_cql_types() -> Vec<String>;
+
+    /// Returns the values of the primary key columns of this row.
+    fn primary_key(&self) -> Self::PrimaryKey<'_>;
+
+    /// Returns the table name, qualified with the keyspace name if it is known.
+    fn qualified_name() -> String {
+        match Self::KEYSPACE {
+            Some(keyspace) => format!("{}.{}", keyspace, Self::NAME),
+            None => Self::NAME.to_owned(),
+        }
+    }
+
+    /// Returns the `CREATE TABLE IF NOT EXISTS` statement creating this table.
+    fn create_table_statement() -> Statement {
+        let mut cql = format!("CREATE TABLE IF NOT EXISTS {} (", Self::qualified_name());
+        for (column, typ) in Self::COLUMNS.iter().zip(Self::column_cql_types()) {
+            cql.push_str(column);
+            cql.push(' ');
+            cql.push_str(&typ);
+            cql.push_str(", ");
+        }
+        cql.push_str("PRIMARY KEY ((");
+        cql.push_str(&Self::PARTITION_KEY.join(", "));
+        cql.push(')');
+        for column in Self::CLUSTERING_KEY {
+            cql.push_str(", ");
+            cql.push_str(column);
+        }
+        cql.push_str("))");
+        Statement::new(cql)
+    }
+
+    /// Returns the statement inserting a whole row. Its bind markers are named after the columns.
+    fn insert_statement() -> Statement {
+        let cql = format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            Self::qualified_name(),
+            Self::COLUMNS.join(", "),
+            vec!["?"; Self::COLUMNS.len()].join(", ")
+        );
+        Statement::new(cql)
+    }
+
+    /// Returns the statement selecting the row with the given primary key.
+    ///
+    /// The statement is marked as idempotent.
+    fn select_by_primary_key_statement() -> Statement {
+        let cql = format!(
+            "SELECT {} FROM {}{}",
+            Self::COLUMNS.join(", "),
+            Self::qualified_name(),
+            primary_key_relations::<Self>()
+        );
+        let mut statement = Statement::new(cql);
+        statement.set_is_idempotent(true);
+        statement
+    }
+
+    /// Returns the statement deleting the row with the given primary key.
+    fn delete_by_primary_key_statement() -> Statement {
+        let cql = format!(
+            "DELETE FROM {}{}",
+            Self::qualified_name(),
+            primary_key_relations::<Self>()
+        );
+        Statement::new(cql)
+    }
+}
+
+fn primary_key_relations<T: Table + ?Sized>() -> String {
+    let mut cql = String::new();
+    for (i, column) in T::PARTITION_KEY.iter().chain(T::CLUSTERING_KEY).enumerate() {
+        cql.push_str(if i == 0 { " WHERE " } else { " AND " });
+        cql.push_str(column);
+        cql.push_str(" = ?");
+    }
+    cql
+}
+
+/// Statements of a [`Table`], prepared on a [`Session`].
+pub struct PreparedTable<T> {
+    insert: PreparedStatement,
+    select_by_primary_key: PreparedStatement,
+    delete_by_primary_key: PreparedStatement,
+    _table: PhantomData<fn() -> T>,
+}
+
+impl<T: Table> PreparedTable<T> {
+    /// Prepares the insert, select and delete statements of the table.
+    pub async fn prepare(session: &Session) -> Result<Self, PrepareError> {
+        Ok(Self {
+            insert: session.prepare(T::insert_statement()).await?,
+            select_by_primary_key: session
+                .prepare(T::select_by_primary_key_statement())
+                .await?,
+            delete_by_primary_key: session
+                .prepare(T::delete_by_primary_key_statement())
+                .await?,
+            _table: PhantomData,
+        })
+    }
+
+    /// Returns the prepared [`Table::insert_statement`].
+    pub fn insert_statement(&self) -> &PreparedStatement {
+        &self.insert
+    }
+
+    /// Returns the prepared [`Table::select_by_primary_key_statement`].
+    pub fn select_by_primary_key_statement(&self) -> &PreparedStatement {
+        &self.select_by_primary_key
+    }
+
+    /// Returns the prepared [`Table::delete_by_primary_key_statement`].
+    pub fn delete_by_primary_key_statement(&self) -> &PreparedStatement {
+        &self.delete_by_primary_key
+    }
+
+    /// Inserts the row, overwriting the existing one with the same primary key.
+    pub async fn insert(&self, session: &Session, row: &T) -> Result<QueryResult, ExecutionError>
+    where
+        T: SerializeRow,
+    {
+        session.execute_unpaged(&self.insert, row).await
+    }
+
+    /// Selects the row with the given primary key.
+    ///
+    /// The key contains values of [`Table::PARTITION_KEY`] columns followed by values
+    /// of [`Table::CLUSTERING_KEY`] columns, e.g. as a tuple.
+    /// The result contains at most one row.
+    pub async fn select_by_primary_key(
+        &self,
+        session: &Session,
+        key: impl SerializeRow,
+    ) -> Result<QueryResult, ExecutionError> {
+        session
+            .execute_unpaged(&self.select_by_primary_key, key)
+            .await
+    }
+
+    /// Deletes the row with the given primary key.
+    ///
+    /// The key has the same format as in [`PreparedTable::select_by_primary_key`].
+    pub async fn delete_by_primary_key(
+        &self,
+        session: &Session,
+        key: impl SerializeRow,
+    ) -> Result<QueryResult, ExecutionError> {
+        session
+            .execute_unpaged(&self.delete_by_primary_key, key)
+            .await
+    }
+
+    /// Deletes the row with the same primary key as the given one.
+    pub async fn delete(&self, session: &Session, row: &T) -> Result<QueryResult, ExecutionError> {
+        self.delete_by_primary_key(session, row.primary_key()).await
+    }
+}
+
+impl<T> Clone for PreparedTable<T> {
+    fn clone(&self) -> Self {
+        Self {
+            insert: self.insert.clone(),
+            select_by_primary_key: self.select_by_primary_key.clone(),
+            delete_by_primary_key: self.delete_by_primary_key.clone(),
+            _table: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for PreparedTable<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PreparedTable")
+            .field("table", &std::any::type_name::<T>())
+            .field("insert", &self.insert)
+            .field("select_by_primary_key", &self.select_by_primary_key)
+            .field("delete_by_primary_key", &self.delete_by_primary_key)
+            .finish()
+    }
+}
+
+/// A Rust type with a corresponding CQL type, used as the type of a table column
+/// by `#[derive(Table)]`.
+///
+/// Collections nested in other collections are frozen, e.g. `Vec<Vec<i32>>`
+/// is mapped to `list<frozen<list<int>>>`.
+/// Types without an implementation (e.g. user defined types) can be given
+/// the CQL type explicitly, with the `#[table(cql_type = "...")]` field attribute.
+pub trait CqlTypeName {
+    /// Returns the name of the CQL type, e.g. `int` or `map<text, bigint>`.
+    fn cql_type_name() -> String;
+
+    /// Returns the name of the CQL type when nested in a collection.
+    /// Non-frozen collections must override it to return a frozen type.
+    fn nested_cql_type_name() -> String {
+        Self::cql_type_name()
+    }
+}
+
+macro_rules! impl_cql_type_name {
+    ($($typ:ty => $name:literal),* $(,)?) => {
+        $(
+            impl CqlTypeName for $typ {
+                fn cql_type_name() -> String {
+                    $name.to_owned()
+                }
+            }
+        )*
+    };
+}
+
+impl_cql_type_name! {
+    bool => "boolean",
+    i8 => "tinyint",
+    i16 => "smallint",
+    i32 => "int",
+    i64 => "bigint",
+    f32 => "float",
+    f64 => "double",
+    str => "text",
+    String => "text",
+    Vec<u8> => "blob",
+    Bytes => "blob",
+    Uuid => "uuid",
+    CqlTimeuuid => "timeuuid",
+    IpAddr => "inet",
+    CqlDate => "date",
+    CqlTime => "time",
+    CqlTimestamp => "timestamp",
+    std::time::SystemTime => "timestamp",
+    CqlDuration => "duration",
+    CqlDecimal => "decimal",
+    CqlVarint => "varint",
+    Counter => "counter",
+}
+
+#[cfg(feature = "chrono-04")]
+impl_cql_type_name! {
+    chrono::NaiveDate => "date",
+    chrono::NaiveTime => "time",
+}
+
+#[cfg(feature = "chrono-04")]
+impl<Tz: chrono::TimeZone> CqlTypeName for chrono::DateTime<Tz> {
+    fn cql_type_name() -> String {
+        "timestamp".to_owned()
+    }
+}
+
+impl<T: CqlTypeName + ?Sized> CqlTypeName for &T {
+    fn cql_type_name() -> String {
+        T::cql_type_name()
+    }
+
+    fn nested_cql_type_name() -> String {
+        T::nested_cql_type_name()
+    }
+}
+
+impl<T: CqlTypeName + ?Sized> CqlTypeName for Box<T> {
+    fn cql_type_name() -> String {
+        T::cql_type_name()
+    }
+
+    fn nested_cql_type_name() -> String {
+        T::nested_cql_type_name()
+    }
+}
+
+impl<T: CqlTypeName> CqlTypeName for Option<T> {
+    fn cql_type_name() -> String {
+        T::cql_type_name()
+    }
+
+    fn nested_cql_type_name() -> String {
+        T::nested_cql_type_name()
+    }
+}
+
+macro_rules! impl_cql_type_name_for_collection {
+    ($name:literal, $typ:ty, [$($params:tt)*], $($elem:ident),+) => {
+        impl<$($params)*> CqlTypeName for $typ {
+            fn cql_type_name() -> String {
+                format!(
+                    concat!($name, "<{}>"),
+                    [$($elem::nested_cql_type_name()),+].join(", ")
+                )
+            }
+
+            fn nested_cql_type_name() -> String {
+                format!("frozen<{}>", Self::cql_type_name())
+            }
+        }
+    };
+}
+
+impl_cql_type_name_for_collection!("list", Vec<T>, [T: CqlTypeName], T);
+impl_cql_type_name_for_collection!("set", BTreeSet<T>, [T: CqlTypeName], T);
+impl_cql_type_name_for_collection!("set", HashSet<T, S>, [T: CqlTypeName, S], T);
+impl_cql_type_name_for_collection!("map", BTreeMap<K, V>, [K: CqlTypeName, V: CqlTypeName], K, V);
+impl_cql_type_name_for_collection!(
+    "map",
+    HashMap<K, V, S>,
+    [K: CqlTypeName, V: CqlTypeName, S],
+    K,
+    V
+);
+
+macro_rules! impl_cql_type_name_for_tuple {
+    ($($typs:ident),*) => {
+        // Tuples are always frozen.
+        impl<$($typs: CqlTypeName),*> CqlTypeName for ($($typs,)*) {
+            fn cql_type_name() -> String {
+                format!("tuple<{}>", [$($typs::nested_cql_type_name()),*].join(", "))
+            }
+        }
+    };
+}
+
+impl_cql_type_name_for_tuple!(T0);
+impl_cql_type_name_for_tuple!(T0, T1);
+impl_cql_type_name_for_tuple!(T0, T1, T2);
+impl_cql_type_name_for_tuple!(T0, T1, T2, T3);
+impl_cql_type_name_for_tuple!(T0, T1, T2, T3, T4);
+impl_cql_type_name_for_tuple!(T0, T1, T2, T3, T4, T5);
+impl_cql_type_name_for_tuple!(T0, T1, T2, T3, T4, T5, T6);
+impl_cql_type_name_for_tuple!(T0, T1, T2, T3, T4, T5, T6, T7);
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashSet};
+
+    use bytes::Bytes;
+
+    use super::{CqlTypeName, Table};
+
+    #[derive(crate::Table, crate::SerializeRow)]
+    #[table(crate = crate, keyspace = "ks")]
+    #[scylla(crate = crate)]
+    struct UserEvent {
+        #[table(partition_key)]
+        user_id: i64,
+        #[table(partition_key)]
+        day: i32,
+        #[table(clustering_key)]
+        #[scylla(rename = "ts")]
+        timestamp: i64,
+        payload: Option<Vec<u8>>,
+        #[scylla(skip)]
+        _ignored: (),
+        #[table(cql_type = "frozen<my_udt>")]
+        details: Vec<u8>,
+    }
+
+    #[derive(crate::Table)]
+    #[table(crate = crate)]
+    struct HTTPRequestLog {
+        #[table(partition_key)]
+        id: i32,
+    }
+
+    #[derive(crate::Table, crate::SerializeRow)]
+    #[table(crate = crate)]
+    #[scylla(crate = crate, rename_all = "camelCase")]
+    struct RenamedColumns {
+        #[table(partition_key)]
+        #[scylla(rename = "id")]
+        user_id: i64,
+        user_name: String,
+    }
+
+    #[test]
+    fn cql_type_names() {
+        assert_eq!(i32::cql_type_name(), "int");
+        assert_eq!(<&str>::cql_type_name(), "text");
+        assert_eq!(<Option<String>>::cql_type_name(), "text");
+        assert_eq!(<Vec<u8>>::cql_type_name(), "blob");
+        assert_eq!(<Vec<Bytes>>::cql_type_name(), "list<blob>");
+        assert_eq!(<HashSet<i64>>::cql_type_name(), "set<bigint>");
+        assert_eq!(
+            <BTreeMap<String, Vec<Vec<i32>>>>::cql_type_name(),
+            "map<text, frozen<list<frozen<list<int>>>>>"
+        );
+        assert_eq!(
+            <(i32, Vec<String>)>::cql_type_name(),
+            "tuple<int, frozen<list<text>>>"
+        );
+        assert_eq!(
+            <Vec<(i32, bool)>>::cql_type_name(),
+            "list<tuple<int, boolean>>"
+        );
+    }
+
+    #[test]
+    fn derived_table() {
+        assert_eq!(UserEvent::KEYSPACE, Some("ks"));
+        assert_eq!(UserEvent::NAME, "user_event");
+        assert_eq!(UserEvent::qualified_name(), "ks.user_event");
+        assert_eq!(
+            UserEvent::COLUMNS,
+            ["user_id", "day", "ts", "payload", "details"]
+        );
+        assert_eq!(UserEvent::PARTITION_KEY, ["user_id", "day"]);
+        assert_eq!(UserEvent::CLUSTERING_KEY, ["ts"]);
+
+        assert_eq!(HTTPRequestLog::NAME, "http_request_log");
+        assert_eq!(HTTPRequestLog::qualified_name(), "http_request_log");
+        assert_eq!(HTTPRequestLog::CLUSTERING_KEY, [] as [&str; 0]);
+
+        assert_eq!(RenamedColumns::COLUMNS, ["id", "userName"]);
+
+        let event = UserEvent {
+            user_id: 1,
+            day: 2,
+            timestamp: 3,
+            payload: None,
+            _ignored: (),
+            details: Vec::new(),
+        };
+        assert_eq!(event.primary_key(), (&1, &2, &3));
+    }
+
+    #[test]
+    fn table_statements() {
+        assert_eq!(
+            UserEvent::create_table_statement().contents,
+            "CREATE TABLE IF NOT EXISTS ks.user_event (user_id bigint, day int, ts bigint, \
+             payload blob, details frozen<my_udt>, PRIMARY KEY ((user_id, day), ts))"
+        );
+        assert_eq!(
+            HTTPRequestLog::create_table_statement().contents,
+            "CREATE TABLE IF NOT EXISTS http_request_log (id int, PRIMARY KEY ((id)))"
+        );
+        assert_eq!(
+            UserEvent::insert_statement().contents,
+            "INSERT INTO ks.user_event (user_id, day, ts, payload, details) VALUES (?, ?, ?, ?, ?)"
+        );
+        let select = UserEvent::select_by_primary_key_statement();
+        assert_eq!(
+            select.contents,
+            "SELECT user_id, day, ts, payload, details FROM ks.user_event \
+             WHERE user_id = ? AND day = ? AND ts = ?"
+        );
+        assert!(select.get_is_idempotent());
+        assert_eq!(
+            UserEvent::delete_by_primary_key_statement().contents,
+            "DELETE FROM ks.user_event WHERE user_id = ? AND day = ? AND ts = ?"
+        );
+    }
+}
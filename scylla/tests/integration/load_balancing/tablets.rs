@@ -304,6 +304,19 @@ async fn test_default_policy_is_tablet_aware() {
                 assert_eq!(feedbacks, 0);
             }
 
+            // The tablets used for routing are exposed in the cluster state.
+            let cluster_state = session.get_cluster_state();
+            assert!(cluster_state.is_tablet_table(&ks, "t"));
+            assert!(cluster_state
+                .get_tablet_tables()
+                .contains(&(ks.as_str(), "t")));
+            let known_tablets = cluster_state.get_tablets(&ks, "t").unwrap();
+            assert_eq!(known_tablets.len(), TABLET_COUNT);
+            for ((range, replicas), tablet) in known_tablets.iter().zip(&tablets) {
+                assert_eq!(range.end(), tablet.last_token);
+                assert_eq!(replicas.len(), tablet.replicas.len());
+            }
+
             running_proxy
         },
     )
@@ -1,3 +1,8 @@
+use std::sync::Arc;
+
+use scylla::cluster::Node;
+use scylla::routing::{Shard, Token};
+
 use crate::utils::{
     create_new_session_builder, scylla_supports_tablets, setup_tracing, unique_keyspace_name,
     PerformDDL as _,
@@ -63,3 +68,46 @@ async fn test_token_awareness() {
         assert_eq!(tracing_info.nodes().len(), 1);
     }
 }
+
+#[tokio::test]
+async fn test_token_ranges_cover_the_ring() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    // Tablets are learned lazily, so only the token ring is guaranteed to be known.
+    let mut create_ks = format!(
+        "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 2}}"
+    );
+    if scylla_supports_tablets(&session).await {
+        create_ks += " AND TABLETS = {'enabled': false}"
+    }
+
+    session.ddl(create_ks).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a text primary key)"
+        ))
+        .await
+        .unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    let ranges = cluster_state.get_token_ranges(&ks, "t");
+
+    assert_eq!(ranges.first().unwrap().0.start(), i64::MIN);
+    assert_eq!(ranges.last().unwrap().0.end(), i64::MAX);
+    for pair in ranges.windows(2) {
+        assert_eq!(pair[0].0.end(), pair[1].0.start());
+    }
+    for (range, replicas) in &ranges {
+        let endpoints = cluster_state.get_token_endpoints(&ks, "t", Token::new(range.start() + 1));
+        let host_ids = |replicas: &[(Arc<Node>, Shard)]| {
+            replicas
+                .iter()
+                .map(|(node, shard)| (node.host_id, *shard))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(host_ids(replicas), host_ids(&endpoints));
+    }
+}
@@ -1,2 +1,3 @@
 mod complex_pk;
 mod hygiene;
+mod table;
@@ -0,0 +1,67 @@
+use scylla::table::{PreparedTable, Table};
+use scylla::{DeserializeRow, SerializeRow};
+
+use crate::utils::{
+    create_new_session_builder, setup_tracing, unique_keyspace_name, PerformDDL as _,
+};
+
+#[tokio::test]
+async fn test_macros_table() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session.use_keyspace(ks, true).await.unwrap();
+
+    #[derive(scylla::Table, SerializeRow, DeserializeRow, PartialEq, Debug, Clone)]
+    struct SensorReading {
+        #[table(partition_key)]
+        sensor: String,
+        #[table(partition_key)]
+        day: i32,
+        #[table(clustering_key)]
+        #[scylla(rename = "ts")]
+        timestamp: i64,
+        value: Option<f64>,
+        labels: Vec<String>,
+    }
+
+    session
+        .ddl(SensorReading::create_table_statement())
+        .await
+        .unwrap();
+    let table = PreparedTable::<SensorReading>::prepare(&session)
+        .await
+        .unwrap();
+
+    let reading = SensorReading {
+        sensor: "thermometer".into(),
+        day: 7,
+        timestamp: 123,
+        value: Some(21.5),
+        labels: vec!["kitchen".into()],
+    };
+    table.insert(&session, &reading).await.unwrap();
+
+    let fetched: Option<SensorReading> = table
+        .select_by_primary_key(&session, reading.primary_key())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .maybe_first_row()
+        .unwrap();
+    assert_eq!(fetched, Some(reading.clone()));
+
+    table.delete(&session, &reading).await.unwrap();
+    let fetched: Option<SensorReading> = table
+        .select_by_primary_key(&session, ("thermometer", 7_i32, 123_i64))
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .maybe_first_row()
+        .unwrap();
+    assert_eq!(fetched, None);
+}
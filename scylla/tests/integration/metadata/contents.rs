@@ -149,9 +149,8 @@ async fn test_schema_types_in_metadata() {
         .unwrap();
 
     session.await_schema_agreement().await.unwrap();
-    session.refresh_metadata().await.unwrap();
-
-    let cluster_state = session.get_cluster_state();
+    // The refreshed state is returned once it is installed.
+    let cluster_state = session.refresh_metadata().await.unwrap();
     let tables = &cluster_state.get_keyspace(&ks).unwrap().tables;
 
     assert_eq!(
@@ -436,6 +435,47 @@ async fn test_table_partitioner_in_metadata() {
         cdc_table.partitioner.as_ref().unwrap(),
         "com.scylladb.dht.CDCPartitioner"
     );
+    assert_eq!(
+        table.options.cdc.as_ref().unwrap()["enabled"],
+        "true".to_string()
+    );
+}
+
+#[tokio::test]
+async fn test_table_options_in_metadata() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!("CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"))
+        .await
+        .unwrap();
+    session.use_keyspace(ks.clone(), false).await.unwrap();
+
+    session
+        .ddl(
+            "CREATE TABLE t (pk int PRIMARY KEY, v int) WITH gc_grace_seconds = 3600 \
+            AND default_time_to_live = 60 \
+            AND compaction = {'class': 'LeveledCompactionStrategy'} \
+            AND caching = {'keys': 'ALL', 'rows_per_partition': 'NONE'}",
+        )
+        .await
+        .unwrap();
+
+    session.await_schema_agreement().await.unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    let options = &cluster_state.get_keyspace(&ks).unwrap().tables["t"].options;
+
+    assert_eq!(options.gc_grace_seconds, Some(3600));
+    assert_eq!(options.default_time_to_live, Some(60));
+    // Cassandra reports the fully qualified name of the class.
+    assert!(options.compaction["class"].ends_with("LeveledCompactionStrategy"));
+    assert_eq!(options.caching["keys"], "ALL");
+    assert_eq!(options.caching["rows_per_partition"], "NONE");
+    assert!(!options.compression.is_empty());
 }
 
 #[tokio::test]
@@ -493,7 +533,89 @@ async fn test_views_in_schema_info() {
     assert_eq!(
         views_base_table,
         std::collections::HashSet::from([&"t".to_string()])
-    )
+    );
+
+    let mv1 = &keyspace_meta.views["mv1"];
+    assert!(mv1.include_all_columns);
+    assert!(mv1.where_clause.contains("v IS NOT NULL"));
+    assert_eq!(mv1.view_metadata.partition_key, vec!["v".to_string()]);
+    assert_eq!(mv1.view_metadata.clustering_key, vec!["id".to_string()]);
+    assert!(!keyspace_meta.views["mv2"].include_all_columns);
+
+    let mut views_of_t = keyspace_meta
+        .views_of_table("t")
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    views_of_t.sort_unstable();
+    assert_eq!(views_of_t, ["mv1", "mv2"]);
+    assert_eq!(keyspace_meta.views_of_table("mv1").count(), 0);
+}
+
+#[tokio::test]
+async fn test_user_defined_functions_in_metadata() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!("CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"))
+        .await
+        .unwrap();
+    session.use_keyspace(ks.clone(), false).await.unwrap();
+
+    // User defined functions are disabled by default, both in Scylla and Cassandra.
+    if let Err(err) = session
+        .ddl("CREATE FUNCTION acc(state int, v int) RETURNS NULL ON NULL INPUT RETURNS int LANGUAGE lua AS 'return state + v'")
+        .await
+    {
+        println!("Skipping because the cluster doesn't support Lua UDFs: {err}");
+        return;
+    }
+    session
+        .ddl("CREATE FUNCTION acc(state int, v list<int>) RETURNS NULL ON NULL INPUT RETURNS int LANGUAGE lua AS 'return state + #v'")
+        .await
+        .unwrap();
+    session
+        .ddl("CREATE AGGREGATE my_sum(int) SFUNC acc STYPE int INITCOND 0")
+        .await
+        .unwrap();
+
+    session.await_schema_agreement().await.unwrap();
+    session.refresh_metadata().await.unwrap();
+
+    let cluster_state = session.get_cluster_state();
+    let keyspace = cluster_state.get_keyspace(&ks).unwrap();
+
+    assert_eq!(keyspace.functions.keys().collect::<Vec<_>>(), ["acc"]);
+    let overloads = &keyspace.functions["acc"];
+    assert_eq!(overloads.len(), 2);
+    for function in overloads {
+        assert_eq!(function.argument_names, ["state", "v"]);
+        assert_eq!(
+            function.argument_types[0],
+            ColumnType::Native(NativeType::Int)
+        );
+        assert_eq!(function.return_type, ColumnType::Native(NativeType::Int));
+        assert_eq!(function.language, "lua");
+        assert!(!function.called_on_null_input);
+    }
+    // Whether collection arguments are implicitly frozen differs between databases.
+    assert!(overloads.iter().any(|function| matches!(
+        &function.argument_types[1],
+        ColumnType::Collection {
+            typ: CollectionType::List(elem),
+            ..
+        } if **elem == ColumnType::Native(NativeType::Int)
+    )));
+
+    assert_eq!(keyspace.aggregates.keys().collect::<Vec<_>>(), ["my_sum"]);
+    let my_sum = &keyspace.aggregates["my_sum"][0];
+    assert_eq!(my_sum.argument_types, [ColumnType::Native(NativeType::Int)]);
+    assert_eq!(my_sum.return_type, ColumnType::Native(NativeType::Int));
+    assert_eq!(my_sum.state_type, ColumnType::Native(NativeType::Int));
+    assert_eq!(my_sum.state_function, "acc");
+    assert_eq!(my_sum.final_function, None);
+    assert_eq!(my_sum.initial_condition.as_deref(), Some("0"));
 }
 
 /// This test case indicates that we support enough CQL types to parse schema keyspace information.
@@ -518,3 +640,17 @@ async fn test_fetch_system_keyspace() {
         .unwrap()
         .for_each(|_| ());
 }
+
+#[tokio::test]
+async fn test_fetch_scylla_version() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    // Cassandra nodes report no version; ScyllaDB nodes always report one.
+    for node in session.get_cluster_state().get_nodes_info() {
+        let version = node.fetch_scylla_version().await.unwrap();
+        if let Some(version) = version {
+            assert!(!version.is_empty());
+        }
+    }
+}
@@ -14,7 +14,10 @@ async fn test_db_errors() {
 
     // SyntaxError on bad query
     assert!(matches!(
-        session.query_unpaged("gibberish", &[]).await,
+        session
+            .query_unpaged("gibberish", &[])
+            .await
+            .map_err(ExecutionError::into_inner),
         Err(ExecutionError::LastAttemptError(
             RequestAttemptError::DbError(DbError::SyntaxError, _)
         ))
@@ -24,10 +27,11 @@ async fn test_db_errors() {
     session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
 
     let create_keyspace_res = session.ddl(format!("CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await;
-    let keyspace_exists_error: DbError = match create_keyspace_res {
-        Err(ExecutionError::LastAttemptError(RequestAttemptError::DbError(e, _))) => e,
-        _ => panic!("Second CREATE KEYSPACE didn't return an error!"),
-    };
+    let keyspace_exists_error: DbError =
+        match create_keyspace_res.map_err(ExecutionError::into_inner) {
+            Err(ExecutionError::LastAttemptError(RequestAttemptError::DbError(e, _))) => e,
+            _ => panic!("Second CREATE KEYSPACE didn't return an error!"),
+        };
 
     assert_eq!(
         keyspace_exists_error,
@@ -48,7 +52,7 @@ async fn test_db_errors() {
     let create_table_res = session
         .ddl(format!("CREATE TABLE {ks}.tab (a text primary key)"))
         .await;
-    let create_tab_error: DbError = match create_table_res {
+    let create_tab_error: DbError = match create_table_res.map_err(ExecutionError::into_inner) {
         Err(ExecutionError::LastAttemptError(RequestAttemptError::DbError(e, _))) => e,
         _ => panic!("Second CREATE TABLE didn't return an error!"),
     };
@@ -99,7 +103,10 @@ async fn test_rate_limit_exceeded_exception() {
         }
     }
 
-    match maybe_err.expect("Rate limit error didn't occur") {
+    match maybe_err
+        .expect("Rate limit error didn't occur")
+        .into_inner()
+    {
         ExecutionError::LastAttemptError(RequestAttemptError::DbError(
             DbError::RateLimitReached { op_type, .. },
             _,
@@ -5,6 +5,7 @@ use crate::utils::{create_new_session_builder, find_local_ip_for_destination, se
 use assert_matches::assert_matches;
 use futures::FutureExt as _;
 use scylla::client::session_builder::SessionBuilder;
+use scylla::client::{PoolState, ShardPoolState};
 use scylla::errors::{ConnectionError, ConnectionPoolError, MetadataError, NewSessionError};
 use tokio::net::TcpListener;
 
@@ -122,3 +123,27 @@ async fn test_connection_failure() {
         Err(err) => println!("Connection error (it was expected): {err:?}"),
     }
 }
+
+#[tokio::test]
+async fn pool_status_of_connected_session() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let status = session.pool_status();
+    assert_eq!(
+        status.len(),
+        session.get_cluster_state().get_nodes_info().len()
+    );
+    for node_status in status {
+        let PoolState::Connected { shards } = node_status.state else {
+            panic!(
+                "Pool of node {} is not connected: {:?}",
+                node_status.node.address, node_status.state
+            );
+        };
+        assert!(!shards.is_empty());
+        assert!(shards
+            .iter()
+            .any(|shard| matches!(shard, ShardPoolState::Connected { .. })));
+    }
+}
@@ -141,3 +141,104 @@ async fn test_iter_methods_with_modification_statements() {
         .ok_or(())
         .unwrap_err(); // assert empty
 }
+
+#[tokio::test]
+async fn test_iter_with_configured_prefetch() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for b in 0..100 {
+        session.execute_unpaged(&insert, (0, b)).await.unwrap();
+    }
+
+    // Strictly pull-based paging, and paging bounded by the size of prefetched pages
+    for (prefetch_pages, max_prefetched_bytes) in [(0, None), (4, Some(1.try_into().unwrap()))] {
+        let mut select = Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 0"));
+        select.set_page_size(7);
+        select.set_prefetch_pages(prefetch_pages);
+        select.set_max_prefetched_bytes(max_prefetched_bytes);
+
+        let rows: Vec<(i32,)> = session
+            .query_iter(select.clone(), &[])
+            .await
+            .unwrap()
+            .rows_stream::<(i32,)>()
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, (0..100).map(|b| (b,)).collect::<Vec<_>>());
+
+        let prepared = session.prepare(select).await.unwrap();
+        assert_eq!(prepared.get_prefetch_pages(), prefetch_pages);
+        let rows: Vec<(i32,)> = session
+            .execute_iter(prepared, &[])
+            .await
+            .unwrap()
+            .rows_stream::<(i32,)>()
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(rows, (0..100).map(|b| (b,)).collect::<Vec<_>>());
+    }
+}
+
+#[tokio::test]
+async fn test_page_execution_info() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, primary key (a, b))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    for b in 0..10 {
+        session.execute_unpaged(&insert, (0, b)).await.unwrap();
+    }
+
+    let mut select = Statement::new(format!("SELECT b FROM {ks}.t WHERE a = 0"));
+    select.set_page_size(3);
+    let mut rows = session
+        .query_iter(select, &[])
+        .await
+        .unwrap()
+        .rows_stream::<(i32,)>()
+        .unwrap();
+
+    let mut page_numbers = Vec::new();
+    while let Some((b,)) = rows.try_next().await.unwrap() {
+        let info = rows.current_page_execution_info();
+        assert_eq!(info.page_number(), b as usize / 3 + 1);
+        assert!(info.coordinator().is_some());
+        assert!(info.attempts() >= 1);
+        assert_eq!(info.attempt_history().len(), info.attempts());
+        assert!(info.attempt_history().last().unwrap().error().is_none());
+        assert!(info.tracing_id().is_none());
+        page_numbers.push(info.page_number());
+    }
+    page_numbers.dedup();
+    assert_eq!(page_numbers, vec![1, 2, 3, 4]);
+}
@@ -1,8 +1,10 @@
 use crate::utils::{setup_tracing, test_with_3_node_cluster, unique_keyspace_name, PerformDDL};
+use assert_matches::assert_matches;
 use scylla::client::execution_profile::ExecutionProfile;
 use scylla::client::session::Session;
 use scylla::client::session_builder::SessionBuilder;
-use scylla::policies::retry::FallthroughRetryPolicy;
+use scylla::errors::{DbError, RequestAttemptError};
+use scylla::policies::retry::{FallthroughRetryPolicy, RetryDecision};
 use scylla::policies::speculative_execution::SimpleSpeculativeExecutionPolicy;
 use scylla::statement::unprepared::Statement;
 use std::sync::Arc;
@@ -139,13 +141,26 @@ async fn retries_occur() {
         running_proxy.running_nodes[2]
             .change_request_rules(Some(vec![forge_error_rule.clone()]));
 
-        session.query_unpaged(s.clone(), (2,)).await.unwrap();
+        let result = session.query_unpaged(s.clone(), (2,)).await.unwrap();
+        // Attempts on the failing nodes, if any, precede the successful one.
+        let (last, failed) = result.attempt_history().split_last().unwrap();
+        assert!(last.error().is_none());
+        assert!(failed.len() <= 2);
+        for attempt in failed {
+            assert_matches!(attempt.error(), Some(RequestAttemptError::DbError(DbError::ServerError, _)));
+            assert_eq!(attempt.retry_decision(), Some(&RetryDecision::RetryNextTarget(None)));
+        }
 
         info!("--------------------- third query - all nodes not responding  ----------------");
         running_proxy.running_nodes[1]
             .change_request_rules(Some(vec![forge_error_rule]));
 
-        session.query_unpaged(s.clone(), (1,)).await.unwrap_err();
+        let err = session.query_unpaged(s.clone(), (1,)).await.unwrap_err();
+        // The request was attempted once on each node, and the error carries all the attempts.
+        let history = err.attempt_history();
+        assert_eq!(history.len(), 3);
+        assert!(history.iter().all(|attempt| attempt.error().is_some()));
+        assert_eq!(err.attempt_context().unwrap().attempt(), 3);
 
         info!("--------------------- fourth query - 0 and 1 nodes not responding  ----------------");
         running_proxy.running_nodes[2]
@@ -1,6 +1,8 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use assert_matches::assert_matches;
+use scylla::client::schema_agreement::SchemaAgreementOptions;
 use scylla::client::session::Session;
 use scylla::client::session_builder::SessionBuilder;
 use scylla::errors::{ExecutionError, RequestAttemptError, SchemaAgreementError};
@@ -12,8 +14,11 @@ use scylla_proxy::{
     ShardAwareness, WorkerError,
 };
 
+use uuid::Uuid;
+
 use crate::utils::{
-    calculate_proxy_host_ids, setup_tracing, test_with_3_node_cluster, unique_keyspace_name,
+    calculate_proxy_host_ids, create_new_session_builder, setup_tracing, test_with_3_node_cluster,
+    unique_keyspace_name, PerformDDL as _,
 };
 
 async fn run_some_ddl_with_unreachable_node(
@@ -154,3 +159,40 @@ async fn test_schema_await_with_unreachable_node() {
         Err(err) => panic!("{}", err),
     }
 }
+
+#[tokio::test]
+async fn test_await_schema_agreement_with_options() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+    session.ddl(format!("CREATE KEYSPACE {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+
+    let host_ids: Vec<Uuid> = session
+        .get_cluster_state()
+        .get_nodes_info()
+        .iter()
+        .map(|node| node.host_id)
+        .collect();
+    let options = SchemaAgreementOptions::new()
+        .with_timeout(Duration::from_secs(120))
+        .with_interval(Duration::from_millis(50))
+        .with_required_hosts(host_ids);
+    let version = session
+        .await_schema_agreement_with_options(&options)
+        .await
+        .unwrap();
+    assert_eq!(
+        session.check_schema_agreement().await.unwrap(),
+        Some(version)
+    );
+
+    // A host which is not a part of the cluster can never take part in the agreement.
+    let absent_host = Uuid::new_v4();
+    let options = SchemaAgreementOptions::new().with_required_hosts([absent_host]);
+    assert_matches!(
+        session.await_schema_agreement_with_options(&options).await,
+        Err(SchemaAgreementError::RequiredHostAbsent(host_id)) if host_id == absent_host
+    );
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
@@ -4,14 +4,17 @@ use crate::utils::{
 };
 use assert_matches::assert_matches;
 use scylla::client::session::Session;
-use scylla::errors::{BadQuery, ExecutionError, RequestAttemptError};
+use scylla::errors::{BadQuery, ChunkedBatchError, ExecutionError, RequestAttemptError};
 use scylla::frame::frame_errors::{BatchSerializationError, CqlRequestSerializationError};
 use scylla::response::query_result::{QueryResult, QueryRowsResult};
-use scylla::statement::batch::{Batch, BatchStatement, BatchType};
+use scylla::statement::batch::{
+    Batch, BatchChunking, BatchStatement, BatchStatementKindError, BatchType, TypedBatch,
+};
 use scylla::statement::prepared::PreparedStatement;
 use scylla::statement::unprepared::Statement;
 use scylla::value::Counter;
 use std::collections::BTreeSet;
+use std::num::NonZeroU16;
 use std::sync::Arc;
 
 #[tokio::test]
@@ -47,7 +50,7 @@ async fn batch_statements_and_values_mismatch_detected() {
     {
         let err = session.batch(&batch, &((1, 2), ())).await.unwrap_err();
         assert_matches!(
-            err,
+            err.into_inner(),
             ExecutionError::LastAttemptError(RequestAttemptError::CqlRequestSerialization(
                 CqlRequestSerializationError::BatchSerialization(
                     BatchSerializationError::ValuesAndStatementsLengthMismatch {
@@ -66,7 +69,7 @@ async fn batch_statements_and_values_mismatch_detected() {
             .await
             .unwrap_err();
         assert_matches!(
-            err,
+            err.into_inner(),
             ExecutionError::LastAttemptError(RequestAttemptError::CqlRequestSerialization(
                 CqlRequestSerializationError::BatchSerialization(
                     BatchSerializationError::ValuesAndStatementsLengthMismatch {
@@ -218,6 +221,45 @@ async fn test_quietly_prepare_batch() {
     }
 }
 
+#[tokio::test]
+async fn test_typed_batch() {
+    setup_tracing();
+    let session = Arc::new(create_new_session_builder().build().await.unwrap());
+
+    let ks = unique_keyspace_name();
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session.use_keyspace(ks.clone(), false).await.unwrap();
+
+    session
+        .ddl("CREATE TABLE test_batch_table (a int, b int, primary key (a, b))")
+        .await
+        .unwrap();
+
+    let prepared_insert_a_b: PreparedStatement = session
+        .prepare("insert into test_batch_table (a, b) values (?, ?)")
+        .await
+        .unwrap();
+
+    let mut template = Batch::new(BatchType::Unlogged);
+    template.set_timestamp(Some(42));
+    let batch = TypedBatch::from_template(&template)
+        .append_statement(prepared_insert_a_b.clone(), (1, 2))
+        .append_statement("insert into test_batch_table (a, b) values (?, 7)", (1,))
+        .append_statement("insert into test_batch_table (a, b) values (3, 4)", ())
+        .append_statement(prepared_insert_a_b, (5_i32, 6_i32));
+    assert_eq!(batch.len(), 4);
+    assert_eq!(batch.batch().get_timestamp(), Some(42));
+
+    // Typed batches can be moved to other tasks.
+    let task_session = Arc::clone(&session);
+    tokio::spawn(async move { task_session.typed_batch(&batch).await })
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_test_batch_table_rows_contain(&session, &[(1, 2), (1, 7), (3, 4), (5, 6)]).await;
+}
+
 async fn assert_test_batch_table_rows_contain(sess: &Session, expected_rows: &[(i32, i32)]) {
     let selected_rows: BTreeSet<(i32, i32)> = sess
         .query_unpaged("SELECT a, b FROM test_batch_table", ())
@@ -604,6 +646,88 @@ async fn test_counter_batch() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_counter_batch_validation() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    // Need to disable tablets in this test because they don't support counters yet.
+    let mut create_ks = format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}");
+    if scylla_supports_tablets(&session).await {
+        create_ks += " AND TABLETS = {'enabled': false}"
+    }
+
+    session.ddl(create_ks).await.unwrap();
+    session.use_keyspace(&ks, true).await.unwrap();
+    session
+        .ddl("CREATE TABLE IF NOT EXISTS t_counters (key int PRIMARY KEY, value counter)")
+        .await
+        .unwrap();
+    session
+        .ddl("CREATE TABLE IF NOT EXISTS t_regular (key int PRIMARY KEY, value int)")
+        .await
+        .unwrap();
+
+    let increment = session
+        .prepare("UPDATE t_counters SET value = value + ? WHERE key = ?")
+        .await
+        .unwrap();
+    session
+        .update_counters([
+            (increment.clone(), (Counter(1), 1)),
+            (increment.clone(), (Counter(2), 1)),
+            (increment.clone(), (Counter(5), 2)),
+        ])
+        .await
+        .unwrap();
+    let rows: Vec<(i32, Counter)> = session
+        .query_unpaged("SELECT key, value FROM t_counters", ())
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(i32, Counter)>()
+        .unwrap()
+        .map(Result::unwrap)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    assert_eq!(rows, [(1, Counter(3)), (2, Counter(5))]);
+
+    // A regular statement in a counter batch
+    let mut counter_batch = Batch::new(BatchType::Counter);
+    counter_batch.append_statement(increment.clone());
+    counter_batch.append_statement("INSERT INTO t_regular (key, value) VALUES (?, ?)");
+    let err = session
+        .batch(&counter_batch, ((Counter(1), 1), (1, 1)))
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        ExecutionError::BadQuery(BadQuery::BatchStatementKindMismatch(
+            1,
+            BatchStatementKindError::NotCounterUpdate
+        ))
+    );
+
+    // A counter update in a logged batch
+    let mut logged_batch = Batch::new(BatchType::Logged);
+    logged_batch.append_statement("INSERT INTO t_regular (key, value) VALUES (?, ?)");
+    logged_batch.append_statement(increment);
+    let err = session
+        .batch(&logged_batch, ((1, 1), (Counter(1), 1)))
+        .await
+        .unwrap_err();
+    assert_matches!(
+        err,
+        ExecutionError::BadQuery(BadQuery::BatchStatementKindMismatch(
+            1,
+            BatchStatementKindError::CounterUpdateInNonCounterBatch
+        ))
+    );
+}
+
 // This is a regression test for #1134.
 #[tokio::test]
 async fn test_batch_to_multiple_tables() {
@@ -639,3 +763,56 @@ async fn test_batch_to_multiple_tables() {
         .await
         .unwrap();
 }
+
+#[tokio::test]
+async fn test_batch_chunked() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session.use_keyspace(&ks, true).await.unwrap();
+    session
+        .ddl("CREATE TABLE IF NOT EXISTS t_batch_chunked (a int primary key, b int)")
+        .await
+        .unwrap();
+
+    let chunking = BatchChunking::new()
+        .with_max_statements(NonZeroU16::new(7).unwrap())
+        .with_max_serialized_size(usize::MAX);
+    let results = session
+        .batch_chunked(
+            &Batch::new(BatchType::Unlogged),
+            (0..100_i32).map(|a| ("INSERT INTO t_batch_chunked (a, b) VALUES (?, ?)", (a, a))),
+            &chunking,
+        )
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 15);
+
+    let rows: Vec<(i32, i32)> = session
+        .query_unpaged("SELECT a, b FROM t_batch_chunked", &[])
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(i32, i32)>()
+        .unwrap()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(rows.len(), 100);
+
+    // Values not matching the statement are reported before anything is executed.
+    let error = session
+        .batch_chunked(
+            &Batch::new(BatchType::Unlogged),
+            [
+                ("INSERT INTO t_batch_chunked (a, b) VALUES (?, ?)", (1_i32,)),
+                ("INSERT INTO t_batch_chunked (a, b) VALUES (?, 2)", (1_i32,)),
+            ],
+            &chunking,
+        )
+        .await
+        .unwrap_err();
+    assert_matches!(error, ChunkedBatchError::BadStatement { index: 0, .. });
+}
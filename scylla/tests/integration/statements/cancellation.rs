@@ -0,0 +1,127 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use assert_matches::assert_matches;
+use futures::StreamExt as _;
+use scylla::client::session::Session;
+use scylla::client::session_builder::SessionBuilder;
+use scylla::client::CancellationToken;
+use scylla::errors::{ExecutionError, NextPageError, NextRowError, RequestError};
+use scylla::statement::unprepared::Statement;
+use scylla_proxy::{
+    Condition, ProxyError, Reaction as _, RequestOpcode, RequestReaction, RequestRule,
+    ShardAwareness, WorkerError,
+};
+
+use crate::utils::{setup_tracing, test_with_3_node_cluster, unique_keyspace_name, PerformDDL};
+
+fn cancel_after(token: &CancellationToken, delay: Duration) {
+    let token = token.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(delay).await;
+        token.cancel();
+    });
+}
+
+#[tokio::test]
+#[ntest::timeout(30000)]
+#[cfg_attr(scylla_cloud_tests, ignore)]
+async fn requests_are_cancelled() {
+    setup_tracing();
+
+    let res = test_with_3_node_cluster(
+        ShardAwareness::QueryNode,
+        |proxy_uris, translation_map, mut running_proxy| async move {
+            let session: Session = SessionBuilder::new()
+                .known_node(proxy_uris[0].as_str())
+                .address_translator(Arc::new(translation_map))
+                .build()
+                .await
+                .unwrap();
+
+            let ks = unique_keyspace_name();
+            session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 3}}")).await.unwrap();
+            session.use_keyspace(ks, false).await.unwrap();
+            session
+                .ddl("CREATE TABLE cancel_me (a int primary key)")
+                .await
+                .unwrap();
+            for i in 0..10 {
+                session
+                    .query_unpaged("INSERT INTO cancel_me (a) VALUES (?)", (i,))
+                    .await
+                    .unwrap();
+            }
+
+            let never_respond_rule = RequestRule(
+                Condition::RequestOpcode(RequestOpcode::Query)
+                    .and(Condition::BodyContainsCaseSensitive(Box::new(*b"cancel_me"))),
+                RequestReaction::drop_frame(),
+            );
+
+            // A request which never gets a response fails once the token is cancelled.
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![never_respond_rule.clone()]));
+            }
+            let token = CancellationToken::new();
+            let mut statement = Statement::new("SELECT a FROM cancel_me");
+            statement.set_cancellation_token(Some(token.clone()));
+            cancel_after(&token, Duration::from_millis(100));
+            let err = session
+                .query_unpaged(statement.clone(), ())
+                .await
+                .unwrap_err();
+            assert_matches!(err, ExecutionError::Cancelled);
+
+            // Requests with an already cancelled token fail immediately.
+            let err = session
+                .query_unpaged(statement.clone(), ())
+                .await
+                .unwrap_err();
+            assert_matches!(err, ExecutionError::Cancelled);
+
+            // A pager stops fetching pages once the token is cancelled.
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(None);
+            }
+            let token = CancellationToken::new();
+            statement.set_cancellation_token(Some(token.clone()));
+            statement.set_page_size(1);
+            let mut rows = session
+                .query_iter(statement, ())
+                .await
+                .unwrap()
+                .rows_stream::<(i32,)>()
+                .unwrap();
+            rows.next().await.unwrap().unwrap();
+
+            for node in running_proxy.running_nodes.iter_mut() {
+                node.change_request_rules(Some(vec![never_respond_rule.clone()]));
+            }
+            cancel_after(&token, Duration::from_millis(100));
+            let mut cancelled = false;
+            while let Some(row) = rows.next().await {
+                if let Err(err) = row {
+                    assert_matches!(
+                        err,
+                        NextRowError::NextPageError(NextPageError::RequestFailure(
+                            RequestError::Cancelled
+                        ))
+                    );
+                    cancelled = true;
+                    break;
+                }
+            }
+            assert!(cancelled, "the pager should have failed due to cancellation");
+
+            running_proxy
+        },
+    )
+    .await;
+
+    match res {
+        Ok(()) => (),
+        Err(ProxyError::Worker(WorkerError::DriverDisconnected(_))) => (),
+        Err(err) => panic!("{}", err),
+    }
+}
@@ -0,0 +1,89 @@
+use std::num::NonZeroUsize;
+
+use futures::{StreamExt as _, TryStreamExt as _};
+
+use crate::utils::{
+    create_new_session_builder, setup_tracing, unique_keyspace_name, PerformDDL as _,
+};
+
+#[tokio::test]
+async fn test_execute_concurrently() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int primary key, b int)"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b) VALUES (?, ?)"))
+        .await
+        .unwrap();
+    let select = session
+        .prepare(format!("SELECT b FROM {ks}.t WHERE a = ?"))
+        .await
+        .unwrap();
+    let max_concurrency = NonZeroUsize::new(8).unwrap();
+
+    let inserted = session
+        .execute_concurrently((0..100_i32).map(|a| (&insert, (a, a * 2))), max_concurrency)
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+    assert_eq!(inserted.len(), 100);
+
+    // Results are yielded in the order of statements.
+    let selected: Vec<i32> = session
+        .execute_concurrently((0..100_i32).map(|a| (&select, (a,))), max_concurrency)
+        .map(|result| {
+            result
+                .unwrap()
+                .into_rows_result()
+                .unwrap()
+                .single_row::<(i32,)>()
+                .unwrap()
+                .0
+        })
+        .collect()
+        .await;
+    assert_eq!(selected, (0..100).map(|a| a * 2).collect::<Vec<_>>());
+
+    // Results are yielded in any order, together with positions of statements.
+    let mut selected: Vec<(usize, i32)> = session
+        .execute_concurrently_unordered((0..100_i32).map(|a| (&select, (a,))), max_concurrency)
+        .map(|(i, result)| {
+            let (b,) = result
+                .unwrap()
+                .into_rows_result()
+                .unwrap()
+                .single_row::<(i32,)>()
+                .unwrap();
+            (i, b)
+        })
+        .collect()
+        .await;
+    selected.sort_unstable();
+    assert_eq!(
+        selected,
+        (0..100).map(|a| (a as usize, a * 2)).collect::<Vec<_>>()
+    );
+
+    // A failed execution does not stop the others.
+    let results: Vec<_> = session
+        .execute_concurrently(
+            [(&select, (1_i32, 2_i32)), (&select, (3_i32, 4_i32))]
+                .into_iter()
+                .chain([(&insert, (5_i32, 6_i32))]),
+            max_concurrency,
+        )
+        .collect()
+        .await;
+    assert!(results[0].is_err());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+}
@@ -1,10 +1,13 @@
 mod batch;
+mod cancellation;
+mod concurrent;
 mod consistency;
 mod coordinator;
 mod execution_profiles;
 mod named_bind_markers;
 mod prepared;
 mod request_timeout;
+mod scan;
 mod timestamps;
 mod transparent_reprepare;
 mod unprepared;
@@ -2,13 +2,14 @@ use assert_matches::assert_matches;
 use itertools::Itertools;
 use scylla::client::session::Session;
 use scylla::cluster::metadata::{ColumnType, NativeType};
+use scylla::cluster::Node;
 use scylla::errors::{DbError, PrepareError, RequestAttemptError};
 use scylla::frame::response::result::{ColumnSpec, TableSpec};
 use scylla::policies::load_balancing::{NodeIdentifier, SingleTargetLoadBalancingPolicy};
 use scylla::response::{PagingState, PagingStateResponse};
 use scylla::routing::partitioner::PartitionerName;
-use scylla::routing::Token;
-use scylla::serialize::row::SerializeRow;
+use scylla::routing::{Shard, Token};
+use scylla::serialize::row::{SerializeRow, SerializedValues};
 use scylla::statement::prepared::PreparedStatement;
 use scylla::statement::Statement;
 use scylla_cql::frame::types;
@@ -105,6 +106,13 @@ async fn test_prepared_statement() {
             .compute_token(&ks, "t2", &(values.0,))
             .unwrap();
         assert_eq!(token, cluster_state_token);
+        let mut partition_key = SerializedValues::new();
+        partition_key
+            .add_value(&values.0, &ColumnType::Native(NativeType::Int))
+            .unwrap();
+        let standalone_token =
+            Token::from_partition_key(&PartitionerName::Murmur3, &partition_key).unwrap();
+        assert_eq!(token, standalone_token);
     }
     {
         let (value,): (i64,) = session
@@ -311,11 +319,26 @@ async fn test_token_calculation() {
             .unwrap()
             .unwrap();
         assert_eq!(token, prepared_token);
-        let cluster_state_token = session
-            .get_cluster_state()
+        let cluster_state = session.get_cluster_state();
+        let cluster_state_token = cluster_state
             .compute_token(ks_name, table_name, &pk_values)
             .unwrap();
         assert_eq!(token, cluster_state_token);
+
+        let replicas_of = |replicas: Vec<(Arc<Node>, Shard)>| {
+            replicas
+                .into_iter()
+                .map(|(node, shard)| (node.host_id, shard))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            replicas_of(
+                cluster_state
+                    .get_endpoints(ks_name, table_name, &pk_values)
+                    .unwrap()
+            ),
+            replicas_of(cluster_state.get_token_endpoints(ks_name, table_name, token))
+        );
     }
 
     // Different sizes of the key
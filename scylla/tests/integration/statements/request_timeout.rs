@@ -1,4 +1,4 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use assert_matches::assert_matches;
 use scylla::{
@@ -24,7 +24,7 @@ async fn test_request_timeout() {
         query.set_request_timeout(Some(Duration::from_millis(1)));
         match session.query_unpaged(query, &[]).await {
             Ok(_) => panic!("the query should have failed due to a client-side timeout"),
-            Err(e) => assert_matches!(e, ExecutionError::RequestTimeout(_)),
+            Err(e) => assert_matches!(e.inner(), ExecutionError::RequestTimeout(_)),
         }
 
         let mut prepared = session
@@ -35,7 +35,7 @@ async fn test_request_timeout() {
         prepared.set_request_timeout(Some(Duration::from_millis(1)));
         match session.execute_unpaged(&prepared, &[]).await {
             Ok(_) => panic!("the prepared query should have failed due to a client-side timeout"),
-            Err(e) => assert_matches!(e, ExecutionError::RequestTimeout(_)),
+            Err(e) => assert_matches!(e.inner(), ExecutionError::RequestTimeout(_)),
         };
     }
     {
@@ -49,7 +49,7 @@ async fn test_request_timeout() {
 
         match timeouting_session.query_unpaged(query.clone(), &[]).await {
             Ok(_) => panic!("the query should have failed due to a client-side timeout"),
-            Err(e) => assert_matches!(e, ExecutionError::RequestTimeout(_)),
+            Err(e) => assert_matches!(e.inner(), ExecutionError::RequestTimeout(_)),
         };
 
         query.set_request_timeout(Some(Duration::from_secs(10000)));
@@ -65,7 +65,7 @@ async fn test_request_timeout() {
 
         match timeouting_session.execute_unpaged(&prepared, &[]).await {
             Ok(_) => panic!("the prepared query should have failed due to a client-side timeout"),
-            Err(e) => assert_matches!(e, ExecutionError::RequestTimeout(_)),
+            Err(e) => assert_matches!(e.inner(), ExecutionError::RequestTimeout(_)),
         };
 
         prepared.set_request_timeout(Some(Duration::from_secs(10000)));
@@ -73,3 +73,34 @@ async fn test_request_timeout() {
         timeouting_session.execute_unpaged(&prepared, &[]).await.expect("the prepared query should have not failed, because no client-side timeout was specified");
     }
 }
+
+#[tokio::test]
+async fn test_deadline() {
+    setup_tracing();
+
+    let session = create_new_session_builder().build().await.unwrap();
+
+    let mut query: Statement = Statement::new("SELECT * FROM system_schema.tables");
+    query.set_deadline(Some(Instant::now()));
+    match session.query_unpaged(query.clone(), &[]).await {
+        Ok(_) => panic!("the query should have failed due to an exceeded deadline"),
+        Err(e) => assert_matches!(e.inner(), ExecutionError::DeadlineExceeded),
+    }
+
+    query.set_deadline(Some(Instant::now() + Duration::from_secs(10000)));
+    session
+        .query_unpaged(query, &[])
+        .await
+        .expect("the query should have not failed, because the deadline is far in the future");
+
+    let mut prepared = session
+        .prepare("SELECT * FROM system_schema.tables")
+        .await
+        .unwrap();
+
+    prepared.set_deadline(Some(Instant::now()));
+    match session.execute_unpaged(&prepared, &[]).await {
+        Ok(_) => panic!("the prepared query should have failed due to an exceeded deadline"),
+        Err(e) => assert_matches!(e.inner(), ExecutionError::DeadlineExceeded),
+    };
+}
@@ -0,0 +1,97 @@
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+
+use futures::TryStreamExt as _;
+use scylla::errors::TokenRangeScanError;
+use scylla::scan::{TokenRange, TokenRangeScan};
+
+use crate::utils::{
+    create_new_session_builder, setup_tracing, unique_keyspace_name, PerformDDL as _,
+};
+
+#[tokio::test]
+async fn test_token_range_scan() {
+    setup_tracing();
+    let session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session
+        .ddl(format!(
+            "CREATE TABLE IF NOT EXISTS {ks}.t (a int, b int, c text, primary key ((a, b)))"
+        ))
+        .await
+        .unwrap();
+
+    let insert = session
+        .prepare(format!("INSERT INTO {ks}.t (a, b, c) VALUES (?, ?, ?)"))
+        .await
+        .unwrap();
+    for a in 0..500_i32 {
+        session
+            .execute_unpaged(&insert, (a, a * 2, a.to_string()))
+            .await
+            .unwrap();
+    }
+    session.refresh_metadata().await.unwrap();
+
+    let scan = TokenRangeScan::new(&ks, "t")
+        .columns(["a", "b", "c"])
+        .max_concurrency(NonZeroUsize::new(4).unwrap())
+        .page_size(7);
+    let ranges = scan.token_ranges(&session.get_cluster_state());
+    assert!(!ranges.is_empty());
+
+    let mut rows = scan.execute::<(i32, i32, String)>(&session).unwrap();
+    let mut scanned = HashSet::new();
+    while let Some((a, b, c)) = rows.try_next().await.unwrap() {
+        assert_eq!(b, a * 2);
+        assert_eq!(c, a.to_string());
+        // Every row is returned exactly once.
+        assert!(scanned.insert(a));
+    }
+    assert_eq!(scanned, (0..500).collect::<HashSet<_>>());
+
+    let progress = rows.progress();
+    assert!(progress.is_completed());
+    assert_eq!(progress.total_ranges(), ranges.len());
+    assert!(progress.unfinished_ranges().is_empty());
+
+    // Scanning a subset of ranges returns only the rows of these ranges.
+    let (first_half, second_half) = ranges.split_at(ranges.len() / 2);
+    let first: Vec<(i32,)> = scan
+        .clone()
+        .columns(["a"])
+        .execute_ranges::<(i32,)>(&session, first_half.iter().copied())
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let second: Vec<(i32,)> = scan
+        .clone()
+        .columns(["a"])
+        .execute_ranges::<(i32,)>(&session, second_half.iter().copied())
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    let mut all: Vec<i32> = first.into_iter().chain(second).map(|(a,)| a).collect();
+    all.sort_unstable();
+    assert_eq!(all, (0..500).collect::<Vec<_>>());
+
+    // The whole ring as a single range.
+    let count = scan
+        .execute_ranges::<(i32, i32, String)>(&session, [TokenRange::FULL])
+        .unwrap()
+        .try_fold(0, |count, _| async move { Ok(count + 1) })
+        .await
+        .unwrap();
+    assert_eq!(count, 500);
+
+    let err = TokenRangeScan::new(&ks, "no_such_table")
+        .execute::<(i32,)>(&session)
+        .unwrap_err();
+    assert!(matches!(err, TokenRangeScanError::UnknownTable { .. }));
+
+    session.ddl(format!("DROP KEYSPACE {ks}")).await.unwrap();
+}
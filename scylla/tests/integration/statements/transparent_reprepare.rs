@@ -1,5 +1,8 @@
+use std::sync::{Arc, Mutex};
+
 use scylla::{
     client::{caching_session::CachingSession, session::Session},
+    observability::repreparation::{RepreparationEvent, RepreparationListener},
     statement::batch::Batch,
 };
 
@@ -216,3 +219,62 @@ async fn test_unprepared_reprepare_in_caching_session_execute() {
     all_rows.sort_unstable();
     assert_eq!(all_rows, vec![(1, 2, 3), (1, 3, 2)]);
 }
+
+#[derive(Debug, Default)]
+struct RecordingListener {
+    events: Mutex<Vec<RepreparationEvent>>,
+}
+
+impl RepreparationListener for RecordingListener {
+    fn on_repreparation(&self, event: &RepreparationEvent) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+// A test which checks that transparent repreparation is reported to the repreparation listener.
+#[tokio::test]
+async fn test_repreparation_listener() {
+    setup_tracing();
+
+    let listener = Arc::new(RecordingListener::default());
+    let session = create_new_session_builder()
+        .repreparation_listener(listener.clone())
+        .build()
+        .await
+        .unwrap();
+    let ks = unique_keyspace_name();
+
+    session.ddl(format!("CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}")).await.unwrap();
+    session.use_keyspace(ks, false).await.unwrap();
+
+    session
+        .ddl("CREATE TABLE IF NOT EXISTS tab (a int, b int, c int, primary key (a, b, c))")
+        .await
+        .unwrap();
+
+    let insert_a_b_c = session
+        .prepare("INSERT INTO tab (a, b, c) VALUES (?, ?, ?)")
+        .await
+        .unwrap();
+
+    session
+        .execute_unpaged(&insert_a_b_c, (1, 2, 3))
+        .await
+        .unwrap();
+    assert!(listener.events.lock().unwrap().is_empty());
+
+    // Altering the table clears prepared statement cache
+    session.ddl("ALTER TABLE tab ADD d int").await.unwrap();
+
+    session
+        .execute_unpaged(&insert_a_b_c, (1, 2, 3))
+        .await
+        .unwrap();
+
+    let events = listener.events.lock().unwrap();
+    assert!(!events.is_empty());
+    for event in events.iter() {
+        assert_eq!(&event.statement_id, insert_a_b_c.get_id());
+        assert!(event.succeeded);
+    }
+}
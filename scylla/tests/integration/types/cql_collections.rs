@@ -6,6 +6,7 @@ use scylla::cluster::metadata::NativeType;
 use scylla::deserialize::value::DeserializeValue;
 use scylla::value::{
     CqlDate, CqlDecimal, CqlDuration, CqlTime, CqlTimestamp, CqlTimeuuid, CqlValue, CqlVarint,
+    CqlVector,
 };
 use scylla::{client::session::Session, cluster::metadata::ColumnType};
 use scylla_cql::serialize::value::SerializeValue;
@@ -605,6 +606,34 @@ async fn test_vector_type_all_types() {
     .await;
 }
 
+// TODO: Remove this ignore when vector type is available in ScyllaDB
+#[cfg_attr(not(cassandra_tests), ignore)]
+#[tokio::test]
+async fn test_cql_vector() {
+    setup_tracing();
+    let session: Session = connect().await;
+
+    let table_name: &str = "test_cql_vector_tab";
+    create_table(&session, table_name, "vector<float, 3>").await;
+
+    let embedding = CqlVector(vec![0.5_f32, -1.25, 3.0]);
+    insert_and_select(&session, table_name, &embedding, &embedding).await;
+
+    // `Vec<f32>` works with vectors too.
+    let vec: Vec<f32> = embedding.clone().into();
+    insert_and_select(&session, table_name, &vec, &embedding).await;
+    insert_and_select(&session, table_name, &embedding, &vec).await;
+
+    // The number of elements must match the number of dimensions.
+    session
+        .query_unpaged(
+            format!("INSERT INTO {table_name} (p, val) VALUES (0, ?)"),
+            (CqlVector(vec![1.0_f32, 2.0]),),
+        )
+        .await
+        .unwrap_err();
+}
+
 /// ScyllaDB does not distinguish empty collections from nulls. That is, INSERTing an empty collection
 /// is equivalent to nullifying the corresponding column.
 /// As pointed out in [#1001](https://github.com/scylladb/scylla-rust-driver/issues/1001), it's a nice
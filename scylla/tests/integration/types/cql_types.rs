@@ -141,6 +141,37 @@ async fn test_varint04() {
     run_tests::<num_bigint_04::BigInt>(&tests, "varint").await;
 }
 
+#[tokio::test]
+async fn test_varint_i128() {
+    setup_tracing();
+    let tests = [
+        "0",
+        "1",
+        "127",
+        "128",
+        "-1",
+        "-129",
+        "123456789012345678901234567890",
+        "-123456789012345678901234567890",
+        "170141183460469231731687303715884105727", // i128::MAX
+        "-170141183460469231731687303715884105728", // i128::MIN
+    ];
+    run_tests::<i128>(&tests, "varint").await;
+}
+
+#[tokio::test]
+async fn test_varint_u128() {
+    setup_tracing();
+    let tests = [
+        "0",
+        "1",
+        "128",
+        "123456789012345678901234567890",
+        "340282366920938463463374607431768211455", // u128::MAX
+    ];
+    run_tests::<u128>(&tests, "varint").await;
+}
+
 #[tokio::test]
 async fn test_cql_varint() {
     setup_tracing();
@@ -839,6 +870,86 @@ async fn test_cql_timestamp() {
     }
 }
 
+#[tokio::test]
+async fn test_system_time() {
+    setup_tracing();
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    let session = init_test("system_time_tests", "timestamp").await;
+
+    let tests = [
+        ("0", UNIX_EPOCH),
+        ("1000", UNIX_EPOCH + Duration::from_secs(1)),
+        (
+            "1687219200123",
+            UNIX_EPOCH + Duration::from_millis(1687219200123),
+        ),
+    ];
+
+    for (timestamp_str, system_time) in tests {
+        // Insert timestamp as a string and verify that it matches
+        session
+            .query_unpaged(
+                format!("INSERT INTO system_time_tests (id, val) VALUES (0, '{timestamp_str}')"),
+                &[],
+            )
+            .await
+            .unwrap();
+
+        let (read_time,) = session
+            .query_unpaged("SELECT val from system_time_tests", &[])
+            .await
+            .unwrap()
+            .into_rows_result()
+            .unwrap()
+            .single_row::<(SystemTime,)>()
+            .unwrap();
+
+        assert_eq!(read_time, system_time);
+
+        // Insert timestamp as a bound SystemTime value and verify that it matches
+        session
+            .query_unpaged(
+                "INSERT INTO system_time_tests (id, val) VALUES (0, ?)",
+                (system_time,),
+            )
+            .await
+            .unwrap();
+
+        let (read_time,) = session
+            .query_unpaged("SELECT val from system_time_tests", &[])
+            .await
+            .unwrap()
+            .into_rows_result()
+            .unwrap()
+            .single_row::<(SystemTime,)>()
+            .unwrap();
+
+        assert_eq!(read_time, system_time);
+    }
+
+    // Sub-millisecond precision is lost on write
+    let nanosecond_precision = UNIX_EPOCH + Duration::from_nanos(1_000_001);
+    session
+        .query_unpaged(
+            "INSERT INTO system_time_tests (id, val) VALUES (0, ?)",
+            (nanosecond_precision,),
+        )
+        .await
+        .unwrap();
+
+    let (read_time,) = session
+        .query_unpaged("SELECT val from system_time_tests", &[])
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(SystemTime,)>()
+        .unwrap();
+
+    assert_eq!(read_time, UNIX_EPOCH + Duration::from_millis(1));
+}
+
 #[cfg(feature = "chrono-04")]
 #[tokio::test]
 async fn test_date_time_04() {
@@ -1307,6 +1418,104 @@ async fn test_timeuuid_ordering() {
     }
 }
 
+#[tokio::test]
+async fn test_timeuuid_timestamp_range() {
+    setup_tracing();
+    let session: Session = create_new_session_builder().build().await.unwrap();
+    let ks = unique_keyspace_name();
+
+    session
+        .ddl(format!(
+            "CREATE KEYSPACE IF NOT EXISTS {ks} WITH REPLICATION = \
+            {{'class' : 'NetworkTopologyStrategy', 'replication_factor' : 1}}"
+        ))
+        .await
+        .unwrap();
+    session.use_keyspace(ks, false).await.unwrap();
+
+    session
+        .ddl("CREATE TABLE tab (p int, t timeuuid, PRIMARY KEY (p, t))")
+        .await
+        .unwrap();
+
+    let timeuuid_vals = [
+        "fed339e0-0efb-11ee-8080-808080808080",
+        "fed35080-0efb-11ee-a1ca-00006490e9a4",
+        "fed35080-0efb-11ee-ffff-ffffffffffff",
+        "fed360ef-0efb-11ee-7f7f-7f7f7f7f7f7f",
+        "00000257-0efc-11ee-9547-00006490e9a6",
+    ]
+    .map(|s| CqlTimeuuid::from_str(s).unwrap());
+    for timeuuid in timeuuid_vals {
+        session
+            .query_unpaged("INSERT INTO tab (p, t) VALUES (0, ?)", (timeuuid,))
+            .await
+            .unwrap();
+
+        // The timestamp is the same as computed by ScyllaDB
+        let (timestamp,): (CqlTimestamp,) = session
+            .query_unpaged(
+                "SELECT toTimestamp(t) FROM tab WHERE p = 0 AND t = ?",
+                (timeuuid,),
+            )
+            .await
+            .unwrap()
+            .into_rows_result()
+            .unwrap()
+            .single_row()
+            .unwrap();
+        assert_eq!(timestamp, timeuuid.timestamp());
+
+        // And so are the bounds
+        let (min, max): (CqlTimeuuid, CqlTimeuuid) = session
+            .query_unpaged(
+                "SELECT minTimeuuid(?), maxTimeuuid(?) FROM tab WHERE p = 0 AND t = ?",
+                (timestamp, timestamp, timeuuid),
+            )
+            .await
+            .unwrap()
+            .into_rows_result()
+            .unwrap()
+            .single_row()
+            .unwrap();
+        assert_eq!(
+            min.as_bytes(),
+            CqlTimeuuid::min_for_timestamp(timestamp).as_bytes()
+        );
+        assert_eq!(
+            max.as_bytes(),
+            CqlTimeuuid::max_for_timestamp(timestamp).as_bytes()
+        );
+    }
+
+    // The range built client-side selects the same rows as ScyllaDB
+    let timestamp = timeuuid_vals[0].timestamp();
+    let (min, max) = (
+        CqlTimeuuid::min_for_timestamp(timestamp),
+        CqlTimeuuid::max_for_timestamp(timestamp),
+    );
+    let selected: Vec<CqlTimeuuid> = session
+        .query_unpaged(
+            "SELECT t FROM tab WHERE p = 0 AND t >= minTimeuuid(?) AND t <= maxTimeuuid(?)",
+            (timestamp, timestamp),
+        )
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .rows::<(CqlTimeuuid,)>()
+        .unwrap()
+        .map(|r| r.unwrap().0)
+        .collect();
+    let expected: Vec<CqlTimeuuid> = timeuuid_vals
+        .into_iter()
+        .filter(|t| min <= *t && *t <= max)
+        .sorted()
+        .collect();
+    assert_eq!(selected, expected);
+    assert_eq!(selected.len(), 4);
+}
+
 #[tokio::test]
 async fn test_inet() {
     setup_tracing();
@@ -1582,6 +1791,42 @@ async fn test_udt_after_schema_update() {
             third: None,
         }
     );
+
+    // Fields which are not in the UDT (yet) are initialized with their defaults.
+    fn default_fourth() -> i64 {
+        -1
+    }
+
+    #[derive(DeserializeValue, Debug, PartialEq)]
+    struct UdtV3 {
+        first: i32,
+        second: bool,
+        third: Option<String>,
+        #[scylla(default = "default_fourth")]
+        fourth: i64,
+        #[scylla(default)]
+        fifth: Vec<String>,
+    }
+
+    let (read_udt,): (UdtV3,) = session
+        .query_unpaged(format!("SELECT val from {table_name} WHERE id = 0"), &[])
+        .await
+        .unwrap()
+        .into_rows_result()
+        .unwrap()
+        .single_row::<(UdtV3,)>()
+        .unwrap();
+
+    assert_eq!(
+        read_udt,
+        UdtV3 {
+            first: 123,
+            second: true,
+            third: None,
+            fourth: -1,
+            fifth: Vec::new(),
+        }
+    );
 }
 
 #[tokio::test]